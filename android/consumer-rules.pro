@@ -0,0 +1,4 @@
+# Kept in the apps using the plugin: the Tauri runtime finds the plugin and its commands by reflection
+-keep class com.plugin.mcp.McpPlugin { *; }
+-keep class com.plugin.mcp.SnapshotArgs { *; }
+-keep class com.plugin.mcp.SnapshotClip { *; }
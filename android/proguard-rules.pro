@@ -0,0 +1 @@
+# Rules applied when the library itself is minified, which it is not
@@ -0,0 +1,101 @@
+package com.plugin.mcp
+
+import android.app.Activity
+import android.graphics.Bitmap
+import android.graphics.Canvas
+import android.util.Base64
+import android.webkit.WebView
+import app.tauri.annotation.Command
+import app.tauri.annotation.InvokeArg
+import app.tauri.annotation.TauriPlugin
+import app.tauri.plugin.Invoke
+import app.tauri.plugin.JSObject
+import app.tauri.plugin.Plugin
+import java.io.ByteArrayOutputStream
+import kotlin.math.max
+import kotlin.math.roundToInt
+
+/** Part of the viewport, in CSS pixels. */
+@InvokeArg
+class SnapshotClip {
+    var x: Double = 0.0
+    var y: Double = 0.0
+    var width: Double = 0.0
+    var height: Double = 0.0
+}
+
+@InvokeArg
+class SnapshotArgs {
+    var clip: SnapshotClip? = null
+    var maxWidth: Int? = null
+    /** `png` or `jpeg`. */
+    var format: String = "png"
+    var quality: Int = 100
+}
+
+/** The Android side of tauri-plugin-mcp, for what the Rust side cannot reach. */
+@TauriPlugin
+class McpPlugin(private val activity: Activity) : Plugin(activity) {
+    private var webView: WebView? = null
+
+    override fun load(webView: WebView) {
+        this.webView = webView
+    }
+
+    /**
+     * Snapshot the visible part of the page in device pixels, cropped to `clip`, scaled down
+     * to `maxWidth` pixels wide and encoded as PNG or JPEG in base64.
+     */
+    @Command
+    fun snapshotWebview(invoke: Invoke) {
+        val args = invoke.parseArgs(SnapshotArgs::class.java)
+        activity.runOnUiThread {
+            val webView = this.webView
+            if (webView == null || webView.width == 0 || webView.height == 0) {
+                invoke.reject("The webview is not laid out yet")
+                return@runOnUiThread
+            }
+            try {
+                val page = Bitmap.createBitmap(webView.width, webView.height, Bitmap.Config.ARGB_8888)
+                val canvas = Canvas(page)
+                // The WebView draws its content at the scroll offset of the page
+                canvas.translate(-webView.scrollX.toFloat(), -webView.scrollY.toFloat())
+                webView.draw(canvas)
+
+                // CSS pixels are density-independent pixels in the WebView
+                val density = webView.resources.displayMetrics.density
+                var image = page
+                args.clip?.let { clip ->
+                    val left = (clip.x * density).roundToInt().coerceIn(0, page.width - 1)
+                    val top = (clip.y * density).roundToInt().coerceIn(0, page.height - 1)
+                    val width = (clip.width * density).roundToInt().coerceIn(1, page.width - left)
+                    val height = (clip.height * density).roundToInt().coerceIn(1, page.height - top)
+                    image = Bitmap.createBitmap(page, left, top, width, height)
+                }
+                val maxWidth = args.maxWidth
+                if (maxWidth != null && maxWidth > 0 && image.width > maxWidth) {
+                    val height = max(1, (image.height.toLong() * maxWidth / image.width).toInt())
+                    image = Bitmap.createScaledBitmap(image, maxWidth, height, true)
+                }
+
+                val format = if (args.format == "jpeg") {
+                    Bitmap.CompressFormat.JPEG
+                } else {
+                    Bitmap.CompressFormat.PNG
+                }
+                val output = ByteArrayOutputStream()
+                if (!image.compress(format, args.quality.coerceIn(1, 100), output)) {
+                    invoke.reject("Could not encode the snapshot")
+                    return@runOnUiThread
+                }
+                val result = JSObject()
+                result.put("data", Base64.encodeToString(output.toByteArray(), Base64.NO_WRAP))
+                result.put("width", image.width)
+                result.put("height", image.height)
+                invoke.resolve(result)
+            } catch (e: Exception) {
+                invoke.reject(e.message ?: "The snapshot failed")
+            }
+        }
+    }
+}
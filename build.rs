@@ -4,15 +4,23 @@ const COMMANDS: &[&str] = &[
     "eval_js",
     "get_element_text",
     "get_html",
+    "get_server_mode",
+    "get_session_status",
     "get_title",
     "get_url",
     "list_windows",
     "ping",
+    "restart_socket_server",
     "set_element_value",
+    "start_socket_server",
+    "stop_socket_server",
     "take_screenshot",
     "type_text",
 ];
 
 fn main() {
-    tauri_plugin::Builder::new(COMMANDS).build();
+    tauri_plugin::Builder::new(COMMANDS)
+        .android_path("android")
+        .ios_path("ios")
+        .build();
 }
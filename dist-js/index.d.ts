@@ -1,2 +1,12 @@
+import { UnlistenFn } from '@tauri-apps/api/event';
 export declare function setupPluginListeners(): Promise<void>;
 export declare function cleanupPluginListeners(): Promise<void>;
+export type SessionState = 'disconnected' | 'idle' | 'executing';
+export interface SessionStatus {
+    state: SessionState;
+    connectedClients: number;
+    command: string | null;
+}
+export declare function getSessionStatus(): Promise<SessionStatus>;
+export declare function onSessionStatus(handler: (status: SessionStatus) => void): Promise<UnlistenFn>;
+export declare function registerRouteProvider(provider: () => unknown): () => void;
@@ -1,31 +1,22 @@
-import { emit } from '@tauri-apps/api/event'; // For emitting the response
+import { invoke } from '@tauri-apps/api/core';
+import { emit, listen, UnlistenFn } from '@tauri-apps/api/event'; // For emitting the response
 import { getCurrentWebviewWindow, WebviewWindow } from '@tauri-apps/api/webviewWindow'; // For window-specific listener
 
 // Track the unlisten functions for cleanup
-let domContentUnlistenFunction: (() => void) | null = null;
 let localStorageUnlistenFunction: (() => void) | null = null;
 let jsExecutionUnlistenFunction: (() => void) | null = null;
-let elementPositionUnlistenFunction: (() => void) | null = null;
 let sendTextToElementUnlistenFunction: (() => void) | null = null;
 
 export async function setupPluginListeners() { 
     const currentWindow: WebviewWindow = getCurrentWebviewWindow();
-    domContentUnlistenFunction = await currentWindow.listen('got-dom-content', handleDomContentRequest);
     localStorageUnlistenFunction = await currentWindow.listen('get-local-storage', handleLocalStorageRequest);
     jsExecutionUnlistenFunction = await currentWindow.listen('execute-js', handleJsExecutionRequest);
-    elementPositionUnlistenFunction = await currentWindow.listen('get-element-position', handleGetElementPositionRequest);
     sendTextToElementUnlistenFunction = await currentWindow.listen('send-text-to-element', handleSendTextToElementRequest);
     
-    console.log('TAURI-PLUGIN-MCP: Event listeners for "got-dom-content", "get-local-storage", "execute-js", "get-element-position", and "send-text-to-element" are set up on the current window.');
+    console.log('TAURI-PLUGIN-MCP: Event listeners for "get-local-storage", "execute-js" and "send-text-to-element" are set up on the current window.');
 }
 
 export async function cleanupPluginListeners() {
-    if (domContentUnlistenFunction) {
-        domContentUnlistenFunction();
-        domContentUnlistenFunction = null;
-        console.log('TAURI-PLUGIN-MCP: Event listener for "got-dom-content" has been removed.');
-    }
-    
     if (localStorageUnlistenFunction) {
         localStorageUnlistenFunction();
         localStorageUnlistenFunction = null;
@@ -38,12 +29,6 @@ export async function cleanupPluginListeners() {
         console.log('TAURI-PLUGIN-MCP: Event listener for "execute-js" has been removed.');
     }
     
-    if (elementPositionUnlistenFunction) {
-        elementPositionUnlistenFunction();
-        elementPositionUnlistenFunction = null;
-        console.log('TAURI-PLUGIN-MCP: Event listener for "get-element-position" has been removed.');
-    }
-    
     if (sendTextToElementUnlistenFunction) {
         sendTextToElementUnlistenFunction();
         sendTextToElementUnlistenFunction = null;
@@ -51,162 +36,107 @@ export async function cleanupPluginListeners() {
     }
 }
 
-async function handleGetElementPositionRequest(event: any) {
-    console.log('TAURI-PLUGIN-MCP: Received get-element-position, payload:', event.payload);
-    
-    try {
-        const { selectorType, selectorValue, shouldClick = false } = event.payload;
-        
-        // Find the element based on the selector type
-        let element = null;
-        let debugInfo = [];
-        
-        switch (selectorType) {
-            case 'id':
-                element = document.getElementById(selectorValue);
-                if (!element) {
-                    debugInfo.push(`No element found with id="${selectorValue}"`);
-                }
-                break;
-            case 'class':
-                // Get the first element with the class
-                const elemsByClass = document.getElementsByClassName(selectorValue);
-                element = elemsByClass.length > 0 ? elemsByClass[0] : null;
-                if (!element) {
-                    debugInfo.push(`No elements found with class="${selectorValue}" (total matching: 0)`);
-                } else if (elemsByClass.length > 1) {
-                    debugInfo.push(`Found ${elemsByClass.length} elements with class="${selectorValue}", using the first one`);
-                }
-                break;
-            case 'tag':
-                // Get the first element with the tag name
-                const elemsByTag = document.getElementsByTagName(selectorValue);
-                element = elemsByTag.length > 0 ? elemsByTag[0] : null;
-                if (!element) {
-                    debugInfo.push(`No elements found with tag="${selectorValue}" (total matching: 0)`);
-                } else if (elemsByTag.length > 1) {
-                    debugInfo.push(`Found ${elemsByTag.length} elements with tag="${selectorValue}", using the first one`);
-                }
-                break;
-            case 'text':
-                // Find element by text content
-                element = findElementByText(selectorValue);
-                if (!element) {
-                    debugInfo.push(`No element found with text="${selectorValue}"`);
-                    // Check if any element contains part of the text (for debugging)
-                    const containingElements = Array.from(document.querySelectorAll('*'))
-                        .filter(el => el.textContent && el.textContent.includes(selectorValue));
-                    
-                    if (containingElements.length > 0) {
-                        debugInfo.push(`Found ${containingElements.length} elements containing part of the text.`);
-                        debugInfo.push(`First element with partial match: ${containingElements[0].tagName}, text="${containingElements[0].textContent?.trim()}"`);
-                    }
-                    
-                    // Check for similar inputs
-                    const inputs = Array.from(document.querySelectorAll('input, textarea'));
-                    const inputsWithSimilarPlaceholders = inputs
-                        .filter(input => 
-                            (input as HTMLInputElement).placeholder && 
-                            (input as HTMLInputElement).placeholder.includes(selectorValue)
-                        );
-                        
-                    if (inputsWithSimilarPlaceholders.length > 0) {
-                        debugInfo.push(`Found ${inputsWithSimilarPlaceholders.length} input elements with similar placeholders.`);
-                        const firstMatch = inputsWithSimilarPlaceholders[0] as HTMLInputElement;
-                        debugInfo.push(`First input with similar placeholder: ${firstMatch.tagName}, placeholder="${firstMatch.placeholder}"`);
-                    }
-                }
-                break;
-            default:
-                throw new Error(`Unsupported selector type: ${selectorType}`);
-        }
-        
-        if (!element) {
-            throw new Error(`Element with ${selectorType}="${selectorValue}" not found. ${debugInfo.join(' ')}`);
-        }
-        
-        // Get element position
-        const rect = element.getBoundingClientRect();
-        console.log('TAURI-PLUGIN-MCP: Element rect:', { 
-            left: rect.left, 
-            top: rect.top, 
-            right: rect.right, 
-            bottom: rect.bottom,
-            width: rect.width,
-            height: rect.height
-        });
+export type SessionState = 'disconnected' | 'idle' | 'executing';
 
-        // Calculate center of the element in viewport-relative CSS pixels
-        const elementViewportCssX = rect.left + (rect.width / 2);
-        const elementViewportCssY = rect.top + (rect.height / 2);
+export interface SessionStatus {
+    state: SessionState;
+    connectedClients: number;
+    // Tool running, the latest started when several are
+    command: string | null;
+}
 
-        // Account for Webview Scrolling (CSS Pixels)
-        const elementDocumentCssX = elementViewportCssX + window.scrollX;
-        const elementDocumentCssY = elementViewportCssY + window.scrollY;
-        
-        // Always return the raw document coordinates (ideal for mouse_movement)
-        const targetX = elementDocumentCssX;
-        const targetY = elementDocumentCssY;
-        
-        console.log('TAURI-PLUGIN-MCP: Raw coordinates for mouse_movement:', { x: targetX, y: targetY });
+// What the MCP session is doing, e.g. to render an "AI is controlling this app" indicator
+export async function getSessionStatus(): Promise<SessionStatus> {
+    return await invoke<SessionStatus>('plugin:tauri-mcp|get_session_status');
+}
+
+// Calls the handler with each new session status, until the returned function is called
+export async function onSessionStatus(handler: (status: SessionStatus) => void): Promise<UnlistenFn> {
+    return await listen<SessionStatus>('tauri-mcp:session-status', (event) => handler(event.payload));
+}
 
-        // Click the element if requested
-        let clickResult = null;
-        if (shouldClick) {
-            clickResult = clickElement(element, elementViewportCssX, elementViewportCssY);
+// Reports the route of the app's client-side router to get_page_info, e.g.
+// `registerRouteProvider(() => router.currentRoute.value.fullPath)`. The provider returns
+// the path, or an object such as { path, name, params }. Returns a function removing it
+export function registerRouteProvider(provider: () => unknown): () => void {
+    const target = window as any;
+    target.__TAURI_MCP_ROUTE__ = provider;
+    return () => {
+        if (target.__TAURI_MCP_ROUTE__ === provider) delete target.__TAURI_MCP_ROUTE__;
+    };
+}
+
+// Requests from the plugin arrive as { requestId, payload }; the requestId is echoed back
+// so the plugin can match each response to its request when several overlap
+function unwrapRequest(event: any): { requestId?: number; payload: any } {
+    const message = event.payload;
+    if (message && typeof message === 'object' && typeof message.requestId === 'number' && 'payload' in message) {
+        return { requestId: message.requestId, payload: message.payload };
+    }
+    return { payload: message };
+}
+
+async function respond(eventName: string, requestId: number | undefined, payload: any) {
+    await emit(eventName, requestId === undefined ? payload : { requestId, payload });
+}
+
+// Document of the same-origin iframe a request targets: the CSS selector of the frame, or
+// the selectors of the nested frames leading to it, outermost first
+function frameDocument(frameSelector: string | string[] | null | undefined): Document {
+    let doc = document;
+    const path = frameSelector == null ? [] : ([] as string[]).concat(frameSelector);
+    for (const selector of path) {
+        const frame = doc.querySelector(selector);
+        if (!frame || (frame.tagName !== 'IFRAME' && frame.tagName !== 'FRAME')) {
+            throw new Error(`No frame matches ${selector}`);
         }
+        const frameDoc = (frame as HTMLIFrameElement).contentDocument;
+        if (!frameDoc) {
+            throw new Error(`Frame ${selector} is cross-origin or not loaded`);
+        }
+        doc = frameDoc;
+    }
+    return doc;
+}
 
-        await emit('get-element-position-response', {
-            success: true,
-            data: {
-                x: targetX,
-                y: targetY,
-                element: {
-                    tag: element.tagName,
-                    classes: element.className,
-                    id: element.id,
-                    text: element.textContent?.trim() || '',
-                    placeholder: element instanceof HTMLInputElement ? element.placeholder : undefined
-                },
-                clicked: shouldClick,
-                clickResult,
-                debug: {
-                    elementRect: rect,
-                    viewportCenter: {
-                        x: elementViewportCssX,
-                        y: elementViewportCssY
-                    },
-                    documentCenter: {
-                        x: elementDocumentCssX,
-                        y: elementDocumentCssY
-                    },
-                    window: {
-                        innerSize: {
-                            width: window.innerWidth,
-                            height: window.innerHeight
-                        },
-                        scrollPosition: {
-                            x: window.scrollX,
-                            y: window.scrollY
-                        }
-                    }
-                }
+// Elements of iframes belong to another realm, where instanceof against this window's classes fails
+function isTextField(element: Element): element is HTMLInputElement | HTMLTextAreaElement {
+    return element.tagName === 'INPUT' || element.tagName === 'TEXTAREA';
+}
+
+// First match of selector in root or in the open shadow roots under it, nested ones included
+function deepQuerySelector(root: Document | ShadowRoot, selector: string): Element | null {
+    const match = root.querySelector(selector);
+    if (match) {
+        return match;
+    }
+    for (const element of root.querySelectorAll('*')) {
+        if (element.shadowRoot) {
+            const shadowMatch = deepQuerySelector(element.shadowRoot, selector);
+            if (shadowMatch) {
+                return shadowMatch;
             }
-        });
+        }
+    }
+    return null;
+}
 
-    } catch (error) {
-        console.error('TAURI-PLUGIN-MCP: Error handling get-element-position request', error);
-        await emit('get-element-position-response', {
-            success: false,
-            error: error instanceof Error ? error.toString() : String(error)
-        }).catch(e => console.error('TAURI-PLUGIN-MCP: Error emitting error response', e));
+// Elements of root and of the open shadow roots under it, in document order
+function deepElements(root: Document | ShadowRoot): Element[] {
+    const elements: Element[] = [];
+    for (const element of root.querySelectorAll('*')) {
+        elements.push(element);
+        if (element.shadowRoot) {
+            elements.push(...deepElements(element.shadowRoot));
+        }
     }
+    return elements;
 }
 
 // Helper function to find an element by its text content
-function findElementByText(text: string): Element | null {
-    // Get all elements in the document
-    const allElements = document.querySelectorAll('*');
+function findElementByText(text: string, root: Document = document): Element | null {
+    // Get all elements in the document, shadow roots included
+    const allElements = deepElements(root);
     
     // First try exact text content matching
     for (const element of allElements) {
@@ -216,7 +146,7 @@ function findElementByText(text: string): Element | null {
         }
         
         // Check placeholder attribute (for input fields)
-        if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {
+        if (isTextField(element)) {
             if (element.placeholder === text) {
                 return element;
             }
@@ -241,7 +171,7 @@ function findElementByText(text: string): Element | null {
         }
         
         // Check if text is contained within placeholder
-        if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {
+        if (isTextField(element)) {
             if (element.placeholder && element.placeholder.includes(text)) {
                 return element;
             }
@@ -263,84 +193,12 @@ function findElementByText(text: string): Element | null {
     return null;
 }
 
-// Helper function to click an element
-function clickElement(element: Element, centerX: number, centerY: number) {
-    try {
-        // Create and dispatch mouse events
-        const mouseDown = new MouseEvent('mousedown', {
-            bubbles: true,
-            cancelable: true,
-            view: window,
-            clientX: centerX,
-            clientY: centerY
-        });
-        
-        const mouseUp = new MouseEvent('mouseup', {
-            bubbles: true,
-            cancelable: true,
-            view: window,
-            clientX: centerX,
-            clientY: centerY
-        });
-        
-        const click = new MouseEvent('click', {
-            bubbles: true,
-            cancelable: true,
-            view: window,
-            clientX: centerX,
-            clientY: centerY
-        });
-        
-        // Dispatch the events
-        element.dispatchEvent(mouseDown);
-        element.dispatchEvent(mouseUp);
-        element.dispatchEvent(click);
-        
-        return {
-            success: true,
-            elementTag: element.tagName,
-            position: { x: centerX, y: centerY }
-        };
-    } catch (error) {
-        console.error('TAURI-PLUGIN-MCP: Error clicking element:', error);
-        return {
-            success: false,
-            error: error instanceof Error ? error.toString() : String(error)
-        };
-    }
-}
-
-async function handleDomContentRequest(event: any) {
-    console.log('TAURI-PLUGIN-MCP: Received got-dom-content, payload:', event.payload);
-    
-    try {
-        const domContent = getDomContent();
-        await emit('got-dom-content-response', domContent);
-        console.log('TAURI-PLUGIN-MCP: Emitted got-dom-content-response');
-    } catch (error) {
-        console.error('TAURI-PLUGIN-MCP: Error handling dom content request', error);
-        await emit('got-dom-content-response', '').catch(e => 
-            console.error('TAURI-PLUGIN-MCP: Error emitting empty response', e)
-        );
-    }
-}
-
-function getDomContent(): string {
-    if (document.readyState === 'complete' || document.readyState === 'interactive') {
-        const domContent = document.documentElement.outerHTML;
-        console.log('TAURI-PLUGIN-MCP: DOM content fetched, length:', domContent.length);
-        return domContent;
-    } 
-    
-    console.warn('TAURI-PLUGIN-MCP: DOM not fully loaded when got-dom-content received. Returning empty content.');
-    return '';
-}
-
 async function handleLocalStorageRequest(event: any) {
     console.log('TAURI-PLUGIN-MCP: Received get-local-storage, payload:', event.payload);
+    const { requestId, payload } = unwrapRequest(event);
     
     try {
-        const { action, key, value } = event.payload;
+        const { action, key, value } = payload;
         
         // Convert values that might be JSON strings to their actual values
         let processedKey = key;
@@ -377,11 +235,11 @@ async function handleLocalStorageRequest(event: any) {
         });
         
         const result = performLocalStorageOperation(action, processedKey, processedValue);
-        await emit('get-local-storage-response', result);
+        await respond('get-local-storage-response', requestId, result);
         console.log('TAURI-PLUGIN-MCP: Emitted get-local-storage-response');
     } catch (error) {
         console.error('TAURI-PLUGIN-MCP: Error handling localStorage request', error);
-        await emit('get-local-storage-response', { 
+        await respond('get-local-storage-response', requestId, { 
             success: false, 
             error: error instanceof Error ? error.toString() : String(error) 
         }).catch(e => 
@@ -464,10 +322,11 @@ function performLocalStorageOperation(action: string, key?: string | any, value?
 // Handle JS execution requests
 async function handleJsExecutionRequest(event: any) {
     console.log('TAURI-PLUGIN-MCP: Received execute-js, payload:', event.payload);
+    const { requestId, payload } = unwrapRequest(event);
     
     try {
         // Extract the code to execute
-        const code = event.payload;
+        const code = payload;
         
         // Execute the code
         const result = executeJavaScript(code);
@@ -479,13 +338,13 @@ async function handleJsExecutionRequest(event: any) {
         };
         
         // Send back the result
-        await emit('execute-js-response', response);
+        await respond('execute-js-response', requestId, response);
         console.log('TAURI-PLUGIN-MCP: Emitted execute-js-response');
     } catch (error) {
         console.error('TAURI-PLUGIN-MCP: Error executing JavaScript:', error);
         const errorMessage = error instanceof Error ? error.toString() : String(error);
         
-        await emit('execute-js-response', {
+        await respond('execute-js-response', requestId, {
             result: null,
             type: 'error',
             error: errorMessage
@@ -510,9 +369,11 @@ function executeJavaScript(code: string): any {
 
 async function handleSendTextToElementRequest(event: any) {
     console.log('TAURI-PLUGIN-MCP: Received send-text-to-element, payload:', event.payload);
+    const { requestId, payload } = unwrapRequest(event);
     
     try {
-        const { selectorType, selectorValue, text, delayMs = 20 } = event.payload;
+        const { selectorType, selectorValue, text, delayMs = 20, frameSelector } = payload;
+        const doc = frameDocument(frameSelector);
         
         // Find the element based on the selector type
         let element = null;
@@ -520,15 +381,15 @@ async function handleSendTextToElementRequest(event: any) {
         
         switch (selectorType) {
             case 'id':
-                element = document.getElementById(selectorValue);
+                element = doc.getElementById(selectorValue) || deepQuerySelector(doc, `#${CSS.escape(selectorValue)}`);
                 if (!element) {
                     debugInfo.push(`No element found with id="${selectorValue}"`);
                 }
                 break;
             case 'class':
                 // Get the first element with the class
-                const elemsByClass = document.getElementsByClassName(selectorValue);
-                element = elemsByClass.length > 0 ? elemsByClass[0] : null;
+                const elemsByClass = doc.getElementsByClassName(selectorValue);
+                element = elemsByClass.length > 0 ? elemsByClass[0] : deepQuerySelector(doc, `.${CSS.escape(selectorValue)}`);
                 if (!element) {
                     debugInfo.push(`No elements found with class="${selectorValue}" (total matching: 0)`);
                 } else if (elemsByClass.length > 1) {
@@ -537,8 +398,8 @@ async function handleSendTextToElementRequest(event: any) {
                 break;
             case 'tag':
                 // Get the first element with the tag name
-                const elemsByTag = document.getElementsByTagName(selectorValue);
-                element = elemsByTag.length > 0 ? elemsByTag[0] : null;
+                const elemsByTag = doc.getElementsByTagName(selectorValue);
+                element = elemsByTag.length > 0 ? elemsByTag[0] : deepQuerySelector(doc, CSS.escape(selectorValue));
                 if (!element) {
                     debugInfo.push(`No elements found with tag="${selectorValue}" (total matching: 0)`);
                 } else if (elemsByTag.length > 1) {
@@ -547,7 +408,7 @@ async function handleSendTextToElementRequest(event: any) {
                 break;
             case 'text':
                 // Find element by text content
-                element = findElementByText(selectorValue);
+                element = findElementByText(selectorValue, doc);
                 if (!element) {
                     debugInfo.push(`No element found with text="${selectorValue}"`);
                 }
@@ -561,10 +422,7 @@ async function handleSendTextToElementRequest(event: any) {
         }
         
         // Check if the element is an input field, textarea, or has contentEditable
-        const isEditableElement = 
-            element instanceof HTMLInputElement || 
-            element instanceof HTMLTextAreaElement || 
-            element.isContentEditable;
+        const isEditableElement = isTextField(element) || element.isContentEditable;
             
         if (!isEditableElement) {
             console.warn(`Element is not normally editable: ${element.tagName}. Will try to set value/textContent directly.`);
@@ -574,7 +432,7 @@ async function handleSendTextToElementRequest(event: any) {
         element.focus();
         
         // Set the text content based on element type
-        if (element instanceof HTMLInputElement || element instanceof HTMLTextAreaElement) {
+        if (isTextField(element)) {
             await simulateReactInputTyping(element, text, delayMs);
         } else if (element.isContentEditable) {
             // For contentEditable elements 
@@ -600,14 +458,14 @@ async function handleSendTextToElementRequest(event: any) {
             console.warn('TAURI-PLUGIN-MCP: Element is not an input, textarea, or contentEditable. Text was set directly but may not behave as expected.');
         }
         
-        await emit('send-text-to-element-response', {
+        await respond('send-text-to-element-response', requestId, {
             success: true,
             data: {
                 element: {
                     tag: element.tagName,
                     classes: element.className,
                     id: element.id,
-                    type: element instanceof HTMLInputElement ? element.type : null,
+                    type: element.tagName === 'INPUT' ? element.type : null,
                     text: text,
                     isEditable: isEditableElement
                 }
@@ -615,7 +473,7 @@ async function handleSendTextToElementRequest(event: any) {
         });
     } catch (error) {
         console.error('TAURI-PLUGIN-MCP: Error handling send-text-to-element request', error);
-        await emit('send-text-to-element-response', {
+        await respond('send-text-to-element-response', requestId, {
             success: false,
             error: error instanceof Error ? error.toString() : String(error)
         }).catch(e => console.error('TAURI-PLUGIN-MCP: Error emitting error response', e));
@@ -717,6 +575,9 @@ async function simulateReactInputTyping(element: HTMLInputElement | HTMLTextArea
 // Helper function to type text into a contentEditable element with a delay
 async function typeIntoContentEditable(element: HTMLElement, text: string, delayMs: number): Promise<void> {
     console.log('TAURI-PLUGIN-MCP: Using general contentEditable typing approach');
+    // Editors in iframes work on their own document
+    const doc = element.ownerDocument;
+    const view = doc.defaultView || window;
     
     try {
         // Focus first
@@ -744,8 +605,8 @@ async function typeIntoContentEditable(element: HTMLElement, text: string, delay
             
             // Insert the character by simulating typing
             // Use DOM selection and insertNode for proper insertion at cursor
-            const selection = window.getSelection();
-            const range = document.createRange();
+            const selection = view.getSelection();
+            const range = doc.createRange();
             
             // Set range to end of element
             range.selectNodeContents(element);
@@ -756,7 +617,7 @@ async function typeIntoContentEditable(element: HTMLElement, text: string, delay
             selection?.addRange(range);
             
             // Insert text at cursor position
-            const textNode = document.createTextNode(char);
+            const textNode = doc.createTextNode(char);
             range.insertNode(textNode);
             
             // Move selection to after inserted text
@@ -804,6 +665,9 @@ async function typeIntoContentEditable(element: HTMLElement, text: string, delay
 // Helper function specifically for Lexical Editor
 async function typeIntoLexicalEditor(element: HTMLElement, text: string, delayMs: number): Promise<void> {
     console.log('TAURI-PLUGIN-MCP: Starting specialized Lexical editor typing');
+    // Editors in iframes work on their own document
+    const doc = element.ownerDocument;
+    const view = doc.defaultView || window;
     
     try {
         // First focus the element
@@ -833,7 +697,7 @@ async function typeIntoLexicalEditor(element: HTMLElement, text: string, delayMs
             const char = text[i];
             
             // Find active element in case Lexical changed it
-            const activeElement = document.activeElement;
+            const activeElement = doc.activeElement;
             const currentTarget = (activeElement && element.contains(activeElement)) 
                 ? activeElement 
                 : targetParagraph;
@@ -859,7 +723,7 @@ async function typeIntoLexicalEditor(element: HTMLElement, text: string, delayMs
             
             // Use execCommand for more reliable text insertion
             if (!beforeInputEvent.defaultPrevented) {
-                document.execCommand('insertText', false, char);
+                doc.execCommand('insertText', false, char);
             }
             
             // Dispatch input event
@@ -889,8 +753,8 @@ async function typeIntoLexicalEditor(element: HTMLElement, text: string, delayMs
         
         // Final selection adjustment (move to end of text)
         try {
-            const selection = window.getSelection();
-            const range = document.createRange();
+            const selection = view.getSelection();
+            const range = doc.createRange();
             range.selectNodeContents(targetParagraph);
             range.collapse(false); // Collapse to end
             selection?.removeAllRanges();
@@ -917,6 +781,9 @@ async function typeIntoLexicalEditor(element: HTMLElement, text: string, delayMs
 // Helper function specifically for Slate Editor
 async function typeIntoSlateEditor(element: HTMLElement, text: string, delayMs: number): Promise<void> {
     console.log('TAURI-PLUGIN-MCP: Starting specialized Slate editor typing');
+    // Editors in iframes work on their own document
+    const doc = element.ownerDocument;
+    const view = doc.defaultView || window;
     
     try {
         // Focus the element
@@ -925,13 +792,13 @@ async function typeIntoSlateEditor(element: HTMLElement, text: string, delayMs:
         
         // Find the actual editable div in Slate editor
         const editableDiv = element.querySelector('[contenteditable="true"]') || element;
-        if (editableDiv instanceof HTMLElement) {
+        if (editableDiv instanceof view.HTMLElement) {
             editableDiv.focus();
         }
         
         // For Slate, we'll try the execCommand approach which is often more reliable
-        document.execCommand('selectAll', false, undefined);
-        document.execCommand('delete', false, undefined);
+        doc.execCommand('selectAll', false, undefined);
+        doc.execCommand('delete', false, undefined);
         await new Promise(resolve => setTimeout(resolve, 50));
         
         // Simulate typing with proper events
@@ -939,7 +806,7 @@ async function typeIntoSlateEditor(element: HTMLElement, text: string, delayMs:
             const char = text[i];
             
             // Ensure we're targeting the active element (Slate may change focus)
-            const activeElement = document.activeElement || editableDiv;
+            const activeElement = doc.activeElement || editableDiv;
             
             // Key events sequence
             activeElement.dispatchEvent(new KeyboardEvent('keydown', {
@@ -949,7 +816,7 @@ async function typeIntoSlateEditor(element: HTMLElement, text: string, delayMs:
             }));
             
             // Use execCommand for insertion
-            document.execCommand('insertText', false, char);
+            doc.execCommand('insertText', false, char);
             
             activeElement.dispatchEvent(new InputEvent('input', {
                 bubbles: true,
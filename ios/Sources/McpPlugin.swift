@@ -0,0 +1,91 @@
+import SwiftRs
+import Tauri
+import UIKit
+import WebKit
+
+/// Part of the viewport, in CSS pixels.
+class SnapshotClip: Decodable {
+  let x: Double
+  let y: Double
+  let width: Double
+  let height: Double
+}
+
+class SnapshotArgs: Decodable {
+  let clip: SnapshotClip?
+  let maxWidth: Int?
+  /// `png` or `jpeg`.
+  let format: String
+  let quality: Int
+}
+
+struct SnapshotResponse: Encodable {
+  /// The encoded image, base64.
+  let data: String
+  let width: Int
+  let height: Int
+}
+
+/// The iOS side of tauri-plugin-mcp, for what the Rust side cannot reach.
+class McpPlugin: Plugin {
+  private var webView: WKWebView?
+
+  @objc public override func load(webview: WKWebView) {
+    self.webView = webview
+  }
+
+  /// Snapshot the visible part of the page in device pixels, cropped to `clip`, scaled down
+  /// to `maxWidth` pixels wide and encoded as PNG or JPEG in base64.
+  @objc public func snapshotWebview(_ invoke: Invoke) throws {
+    let args = try invoke.parseArgs(SnapshotArgs.self)
+    DispatchQueue.main.async {
+      guard let webView = self.webView else {
+        invoke.reject("The webview is not loaded yet")
+        return
+      }
+      let configuration = WKSnapshotConfiguration()
+      // The web view's coordinates are CSS pixels of the viewport
+      if let clip = args.clip {
+        configuration.rect = CGRect(x: clip.x, y: clip.y, width: clip.width, height: clip.height)
+      }
+      webView.takeSnapshot(with: configuration) { image, error in
+        guard let image = image else {
+          invoke.reject(error?.localizedDescription ?? "The snapshot failed")
+          return
+        }
+        let pixelWidth = image.size.width * image.scale
+        let pixelHeight = image.size.height * image.scale
+        var width = pixelWidth.rounded()
+        var height = pixelHeight.rounded()
+        if let maxWidth = args.maxWidth, maxWidth > 0, width > CGFloat(maxWidth) {
+          width = CGFloat(maxWidth)
+          height = max(1, (pixelHeight * width / pixelWidth).rounded(.down))
+        }
+
+        // Redraw at one pixel per point, so the size is in device pixels
+        let format = UIGraphicsImageRendererFormat()
+        format.scale = 1
+        let size = CGSize(width: width, height: height)
+        let scaled = UIGraphicsImageRenderer(size: size, format: format).image { _ in
+          image.draw(in: CGRect(origin: .zero, size: size))
+        }
+        let data =
+          args.format == "jpeg"
+          ? scaled.jpegData(compressionQuality: CGFloat(min(max(args.quality, 1), 100)) / 100)
+          : scaled.pngData()
+        guard let data = data else {
+          invoke.reject("Could not encode the snapshot")
+          return
+        }
+        invoke.resolve(
+          SnapshotResponse(
+            data: data.base64EncodedString(), width: Int(width), height: Int(height)))
+      }
+    }
+  }
+}
+
+@_cdecl("init_plugin_mcp")
+func initPlugin() -> Plugin {
+  return McpPlugin()
+}
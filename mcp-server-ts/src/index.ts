@@ -18,7 +18,7 @@ async function main() {
     await initializeSocket();
     
     // Register all tools with the server
-    registerAllTools(server);
+    await registerAllTools(server);
     
     // Connect the server to stdio transport
     const transport = new StdioServerTransport();
@@ -0,0 +1,30 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerActionOverlayTool(server: McpServer) {
+  server.tool(
+    "set_action_overlay",
+    "Turns the action overlay on or off for the following commands: before clicking, typing into, setting or focusing an element, a ghost cursor moves to it and the element is outlined with the action's name, so people watching the session or a recording of it can follow what the agent does. Each command waits a quarter of a second for the cursor to arrive. Input tools also take a show_action parameter to show a single action.",
+    {
+      enabled: z.boolean().describe("Show the actions of the following commands."),
+    },
+    {
+      title: "Set Action Overlay",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ enabled }) => {
+      try {
+        const result = await socketClient.sendCommand('set_action_overlay', { enabled });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Action overlay error:', error);
+        return createErrorResponse(`Failed to set action overlay: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,61 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerAppFilesTools(server: McpServer) {
+  server.tool(
+    "read_app_file",
+    "Reads a file, or lists a directory, in one of the directories the Tauri application exposes (e.g. its data dir or a test fixtures dir), to verify exported output. Paths are relative to the named root.",
+    {
+      root: z.string().describe("Name of a file root configured by the app."),
+      path: z.string().optional().describe("Path relative to the root. Defaults to the root itself."),
+      encoding: z.enum(["utf8", "base64"]).optional().describe("Encoding of the returned content; use base64 for binary files. Defaults to utf8."),
+      max_bytes: z.number().int().positive().optional().describe("Maximum number of bytes returned. Defaults to 10 MiB."),
+    },
+    {
+      title: "Read App File",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ root, path, encoding, max_bytes }) => {
+      try {
+        const result = await socketClient.sendCommand('read_app_file', { root, path, encoding, max_bytes });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Read app file error:', error);
+        return createErrorResponse(`Failed to read app file: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "write_app_file",
+    "Writes a file in one of the directories the Tauri application exposes, e.g. to place a fixture before an import. Missing directories are created.",
+    {
+      root: z.string().describe("Name of a file root configured by the app."),
+      path: z.string().describe("Path relative to the root."),
+      content: z.string().describe("Content to write."),
+      encoding: z.enum(["utf8", "base64"]).optional().describe("Encoding of the content; use base64 for binary files. Defaults to utf8."),
+      append: z.boolean().optional().describe("Append to the file instead of replacing it. Defaults to false."),
+    },
+    {
+      title: "Write App File",
+      readOnlyHint: false,
+      destructiveHint: true,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ root, path, content, encoding, append }) => {
+      try {
+        const result = await socketClient.sendCommand('write_app_file', { root, path, content, encoding, append });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Write app file error:', error);
+        return createErrorResponse(`Failed to write app file: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,33 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerAppLogsTool(server: McpServer) {
+  server.tool(
+    "get_app_logs",
+    "Gets the log records of the Tauri application's Rust side (requires the app to install the plugin's LogBridge). Pass the returned cursor as `since` to only get the records logged afterwards, e.g. to correlate an interaction with backend logging.",
+    {
+      since: z.number().int().nonnegative().optional().describe("Only report the records after this ID (the cursor of a previous call)."),
+      level: z.enum(["error", "warn", "info", "debug", "trace"]).optional().describe("Only report records at this level or more severe."),
+      target: z.string().optional().describe("Only report records whose target starts with this prefix, e.g. a crate or module path."),
+      limit: z.number().int().positive().optional().describe("Maximum number of records returned. Defaults to 500."),
+    },
+    {
+      title: "Get App Logs",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ since, level, target, limit }) => {
+      try {
+        const result = await socketClient.sendCommand('get_app_logs', { since, level, target, limit });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('App logs error:', error);
+        return createErrorResponse(`Failed to get app logs: ${(error as Error).message}`);
+      }
+    },
+  );
+}
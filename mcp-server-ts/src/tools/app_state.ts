@@ -0,0 +1,30 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerAppStateTool(server: McpServer) {
+  server.tool(
+    "get_app_state",
+    "Gets snapshots of the backend state the Tauri application chose to expose (e.g. settings, session, counters), to verify the effect of UI actions. The response lists the names of all exposed states.",
+    {
+      names: z.array(z.string()).optional().describe("States to get. Defaults to all exposed states."),
+    },
+    {
+      title: "Get App State",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ names }) => {
+      try {
+        const result = await socketClient.sendCommand('get_app_state', { names });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('App state error:', error);
+        return createErrorResponse(`Failed to get app state: ${(error as Error).message}`);
+      }
+    },
+  );
+}
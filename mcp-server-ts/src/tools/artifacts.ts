@@ -0,0 +1,84 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerArtifactTools(server: McpServer) {
+  server.tool(
+    "list_artifacts",
+    "Lists session artifacts (recordings, baselines, audit logs, ...) stored by the Tauri MCP plugin. Each entry contains the artifact ID to use with get_artifact and delete_artifact, its kind, name, size and creation time.",
+    {
+      kind: z.string().optional().describe("Only list artifacts of this kind, e.g. 'recording' or 'audit'."),
+    },
+    {
+      title: "List Stored Session Artifacts",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ kind }) => {
+      try {
+        const result = await socketClient.sendCommand('list_artifacts', { kind });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Artifact listing error:', error);
+        return createErrorResponse(`Failed to list artifacts: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "get_artifact",
+    "Retrieves the content of a stored session artifact by ID. Text artifacts are returned as text, binary artifacts as base64.",
+    {
+      id: z.string().describe("Required. The artifact ID as returned by list_artifacts or another tool."),
+    },
+    {
+      title: "Get Stored Session Artifact",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ id }) => {
+      try {
+        const result = await socketClient.sendCommand('get_artifact', { id });
+        const content = Buffer.from(result.contentBase64, 'base64');
+        const text = content.toString('utf8');
+        // Return binary content untouched if it does not round-trip as UTF-8
+        if (Buffer.from(text, 'utf8').equals(content)) {
+          return createSuccessResponse(text);
+        }
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Artifact retrieval error:', error);
+        return createErrorResponse(`Failed to get artifact: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "delete_artifact",
+    "Deletes a stored session artifact by ID.",
+    {
+      id: z.string().describe("Required. The artifact ID to delete."),
+    },
+    {
+      title: "Delete Stored Session Artifact",
+      readOnlyHint: false,
+      destructiveHint: true,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ id }) => {
+      try {
+        await socketClient.sendCommand('delete_artifact', { id });
+        return createSuccessResponse(`Artifact ${id} deleted`);
+      } catch (error) {
+        console.error('Artifact deletion error:', error);
+        return createErrorResponse(`Failed to delete artifact: ${(error as Error).message}`);
+      }
+    },
+  );
+}
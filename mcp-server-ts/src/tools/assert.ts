@@ -0,0 +1,89 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+const windowLabel = z.string().optional().describe("The label of the window to check. Defaults to the focused window.");
+const selectorType = z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'.");
+const selectorValue = z.string().describe("The id, class, tag name, text or CSS selector of the element.");
+const negate = z.boolean().optional().describe("Assert the opposite.");
+const timeoutMs = z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app.");
+
+const annotations = (title: string) => ({
+  title,
+  readOnlyHint: true,
+  destructiveHint: false,
+  idempotentHint: true,
+  openWorldHint: false,
+});
+
+async function runAssertion(command: string, params: Record<string, unknown>) {
+  try {
+    const result = await socketClient.sendCommand(command, params);
+    return createSuccessResponse(formatResultAsText(result));
+  } catch (error) {
+    console.error('Assertion error:', error);
+    return createErrorResponse(`Failed to evaluate ${command}: ${(error as Error).message}`);
+  }
+}
+
+export function registerAssertTools(server: McpServer) {
+  server.tool(
+    "assert_element_exists",
+    "Checks that an element exists in the page (or does not, with negate). Returns a verdict with passed, expected, actual and a message; a failed assertion is not an error.",
+    {
+      window_label: windowLabel,
+      selector_type: selectorType,
+      selector_value: selectorValue,
+      negate: negate.describe("Assert that the element does not exist."),
+      timeout_ms: timeoutMs,
+    },
+    annotations("Assert Element Exists"),
+    async (params) => runAssertion('assert_element_exists', params),
+  );
+
+  server.tool(
+    "assert_element_visible",
+    "Checks that an element is visible: it exists, is rendered (not display:none, visibility:hidden or opacity 0) and has a size. With negate, checks that it is not visible. The message explains why a failed assertion failed.",
+    {
+      window_label: windowLabel,
+      selector_type: selectorType,
+      selector_value: selectorValue,
+      negate: negate.describe("Assert that the element is missing or hidden."),
+      timeout_ms: timeoutMs,
+    },
+    annotations("Assert Element Visible"),
+    async (params) => runAssertion('assert_element_visible', params),
+  );
+
+  server.tool(
+    "assert_text_contains",
+    "Checks that the rendered text of an element, or of the whole page without a selector, contains a string. The actual text (up to 2000 characters) is returned for comparison.",
+    {
+      window_label: windowLabel,
+      selector_type: selectorType.optional(),
+      selector_value: selectorValue.optional(),
+      text: z.string().describe("The text that should appear."),
+      ignore_case: z.boolean().optional().describe("Compare case-insensitively."),
+      negate: negate.describe("Assert that the text does not appear."),
+      timeout_ms: timeoutMs,
+    },
+    annotations("Assert Text Contains"),
+    async (params) => runAssertion('assert_text_contains', params),
+  );
+
+  server.tool(
+    "assert_url_matches",
+    "Checks the current URL of a window. By default the pattern is a substring of the URL; use exact to compare the whole URL or regex for a JavaScript regular expression.",
+    {
+      window_label: windowLabel,
+      pattern: z.string().describe("Substring, full URL or regular expression to check the URL against."),
+      exact: z.boolean().optional().describe("The URL must equal the pattern."),
+      regex: z.boolean().optional().describe("The pattern is a JavaScript regular expression, e.g. '/settings/\\\\d+$'."),
+      negate: negate.describe("Assert that the URL does not match."),
+      timeout_ms: timeoutMs,
+    },
+    annotations("Assert URL Matches"),
+    async (params) => runAssertion('assert_url_matches', params),
+  );
+}
@@ -0,0 +1,31 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerAuditLogTool(server: McpServer) {
+  server.tool(
+    "get_audit_log",
+    "Returns the audit log of socket commands executed against the Tauri application: timestamp, command, truncated payload, outcome and duration of each call, oldest first. Useful to review exactly what an agent did to the app.",
+    {
+      limit: z.number().int().positive().optional().describe("Only return the most recent N entries."),
+      command: z.string().optional().describe("Only return entries for this command name, e.g. 'execute_js'."),
+    },
+    {
+      title: "Get Audit Log of Executed Commands",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ limit, command }) => {
+      try {
+        const result = await socketClient.sendCommand('get_audit_log', { limit, command });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Audit log retrieval error:', error);
+        return createErrorResponse(`Failed to get audit log: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,62 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, formatResultAsText } from "./response-helpers.js";
+
+interface CanvasCapture {
+  data: string | null;
+  mimeType: string;
+  [key: string]: unknown;
+}
+
+export function registerCaptureCanvasTool(server: McpServer) {
+  server.tool(
+    "capture_canvas",
+    "Captures the content of <canvas> elements (charts, WebGL scenes, drawings) as images, since their content is invisible to get_dom and get_page_text. Returns each canvas as an image plus its size, context type and whether it came out blank. WebGL canvases are read right after the app draws a frame; blank ones are retried after a resize event.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the canvas. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).optional().describe("How to find the canvas. Defaults to 'css'."),
+      selector_value: z.string().optional().describe("The id, class, tag name, text or CSS selector of the canvas. Defaults to 'canvas'."),
+      all: z.boolean().optional().describe("Capture every matching canvas instead of the first one (css, tag and class selectors)."),
+      format: z.enum(["png", "jpeg", "webp"]).optional().describe("Image format. Defaults to png."),
+      quality: z.number().min(0).max(1).optional().describe("Quality of jpeg and webp images, from 0 to 1."),
+      rerender: z.boolean().optional().describe("Dispatch a resize event and capture again when a canvas is blank. Defaults to true."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Capture Canvas Content",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, all, format, quality, rerender, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('capture_canvas', {
+          window_label,
+          selector_type,
+          selector_value,
+          all,
+          format,
+          quality,
+          rerender,
+          timeout_ms,
+        }) as { canvases?: CanvasCapture[] };
+        const canvases = result?.canvases ?? [];
+        const summary = canvases.map(({ data: _data, ...rest }) => rest);
+        return {
+          isError: false,
+          content: [
+            { type: "text" as const, text: formatResultAsText(summary) },
+            ...canvases
+              .filter((canvas) => canvas.data)
+              .map((canvas) => ({ type: "image" as const, data: canvas.data as string, mimeType: canvas.mimeType })),
+          ],
+        };
+      } catch (error) {
+        console.error('Canvas capture error:', error);
+        return createErrorResponse(`Failed to capture canvas: ${(error as Error).message}`);
+      }
+    },
+  );
+}
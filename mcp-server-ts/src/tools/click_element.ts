@@ -0,0 +1,50 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerClickElementTool(server: McpServer) {
+  server.tool(
+    "click_element",
+    "Clicks an element found by selector. An element that is not fully visible is scrolled into view first. The 'dom' strategy (default) dispatches pointer and mouse events in the page; 'os' moves the real mouse and clicks at the element's center, which the page sees as a trusted click; 'auto' uses the OS mouse when OS-level input is fully available. The response reports the strategy used.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      button: z.enum(["left", "middle", "right"]).optional().describe("Mouse button. Defaults to left; right opens the context menu."),
+      click_count: z.number().int().min(1).max(3).optional().describe("1 for a click, 2 for a double click, 3 for a triple click. Defaults to 1."),
+      modifiers: z.array(z.enum(["shift", "ctrl", "alt", "meta"])).optional().describe("Modifier keys held during the click."),
+      strategy: z.enum(["auto", "dom", "os"]).optional().describe("How to click. Defaults to 'dom'."),
+      scroll_into_view: z.boolean().optional().describe("Scroll the element to the center of the window first when it is not fully visible. Defaults to true."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Click Element by Selector",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, button, click_count, modifiers, strategy, scroll_into_view, show_action, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('click_element', {
+          window_label,
+          selector_type,
+          selector_value,
+          button,
+          click_count,
+          modifiers,
+          strategy,
+          scroll_into_view,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Click element error:', error);
+        return createErrorResponse(`Failed to click element: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -1,10 +1,13 @@
 import * as net from 'net';
 import * as os from 'os';
 import * as fs from 'fs';
+import * as path from 'path';
 
 // Constants
 const SOCKET_FILENAME = 'tauri-mcp.sock';
 const DEFAULT_SOCKET_PATH = `/private/tmp/${SOCKET_FILENAME}`;
+// Socket protocol version spoken by this client
+const PROTOCOL_VERSION = 1;
 
 // Connection configuration types
 export interface IpcConfig {
@@ -20,6 +23,70 @@ export interface TcpConfig {
 
 export type ConnectionConfig = IpcConfig | TcpConfig;
 
+// Running app advertised in the discovery directory by the Tauri plugin
+export interface DiscoveredInstance {
+  appName: string;
+  pid: number;
+  transport: 'ipc' | 'tcp';
+  address: string;
+  protocolVersion: number;
+  startedMs: number;
+}
+
+// Directory where running apps advertise their socket
+const DISCOVERY_DIR = path.join(os.tmpdir(), 'tauri-mcp');
+
+function isProcessAlive(pid: number): boolean {
+  try {
+    process.kill(pid, 0);
+    return true;
+  } catch (error) {
+    // EPERM means the process exists but belongs to another user
+    return (error as NodeJS.ErrnoException).code === 'EPERM';
+  }
+}
+
+// List the running apps, most recently started first
+export function discoverInstances(): DiscoveredInstance[] {
+  let files: string[];
+  try {
+    files = fs.readdirSync(DISCOVERY_DIR).filter((file) => file.endsWith('.json'));
+  } catch {
+    return [];
+  }
+
+  const instances: DiscoveredInstance[] = [];
+  for (const file of files) {
+    try {
+      const instance = JSON.parse(fs.readFileSync(path.join(DISCOVERY_DIR, file), 'utf8'));
+      if (isProcessAlive(instance.pid)) {
+        instances.push(instance);
+      }
+    } catch {
+      // Ignore files being written or removed
+    }
+  }
+  return instances.sort((a, b) => b.startedMs - a.startedMs);
+}
+
+// Pick the app to connect to, optionally by name (TAURI_MCP_APP)
+function discoverTarget(appName?: string): ConnectionConfig | null {
+  const instance = discoverInstances().find((candidate) => !appName || candidate.appName === appName);
+  if (!instance) {
+    return null;
+  }
+  console.error(`Discovered ${instance.appName || 'Tauri app'} (pid ${instance.pid}) at ${instance.address}`);
+  if (instance.transport === 'tcp') {
+    const separator = instance.address.lastIndexOf(':');
+    return {
+      type: 'tcp',
+      host: instance.address.substring(0, separator),
+      port: parseInt(instance.address.substring(separator + 1), 10),
+    };
+  }
+  return { type: 'ipc', path: instance.address };
+}
+
 // Socket client for Tauri IPC/TCP
 export class TauriSocketClient {
   private config: ConnectionConfig;
@@ -27,11 +94,17 @@ export class TauriSocketClient {
   private isConnected = false;
   private responseCallbacks: Map<string, { resolve: (value: any) => void, reject: (reason: any) => void }> = new Map();
   private buffer = '';
+  // Chunks of streamed responses, by request ID
+  private streamedChunks: Map<string, string[]> = new Map();
   private reconnectAttempts = 0;
+  // Keepalive messages the app sends every keepaliveIntervalMs, to notice a dead connection
+  private keepaliveIntervalMs: number | null = null;
+  private keepaliveTimer: NodeJS.Timeout | null = null;
+  private lastDataAt = 0;
 
   constructor(config?: ConnectionConfig) {
-    // Default to IPC with default path
-    this.config = config || { type: 'ipc', path: DEFAULT_SOCKET_PATH };
+    // Default to IPC, discovering the running app at connection time
+    this.config = config || { type: 'ipc' };
   }
 
   async connect(): Promise<void> {
@@ -41,20 +114,31 @@ export class TauriSocketClient {
       let connectionOptions: net.NetConnectOpts;
       let connectionInfo: string;
 
-      if (this.config.type === 'tcp') {
+      // Without an explicit path, look for a running app in the discovery directory
+      let config = this.config;
+      let discovered = false;
+      if (config.type === 'ipc' && !config.path) {
+        const target = discoverTarget(process.env.TAURI_MCP_APP);
+        discovered = target !== null;
+        config = target || config;
+      }
+
+      if (config.type === 'tcp') {
         // TCP connection
         connectionOptions = {
-          host: this.config.host,
-          port: this.config.port
+          host: config.host,
+          port: config.port
         };
-        connectionInfo = `TCP ${this.config.host}:${this.config.port}`;
+        connectionInfo = `TCP ${config.host}:${config.port}`;
       } else {
         // IPC connection
-        let connectionPath = this.config.path || DEFAULT_SOCKET_PATH;
+        let connectionPath = config.path || DEFAULT_SOCKET_PATH;
         
         // On Windows, the socket is created as a named pipe in a specific location
         if (os.platform() === 'win32') {
-          connectionPath = `\\\\.\\pipe\\tmp\\${SOCKET_FILENAME}`;
+          connectionPath = discovered
+            ? `\\\\.\\pipe\\${connectionPath}`
+            : `\\\\.\\pipe\\tmp\\${SOCKET_FILENAME}`;
           console.error(`Using Windows-specific pipe path: ${connectionPath}`);
         }
         
@@ -70,11 +154,18 @@ export class TauriSocketClient {
         console.error(`Connected to Tauri socket server at ${connectionInfo}`);
         
         // Setup data handler
+        this.lastDataAt = Date.now();
         this.client!.on('data', (data) => {
+          this.lastDataAt = Date.now();
           this.handleData(data);
         });
         
-        resolve();
+        this.handshake().then(resolve, (err) => {
+          // Reconnecting would be rejected the same way
+          this.reconnectAttempts = Infinity;
+          this.client?.destroy();
+          reject(err);
+        });
       });
 
       this.client!.on('error', (err) => {
@@ -86,6 +177,15 @@ export class TauriSocketClient {
       this.client!.on('close', () => {
         this.isConnected = false;
         console.error('Socket connection closed');
+        this.stopKeepaliveWatch();
+
+        // Requests sent on this connection will never be answered
+        for (const [id, callback] of this.responseCallbacks.entries()) {
+          callback.reject(new Error('Connection to the Tauri app closed'));
+          this.responseCallbacks.delete(id);
+        }
+        this.streamedChunks.clear();
+        this.buffer = '';
         
         // Try to reconnect if not too many attempts
         if (this.reconnectAttempts < 3) {
@@ -101,6 +201,53 @@ export class TauriSocketClient {
     });
   }
   
+  // Exchange protocol versions so an incompatible app is reported up front, and send the
+  // token of apps requiring one (TAURI_MCP_TOKEN)
+  private async handshake(): Promise<void> {
+    try {
+      const token = process.env.TAURI_MCP_TOKEN;
+      const result = await this.sendCommand('handshake', {
+        protocolVersion: PROTOCOL_VERSION,
+        keepalive: true,
+        ...(token ? { token } : {}),
+      });
+      console.error(`Handshake done, app speaks protocol version ${result?.protocolVersion}`);
+      this.keepaliveIntervalMs = result?.keepaliveIntervalMs ?? null;
+      this.startKeepaliveWatch();
+    } catch (error) {
+      const message = (error as Error).message;
+      // Apps predating the handshake only speak version 1
+      if (message.includes('Unknown command')) {
+        return;
+      }
+      throw new Error(`Tauri app rejected the connection: ${message}`);
+    }
+  }
+
+  // Close connections the app stopped answering, three keepalives missed, so the next
+  // command reconnects, e.g. to the app restarted in the meantime
+  private startKeepaliveWatch() {
+    this.stopKeepaliveWatch();
+    const interval = this.keepaliveIntervalMs;
+    if (!interval) {
+      return;
+    }
+    this.keepaliveTimer = setInterval(() => {
+      if (Date.now() - this.lastDataAt > interval * 3) {
+        console.error(`No keepalive from the Tauri app for ${interval * 3} ms, closing the connection`);
+        this.client?.destroy();
+      }
+    }, interval);
+    this.keepaliveTimer.unref();
+  }
+
+  private stopKeepaliveWatch() {
+    if (this.keepaliveTimer) {
+      clearInterval(this.keepaliveTimer);
+      this.keepaliveTimer = null;
+    }
+  }
+
   private handleData(data: Buffer) {
     // Accumulate data in the buffer
     this.buffer += data.toString();
@@ -117,15 +264,41 @@ export class TauriSocketClient {
       
       try {
         const response = JSON.parse(jsonStr);
+
+        // Keepalives and notices of the app answer no request
+        if (response.id === undefined && response.keepalive !== undefined) {
+          continue;
+        }
+        if (response.id === undefined && response.event === 'idleTimeout') {
+          console.error(`The Tauri app closes connections idle for ${response.idleTimeoutMs} ms`);
+          continue;
+        }
+
+        // Streamed responses arrive as chunks followed by a terminator carrying the status
+        if (response.chunk && typeof response.id === 'string') {
+          const parts = this.streamedChunks.get(response.id) ?? [];
+          parts[response.chunk.seq] = response.chunk.data;
+          this.streamedChunks.set(response.id, parts);
+          continue;
+        }
+        if (response.streamed && typeof response.id === 'string') {
+          const parts = this.streamedChunks.get(response.id) ?? [];
+          this.streamedChunks.delete(response.id);
+          console.error(`Reassembled streamed response from ${parts.length} chunks`);
+          if (response.success) {
+            response.data = JSON.parse(parts.join(''));
+          }
+        }
         
-        // Process all matching callbacks that might be waiting for this response
-        // Rather than just taking the first one, match based on timestamps (oldest first)
+        // Match the response by its ID when the server echoes it,
+        // otherwise fall back to the oldest pending request (IDs start with a timestamp)
         const callbackIds = Array.from(this.responseCallbacks.keys());
         
         if (callbackIds.length > 0) {
-          // Sort by timestamp (assuming IDs start with timestamp)
           callbackIds.sort();
-          const callbackId = callbackIds[0];
+          const callbackId = typeof response.id === 'string' && this.responseCallbacks.has(response.id)
+            ? response.id
+            : callbackIds[0];
           
           const callback = this.responseCallbacks.get(callbackId);
           if (callback) {
@@ -136,7 +309,10 @@ export class TauriSocketClient {
               // If the server indicates failure, reject the promise with the error message
               const errorMsg = response.error || 'Command failed without specific error';
               console.error(`Command failed with error: ${errorMsg}`);
-              callback.reject(new Error(errorMsg));
+              // Keep structured error details (e.g. rate limit code and retryAfterMs)
+              const error = new Error(errorMsg) as Error & { data?: unknown };
+              error.data = response.data;
+              callback.reject(error);
             } else {
               callback.resolve(response.data);
             }
@@ -172,7 +348,11 @@ export class TauriSocketClient {
     }
   }
 
-  async sendCommand(command: string, payload: Record<string, any> | string = {}): Promise<any> {
+  /**
+   * Send a command and wait for its response.
+   * If `signal` is aborted while the request is in flight, a `cancel` command is sent for it.
+   */
+  async sendCommand(command: string, payload: Record<string, any> | string = {}, signal?: AbortSignal): Promise<any> {
     if (!this.isConnected) {
       try {
         await this.connect();
@@ -198,14 +378,26 @@ export class TauriSocketClient {
         finalPayload = payload;
       }
       
+      // Generate a unique ID for this request including timestamp for ordering
+      const requestId = Date.now().toString() + Math.random().toString(36).substring(2);
+      this.responseCallbacks.set(requestId, { resolve, reject });
+
       const request = JSON.stringify({
+        id: requestId,
         command,
-        payload: finalPayload
+        payload: finalPayload,
+        stream: true
       }) + '\n';
 
-      // Generate a unique ID for this request including timestamp for ordering
-      const requestId = Date.now().toString() + Math.random().toString(36).substring(2);
-      this.responseCallbacks.set(requestId, { resolve, reject });
+      signal?.addEventListener('abort', () => {
+        if (this.responseCallbacks.has(requestId)) {
+          this.responseCallbacks.delete(requestId);
+          this.sendCommand('cancel', { requestId }).catch((err) => {
+            console.error(`Failed to cancel request ${requestId}: ${err.message}`);
+          });
+          reject(new Error('Request cancelled'));
+        }
+      }, { once: true });
 
       // Log the request
       console.error(`Sending request: ${command} with payload: ${JSON.stringify(finalPayload)}`);
@@ -219,13 +411,22 @@ export class TauriSocketClient {
         }
       });
       
-      // Set a timeout to prevent hanging if response never comes
+      // Set a timeout to prevent hanging if response never comes,
+      // leaving room for a longer timeout requested from the app
+      const requestedTimeout = Number(finalPayload.timeout_ms ?? finalPayload.timeoutMs) || 0;
+      const clientTimeout = Math.max(30000, requestedTimeout + 5000);
       setTimeout(() => {
         if (this.responseCallbacks.has(requestId)) {
           this.responseCallbacks.delete(requestId);
-          reject(new Error('Request timed out after 30 seconds'));
+          // Commands queued while the session is paused would otherwise run on resume
+          if (command !== 'cancel') {
+            this.sendCommand('cancel', { requestId }).catch((err) => {
+              console.error(`Failed to cancel request ${requestId}: ${err.message}`);
+            });
+          }
+          reject(new Error(`Request timed out after ${clientTimeout / 1000} seconds`));
         }
-      }, 30000);
+      }, clientTimeout);
     });
   }
 }
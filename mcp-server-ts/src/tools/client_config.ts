@@ -0,0 +1,38 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerClientConfigTool(server: McpServer) {
+  server.tool(
+    "generate_client_config",
+    "Generates ready-to-paste MCP client configuration for this app, derived from its live plugin configuration: the server command and arguments, and the environment selecting its transport (application name, custom socket path or TCP host and port) and auth token. Returns the JSON to merge into the client's configuration file, where that file is, and for Claude Code the `claude mcp add` command line.",
+    {
+      client: z.enum(["claude_desktop", "claude_code", "cursor", "vscode", "windsurf"]).default("claude_desktop").describe("The MCP client to configure. Defaults to 'claude_desktop'."),
+      server: z.enum(["node", "binary"]).optional().describe("Start the TypeScript server with Node.js, or the tauri-mcp-server binary. Defaults to Node when the TypeScript server of the plugin sources is built."),
+      server_path: z.string().optional().describe("Path of mcp-server-ts/build/index.js for Node, or of the tauri-mcp-server binary."),
+      server_name: z.string().optional().describe("Name of the server entry. Defaults to 'tauri-mcp'."),
+    },
+    {
+      title: "Generate MCP Client Configuration",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ client, server: launcher, server_path, server_name }) => {
+      try {
+        const result = await socketClient.sendCommand('generate_client_config', {
+          client,
+          server: launcher,
+          server_path,
+          server_name,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Client configuration error:', error);
+        return createErrorResponse(`Failed to generate client configuration: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,48 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerControlMediaTool(server: McpServer) {
+  server.tool(
+    "control_media",
+    "Plays, pauses, seeks, mutes or unmutes an <audio> or <video> element, or just reads its state, and returns currentTime, duration (null while unknown or for live streams), paused, ended, seeking, muted, volume, playbackRate, readyState (0 no data to 4 enough to play through), networkState, buffered ranges and any media error. Use it to verify media playback without watching pixels. playError tells why play() failed, e.g. an autoplay policy.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element, e.g. 'video'."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first."),
+      action: z.enum(["status", "play", "pause", "seek", "mute", "unmute"]).optional().describe("What to do. Defaults to 'status', which changes nothing."),
+      time: z.number().min(0).optional().describe("Position to seek to, in seconds. Required for 'seek'."),
+      volume: z.number().min(0).max(1).optional().describe("Volume to set, between 0 and 1."),
+      playback_rate: z.number().positive().optional().describe("Playback speed to set, 1 being normal."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Control Media Element",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, frame_selector, action, time, volume, playback_rate, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('control_media', {
+          window_label,
+          selector_type,
+          selector_value,
+          frame_selector,
+          action,
+          time,
+          volume,
+          playback_rate,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Media control error:', error);
+        return createErrorResponse(`Failed to control media: ${(error as Error).message}`);
+      }
+    },
+  );
+}
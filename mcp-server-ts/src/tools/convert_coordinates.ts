@@ -0,0 +1,33 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerConvertCoordinatesTool(server: McpServer) {
+  server.tool(
+    "convert_coordinates",
+    "Converts a point of a window between coordinate spaces, to debug clicks that land in the wrong place on HiDPI screens: viewport (CSS pixels as getBoundingClientRect reports them), document (viewport plus scroll), window (logical pixels from the window's outer corner, as simulate_mouse_movement takes them), logical and physical screen pixels. Returns the point in every space, where the OS mouse goes for it, and the window's scale factor and position.",
+    {
+      x: z.number().describe("Horizontal position of the point."),
+      y: z.number().describe("Vertical position of the point."),
+      from: z.enum(["viewport", "document", "window", "logical", "physical"]).optional().describe("Space the point is given in. Defaults to 'viewport'."),
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+    },
+    {
+      title: "Convert Coordinates",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ x, y, from, window_label }) => {
+      try {
+        const result = await socketClient.sendCommand('convert_coordinates', { x, y, from, window_label });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Coordinate conversion error:', error);
+        return createErrorResponse(`Failed to convert coordinates: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,31 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerDeepLinkTool(server: McpServer) {
+  server.tool(
+    "trigger_deep_link",
+    "Delivers a URL to the Tauri application as if it was opened through its custom URL scheme (e.g. 'myapp://invite/abc'), to test deep-link flows on any platform. Works with tauri-plugin-deep-link's onOpenUrl handlers.",
+    {
+      url: z.string().describe("URL to open, e.g. 'myapp://invite/abc'."),
+      event: z.string().optional().describe("Event to emit instead of tauri-plugin-deep-link's 'deep-link://new-url', for apps handling links themselves."),
+    },
+    {
+      title: "Trigger Deep Link",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ url, event }) => {
+      try {
+        const result = await socketClient.sendCommand('trigger_deep_link', { url, event });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Deep link error:', error);
+        return createErrorResponse(`Failed to trigger deep link: ${(error as Error).message}`);
+      }
+    },
+  );
+}
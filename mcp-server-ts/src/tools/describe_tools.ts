@@ -0,0 +1,32 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerDescribeToolsTool(server: McpServer) {
+  server.tool(
+    "describe_tools",
+    "Reports the live platform capabilities of the Tauri application (OS input, window management, webview access) and which tools are available, degraded (with the reason) or unavailable on this platform.",
+    {
+      include_unavailable: z.boolean().optional().describe("Also list tools that are unavailable on this platform. Defaults to false."),
+    },
+    {
+      title: "Describe Tool Availability on This Platform",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ include_unavailable }) => {
+      try {
+        const result = await socketClient.sendCommand('describe_tools', { includeUnavailable: include_unavailable });
+        // The tool list of this server already carries each tool's description and schema
+        const tools = result?.tools?.map(({ description, inputSchema, ...tool }: any) => tool);
+        return createSuccessResponse(formatResultAsText({ ...result, tools }));
+      } catch (error) {
+        console.error('Tool description error:', error);
+        return createErrorResponse(`Failed to describe tools: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,117 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerDialogTools(server: McpServer) {
+  server.tool(
+    "set_dialog_interception",
+    "Turns interception of native dialogs (file open/save, message, ask, confirm) opened through the Tauri dialog plugin on or off. While enabled, no native dialog is shown: the dialog waits until it is answered with respond_to_dialog, and a 'dialogOpened' event is published for poll_events. Queued auto-responses answer the next dialogs without a round trip. Returns the interception state and pending dialogs.",
+    {
+      enabled: z.boolean().optional().describe("Enable or disable interception. Leave out to keep the current state."),
+      auto_responses: z.array(z.any()).optional().describe("Answers given automatically to the next dialogs, in order, replacing any queued ones: a path or array of paths for open, a path for save, a boolean for ask/confirm, null to cancel. Pass [] to clear the queue."),
+    },
+    {
+      title: "Configure Native Dialog Interception",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ enabled, auto_responses }) => {
+      try {
+        const result = await socketClient.sendCommand('set_dialog_interception', {
+          enabled,
+          autoResponses: auto_responses,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Dialog interception error:', error);
+        return createErrorResponse(`Failed to configure dialog interception: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "list_dialogs",
+    "Lists the intercepted dialogs waiting for an answer, oldest first, with their ID, window, kind (open, save, message, ask, confirm) and the options the app passed (title, filters, default path...). Also returns the dialog policy and the dialogs it answered.",
+    {},
+    {
+      title: "List Pending Dialogs",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('list_dialogs', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Dialog listing error:', error);
+        return createErrorResponse(`Failed to list dialogs: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "respond_to_dialog",
+    "Answers an intercepted dialog as if the user had, resuming the app code awaiting it. Use a path or array of paths for open dialogs, a path for save dialogs, true/false for ask and confirm, and null to cancel. Paths are used as given on the machine running the app.",
+    {
+      dialog_id: z.string().optional().describe("The dialog to answer, as returned by list_dialogs or the dialogOpened event. Defaults to the oldest pending dialog."),
+      value: z.any().optional().describe("The answer. Leave out or null to cancel the dialog."),
+    },
+    {
+      title: "Answer Pending Dialog",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ dialog_id, value }) => {
+      try {
+        const result = await socketClient.sendCommand('respond_to_dialog', {
+          dialogId: dialog_id,
+          value: value ?? null,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Dialog answer error:', error);
+        return createErrorResponse(`Failed to answer dialog: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "set_dialog_policy",
+    "Sets how the webview's alert, confirm and prompt dialogs are answered, so they never block the session. While a policy is set they return immediately: the first matching rule gives the answer, otherwise confirms are accepted and prompts get their default value (unless accept_confirms/accept_prompts say otherwise). Each answered dialog is recorded (see list_dialogs) and published as a 'dialogAnswered' event. With plugin_dialogs, message/ask/confirm dialogs of the Tauri dialog plugin are answered the same way.",
+    {
+      disable: z.boolean().optional().describe("Remove the policy so dialogs show normally again."),
+      accept_confirms: z.boolean().optional().describe("Answer confirm dialogs no rule matches with OK (true, the default) or Cancel."),
+      accept_prompts: z.boolean().optional().describe("Answer prompts no rule matches with their default value (true, the default) or cancel them."),
+      rules: z.array(z.object({
+        kind: z.enum(["alert", "confirm", "prompt", "message", "ask"]).optional().describe("Kind of dialog the rule applies to. Defaults to any."),
+        message_contains: z.string().optional().describe("Text the dialog message has to contain. Defaults to any message."),
+        answer: z.any().optional().describe("true/false for confirm and ask, text or null (cancel) for prompt, button label for plugin message dialogs."),
+      })).optional().describe("Rules tried in order before the defaults."),
+      plugin_dialogs: z.boolean().optional().describe("Also answer message, ask and confirm dialogs of the Tauri dialog plugin opened from the webview. Defaults to false."),
+    },
+    {
+      title: "Set Dialog Policy",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ disable, accept_confirms, accept_prompts, rules, plugin_dialogs }) => {
+      try {
+        const policy = disable ? null : { accept_confirms, accept_prompts, rules, plugin_dialogs };
+        const result = await socketClient.sendCommand('set_dialog_policy', { policy });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Dialog policy error:', error);
+        return createErrorResponse(`Failed to set dialog policy: ${(error as Error).message}`);
+      }
+    },
+  );
+}
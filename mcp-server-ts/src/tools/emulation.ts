@@ -0,0 +1,274 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerEmulationTools(server: McpServer) {
+  server.tool(
+    "set_emulation",
+    "Makes a window deterministic: sets Date and performance.now to an emulated clock (frozen, or running from a given time) and seeds Math.random, crypto.getRandomValues and crypto.randomUUID. Overrides survive reloads. Call without options to read the current overrides.",
+    {
+      window_label: z.string().optional().describe("The label of the window to emulate in. Defaults to the focused window."),
+      clock: z.object({
+        time_ms: z.number().int().nonnegative().optional().describe("Start time in milliseconds since the Unix epoch. Defaults to now."),
+        frozen: z.boolean().optional().describe("Keep the clock still; it then only moves with advance_clock."),
+      }).optional().describe("Emulate the clock."),
+      random_seed: z.number().int().nonnegative().optional().describe("Seed for the random generators; the same seed gives the same sequence."),
+      reset: z.array(z.enum(["clock", "random", "geolocation", "locale", "theme", "device", "network", "mocks", "all"])).optional().describe("Overrides to remove first."),
+    },
+    {
+      title: "Emulate Clock and Randomness",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, clock, random_seed, reset }) => {
+      try {
+        const result = await socketClient.sendCommand('set_emulation', {
+          window_label,
+          clock,
+          random_seed,
+          reset,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Emulation error:', error);
+        return createErrorResponse(`Failed to set emulation: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "advance_clock",
+    "Moves the emulated clock of a window forward, e.g. to step through a countdown or expire a session while the clock is frozen. Timers (setTimeout, setInterval) are not fast-forwarded.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      ms: z.number().int().positive().describe("Milliseconds to add to the clock."),
+    },
+    {
+      title: "Advance Emulated Clock",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ window_label, ms }) => {
+      try {
+        const result = await socketClient.sendCommand('advance_clock', { window_label, ms });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Clock error:', error);
+        return createErrorResponse(`Failed to advance clock: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "set_geolocation",
+    "Makes navigator.geolocation of a window report a given position, with the permission granted, or deny location access. Running watchPosition watches receive the new position. Use reset to restore the real location service.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      latitude: z.number().min(-90).max(90).optional().describe("Latitude in degrees. Required unless denied or reset is set."),
+      longitude: z.number().min(-180).max(180).optional().describe("Longitude in degrees. Required unless denied or reset is set."),
+      accuracy: z.number().nonnegative().optional().describe("Accuracy radius in meters. Defaults to 10."),
+      altitude: z.number().optional().describe("Altitude in meters."),
+      heading: z.number().min(0).max(360).optional().describe("Heading in degrees clockwise from north."),
+      speed: z.number().nonnegative().optional().describe("Speed in meters per second."),
+      denied: z.boolean().optional().describe("Fail location requests with PERMISSION_DENIED."),
+      reset: z.boolean().optional().describe("Remove the override."),
+    },
+    {
+      title: "Emulate Geolocation",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('set_geolocation', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Geolocation error:', error);
+        return createErrorResponse(`Failed to set geolocation: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "set_locale",
+    "Makes a window see another locale, language list or timezone, without changing the OS settings: sets the default locale of Intl and toLocaleString, navigator.language(s), and the timezone of local Date methods. Use reset to remove the override.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      locale: z.string().optional().describe("BCP 47 locale, e.g. 'fr-FR'."),
+      languages: z.array(z.string()).optional().describe("navigator.languages, most preferred first. Defaults to [locale]."),
+      timezone: z.string().optional().describe("IANA timezone, e.g. 'America/New_York'."),
+      reset: z.boolean().optional().describe("Remove the override."),
+    },
+    {
+      title: "Emulate Locale and Timezone",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('set_locale', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Locale error:', error);
+        return createErrorResponse(`Failed to set locale: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "set_theme",
+    "Switches a window between light and dark: sets the native Tauri window theme and overrides prefers-color-scheme in the page (matchMedia and stylesheet rules). 'system' removes both overrides. Without theme, only reports the window theme and the color scheme the page sees.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      theme: z.enum(["light", "dark", "system"]).optional().describe("Theme to switch to. Omit to query the current theme."),
+      native: z.boolean().optional().describe("Set the native window theme. Defaults to true."),
+      media: z.boolean().optional().describe("Override prefers-color-scheme in the page. Defaults to true."),
+    },
+    {
+      title: "Set or Query Theme",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('set_theme', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Theme error:', error);
+        return createErrorResponse(`Failed to set theme: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "emulate_device",
+    "Emulates a device in a window to exercise responsive layouts: resizes the content area to the device viewport and overrides devicePixelRatio, screen size, user agent and touch support (mouse input also fires touch events). Pick a named profile and/or give explicit values; reset restores the window.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      device: z.enum(["iPhone SE", "iPhone 15", "iPhone 15 Pro Max", "Pixel 8", "Galaxy S23", "iPad Air", "iPad Pro 12.9", "Laptop", "Desktop"]).optional().describe("Device profile to emulate."),
+      width: z.number().int().positive().optional().describe("Viewport width in CSS pixels; overrides the profile."),
+      height: z.number().int().positive().optional().describe("Viewport height in CSS pixels; overrides the profile."),
+      device_scale_factor: z.number().positive().optional().describe("devicePixelRatio; overrides the profile."),
+      touch: z.boolean().optional().describe("Emulate a touch screen; overrides the profile."),
+      user_agent: z.string().optional().describe("navigator.userAgent; overrides the profile."),
+      landscape: z.boolean().optional().describe("Swap width and height."),
+      reset: z.boolean().optional().describe("Remove the emulation and restore the window size."),
+    },
+    {
+      title: "Emulate Device",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('emulate_device', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Device emulation error:', error);
+        return createErrorResponse(`Failed to emulate device: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "set_network_conditions",
+    "Simulates poor or missing connectivity for the fetch and XMLHttpRequest requests of a window: offline mode, added latency, and download/upload bandwidth limits. Tauri IPC is not affected. Use it to check loading states and error handling; reset restores the network.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      preset: z.enum(["offline", "slow-3g", "fast-3g"]).optional().describe("Starting conditions; the other fields override them."),
+      offline: z.boolean().optional().describe("Fail requests like a network error."),
+      latency_ms: z.number().int().nonnegative().optional().describe("Delay added before each request."),
+      download_kbps: z.number().int().positive().optional().describe("Download bandwidth in kilobits per second (fetch only)."),
+      upload_kbps: z.number().int().positive().optional().describe("Upload bandwidth in kilobits per second."),
+      reset: z.boolean().optional().describe("Remove the conditions."),
+    },
+    {
+      title: "Set Network Conditions",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('set_network_conditions', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Network conditions error:', error);
+        return createErrorResponse(`Failed to set network conditions: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "add_mock_route",
+    "Intercepts fetch and XMLHttpRequest requests of a window matching a URL pattern and answers them with a canned status, headers and body, or sends them to another URL, to test the frontend against simulated backend responses. Returns the active routes with their ids.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      id: z.string().optional().describe("Route id; a route with the same id is replaced. Generated when omitted."),
+      url: z.string().describe("Glob ('*' matches anything) or regular expression, matched against the full URL or its path and query, e.g. '/api/users*'."),
+      regex: z.boolean().optional().describe("Treat url as a regular expression."),
+      method: z.string().optional().describe("Only match this HTTP method."),
+      status: z.number().int().min(200).max(599).optional().describe("Response status. Defaults to 200."),
+      headers: z.record(z.string()).optional().describe("Response headers."),
+      body: z.any().optional().describe("Response body; anything but a string is sent as JSON."),
+      rewrite_url: z.string().optional().describe("Send matching requests to this URL instead of answering them."),
+      delay_ms: z.number().int().nonnegative().optional().describe("Delay before the response."),
+    },
+    {
+      title: "Add Mock Route",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('add_mock_route', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Mock route error:', error);
+        return createErrorResponse(`Failed to add mock route: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "remove_mock_route",
+    "Removes a mock route added with add_mock_route, or all routes of a window.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      id: z.string().optional().describe("Id of the route to remove."),
+      all: z.boolean().optional().describe("Remove every route of the window."),
+    },
+    {
+      title: "Remove Mock Route",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async (params) => {
+      try {
+        const result = await socketClient.sendCommand('remove_mock_route', params);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Mock route error:', error);
+        return createErrorResponse(`Failed to remove mock route: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -9,7 +9,7 @@ export function registerExecuteJsTool(server: McpServer) {
     "Executes arbitrary JavaScript code within the context of a specified application window's webview (e.g., a Tauri webview). Returns the result of the last executed statement or a promise resolution. Caution: This tool is destructive and can modify the window's content, state, or trigger unintended actions. Use with careful consideration of the code being executed.",
     {
       code: z.string().describe("Required. The string of JavaScript code to be executed in the target window's webview context. Ensure the code is safe and achieves the intended purpose. Malformed or malicious code can lead to errors or unwanted behavior."),
-      window_label: z.string().default("main").describe("The identifier (e.g., visible title or internal label) of the application window where the JavaScript code will be executed. Defaults to 'main' if not specified."),
+      window_label: z.string().optional().describe("The identifier (e.g., visible title or internal label) of the application window where the JavaScript code will be executed. Defaults to the focused window."),
       timeout_ms: z.number().int().positive().optional().describe("The maximum time in milliseconds to allow for the JavaScript execution. If the script exceeds this timeout, its execution will be terminated, and an error may be returned."),
     },
     {
@@ -29,12 +29,9 @@ export function registerExecuteJsTool(server: McpServer) {
         const params = { code, window_label, timeout_ms };
         logCommandParams('execute_js', params);
         
-        // Use default window label if not provided
-        const effectiveWindowLabel = window_label || 'main';
-        
         const result = await socketClient.sendCommand('execute_js', {
           code,
-          window_label: effectiveWindowLabel,
+          window_label,
           timeout_ms
         });
         
@@ -0,0 +1,55 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+const fieldSchema = z.object({
+  selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).optional().describe("How to find the field. Defaults to 'css'."),
+  selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the field."),
+  value: z.any().optional().describe("Text for inputs and textareas, option value(s) for selects, true/false for checkboxes and radios."),
+  label: z.any().optional().describe("Option label(s) of a select, instead of value."),
+});
+
+export function registerFillFormTool(server: McpServer) {
+  server.tool(
+    "fill_form",
+    "Fills several form fields in one call, in order, with the same semantics as set_element_value: text for inputs and textareas, option values or labels for selects, true/false for checkboxes and radios. input/change events fire for each field so framework state updates. Returns a result per field (final state or error) instead of failing on the first bad selector.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the form. Defaults to the focused window."),
+      fields: z.union([
+        z.record(z.any()),
+        z.array(fieldSchema),
+      ]).describe("Either a map of CSS selector to value, e.g. {\"#email\": \"a@b.c\", \"#remember\": true, \"select[name=country]\": \"be\"}, or an array of fields to use other selector types or select options by label. Fields are filled in the given order."),
+      stop_on_error: z.boolean().optional().describe("Skip the remaining fields after the first failure. By default every field is attempted."),
+      blur: z.boolean().optional().describe("Blur the last focused field at the end, so validation on blur runs."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Fill Form Fields",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, fields, stop_on_error, blur, show_action, timeout_ms }) => {
+      try {
+        const fieldList = Array.isArray(fields)
+          ? fields
+          : Object.entries(fields).map(([selector_value, value]) => ({ selector_type: "css", selector_value, value }));
+        const result = await socketClient.sendCommand('fill_form', {
+          window_label,
+          fields: fieldList,
+          stop_on_error,
+          blur,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Fill form error:', error);
+        return createErrorResponse(`Failed to fill form: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,76 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerFocusTools(server: McpServer) {
+  server.tool(
+    "focus_element",
+    "Gives keyboard focus to an element, so the next simulated keystrokes go to it. Reports whether focus moved (with the reason when the element is disabled or not focusable), the previously focused element and the one focused now.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      prevent_scroll: z.boolean().optional().describe("Do not scroll the element into view when focusing it."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Focus Element",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, prevent_scroll, show_action, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('focus_element', {
+          window_label,
+          selector_type,
+          selector_value,
+          prevent_scroll,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Focus element error:', error);
+        return createErrorResponse(`Failed to focus element: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "blur_element",
+    "Removes keyboard focus from an element, firing blur/focusout so validation on blur runs. Without a selector, blurs whatever element currently has focus. Reports the previously focused element and the one focused now.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).optional().describe("How to find the element. Leave out with selector_value to blur the focused element."),
+      selector_value: z.string().optional().describe("The id, class, tag name, text or CSS selector of the element."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Blur Element",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, show_action, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('blur_element', {
+          window_label,
+          selector_type,
+          selector_value,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Blur element error:', error);
+        return createErrorResponse(`Failed to blur element: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -5,9 +5,17 @@ import { socketClient } from "./client.js";
 export function registerGetDomTool(server: McpServer) {
   server.tool(
     "get_dom",
-    "Retrieves the full HTML Document Object Model (DOM) content from the specified application window as a string. This tool is read-only and provides a snapshot of the window's current HTML structure. Useful for parsing, analysis, or data extraction.",
+    "Retrieves the HTML Document Object Model (DOM) content from the specified application window as a string. This tool is read-only and provides a snapshot of the window's current HTML structure. Useful for parsing, analysis, or data extraction. Full pages can be very large: use the filtering options and max_length to keep the snapshot small.",
     {
-      window_label: z.string().default("main").describe("The identifier (e.g., visible title or internal label) of the application window from which to retrieve the DOM content. Defaults to 'main' if not specified."),
+      window_label: z.string().optional().describe("The identifier (e.g., visible title or internal label) of the application window from which to retrieve the DOM content. Defaults to the focused window."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Snapshot a same-origin iframe instead of the page: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first."),
+      strip_scripts: z.boolean().optional().describe("Drop <script> and <noscript> elements."),
+      strip_styles: z.boolean().optional().describe("Drop <style> elements and stylesheet links."),
+      collapse_whitespace: z.boolean().optional().describe("Collapse runs of whitespace and drop whitespace-only text."),
+      exclude_hidden: z.boolean().optional().describe("Drop elements that are not rendered (display: none, visibility: hidden, hidden attribute)."),
+      max_depth: z.number().int().nonnegative().optional().describe("Replace the children of elements deeper than this with a comment counting them."),
+      max_length: z.number().int().positive().optional().describe("Cut the HTML after this many characters and append a truncation marker."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app (5 seconds unless changed). Raise it for huge pages."),
     },
     {
       title: "Retrieve HTML DOM Content from Application Window",
@@ -16,14 +24,15 @@ export function registerGetDomTool(server: McpServer) {
       idempotentHint: true,
       openWorldHint: false,
     },
-    async ({ window_label }) => {
+    async ({ window_label, timeout_ms, ...options }) => {
       try {
         console.error(`Getting DOM with params: ${JSON.stringify({
-          window_label
+          window_label,
+          timeout_ms,
+          ...options
         })}`);
         
-        // The server expects just a string, not an object
-        const result = await socketClient.sendCommand('get_dom', window_label);
+        const result = await socketClient.sendCommand('get_dom', { window_label, timeout_ms, ...options });
         
         console.error(`Got DOM result type: ${typeof result}, length: ${
           typeof result === 'string' ? result.length : 'unknown'
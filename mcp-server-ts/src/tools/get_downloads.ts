@@ -0,0 +1,38 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerGetDownloadsTool(server: McpServer) {
+  server.tool(
+    "get_downloads",
+    "Gets the files the webviews downloaded: URL, suggested file name, destination path, MIME type, expected and received bytes, size on disk, and status (inProgress, finished, failed, cancelled). Use it to check export features: click the export button, then call this with wait_for_completion. When the app sets a download directory, files go there instead of the user's Downloads (sandboxDir). Downloads are recorded on Linux, and elsewhere only when the app reports them.",
+    {
+      window_label: z.string().optional().describe("Only report the downloads of this window. Defaults to all windows."),
+      since: z.number().int().nonnegative().optional().describe("Only report the downloads after this ID (the cursor of a previous call)."),
+      wait_for_completion: z.boolean().optional().describe("First wait for a download to end: one in progress or the next one started, or the first one after `since` when set. Defaults to false."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for a download, in milliseconds. Defaults to the timeout configured in the app (25 seconds unless changed)."),
+    },
+    {
+      title: "Get Downloads",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, since, wait_for_completion, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('get_downloads', {
+          window_label,
+          since,
+          wait_for_completion,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Downloads error:', error);
+        return createErrorResponse(`Failed to get downloads: ${(error as Error).message}`);
+      }
+    },
+  );
+}
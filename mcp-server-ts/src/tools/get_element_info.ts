@@ -0,0 +1,40 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerGetElementInfoTool(server: McpServer) {
+  server.tool(
+    "get_element_info",
+    "Inspects an element: attributes, dataset, computed styles, bounding rect, scroll offsets and state (visible, disabled, focused, checked, value). Use it to verify visual state, e.g. whether a button is disabled or greyed out, without a screenshot.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      styles: z.array(z.string()).optional().describe("Computed style properties to return, e.g. ['opacity', 'cursor']. Use ['*'] for all of them. Defaults to a common set (display, visibility, opacity, colors, cursor, pointer-events, font)."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Inspect Element Attributes and Styles",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, styles, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('get_element_info', {
+          window_label,
+          selector_type,
+          selector_value,
+          styles,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Element info error:', error);
+        return createErrorResponse(`Failed to get element info: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -13,12 +13,15 @@ ${clickInfo}`;
 export function registerGetElementPositionTool(server: McpServer) {
   server.tool(
     "get_element_position",
-    "Finds an HTML element on the page by ID, class, tag name, or text content, and returns its raw position coordinates for use with mouse_movement. Can optionally click the element.",
+    "Finds an HTML element on the page by ID, class, tag name, text content, CSS selector, XPath, ARIA label, ARIA role or form label, and returns its raw position coordinates for use with mouse_movement. Can optionally click the element. Works on any page, without the app's frontend listening for MCP events.",
     {
-      selector_type: z.enum(["id", "class", "tag", "text"]).describe("The type of selector to use: 'id', 'class', 'tag', or 'text'."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("The type of selector to use: 'id', 'class', 'tag', 'text', 'css', 'xpath', 'aria-label', 'role' (an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]') or 'label' (a form control by its label text)."),
       selector_value: z.string().describe("The value to search for based on the selector type."),
-      window_label: z.string().default("main").describe("The identifier of the application window to search in. Defaults to 'main' if not specified."),
+      window_label: z.string().optional().describe("The identifier of the application window to search in. Defaults to the focused window."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first. The position is still given in the page."),
       should_click: z.boolean().default(false).describe("Whether to click the element once found. Default is false."),
+      scroll_into_view: z.boolean().optional().describe("Scroll the element to the center of the window first when it is not fully visible, so the position is inside the window. Default is false."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app (5 seconds unless changed)."),
     },
     {
       title: "Find Element and Get Position",
@@ -27,13 +30,16 @@ export function registerGetElementPositionTool(server: McpServer) {
       idempotentHint: true,
       openWorldHint: false,
     },
-    async ({ selector_type, selector_value, window_label, should_click }) => {
+    async ({ selector_type, selector_value, window_label, frame_selector, should_click, scroll_into_view, timeout_ms }) => {
       try {
         const payload = {
           selector_type,
+          frame_selector,
           selector_value,
           window_label,
-          should_click
+          should_click,
+          scroll_into_view,
+          timeout_ms
         };
         
         logCommandParams('get_element_position', payload);
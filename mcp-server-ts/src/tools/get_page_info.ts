@@ -0,0 +1,34 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerGetPageInfoTool(server: McpServer) {
+  server.tool(
+    "get_page_info",
+    "Returns basic facts about a window's page: URL, title, document.readyState, history length, visibility, whether it has focus, and the route of its client-side router when the app registered a route provider. Cheaper than get_ui_snapshot when only the location or loading state matters.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Get Page Info",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('get_page_info', {
+          window_label,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Page info error:', error);
+        return createErrorResponse(`Failed to get page info: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,40 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse } from "./response-helpers.js";
+
+export function registerGetPageTextTool(server: McpServer) {
+  server.tool(
+    "get_page_text",
+    "Retrieves the rendered, visible text of an application window (its innerText), or of every element matching a CSS selector. Usually 10-50x smaller than the DOM; prefer it over get_dom when reading what the page says. Set readable to focus on the main content and skip navigation, headers, footers and sidebars.",
+    {
+      window_label: z.string().optional().describe("The label of the window to read. Defaults to the focused window."),
+      selector: z.string().optional().describe("CSS selector. When set, the text of every matching element is returned, separated by blank lines."),
+      readable: z.boolean().optional().describe("Prefer the main content (article, main) and skip navigation, headers, footers and sidebars."),
+      max_length: z.number().int().positive().optional().describe("Cut the text after this many characters and append a truncation marker."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Retrieve Visible Text from Application Window",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector, readable, max_length, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('get_page_text', {
+          window_label,
+          selector,
+          readable,
+          max_length,
+          timeout_ms,
+        });
+        return createSuccessResponse(typeof result?.text === 'string' ? result.text : JSON.stringify(result));
+      } catch (error) {
+        console.error('Page text retrieval error:', error);
+        return createErrorResponse(`Failed to get page text: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,58 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse } from "./response-helpers.js";
+
+export function registerGetUiSnapshotTool(server: McpServer) {
+  server.tool(
+    "get_ui_snapshot",
+    "Observes an application window in one round trip: its URL and title, the focused element, a compressed screenshot of the viewport, and an outline of the visible landmarks, headings and controls with their role, accessible name and state (focused, disabled, checked, value...), indented by nesting. Prefer it over separate get_dom, get_page_text and execute_js calls to see where the app is and what can be done next.",
+    {
+      window_label: z.string().optional().describe("The label of the window to observe. Defaults to the focused window."),
+      max_nodes: z.number().int().positive().optional().describe("Most outline lines to return. Defaults to 500."),
+      screenshot: z.boolean().optional().describe("Include a JPEG screenshot of the viewport. Defaults to true. Only available on Linux for now."),
+      screenshot_max_width: z.number().int().positive().optional().describe("Widest screenshot in pixels, larger viewports are scaled down. Defaults to 1024."),
+      screenshot_quality: z.number().int().min(1).max(100).optional().describe("JPEG quality of the screenshot, from 1 to 100. Defaults to 60."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Observe Application Window",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, max_nodes, screenshot, screenshot_max_width, screenshot_quality, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('get_ui_snapshot', {
+          window_label,
+          max_nodes,
+          screenshot,
+          screenshot_max_width,
+          screenshot_quality,
+          timeout_ms,
+        });
+        const focused = result.focused
+          ? `${result.focused.role || result.focused.tag}${result.focused.name ? ` "${result.focused.name}"` : ''} (${result.focused.selector})`
+          : 'nothing';
+        const truncated = result.truncated ? `\n[truncated: showing ${max_nodes ?? 500} of ${result.nodeCount} elements]` : '';
+        const screenshotError = result.screenshotError ? `\nScreenshot unavailable: ${result.screenshotError}` : '';
+        return {
+          isError: false,
+          content: [
+            {
+              type: "text" as const,
+              text: `URL: ${result.url}\nTitle: ${result.title}\nFocused: ${focused}${screenshotError}\n\n${result.outline}${truncated}`,
+            },
+            ...(result.screenshot?.data
+              ? [{ type: "image" as const, data: result.screenshot.data as string, mimeType: result.screenshot.mimeType as string }]
+              : []),
+          ],
+        };
+      } catch (error) {
+        console.error('UI snapshot error:', error);
+        return createErrorResponse(`Failed to get UI snapshot: ${(error as Error).message}`);
+      }
+    },
+  );
+}
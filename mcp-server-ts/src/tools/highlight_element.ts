@@ -0,0 +1,46 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerHighlightElementTool(server: McpServer) {
+  server.tool(
+    "highlight_element",
+    "Outlines the element a selector finds, or every matching element, for a few seconds with an optional label, so a person watching the app can check that the selector resolves to what the agent thinks. The outlines follow their elements as the page scrolls and do not catch clicks. Returns how many elements matched and the tag, id, text and rect of the outlined ones.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first."),
+      all: z.boolean().optional().describe("Outline every matching element instead of the first one (css, class, tag and xpath selectors), up to 100."),
+      label: z.string().optional().describe("Text shown above each outline, numbered when several elements match."),
+      duration_ms: z.number().int().positive().optional().describe("How long the outlines stay, in milliseconds. Defaults to 2000."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Highlight Element",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, frame_selector, all, label, duration_ms, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('highlight_element', {
+          window_label,
+          selector_type,
+          selector_value,
+          frame_selector,
+          all,
+          label,
+          duration_ms,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Highlight element error:', error);
+        return createErrorResponse(`Failed to highlight element: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -1,27 +1,173 @@
 import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
 import { registerExecuteJsTool } from "./execute_js.js";
 import { registerGetDomTool } from "./get_dom.js";
+import { registerGetPageTextTool } from "./get_page_text.js";
 import { registerManageWindowTool } from "./manage_window.js";
 import { registerManageLocalStorageTool } from "./manage_local_storage.js";
 import { registerTextInputTool } from "./text_input.js";
 import { registerMouseMovementTool } from "./mouse_movement.js";
 import { registerGetElementPositionTool } from "./get_element_position.js";
+import { registerGetElementInfoTool } from "./get_element_info.js";
+import { registerInspectElementTool } from "./inspect_element.js";
+import { registerScrollIntoViewTool } from "./scroll_into_view.js";
+import { registerHighlightElementTool } from "./highlight_element.js";
+import { registerControlMediaTool } from "./control_media.js";
+import { registerClickElementTool } from "./click_element.js";
+import { registerSetElementValueTool } from "./set_element_value.js";
+import { registerSetFileInputTool } from "./set_file_input.js";
+import { registerFillFormTool } from "./fill_form.js";
+import { registerFocusTools } from "./focus_element.js";
+import { registerAssertTools } from "./assert.js";
+import { registerRunScriptTool } from "./run_script.js";
+import { registerInteractionRecordingTools } from "./interaction_recording.js";
+import { registerEmulationTools } from "./emulation.js";
+import { registerDialogTools } from "./dialogs.js";
+import { registerPermissionTools } from "./permissions.js";
 import { registerSendTextToElementTool } from "./send_text_to_element.js";
+import { registerServerModeTool } from "./server_mode.js";
+import { registerClientConfigTool } from "./client_config.js";
+import { registerPingTool } from "./ping.js";
+import { registerArtifactTools } from "./artifacts.js";
+import { registerAuditLogTool } from "./audit.js";
+import { registerPollEventsTool } from "./poll_events.js";
+import { registerRecoverSessionTool } from "./recover_session.js";
+import { registerListClientsTool } from "./list_clients.js";
+import { registerStatusTool } from "./status.js";
+import { registerProcessStatsTool } from "./process_stats.js";
+import { registerJsErrorsTool } from "./js_errors.js";
+import { registerAppLogsTool } from "./app_logs.js";
+import { registerAppStateTool } from "./app_state.js";
+import { registerStoreTool } from "./store.js";
+import { registerQueryDbTool } from "./query_db.js";
+import { registerAppFilesTools } from "./app_files.js";
+import { registerDeepLinkTool } from "./deep_link.js";
+import { registerTrayTools } from "./tray.js";
+import { registerMenuTools } from "./menu.js";
+import { registerCaptureCanvasTool } from "./capture_canvas.js";
+import { registerPrintToPdfTool } from "./print_to_pdf.js";
+import { registerGetUiSnapshotTool } from "./get_ui_snapshot.js";
+import { registerGetPageInfoTool } from "./get_page_info.js";
+import { registerWaitForNavigationTool } from "./wait_for_navigation.js";
+import { registerGetDownloadsTool } from "./get_downloads.js";
+import { registerSessionPauseTools } from "./session_pause.js";
+import { registerInputConsentTool } from "./input_consent.js";
+import { registerActionOverlayTool } from "./action_overlay.js";
+import { registerTraceTools } from "./trace.js";
+import { registerConvertCoordinatesTool } from "./convert_coordinates.js";
+import { registerKeyPressTool } from "./key_press.js";
+import { registerTouchTool } from "./touch.js";
+import { registerDescribeToolsTool } from "./describe_tools.js";
 import { socketClient } from "./client.js";
 
 // Re-export the socket client for direct use
 export { socketClient } from "./client.js";
 
+// Tool availability as reported by the app's describe_tools command
+interface ToolDescription {
+  name: string;
+  state: 'available' | 'degraded' | 'unavailable';
+  reason?: string | null;
+}
+
+// Fetch the live tool availability from the app, or null if the app does not support it
+async function fetchToolDescriptions(): Promise<ToolDescription[] | null> {
+  try {
+    const result = await socketClient.sendCommand('describe_tools', {});
+    return result.tools as ToolDescription[];
+  } catch (error) {
+    console.error('Could not fetch tool capabilities, registering all tools:', error);
+    return null;
+  }
+}
+
+// Wrap the server so unavailable tools are hidden and degraded ones are annotated
+function capabilityAwareServer(server: McpServer, tools: ToolDescription[] | null): McpServer {
+  if (!tools) {
+    return server;
+  }
+
+  const byName = new Map(tools.map((tool) => [tool.name, tool]));
+  return new Proxy(server, {
+    get(target, prop, receiver) {
+      if (prop !== 'tool') {
+        return Reflect.get(target, prop, receiver);
+      }
+      return (name: string, description: string, ...rest: any[]) => {
+        const tool = byName.get(name);
+        if (!tool || tool.state === 'unavailable') {
+          console.error(`Hiding tool ${name}: unavailable on the target application`);
+          return;
+        }
+        if (tool.state === 'degraded') {
+          description = `${description} (Degraded on this platform: ${tool.reason})`;
+        }
+        return (target.tool as any)(name, description, ...rest);
+      };
+    },
+  });
+}
+
 // Function to register all tools with a server instance
-export function registerAllTools(server: McpServer) {
+export async function registerAllTools(mcpServer: McpServer) {
+  const server = capabilityAwareServer(mcpServer, await fetchToolDescriptions());
   registerExecuteJsTool(server);
   registerGetDomTool(server);
+  registerGetPageTextTool(server);
   registerManageWindowTool(server);
   registerManageLocalStorageTool(server);
   registerTextInputTool(server);
   registerMouseMovementTool(server);
   registerGetElementPositionTool(server);
+  registerGetElementInfoTool(server);
+  registerInspectElementTool(server);
+  registerScrollIntoViewTool(server);
+  registerHighlightElementTool(server);
+  registerControlMediaTool(server);
+  registerClickElementTool(server);
+  registerSetElementValueTool(server);
+  registerSetFileInputTool(server);
+  registerFillFormTool(server);
+  registerFocusTools(server);
+  registerAssertTools(server);
+  registerRunScriptTool(server);
+  registerInteractionRecordingTools(server);
+  registerEmulationTools(server);
+  registerDialogTools(server);
+  registerPermissionTools(server);
   registerSendTextToElementTool(server);
+  registerServerModeTool(server);
+  registerArtifactTools(server);
+  registerAuditLogTool(server);
+  registerDescribeToolsTool(server);
+  registerPollEventsTool(server);
+  registerRecoverSessionTool(server);
+  registerListClientsTool(server);
+  registerStatusTool(server);
+  registerProcessStatsTool(server);
+  registerJsErrorsTool(server);
+  registerAppLogsTool(server);
+  registerAppStateTool(server);
+  registerStoreTool(server);
+  registerQueryDbTool(server);
+  registerAppFilesTools(server);
+  registerDeepLinkTool(server);
+  registerTrayTools(server);
+  registerMenuTools(server);
+  registerCaptureCanvasTool(server);
+  registerPrintToPdfTool(server);
+  registerGetUiSnapshotTool(server);
+  registerGetPageInfoTool(server);
+  registerWaitForNavigationTool(server);
+  registerGetDownloadsTool(server);
+  registerSessionPauseTools(server);
+  registerInputConsentTool(server);
+  registerActionOverlayTool(server);
+  registerTraceTools(server);
+  registerConvertCoordinatesTool(server);
+  registerKeyPressTool(server);
+  registerTouchTool(server);
+  registerClientConfigTool(server);
+  registerPingTool(server);
 }
 
 // Function to initialize socket connection (can be awaited before registering tools)
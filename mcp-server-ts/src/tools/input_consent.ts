@@ -0,0 +1,27 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerInputConsentTool(server: McpServer) {
+  server.tool(
+    "get_input_consent",
+    "Reports whether the app requires the user's consent before simulate_text_input, simulate_mouse_movement and OS-level click_element drive the real mouse and keyboard, and where that consent stands: notAsked, pending (the prompt is shown and input waits for the user), granted or denied. When denied, use DOM-level tools such as click_element with the dom strategy or send_text_to_element instead.",
+    {},
+    {
+      title: "Get Input Consent",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('get_input_consent', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Input consent error:', error);
+        return createErrorResponse(`Failed to get input consent: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,60 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, formatResultAsText } from "./response-helpers.js";
+
+interface ElementScreenshot {
+  mimeType: string;
+  data: string;
+  [key: string]: unknown;
+}
+
+export function registerInspectElementTool(server: McpServer) {
+  server.tool(
+    "inspect_element",
+    "Inspects one element in a single call: a screenshot cropped to it, its bounding box, text, accessible role and name, attributes, value, and interactability flags (visible, inViewport, enabled, editable, focusable, focused, checked, obscured by another element, receivesPointerEvents, clickable). Use it to check why a click or typing did not work. Elements cut off by the viewport are scrolled into view for the screenshot. Screenshots are only taken on Linux for now; elsewhere screenshotError says why.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first. The bounding box is still given in the window's viewport."),
+      screenshot: z.boolean().optional().describe("Include a PNG of the element. Defaults to true."),
+      padding: z.number().min(0).optional().describe("CSS pixels of surroundings to keep on each side of the element in the screenshot. Defaults to 0."),
+      max_text_length: z.number().int().positive().optional().describe("Longest text returned, in characters. Defaults to 2000."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Inspect Element with Screenshot",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, frame_selector, screenshot, padding, max_text_length, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('inspect_element', {
+          window_label,
+          selector_type,
+          selector_value,
+          frame_selector,
+          screenshot,
+          padding,
+          max_text_length,
+          timeout_ms,
+        }) as { screenshot?: ElementScreenshot | null };
+        const { screenshot: image, ...summary } = result ?? {};
+        const { data: _data, ...imageInfo } = image ?? {};
+        return {
+          isError: false,
+          content: [
+            { type: "text" as const, text: formatResultAsText(image ? { ...summary, screenshot: imageInfo } : summary) },
+            ...(image?.data ? [{ type: "image" as const, data: image.data, mimeType: image.mimeType }] : []),
+          ],
+        };
+      } catch (error) {
+        console.error('Element inspection error:', error);
+        return createErrorResponse(`Failed to inspect element: ${(error as Error).message}`);
+      }
+    },
+  );
+}
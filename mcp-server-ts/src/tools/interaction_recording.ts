@@ -0,0 +1,57 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerInteractionRecordingTools(server: McpServer) {
+  server.tool(
+    "start_interaction_recording",
+    "Starts recording what a person does in the app (clicks, field changes, Enter/Escape and shortcuts, navigations) so the flow can be replayed later with run_script. Ask the user to perform the flow, then call stop_interaction_recording.",
+    {
+      window_label: z.string().optional().describe("Only record this window. Defaults to all windows."),
+    },
+    {
+      title: "Start Recording User Interactions",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ window_label }) => {
+      try {
+        const result = await socketClient.sendCommand('start_interaction_recording', {
+          windowLabel: window_label,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Interaction recording error:', error);
+        return createErrorResponse(`Failed to start interaction recording: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "stop_interaction_recording",
+    "Stops the interaction recording and returns the recorded flow as a run_script payload ({steps, onError}), ready to be replayed or edited, e.g. to add assertions.",
+    {
+      save: z.boolean().optional().describe("Also store the script as a 'script' session artifact."),
+      name: z.string().optional().describe("Name of the stored artifact. Defaults to 'interaction-script.json'."),
+    },
+    {
+      title: "Stop Recording User Interactions",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ save, name }) => {
+      try {
+        const result = await socketClient.sendCommand('stop_interaction_recording', { save, name });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Interaction recording error:', error);
+        return createErrorResponse(`Failed to stop interaction recording: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,32 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerJsErrorsTool(server: McpServer) {
+  server.tool(
+    "get_js_errors",
+    "Gets the uncaught JavaScript errors and unhandled promise rejections of the webviews, with message, source location and stack trace. Pass the returned cursor as `since` to only get the errors that happened afterwards, e.g. after an interaction.",
+    {
+      window_label: z.string().optional().describe("Only report the errors of this window. Defaults to all windows."),
+      since: z.number().int().nonnegative().optional().describe("Only report the errors after this ID (the cursor of a previous call)."),
+      clear: z.boolean().optional().describe("Forget the stored errors once reported. Defaults to false."),
+    },
+    {
+      title: "Get JavaScript Errors",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ window_label, since, clear }) => {
+      try {
+        const result = await socketClient.sendCommand('get_js_errors', { window_label, since, clear });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('JS errors error:', error);
+        return createErrorResponse(`Failed to get JavaScript errors: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,42 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse } from "./response-helpers.js";
+
+export function registerKeyPressTool(server: McpServer) {
+  server.tool(
+    "simulate_key_press",
+    "Presses keys together and releases them in reverse, for shortcuts and navigation keys such as ctrl+s, escape or arrow keys. Keys are given either by name, which the keyboard layout maps to keys, or by W3C physical key code, which presses the same keys whatever the layout: ['ControlLeft', 'KeyS'] is Ctrl+S on a US keyboard and the key where S is on a US keyboard on any other. Use simulate_text_input to type text.",
+    {
+      keys: z.array(z.string()).optional().describe("Keys by name: modifiers 'ctrl', 'shift', 'alt', 'meta' (cmd), 'enter', 'tab', 'escape', 'backspace', 'delete', 'space', arrow keys 'up'/'down'/'left'/'right', 'home', 'end', 'pageup', 'pagedown', 'f1' to 'f12', or a single character."),
+      codes: z.array(z.string()).optional().describe("Physical keys by W3C KeyboardEvent.code value, used instead of keys: 'KeyA' to 'KeyZ', 'Digit0' to 'Digit9', 'ControlLeft', 'ShiftRight', 'AltLeft', 'MetaLeft', 'Enter', 'Escape', 'ArrowUp', 'F5', 'Minus', 'BracketLeft', 'IntlBackslash'..."),
+      input_mode: z.enum(["os", "dom"]).optional().describe("'os' (default) presses with the OS keyboard in whatever window has the focus. 'dom' dispatches synthetic keydown and keyup events to the focused element of the window's page, with the key values of a US layout."),
+      window_label: z.string().optional().describe("The window pressed into: its page in DOM mode, the window focused with focus_window in OS mode. Defaults to the focused window."),
+      focus_window: z.boolean().optional().describe("In OS mode, focus the window before pressing; otherwise the keys go to whatever app has the focus. Default is false."),
+      show_action: z.boolean().optional().describe("Outline the focused element in the window before pressing, for people watching. Defaults to the setting of set_action_overlay."),
+    },
+    {
+      title: "Simulate Key Press",
+      readOnlyHint: false,
+      destructiveHint: true,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ keys, codes, input_mode, window_label, focus_window, show_action }, extra) => {
+      try {
+        const result = await socketClient.sendCommand('simulate_key_press', {
+          keys,
+          codes,
+          input_mode,
+          window_label,
+          focus_window,
+          show_action
+        }, extra.signal);
+        return createSuccessResponse(`Pressed ${(codes ?? keys ?? []).join('+')} in ${result?.durationMs ?? 0}ms`);
+      } catch (error) {
+        console.error('Key press simulation error:', error);
+        return createErrorResponse(`Failed to simulate key press: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,27 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerListClientsTool(server: McpServer) {
+  server.tool(
+    "list_clients",
+    "Lists the clients currently connected to the Tauri application's socket server (including this one): transport, peer address for TCP, connection time, number of requests and last command. Useful when several MCP clients or debugging tools share the same app.",
+    {},
+    {
+      title: "List Connected Socket Clients",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('list_clients', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Client listing error:', error);
+        return createErrorResponse(`Failed to list clients: ${(error as Error).message}`);
+      }
+    },
+  );
+}
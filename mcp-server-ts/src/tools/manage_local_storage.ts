@@ -60,21 +60,18 @@ export function registerManageLocalStorageTool(server: McpServer) {
           };
         }
         
-        // Use default window label if not provided
-        const effectiveWindowLabel = window_label || 'main';
-        
         console.error(`Managing localStorage with params: ${JSON.stringify({
           action,
           key,
           value: value?.substring(0, 50) + (value && value.length > 50 ? '...' : ''),
-          window_label: effectiveWindowLabel
+          window_label
         })}`);
         
         const result = await socketClient.sendCommand('manage_local_storage', {
           action,
           key,
           value,
-          window_label: effectiveWindowLabel
+          window_label
         });
         
         console.error(`Got localStorage result type: ${typeof result}`);
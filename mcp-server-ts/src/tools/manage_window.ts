@@ -8,7 +8,7 @@ export function registerManageWindowTool(server: McpServer) {
     "Manages the state and geometry of Tauri application windows. Allows operations such as focusing, minimizing, maximizing, closing, showing/hiding, positioning, resizing, centering, and toggling fullscreen. Some operations like 'close' are destructive.",
     {
       operation: z.enum(["focus", "minimize", "maximize", "unmaximize", "close", "show", "hide", "setPosition", "setSize", "center", "toggleFullscreen"]).describe("Required. The window management operation to perform. Valid values are: focus, minimize, maximize, unmaximize, close, show, hide, setPosition, setSize, center, toggleFullscreen."),
-      window_label: z.string().default("main").describe("The identifier (e.g., visible title or internal label) of the application window to control. Defaults to 'main' if not specified."),
+      window_label: z.string().optional().describe("The identifier (e.g., visible title or internal label) of the application window to control. Defaults to the focused window."),
       x: z.number().int().optional().describe("The X-coordinate (in screen pixels) for the window's top-left corner. Required and used only for the 'setPosition' operation."),
       y: z.number().int().optional().describe("The Y-coordinate (in screen pixels) for the window's top-left corner. Required and used only for the 'setPosition' operation."),
       width: z.number().int().positive().optional().describe("The desired width of the window in pixels. Required and used only for the 'setSize' operation."),
@@ -32,7 +32,7 @@ export function registerManageWindowTool(server: McpServer) {
           height
         })}`);
         
-        await socketClient.sendCommand('manage_window', {
+        const result = await socketClient.sendCommand('manage_window', {
           operation,
           window_label,
           x,
@@ -45,7 +45,9 @@ export function registerManageWindowTool(server: McpServer) {
           content: [
             {
               type: "text",
-              text: `Window operation '${operation}' completed successfully`,
+              text: result?.warning
+                ? `Window operation '${operation}' skipped: ${result.warning}`
+                : `Window operation '${operation}' completed successfully`,
             },
           ],
         };
@@ -0,0 +1,56 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerMenuTools(server: McpServer) {
+  server.tool(
+    "list_menu",
+    "Lists the items of the Tauri application's menu bar: the app menu and the menus of the windows, with each item's id, text, kind, enabled and checked state, and submenus. Use the ids or texts with click_menu_item.",
+    {
+      window_label: z.string().optional().describe("Only list the menu of this window. Defaults to the app menu and every window."),
+    },
+    {
+      title: "List Menu",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label }) => {
+      try {
+        const result = await socketClient.sendCommand('list_menu', { window_label });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('List menu error:', error);
+        return createErrorResponse(`Failed to list menu: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "click_menu_item",
+    "Selects a menu bar item as if the user clicked it, by id or by path (e.g. 'File > Export > PDF'), and returns the menu event delivered to the app. Check items are toggled; disabled items are refused.",
+    {
+      id: z.string().optional().describe("ID of the menu item, as returned by list_menu."),
+      path: z.string().optional().describe("Texts leading to the item separated by '>', e.g. 'File > Export > PDF'. Case-insensitive."),
+      window_label: z.string().optional().describe("Look in the menu of this window. Defaults to the app menu, then every window."),
+    },
+    {
+      title: "Click Menu Item",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ id, path, window_label }) => {
+      try {
+        const result = await socketClient.sendCommand('click_menu_item', { id, path, window_label });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Click menu item error:', error);
+        return createErrorResponse(`Failed to click menu item: ${(error as Error).message}`);
+      }
+    },
+  );
+}
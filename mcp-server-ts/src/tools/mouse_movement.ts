@@ -12,6 +12,9 @@ export function registerMouseMovementTool(server: McpServer) {
       relative: z.boolean().optional().describe("If true, the x and y coordinates are treated as offsets relative to the mouse cursor's current position. If false (default), x and y are absolute screen coordinates."),
       click: z.boolean().optional().describe("If true, performs a mouse click at the target coordinates after movement. Default is false."),
       button: z.enum(["left", "right", "middle"]).optional().describe("Specifies which mouse button to click. Options are 'left', 'right', or 'middle'. Default is 'left'."),
+      input_mode: z.enum(["os", "dom"]).optional().describe("'os' (default) moves the real OS mouse. 'dom' moves a synthetic pointer in the window's page and dispatches pointer and mouse events there, which works with the window in the background or on CI without a display; relative moves are then in CSS pixels and :hover styles do not apply."),
+      window_label: z.string().optional().describe("The window the positions are taken from. Defaults to the focused window."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and move it to the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
     },
     {
       title: "Simulate Mouse Cursor Movement",
@@ -20,7 +23,7 @@ export function registerMouseMovementTool(server: McpServer) {
       idempotentHint: false,
       openWorldHint: false,
     },
-    async ({ x, y, relative, click, button }) => {
+    async ({ x, y, relative, click, button, input_mode, window_label, show_action }) => {
       try {
         // X and Y are required by the Zod schema, but let's validate they're numbers
         if (typeof x !== 'number' || typeof y !== 'number') {
@@ -48,7 +51,10 @@ export function registerMouseMovementTool(server: McpServer) {
           y,
           relative,
           click,
-          button
+          button,
+          input_mode,
+          window_label,
+          show_action
         });
         
         const actionText = click 
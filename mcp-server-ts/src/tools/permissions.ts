@@ -0,0 +1,36 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+const decision = z.enum(["grant", "deny"]);
+
+export function registerPermissionTools(server: McpServer) {
+  server.tool(
+    "set_permission_policy",
+    "Pre-answers the permission requests of the webviews (camera and microphone for getUserMedia, geolocation, notifications, clipboard-read, clipboard-write), so flows asking for them never stall on a prompt, and returns the permissions the pages requested so far with their outcome (granted, denied, or prompted when left to the webview). Denied requests fail at once with a NotAllowedError; granted clipboard reads get the system clipboard. Call it without arguments to only get the requests.",
+    {
+      permissions: z.record(z.enum(["camera", "microphone", "geolocation", "notifications", "clipboard-read", "clipboard-write"]), decision).optional().describe("Decision by permission, e.g. { \"camera\": \"grant\", \"clipboard-read\": \"deny\" }. Replaces the current policy."),
+      default: decision.optional().describe("Decision for the permissions not listed. Defaults to letting the webview decide."),
+      clear: z.boolean().optional().describe("Remove the policy so the webview decides again."),
+      clear_requests: z.boolean().optional().describe("Forget the recorded requests once reported. Defaults to false."),
+    },
+    {
+      title: "Set Permission Policy",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ permissions, default: defaultDecision, clear, clear_requests }) => {
+      try {
+        const policy = permissions || defaultDecision ? { permissions, default: defaultDecision } : undefined;
+        const result = await socketClient.sendCommand('set_permission_policy', { policy, clear, clear_requests });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Permission policy error:', error);
+        return createErrorResponse(`Failed to set permission policy: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,32 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerPingTool(server: McpServer) {
+  server.tool(
+    "ping",
+    "Checks the connection to the Tauri app and which app answered: returns the app name, bundle identifier, app version, process id, protocol, Tauri and plugin versions, the app's clock, the payload sent, and the measured round-trip time in milliseconds.",
+    {
+      payload: z.any().optional().describe("Any JSON value, echoed back by the app."),
+    },
+    {
+      title: "Ping Tauri App",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ payload }) => {
+      try {
+        const sentAt = Date.now();
+        const result = await socketClient.sendCommand('ping', { payload });
+        const roundTripMs = Date.now() - sentAt;
+        return createSuccessResponse(formatResultAsText({ ...result, roundTripMs }));
+      } catch (error) {
+        console.error('Ping error:', error);
+        return createErrorResponse(`Failed to ping the app: ${(error as Error).message}`);
+      }
+    },
+  );
+}
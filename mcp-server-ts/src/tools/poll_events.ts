@@ -0,0 +1,41 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerPollEventsTool(server: McpServer) {
+  server.tool(
+    "poll_events",
+    "Long-polls events emitted by the Tauri application (client connections, window creation/destruction/focus, page loads). Returns buffered events after the given cursor, waiting up to timeoutMs for new ones. Pass the returned cursor to the next call, or reuse the same sessionId and let the server remember it. missedEvents is true when older events were dropped from the buffer.",
+    {
+      sessionId: z.string().optional().describe("Session identifier. The server stores the cursor of each session so it can be omitted on later calls."),
+      cursor: z.number().int().nonnegative().optional().describe("Return events with a sequence number greater than this. Overrides the session's stored cursor."),
+      timeoutMs: z.number().int().nonnegative().max(25000).optional().describe("How long to wait for new events when none are buffered. Defaults to 20000."),
+      maxEvents: z.number().int().positive().optional().describe("Maximum number of events to return. Defaults to 100."),
+      kinds: z.array(z.string()).optional().describe("Only return these event kinds, e.g. ['pageLoad', 'windowCreated']."),
+    },
+    {
+      title: "Poll Application Events",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ sessionId, cursor, timeoutMs, maxEvents, kinds }) => {
+      try {
+        const result = await socketClient.sendCommand('poll_events', {
+          sessionId,
+          cursor,
+          // Stay below the socket client's 30 second request timeout
+          timeoutMs: timeoutMs ?? 20000,
+          maxEvents,
+          kinds,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Event polling error:', error);
+        return createErrorResponse(`Failed to poll events: ${(error as Error).message}`);
+      }
+    },
+  );
+}
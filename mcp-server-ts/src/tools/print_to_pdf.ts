@@ -0,0 +1,58 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, formatResultAsText } from "./response-helpers.js";
+
+interface PrintToPdfResult {
+  path: string;
+  size: number;
+  data: string | null;
+}
+
+export function registerPrintToPdfTool(server: McpServer) {
+  server.tool(
+    "print_to_pdf",
+    "Prints the page shown in a window to a PDF file, as the app's print stylesheet lays it out, to check report or invoice layouts. Returns the path and size of the file, and the PDF itself when include_data is set. Only supported on Linux for now.",
+    {
+      window_label: z.string().optional().describe("The label of the window to print. Defaults to the focused window."),
+      path: z.string().optional().describe("Where to write the PDF. Defaults to a new file in the temp directory."),
+      landscape: z.boolean().optional().describe("Print in landscape orientation. Defaults to false."),
+      include_data: z.boolean().optional().describe("Also return the PDF, base64 encoded. Defaults to false."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for printing to finish, in milliseconds. Defaults to 30 seconds."),
+    },
+    {
+      title: "Print Page to PDF",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, path, landscape, include_data, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('print_to_pdf', {
+          window_label,
+          path,
+          landscape,
+          include_data,
+          timeout_ms,
+        }) as PrintToPdfResult;
+        const { data, ...summary } = result;
+        return {
+          isError: false,
+          content: [
+            { type: "text" as const, text: formatResultAsText(summary) },
+            ...(data
+              ? [{
+                  type: "resource" as const,
+                  resource: { uri: `file://${result.path}`, mimeType: "application/pdf", blob: data },
+                }]
+              : []),
+          ],
+        };
+      } catch (error) {
+        console.error('Print to PDF error:', error);
+        return createErrorResponse(`Failed to print to PDF: ${(error as Error).message}`);
+      }
+    },
+  );
+}
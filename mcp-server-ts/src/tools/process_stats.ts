@@ -0,0 +1,30 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerProcessStatsTool(server: McpServer) {
+  server.tool(
+    "get_process_stats",
+    "Reports the resource usage of the Tauri application process and its child processes (e.g. webview processes): CPU %, resident memory, open file descriptors or handles, and thread count, with totals. CPU is measured since the previous call. Call it periodically during long sessions to detect leaks.",
+    {
+      include_children: z.boolean().optional().describe("Also report the processes started by the app. Defaults to true."),
+    },
+    {
+      title: "Get Process Stats",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ include_children }) => {
+      try {
+        const result = await socketClient.sendCommand('get_process_stats', { include_children });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Process stats error:', error);
+        return createErrorResponse(`Failed to get process stats: ${(error as Error).message}`);
+      }
+    },
+  );
+}
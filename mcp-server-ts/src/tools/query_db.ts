@@ -0,0 +1,34 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerQueryDbTool(server: McpServer) {
+  server.tool(
+    "query_db",
+    "Runs SQL on a SQLite database of the Tauri application, e.g. to verify that a UI action persisted rows. Only databases configured by the app can be queried, and they are read-only unless the app made them writable. Rows are returned as objects keyed by column name.",
+    {
+      database: z.string().describe("Name of the database, as configured by the app."),
+      query: z.string().describe("SQL to run, with ? placeholders for params."),
+      params: z.array(z.union([z.string(), z.number(), z.boolean(), z.null()])).optional().describe("Values bound to the ? placeholders, in order."),
+      max_rows: z.number().int().positive().optional().describe("Maximum number of rows returned. Defaults to 1000."),
+      timeout_ms: z.number().int().positive().optional().describe("Time the query may take. Defaults to the server's timeout."),
+    },
+    {
+      title: "Query Database",
+      readOnlyHint: false,
+      destructiveHint: true,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ database, query, params, max_rows, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('query_db', { database, query, params, max_rows, timeout_ms });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Database query error:', error);
+        return createErrorResponse(`Failed to query database: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,27 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerRecoverSessionTool(server: McpServer) {
+  server.tool(
+    "recover_last_session",
+    "Returns the recording of the previous run of the Tauri application: every command executed with its payload, outcome and duration. The recording is journaled to disk as commands run, so it is available even if the app crashed; 'crashed' tells whether the previous session ended abruptly. Use it after a restart to see what led to a crash.",
+    {},
+    {
+      title: "Recover Previous Session Recording",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('recover_last_session', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Session recovery error:', error);
+        return createErrorResponse(`Failed to recover last session: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,48 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+const stepSchema = z.object({
+  command: z.string().describe("A socket command such as 'click_element', 'send_text_to_element', 'fill_form', 'assert_text_contains', or 'wait'."),
+  payload: z.record(z.any()).optional().describe("The command's payload, as for the corresponding tool. For 'wait': {ms} to sleep, {window_label, selector_type, selector_value, timeout_ms} to wait until an element is visible, or {window_label, pattern, timeout_ms} to wait until the URL contains pattern (5 s by default)."),
+  name: z.string().optional().describe("Label shown in the report."),
+  on_error: z.enum(["stop", "continue"]).optional().describe("Overrides the script's on_error for this step."),
+  retries: z.number().int().min(0).optional().describe("Extra attempts before the step counts as failed."),
+});
+
+export function registerRunScriptTool(server: McpServer) {
+  server.tool(
+    "run_script",
+    "Runs an ordered list of commands inside the app in a single call, e.g. a login flow: fill_form, click_element, wait for an element, assert_text_contains. Failed assertions count as failed steps. Returns a step-by-step report with status, attempts, duration, data and error of each step, and whether the whole script passed. Much faster than one tool call per step.",
+    {
+      steps: z.array(stepSchema).min(1).describe("The steps to run, in order."),
+      on_error: z.enum(["stop", "continue"]).optional().describe("Whether to skip the remaining steps after a failure ('stop', the default) or keep going."),
+    },
+    {
+      title: "Run Scripted Flow",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ steps, on_error }) => {
+      try {
+        const result = await socketClient.sendCommand('run_script', {
+          steps: steps.map((step) => ({
+            command: step.command,
+            payload: step.payload ?? {},
+            name: step.name,
+            onError: step.on_error,
+            retries: step.retries,
+          })),
+          onError: on_error,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Run script error:', error);
+        return createErrorResponse(`Failed to run script: ${(error as Error).message}`);
+      }
+    },
+  );
+}
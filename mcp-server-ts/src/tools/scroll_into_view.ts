@@ -0,0 +1,48 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerScrollIntoViewTool(server: McpServer) {
+  server.tool(
+    "scroll_into_view",
+    "Scrolls an element into view unless it is already fully visible, and waits for the scrolling to settle. Use it before get_element_position and mouse_movement, which otherwise get coordinates outside the window for off-screen elements. Returns whether it scrolled, the element's rect in the window's viewport and whether it is now fully visible.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the element. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the element. 'css' takes any CSS selector and 'xpath' any XPath expression; the first match is used. 'aria-label' matches the accessible label. 'role' takes an ARIA role, optionally with its accessible name, e.g. 'button[name=\"Save\"]', and 'label' finds a form control by its label text. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the element."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first. The rect is still given in the window's viewport."),
+      block: z.enum(["start", "center", "end", "nearest"]).optional().describe("Vertical alignment of the element. Defaults to 'center'."),
+      inline: z.enum(["start", "center", "end", "nearest"]).optional().describe("Horizontal alignment of the element. Defaults to 'nearest'."),
+      behavior: z.enum(["instant", "smooth"]).optional().describe("Jump or scroll smoothly. Defaults to 'instant'."),
+      force: z.boolean().optional().describe("Scroll even when the element is already fully visible, e.g. to align it. Defaults to false."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Scroll Element into View",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, frame_selector, block, inline, behavior, force, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('scroll_into_view', {
+          window_label,
+          selector_type,
+          selector_value,
+          frame_selector,
+          block,
+          inline,
+          behavior,
+          force,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Scroll into view error:', error);
+        return createErrorResponse(`Failed to scroll element into view: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -10,8 +10,11 @@ export function registerSendTextToElementTool(server: McpServer) {
       selector_type: z.enum(["id", "class", "tag", "text"]).describe("The type of selector to use: 'id', 'class', 'tag', or 'text'."),
       selector_value: z.string().describe("The value to search for based on the selector type."),
       text: z.string().describe("The text to input into the element."),
-      window_label: z.string().default("main").describe("The identifier of the application window to search in. Defaults to 'main' if not specified."),
+      window_label: z.string().optional().describe("The identifier of the application window to search in. Defaults to the focused window."),
+      frame_selector: z.union([z.string(), z.array(z.string())]).optional().describe("Look inside a same-origin iframe: the CSS selector of the iframe, or the selectors of the nested iframes leading to it, outermost first."),
       delay_ms: z.number().default(20).describe("The delay between keystrokes in milliseconds (for realistic typing simulation). Default is 20ms."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the typing to complete, in milliseconds. Defaults to the timeout configured in the app (30 seconds unless changed). Raise it for long texts."),
     },
     {
       title: "Send Text to Element",
@@ -20,7 +23,7 @@ export function registerSendTextToElementTool(server: McpServer) {
       idempotentHint: false, // Text input can have side effects
       openWorldHint: false,
     },
-    async ({ selector_type, selector_value, text, window_label, delay_ms }) => {
+    async ({ selector_type, selector_value, text, window_label, frame_selector, delay_ms, show_action, timeout_ms }, extra) => {
       try {
         console.error(`Sending text to element with params: ${JSON.stringify({
           selector_type,
@@ -33,13 +36,16 @@ export function registerSendTextToElementTool(server: McpServer) {
         // Create the payload object
         const payload = {
           selector_type,
+          frame_selector,
           selector_value,
           text,
           window_label,
-          delay_ms
+          delay_ms,
+          show_action,
+          timeout_ms
         };
         
-        const result = await socketClient.sendCommand('send_text_to_element', payload);
+        const result = await socketClient.sendCommand('send_text_to_element', payload, extra.signal);
         
         console.error(`Got result: ${JSON.stringify(result)}`);
         
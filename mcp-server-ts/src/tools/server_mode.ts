@@ -0,0 +1,27 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerServerModeTool(server: McpServer) {
+  server.tool(
+    "get_server_mode",
+    "Reports the safety mode of the Tauri MCP plugin: whether the app is a debug build, whether the socket server was explicitly allowed in a release build (via config or the TAURI_MCP_ALLOW_IN_RELEASE environment variable), and whether the socket server is running.",
+    {},
+    {
+      title: "Get Tauri MCP Plugin Safety Mode",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('get_server_mode', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Server mode retrieval error:', error);
+        return createErrorResponse(`Failed to get server mode: ${(error as Error).message}`);
+      }
+    },
+  );
+}
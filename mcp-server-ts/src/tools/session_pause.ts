@@ -0,0 +1,54 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerSessionPauseTools(server: McpServer) {
+  server.tool(
+    "pause_session",
+    "Hands the app to a human: shows a banner on top of every window ('MCP agent paused — user in control' by default) and holds input commands (clicks, typing, form filling, mouse, menu and tray clicks) until resume_session, or until the user presses 'Resume agent' in the banner. Read-only tools keep working. Returns the pause status with the queued commands.",
+    {
+      message: z.string().optional().describe("Text of the banner. Defaults to 'MCP agent paused — user in control'."),
+    },
+    {
+      title: "Pause Session",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ message }) => {
+      try {
+        const result = await socketClient.sendCommand('pause_session', { message });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Pause session error:', error);
+        return createErrorResponse(`Failed to pause session: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "resume_session",
+    "Takes control back after pause_session: hides the banner and runs the input commands queued meanwhile in the order they arrived, or fails them with discard_queued. Returns how many were released or discarded.",
+    {
+      discard_queued: z.boolean().optional().describe("Fail the queued input commands instead of running them, e.g. when the user changed the page. Defaults to false."),
+    },
+    {
+      title: "Resume Session",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ discard_queued }) => {
+      try {
+        const result = await socketClient.sendCommand('resume_session', { discard_queued });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Resume session error:', error);
+        return createErrorResponse(`Failed to resume session: ${(error as Error).message}`);
+      }
+    },
+  );
+}
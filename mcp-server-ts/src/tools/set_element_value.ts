@@ -0,0 +1,46 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+const scalar = z.union([z.string(), z.number(), z.boolean()]);
+
+export function registerSetElementValueTool(server: McpServer) {
+  server.tool(
+    "set_element_value",
+    "Sets the value of a form control the way a user would, firing input/change events so frameworks like React pick it up: chooses <option>s of a <select> by value or label, checks or unchecks checkboxes and radios, or sets range sliders, dates, colors and other inputs. Use send_text_to_element for typing into text fields.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the control. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the control. 'css' takes any CSS selector; the first match is used. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the control."),
+      value: z.union([scalar, z.array(z.string())]).optional().describe("For a select, the option value (an array for multi-selects). For a checkbox or radio, true to check and false to uncheck; omit to toggle a checkbox or check a radio. For other inputs, the new value."),
+      label: z.union([z.string(), z.array(z.string())]).optional().describe("For a select, choose options by their visible label instead of their value."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Set Form Control Value",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, value, label, show_action, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('set_element_value', {
+          window_label,
+          selector_type,
+          selector_value,
+          value,
+          label,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Set element value error:', error);
+        return createErrorResponse(`Failed to set element value: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,45 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import * as path from 'path';
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerSetFileInputTool(server: McpServer) {
+  server.tool(
+    "set_file_input",
+    "Attaches files to an <input type=\"file\"> without a native file dialog, firing input/change events like a real selection. The application reads the files from disk, so paths must exist on the machine running the app (up to 25 MiB in total). Use it to test upload flows.",
+    {
+      window_label: z.string().optional().describe("The label of the window containing the input. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).describe("How to find the input. 'css' takes any CSS selector, e.g. 'input[type=file]'. Open shadow roots are searched too, except by 'xpath', and 'css' takes '>>>' to step into a shadow root, e.g. 'my-app >>> button'."),
+      selector_value: z.string().describe("The id, class, tag name, text or CSS selector of the input."),
+      paths: z.array(z.string()).min(1).describe("Files to attach. Relative paths are resolved against the MCP server's working directory."),
+      append: z.boolean().optional().describe("Keep the files already selected in the input instead of replacing them."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Attach Files to File Input",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, selector_type, selector_value, paths, append, show_action, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('set_file_input', {
+          window_label,
+          selector_type,
+          selector_value,
+          paths: paths.map((file) => path.resolve(file)),
+          append,
+          show_action,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Set file input error:', error);
+        return createErrorResponse(`Failed to set file input: ${(error as Error).message}`);
+      }
+    },
+  );
+}
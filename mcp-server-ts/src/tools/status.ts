@@ -0,0 +1,27 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerStatusTool(server: McpServer) {
+  server.tool(
+    "status",
+    "Reports the health of the Tauri application's socket server: whether it is running, transport and address, uptime, number of connected clients, commands served and the last error. Call it before a run to verify the bridge works.",
+    {},
+    {
+      title: "Get Socket Server Status",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('status', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Status error:', error);
+        return createErrorResponse(`Failed to get server status: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,34 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerStoreTool(server: McpServer) {
+  server.tool(
+    "manage_store",
+    "Reads or writes the entries of a tauri-plugin-store file of the application, to seed or verify persisted settings. Only the store files allowed by the app can be accessed.",
+    {
+      path: z.string().describe("Store file, as used by the app with tauri-plugin-store (e.g. 'settings.json')."),
+      action: z.enum(["get", "set", "delete", "clear", "reload"]).describe("'get' reads a key, or all entries without a key; 'reload' discards unsaved changes and reads the file again."),
+      key: z.string().optional().describe("Entry key. Required for set and delete."),
+      value: z.any().optional().describe("JSON value to store. Required for set."),
+      save: z.boolean().optional().describe("Write the file after a change. Defaults to true."),
+    },
+    {
+      title: "Manage Store",
+      readOnlyHint: false,
+      destructiveHint: true,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ path, action, key, value, save }) => {
+      try {
+        const result = await socketClient.sendCommand('manage_store', { path, action, key, value, save });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Store error:', error);
+        return createErrorResponse(`Failed to manage store: ${(error as Error).message}`);
+      }
+    },
+  );
+}
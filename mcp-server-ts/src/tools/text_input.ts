@@ -10,6 +10,12 @@ export function registerTextInputTool(server: McpServer) {
       text: z.string().describe("Required. The string of text content to be typed out by the simulated keyboard input."),
       delay_ms: z.number().int().nonnegative().optional().describe("The delay in milliseconds between each simulated keystroke. Adjusts the typing speed."),
       initial_delay_ms: z.number().int().nonnegative().optional().describe("An initial delay in milliseconds before the simulation of typing begins. Useful for ensuring the target field is ready."),
+      strategy: z.enum(["type", "paste"]).optional().describe("'type' (default) types one grapheme at a time, so emoji, combining marks and flags go out whole. 'paste' pastes the whole text at once, which keyboard layouts and input methods cannot mangle; in OS mode it goes through the system clipboard, whose previous text is put back. Use it for CJK, RTL scripts or long text."),
+      input_mode: z.enum(["os", "dom"]).optional().describe("'os' (default) types with the OS keyboard into whatever window has the focus. 'dom' dispatches synthetic keyboard and input events to the focused element of the window's page, which works with the window in the background or on CI without a display; the events are not trusted by the page and no initial delay applies unless set."),
+      window_label: z.string().optional().describe("The window typed into: its page in DOM mode, the window focused with focus_window in OS mode. Defaults to the focused window."),
+      focus_window: z.boolean().optional().describe("In OS mode, focus the window before typing; otherwise the text goes to whatever app has the focus. Default is false."),
+      restore_focus: z.boolean().optional().describe("With focus_window, give the focus back to the window that had it once typed. Windows of other apps are only restored on Windows."),
+      show_action: z.boolean().optional().describe("Outline the focused element in the window before typing, for people watching. Defaults to the setting of set_action_overlay."),
     },
     {
       title: "Simulate Keyboard Text Input into Focused Field",
@@ -18,7 +24,7 @@ export function registerTextInputTool(server: McpServer) {
       idempotentHint: false,
       openWorldHint: false,
     },
-    async ({ text, delay_ms, initial_delay_ms }) => {
+    async ({ text, delay_ms, initial_delay_ms, strategy, input_mode, window_label, focus_window, restore_focus, show_action }, extra) => {
       try {
         // Validate required parameters
         if (!text) {
@@ -39,17 +45,28 @@ export function registerTextInputTool(server: McpServer) {
           initial_delay_ms
         })}`);
         
-        await socketClient.sendCommand('simulate_text_input', {
+        const result = await socketClient.sendCommand('simulate_text_input', {
           text,
           delay_ms,
-          initial_delay_ms
-        });
+          initial_delay_ms,
+          strategy,
+          input_mode,
+          window_label,
+          focus_window,
+          restore_focus,
+          show_action
+        }, extra.signal);
         
+        const failures: Array<{ index: number; text: string; error: string }> = result?.failures ?? [];
+        const summary = failures.length === 0
+          ? `Successfully simulated typing of ${result?.charsTyped ?? text.length} characters`
+          : `Typed ${result?.charsTyped ?? 0} characters, ${failures.length} could not be typed:\n`
+            + failures.map((f) => `- #${f.index} ${JSON.stringify(f.text)}: ${f.error}`).join('\n');
         return {
           content: [
             {
               type: "text",
-              text: `Successfully simulated typing of ${text.length} characters`,
+              text: summary,
             },
           ],
         };
@@ -0,0 +1,58 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerTouchTool(server: McpServer) {
+  server.tool(
+    "simulate_touch",
+    "Performs a touch gesture in a page with synthetic touch pointer events, plus touch events where the webview has them, to test touch-optimized UIs on desktop: 'tap' (followed by the emulated mouse events and click), 'long_press' (with a contextmenu event), 'swipe' by dx/dy at a given duration or velocity, and a two-finger 'pinch' scaling the distance between the fingers. The gesture starts at the center of an element found by selector, or at a point in viewport CSS pixels.",
+    {
+      gesture: z.enum(["tap", "long_press", "swipe", "pinch"]).describe("The gesture to perform."),
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      selector_type: z.enum(["id", "class", "tag", "text", "css", "xpath", "aria-label", "role", "label"]).optional().describe("How to find the element the gesture starts on, at its center. The element is scrolled into view first."),
+      selector_value: z.string().optional().describe("The id, class, tag name, text or CSS selector of the element."),
+      x: z.number().optional().describe("Horizontal position the gesture starts at, in viewport CSS pixels, when no selector is given."),
+      y: z.number().optional().describe("Vertical position the gesture starts at, in viewport CSS pixels, when no selector is given."),
+      dx: z.number().optional().describe("Horizontal distance a swipe travels, in CSS pixels; negative swipes left."),
+      dy: z.number().optional().describe("Vertical distance a swipe travels, in CSS pixels; negative swipes up."),
+      velocity: z.number().positive().optional().describe("Speed of a swipe in CSS pixels per millisecond, instead of duration_ms. Around 1 is a fling."),
+      scale: z.number().positive().optional().describe("Required for a pinch. Final distance between the fingers over the initial one: below 1 pinches in (zoom out), above 1 spreads out (zoom in)."),
+      spread: z.number().positive().optional().describe("Initial distance between the two fingers of a pinch, in CSS pixels. Defaults to 100."),
+      duration_ms: z.number().int().nonnegative().optional().describe("How long the fingers stay down. Defaults to 50 for a tap, 600 for a long press, 300 for a swipe and 400 for a pinch."),
+      show_action: z.boolean().optional().describe("Show a ghost cursor and outline the target in the window before acting, for people watching. Defaults to the setting of set_action_overlay."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait for the webview to answer, in milliseconds, on top of the gesture. Defaults to the timeout configured in the app."),
+    },
+    {
+      title: "Simulate Touch Gesture",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ gesture, window_label, selector_type, selector_value, x, y, dx, dy, velocity, scale, spread, duration_ms, show_action, timeout_ms }, extra) => {
+      try {
+        const result = await socketClient.sendCommand('simulate_touch', {
+          gesture,
+          window_label,
+          selector_type,
+          selector_value,
+          x,
+          y,
+          dx,
+          dy,
+          velocity,
+          scale,
+          spread,
+          duration_ms,
+          show_action,
+          timeout_ms,
+        }, extra.signal);
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Touch simulation error:', error);
+        return createErrorResponse(`Failed to simulate touch: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,57 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerTraceTools(server: McpServer) {
+  server.tool(
+    "start_trace",
+    "Starts tracing the following tool calls for offline review of a run: each call is recorded with its payload, response, timing, the page URL, title, DOM hash and a screenshot before and after it, and the JavaScript errors, console messages, fetch/XHR requests and app logs reported while it ran. stop_trace stores it all as a zip bundle.",
+    {
+      name: z.string().optional().describe("Name of the bundle. Defaults to 'trace'."),
+      window_label: z.string().optional().describe("Window whose page is read around calls that do not name a window. Defaults to the focused window."),
+      include_dom: z.boolean().optional().describe("Store the HTML of each distinct DOM in the bundle, not only its hash. Defaults to true."),
+      include_screenshots: z.boolean().optional().describe("Store a JPEG of the viewport before and after each call. Defaults to true. Only available on Linux for now."),
+    },
+    {
+      title: "Start Trace",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ name, window_label, include_dom, include_screenshots }) => {
+      try {
+        const result = await socketClient.sendCommand('start_trace', { name, window_label, include_dom, include_screenshots });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Start trace error:', error);
+        return createErrorResponse(`Failed to start trace: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "stop_trace",
+    "Stops the trace started by start_trace and stores it as a 'trace' artifact: a zip with trace.json listing the steps, one steps/NNNN-<command>.json file per call, and dom/<hash>.html snapshots. Returns the step list and the artifact, which get_artifact retrieves.",
+    {
+      discard: z.boolean().optional().describe("Drop the trace instead of storing it. Defaults to false."),
+    },
+    {
+      title: "Stop Trace",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ discard }) => {
+      try {
+        const result = await socketClient.sendCommand('stop_trace', { discard });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Stop trace error:', error);
+        return createErrorResponse(`Failed to stop trace: ${(error as Error).message}`);
+      }
+    },
+  );
+}
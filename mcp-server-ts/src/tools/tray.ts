@@ -0,0 +1,79 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerTrayTools(server: McpServer) {
+  server.tool(
+    "list_trays",
+    "Lists the tray icons the Tauri application registered for automation, with their menu items (id, text, kind, enabled and checked state, submenus). Use the item ids with trigger_tray_menu_item.",
+    {},
+    {
+      title: "List Trays",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async () => {
+      try {
+        const result = await socketClient.sendCommand('list_trays', {});
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('List trays error:', error);
+        return createErrorResponse(`Failed to list trays: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "trigger_tray_menu_item",
+    "Selects a tray menu item as if the user clicked it: check items are toggled and the app's menu handler runs. Disabled items are refused.",
+    {
+      item_id: z.string().describe("ID of the menu item, as returned by list_trays."),
+      tray_id: z.string().optional().describe("Tray whose menu holds the item. Defaults to searching every registered tray."),
+    },
+    {
+      title: "Trigger Tray Menu Item",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ item_id, tray_id }) => {
+      try {
+        const result = await socketClient.sendCommand('trigger_tray_menu_item', { item_id, tray_id });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Trigger tray menu item error:', error);
+        return createErrorResponse(`Failed to trigger tray menu item: ${(error as Error).message}`);
+      }
+    },
+  );
+
+  server.tool(
+    "click_tray",
+    "Clicks a tray icon, delivering the press and release (and optionally a double click) to the app's tray icon handler.",
+    {
+      tray_id: z.string().describe("ID of the tray icon, as returned by list_trays."),
+      button: z.enum(["left", "middle", "right"]).optional().describe("Mouse button. Defaults to left."),
+      double: z.boolean().optional().describe("Also send a double click event. Defaults to false."),
+    },
+    {
+      title: "Click Tray",
+      readOnlyHint: false,
+      destructiveHint: false,
+      idempotentHint: false,
+      openWorldHint: false,
+    },
+    async ({ tray_id, button, double }) => {
+      try {
+        const result = await socketClient.sendCommand('click_tray', { tray_id, button, double });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Click tray error:', error);
+        return createErrorResponse(`Failed to click tray: ${(error as Error).message}`);
+      }
+    },
+  );
+}
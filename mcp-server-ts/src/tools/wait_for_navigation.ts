@@ -0,0 +1,44 @@
+import { McpServer } from "@modelcontextprotocol/sdk/server/mcp.js";
+import { z } from "zod";
+import { socketClient } from "./client.js";
+import { createErrorResponse, createSuccessResponse, formatResultAsText } from "./response-helpers.js";
+
+export function registerWaitForNavigationTool(server: McpServer) {
+  server.tool(
+    "wait_for_navigation",
+    "Waits until a window navigates to a URL matching a pattern, or at all without one. Counts finished page loads and client-side route changes (pushState, replaceState, back/forward, anchors). A window already at a matching URL resolves at once, since navigations often finish before this is called; pass the cursor of an earlier response as after_seq to only count later navigations. Use it after clicks or form submissions that change pages.",
+    {
+      window_label: z.string().optional().describe("The label of the window. Defaults to the focused window."),
+      pattern: z.string().optional().describe("Text the URL must contain, e.g. '/dashboard'. Without a pattern, the next navigation resolves."),
+      exact: z.boolean().optional().describe("The URL must equal the pattern. Defaults to false."),
+      regex: z.boolean().optional().describe("Treat the pattern as a regular expression (Rust syntax, no lookaround). Defaults to false."),
+      wait_for_load: z.boolean().optional().describe("Only count full page loads, not client-side route changes. Defaults to false."),
+      after_seq: z.number().int().min(0).optional().describe("Only count navigations after this cursor, from an earlier response."),
+      timeout_ms: z.number().int().positive().optional().describe("How long to wait, in milliseconds. Defaults to the timeout configured in the app (25 seconds unless changed)."),
+    },
+    {
+      title: "Wait for Navigation",
+      readOnlyHint: true,
+      destructiveHint: false,
+      idempotentHint: true,
+      openWorldHint: false,
+    },
+    async ({ window_label, pattern, exact, regex, wait_for_load, after_seq, timeout_ms }) => {
+      try {
+        const result = await socketClient.sendCommand('wait_for_navigation', {
+          window_label,
+          pattern,
+          exact,
+          regex,
+          wait_for_load,
+          after_seq,
+          timeout_ms,
+        });
+        return createSuccessResponse(formatResultAsText(result));
+      } catch (error) {
+        console.error('Wait for navigation error:', error);
+        return createErrorResponse(`Failed to wait for navigation: ${(error as Error).message}`);
+      }
+    },
+  );
+}
@@ -0,0 +1,215 @@
+use log::{Level, LevelFilter, Log, Metadata, Record};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::{Mutex, OnceLock};
+
+use crate::audit::now_ms;
+
+/// Number of records kept by default.
+const DEFAULT_CAPACITY: usize = 2000;
+
+static APP_LOGS: OnceLock<AppLogs> = OnceLock::new();
+
+/// A log record of the host app.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLogRecord {
+    /// Sequence number, used as the `since` cursor.
+    pub id: u64,
+    pub timestamp_ms: u64,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+    pub file: Option<String>,
+    pub line: Option<u32>,
+}
+
+/// Records matching a query on the captured logs.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppLogQuery {
+    pub records: Vec<AppLogRecord>,
+    /// ID to pass as `since` to only get the records logged afterwards.
+    pub cursor: u64,
+    /// True when records after `since` were dropped before they could be fetched.
+    pub missed_records: bool,
+}
+
+struct Inner {
+    records: VecDeque<AppLogRecord>,
+    next_id: u64,
+}
+
+/// Ring buffer of the log records captured by the [`LogBridge`].
+pub struct AppLogs {
+    inner: Mutex<Inner>,
+    capacity: usize,
+}
+
+impl AppLogs {
+    /// The captured logs, or None when no [`LogBridge`] is installed.
+    pub fn get() -> Option<&'static AppLogs> {
+        APP_LOGS.get()
+    }
+
+    fn push(&self, record: &Record) {
+        // Format before locking, in case a Display implementation logs
+        let message = record.args().to_string();
+        let mut inner = self.inner.lock().unwrap();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        while inner.records.len() >= self.capacity.max(1) {
+            inner.records.pop_front();
+        }
+        inner.records.push_back(AppLogRecord {
+            id,
+            timestamp_ms: now_ms(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message,
+            file: record.file().map(str::to_string),
+            line: record.line(),
+        });
+    }
+
+    /// ID of the most recent record (0 if nothing was logged yet).
+    pub fn latest_id(&self) -> u64 {
+        self.inner.lock().unwrap().next_id - 1
+    }
+
+    /// Records after the `since` ID, oldest first, at `level` or more severe and with a
+    /// target starting with `target`. When more than `limit` match, the oldest are returned
+    /// and the cursor points after them.
+    pub fn query(
+        &self,
+        since: u64,
+        level: Option<Level>,
+        target: Option<&str>,
+        limit: usize,
+    ) -> AppLogQuery {
+        let inner = self.inner.lock().unwrap();
+        let oldest = inner.records.front().map_or(inner.next_id, |r| r.id);
+        let missed_records = since + 1 < oldest && since < inner.next_id - 1;
+
+        let mut records = Vec::new();
+        // Filtered out records still advance the cursor
+        let mut cursor = since.max(inner.next_id - 1);
+        for record in inner.records.iter().filter(|r| r.id > since) {
+            if records.len() >= limit {
+                cursor = record.id - 1;
+                break;
+            }
+            if level.is_none_or(|level| record.level <= level)
+                && target.is_none_or(|target| record.target.starts_with(target))
+            {
+                records.push(record.clone());
+            }
+        }
+
+        AppLogQuery {
+            records,
+            cursor,
+            missed_records,
+        }
+    }
+}
+
+/// Logger capturing the host app's log records for the `get_app_logs` command.
+///
+/// Only one logger can be installed per process, so the bridge can forward the records
+/// to the app's own logger, e.g. the one of the Tauri log plugin:
+///
+/// ```rust,ignore
+/// let (log_plugin, max_level, logger) = tauri_plugin_log::Builder::new().split(app.handle())?;
+/// tauri_plugin_mcp::LogBridge::new(log::LevelFilter::Debug)
+///     .forward_to(logger, max_level)
+///     .install()?;
+/// app.handle().plugin(log_plugin)?;
+/// ```
+pub struct LogBridge {
+    level: LevelFilter,
+    capacity: usize,
+    forward: Option<(Box<dyn Log>, LevelFilter)>,
+}
+
+impl LogBridge {
+    /// Capture the records at `level` or more severe.
+    pub fn new(level: LevelFilter) -> Self {
+        Self {
+            level,
+            capacity: DEFAULT_CAPACITY,
+            forward: None,
+        }
+    }
+
+    /// Set the number of records kept (2000 by default); the oldest are dropped first.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Also pass the records up to `level` to another logger.
+    pub fn forward_to(mut self, logger: Box<dyn Log>, level: LevelFilter) -> Self {
+        self.forward = Some((logger, level));
+        self
+    }
+
+    /// Install the bridge as the global logger. Fails if a logger is already installed.
+    pub fn install(self) -> crate::Result<()> {
+        let max_level = self
+            .forward
+            .as_ref()
+            .map_or(self.level, |(_, level)| self.level.max(*level));
+        let logs = APP_LOGS.get_or_init(|| AppLogs {
+            inner: Mutex::new(Inner {
+                records: VecDeque::new(),
+                next_id: 1,
+            }),
+            capacity: self.capacity,
+        });
+        log::set_boxed_logger(Box::new(BridgeLogger {
+            level: self.level,
+            forward: self.forward,
+            logs,
+        }))
+        .map_err(|e| crate::Error::Anyhow(format!("Failed to install log bridge: {}", e)))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+}
+
+struct BridgeLogger {
+    level: LevelFilter,
+    forward: Option<(Box<dyn Log>, LevelFilter)>,
+    logs: &'static AppLogs,
+}
+
+impl BridgeLogger {
+    fn forwards(&self, metadata: &Metadata) -> Option<&dyn Log> {
+        self.forward
+            .as_ref()
+            .filter(|(logger, level)| metadata.level() <= *level && logger.enabled(metadata))
+            .map(|(logger, _)| logger.as_ref())
+    }
+}
+
+impl Log for BridgeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level || self.forwards(metadata).is_some()
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= self.level {
+            self.logs.push(record);
+        }
+        if let Some(logger) = self.forwards(record.metadata()) {
+            logger.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        if let Some((logger, _)) = &self.forward {
+            logger.flush();
+        }
+    }
+}
@@ -0,0 +1,334 @@
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::audit::now_ms;
+use crate::error::Error;
+
+/// Opaque identifier of a stored artifact, returned in tool responses instead of raw paths.
+pub type ArtifactId = String;
+
+/// Metadata describing a stored artifact.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactInfo {
+    pub id: ArtifactId,
+    /// Artifact category, e.g. "recording", "baseline" or "audit".
+    pub kind: String,
+    pub name: String,
+    pub size: u64,
+    pub created_ms: u64,
+}
+
+impl ArtifactInfo {
+    /// Rebuild the metadata encoded in an artifact ID.
+    pub fn from_id(id: &str, size: u64) -> Option<Self> {
+        let mut parts = id.splitn(4, '_');
+        let kind = parts.next()?.to_string();
+        let created_ms = parts.next()?.parse().ok()?;
+        let _seq = parts.next()?;
+        let name = parts.next()?.to_string();
+        Some(ArtifactInfo {
+            id: id.to_string(),
+            kind,
+            name,
+            size,
+            created_ms,
+        })
+    }
+}
+
+/// Storage backend for session artifacts (recordings, baselines, audit logs...).
+pub trait ArtifactStore: Send + Sync {
+    /// Store a new artifact and return its metadata.
+    fn put(&self, kind: &str, name: &str, data: &[u8]) -> crate::Result<ArtifactInfo>;
+
+    /// Append data to an existing artifact.
+    ///
+    /// The default implementation rewrites the whole artifact; backends that support
+    /// real appends should override it.
+    fn append(&self, id: &str, data: &[u8]) -> crate::Result<()> {
+        let mut content = self.get(id)?;
+        content.extend_from_slice(data);
+        self.write(id, &content)
+    }
+
+    /// Replace the content of an existing artifact.
+    fn write(&self, id: &str, data: &[u8]) -> crate::Result<()>;
+
+    /// Read the content of an artifact.
+    fn get(&self, id: &str) -> crate::Result<Vec<u8>>;
+
+    /// List stored artifacts, optionally filtered by kind.
+    fn list(&self, kind: Option<&str>) -> crate::Result<Vec<ArtifactInfo>>;
+
+    /// Delete an artifact.
+    fn delete(&self, id: &str) -> crate::Result<()>;
+}
+
+static ARTIFACT_SEQ: AtomicU64 = AtomicU64::new(0);
+
+/// Generate a new artifact ID for the given kind and name.
+pub fn new_artifact_id(kind: &str, name: &str) -> ArtifactId {
+    let created_ms = now_ms();
+    let seq = ARTIFACT_SEQ.fetch_add(1, Ordering::Relaxed);
+    // The kind must not contain the '_' separator
+    let kind = sanitize(kind).replace('_', "-");
+    format!("{}_{}_{}_{}", kind, created_ms, seq, sanitize(name))
+}
+
+/// `value` as a safe file name: other characters than ASCII letters, digits, `-`, `_` and `.`
+/// become `-`, and leading dots are dropped.
+pub(crate) fn sanitize(value: &str) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim_start_matches('.').to_string();
+    if cleaned.is_empty() {
+        "artifact".to_string()
+    } else {
+        cleaned
+    }
+}
+
+/// Reject IDs that could escape the store (path separators, parent references...).
+fn validate_id(id: &str) -> crate::Result<()> {
+    let valid = !id.is_empty()
+        && !id.starts_with('.')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.');
+    if valid && ArtifactInfo::from_id(id, 0).is_some() {
+        Ok(())
+    } else {
+        Err(Error::Artifact(format!("Invalid artifact id: {}", id)))
+    }
+}
+
+/// Artifact store backed by a local directory.
+pub struct LocalDirStore {
+    root: PathBuf,
+}
+
+impl LocalDirStore {
+    /// Create a store rooted at the given directory. The directory is created on first write.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    /// Root directory of the store.
+    pub fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+
+    fn path_for(&self, id: &str) -> crate::Result<PathBuf> {
+        validate_id(id)?;
+        Ok(self.root.join(id))
+    }
+}
+
+impl ArtifactStore for LocalDirStore {
+    fn put(&self, kind: &str, name: &str, data: &[u8]) -> crate::Result<ArtifactInfo> {
+        fs::create_dir_all(&self.root)?;
+        let id = new_artifact_id(kind, name);
+        fs::write(self.path_for(&id)?, data)?;
+        ArtifactInfo::from_id(&id, data.len() as u64)
+            .ok_or_else(|| Error::Artifact(format!("Invalid artifact id: {}", id)))
+    }
+
+    fn append(&self, id: &str, data: &[u8]) -> crate::Result<()> {
+        let mut file = OpenOptions::new().append(true).open(self.path_for(id)?)?;
+        file.write_all(data)?;
+        Ok(())
+    }
+
+    fn write(&self, id: &str, data: &[u8]) -> crate::Result<()> {
+        fs::write(self.path_for(id)?, data)?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> crate::Result<Vec<u8>> {
+        fs::read(self.path_for(id)?)
+            .map_err(|e| Error::Artifact(format!("Failed to read artifact {}: {}", id, e)))
+    }
+
+    fn list(&self, kind: Option<&str>) -> crate::Result<Vec<ArtifactInfo>> {
+        let entries = match fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut artifacts = Vec::new();
+        for entry in entries.flatten() {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+            if let Some(info) = ArtifactInfo::from_id(&id, size)
+                && kind.is_none_or(|k| k == info.kind)
+            {
+                artifacts.push(info);
+            }
+        }
+        artifacts.sort_by_key(|a| a.created_ms);
+        Ok(artifacts)
+    }
+
+    fn delete(&self, id: &str) -> crate::Result<()> {
+        fs::remove_file(self.path_for(id)?)
+            .map_err(|e| Error::Artifact(format!("Failed to delete artifact {}: {}", id, e)))
+    }
+}
+
+/// Artifact store backed by an S3-compatible bucket.
+#[cfg(feature = "s3")]
+pub struct S3ArtifactStore {
+    bucket: Box<s3::Bucket>,
+    prefix: String,
+}
+
+#[cfg(feature = "s3")]
+impl S3ArtifactStore {
+    /// Create a store for the given bucket.
+    ///
+    /// Credentials are read from the standard AWS environment variables and profile.
+    /// Set `endpoint` to use an S3-compatible service such as MinIO; path-style
+    /// addressing is used in that case.
+    pub fn new(
+        bucket: &str,
+        region: &str,
+        endpoint: Option<String>,
+        prefix: impl Into<String>,
+    ) -> crate::Result<Self> {
+        let region = match endpoint {
+            Some(endpoint) => s3::Region::Custom {
+                region: region.to_string(),
+                endpoint,
+            },
+            None => region
+                .parse()
+                .map_err(|e| Error::Artifact(format!("Invalid S3 region {}: {}", region, e)))?,
+        };
+        let custom_endpoint = matches!(region, s3::Region::Custom { .. });
+        let credentials = s3::creds::Credentials::default()
+            .map_err(|e| Error::Artifact(format!("Failed to load S3 credentials: {}", e)))?;
+        let mut bucket = s3::Bucket::new(bucket, region, credentials)
+            .map_err(|e| Error::Artifact(format!("Failed to open S3 bucket: {}", e)))?;
+        if custom_endpoint {
+            bucket = bucket.with_path_style();
+        }
+
+        Ok(Self {
+            bucket,
+            prefix: prefix.into().trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn key_for(&self, id: &str) -> crate::Result<String> {
+        validate_id(id)?;
+        if self.prefix.is_empty() {
+            Ok(id.to_string())
+        } else {
+            Ok(format!("{}/{}", self.prefix, id))
+        }
+    }
+}
+
+#[cfg(feature = "s3")]
+impl ArtifactStore for S3ArtifactStore {
+    fn put(&self, kind: &str, name: &str, data: &[u8]) -> crate::Result<ArtifactInfo> {
+        let id = new_artifact_id(kind, name);
+        self.write(&id, data)?;
+        ArtifactInfo::from_id(&id, data.len() as u64)
+            .ok_or_else(|| Error::Artifact(format!("Invalid artifact id: {}", id)))
+    }
+
+    fn write(&self, id: &str, data: &[u8]) -> crate::Result<()> {
+        self.bucket
+            .put_object(self.key_for(id)?, data)
+            .map_err(|e| Error::Artifact(format!("Failed to upload artifact {}: {}", id, e)))?;
+        Ok(())
+    }
+
+    fn get(&self, id: &str) -> crate::Result<Vec<u8>> {
+        let response = self
+            .bucket
+            .get_object(self.key_for(id)?)
+            .map_err(|e| Error::Artifact(format!("Failed to download artifact {}: {}", id, e)))?;
+        Ok(response.to_vec())
+    }
+
+    fn list(&self, kind: Option<&str>) -> crate::Result<Vec<ArtifactInfo>> {
+        let prefix = if self.prefix.is_empty() {
+            String::new()
+        } else {
+            format!("{}/", self.prefix)
+        };
+        let pages = self
+            .bucket
+            .list(prefix.clone(), None)
+            .map_err(|e| Error::Artifact(format!("Failed to list artifacts: {}", e)))?;
+
+        let mut artifacts: Vec<ArtifactInfo> = pages
+            .into_iter()
+            .flat_map(|page| page.contents)
+            .filter_map(|object| {
+                let id = object.key.strip_prefix(&prefix).unwrap_or(&object.key);
+                ArtifactInfo::from_id(id, object.size)
+            })
+            .filter(|info| kind.is_none_or(|k| k == info.kind))
+            .collect();
+        artifacts.sort_by_key(|a| a.created_ms);
+        Ok(artifacts)
+    }
+
+    fn delete(&self, id: &str) -> crate::Result<()> {
+        self.bucket
+            .delete_object(self.key_for(id)?)
+            .map_err(|e| Error::Artifact(format!("Failed to delete artifact {}: {}", id, e)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_keeps_names_inside_the_directory() {
+        assert_eq!(sanitize("get_dom"), "get_dom");
+        assert_eq!(sanitize("../../etc/passwd"), "-..-etc-passwd");
+        assert_eq!(sanitize("/abs/path"), "-abs-path");
+        assert_eq!(sanitize("a\\b"), "a-b");
+        assert_eq!(sanitize(".hidden"), "hidden");
+    }
+
+    #[test]
+    fn sanitize_never_returns_an_empty_name() {
+        assert_eq!(sanitize(""), "artifact");
+        assert_eq!(sanitize(".."), "artifact");
+    }
+
+    #[test]
+    fn generated_ids_are_valid() {
+        let id = new_artifact_id("trace_bundle", "../checkout flow");
+        assert!(validate_id(&id).is_ok(), "{}", id);
+        let info = ArtifactInfo::from_id(&id, 0).unwrap();
+        assert_eq!(info.kind, "trace-bundle");
+    }
+
+    #[test]
+    fn ids_escaping_the_store_are_rejected() {
+        for id in ["", "../trace_1_2_x", "trace_1_2_a/b", ".trace_1_2_x"] {
+            assert!(validate_id(id).is_err(), "{}", id);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use log::error;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::artifacts::{ArtifactId, ArtifactStore};
+
+/// Maximum number of characters of the payload kept in an audit entry.
+const MAX_PAYLOAD_CHARS: usize = 512;
+
+/// Current time in milliseconds since the Unix epoch.
+pub(crate) fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// A single socket command as seen by the audit log.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub timestamp_ms: u64,
+    pub command: String,
+    /// Serialized payload, truncated to a few hundred characters.
+    pub payload: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Records every socket command executed against the app.
+///
+/// Entries are kept in an in-memory ring buffer and, when enabled, appended as
+/// JSON lines to an "audit" artifact.
+pub struct AuditLog {
+    entries: Mutex<VecDeque<AuditEntry>>,
+    capacity: usize,
+    store: Option<Arc<dyn ArtifactStore>>,
+    artifact_id: Mutex<Option<ArtifactId>>,
+}
+
+impl AuditLog {
+    /// Create an audit log keeping the last `capacity` entries in memory.
+    ///
+    /// If a store is given, every entry is also persisted to a JSONL artifact.
+    pub fn new(capacity: usize, store: Option<Arc<dyn ArtifactStore>>) -> Self {
+        Self {
+            entries: Mutex::new(VecDeque::with_capacity(capacity.min(1024))),
+            capacity,
+            store,
+            artifact_id: Mutex::new(None),
+        }
+    }
+
+    /// Record the outcome of a command. `payload` is the summary built by [`summarize_payload`].
+    pub fn record(
+        &self,
+        command: &str,
+        payload: String,
+        success: bool,
+        error: Option<String>,
+        duration: Duration,
+    ) {
+        if self.capacity == 0 && self.store.is_none() {
+            return;
+        }
+
+        let entry = AuditEntry {
+            timestamp_ms: now_ms(),
+            command: command.to_string(),
+            payload,
+            success,
+            error,
+            duration_ms: duration.as_millis() as u64,
+        };
+
+        if let Some(store) = &self.store {
+            self.persist(store.as_ref(), &entry);
+        }
+
+        if self.capacity > 0 {
+            let mut entries = self.entries.lock().unwrap();
+            while entries.len() >= self.capacity {
+                entries.pop_front();
+            }
+            entries.push_back(entry);
+        }
+    }
+
+    /// Return the most recent entries, oldest first, optionally filtered by command.
+    pub fn entries(&self, limit: Option<usize>, command: Option<&str>) -> Vec<AuditEntry> {
+        let entries = self.entries.lock().unwrap();
+        let mut matching: Vec<AuditEntry> = entries
+            .iter()
+            .filter(|e| command.is_none_or(|c| c == e.command))
+            .cloned()
+            .collect();
+        if let Some(limit) = limit {
+            let skip = matching.len().saturating_sub(limit);
+            matching.drain(..skip);
+        }
+        matching
+    }
+
+    /// ID of the artifact the log is persisted to, if any entry was written yet.
+    pub fn artifact_id(&self) -> Option<ArtifactId> {
+        self.artifact_id.lock().unwrap().clone()
+    }
+
+    fn persist(&self, store: &dyn ArtifactStore, entry: &AuditEntry) {
+        let mut line = match serde_json::to_vec(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                error!("[TAURI_MCP] Failed to serialize audit entry: {}", e);
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        let mut artifact_id = self.artifact_id.lock().unwrap();
+        let result = match artifact_id.as_deref() {
+            Some(id) => store.append(id, &line),
+            None => store.put("audit", "audit-log.jsonl", &line).map(|info| {
+                *artifact_id = Some(info.id);
+            }),
+        };
+        if let Err(e) = result {
+            error!("[TAURI_MCP] Failed to persist audit entry: {}", e);
+        }
+    }
+}
+
+/// `payload` with the values of its `token` fields hidden, for logs.
+pub(crate) fn redacted(payload: &Value) -> Value {
+    match payload {
+        Value::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(key, value)| {
+                    let value = if key == "token" {
+                        Value::from("[redacted]")
+                    } else {
+                        redacted(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redacted).collect()),
+        value => value.clone(),
+    }
+}
+
+/// Serialize a payload for the audit log, truncated to a few hundred characters.
+pub(crate) fn summarize_payload(payload: &Value) -> String {
+    let serialized = redacted(payload).to_string();
+    if serialized.chars().count() <= MAX_PAYLOAD_CHARS {
+        serialized
+    } else {
+        let truncated: String = serialized.chars().take(MAX_PAYLOAD_CHARS).collect();
+        format!("{}... ({} bytes total)", truncated, serialized.len())
+    }
+}
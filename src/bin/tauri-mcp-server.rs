@@ -0,0 +1,362 @@
+//! MCP server speaking JSON-RPC over stdio and forwarding tool calls to the socket server of
+//! a running Tauri app, as a drop-in replacement for the TypeScript server in
+//! `mcp-server-ts`. Built with the `mcp-server` feature.
+//!
+//! It is configured with the same environment variables: `TAURI_MCP_CONNECTION_TYPE=tcp`
+//! with `TAURI_MCP_TCP_HOST` and `TAURI_MCP_TCP_PORT`, or `TAURI_MCP_IPC_PATH`. Without
+//! them, it connects to the most recently started app of the discovery directory, or the
+//! one named by `TAURI_MCP_APP`. `TAURI_MCP_TOKEN` is sent to apps requiring a token.
+
+use interprocess::local_socket::{
+    GenericFilePath, GenericNamespaced, Stream as IpcStream, ToFsName, ToNsName, prelude::*,
+};
+use serde_json::{Value, json};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+use tauri_plugin_mcp::{PROTOCOL_VERSION, discover_instances};
+
+/// MCP protocol version answered to clients that do not ask for one.
+const MCP_PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// Socket commands that are part of the connection, not tools.
+const CONNECTION_COMMANDS: &[&str] = &["handshake", "cancel"];
+
+/// Commands without side effects, which can be sent again when their response was lost.
+const RETRIABLE_COMMANDS: &[&str] = &["describe_tools", "ping"];
+
+/// Why a command got no response.
+enum SendError {
+    /// Nothing of the request reached the connection, so the app did not run it.
+    NotSent(String),
+    /// The connection broke after the request was sent, the app may have run it.
+    Lost(String),
+}
+
+impl SendError {
+    fn message(self) -> String {
+        match self {
+            Self::NotSent(message) | Self::Lost(message) => message,
+        }
+    }
+}
+
+/// Where the app listens.
+enum Target {
+    Ipc(String),
+    Tcp(String),
+}
+
+/// The app to connect to, from the environment or the discovery directory.
+fn target() -> Result<Target, String> {
+    if std::env::var("TAURI_MCP_CONNECTION_TYPE").is_ok_and(|kind| kind == "tcp") {
+        let host = std::env::var("TAURI_MCP_TCP_HOST").unwrap_or_else(|_| "127.0.0.1".into());
+        let port = std::env::var("TAURI_MCP_TCP_PORT").unwrap_or_else(|_| "9999".into());
+        return Ok(Target::Tcp(format!("{}:{}", host, port)));
+    }
+    if let Ok(path) = std::env::var("TAURI_MCP_IPC_PATH") {
+        return Ok(Target::Ipc(path));
+    }
+
+    let app_name = std::env::var("TAURI_MCP_APP").ok();
+    let instance = discover_instances()
+        .into_iter()
+        .find(|instance| {
+            app_name
+                .as_ref()
+                .is_none_or(|name| instance.app_name == *name)
+        })
+        .ok_or_else(|| match &app_name {
+            Some(name) => format!("No running Tauri app named {} was found", name),
+            None => "No running Tauri app with the MCP plugin was found".to_string(),
+        })?;
+    eprintln!(
+        "Discovered {} (pid {}) at {}",
+        instance.app_name, instance.pid, instance.address
+    );
+    Ok(match instance.transport.as_str() {
+        "tcp" => Target::Tcp(instance.address),
+        _ => Target::Ipc(instance.address),
+    })
+}
+
+trait Duplex: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Duplex for T {}
+
+/// Connection to the socket server of the app, speaking newline-delimited JSON.
+struct AppConnection {
+    stream: BufReader<Box<dyn Duplex>>,
+    next_id: u64,
+}
+
+impl AppConnection {
+    fn open() -> Result<Self, String> {
+        let stream: Box<dyn Duplex> = match target()? {
+            Target::Tcp(address) => Box::new(
+                TcpStream::connect(&address)
+                    .map_err(|e| format!("Failed to connect to {}: {}", address, e))?,
+            ),
+            Target::Ipc(path) => {
+                let name = if cfg!(windows) {
+                    path.clone().to_ns_name::<GenericNamespaced>()
+                } else {
+                    path.clone().to_fs_name::<GenericFilePath>()
+                }
+                .map_err(|e| format!("Invalid socket path {}: {}", path, e))?;
+                Box::new(
+                    IpcStream::connect(name)
+                        .map_err(|e| format!("Failed to connect to {}: {}", path, e))?,
+                )
+            }
+        };
+        let mut connection = AppConnection {
+            stream: BufReader::new(stream),
+            next_id: 0,
+        };
+
+        let mut handshake = json!({ "protocolVersion": PROTOCOL_VERSION });
+        if let Ok(token) = std::env::var("TAURI_MCP_TOKEN") {
+            handshake["token"] = Value::String(token);
+        }
+        let response = connection
+            .send("handshake", handshake)
+            .map_err(SendError::message)?;
+        if response.get("success").and_then(Value::as_bool) != Some(true) {
+            return Err(format!(
+                "Tauri app rejected the connection: {}",
+                response["error"].as_str().unwrap_or("unknown error")
+            ));
+        }
+        Ok(connection)
+    }
+
+    /// Send a command and wait for its response.
+    fn send(&mut self, command: &str, payload: Value) -> Result<Value, SendError> {
+        self.next_id += 1;
+        let id = format!("stdio-{}", self.next_id);
+        let request = json!({ "id": id, "command": command, "payload": payload });
+        let mut line = request.to_string().into_bytes();
+        line.push(b'\n');
+        let stream = self.stream.get_mut();
+        let mut written = 0;
+        while written < line.len() {
+            match stream.write(&line[written..]) {
+                Ok(0) => {
+                    return Err(SendError::Lost(format!(
+                        "Failed to send {}: the connection is closed",
+                        command
+                    )));
+                }
+                Ok(count) => written += count,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) if written == 0 => {
+                    return Err(SendError::NotSent(format!(
+                        "Failed to send {}: {}",
+                        command, e
+                    )));
+                }
+                Err(e) => {
+                    return Err(SendError::Lost(format!(
+                        "Failed to send {}: {}",
+                        command, e
+                    )));
+                }
+            }
+        }
+        stream
+            .flush()
+            .map_err(|e| SendError::Lost(format!("Failed to send {}: {}", command, e)))?;
+
+        loop {
+            let mut line = String::new();
+            let read = self.stream.read_line(&mut line).map_err(|e| {
+                SendError::Lost(format!("Failed to read the response to {}: {}", command, e))
+            })?;
+            if read == 0 {
+                return Err(SendError::Lost(
+                    "The Tauri app closed the connection".to_string(),
+                ));
+            }
+            let Ok(response) = serde_json::from_str::<Value>(&line) else {
+                continue;
+            };
+            if response.get("id").and_then(Value::as_str) == Some(id.as_str()) {
+                return Ok(response);
+            }
+        }
+    }
+}
+
+/// The MCP server, connecting to the app on first use and again after it went away.
+#[derive(Default)]
+struct Proxy {
+    connection: Option<AppConnection>,
+}
+
+impl Proxy {
+    /// Run a command of the app, reconnecting once if the connection was lost. The command
+    /// is only sent again when the app cannot have run it, or has no side effects, so tools
+    /// never run twice.
+    fn call(&mut self, command: &str, payload: Value) -> Result<Value, String> {
+        for attempt in 0..2 {
+            if self.connection.is_none() {
+                self.connection = Some(AppConnection::open()?);
+            }
+            let connection = self
+                .connection
+                .as_mut()
+                .expect("connection was just opened");
+            match connection.send(command, payload.clone()) {
+                Ok(response) => return Ok(response),
+                Err(e) => {
+                    self.connection = None;
+                    let retriable =
+                        matches!(e, SendError::NotSent(_)) || RETRIABLE_COMMANDS.contains(&command);
+                    if attempt == 1 || !retriable {
+                        return Err(e.message());
+                    }
+                    eprintln!("{}, reconnecting", e.message());
+                }
+            }
+        }
+        unreachable!("the second attempt returns")
+    }
+
+    fn handle(&mut self, method: &str, params: Value) -> Result<Value, (i64, String)> {
+        match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(MCP_PROTOCOL_VERSION),
+                "capabilities": { "tools": { "listChanged": false } },
+                "serverInfo": {
+                    "name": "tauri-mcp-server",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => self.list_tools().map_err(|e| (-32603, e)),
+            "tools/call" => {
+                let name = params
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .ok_or((-32602, "tools/call needs a tool name".to_string()))?;
+                let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+                Ok(self.call_tool(name, arguments))
+            }
+            _ => Err((-32601, format!("Method not found: {}", method))),
+        }
+    }
+
+    /// The tools available in the app, each taking its socket payload as arguments, with the
+    /// descriptions and payload schemas the app gives.
+    fn list_tools(&mut self) -> Result<Value, String> {
+        let response = self.call("describe_tools", json!({}))?;
+        let tools = response["data"]["tools"]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let tools: Vec<Value> = tools
+            .iter()
+            .filter_map(|tool| {
+                let name = tool.get("name")?.as_str()?;
+                if CONNECTION_COMMANDS.contains(&name) {
+                    return None;
+                }
+                // Apps with an older plugin describe neither
+                let mut description = match tool.get("description").and_then(Value::as_str) {
+                    Some(description) if !description.is_empty() => description.to_string(),
+                    _ => format!(
+                        "Runs the {} command of the Tauri app. The arguments are its payload, as documented in the tauri-plugin-mcp README.",
+                        name
+                    ),
+                };
+                if let Some(reason) = tool.get("reason").and_then(Value::as_str) {
+                    description.push_str(&format!(" Limited on this platform: {}.", reason));
+                }
+                let input_schema = match tool.get("inputSchema") {
+                    Some(schema) if schema.is_object() => schema.clone(),
+                    _ => json!({ "type": "object", "additionalProperties": true }),
+                };
+                Some(json!({
+                    "name": name,
+                    "description": description,
+                    "inputSchema": input_schema,
+                }))
+            })
+            .collect();
+        Ok(json!({ "tools": tools }))
+    }
+
+    fn call_tool(&mut self, name: &str, arguments: Value) -> Value {
+        let (is_error, text) = match self.call(name, arguments) {
+            Ok(response) if response.get("success").and_then(Value::as_bool) == Some(true) => {
+                let data = response.get("data").cloned().unwrap_or(Value::Null);
+                (
+                    false,
+                    serde_json::to_string_pretty(&data).unwrap_or_default(),
+                )
+            }
+            Ok(response) => (
+                true,
+                response["error"]
+                    .as_str()
+                    .unwrap_or("The command failed")
+                    .to_string(),
+            ),
+            Err(e) => (true, e),
+        };
+        json!({
+            "content": [{ "type": "text", "text": text }],
+            "isError": is_error,
+        })
+    }
+}
+
+fn main() {
+    let mut proxy = Proxy::default();
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout();
+
+    for line in stdin.lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(e) => {
+                let error = json!({
+                    "jsonrpc": "2.0",
+                    "id": null,
+                    "error": { "code": -32700, "message": format!("Parse error: {}", e) },
+                });
+                let _ = writeln!(stdout, "{}", error).and_then(|_| stdout.flush());
+                continue;
+            }
+        };
+        // Notifications, such as notifications/initialized, get no answer
+        let Some(id) = message.get("id").cloned() else {
+            continue;
+        };
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(json!({}));
+
+        let response = match proxy.handle(method, params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, error)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": error },
+            }),
+        };
+        if writeln!(stdout, "{}", response)
+            .and_then(|_| stdout.flush())
+            .is_err()
+        {
+            break;
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Tracks the cancellation tokens of in-flight requests by client and request ID, since
+/// clients choose their IDs independently and may well pick the same ones.
+#[derive(Default)]
+pub(crate) struct CancellationRegistry {
+    tokens: Mutex<HashMap<(u64, String), CancellationToken>>,
+}
+
+impl CancellationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create the token of a new request of `client`.
+    pub fn register(&self, client: u64, request_id: &str) -> CancellationToken {
+        let token = CancellationToken::new();
+        self.tokens
+            .lock()
+            .unwrap()
+            .insert((client, request_id.to_string()), token.clone());
+        token
+    }
+
+    /// Signal cancellation of a request of `client`. Returns false if no such request is in
+    /// flight.
+    pub fn cancel(&self, client: u64, request_id: &str) -> bool {
+        match self
+            .tokens
+            .lock()
+            .unwrap()
+            .get(&(client, request_id.to_string()))
+        {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Forget a request once it has been answered.
+    pub fn complete(&self, client: u64, request_id: &str) {
+        self.tokens
+            .lock()
+            .unwrap()
+            .remove(&(client, request_id.to_string()));
+    }
+}
@@ -0,0 +1,42 @@
+/// Injected in every webview. Remembers the context type and attributes of each canvas for
+/// `capture_canvas`, which cannot ask without creating a context, and optionally makes
+/// WebGL contexts keep their drawing buffer so they can be read at any time.
+const CANVAS_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_CANVAS__ || typeof HTMLCanvasElement === 'undefined') return;
+    const state = { contexts: new WeakMap() };
+    window.__TAURI_MCP_CANVAS__ = state;
+    const preserve = __PRESERVE__;
+    const originalGetContext = HTMLCanvasElement.prototype.getContext;
+    HTMLCanvasElement.prototype.getContext = function (type, attributes) {
+        const webgl = /^(webgl2?|experimental-webgl)$/.test(type);
+        if (webgl && preserve) {
+            attributes = Object.assign({}, attributes, { preserveDrawingBuffer: true });
+        }
+        const context = originalGetContext.call(this, type, attributes);
+        if (context && !state.contexts.has(this)) {
+            state.contexts.set(this, {
+                type: type === 'experimental-webgl' ? 'webgl' : type,
+                webgl,
+                preserveDrawingBuffer: webgl && typeof context.getContextAttributes === 'function'
+                    ? Boolean((context.getContextAttributes() || {}).preserveDrawingBuffer)
+                    : null,
+            });
+        }
+        return context;
+    };
+})();
+"#;
+
+/// Script hooking canvas context creation, forcing `preserveDrawingBuffer` on WebGL
+/// contexts when `preserve_drawing_buffers` is set.
+pub(crate) fn init_script(preserve_drawing_buffers: bool) -> String {
+    CANVAS_HOOK_SCRIPT.replace(
+        "__PRESERVE__",
+        if preserve_drawing_buffers {
+            "true"
+        } else {
+            "false"
+        },
+    )
+}
@@ -0,0 +1,282 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::headless::HeadlessMode;
+use crate::shared::commands;
+use crate::tool_docs;
+
+/// Platform capability a tool may depend on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Capability {
+    /// OS-level keyboard and mouse simulation.
+    OsInput,
+    /// Native window management (position, size, focus...).
+    WindowManagement,
+    /// Access to the webview content (DOM, JavaScript, storage).
+    Webview,
+    /// Screenshots of the page through the webview's snapshot API.
+    WebviewSnapshot,
+}
+
+/// Availability of a capability or tool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CapabilityState {
+    Available,
+    /// Works with limitations, see the reason.
+    Degraded,
+    /// Calls would fail on this platform.
+    Unavailable,
+}
+
+/// Result of probing a single capability.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CapabilityReport {
+    pub capability: Capability,
+    pub state: CapabilityState,
+    pub reason: Option<String>,
+}
+
+impl CapabilityReport {
+    fn available(capability: Capability) -> Self {
+        Self {
+            capability,
+            state: CapabilityState::Available,
+            reason: None,
+        }
+    }
+
+    fn degraded(capability: Capability, reason: &str) -> Self {
+        Self {
+            capability,
+            state: CapabilityState::Degraded,
+            reason: Some(reason.to_string()),
+        }
+    }
+
+    fn unavailable(capability: Capability, reason: &str) -> Self {
+        Self {
+            capability,
+            state: CapabilityState::Unavailable,
+            reason: Some(reason.to_string()),
+        }
+    }
+}
+
+/// Availability of a socket tool, derived from the capabilities it requires.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolDescription {
+    pub name: String,
+    /// What the tool does.
+    pub description: String,
+    /// JSON schema of the tool's payload.
+    pub input_schema: Value,
+    pub state: CapabilityState,
+    pub reason: Option<String>,
+    pub requires: Vec<Capability>,
+}
+
+/// Capabilities required by a socket command.
+pub fn tool_requirements(command: &str) -> &'static [Capability] {
+    match command {
+        commands::SIMULATE_TEXT_INPUT
+        | commands::SIMULATE_MOUSE_MOVEMENT
+        | commands::SIMULATE_KEY_PRESS => &[Capability::OsInput, Capability::WindowManagement],
+        commands::MANAGE_WINDOW | commands::CONVERT_COORDINATES => &[Capability::WindowManagement],
+        commands::GET_DOM
+        | commands::MANAGE_LOCAL_STORAGE
+        | commands::EXECUTE_JS
+        | commands::GET_ELEMENT_POSITION
+        | commands::SEND_TEXT_TO_ELEMENT
+        | commands::GET_PAGE_TEXT
+        | commands::GET_ELEMENT_INFO
+        | commands::SCROLL_INTO_VIEW
+        | commands::HIGHLIGHT_ELEMENT
+        | commands::GET_PAGE_INFO
+        | commands::WAIT_FOR_NAVIGATION
+        | commands::GET_DOWNLOADS
+        | commands::CONTROL_MEDIA
+        | commands::CLICK_ELEMENT
+        | commands::SIMULATE_TOUCH
+        | commands::SET_ELEMENT_VALUE
+        | commands::SET_FILE_INPUT
+        | commands::LIST_DIALOGS
+        | commands::RESPOND_TO_DIALOG
+        | commands::SET_DIALOG_INTERCEPTION
+        | commands::SET_DIALOG_POLICY
+        | commands::SET_PERMISSION_POLICY
+        | commands::CAPTURE_CANVAS
+        | commands::PRINT_TO_PDF
+        | commands::FOCUS_ELEMENT
+        | commands::BLUR_ELEMENT
+        | commands::FILL_FORM
+        | commands::ASSERT_ELEMENT_EXISTS
+        | commands::ASSERT_ELEMENT_VISIBLE
+        | commands::ASSERT_TEXT_CONTAINS
+        | commands::ASSERT_URL_MATCHES
+        | commands::START_INTERACTION_RECORDING
+        | commands::STOP_INTERACTION_RECORDING
+        | commands::SET_EMULATION
+        | commands::ADVANCE_CLOCK
+        | commands::SET_GEOLOCATION
+        | commands::SET_LOCALE
+        | commands::SET_THEME
+        | commands::SET_NETWORK_CONDITIONS
+        | commands::ADD_MOCK_ROUTE
+        | commands::REMOVE_MOCK_ROUTE
+        | commands::GET_JS_ERRORS => &[Capability::Webview],
+        commands::INSPECT_ELEMENT | commands::GET_UI_SNAPSHOT => {
+            &[Capability::Webview, Capability::WebviewSnapshot]
+        }
+        commands::START_TRACE => &[Capability::WebviewSnapshot],
+        commands::EMULATE_DEVICE => &[Capability::Webview, Capability::WindowManagement],
+        _ => &[],
+    }
+}
+
+/// Probe the live platform capabilities, as the tools use them in `headless` mode.
+pub fn probe(headless: HeadlessMode) -> Vec<CapabilityReport> {
+    vec![
+        probe_os_input(headless),
+        probe_window_management(headless),
+        CapabilityReport::available(Capability::Webview),
+        probe_webview_snapshot(),
+    ]
+}
+
+/// Describe every socket tool with its availability on this platform.
+pub fn describe_tools(reports: &[CapabilityReport]) -> Vec<ToolDescription> {
+    commands::ALL
+        .iter()
+        .map(|name| describe_tool(name, reports))
+        .collect()
+}
+
+/// Describe a single socket tool with its payload and availability on this platform.
+pub fn describe_tool(name: &str, reports: &[CapabilityReport]) -> ToolDescription {
+    let requires = tool_requirements(name);
+    let worst = worst_requirement(name, reports);
+
+    ToolDescription {
+        name: name.to_string(),
+        description: tool_docs::description(name).to_string(),
+        input_schema: tool_docs::input_schema(name),
+        state: worst.map_or(CapabilityState::Available, |r| r.state),
+        reason: worst.and_then(|r| r.reason.clone()),
+        requires: requires.to_vec(),
+    }
+}
+
+/// The capability required by a tool that is in the worst state, which decides whether the
+/// tool works. None for tools requiring nothing.
+pub fn worst_requirement<'a>(
+    name: &str,
+    reports: &'a [CapabilityReport],
+) -> Option<&'a CapabilityReport> {
+    let requires = tool_requirements(name);
+    reports
+        .iter()
+        .filter(|r| requires.contains(&r.capability))
+        .max_by_key(|r| r.state)
+}
+
+fn probe_os_input(headless: HeadlessMode) -> CapabilityReport {
+    if cfg!(mobile) {
+        return CapabilityReport::unavailable(
+            Capability::OsInput,
+            "OS-level input simulation is not supported on mobile",
+        );
+    }
+    // Headless, the input tools fall back to DOM events instead of failing
+    if let Some(reason) = headless.reason() {
+        return CapabilityReport::degraded(
+            Capability::OsInput,
+            &format!("Headless, {}: input is sent as DOM events", reason),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let has_x11 = std::env::var_os("DISPLAY").is_some();
+        let has_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some()
+            || std::env::var("XDG_SESSION_TYPE").is_ok_and(|t| t == "wayland");
+        if !has_x11 && !has_wayland {
+            return CapabilityReport::unavailable(
+                Capability::OsInput,
+                "No display server found (DISPLAY and WAYLAND_DISPLAY are unset)",
+            );
+        }
+        if has_wayland {
+            return CapabilityReport::degraded(
+                Capability::OsInput,
+                "Wayland session: OS-level input only reaches XWayland windows",
+            );
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        if !macos::accessibility_trusted() {
+            return CapabilityReport::unavailable(
+                Capability::OsInput,
+                "Accessibility permission has not been granted to the app",
+            );
+        }
+    }
+
+    CapabilityReport::available(Capability::OsInput)
+}
+
+fn probe_window_management(headless: HeadlessMode) -> CapabilityReport {
+    if cfg!(mobile) {
+        return CapabilityReport::unavailable(
+            Capability::WindowManagement,
+            "Window management is not supported on mobile",
+        );
+    }
+    if let Some(reason) = headless.reason() {
+        return CapabilityReport::degraded(
+            Capability::WindowManagement,
+            &format!("Headless, {}: window operations are skipped", reason),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            return CapabilityReport::degraded(
+                Capability::WindowManagement,
+                "Wayland compositors ignore window positioning requests",
+            );
+        }
+    }
+
+    CapabilityReport::available(Capability::WindowManagement)
+}
+
+// The screenshot fields of the tools are left out where this is degraded
+fn probe_webview_snapshot() -> CapabilityReport {
+    if cfg!(any(target_os = "linux", mobile)) {
+        return CapabilityReport::available(Capability::WebviewSnapshot);
+    }
+    CapabilityReport::degraded(
+        Capability::WebviewSnapshot,
+        "Webview screenshots are only taken on Linux, Android and iOS for now: screenshots are left out",
+    )
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+    #[link(name = "ApplicationServices", kind = "framework")]
+    unsafe extern "C" {
+        fn AXIsProcessTrusted() -> bool;
+    }
+
+    pub fn accessibility_trusted() -> bool {
+        // SAFETY: AXIsProcessTrusted takes no arguments and only reads process state
+        unsafe { AXIsProcessTrusted() }
+    }
+}
@@ -0,0 +1,221 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use crate::SocketType;
+use crate::discovery::default_socket_path;
+use crate::models::GenerateClientConfigRequest;
+
+/// Name of the server entry in the generated configurations.
+pub const DEFAULT_SERVER_NAME: &str = "tauri-mcp";
+
+/// MCP clients a configuration can be generated for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpClient {
+    #[default]
+    ClaudeDesktop,
+    ClaudeCode,
+    Cursor,
+    Vscode,
+    Windsurf,
+}
+
+/// Program the client starts to talk to the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum McpServerLauncher {
+    /// The TypeScript server of `mcp-server-ts`, run with Node.js.
+    Node,
+    /// The `tauri-mcp-server` binary of the `mcp-server` feature.
+    Binary,
+}
+
+/// Configuration to paste into an MCP client.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientConfig {
+    pub client: McpClient,
+    pub server: McpServerLauncher,
+    /// The JSON to merge into the client's configuration file.
+    pub config: Value,
+    /// Where the client reads its configuration, if it has a file.
+    pub config_file: Option<String>,
+    /// Shell command registering the server, for clients with a CLI.
+    pub command_line: Option<String>,
+    /// Environment the server is started with.
+    pub env: BTreeMap<String, String>,
+    pub notes: Vec<String>,
+}
+
+/// Build the configuration connecting the requested client to the app listening on
+/// `socket_type`. Without a server, the TypeScript server of the plugin sources is used
+/// when it is built, and the `tauri-mcp-server` binary from `PATH` otherwise.
+pub(crate) fn generate(
+    request: GenerateClientConfigRequest,
+    application_name: &str,
+    socket_type: &SocketType,
+    auth_token: Option<&str>,
+) -> ClientConfig {
+    let GenerateClientConfigRequest {
+        client,
+        server,
+        server_path,
+        server_name,
+    } = request;
+    let mut notes = Vec::new();
+    let bundled_server = bundled_ts_server();
+    let server = server.unwrap_or(if server_path.is_some() || bundled_server.is_some() {
+        McpServerLauncher::Node
+    } else {
+        McpServerLauncher::Binary
+    });
+    let (command, args) = match server {
+        McpServerLauncher::Node => {
+            let script = server_path
+                .or_else(|| bundled_server.map(|path| path.to_string_lossy().to_string()))
+                .unwrap_or_else(|| {
+                    notes.push(
+                        "The TypeScript server is not built: run `pnpm i && pnpm build` in mcp-server-ts and replace the path in args".to_string(),
+                    );
+                    "mcp-server-ts/build/index.js".to_string()
+                });
+            ("node".to_string(), vec![script])
+        }
+        McpServerLauncher::Binary => {
+            if server_path.is_none() {
+                notes.push(
+                    "tauri-mcp-server must be on PATH: cargo install tauri-plugin-mcp --features mcp-server".to_string(),
+                );
+            }
+            (
+                server_path.unwrap_or_else(|| "tauri-mcp-server".to_string()),
+                Vec::new(),
+            )
+        }
+    };
+
+    let mut env = BTreeMap::new();
+    match socket_type {
+        SocketType::Ipc { path: Some(path) } if *path != default_socket_path(application_name) => {
+            env.insert(
+                "TAURI_MCP_IPC_PATH".to_string(),
+                path.to_string_lossy().to_string(),
+            );
+        }
+        // The default socket path changes with each run, discovery finds it by name
+        SocketType::Ipc { .. } => {
+            env.insert("TAURI_MCP_APP".to_string(), application_name.to_string());
+        }
+        SocketType::Tcp { host, port } => {
+            let host = if host == "0.0.0.0" || host == "::" {
+                notes.push(format!(
+                    "The app listens on every interface ({}): replace 127.0.0.1 with its address when the client runs on another machine",
+                    host
+                ));
+                "127.0.0.1"
+            } else {
+                host.as_str()
+            };
+            env.insert("TAURI_MCP_CONNECTION_TYPE".to_string(), "tcp".to_string());
+            env.insert("TAURI_MCP_TCP_HOST".to_string(), host.to_string());
+            env.insert("TAURI_MCP_TCP_PORT".to_string(), port.to_string());
+        }
+    }
+    if let Some(token) = auth_token {
+        env.insert("TAURI_MCP_TOKEN".to_string(), token.to_string());
+        notes.push(
+            "The configuration contains the auth token of the app, keep it out of version control"
+                .to_string(),
+        );
+    }
+
+    let name = server_name.unwrap_or_else(|| DEFAULT_SERVER_NAME.to_string());
+    let mut entry = json!({ "command": command, "args": args, "env": env });
+    let (config, config_file, command_line) = match client {
+        McpClient::ClaudeDesktop => (
+            json!({ "mcpServers": { &name: entry } }),
+            Some(claude_desktop_config_file()),
+            None,
+        ),
+        McpClient::ClaudeCode => {
+            let mut line = format!("claude mcp add {}", shell_quote(&name));
+            for (key, value) in &env {
+                line.push_str(&format!(
+                    " -e {}",
+                    shell_quote(&format!("{}={}", key, value))
+                ));
+            }
+            line.push_str(&format!(" -- {}", shell_quote(&command)));
+            for arg in &args {
+                line.push_str(&format!(" {}", shell_quote(arg)));
+            }
+            (
+                json!({ "mcpServers": { &name: entry } }),
+                Some(".mcp.json".to_string()),
+                Some(line),
+            )
+        }
+        McpClient::Cursor => (
+            json!({ "mcpServers": { &name: entry } }),
+            Some(".cursor/mcp.json".to_string()),
+            None,
+        ),
+        McpClient::Vscode => {
+            entry["type"] = json!("stdio");
+            (
+                json!({ "servers": { &name: entry } }),
+                Some(".vscode/mcp.json".to_string()),
+                None,
+            )
+        }
+        McpClient::Windsurf => (
+            json!({ "mcpServers": { &name: entry } }),
+            Some("~/.codeium/windsurf/mcp_config.json".to_string()),
+            None,
+        ),
+    };
+
+    ClientConfig {
+        client,
+        server,
+        config,
+        config_file,
+        command_line,
+        env,
+        notes,
+    }
+}
+
+/// The TypeScript server next to the plugin sources, when it was built.
+fn bundled_ts_server() -> Option<PathBuf> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("mcp-server-ts")
+        .join("build")
+        .join("index.js");
+    path.is_file().then_some(path)
+}
+
+fn claude_desktop_config_file() -> String {
+    if cfg!(target_os = "macos") {
+        "~/Library/Application Support/Claude/claude_desktop_config.json".to_string()
+    } else if cfg!(windows) {
+        "%APPDATA%\\Claude\\claude_desktop_config.json".to_string()
+    } else {
+        "~/.config/Claude/claude_desktop_config.json".to_string()
+    }
+}
+
+/// Quote `value` for a POSIX shell when it needs it.
+fn shell_quote(value: &str) -> String {
+    let plain = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_./:=@%+,".contains(c));
+    if plain && !value.is_empty() {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use crate::audit::now_ms;
+use crate::hooks::LifecycleHooks;
+
+/// A client connected to the socket server.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientInfo {
+    pub id: u64,
+    /// "ipc" or "tcp".
+    pub transport: String,
+    /// Peer address for TCP clients.
+    pub address: Option<String>,
+    pub connected_ms: u64,
+    pub last_activity_ms: u64,
+    pub requests: u64,
+    pub last_command: Option<String>,
+}
+
+/// Keeps track of the clients currently connected to the socket server.
+#[derive(Default)]
+pub struct ClientRegistry {
+    next_id: AtomicU64,
+    clients: Mutex<BTreeMap<u64, ClientInfo>>,
+    hooks: Arc<LifecycleHooks>,
+}
+
+impl ClientRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A registry telling `hooks` about the clients coming and going.
+    pub(crate) fn with_hooks(hooks: Arc<LifecycleHooks>) -> Self {
+        Self {
+            hooks,
+            ..Self::default()
+        }
+    }
+
+    /// Register a new connection. The client is removed when the handle is dropped.
+    pub fn connect(self: &Arc<Self>, transport: &str, address: Option<String>) -> ClientHandle {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let now = now_ms();
+        let info = ClientInfo {
+            id,
+            transport: transport.to_string(),
+            address,
+            connected_ms: now,
+            last_activity_ms: now,
+            requests: 0,
+            last_command: None,
+        };
+        self.clients.lock().unwrap().insert(id, info.clone());
+        self.hooks.client_connected(&info);
+        ClientHandle {
+            id,
+            registry: self.clone(),
+        }
+    }
+
+    /// Currently connected clients, oldest first.
+    pub fn list(&self) -> Vec<ClientInfo> {
+        self.clients.lock().unwrap().values().cloned().collect()
+    }
+}
+
+/// Registration of a connected client, removed from the registry on drop.
+pub struct ClientHandle {
+    id: u64,
+    registry: Arc<ClientRegistry>,
+}
+
+impl ClientHandle {
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// Note that the client sent a command.
+    pub fn record_request(&self, command: &str) {
+        if let Some(info) = self.registry.clients.lock().unwrap().get_mut(&self.id) {
+            info.requests += 1;
+            info.last_activity_ms = now_ms();
+            info.last_command = Some(command.to_string());
+        }
+    }
+}
+
+impl Drop for ClientHandle {
+    fn drop(&mut self) {
+        let info = self.registry.clients.lock().unwrap().remove(&self.id);
+        if let Some(info) = info {
+            self.registry.hooks.client_disconnected(&info);
+        }
+    }
+}
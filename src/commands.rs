@@ -1 +1,31 @@
+use tauri::{AppHandle, Runtime, command};
 
+use crate::Result;
+use crate::TauriMcpExt;
+use crate::models::ServerModeResponse;
+use crate::session_status::SessionStatus;
+
+#[command]
+pub(crate) async fn get_server_mode<R: Runtime>(app: AppHandle<R>) -> Result<ServerModeResponse> {
+    Ok(app.tauri_mcp().server_mode())
+}
+
+#[command]
+pub(crate) async fn start_socket_server<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.tauri_mcp().start_socket_server()
+}
+
+#[command]
+pub(crate) async fn stop_socket_server<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.tauri_mcp().stop_socket_server()
+}
+
+#[command]
+pub(crate) async fn restart_socket_server<R: Runtime>(app: AppHandle<R>) -> Result<()> {
+    app.tauri_mcp().restart_socket_server()
+}
+
+#[command]
+pub(crate) async fn get_session_status<R: Runtime>(app: AppHandle<R>) -> Result<SessionStatus> {
+    Ok(app.tauri_mcp().session_status())
+}
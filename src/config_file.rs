@@ -0,0 +1,189 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::PluginConfig;
+use crate::error::Error;
+
+/// The `plugins.tauri-mcp` section of `tauri.conf.json`.
+///
+/// Every field is optional and overrides the [`PluginConfig`] given in code, so the
+/// plugin can be reconfigured without recompiling the app. Lists add to the ones given in
+/// code. `preserveDrawingBuffers` is not available here: it is part of the script injected
+/// in the webviews, which is built before the app configuration is read.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PluginFileConfig {
+    pub application_name: Option<String>,
+    /// Path of the IPC socket, or name of the Windows named pipe.
+    pub socket_path: Option<PathBuf>,
+    /// Listen on TCP instead of the IPC socket.
+    pub tcp: Option<TcpConfig>,
+    pub start_socket_server: Option<bool>,
+    pub allow_in_release: Option<bool>,
+    /// Directory of the session artifacts.
+    pub artifact_dir: Option<PathBuf>,
+    pub audit_capacity: Option<usize>,
+    pub persist_audit_log: Option<bool>,
+    pub event_buffer_capacity: Option<usize>,
+    /// Maximum sustained commands per second of a connection.
+    pub rate_limit: Option<u32>,
+    pub max_in_flight: Option<usize>,
+    /// Interval of the keepalive messages in milliseconds, 0 disables them.
+    pub keepalive_interval_ms: Option<u64>,
+    /// How long a connection may stay without requests, in milliseconds, 0 disables it.
+    pub idle_timeout_ms: Option<u64>,
+    pub record_sessions: Option<bool>,
+    pub session_dir: Option<PathBuf>,
+    pub advertise_mdns: Option<bool>,
+    pub timeouts: Option<TimeoutsConfig>,
+    /// How long in-flight commands may finish when the app exits, in milliseconds.
+    pub shutdown_timeout_ms: Option<u64>,
+    pub intercept_dialogs: Option<bool>,
+    pub require_input_consent: Option<bool>,
+    pub show_actions: Option<bool>,
+    pub headless: Option<bool>,
+    /// Directory the webviews' downloads are saved in.
+    pub download_dir: Option<PathBuf>,
+    #[serde(default)]
+    pub allowed_stores: Vec<PathBuf>,
+    #[serde(default)]
+    pub databases: Vec<DatabaseConfig>,
+    /// Directories of `read_app_file` and `write_app_file`, by name.
+    #[serde(default)]
+    pub file_roots: BTreeMap<String, PathBuf>,
+}
+
+/// TCP transport of the socket server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TcpConfig {
+    #[serde(default = "default_tcp_host")]
+    pub host: String,
+    pub port: u16,
+}
+
+fn default_tcp_host() -> String {
+    "127.0.0.1".to_string()
+}
+
+/// How long tools wait for the webview, in milliseconds.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TimeoutsConfig {
+    pub default_ms: Option<u64>,
+    /// Timeouts by command name.
+    #[serde(default)]
+    pub tools: BTreeMap<String, u64>,
+}
+
+/// A SQLite database `query_db` may access.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DatabaseConfig {
+    pub name: String,
+    pub url: String,
+    /// Whether clients may also change the database. Default is false.
+    #[serde(default)]
+    pub writable: bool,
+}
+
+impl PluginFileConfig {
+    /// Override `config` with the values set in the file.
+    pub fn apply(self, mut config: PluginConfig) -> crate::Result<PluginConfig> {
+        if let Some(name) = self.application_name {
+            config.application_name = name;
+        }
+        match (self.socket_path, self.tcp) {
+            (Some(_), Some(_)) => {
+                return Err(Error::PluginInit(
+                    "plugins.tauri-mcp sets both socketPath and tcp, choose one transport"
+                        .to_string(),
+                ));
+            }
+            (Some(path), None) => config = config.socket_path(path),
+            (None, Some(tcp)) => config = config.tcp(tcp.host, tcp.port),
+            (None, None) => {}
+        }
+        if let Some(start) = self.start_socket_server {
+            config = config.start_socket_server(start);
+        }
+        if let Some(allow) = self.allow_in_release {
+            config = config.allow_in_release(allow);
+        }
+        if let Some(dir) = self.artifact_dir {
+            config = config.artifact_dir(dir);
+        }
+        if let Some(capacity) = self.audit_capacity {
+            config = config.audit_log(capacity);
+        }
+        if let Some(persist) = self.persist_audit_log {
+            config = config.persist_audit_log(persist);
+        }
+        if let Some(capacity) = self.event_buffer_capacity {
+            config = config.event_buffer(capacity);
+        }
+        if let Some(commands_per_second) = self.rate_limit {
+            config = config.rate_limit(commands_per_second);
+        }
+        if let Some(max) = self.max_in_flight {
+            config = config.max_in_flight(max);
+        }
+        if let Some(interval_ms) = self.keepalive_interval_ms {
+            config = config.keepalive_interval(Duration::from_millis(interval_ms));
+        }
+        if let Some(timeout_ms) = self.idle_timeout_ms {
+            config = config.idle_timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(record) = self.record_sessions {
+            config = config.record_sessions(record);
+        }
+        if let Some(dir) = self.session_dir {
+            config = config.session_dir(dir);
+        }
+        if let Some(advertise) = self.advertise_mdns {
+            config = config.advertise_mdns(advertise);
+        }
+        if let Some(timeouts) = self.timeouts {
+            if let Some(default_ms) = timeouts.default_ms {
+                config = config.default_timeout(Duration::from_millis(default_ms));
+            }
+            for (command, timeout_ms) in timeouts.tools {
+                config = config.tool_timeout(&command, Duration::from_millis(timeout_ms));
+            }
+        }
+        if let Some(timeout_ms) = self.shutdown_timeout_ms {
+            config = config.shutdown_timeout(Duration::from_millis(timeout_ms));
+        }
+        if let Some(intercept) = self.intercept_dialogs {
+            config = config.intercept_dialogs(intercept);
+        }
+        if let Some(require) = self.require_input_consent {
+            config = config.require_input_consent(require);
+        }
+        if let Some(show) = self.show_actions {
+            config = config.show_actions(show);
+        }
+        if let Some(headless) = self.headless {
+            config = config.headless(headless);
+        }
+        if let Some(dir) = self.download_dir {
+            config = config.download_dir(dir);
+        }
+        for path in self.allowed_stores {
+            config = config.allow_store(path);
+        }
+        for database in self.databases {
+            config = if database.writable {
+                config.writable_database(database.name, database.url)
+            } else {
+                config.database(database.name, database.url)
+            };
+        }
+        for (name, dir) in self.file_roots {
+            config = config.file_root(name, dir);
+        }
+        Ok(config)
+    }
+}
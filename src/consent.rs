@@ -0,0 +1,246 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Runtime};
+use tokio::sync::watch;
+
+use crate::TauriMcpExt;
+use crate::audit::now_ms;
+use crate::platform::consent_prompt;
+use crate::shared::commands;
+
+/// Commands driving the OS mouse and keyboard, which need the user's consent when it is
+/// required, unless they run in DOM mode. `click_element` only does with the `os` and
+/// `auto` strategies.
+pub(crate) const CONSENT_COMMANDS: &[&str] = &[
+    commands::SIMULATE_TEXT_INPUT,
+    commands::SIMULATE_MOUSE_MOVEMENT,
+    commands::SIMULATE_KEY_PRESS,
+];
+
+fn drives_os_input(command: &str, payload: &Value) -> bool {
+    if command == commands::CLICK_ELEMENT {
+        return payload
+            .get("strategy")
+            .and_then(Value::as_str)
+            .is_some_and(|strategy| strategy != "dom");
+    }
+    let dom_mode = payload
+        .get("input_mode")
+        .or_else(|| payload.get("inputMode"))
+        .and_then(Value::as_str)
+        == Some("dom");
+    CONSENT_COMMANDS.contains(&command) && !dom_mode
+}
+
+/// Where the user stands on input simulation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConsentState {
+    /// No input command was sent yet.
+    NotAsked,
+    /// The prompt is shown, input commands wait for the answer.
+    Pending,
+    Granted,
+    Denied,
+}
+
+/// Consent to input simulation, as reported by `get_input_consent`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConsentStatus {
+    /// Whether input simulation needs consent at all.
+    pub required: bool,
+    pub state: ConsentState,
+    pub asked_ms: Option<u64>,
+    pub decided_ms: Option<u64>,
+}
+
+/// Shows the app's own consent prompt instead of the plugin's native dialog, and answers it
+/// with [`InputConsent::grant`] or [`InputConsent::deny`].
+pub(crate) type ConsentPrompt<R> = Arc<dyn Fn(&AppHandle<R>) + Send + Sync>;
+
+struct ConsentInner<R: Runtime> {
+    asked_ms: Option<u64>,
+    decided_ms: Option<u64>,
+    /// Number of the native dialog waiting for an answer, bumped on every prompt and reset
+    /// so the late answer of an earlier dialog is ignored.
+    prompt_id: u64,
+    prompt: Option<ConsentPrompt<R>>,
+}
+
+/// Opt-in interlock on input simulation: the first `simulate_*` command of the session
+/// asks the user, and input simulation only runs once they allowed it. A session lasts until
+/// a client disconnects.
+pub struct InputConsent<R: Runtime> {
+    required: bool,
+    inner: Mutex<ConsentInner<R>>,
+    state: watch::Sender<ConsentState>,
+}
+
+impl<R: Runtime> InputConsent<R> {
+    pub(crate) fn new(required: bool) -> Self {
+        Self {
+            required,
+            inner: Mutex::new(ConsentInner {
+                asked_ms: None,
+                decided_ms: None,
+                prompt_id: 0,
+                prompt: None,
+            }),
+            state: watch::Sender::new(ConsentState::NotAsked),
+        }
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn status(&self) -> ConsentStatus {
+        let inner = self.inner.lock().unwrap();
+        ConsentStatus {
+            required: self.required,
+            state: *self.state.borrow(),
+            asked_ms: inner.asked_ms,
+            decided_ms: inner.decided_ms,
+        }
+    }
+
+    /// Show the app's own prompt instead of the native dialog.
+    pub(crate) fn set_prompt(&self, prompt: ConsentPrompt<R>) {
+        self.inner.lock().unwrap().prompt = Some(prompt);
+    }
+
+    /// Allow input simulation for the rest of the session.
+    pub fn grant(&self, app: &AppHandle<R>) {
+        self.decide(app, ConsentState::Granted);
+    }
+
+    /// Refuse input simulation for the rest of the session.
+    pub fn deny(&self, app: &AppHandle<R>) {
+        self.decide(app, ConsentState::Denied);
+    }
+
+    /// Forget the answer, so the next input command asks again.
+    pub fn reset(&self, _app: &AppHandle<R>) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            inner.asked_ms = None;
+            inner.decided_ms = None;
+            inner.prompt_id += 1;
+        }
+        self.state.send_replace(ConsentState::NotAsked);
+    }
+
+    /// End the session when a client disconnects, so the next client is asked again.
+    pub(crate) fn client_disconnected(&self, app: &AppHandle<R>) {
+        if self.required && *self.state.borrow() != ConsentState::NotAsked {
+            info!("[TAURI_MCP] A client disconnected, input simulation needs consent again");
+            self.reset(app);
+        }
+    }
+
+    fn decide(&self, app: &AppHandle<R>, decision: ConsentState) {
+        self.inner.lock().unwrap().decided_ms = Some(now_ms());
+        self.state.send_replace(decision);
+        let granted = decision == ConsentState::Granted;
+        info!(
+            "[TAURI_MCP] Input simulation {} by the user",
+            if granted { "allowed" } else { "denied" }
+        );
+        app.tauri_mcp()
+            .events()
+            .publish("inputConsent", serde_json::json!({ "granted": granted }));
+    }
+
+    /// Answer from the native dialog, ignored unless it is the one still waiting.
+    fn answered(&self, app: &AppHandle<R>, prompt_id: u64, granted: bool) {
+        let current = self.inner.lock().unwrap().prompt_id == prompt_id
+            && *self.state.borrow() == ConsentState::Pending;
+        if !current {
+            warn!("[TAURI_MCP] Ignoring the answer of an outdated consent prompt");
+            return;
+        }
+        self.decide(
+            app,
+            if granted {
+                ConsentState::Granted
+            } else {
+                ConsentState::Denied
+            },
+        );
+    }
+
+    /// Ask for consent if `command` drives the OS input and consent is required, then
+    /// wait for the answer. Fails when the user denied it.
+    pub(crate) async fn check(
+        &self,
+        app: &AppHandle<R>,
+        command: &str,
+        payload: &Value,
+    ) -> crate::Result<()> {
+        if !self.required || !drives_os_input(command, payload) {
+            return Ok(());
+        }
+        let mut state = self.state.subscribe();
+        loop {
+            let ask = self.state.send_if_modified(|state| {
+                if *state == ConsentState::NotAsked {
+                    *state = ConsentState::Pending;
+                    true
+                } else {
+                    false
+                }
+            });
+            if ask {
+                self.ask(app);
+            }
+
+            let decision = *state
+                .wait_for(|state| *state != ConsentState::Pending)
+                .await
+                .map_err(|e| crate::Error::Anyhow(format!("Input consent is gone: {}", e)))?;
+            match decision {
+                ConsentState::Granted => return Ok(()),
+                ConsentState::Denied => {
+                    return Err(crate::Error::Anyhow(format!(
+                        "{} needs the user's consent to input simulation, which they denied",
+                        command
+                    )));
+                }
+                // Reset while waiting, ask again
+                ConsentState::NotAsked | ConsentState::Pending => {}
+            }
+        }
+    }
+
+    fn ask(&self, app: &AppHandle<R>) {
+        let (prompt, prompt_id) = {
+            let mut inner = self.inner.lock().unwrap();
+            inner.asked_ms = Some(now_ms());
+            inner.decided_ms = None;
+            inner.prompt_id += 1;
+            (inner.prompt.clone(), inner.prompt_id)
+        };
+        info!("[TAURI_MCP] Asking the user's consent to input simulation");
+        app.tauri_mcp()
+            .events()
+            .publish("inputConsentRequested", serde_json::json!({}));
+        match prompt {
+            Some(prompt) => prompt(app),
+            None => {
+                let handle = app.clone();
+                consent_prompt::show(
+                    app,
+                    Box::new(move |granted| {
+                        handle
+                            .tauri_mcp()
+                            .input_consent()
+                            .answered(&handle, prompt_id, granted)
+                    }),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tauri::{Runtime, WebviewWindow};
+
+use crate::error::Error;
+
+/// A coordinate space points can be converted between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CoordinateSpace {
+    /// CSS pixels from the top-left corner of the webview's viewport, as
+    /// `getBoundingClientRect` reports them.
+    Viewport,
+    /// CSS pixels from the top-left corner of the page, i.e. viewport plus scroll offset.
+    Document,
+    /// Logical pixels from the window's outer corner, decorations included, as
+    /// `simulate_mouse_movement` takes them.
+    Window,
+    /// Logical pixels on the virtual screen, at the window's scale factor.
+    Logical,
+    /// Physical pixels on the virtual screen.
+    Physical,
+}
+
+/// Where a window is on screen, to convert between coordinate spaces.
+///
+/// CSS pixels are taken as logical pixels, which holds unless the webview is zoomed.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowGeometry {
+    pub scale_factor: f64,
+    /// Physical position of the webview's top-left corner.
+    pub inner_x: i32,
+    pub inner_y: i32,
+    /// Physical position of the window's outer corner.
+    pub outer_x: i32,
+    pub outer_y: i32,
+    /// Scroll offset of the page in CSS pixels, zero unless set with [`Self::with_scroll`].
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+}
+
+impl WindowGeometry {
+    /// Read the position and scale factor of a window.
+    pub fn of<R: Runtime>(window: &WebviewWindow<R>) -> crate::Result<Self> {
+        let scale_factor = window
+            .scale_factor()
+            .map_err(|e| Error::Anyhow(format!("Failed to get scale factor: {}", e)))?;
+        let inner = window
+            .inner_position()
+            .map_err(|e| Error::Anyhow(format!("Failed to get window inner position: {}", e)))?;
+        let outer = window
+            .outer_position()
+            .map_err(|e| Error::Anyhow(format!("Failed to get window position: {}", e)))?;
+        Ok(Self {
+            scale_factor,
+            inner_x: inner.x,
+            inner_y: inner.y,
+            outer_x: outer.x,
+            outer_y: outer.y,
+            scroll_x: 0.0,
+            scroll_y: 0.0,
+        })
+    }
+
+    /// The same geometry with the page scrolled by (`x`, `y`) CSS pixels.
+    pub fn with_scroll(self, x: f64, y: f64) -> Self {
+        Self {
+            scroll_x: x,
+            scroll_y: y,
+            ..self
+        }
+    }
+
+    /// Convert a point from one space to another.
+    pub fn convert(
+        &self,
+        from: CoordinateSpace,
+        to: CoordinateSpace,
+        (x, y): (f64, f64),
+    ) -> (f64, f64) {
+        self.point_in(to, self.physical_point(from, (x, y)))
+    }
+
+    /// Physical screen position of a point given in `space`.
+    fn physical_point(&self, space: CoordinateSpace, (x, y): (f64, f64)) -> (f64, f64) {
+        let scale = self.scale_factor;
+        match space {
+            CoordinateSpace::Viewport => (
+                f64::from(self.inner_x) + x * scale,
+                f64::from(self.inner_y) + y * scale,
+            ),
+            CoordinateSpace::Document => self.physical_point(
+                CoordinateSpace::Viewport,
+                (x - self.scroll_x, y - self.scroll_y),
+            ),
+            CoordinateSpace::Window => (
+                f64::from(self.outer_x) + x * scale,
+                f64::from(self.outer_y) + y * scale,
+            ),
+            CoordinateSpace::Logical => (x * scale, y * scale),
+            CoordinateSpace::Physical => (x, y),
+        }
+    }
+
+    /// Position in `space` of a physical screen point.
+    fn point_in(&self, space: CoordinateSpace, (x, y): (f64, f64)) -> (f64, f64) {
+        let scale = self.scale_factor;
+        match space {
+            CoordinateSpace::Viewport => (
+                (x - f64::from(self.inner_x)) / scale,
+                (y - f64::from(self.inner_y)) / scale,
+            ),
+            CoordinateSpace::Document => {
+                let (x, y) = self.point_in(CoordinateSpace::Viewport, (x, y));
+                (x + self.scroll_x, y + self.scroll_y)
+            }
+            CoordinateSpace::Window => (
+                (x - f64::from(self.outer_x)) / scale,
+                (y - f64::from(self.outer_y)) / scale,
+            ),
+            CoordinateSpace::Logical => (x / scale, y / scale),
+            CoordinateSpace::Physical => (x, y),
+        }
+    }
+
+    /// Position to give the OS mouse for a point: macOS places the mouse in points,
+    /// the other platforms in physical pixels.
+    pub fn os_input_point(&self, space: CoordinateSpace, point: (f64, f64)) -> (i32, i32) {
+        let target = if cfg!(target_os = "macos") {
+            CoordinateSpace::Logical
+        } else {
+            CoordinateSpace::Physical
+        };
+        let (x, y) = self.convert(space, target, point);
+        (x.round() as i32, y.round() as i32)
+    }
+}
@@ -0,0 +1,222 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// A SQLite database `query_db` may access.
+#[derive(Debug, Clone)]
+pub struct DatabaseConnection {
+    /// Name clients use to pick the database.
+    pub name: String,
+    /// `sqlite:` URL as given to tauri-plugin-sql; relative paths are resolved against the
+    /// app config dir like the SQL plugin does.
+    pub url: String,
+    /// Whether the database is opened read-only (the default).
+    pub read_only: bool,
+}
+
+/// Rows returned by a query.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub columns: Vec<String>,
+    /// Each row as an object keyed by column name; blobs are base64 encoded.
+    pub rows: Vec<Map<String, Value>>,
+    /// Rows changed by the statements, on writable databases.
+    pub rows_affected: u64,
+    /// True when the query returned more rows than `max_rows`.
+    pub truncated: bool,
+}
+
+/// The databases configured for `query_db`, with their connection pools once opened.
+pub struct Databases {
+    connections: Vec<DatabaseConnection>,
+    #[cfg(feature = "sql")]
+    pools: tokio::sync::Mutex<std::collections::HashMap<String, sqlx::SqlitePool>>,
+}
+
+impl Databases {
+    pub(crate) fn new(connections: Vec<DatabaseConnection>) -> Self {
+        Self {
+            connections,
+            #[cfg(feature = "sql")]
+            pools: Default::default(),
+        }
+    }
+
+    /// The configured databases.
+    pub fn connections(&self) -> &[DatabaseConnection] {
+        &self.connections
+    }
+
+    /// Run SQL on a configured database, returning at most `max_rows` rows. Read-only
+    /// databases are opened with SQLite's read-only flag and `query_only` pragma, so any
+    /// write fails, and cannot attach other databases.
+    #[cfg(feature = "sql")]
+    pub async fn query<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        name: &str,
+        sql: &str,
+        params: &[Value],
+        max_rows: usize,
+    ) -> crate::Result<QueryResult> {
+        use futures::TryStreamExt;
+        use sqlx::{Either, Executor};
+
+        let pool = self.pool(app, name).await?;
+        let mut query = sqlx::query(sql);
+        for param in params {
+            query = match param {
+                Value::Null => query.bind(None::<String>),
+                Value::Bool(value) => query.bind(*value),
+                Value::Number(number) => match number.as_i64() {
+                    Some(value) => query.bind(value),
+                    None => query.bind(number.as_f64()),
+                },
+                Value::String(value) => query.bind(value.clone()),
+                value => query.bind(value.to_string()),
+            };
+        }
+
+        let mut result = QueryResult {
+            columns: Vec::new(),
+            rows: Vec::new(),
+            rows_affected: 0,
+            truncated: false,
+        };
+        // The executor runs each statement of the SQL and reports its rows, then its changes
+        let mut stream = (&pool).fetch_many(query);
+        while let Some(item) = stream.try_next().await.map_err(query_error)? {
+            match item {
+                Either::Left(done) => result.rows_affected += done.rows_affected(),
+                Either::Right(row) => {
+                    if result.rows.len() >= max_rows {
+                        result.truncated = true;
+                        break;
+                    }
+                    let (columns, row) = row_to_json(&row);
+                    if result.columns.is_empty() {
+                        result.columns = columns;
+                    }
+                    result.rows.push(row);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    #[cfg(feature = "sql")]
+    async fn pool<R: tauri::Runtime>(
+        &self,
+        app: &tauri::AppHandle<R>,
+        name: &str,
+    ) -> crate::Result<sqlx::SqlitePool> {
+        use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+        use tauri::Manager;
+
+        let mut pools = self.pools.lock().await;
+        if let Some(pool) = pools.get(name) {
+            return Ok(pool.clone());
+        }
+        let connection = self
+            .connections
+            .iter()
+            .find(|connection| connection.name == name)
+            .ok_or_else(|| {
+                crate::Error::Anyhow(format!(
+                    "Database {} is not configured, add it with PluginConfig::database",
+                    name
+                ))
+            })?;
+
+        let path = connection
+            .url
+            .strip_prefix("sqlite:")
+            .ok_or_else(|| {
+                crate::Error::Anyhow(format!("Unsupported database URL {}", connection.url))
+            })?
+            .trim_start_matches("//");
+        let path = if path == ":memory:" || std::path::Path::new(path).is_absolute() {
+            std::path::PathBuf::from(path)
+        } else {
+            app.path().app_config_dir()?.join(path)
+        };
+        let mut options = SqliteConnectOptions::new()
+            .filename(&path)
+            .read_only(connection.read_only);
+        if connection.read_only {
+            options = options.pragma("query_only", "ON");
+        }
+        let read_only = connection.read_only;
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .after_connect(move |conn, _| {
+                Box::pin(async move {
+                    if read_only {
+                        forbid_attach(conn).await?;
+                    }
+                    Ok(())
+                })
+            })
+            .connect_with(options)
+            .await
+            .map_err(|e| {
+                crate::Error::Anyhow(format!(
+                    "Failed to open database {} at {}: {}",
+                    name,
+                    path.display(),
+                    e
+                ))
+            })?;
+        pools.insert(name.to_string(), pool.clone());
+        Ok(pool)
+    }
+}
+
+/// Make `ATTACH DATABASE` fail on the connection, which the read-only flags allow and which
+/// would let queries read any SQLite file the app can open.
+#[cfg(feature = "sql")]
+async fn forbid_attach(conn: &mut sqlx::SqliteConnection) -> Result<(), sqlx::Error> {
+    let mut handle = conn.lock_handle().await?;
+    // SAFETY: the locked handle is a live connection no other thread is using
+    unsafe {
+        libsqlite3_sys::sqlite3_limit(
+            handle.as_raw_handle().as_ptr(),
+            libsqlite3_sys::SQLITE_LIMIT_ATTACHED,
+            0,
+        );
+    }
+    Ok(())
+}
+
+#[cfg(feature = "sql")]
+fn query_error(error: sqlx::Error) -> crate::Error {
+    crate::Error::Anyhow(format!("Query failed: {}", error))
+}
+
+/// Column names and values of a row, decoded by the storage class of each value.
+#[cfg(feature = "sql")]
+fn row_to_json(row: &sqlx::sqlite::SqliteRow) -> (Vec<String>, Map<String, Value>) {
+    use base64::Engine;
+    use sqlx::{Column, Row, TypeInfo, ValueRef};
+
+    let mut columns = Vec::new();
+    let mut values = Map::new();
+    for (index, column) in row.columns().iter().enumerate() {
+        let value = match row.try_get_raw(index) {
+            Ok(raw) if raw.is_null() => Value::Null,
+            Ok(raw) => match raw.type_info().name() {
+                "INTEGER" | "BOOLEAN" => row.try_get::<i64, _>(index).map(Value::from),
+                "REAL" | "NUMERIC" => row.try_get::<f64, _>(index).map(Value::from),
+                "BLOB" => row.try_get::<Vec<u8>, _>(index).map(|bytes| {
+                    Value::from(base64::engine::general_purpose::STANDARD.encode(bytes))
+                }),
+                _ => row.try_get::<String, _>(index).map(Value::from),
+            }
+            .unwrap_or(Value::Null),
+            Err(_) => Value::Null,
+        };
+        columns.push(column.name().to_string());
+        values.insert(column.name().to_string(), value);
+    }
+    (columns, values)
+}
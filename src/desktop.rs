@@ -1,169 +1,1154 @@
+use crate::app_logs::AppLogQuery;
+use crate::artifacts::{ArtifactStore, LocalDirStore};
+use crate::audit::AuditLog;
+use crate::cancellation::CancellationRegistry;
+use crate::client_config::{self, ClientConfig};
+use crate::clients::{ClientInfo, ClientRegistry};
+use crate::consent::{ConsentStatus, InputConsent};
+use crate::database::{Databases, QueryResult};
+use crate::dialogs::{DialogInterceptor, PendingDialog};
+#[cfg(desktop)]
+use crate::discovery::default_socket_path;
+use crate::downloads::DownloadLog;
+use crate::emulation::Emulation;
+use crate::emulation::EmulationSettings;
 use crate::error::Error;
+use crate::events::{EventBus, PollResult};
+use crate::files::FileRoots;
+use crate::handoff::{PauseStatus, ResumeOutcome, SessionPause};
+use crate::headless::HeadlessMode;
+use crate::hooks::{LifecycleHooks, ToolDecision, ToolInvocation};
+use crate::interactions::InteractionRecorder;
+use crate::js_errors::JsErrorLog;
+use crate::menu::MenuEntry;
+#[cfg(desktop)]
+use crate::menu::MenuHandlers;
 use crate::models::*;
+use crate::navigation::NavigationLog;
+use crate::overlay::ActionOverlay;
+use crate::permissions::PermissionGate;
+use crate::platform::focus::{self, FocusedWindow};
+#[cfg(desktop)]
+use crate::platform::input::EnigoInput;
+use crate::platform::input::{InputBackend, InputFactory};
+use crate::process_stats::{ProcessMonitor, ProcessStatsReport};
+use crate::recording::SessionRecorder;
+use crate::server_mode::{self, ServerMode};
+use crate::session_status::{SESSION_STATUS_EVENT, SessionActivity, SessionStatus};
+use crate::shared::commands;
 use crate::shared::{
     McpInterface, MouseMovementParams, MouseMovementResult, TextInputParams, TextInputResult,
     WindowManagerParams, WindowManagerResult,
 };
-use crate::socket_server::SocketServer;
-use crate::tools::mouse_movement;
-use crate::{PluginConfig, Result};
-use enigo::{Enigo, Keyboard, Settings};
-use log::info;
+use crate::shutdown::Shutdown;
+use crate::socket_server::{SocketServer, endpoint_address};
+use crate::state_inspector::StateInspector;
+use crate::status::{ServerStats, ServerStatus};
+use crate::timeouts::Timeouts;
+use crate::tools::{self, dom_input, mouse_movement};
+use crate::trace::Tracer;
+use crate::tray::TrayInfo;
+#[cfg(all(desktop, feature = "tray"))]
+use crate::tray::TrayRegistry;
+use crate::{PluginConfig, Result, SocketType};
+use log::{debug, info, warn};
+use serde::Serialize;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::sync::{Arc, Mutex};
 use std::thread;
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager, Runtime, plugin::PluginApi};
+#[cfg(mobile)]
+use tauri::plugin::PluginHandle;
+use tauri::{AppHandle, Emitter, Manager, Runtime, plugin::PluginApi};
+use tokio_util::sync::CancellationToken;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Package of the Kotlin side of the plugin, in `android/`.
+#[cfg(target_os = "android")]
+const ANDROID_PLUGIN_IDENTIFIER: &str = "com.plugin.mcp";
+
+// The Swift side of the plugin, in `ios/`
+#[cfg(target_os = "ios")]
+tauri::ios_plugin_binding!(init_plugin_mcp);
 
 // ----- TauriMcp Implementation -----
 
 pub fn init<R: Runtime, C: DeserializeOwned>(
     app: &AppHandle<R>,
-    _api: PluginApi<R, C>,
+    api: PluginApi<R, C>,
     config: &PluginConfig,
 ) -> crate::Result<TauriMcp<R>> {
-    let socket_server = if config.start_socket_server {
-        let mut server = SocketServer::new(app.clone(), config.socket_type.clone());
-        server.start()?;
-        Some(Arc::new(Mutex::new(server)))
+    #[cfg(target_os = "android")]
+    let mobile_plugin = api.register_android_plugin(ANDROID_PLUGIN_IDENTIFIER, "McpPlugin")?;
+    #[cfg(target_os = "ios")]
+    let mobile_plugin = api.register_ios_plugin(init_plugin_mcp)?;
+    #[cfg(desktop)]
+    let _ = api;
+    let mode = ServerMode::resolve(config);
+    let events = Arc::new(EventBus::new(config.event_buffer_capacity));
+    let hooks = Arc::new(LifecycleHooks::new(
+        config.on_client_connected.clone(),
+        config.on_client_disconnected.clone(),
+        config.on_tool_invoked.clone(),
+    ));
+    let emitter = app.clone();
+    let session_activity = Arc::new(SessionActivity::new(Arc::new(move |status| {
+        if let Err(e) = emitter.emit(SESSION_STATUS_EVENT, status) {
+            debug!("[TAURI_MCP] Failed to emit the session status: {}", e);
+        }
+    })));
+    let activity = session_activity.clone();
+    hooks.add_client_connected(Arc::new(move |_| activity.client_connected()));
+    let activity = session_activity.clone();
+    hooks.add_client_disconnected(Arc::new(move |_| activity.client_disconnected()));
+    let clients = Arc::new(ClientRegistry::with_hooks(hooks.clone()));
+    let stats = Arc::new(ServerStats::new());
+    let socket_type = match &config.socket_type {
+        #[cfg(mobile)]
+        SocketType::Ipc { path: None } => crate::mobile::default_socket_type(),
+        #[cfg(desktop)]
+        SocketType::Ipc { path: None } => SocketType::Ipc {
+            path: Some(default_socket_path(&config.application_name)),
+        },
+        socket_type => socket_type.clone(),
+    };
+    let socket_server = if mode.allows_socket_server() {
+        let mut server = SocketServer::new(
+            app.clone(),
+            socket_type.clone(),
+            events.clone(),
+            config.rate_limits,
+            clients.clone(),
+            stats.clone(),
+            config.application_name.clone(),
+        );
+        server.set_advertise_mdns(config.advertise_mdns);
+        server.set_auth_token(config.auth_token.clone());
+        server.set_keepalive(config.keepalive);
+        if config.start_socket_server {
+            server.start()?;
+        }
+        Some(Mutex::new(server))
     } else {
+        if config.start_socket_server {
+            warn!("[TAURI_MCP] Refusing to start socket server in release build without opt-in");
+        }
         None
     };
 
+    info!("[TAURI_MCP] Running in {:?} mode", mode);
+    let headless = HeadlessMode::resolve(config);
+    if let Some(reason) = headless.reason() {
+        warn!(
+            "[TAURI_MCP] Running headless, {}: input tools use DOM events and window operations are skipped",
+            reason
+        );
+    }
+
+    let artifacts = config.artifact_store.clone().unwrap_or_else(|| {
+        let root = app
+            .path()
+            .app_local_data_dir()
+            .unwrap_or_else(|_| std::env::temp_dir())
+            .join("mcp-artifacts");
+        info!("[TAURI_MCP] Storing artifacts in {}", root.display());
+        Arc::new(LocalDirStore::new(root))
+    });
+
+    let audit = AuditLog::new(
+        config.audit_capacity,
+        config.persist_audit_log.then(|| artifacts.clone()),
+    );
+
+    let recorder = if config.record_sessions {
+        let dir = config.session_dir.clone().unwrap_or_else(|| {
+            app.path()
+                .app_local_data_dir()
+                .unwrap_or_else(|_| std::env::temp_dir())
+                .join("mcp-sessions")
+        });
+        SessionRecorder::start(dir).unwrap_or_else(|e| {
+            warn!("[TAURI_MCP] Failed to start session recording: {}", e);
+            SessionRecorder::disabled()
+        })
+    } else {
+        SessionRecorder::disabled()
+    };
+
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .unwrap_or_else(|_| std::env::temp_dir());
+    let downloads = DownloadLog::new(
+        events.clone(),
+        config
+            .download_dir
+            .as_ref()
+            .map(|dir| app_data_dir.join(dir)),
+    );
+
     Ok(TauriMcp {
         app: app.clone(),
         socket_server,
         application_name: config.application_name.clone(),
+        mode,
+        allow_in_release: config.allow_in_release,
+        headless,
+        hooks,
+        session_activity,
+        artifacts,
+        audit,
+        events,
+        recorder,
+        cancellations: CancellationRegistry::new(),
+        clients,
+        socket_type,
+        auth_token: config.auth_token.clone(),
+        stats,
+        timeouts: config.timeouts.clone(),
+        shutdown: Shutdown::new(config.shutdown_timeout),
+        dialogs: DialogInterceptor::new(config.intercept_dialogs),
+        permissions: PermissionGate::new(),
+        interactions: InteractionRecorder::new(),
+        tracer: Tracer::new(),
+        session_pause: SessionPause::new(),
+        input_consent: InputConsent::new(config.require_input_consent),
+        input_backend: Mutex::new(None),
+        action_overlay: ActionOverlay::new(config.show_actions),
+        emulation: Emulation::new(),
+        process_monitor: ProcessMonitor::new(),
+        js_errors: JsErrorLog::new(),
+        navigations: NavigationLog::new(),
+        downloads,
+        state_inspector: StateInspector::new(),
+        allowed_stores: config.allowed_stores.clone(),
+        databases: Databases::new(config.databases.clone()),
+        file_roots: FileRoots::new(&config.file_roots, &app_data_dir),
+        #[cfg(desktop)]
+        menu_handlers: MenuHandlers::new(),
+        #[cfg(all(desktop, feature = "tray"))]
+        trays: TrayRegistry::new(),
+        #[cfg(mobile)]
+        mobile_plugin,
     })
 }
 
 /// Access to the tauri-mcp APIs.
 pub struct TauriMcp<R: Runtime> {
     app: AppHandle<R>,
-    socket_server: Option<Arc<Mutex<SocketServer<R>>>>,
+    /// None when the safety mode forbids the socket server.
+    socket_server: Option<Mutex<SocketServer<R>>>,
     application_name: String,
+    mode: ServerMode,
+    allow_in_release: bool,
+    headless: HeadlessMode,
+    hooks: Arc<LifecycleHooks>,
+    session_activity: Arc<SessionActivity>,
+    artifacts: Arc<dyn ArtifactStore>,
+    audit: AuditLog,
+    events: Arc<EventBus>,
+    recorder: SessionRecorder,
+    cancellations: CancellationRegistry,
+    clients: Arc<ClientRegistry>,
+    socket_type: SocketType,
+    auth_token: Option<String>,
+    stats: Arc<ServerStats>,
+    timeouts: Timeouts,
+    shutdown: Shutdown,
+    dialogs: DialogInterceptor,
+    permissions: PermissionGate,
+    interactions: InteractionRecorder,
+    tracer: Tracer,
+    session_pause: SessionPause,
+    input_consent: InputConsent<R>,
+    /// None for the OS input through enigo.
+    input_backend: Mutex<Option<InputFactory>>,
+    action_overlay: ActionOverlay,
+    emulation: Emulation,
+    process_monitor: ProcessMonitor,
+    js_errors: JsErrorLog,
+    navigations: NavigationLog,
+    downloads: DownloadLog,
+    state_inspector: StateInspector,
+    allowed_stores: Vec<std::path::PathBuf>,
+    databases: Databases,
+    file_roots: FileRoots,
+    #[cfg(desktop)]
+    menu_handlers: MenuHandlers<R>,
+    #[cfg(all(desktop, feature = "tray"))]
+    trays: TrayRegistry<R>,
+    /// The Kotlin or Swift side of the plugin.
+    #[cfg(mobile)]
+    mobile_plugin: PluginHandle<R>,
 }
 
 impl<R: Runtime> TauriMcp<R> {
+    /// Echo the request with the identity of the app, so clients can check which instance
+    /// they reached and measure the round trip.
     pub fn ping(&self, payload: PingRequest) -> crate::Result<PingResponse> {
         Ok(PingResponse {
             value: payload.value,
+            payload: payload.payload,
+            server_time_ms: crate::audit::now_ms(),
+            protocol_version: crate::PROTOCOL_VERSION,
+            app_name: self.application_name.clone(),
+            identifier: self.app.config().identifier.clone(),
+            app_version: self.app.package_info().version.to_string(),
+            pid: std::process::id(),
+            tauri_version: tauri::VERSION.to_string(),
+            plugin_version: env!("CARGO_PKG_VERSION").to_string(),
+        })
+    }
+
+    /// Report the active safety mode and whether the socket server is running.
+    pub fn server_mode(&self) -> ServerModeResponse {
+        ServerModeResponse {
+            mode: self.mode,
+            debug_build: cfg!(debug_assertions),
+            allow_in_release: self.allow_in_release,
+            env_opt_in: server_mode::env_opt_in(),
+            socket_server_running: self.is_socket_server_running(),
+        }
+    }
+
+    /// Configuration connecting an MCP client to this app, derived from the live socket
+    /// transport, application name and auth token.
+    pub fn generate_client_config(&self, request: GenerateClientConfigRequest) -> ClientConfig {
+        client_config::generate(
+            request,
+            &self.application_name,
+            &self.socket_type,
+            self.auth_token.as_deref(),
+        )
+    }
+
+    /// Whether the tools run headless, and why.
+    pub fn headless(&self) -> HeadlessMode {
+        self.headless
+    }
+
+    /// The input mode a tool uses for `requested`: headless, OS input becomes DOM events.
+    pub(crate) fn input_mode(&self, command: &str, requested: InputMode) -> InputMode {
+        if requested == InputMode::Os
+            && let Some(reason) = self.headless.reason()
+        {
+            warn!(
+                "[TAURI_MCP] Headless, {}: {} uses DOM events",
+                reason, command
+            );
+            return InputMode::Dom;
+        }
+        requested
+    }
+
+    /// Call `hook` when a client connects, like [`PluginConfig::on_client_connected`].
+    pub fn on_client_connected<F>(&self, hook: F)
+    where
+        F: Fn(&ClientInfo) + Send + Sync + 'static,
+    {
+        self.hooks.add_client_connected(Arc::new(hook));
+    }
+
+    /// Call `hook` when a client disconnects, like [`PluginConfig::on_client_disconnected`].
+    pub fn on_client_disconnected<F>(&self, hook: F)
+    where
+        F: Fn(&ClientInfo) + Send + Sync + 'static,
+    {
+        self.hooks.add_client_disconnected(Arc::new(hook));
+    }
+
+    /// Call `hook` before each tool call, like [`PluginConfig::on_tool_invoked`].
+    pub fn on_tool_invoked<F>(&self, hook: F)
+    where
+        F: Fn(&ToolInvocation) -> ToolDecision + Send + Sync + 'static,
+    {
+        self.hooks.add_tool_invoked(Arc::new(hook));
+    }
+
+    /// What the MCP session is doing: disconnected, idle or running a tool.
+    pub fn session_status(&self) -> SessionStatus {
+        self.session_activity.status()
+    }
+
+    /// Call `listener` whenever the session status changes, e.g. to show that an agent is
+    /// controlling the app. It replaces the previous listener. Webviews get the same
+    /// updates as `tauri-mcp:session-status` events.
+    pub fn set_status_listener<F>(&self, listener: F)
+    where
+        F: Fn(&SessionStatus) + Send + Sync + 'static,
+    {
+        self.session_activity.set_listener(Arc::new(listener));
+    }
+
+    pub(crate) fn session_activity(&self) -> &SessionActivity {
+        &self.session_activity
+    }
+
+    /// Callbacks of the host app on the socket server lifecycle.
+    pub(crate) fn hooks(&self) -> &LifecycleHooks {
+        &self.hooks
+    }
+
+    /// Whether the socket server is accepting connections.
+    pub fn is_socket_server_running(&self) -> bool {
+        self.socket_server
+            .as_ref()
+            .is_some_and(|server| server.lock().unwrap().is_running())
+    }
+
+    fn socket_server(&self) -> Result<&Mutex<SocketServer<R>>> {
+        self.socket_server.as_ref().ok_or_else(|| {
+            Error::PluginInit(
+                "Socket server is disabled in release builds without opt-in".to_string(),
+            )
         })
     }
 
+    /// Start the socket server. Does nothing if it is already running.
+    pub fn start_socket_server(&self) -> Result<()> {
+        self.socket_server()?.lock().unwrap().start()
+    }
+
+    /// Stop the socket server and release its socket.
+    ///
+    /// Connected clients are answered with an error and disconnected when they send their
+    /// next request.
+    pub fn stop_socket_server(&self) -> Result<()> {
+        self.socket_server()?.lock().unwrap().stop()
+    }
+
+    /// Stop the socket server gracefully, as the app does when it exits: new commands are
+    /// refused, the socket is released, in-flight commands get the shutdown timeout to
+    /// finish and the rest are cancelled with a shutdown error. Blocks until they answered.
+    ///
+    /// Tools waiting on a webview need the event loop, so call it off the main thread.
+    pub fn shut_down(&self) {
+        if !self.shutdown.begin() {
+            return;
+        }
+        info!("[TAURI_MCP] Shutting down");
+        if self.is_socket_server_running()
+            && let Err(e) = self.stop_socket_server()
+        {
+            warn!(
+                "[TAURI_MCP] Failed to stop socket server on shutdown: {}",
+                e
+            );
+        }
+        self.shutdown.drain();
+    }
+
+    pub(crate) fn shutdown(&self) -> &Shutdown {
+        &self.shutdown
+    }
+
+    /// Stop the socket server if it is running, then start it again.
+    pub fn restart_socket_server(&self) -> Result<()> {
+        let mut server = self.socket_server()?.lock().unwrap();
+        server.stop()?;
+        server.start()
+    }
+
+    /// Storage backend for session artifacts.
+    pub fn artifact_store(&self) -> &Arc<dyn ArtifactStore> {
+        &self.artifacts
+    }
+
+    /// Audit log of the socket commands executed against the app.
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    /// Event buffer used by long-polling clients.
+    pub fn events(&self) -> &EventBus {
+        &self.events
+    }
+
+    /// Crash-safe journal of the commands executed in this session.
+    pub fn session_recorder(&self) -> &SessionRecorder {
+        &self.recorder
+    }
+
+    /// Clients currently connected to the socket server.
+    pub fn clients(&self) -> &ClientRegistry {
+        &self.clients
+    }
+
+    /// How long tools wait for the webview.
+    pub fn timeouts(&self) -> &Timeouts {
+        &self.timeouts
+    }
+
+    /// Dialogs intercepted from the webview, waiting for a client to answer them.
+    pub fn dialogs(&self) -> &DialogInterceptor {
+        &self.dialogs
+    }
+
+    /// Policy answering the permission requests of the webviews, and the requests made.
+    pub fn permissions(&self) -> &PermissionGate {
+        &self.permissions
+    }
+
+    /// Recorder turning user interactions in the webviews into replayable scripts.
+    pub fn interactions(&self) -> &InteractionRecorder {
+        &self.interactions
+    }
+
+    /// Trace of the tool calls, for review after a run.
+    pub fn tracer(&self) -> &Tracer {
+        &self.tracer
+    }
+
+    /// Pause handing the app to a user, holding input commands until it is resumed.
+    pub fn session_pause(&self) -> &SessionPause {
+        &self.session_pause
+    }
+
+    /// The user's consent to input simulation, when required.
+    pub fn input_consent(&self) -> &InputConsent<R> {
+        &self.input_consent
+    }
+
+    /// Ask for consent to input simulation with the app's own prompt instead of the
+    /// plugin's native dialog. The prompt answers with
+    /// `input_consent().grant(app)` or `deny(app)`.
+    pub fn set_input_consent_prompt<F>(&self, prompt: F)
+    where
+        F: Fn(&AppHandle<R>) + Send + Sync + 'static,
+    {
+        self.input_consent.set_prompt(Arc::new(prompt));
+    }
+
+    /// Open the backend simulating OS input for a command.
+    pub fn input(&self) -> Result<Box<dyn InputBackend>> {
+        let factory = self.input_backend.lock().unwrap().clone();
+        match factory {
+            Some(factory) => factory(),
+            #[cfg(desktop)]
+            None => Ok(Box::new(EnigoInput::new()?)),
+            #[cfg(mobile)]
+            None => Err(crate::mobile::unsupported("OS input")),
+        }
+    }
+
+    /// Simulate OS input with another backend than enigo, e.g. a
+    /// [`RecordingInput`](crate::platform::input::RecordingInput) in tests. `factory` is
+    /// called once per input command.
+    pub fn set_input_backend<F>(&self, factory: F)
+    where
+        F: Fn() -> Result<Box<dyn InputBackend>> + Send + Sync + 'static,
+    {
+        *self.input_backend.lock().unwrap() = Some(Arc::new(factory));
+    }
+
+    /// Ghost cursor and outlines showing the actions of tools in the webviews.
+    pub fn action_overlay(&self) -> &ActionOverlay {
+        &self.action_overlay
+    }
+
+    /// Overrides of browser APIs in the webviews.
+    pub fn emulation(&self) -> &Emulation {
+        &self.emulation
+    }
+
+    /// Resource usage of the app process and its child processes.
+    pub fn process_monitor(&self) -> &ProcessMonitor {
+        &self.process_monitor
+    }
+
+    /// Uncaught errors and unhandled rejections reported by the webviews.
+    pub fn js_errors(&self) -> &JsErrorLog {
+        &self.js_errors
+    }
+
+    /// Page loads and client-side route changes of the webviews.
+    pub fn navigations(&self) -> &NavigationLog {
+        &self.navigations
+    }
+
+    /// Files downloaded by the webviews, and where new downloads are redirected.
+    pub fn downloads(&self) -> &DownloadLog {
+        &self.downloads
+    }
+
+    /// States exposed to clients by the app.
+    pub fn state_inspector(&self) -> &StateInspector {
+        &self.state_inspector
+    }
+
+    /// Expose a snapshot of app state to the `get_app_state` command, e.g.
+    /// `expose_state("settings", move || handle.state::<Settings>().snapshot())`.
+    pub fn expose_state<T, F>(&self, name: impl Into<String>, snapshot: F)
+    where
+        T: Serialize,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.state_inspector.expose(name, snapshot);
+    }
+
+    /// Store files clients may access with `manage_store`.
+    pub fn allowed_stores(&self) -> &[std::path::PathBuf] {
+        &self.allowed_stores
+    }
+
+    /// SQLite databases clients may query with `query_db`.
+    pub fn databases(&self) -> &Databases {
+        &self.databases
+    }
+
+    /// Directories clients may access with `read_app_file` and `write_app_file`.
+    pub fn file_roots(&self) -> &FileRoots {
+        &self.file_roots
+    }
+
+    /// Set a menu event handler for the whole app, like `AppHandle::on_menu_event`. Unlike
+    /// handlers set directly on the app, it is also called for the items clicked with
+    /// `click_menu_item` and `trigger_tray_menu_item`.
+    #[cfg(desktop)]
+    pub fn on_menu_event<F>(&self, handler: F)
+    where
+        F: Fn(&AppHandle<R>, tauri::menu::MenuEvent) + Send + Sync + 'static,
+    {
+        let installed = self.menu_handlers.add(Arc::new(handler));
+        self.app.on_menu_event(installed);
+    }
+
+    #[cfg(desktop)]
+    pub(crate) fn menu_handlers(&self) -> &MenuHandlers<R> {
+        &self.menu_handlers
+    }
+
+    /// The Kotlin or Swift side of the plugin, which takes webview snapshots.
+    #[cfg(mobile)]
+    pub(crate) fn mobile_plugin(&self) -> &PluginHandle<R> {
+        &self.mobile_plugin
+    }
+
+    /// Tray icons clients may drive with `trigger_tray_menu_item` and `click_tray`.
+    #[cfg(all(desktop, feature = "tray"))]
+    pub fn trays(&self) -> &TrayRegistry<R> {
+        &self.trays
+    }
+
+    /// Counters behind [`TauriMcp::server_status`].
+    pub fn server_stats(&self) -> &ServerStats {
+        &self.stats
+    }
+
+    /// Report whether the socket server is healthy: uptime, transport, clients, commands
+    /// served and last error.
+    pub fn server_status(&self) -> ServerStatus {
+        let transport = match self.socket_type {
+            SocketType::Ipc { .. } => "ipc",
+            SocketType::Tcp { .. } => "tcp",
+        };
+        ServerStatus {
+            running: self.is_socket_server_running(),
+            transport: transport.to_string(),
+            address: endpoint_address(&self.socket_type, &self.application_name),
+            uptime_ms: self.stats.uptime_ms(),
+            connected_clients: self.clients.list().len(),
+            commands_served: self.stats.commands_served(),
+            last_error: self.stats.last_error(),
+            paused: self.session_pause.is_paused(),
+        }
+    }
+
+    /// Cancellation tokens of in-flight socket requests.
+    pub(crate) fn cancellations(&self) -> &CancellationRegistry {
+        &self.cancellations
+    }
+
+    /// Run a socket tool in-process, bypassing the socket, and decode its response.
+    async fn call<P: Serialize, T: DeserializeOwned>(&self, command: &str, params: P) -> Result<T> {
+        let payload = serde_json::to_value(params)
+            .map_err(|e| Error::Anyhow(format!("Failed to serialize {} params: {}", command, e)))?;
+        let response =
+            tools::dispatch(&self.app, command, payload, &CancellationToken::new(), None).await?;
+        if !response.success {
+            return Err(Error::Anyhow(response.error.unwrap_or_else(|| {
+                format!("{} failed without specific error", command)
+            })));
+        }
+        serde_json::from_value(response.data.unwrap_or(Value::Null))
+            .map_err(|e| Error::Anyhow(format!("Invalid {} response: {}", command, e)))
+    }
+
+    /// Get the HTML content of a webview window.
+    pub async fn get_dom(&self, window_label: &str) -> Result<String> {
+        self.call(
+            commands::GET_DOM,
+            serde_json::json!({ "window_label": window_label }),
+        )
+        .await
+    }
+
+    /// Get a filtered, size-limited snapshot of the HTML of a webview window.
+    pub async fn get_dom_with_options(
+        &self,
+        window_label: &str,
+        options: DomSnapshotOptions,
+    ) -> Result<String> {
+        let mut params = serde_json::to_value(options)
+            .map_err(|e| Error::Anyhow(format!("Invalid get_dom options: {}", e)))?;
+        params["window_label"] = Value::from(window_label);
+        self.call(commands::GET_DOM, params).await
+    }
+
+    /// Get the rendered text of a webview window, or of the elements matching a selector.
+    pub async fn get_page_text(&self, params: PageTextRequest) -> Result<PageTextResponse> {
+        self.call(commands::GET_PAGE_TEXT, params).await
+    }
+
+    /// Get the URL, title, focused element and an outline of the controls of a webview
+    /// window in one call.
+    pub async fn get_ui_snapshot(&self, params: UiSnapshotRequest) -> Result<UiSnapshotResponse> {
+        self.call(commands::GET_UI_SNAPSHOT, params).await
+    }
+
+    /// Get the attributes, computed styles, geometry and state of an element.
+    pub async fn get_element_info(&self, params: ElementInfoRequest) -> Result<Value> {
+        self.call(commands::GET_ELEMENT_INFO, params).await
+    }
+
+    /// Get an element's screenshot, bounding box, text, attributes and whether it can be
+    /// interacted with, in one call.
+    pub async fn inspect_element(
+        &self,
+        params: InspectElementRequest,
+    ) -> Result<InspectElementResponse> {
+        self.call(commands::INSPECT_ELEMENT, params).await
+    }
+
+    /// Scroll an element into view unless it is already fully visible.
+    pub async fn scroll_into_view(
+        &self,
+        params: ScrollIntoViewRequest,
+    ) -> Result<ScrollIntoViewResponse> {
+        self.call(commands::SCROLL_INTO_VIEW, params).await
+    }
+
+    /// Outline elements found by selector for a while, to check what a selector matches.
+    pub async fn highlight_element(
+        &self,
+        params: HighlightElementRequest,
+    ) -> Result<HighlightElementResponse> {
+        self.call(commands::HIGHLIGHT_ELEMENT, params).await
+    }
+
+    /// Get the URL, title, loading state and history length of a window's page, and the
+    /// route of its client-side router when the app provides it.
+    pub async fn get_page_info(&self, params: PageInfoRequest) -> Result<PageInfoResponse> {
+        self.call(commands::GET_PAGE_INFO, params).await
+    }
+
+    /// Wait until a window navigates to a URL matching a pattern, or at all.
+    pub async fn wait_for_navigation(
+        &self,
+        params: WaitForNavigationRequest,
+    ) -> Result<WaitForNavigationResponse> {
+        self.call(commands::WAIT_FOR_NAVIGATION, params).await
+    }
+
+    /// Get the files downloaded by the webviews, optionally waiting for one to complete.
+    pub async fn get_downloads(&self, params: GetDownloadsRequest) -> Result<GetDownloadsResponse> {
+        self.call(commands::GET_DOWNLOADS, params).await
+    }
+
+    /// Play, pause, seek or mute an audio or video element and get its state.
+    pub async fn control_media(&self, params: ControlMediaRequest) -> Result<MediaState> {
+        self.call(commands::CONTROL_MEDIA, params).await
+    }
+
+    /// Capture the content of canvas elements as images.
+    pub async fn capture_canvas(
+        &self,
+        params: CaptureCanvasRequest,
+    ) -> Result<CaptureCanvasResponse> {
+        self.call(commands::CAPTURE_CANVAS, params).await
+    }
+
+    /// Print the page of a webview window to a PDF file.
+    pub async fn print_to_pdf(&self, params: PrintToPdfRequest) -> Result<PrintToPdfResponse> {
+        self.call(commands::PRINT_TO_PDF, params).await
+    }
+
+    /// Click an element found by selector, with DOM events or the OS mouse.
+    pub async fn click_element(&self, params: ClickElementRequest) -> Result<ClickElementResponse> {
+        self.call(commands::CLICK_ELEMENT, params).await
+    }
+
+    /// Choose a select option, check a checkbox or radio, or set an input's value.
+    pub async fn set_element_value(&self, params: SetElementValueRequest) -> Result<Value> {
+        self.call(commands::SET_ELEMENT_VALUE, params).await
+    }
+
+    /// Attach files from disk to an `<input type="file">`.
+    pub async fn set_file_input(&self, params: SetFileInputRequest) -> Result<Value> {
+        self.call(commands::SET_FILE_INPUT, params).await
+    }
+
+    /// Fill several form fields in one round trip.
+    pub async fn fill_form(&self, params: FillFormRequest) -> Result<Value> {
+        self.call(commands::FILL_FORM, params).await
+    }
+
+    /// Check that an element exists, or does not with `negate`.
+    pub async fn assert_element_exists(
+        &self,
+        params: AssertElementRequest,
+    ) -> Result<AssertionResult> {
+        self.call(commands::ASSERT_ELEMENT_EXISTS, params).await
+    }
+
+    /// Check that an element is rendered and has a size, or is not with `negate`.
+    pub async fn assert_element_visible(
+        &self,
+        params: AssertElementRequest,
+    ) -> Result<AssertionResult> {
+        self.call(commands::ASSERT_ELEMENT_VISIBLE, params).await
+    }
+
+    /// Check that the rendered text of an element or of the page contains a string.
+    pub async fn assert_text_contains(
+        &self,
+        params: AssertTextContainsRequest,
+    ) -> Result<AssertionResult> {
+        self.call(commands::ASSERT_TEXT_CONTAINS, params).await
+    }
+
+    /// Check the URL of a window.
+    pub async fn assert_url_matches(
+        &self,
+        params: AssertUrlMatchesRequest,
+    ) -> Result<AssertionResult> {
+        self.call(commands::ASSERT_URL_MATCHES, params).await
+    }
+
+    /// Run a list of commands in the app and report each step.
+    pub async fn run_script(&self, params: RunScriptRequest) -> Result<RunScriptResponse> {
+        self.call(commands::RUN_SCRIPT, params).await
+    }
+
+    /// Give keyboard focus to an element found by selector.
+    pub async fn focus_element(&self, params: FocusElementRequest) -> Result<Value> {
+        self.call(commands::FOCUS_ELEMENT, params).await
+    }
+
+    /// Remove keyboard focus from an element, or from the focused one.
+    pub async fn blur_element(&self, params: BlurElementRequest) -> Result<Value> {
+        self.call(commands::BLUR_ELEMENT, params).await
+    }
+
+    /// Freeze or shift the clock and seed the randomness of a window.
+    pub async fn set_emulation(&self, params: SetEmulationRequest) -> Result<EmulationSettings> {
+        self.call(commands::SET_EMULATION, params).await
+    }
+
+    /// Move the emulated clock of a window forward.
+    pub async fn advance_clock(&self, params: AdvanceClockRequest) -> Result<EmulationSettings> {
+        self.call(commands::ADVANCE_CLOCK, params).await
+    }
+
+    /// Override the position reported by `navigator.geolocation` in a window.
+    pub async fn set_geolocation(
+        &self,
+        params: SetGeolocationRequest,
+    ) -> Result<EmulationSettings> {
+        self.call(commands::SET_GEOLOCATION, params).await
+    }
+
+    /// Override the locale, languages and timezone seen by a window.
+    pub async fn set_locale(&self, params: SetLocaleRequest) -> Result<EmulationSettings> {
+        self.call(commands::SET_LOCALE, params).await
+    }
+
+    /// Switch the theme of a window, or report it when `theme` is absent.
+    pub async fn set_theme(&self, params: SetThemeRequest) -> Result<ThemeResponse> {
+        self.call(commands::SET_THEME, params).await
+    }
+
+    /// Emulate a device in a window, from a named profile or explicit settings.
+    pub async fn emulate_device(&self, params: EmulateDeviceRequest) -> Result<EmulationSettings> {
+        self.call(commands::EMULATE_DEVICE, params).await
+    }
+
+    /// Slow down or cut the `fetch` and `XMLHttpRequest` requests of a window.
+    pub async fn set_network_conditions(
+        &self,
+        params: SetNetworkConditionsRequest,
+    ) -> Result<EmulationSettings> {
+        self.call(commands::SET_NETWORK_CONDITIONS, params).await
+    }
+
+    /// Answer matching `fetch` and `XMLHttpRequest` requests of a window with a canned
+    /// response, or send them to another URL.
+    pub async fn add_mock_route(&self, params: AddMockRouteRequest) -> Result<EmulationSettings> {
+        self.call(commands::ADD_MOCK_ROUTE, params).await
+    }
+
+    /// Remove a mock route, or all of them.
+    pub async fn remove_mock_route(
+        &self,
+        params: RemoveMockRouteRequest,
+    ) -> Result<EmulationSettings> {
+        self.call(commands::REMOVE_MOCK_ROUTE, params).await
+    }
+
+    /// Report CPU, memory, open files and threads of the app and its child processes.
+    pub async fn get_process_stats(
+        &self,
+        params: GetProcessStatsRequest,
+    ) -> Result<ProcessStatsReport> {
+        self.call(commands::GET_PROCESS_STATS, params).await
+    }
+
+    /// Get the uncaught errors and unhandled rejections of the webviews.
+    pub async fn get_js_errors(&self, params: GetJsErrorsRequest) -> Result<JsErrorsResponse> {
+        self.call(commands::GET_JS_ERRORS, params).await
+    }
+
+    /// Get the host app's log records captured by the [`crate::LogBridge`].
+    pub async fn get_app_logs(&self, params: GetAppLogsRequest) -> Result<AppLogQuery> {
+        self.call(commands::GET_APP_LOGS, params).await
+    }
+
+    /// Get snapshots of the states exposed with [`TauriMcp::expose_state`].
+    pub async fn get_app_state(&self, params: GetAppStateRequest) -> Result<AppStateResponse> {
+        self.call(commands::GET_APP_STATE, params).await
+    }
+
+    /// Read or write the entries of an allowed tauri-plugin-store file.
+    pub async fn manage_store(&self, params: ManageStoreRequest) -> Result<StoreResponse> {
+        self.call(commands::MANAGE_STORE, params).await
+    }
+
+    /// Run SQL on a database configured for `query_db`.
+    pub async fn query_db(&self, params: QueryDbRequest) -> Result<QueryResult> {
+        self.call(commands::QUERY_DB, params).await
+    }
+
+    /// Read a file, or list a directory, under a configured file root.
+    pub async fn read_app_file(&self, params: ReadAppFileRequest) -> Result<AppFileResponse> {
+        self.call(commands::READ_APP_FILE, params).await
+    }
+
+    /// Write a file under a configured file root.
+    pub async fn write_app_file(
+        &self,
+        params: WriteAppFileRequest,
+    ) -> Result<WriteAppFileResponse> {
+        self.call(commands::WRITE_APP_FILE, params).await
+    }
+
+    /// Deliver a URL to the app as if it was opened through its custom scheme.
+    pub async fn trigger_deep_link(
+        &self,
+        params: TriggerDeepLinkRequest,
+    ) -> Result<TriggerDeepLinkResponse> {
+        self.call(commands::TRIGGER_DEEP_LINK, params).await
+    }
+
+    /// List the registered tray icons with their menus.
+    pub async fn list_trays(&self) -> Result<Vec<TrayInfo>> {
+        self.call(commands::LIST_TRAYS, serde_json::json!({})).await
+    }
+
+    /// Select a tray menu item as if the user clicked it.
+    pub async fn trigger_tray_menu_item(
+        &self,
+        params: TriggerTrayMenuItemRequest,
+    ) -> Result<MenuEntry> {
+        self.call(commands::TRIGGER_TRAY_MENU_ITEM, params).await
+    }
+
+    /// List the items of the app and window menus.
+    pub async fn list_menu(&self, params: ListMenuRequest) -> Result<MenuListResponse> {
+        self.call(commands::LIST_MENU, params).await
+    }
+
+    /// Select a menu bar item by ID or path as if the user clicked it.
+    pub async fn click_menu_item(
+        &self,
+        params: ClickMenuItemRequest,
+    ) -> Result<ClickMenuItemResponse> {
+        self.call(commands::CLICK_MENU_ITEM, params).await
+    }
+
+    /// Click a registered tray icon.
+    pub async fn click_tray(&self, params: ClickTrayRequest) -> Result<Value> {
+        self.call(commands::CLICK_TRAY, params).await
+    }
+
+    /// Pause the session for a user: show a banner and hold input commands.
+    pub async fn pause_session(&self, params: PauseSessionRequest) -> Result<PauseStatus> {
+        self.call(commands::PAUSE_SESSION, params).await
+    }
+
+    /// Resume a paused session, running or discarding the held input commands.
+    pub async fn resume_session(&self, params: ResumeSessionRequest) -> Result<ResumeOutcome> {
+        self.call(commands::RESUME_SESSION, params).await
+    }
+
+    /// Get whether input simulation needs and has the user's consent.
+    pub async fn get_input_consent(&self) -> Result<ConsentStatus> {
+        self.call(commands::GET_INPUT_CONSENT, serde_json::json!({}))
+            .await
+    }
+
+    /// Show the actions of tools with a ghost cursor and outlines, or stop showing them.
+    pub async fn set_action_overlay(&self, params: SetActionOverlayRequest) -> Result<Value> {
+        self.call(commands::SET_ACTION_OVERLAY, params).await
+    }
+
+    /// Start tracing the following tool calls into a zip bundle.
+    pub async fn start_trace(&self, params: StartTraceRequest) -> Result<Value> {
+        self.call(commands::START_TRACE, params).await
+    }
+
+    /// Stop tracing and store the bundle as an artifact.
+    pub async fn stop_trace(&self, params: StopTraceRequest) -> Result<StopTraceResponse> {
+        self.call(commands::STOP_TRACE, params).await
+    }
+
+    /// Convert a point of a window between the viewport, document, window and screen
+    /// coordinate spaces.
+    pub async fn convert_coordinates(
+        &self,
+        params: ConvertCoordinatesRequest,
+    ) -> Result<ConvertCoordinatesResponse> {
+        self.call(commands::CONVERT_COORDINATES, params).await
+    }
+
+    /// Press keys together, by name or by physical key code, and release them in reverse.
+    pub async fn simulate_key_press(&self, params: KeyPressRequest) -> Result<KeyPressResponse> {
+        self.call(commands::SIMULATE_KEY_PRESS, params).await
+    }
+
+    /// Tap, long-press, swipe or pinch in a page with synthetic touch events.
+    pub async fn simulate_touch(&self, params: TouchRequest) -> Result<TouchResponse> {
+        self.call(commands::SIMULATE_TOUCH, params).await
+    }
+
+    /// Start recording user interactions as a script.
+    pub async fn start_interaction_recording(
+        &self,
+        params: StartInteractionRecordingRequest,
+    ) -> Result<Value> {
+        self.call(commands::START_INTERACTION_RECORDING, params)
+            .await
+    }
+
+    /// Stop recording user interactions and get the recorded script.
+    pub async fn stop_interaction_recording(
+        &self,
+        params: StopInteractionRecordingRequest,
+    ) -> Result<StopInteractionRecordingResponse> {
+        self.call(commands::STOP_INTERACTION_RECORDING, params)
+            .await
+    }
+
+    /// List the intercepted dialogs waiting for an answer.
+    pub async fn list_dialogs(&self) -> Result<DialogsResponse> {
+        self.call(commands::LIST_DIALOGS, serde_json::json!({}))
+            .await
+    }
+
+    /// Answer an intercepted dialog.
+    pub async fn respond_to_dialog(&self, params: RespondToDialogRequest) -> Result<PendingDialog> {
+        self.call(commands::RESPOND_TO_DIALOG, params).await
+    }
+
+    /// Enable or disable dialog interception and queue automatic answers.
+    pub async fn set_dialog_interception(
+        &self,
+        params: SetDialogInterceptionRequest,
+    ) -> Result<DialogsResponse> {
+        self.call(commands::SET_DIALOG_INTERCEPTION, params).await
+    }
+
+    /// Set or remove the policy answering alert, confirm and prompt dialogs.
+    pub async fn set_dialog_policy(
+        &self,
+        params: SetDialogPolicyRequest,
+    ) -> Result<DialogsResponse> {
+        self.call(commands::SET_DIALOG_POLICY, params).await
+    }
+
+    /// Pre-answer the permission requests of the webviews and get the ones made so far.
+    pub async fn set_permission_policy(
+        &self,
+        params: SetPermissionPolicyRequest,
+    ) -> Result<PermissionsResponse> {
+        self.call(commands::SET_PERMISSION_POLICY, params).await
+    }
+
+    /// Execute JavaScript in a webview window and return the stringified result.
+    pub async fn execute_js(&self, params: ExecuteJsRequest) -> Result<ExecuteJsResponse> {
+        self.call(commands::EXECUTE_JS, params).await
+    }
+
+    /// Get, set, remove or clear localStorage entries of a webview window.
+    pub async fn manage_local_storage(&self, params: LocalStorageRequest) -> Result<Value> {
+        self.call(commands::MANAGE_LOCAL_STORAGE, params).await
+    }
+
+    /// Find an element and return its position, optionally clicking it.
+    pub async fn get_element_position(&self, params: ElementPositionRequest) -> Result<Value> {
+        self.call(commands::GET_ELEMENT_POSITION, params).await
+    }
+
+    /// Type text into an element found by selector.
+    pub async fn send_text_to_element(&self, params: SendTextToElementRequest) -> Result<Value> {
+        self.call(commands::SEND_TEXT_TO_ELEMENT, params).await
+    }
+
+    /// Describe the available tools and platform capabilities.
+    pub async fn describe_tools(
+        &self,
+        params: DescribeToolsRequest,
+    ) -> Result<DescribeToolsResponse> {
+        self.call(commands::DESCRIBE_TOOLS, params).await
+    }
+
+    /// Wait for plugin events after a cursor.
+    pub async fn poll_events(&self, params: PollEventsRequest) -> Result<PollResult> {
+        self.call(commands::POLL_EVENTS, params).await
+    }
+
+    /// List the clients connected to the socket server.
+    pub async fn list_clients(&self) -> Result<Vec<ClientInfo>> {
+        self.call(commands::LIST_CLIENTS, serde_json::json!({}))
+            .await
+    }
+
+    /// Load the recording of the previous session.
+    pub async fn recover_last_session(&self) -> Result<RecoverSessionResponse> {
+        self.call(commands::RECOVER_LAST_SESSION, serde_json::json!({}))
+            .await
+    }
+
     // Add async method to perform window operations
     pub async fn manage_window_async(
         &self,
         params: WindowManagerRequest,
     ) -> Result<WindowManagerResponse> {
-        let window_label = params.window_label.unwrap_or_else(|| "main".to_string());
-
-        // Get the window by label
-        let window = self.app.get_webview_window(&window_label).ok_or_else(|| {
-            Error::WindowOperationFailed(format!("Window not found: {}", window_label))
-        })?;
+        let window = crate::windows::resolve_window(
+            &self.app,
+            params.window_label.as_deref().unwrap_or_default(),
+        )?;
 
-        // Execute the requested operation
-        match params.operation.as_str() {
-            "minimize" => {
-                window.minimize()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "maximize" => {
-                window.maximize()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "unmaximize" => {
-                window.unmaximize()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "close" => {
-                window.close()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "show" => {
-                window.show()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "hide" => {
-                window.hide()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "setPosition" => {
-                if let (Some(x), Some(y)) = (params.x, params.y) {
-                    window.set_position(tauri::Position::Physical(tauri::PhysicalPosition {
-                        x,
-                        y,
-                    }))?;
-                    Ok(WindowManagerResponse {
-                        success: true,
-                        error: None,
-                    })
-                } else {
-                    Err(Error::WindowOperationFailed(
-                        "setPosition requires x and y coordinates".to_string(),
-                    ))
-                }
-            }
-            "setSize" => {
-                if let (Some(width), Some(height)) = (params.width, params.height) {
-                    window
-                        .set_size(tauri::Size::Physical(tauri::PhysicalSize { width, height }))?;
-                    Ok(WindowManagerResponse {
-                        success: true,
-                        error: None,
-                    })
-                } else {
-                    Err(Error::WindowOperationFailed(
-                        "setSize requires width and height parameters".to_string(),
-                    ))
-                }
-            }
-            "center" => {
-                window.center()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "toggleFullscreen" => {
-                let is_fullscreen = window.is_fullscreen()?;
-                window.set_fullscreen(!is_fullscreen)?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            "focus" => {
-                window.set_focus()?;
-                Ok(WindowManagerResponse {
-                    success: true,
-                    error: None,
-                })
-            }
-            _ => Err(Error::WindowOperationFailed(format!(
-                "Unknown window operation: {}",
-                params.operation
-            ))),
+        if let Some(reason) = self.headless.reason() {
+            let warning = format!("Headless, {}: {} was skipped", reason, params.operation);
+            warn!("[TAURI_MCP] {}", warning);
+            return Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: Some(warning),
+            });
         }
+        window_operation(&window, &params)
     }
 
     // Text input simulation
@@ -171,41 +1156,125 @@ impl<R: Runtime> TauriMcp<R> {
         &self,
         params: TextInputRequest,
     ) -> crate::Result<TextInputResponse> {
-        let text = params.text;
+        self.simulate_text_input_cancellable(params, &CancellationToken::new())
+            .await
+    }
+
+    /// Simulate text input, stopping between two graphemes once `cancel` is triggered.
+    pub async fn simulate_text_input_cancellable(
+        &self,
+        params: TextInputRequest,
+        cancel: &CancellationToken,
+    ) -> crate::Result<TextInputResponse> {
+        let window = crate::windows::resolve_window(&self.app, &params.window_label)?;
+
+        let input_mode = self.input_mode(commands::SIMULATE_TEXT_INPUT, params.input_mode);
+        if input_mode == InputMode::Dom {
+            // Synthetic events reach the page whatever has the focus, so no initial delay
+            // is needed unless asked for
+            if let Some(initial_delay_ms) = params.initial_delay_ms {
+                tokio::time::sleep(Duration::from_millis(initial_delay_ms)).await;
+            }
+            let start_time = Instant::now();
+            let timeout = self.timeouts.for_tool(commands::SIMULATE_TEXT_INPUT);
+            let (chars_typed, failures) = dom_input::type_text(
+                &window,
+                &params.text,
+                params.strategy == TextInputStrategy::Paste,
+                Duration::from_millis(params.delay_ms.unwrap_or(20)),
+                cancel,
+                timeout,
+            )
+            .await?;
+            return Ok(TextInputResponse {
+                chars_typed,
+                duration_ms: start_time.elapsed().as_millis() as u64,
+                failures,
+            });
+        }
+
+        // OS input goes to whatever has the focus
+        let previous = if params.focus_window {
+            let previous = focus::focused_window(&self.app);
+            focus::focus(&window)?;
+            previous
+        } else {
+            None
+        };
+        let result = self.type_with_os_input(&params, cancel);
+        if params.restore_focus
+            && let Some(previous) = previous
+            && previous != FocusedWindow::App(window.label().to_string())
+        {
+            focus::restore(&self.app, &previous);
+        }
+        result
+    }
+
+    fn type_with_os_input(
+        &self,
+        params: &TextInputRequest,
+        cancel: &CancellationToken,
+    ) -> crate::Result<TextInputResponse> {
+        let text = &params.text;
         let delay_ms = params.delay_ms.unwrap_or(20);
         let initial_delay_ms = params.initial_delay_ms.unwrap_or(500);
 
-        // Create Enigo instance with the latest API
-        let mut enigo = Enigo::new(&Settings::default())
-            .map_err(|e| Error::Anyhow(format!("Failed to initialize Enigo: {}", e)))?;
+        let mut input = self.input()?;
 
-        // Initial delay before typing
+        // Initial delay before typing, which also lets a focused window come up
         if initial_delay_ms > 0 {
             thread::sleep(Duration::from_millis(initial_delay_ms));
         }
 
         let start_time = Instant::now();
 
-        // Use the text method from the Keyboard trait
-        if delay_ms == 0 {
-            // Fast typing (all at once)
-            Keyboard::text(&mut enigo, &text)
-                .map_err(|e| Error::Anyhow(format!("Failed to simulate text input: {}", e)))?;
-        } else {
-            // Slow typing with configurable delay
-            for c in text.chars() {
-                Keyboard::text(&mut enigo, &c.to_string())
-                    .map_err(|e| Error::Anyhow(format!("Failed to simulate text input: {}", e)))?;
+        if params.strategy == TextInputStrategy::Paste {
+            input.paste(text)?;
+            return Ok(TextInputResponse {
+                chars_typed: text.chars().count() as u32,
+                duration_ms: start_time.elapsed().as_millis() as u64,
+                failures: Vec::new(),
+            });
+        }
 
+        // Type grapheme by grapheme, so emoji sequences, combining marks and flags go out
+        // whole, and one the platform cannot type does not stop the rest
+        let mut chars_typed = 0;
+        let mut failures = Vec::new();
+        for (index, grapheme) in text.graphemes(true).enumerate() {
+            if cancel.is_cancelled() {
+                return Err(Error::Cancelled(format!(
+                    "text input stopped after {} characters",
+                    chars_typed
+                )));
+            }
+            match input.text(grapheme) {
+                Ok(()) => chars_typed += grapheme.chars().count() as u32,
+                Err(e) => failures.push(TextInputFailure {
+                    index: index as u32,
+                    text: grapheme.to_string(),
+                    error: e.to_string(),
+                }),
+            }
+
+            if delay_ms > 0 {
                 thread::sleep(Duration::from_millis(delay_ms));
             }
         }
+        // Nothing typed at all is a broken input, not a few odd characters
+        if chars_typed == 0
+            && let Some(failure) = failures.first()
+        {
+            return Err(Error::Anyhow(failure.error.clone()));
+        }
 
         let duration_ms = start_time.elapsed().as_millis() as u64;
 
         Ok(TextInputResponse {
-            chars_typed: text.chars().count() as u32,
+            chars_typed,
             duration_ms,
+            failures,
         })
     }
 
@@ -221,7 +1290,7 @@ impl<R: Runtime> TauriMcp<R> {
 impl<R: Runtime> Drop for TauriMcp<R> {
     fn drop(&mut self) {
         if let Some(server) = &self.socket_server {
-            if let Ok(server) = server.lock() {
+            if let Ok(mut server) = server.lock() {
                 let _ = server.stop();
             }
         }
@@ -249,6 +1318,7 @@ impl<R: Runtime> McpInterface for TauriMcp<R> {
             Ok(response) => Ok(WindowManagerResult {
                 success: response.success,
                 error: response.error,
+                warning: response.warning,
             }),
             Err(e) => Err(e.to_string()),
         }
@@ -267,6 +1337,11 @@ impl<R: Runtime> McpInterface for TauriMcp<R> {
             text: params.text,
             delay_ms: params.delay_ms,
             initial_delay_ms: params.initial_delay_ms,
+            input_mode: params.input_mode,
+            strategy: params.strategy,
+            window_label: params.window_label,
+            focus_window: params.focus_window,
+            restore_focus: params.restore_focus,
         };
 
         // Run async method
@@ -279,12 +1354,14 @@ impl<R: Runtime> McpInterface for TauriMcp<R> {
                 chars_typed: response.chars_typed,
                 duration_ms: response.duration_ms,
                 error: None,
+                failures: response.failures,
             }),
             Err(e) => Ok(TextInputResult {
                 success: false,
                 chars_typed: 0,
                 duration_ms: 0,
                 error: Some(e.to_string()),
+                failures: Vec::new(),
             }),
         }
     }
@@ -296,3 +1373,127 @@ impl<R: Runtime> McpInterface for TauriMcp<R> {
         crate::tools::mouse_movement::simulate_mouse_movement_shared(&self.app, params)
     }
 }
+
+/// Apply a `manage_window` operation to `window`.
+#[cfg(desktop)]
+fn window_operation<R: Runtime>(
+    window: &tauri::WebviewWindow<R>,
+    params: &WindowManagerRequest,
+) -> Result<WindowManagerResponse> {
+    match params.operation.as_str() {
+        "minimize" => {
+            window.minimize()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "maximize" => {
+            window.maximize()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "unmaximize" => {
+            window.unmaximize()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "close" => {
+            window.close()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "show" => {
+            window.show()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "hide" => {
+            window.hide()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "setPosition" => {
+            if let (Some(x), Some(y)) = (params.x, params.y) {
+                window.set_position(tauri::Position::Physical(tauri::PhysicalPosition { x, y }))?;
+                Ok(WindowManagerResponse {
+                    success: true,
+                    error: None,
+                    warning: None,
+                })
+            } else {
+                Err(Error::WindowOperationFailed(
+                    "setPosition requires x and y coordinates".to_string(),
+                ))
+            }
+        }
+        "setSize" => {
+            if let (Some(width), Some(height)) = (params.width, params.height) {
+                window.set_size(tauri::Size::Physical(tauri::PhysicalSize { width, height }))?;
+                Ok(WindowManagerResponse {
+                    success: true,
+                    error: None,
+                    warning: None,
+                })
+            } else {
+                Err(Error::WindowOperationFailed(
+                    "setSize requires width and height parameters".to_string(),
+                ))
+            }
+        }
+        "center" => {
+            window.center()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "toggleFullscreen" => {
+            let is_fullscreen = window.is_fullscreen()?;
+            window.set_fullscreen(!is_fullscreen)?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        "focus" => {
+            window.set_focus()?;
+            Ok(WindowManagerResponse {
+                success: true,
+                error: None,
+                warning: None,
+            })
+        }
+        _ => Err(Error::WindowOperationFailed(format!(
+            "Unknown window operation: {}",
+            params.operation
+        ))),
+    }
+}
+
+/// Apply a `manage_window` operation to `window`.
+#[cfg(mobile)]
+fn window_operation<R: Runtime>(
+    _window: &tauri::WebviewWindow<R>,
+    _params: &WindowManagerRequest,
+) -> Result<WindowManagerResponse> {
+    Err(crate::mobile::unsupported("Window management"))
+}
@@ -0,0 +1,109 @@
+/// Viewport, pixel ratio, touch support and user agent of a device.
+#[derive(Debug, Clone, Copy)]
+pub struct DeviceProfile {
+    pub name: &'static str,
+    /// Viewport size in CSS pixels, in portrait orientation.
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub touch: bool,
+    /// None keeps the user agent of the webview.
+    pub user_agent: Option<&'static str>,
+}
+
+const IPHONE_USER_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+const IPAD_USER_AGENT: &str = "Mozilla/5.0 (iPad; CPU OS 17_0 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.0 Mobile/15E148 Safari/604.1";
+
+/// Profiles known to `emulate_device`.
+pub const DEVICE_PROFILES: &[DeviceProfile] = &[
+    DeviceProfile {
+        name: "iPhone SE",
+        width: 375,
+        height: 667,
+        device_scale_factor: 2.0,
+        touch: true,
+        user_agent: Some(IPHONE_USER_AGENT),
+    },
+    DeviceProfile {
+        name: "iPhone 15",
+        width: 393,
+        height: 852,
+        device_scale_factor: 3.0,
+        touch: true,
+        user_agent: Some(IPHONE_USER_AGENT),
+    },
+    DeviceProfile {
+        name: "iPhone 15 Pro Max",
+        width: 430,
+        height: 932,
+        device_scale_factor: 3.0,
+        touch: true,
+        user_agent: Some(IPHONE_USER_AGENT),
+    },
+    DeviceProfile {
+        name: "Pixel 8",
+        width: 412,
+        height: 915,
+        device_scale_factor: 2.625,
+        touch: true,
+        user_agent: Some(
+            "Mozilla/5.0 (Linux; Android 14; Pixel 8) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+        ),
+    },
+    DeviceProfile {
+        name: "Galaxy S23",
+        width: 360,
+        height: 780,
+        device_scale_factor: 3.0,
+        touch: true,
+        user_agent: Some(
+            "Mozilla/5.0 (Linux; Android 14; SM-S911B) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Mobile Safari/537.36",
+        ),
+    },
+    DeviceProfile {
+        name: "iPad Air",
+        width: 820,
+        height: 1180,
+        device_scale_factor: 2.0,
+        touch: true,
+        user_agent: Some(IPAD_USER_AGENT),
+    },
+    DeviceProfile {
+        name: "iPad Pro 12.9",
+        width: 1024,
+        height: 1366,
+        device_scale_factor: 2.0,
+        touch: true,
+        user_agent: Some(IPAD_USER_AGENT),
+    },
+    DeviceProfile {
+        name: "Laptop",
+        width: 1366,
+        height: 768,
+        device_scale_factor: 1.0,
+        touch: false,
+        user_agent: None,
+    },
+    DeviceProfile {
+        name: "Desktop",
+        width: 1920,
+        height: 1080,
+        device_scale_factor: 1.0,
+        touch: false,
+        user_agent: None,
+    },
+];
+
+/// Find a profile by name, ignoring case, spaces, dashes and underscores.
+pub fn find_device(name: &str) -> Option<&'static DeviceProfile> {
+    let normalize = |name: &str| {
+        name.chars()
+            .filter(|c| !matches!(c, ' ' | '-' | '_'))
+            .flat_map(char::to_lowercase)
+            .collect::<String>()
+    };
+    let name = normalize(name);
+    DEVICE_PROFILES
+        .iter()
+        .find(|profile| normalize(profile.name) == name)
+}
@@ -0,0 +1,419 @@
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::audit::now_ms;
+
+/// Event emitted by the webview hook when a dialog would open.
+pub(crate) const DIALOG_OPENED_EVENT: &str = "tauri-mcp:dialog-opened";
+
+/// Event emitted by the webview hook when the dialog policy answered a dialog.
+pub(crate) const DIALOG_ANSWERED_EVENT: &str = "tauri-mcp:dialog-answered";
+
+/// Number of answered dialogs kept.
+const ANSWERED_CAPACITY: usize = 200;
+
+/// Injected in every webview. The IPC functions of `__TAURI_INTERNALS__` are frozen, but
+/// the custom protocol IPC goes through the global `fetch`, so the hook wraps it and
+/// answers `plugin:dialog|*` calls itself while interception is enabled.
+///
+/// `alert`, `confirm` and `prompt` block the page until they return, so they cannot wait
+/// for a client: while a policy is set, the hook answers them right away according to it
+/// and reports the answer. The policy is kept in the session storage to survive reloads.
+const DIALOG_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_DIALOGS__ || typeof window.fetch !== 'function') return;
+    const POLICY_KEY = '__tauri_mcp_dialog_policy__';
+    const state = { enabled: __ENABLED__, pending: new Map(), next: 0, policy: null };
+    try {
+        state.policy = JSON.parse(sessionStorage.getItem(POLICY_KEY) || 'null');
+    } catch (_) {}
+    state.resolve = (dialogId, value) => {
+        const resolve = state.pending.get(dialogId);
+        if (resolve) {
+            state.pending.delete(dialogId);
+            resolve(value);
+        }
+    };
+    state.setPolicy = (policy) => {
+        state.policy = policy;
+        try {
+            if (policy) sessionStorage.setItem(POLICY_KEY, JSON.stringify(policy));
+            else sessionStorage.removeItem(POLICY_KEY);
+        } catch (_) {}
+    };
+    window.__TAURI_MCP_DIALOGS__ = state;
+
+    // The first matching rule answers, then the defaults of the policy
+    const answer = (kind, message, defaultValue) => {
+        const policy = state.policy;
+        const text = message === undefined ? '' : String(message);
+        let value;
+        let rule = null;
+        (policy.rules || []).some((candidate, index) => {
+            if (candidate.kind && candidate.kind !== kind) return false;
+            if (candidate.message_contains != null && !text.includes(candidate.message_contains)) return false;
+            value = candidate.answer === undefined ? null : candidate.answer;
+            rule = index;
+            return true;
+        });
+        if (rule === null) {
+            if (kind === 'confirm' || kind === 'ask') value = policy.accept_confirms !== false;
+            else if (kind === 'prompt') value = policy.accept_prompts !== false ? (defaultValue == null ? '' : String(defaultValue)) : null;
+            else if (kind === 'message') value = 'Ok';
+            else value = null;
+        }
+        if (kind === 'confirm' || kind === 'ask') value = Boolean(value);
+        else if (kind === 'prompt') value = value == null ? null : String(value);
+        else if (kind === 'alert') value = null;
+        const internals = window.__TAURI_INTERNALS__;
+        if (internals) {
+            internals.invoke('plugin:event|emit', {
+                event: '__ANSWERED_EVENT__',
+                payload: {
+                    windowLabel: internals.metadata.currentWindow.label,
+                    kind,
+                    message: text,
+                    defaultValue: defaultValue == null ? null : String(defaultValue),
+                    answer: value,
+                    rule,
+                },
+            }).catch(() => {});
+        }
+        return value;
+    };
+    const nativeAlert = window.alert;
+    const nativeConfirm = window.confirm;
+    const nativePrompt = window.prompt;
+    window.alert = function (message) {
+        if (!state.policy) return nativeAlert.apply(this, arguments);
+        answer('alert', message);
+    };
+    window.confirm = function (message) {
+        if (!state.policy) return nativeConfirm.apply(this, arguments);
+        return answer('confirm', message);
+    };
+    window.prompt = function (message, defaultValue) {
+        if (!state.policy) return nativePrompt.apply(this, arguments);
+        return answer('prompt', message, defaultValue);
+    };
+
+    const originalFetch = window.fetch;
+    const DIALOG_COMMAND = /^plugin:dialog\|(open|save|message|ask|confirm)$/;
+    const POLICY_KINDS = ['message', 'ask', 'confirm'];
+    window.fetch = function (input, init) {
+        const internals = window.__TAURI_INTERNALS__;
+        let command = null;
+        try {
+            const url = new URL(typeof input === 'string' ? input : input.url);
+            if (url.protocol === 'ipc:' || url.hostname === 'ipc.localhost') {
+                command = decodeURIComponent(url.pathname.replace(/^\//, ''));
+            }
+        } catch (_) {}
+        const match = command && DIALOG_COMMAND.exec(command);
+        const byPolicy = match && state.policy && state.policy.plugin_dialogs && POLICY_KINDS.includes(match[1]);
+        if (!(state.enabled || byPolicy) || !match || !internals) return originalFetch.apply(this, arguments);
+
+        let args = null;
+        try {
+            args = init && typeof init.body === 'string' ? JSON.parse(init.body) : null;
+        } catch (_) {}
+        if (byPolicy) {
+            const value = answer(match[1], args && args.message);
+            return Promise.resolve(new Response(JSON.stringify(value), {
+                headers: { 'Content-Type': 'application/json', 'Tauri-Response': 'ok' },
+            }));
+        }
+        const dialogId = `${Date.now().toString(36)}-${++state.next}`;
+        return new Promise((resolve) => {
+            state.pending.set(dialogId, (value) => resolve(new Response(JSON.stringify(value === undefined ? null : value), {
+                headers: { 'Content-Type': 'application/json', 'Tauri-Response': 'ok' },
+            })));
+            internals.invoke('plugin:event|emit', {
+                event: '__EVENT__',
+                payload: {
+                    dialogId,
+                    windowLabel: internals.metadata.currentWindow.label,
+                    kind: match[1],
+                    options: args && args.options !== undefined ? args.options : args,
+                },
+            });
+        });
+    };
+})();
+"#;
+
+/// A dialog that the app tried to open while interception was enabled.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingDialog {
+    pub dialog_id: String,
+    pub window_label: String,
+    /// "open", "save", "message", "ask" or "confirm".
+    pub kind: String,
+    /// Options passed by the app (title, filters, default path, buttons...).
+    pub options: Value,
+    #[serde(default)]
+    pub opened_ms: u64,
+}
+
+/// How `alert`, `confirm` and `prompt` dialogs of the webviews are answered, and
+/// optionally the message dialogs of the dialog plugin.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DialogPolicy {
+    /// Answer confirm and ask dialogs no rule matches with OK (the default) or Cancel.
+    #[serde(default = "default_true")]
+    pub accept_confirms: bool,
+    /// Answer prompts no rule matches with their default value (the default), or cancel them.
+    #[serde(default = "default_true")]
+    pub accept_prompts: bool,
+    /// Rules tried in order before the defaults.
+    #[serde(default)]
+    pub rules: Vec<DialogRule>,
+    /// Also answer the message, ask and confirm dialogs of the dialog plugin opened from the
+    /// webview, instead of showing or intercepting them.
+    #[serde(default)]
+    pub plugin_dialogs: bool,
+}
+
+/// Answer of the dialogs matching a [`DialogPolicy`] rule.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct DialogRule {
+    /// "alert", "confirm", "prompt", or "message" and "ask" for the dialog plugin; any
+    /// kind when absent.
+    pub kind: Option<String>,
+    /// Text the message has to contain, any message when absent.
+    pub message_contains: Option<String>,
+    /// Boolean for confirm and ask dialogs, text for prompts (null cancels), button for
+    /// plugin message dialogs; ignored for alerts.
+    #[serde(default)]
+    pub answer: Value,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// A dialog answered by the policy.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnsweredDialog {
+    pub window_label: String,
+    pub kind: String,
+    pub message: String,
+    /// Default value of a prompt.
+    pub default_value: Option<String>,
+    pub answer: Value,
+    /// Index of the rule that answered, None when the defaults did.
+    pub rule: Option<usize>,
+    #[serde(default)]
+    pub answered_ms: u64,
+}
+
+#[derive(Default)]
+struct DialogState {
+    pending: Vec<PendingDialog>,
+    /// Answers given to the next dialogs, in order, before any client is asked.
+    auto_responses: VecDeque<Value>,
+    policy: Option<DialogPolicy>,
+    answered: VecDeque<AnsweredDialog>,
+}
+
+/// Intercepts the dialogs of the Tauri dialog plugin opened from the webview, so clients
+/// are notified and answer them instead of a user, and answers `alert`, `confirm` and
+/// `prompt` according to a [`DialogPolicy`].
+///
+/// Dialogs opened from Rust with the dialog plugin are not intercepted.
+pub struct DialogInterceptor {
+    enabled: AtomicBool,
+    state: Mutex<DialogState>,
+}
+
+impl DialogInterceptor {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            state: Mutex::new(DialogState::default()),
+        }
+    }
+
+    /// Script hooking the dialog plugin in a webview, initially enabled or not.
+    pub(crate) fn init_script(enabled: bool) -> String {
+        DIALOG_HOOK_SCRIPT
+            .replace("__ENABLED__", if enabled { "true" } else { "false" })
+            .replace("__ANSWERED_EVENT__", DIALOG_ANSWERED_EVENT)
+            .replace("__EVENT__", DIALOG_OPENED_EVENT)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Enable or disable interception in every webview.
+    pub fn set_enabled<R: Runtime>(&self, app: &AppHandle<R>, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        for (label, window) in app.webview_windows() {
+            if let Err(e) = window.eval(self.sync_script()) {
+                warn!(
+                    "[TAURI_MCP] Failed to update dialog interception in {}: {}",
+                    label, e
+                );
+            }
+        }
+        info!(
+            "[TAURI_MCP] Dialog interception {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Apply the current interception state to a webview, e.g. after a page load.
+    pub(crate) fn sync<R: Runtime>(&self, webview: &tauri::Webview<R>) {
+        if let Err(e) = webview.eval(self.sync_script()) {
+            warn!(
+                "[TAURI_MCP] Failed to update dialog interception in {}: {}",
+                webview.label(),
+                e
+            );
+        }
+    }
+
+    fn sync_script(&self) -> String {
+        let policy = serde_json::to_string(&self.policy()).unwrap_or_else(|_| "null".into());
+        format!(
+            "if (window.__TAURI_MCP_DIALOGS__) {{ window.__TAURI_MCP_DIALOGS__.enabled = {}; window.__TAURI_MCP_DIALOGS__.setPolicy({}); }}",
+            self.is_enabled(),
+            policy
+        )
+    }
+
+    /// The policy answering `alert`, `confirm` and `prompt`, None when they show normally.
+    pub fn policy(&self) -> Option<DialogPolicy> {
+        self.state.lock().unwrap().policy.clone()
+    }
+
+    /// Set or remove the dialog policy in every webview.
+    pub fn set_policy<R: Runtime>(&self, app: &AppHandle<R>, policy: Option<DialogPolicy>) {
+        let enabled = policy.is_some();
+        self.state.lock().unwrap().policy = policy;
+        for (label, window) in app.webview_windows() {
+            if let Err(e) = window.eval(self.sync_script()) {
+                warn!(
+                    "[TAURI_MCP] Failed to update the dialog policy in {}: {}",
+                    label, e
+                );
+            }
+        }
+        info!(
+            "[TAURI_MCP] Dialog policy {}",
+            if enabled { "set" } else { "removed" }
+        );
+    }
+
+    /// Dialogs answered by the policy, oldest first.
+    pub fn answered(&self) -> Vec<AnsweredDialog> {
+        self.state
+            .lock()
+            .unwrap()
+            .answered
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Record a dialog the policy answered in a webview.
+    pub(crate) fn record_answer(&self, mut dialog: AnsweredDialog) {
+        dialog.answered_ms = now_ms();
+        let mut state = self.state.lock().unwrap();
+        while state.answered.len() >= ANSWERED_CAPACITY {
+            state.answered.pop_front();
+        }
+        state.answered.push_back(dialog);
+    }
+
+    /// Replace the answers given automatically to the next dialogs.
+    pub fn set_auto_responses(&self, responses: Vec<Value>) {
+        self.state.lock().unwrap().auto_responses = responses.into();
+    }
+
+    /// Dialogs waiting for an answer, oldest first.
+    pub fn pending(&self) -> Vec<PendingDialog> {
+        self.state.lock().unwrap().pending.clone()
+    }
+
+    /// Record a dialog reported by a webview. Returns true when a client has to answer it,
+    /// false when a queued auto-response was used.
+    pub(crate) fn opened<R: Runtime>(&self, app: &AppHandle<R>, mut dialog: PendingDialog) -> bool {
+        dialog.opened_ms = now_ms();
+        let auto_response = {
+            let mut state = self.state.lock().unwrap();
+            let auto_response = state.auto_responses.pop_front();
+            if auto_response.is_none() {
+                state.pending.push(dialog.clone());
+            }
+            auto_response
+        };
+        match auto_response {
+            Some(value) => {
+                info!(
+                    "[TAURI_MCP] Answering {} dialog {} automatically",
+                    dialog.kind, dialog.dialog_id
+                );
+                resolve(app, &dialog, &value);
+                false
+            }
+            None => true,
+        }
+    }
+
+    /// Answer a pending dialog, or the oldest one when no ID is given. A null value cancels
+    /// file dialogs; ask and confirm dialogs expect a boolean.
+    pub fn respond<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        dialog_id: Option<&str>,
+        value: Value,
+    ) -> crate::Result<PendingDialog> {
+        let dialog = {
+            let mut state = self.state.lock().unwrap();
+            let index = match dialog_id {
+                Some(id) => state.pending.iter().position(|d| d.dialog_id == id),
+                None => (!state.pending.is_empty()).then_some(0),
+            };
+            index.map(|index| state.pending.remove(index))
+        };
+        let dialog = dialog.ok_or_else(|| {
+            crate::Error::Anyhow(match dialog_id {
+                Some(id) => format!("No pending dialog with ID {}", id),
+                None => "No dialog is waiting for an answer".to_string(),
+            })
+        })?;
+        resolve(app, &dialog, &value);
+        Ok(dialog)
+    }
+}
+
+fn resolve<R: Runtime>(app: &AppHandle<R>, dialog: &PendingDialog, value: &Value) {
+    let Some(webview) = app.get_webview_window(&dialog.window_label) else {
+        warn!(
+            "[TAURI_MCP] Window {} of dialog {} is gone",
+            dialog.window_label, dialog.dialog_id
+        );
+        return;
+    };
+    let script = format!(
+        "window.__TAURI_MCP_DIALOGS__ && window.__TAURI_MCP_DIALOGS__.resolve({}, {});",
+        Value::from(dialog.dialog_id.as_str()),
+        value
+    );
+    if let Err(e) = webview.eval(&script) {
+        warn!(
+            "[TAURI_MCP] Failed to answer dialog {}: {}",
+            dialog.dialog_id, e
+        );
+    }
+}
@@ -0,0 +1,127 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::SocketType;
+use crate::audit::now_ms;
+use crate::protocol::PROTOCOL_VERSION;
+use crate::socket_server::endpoint_address;
+
+/// Longest application name kept in the default socket file name, so the path stays
+/// below the Unix socket path limit.
+const MAX_NAME_CHARS: usize = 32;
+
+/// A running app exposing a socket server, as advertised in the discovery directory.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstanceInfo {
+    pub app_name: String,
+    pub pid: u32,
+    /// "ipc" or "tcp".
+    pub transport: String,
+    /// Socket path or host:port.
+    pub address: String,
+    pub protocol_version: u32,
+    pub started_ms: u64,
+}
+
+/// Directory where running instances advertise their socket.
+pub fn discovery_dir() -> PathBuf {
+    std::env::temp_dir().join("tauri-mcp")
+}
+
+/// Default IPC socket path of this process, unique per application and PID.
+pub fn default_socket_path(application_name: &str) -> PathBuf {
+    let name: String = application_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .take(MAX_NAME_CHARS)
+        .collect();
+    let file_name = if name.is_empty() {
+        format!("tauri-mcp-{}.sock", std::process::id())
+    } else {
+        format!("tauri-mcp-{}-{}.sock", name, std::process::id())
+    };
+    std::env::temp_dir().join(file_name)
+}
+
+/// List the instances advertised in the discovery directory, most recently started first.
+///
+/// Entries left behind by a crashed process are skipped when this can be detected.
+pub fn discover_instances() -> Vec<InstanceInfo> {
+    let Ok(entries) = fs::read_dir(discovery_dir()) else {
+        return Vec::new();
+    };
+
+    let mut instances: Vec<InstanceInfo> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "json" {
+                return None;
+            }
+            serde_json::from_slice(&fs::read(path).ok()?).ok()
+        })
+        .filter(is_alive)
+        .collect();
+    instances.sort_by_key(|instance| std::cmp::Reverse(instance.started_ms));
+    instances
+}
+
+/// Best-effort check that the advertising process is still running.
+fn is_alive(instance: &InstanceInfo) -> bool {
+    let proc_dir = std::path::Path::new("/proc");
+    if cfg!(target_os = "linux") && proc_dir.exists() {
+        return proc_dir.join(instance.pid.to_string()).exists();
+    }
+    // Elsewhere, at least require the socket file to still be there
+    instance.transport != "ipc" || cfg!(windows) || std::path::Path::new(&instance.address).exists()
+}
+
+/// Discovery file of this process.
+fn instance_file() -> PathBuf {
+    discovery_dir().join(format!("{}.json", std::process::id()))
+}
+
+/// Advertise the socket server of this process.
+pub(crate) fn advertise(application_name: &str, socket_type: &SocketType) {
+    let instance = InstanceInfo {
+        app_name: application_name.to_string(),
+        pid: std::process::id(),
+        transport: match socket_type {
+            SocketType::Ipc { .. } => "ipc",
+            SocketType::Tcp { .. } => "tcp",
+        }
+        .to_string(),
+        address: endpoint_address(socket_type, application_name),
+        protocol_version: PROTOCOL_VERSION,
+        started_ms: now_ms(),
+    };
+
+    let result = fs::create_dir_all(discovery_dir()).and_then(|_| {
+        let content = serde_json::to_vec_pretty(&instance).map_err(std::io::Error::other)?;
+        fs::write(instance_file(), content)
+    });
+    match result {
+        Ok(()) => info!(
+            "[TAURI_MCP] Advertised instance in {}",
+            instance_file().display()
+        ),
+        Err(e) => warn!("[TAURI_MCP] Failed to write discovery file: {}", e),
+    }
+}
+
+/// Remove the discovery file of this process.
+pub(crate) fn withdraw() {
+    if let Err(e) = fs::remove_file(instance_file())
+        && e.kind() != std::io::ErrorKind::NotFound
+    {
+        warn!("[TAURI_MCP] Failed to remove discovery file: {}", e);
+    }
+}
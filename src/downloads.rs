@@ -0,0 +1,425 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use crate::audit::now_ms;
+use crate::events::EventBus;
+
+/// Number of downloads kept; the oldest are dropped first.
+const CAPACITY: usize = 100;
+
+/// Where a download is at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DownloadStatus {
+    InProgress,
+    Finished,
+    Failed,
+    Cancelled,
+}
+
+/// A file downloaded by a webview.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Download {
+    /// ID of the download, used as the `since` cursor.
+    pub id: u64,
+    /// Label of the webview that started it, empty if unknown.
+    pub window_label: String,
+    pub url: String,
+    /// File name proposed by the server or the page.
+    pub suggested_name: Option<String>,
+    /// Where the file is written, once decided.
+    pub destination: Option<PathBuf>,
+    pub mime_type: Option<String>,
+    /// Size announced by the server, if any.
+    pub expected_size: Option<u64>,
+    pub received_bytes: u64,
+    /// Size of the file on disk once finished.
+    pub size: Option<u64>,
+    pub status: DownloadStatus,
+    pub error: Option<String>,
+    /// Whether the file was redirected to the sandbox directory.
+    pub sandboxed: bool,
+    pub started_ms: u64,
+    pub finished_ms: Option<u64>,
+}
+
+struct Inner {
+    downloads: VecDeque<Download>,
+    next_id: u64,
+}
+
+/// Keeps the latest downloads of the webviews and optionally redirects them to a sandbox
+/// directory, so tests can check exported files without touching the user's Downloads.
+///
+/// Downloads are recorded by the plugin on Linux. Elsewhere the app can report them from
+/// the `on_download` handler of its windows with [`DownloadLog::requested`] and
+/// [`DownloadLog::finished`].
+pub struct DownloadLog {
+    inner: Mutex<Inner>,
+    notify: Notify,
+    events: Arc<EventBus>,
+    sandbox_dir: Mutex<Option<PathBuf>>,
+}
+
+impl DownloadLog {
+    pub(crate) fn new(events: Arc<EventBus>, sandbox_dir: Option<PathBuf>) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                downloads: VecDeque::new(),
+                next_id: 1,
+            }),
+            notify: Notify::new(),
+            events,
+            sandbox_dir: Mutex::new(sandbox_dir),
+        }
+    }
+
+    /// Directory downloads are redirected to, None when they go where the webview puts them.
+    pub fn sandbox_dir(&self) -> Option<PathBuf> {
+        self.sandbox_dir.lock().unwrap().clone()
+    }
+
+    /// Redirect the next downloads to `dir`, or stop redirecting them with None.
+    pub fn set_sandbox_dir(&self, dir: Option<PathBuf>) {
+        *self.sandbox_dir.lock().unwrap() = dir;
+    }
+
+    /// A free path for `suggested_name` in the sandbox directory, which is created if
+    /// needed. None when downloads are not redirected.
+    pub fn sandbox_destination(&self, suggested_name: &str) -> Option<PathBuf> {
+        let dir = self.sandbox_dir()?;
+        if let Err(e) = std::fs::create_dir_all(&dir) {
+            log::warn!(
+                "[TAURI_MCP] Failed to create the download directory {}: {}",
+                dir.display(),
+                e
+            );
+            return None;
+        }
+        Some(free_path(&dir, suggested_name))
+    }
+
+    /// Record a download a webview started and return its ID. `destination` is where the
+    /// file goes, if already known.
+    pub fn requested(&self, window_label: &str, url: &str, destination: Option<PathBuf>) -> u64 {
+        let sandboxed = self
+            .sandbox_dir()
+            .zip(destination.as_ref())
+            .is_some_and(|(dir, destination)| destination.starts_with(dir));
+        let download = {
+            let mut inner = self.inner.lock().unwrap();
+            let download = Download {
+                id: inner.next_id,
+                window_label: window_label.to_string(),
+                url: url.to_string(),
+                suggested_name: None,
+                destination,
+                mime_type: None,
+                expected_size: None,
+                received_bytes: 0,
+                size: None,
+                status: DownloadStatus::InProgress,
+                error: None,
+                sandboxed,
+                started_ms: now_ms(),
+                finished_ms: None,
+            };
+            inner.next_id += 1;
+            while inner.downloads.len() >= CAPACITY {
+                inner.downloads.pop_front();
+            }
+            inner.downloads.push_back(download.clone());
+            download
+        };
+        self.publish(&download);
+        download.id
+    }
+
+    /// Record the end of the latest download of `url` still in progress, written to `path`.
+    pub fn finished(&self, url: &str, path: Option<PathBuf>, success: bool) {
+        let id = self
+            .inner
+            .lock()
+            .unwrap()
+            .downloads
+            .iter()
+            .rev()
+            .find(|download| download.url == url && download.status == DownloadStatus::InProgress)
+            .map(|download| download.id);
+        let Some(id) = id else {
+            return;
+        };
+        if let Some(path) = path {
+            self.update(id, |download| download.destination = Some(path));
+        }
+        if success {
+            self.complete(id, DownloadStatus::Finished, None);
+        } else {
+            self.complete(
+                id,
+                DownloadStatus::Failed,
+                Some("Download failed".to_string()),
+            );
+        }
+    }
+
+    /// Change a download in progress.
+    pub(crate) fn update(&self, id: u64, change: impl FnOnce(&mut Download)) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(download) = inner
+            .downloads
+            .iter_mut()
+            .find(|download| download.id == id)
+        {
+            change(download);
+        }
+    }
+
+    /// End a download, measuring the file it left behind. Only the first end counts.
+    pub(crate) fn complete(&self, id: u64, status: DownloadStatus, error: Option<String>) {
+        let download = {
+            let mut inner = self.inner.lock().unwrap();
+            let Some(download) = inner.downloads.iter_mut().find(|download| {
+                download.id == id && download.status == DownloadStatus::InProgress
+            }) else {
+                return;
+            };
+            download.status = status;
+            download.error = error;
+            download.finished_ms = Some(now_ms());
+            if status == DownloadStatus::Finished {
+                download.size = download
+                    .destination
+                    .as_ref()
+                    .and_then(|path| std::fs::metadata(path).ok())
+                    .map(|metadata| metadata.len());
+            }
+            download.clone()
+        };
+        self.publish(&download);
+        self.notify.notify_waiters();
+    }
+
+    /// Downloads after the `since` ID, oldest first, optionally of one window only.
+    pub fn downloads(&self, since: u64, window_label: Option<&str>) -> Vec<Download> {
+        self.inner
+            .lock()
+            .unwrap()
+            .downloads
+            .iter()
+            .filter(|download| download.id > since)
+            .filter(|download| window_label.is_none_or(|label| label == download.window_label))
+            .cloned()
+            .collect()
+    }
+
+    /// ID of the most recent download (0 if none was recorded yet).
+    pub fn latest_id(&self) -> u64 {
+        self.inner.lock().unwrap().next_id - 1
+    }
+
+    /// Wait up to `timeout` for the first download after `since` accepted by `filter` to
+    /// end, successfully or not.
+    pub async fn wait_for_completion(
+        &self,
+        since: u64,
+        filter: impl Fn(&Download) -> bool,
+        timeout: Duration,
+    ) -> Option<Download> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Register interest before checking so a completion in between is not missed
+            let notified = self.notify.notified();
+            let found = self
+                .inner
+                .lock()
+                .unwrap()
+                .downloads
+                .iter()
+                .find(|download| {
+                    download.id > since
+                        && download.status != DownloadStatus::InProgress
+                        && filter(download)
+                })
+                .cloned();
+            if found.is_some() {
+                return found;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            let _ = tokio::time::timeout(deadline - now, notified).await;
+        }
+    }
+
+    fn publish(&self, download: &Download) {
+        self.events.publish(
+            "download",
+            serde_json::to_value(download).unwrap_or_default(),
+        );
+    }
+}
+
+/// `name` in `dir`, numbered like "report (1).csv" when taken. Path components of the
+/// name are dropped so a download cannot leave the directory.
+fn free_path(dir: &Path, name: &str) -> PathBuf {
+    let name = Path::new(name)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "download".to_string());
+    let path = dir.join(&name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, extension) = match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{}", extension)),
+        _ => (name.as_str(), String::new()),
+    };
+    (1..)
+        .map(|n| dir.join(format!("{} ({}){}", stem, n, extension)))
+        .find(|path| !path.exists())
+        .unwrap_or(path)
+}
+
+/// Follows the downloads of the WebKitGTK contexts of the webviews.
+#[cfg(target_os = "linux")]
+pub(crate) mod platform {
+    use gtk::glib::object::ObjectType;
+    use std::cell::RefCell;
+    use std::collections::{HashMap, HashSet};
+    use tauri::{AppHandle, Manager, Runtime, Webview};
+    use webkit2gtk::{
+        Download, DownloadError, DownloadExt, URIRequestExt, URIResponseExt, WebContextExt,
+        WebViewExt,
+    };
+
+    use super::DownloadStatus;
+    use crate::TauriMcpExt;
+    use crate::desktop::TauriMcp;
+
+    thread_local! {
+        /// Labels of the webviews, by WebKitWebView pointer.
+        static LABELS: RefCell<HashMap<usize, String>> = RefCell::new(HashMap::new());
+        /// Web contexts already followed; webviews usually share one.
+        static CONTEXTS: RefCell<HashSet<usize>> = RefCell::new(HashSet::new());
+    }
+
+    /// Record the downloads of `webview`, and of the other webviews of its context.
+    pub(crate) fn attach<R: Runtime>(webview: &Webview<R>) {
+        let label = webview.label().to_string();
+        let app = webview.app_handle().clone();
+        let result = webview.with_webview(move |platform_webview| {
+            let inner = platform_webview.inner();
+            LABELS.with(|labels| {
+                labels
+                    .borrow_mut()
+                    .insert(inner.as_ptr() as usize, label.clone())
+            });
+            let Some(context) = inner.web_context() else {
+                return;
+            };
+            let first =
+                CONTEXTS.with(|contexts| contexts.borrow_mut().insert(context.as_ptr() as usize));
+            if first {
+                context.connect_download_started(move |_, download| follow(&app, download));
+            }
+        });
+        if let Err(e) = result {
+            log::warn!(
+                "[TAURI_MCP] Failed to follow the downloads of a webview: {}",
+                e
+            );
+        }
+    }
+
+    fn follow<R: Runtime>(app: &AppHandle<R>, download: &Download) {
+        if app.try_state::<TauriMcp<R>>().is_none() {
+            return;
+        }
+        let label = download
+            .web_view()
+            .and_then(|webview| {
+                LABELS.with(|labels| labels.borrow().get(&(webview.as_ptr() as usize)).cloned())
+            })
+            .unwrap_or_default();
+        let url = download
+            .request()
+            .and_then(|request| request.uri())
+            .map(|uri| uri.to_string())
+            .unwrap_or_default();
+        let id = app.tauri_mcp().downloads().requested(&label, &url, None);
+
+        let handle = app.clone();
+        download.connect_decide_destination(move |download, suggested_name| {
+            let downloads = handle.tauri_mcp().downloads();
+            let response = download.response();
+            downloads.update(id, |entry| {
+                entry.suggested_name = Some(suggested_name.to_string());
+                entry.mime_type = response
+                    .as_ref()
+                    .and_then(|response| response.mime_type())
+                    .map(|mime| mime.to_string());
+                entry.expected_size = response
+                    .as_ref()
+                    .map(|response| response.content_length())
+                    .filter(|length| *length > 0);
+            });
+            // Returning false lets the app's own handler or WebKit pick the destination
+            let Some(path) = downloads.sandbox_destination(suggested_name) else {
+                return false;
+            };
+            match gtk::glib::filename_to_uri(&path, None) {
+                Ok(uri) => {
+                    download.set_destination(&uri);
+                    downloads.update(id, |entry| entry.sandboxed = true);
+                    true
+                }
+                Err(_) => false,
+            }
+        });
+        let handle = app.clone();
+        download.connect_created_destination(move |_, uri| {
+            if let Ok((path, _)) = gtk::glib::filename_from_uri(uri) {
+                handle
+                    .tauri_mcp()
+                    .downloads()
+                    .update(id, |entry| entry.destination = Some(path));
+            }
+        });
+        let handle = app.clone();
+        download.connect_received_data(move |download, _| {
+            let received = download.received_data_length();
+            handle
+                .tauri_mcp()
+                .downloads()
+                .update(id, |entry| entry.received_bytes = received);
+        });
+        // "failed" comes before "finished" for failed downloads, and only the first end counts
+        let handle = app.clone();
+        download.connect_failed(move |_, error| {
+            let status = if error.matches(DownloadError::CancelledByUser) {
+                DownloadStatus::Cancelled
+            } else {
+                DownloadStatus::Failed
+            };
+            handle
+                .tauri_mcp()
+                .downloads()
+                .complete(id, status, Some(error.to_string()));
+        });
+        let handle = app.clone();
+        download.connect_finished(move |_| {
+            handle
+                .tauri_mcp()
+                .downloads()
+                .complete(id, DownloadStatus::Finished, None);
+        });
+    }
+}
@@ -0,0 +1,1249 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, LogicalSize, Runtime};
+
+/// Injected in every webview. Overrides are applied by `apply(settings)` and kept in the
+/// session storage, so a reload applies them again before the app's own scripts run.
+const EMULATION_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_EMULATION__) return;
+    const STORAGE_KEY = '__tauri_mcp_emulation__';
+    const RealDate = Date;
+    const realDateNow = Date.now.bind(Date);
+    const realPerformanceNow = performance.now.bind(performance);
+    const realRandom = Math.random;
+    const realGetRandomValues = window.crypto && crypto.getRandomValues.bind(crypto);
+    const realRandomUUID = window.crypto && crypto.randomUUID && crypto.randomUUID.bind(crypto);
+
+    // Clock: Date and performance.now move together from the emulated start time
+    let clock = null;
+    const elapsed = () => (clock.frozen ? 0 : realPerformanceNow() - clock.realStart) + clock.advanced;
+    function EmulatedDate(...args) {
+        if (!new.target) return new EmulatedDate().toString();
+        if (args.length === 0) return Reflect.construct(RealDate, [EmulatedDate.now()], new.target);
+        // Local date components are in the emulated timezone
+        if (args.length > 1 && locale && locale.timezone) {
+            return Reflect.construct(RealDate, [fromLocal(RealDate.UTC(...args))], new.target);
+        }
+        return Reflect.construct(RealDate, args, new.target);
+    }
+    EmulatedDate.prototype = RealDate.prototype;
+    EmulatedDate.now = () => clock ? Math.floor(clock.timeMs + elapsed()) : realDateNow();
+    EmulatedDate.parse = RealDate.parse;
+    EmulatedDate.UTC = RealDate.UTC;
+    const installDate = () => {
+        window.Date = clock || (locale && locale.timezone) ? EmulatedDate : RealDate;
+    };
+    const setClock = (settings) => {
+        if (!settings) {
+            clock = null;
+            installDate();
+            performance.now = realPerformanceNow;
+            return;
+        }
+        // performance.now never goes back, even when the clock is set again
+        const performanceStart = performance.now();
+        clock = { timeMs: settings.timeMs, frozen: settings.frozen, realStart: realPerformanceNow(), performanceStart, advanced: 0 };
+        installDate();
+        performance.now = () => clock ? clock.performanceStart + elapsed() : realPerformanceNow();
+    };
+
+    // Random: a seeded generator behind Math.random and the crypto helpers
+    const mulberry32 = (seed) => () => {
+        seed = (seed + 0x6D2B79F5) | 0;
+        let t = Math.imul(seed ^ (seed >>> 15), 1 | seed);
+        t = (t + Math.imul(t ^ (t >>> 7), 61 | t)) ^ t;
+        return ((t ^ (t >>> 14)) >>> 0) / 4294967296;
+    };
+    const setRandom = (seed) => {
+        if (seed === null || seed === undefined) {
+            Math.random = realRandom;
+            if (realGetRandomValues) crypto.getRandomValues = realGetRandomValues;
+            if (realRandomUUID) crypto.randomUUID = realRandomUUID;
+            return;
+        }
+        const next = mulberry32(seed >>> 0);
+        Math.random = next;
+        if (realGetRandomValues) {
+            crypto.getRandomValues = (array) => {
+                const bytes = new Uint8Array(array.buffer, array.byteOffset, array.byteLength);
+                for (let i = 0; i < bytes.length; i++) bytes[i] = Math.floor(next() * 256);
+                return array;
+            };
+        }
+        if (realRandomUUID) {
+            crypto.randomUUID = () => {
+                const bytes = crypto.getRandomValues(new Uint8Array(16));
+                bytes[6] = (bytes[6] & 0x0f) | 0x40;
+                bytes[8] = (bytes[8] & 0x3f) | 0x80;
+                const hex = Array.from(bytes, (b) => b.toString(16).padStart(2, '0')).join('');
+                return `${hex.slice(0, 8)}-${hex.slice(8, 12)}-${hex.slice(12, 16)}-${hex.slice(16, 20)}-${hex.slice(20)}`;
+            };
+        }
+    };
+
+    // Geolocation: positions from the settings, or a permission error
+    const geolocation = navigator.geolocation;
+    const realGeolocation = geolocation && {
+        getCurrentPosition: geolocation.getCurrentPosition.bind(geolocation),
+        watchPosition: geolocation.watchPosition.bind(geolocation),
+        clearWatch: geolocation.clearWatch.bind(geolocation),
+    };
+    let position = null;
+    const watchers = new Map();
+    let nextWatch = 1;
+    const deliver = (success, error) => {
+        setTimeout(() => {
+            if (!position) return;
+            if (position.denied) {
+                if (error) error({ code: 1, message: 'User denied Geolocation', PERMISSION_DENIED: 1, POSITION_UNAVAILABLE: 2, TIMEOUT: 3 });
+                return;
+            }
+            success({
+                coords: {
+                    latitude: position.latitude,
+                    longitude: position.longitude,
+                    accuracy: position.accuracy,
+                    altitude: position.altitude ?? null,
+                    altitudeAccuracy: null,
+                    heading: position.heading ?? null,
+                    speed: position.speed ?? null,
+                },
+                timestamp: Date.now(),
+            });
+        }, 0);
+    };
+    if (geolocation) {
+        geolocation.getCurrentPosition = (success, error, options) => position
+            ? deliver(success, error)
+            : realGeolocation.getCurrentPosition(success, error, options);
+        geolocation.watchPosition = (success, error, options) => {
+            const id = nextWatch++;
+            watchers.set(id, { success, error, options, realId: position ? null : realGeolocation.watchPosition(success, error, options) });
+            if (position) deliver(success, error);
+            return id;
+        };
+        geolocation.clearWatch = (id) => {
+            const watcher = watchers.get(id);
+            if (watcher && watcher.realId !== null) realGeolocation.clearWatch(watcher.realId);
+            watchers.delete(id);
+        };
+    }
+    if (navigator.permissions && navigator.permissions.query) {
+        const realQuery = navigator.permissions.query.bind(navigator.permissions);
+        navigator.permissions.query = (descriptor) => position && descriptor && descriptor.name === 'geolocation'
+            ? Promise.resolve({ name: 'geolocation', state: position.denied ? 'denied' : 'granted', onchange: null, addEventListener() {}, removeEventListener() {} })
+            : realQuery(descriptor);
+    }
+    const setGeolocation = (settings) => {
+        if (!geolocation) return;
+        position = settings || null;
+        // Running watches switch to the new source
+        for (const watcher of watchers.values()) {
+            if (position) {
+                if (watcher.realId !== null) realGeolocation.clearWatch(watcher.realId);
+                watcher.realId = null;
+                deliver(watcher.success, watcher.error);
+            } else if (watcher.realId === null) {
+                watcher.realId = realGeolocation.watchPosition(watcher.success, watcher.error, watcher.options);
+            }
+        }
+    };
+
+    // Locale: navigator.language(s), the default locale of Intl and the local timezone.
+    // The wrappers are always installed and defer to the real behavior without overrides.
+    let locale = null;
+    const RealDateTimeFormat = Intl.DateTimeFormat;
+    const realLanguage = navigator.language;
+    const realLanguages = navigator.languages;
+    const defaultLocale = (locales) => locales === undefined && locale && locale.locale ? locale.locale : locales;
+    const withTimezone = (options) => locale && locale.timezone && !(options && options.timeZone)
+        ? { ...options, timeZone: locale.timezone }
+        : options;
+    for (const name of ['Collator', 'DateTimeFormat', 'DisplayNames', 'ListFormat', 'NumberFormat', 'PluralRules', 'RelativeTimeFormat', 'Segmenter']) {
+        const Real = Intl[name];
+        if (!Real) continue;
+        const Wrapped = function (locales, options) {
+            const args = [defaultLocale(locales), name === 'DateTimeFormat' ? withTimezone(options) : options];
+            return Reflect.construct(Real, args, new.target || Real);
+        };
+        Wrapped.prototype = Real.prototype;
+        Wrapped.supportedLocalesOf = Real.supportedLocalesOf.bind(Real);
+        Object.defineProperty(Intl, name, { value: Wrapped, writable: true, configurable: true });
+    }
+    const wrapLocaleMethod = (target, name, dates) => {
+        const real = target[name];
+        target[name] = function (locales, options) {
+            return real.call(this, defaultLocale(locales), dates ? withTimezone(options) : options);
+        };
+    };
+    wrapLocaleMethod(Number.prototype, 'toLocaleString', false);
+    if (typeof BigInt !== 'undefined') wrapLocaleMethod(BigInt.prototype, 'toLocaleString', false);
+    for (const name of ['toLocaleString', 'toLocaleDateString', 'toLocaleTimeString']) wrapLocaleMethod(RealDate.prototype, name, true);
+    const realLocaleCompare = String.prototype.localeCompare;
+    String.prototype.localeCompare = function (that, locales, options) {
+        return realLocaleCompare.call(this, that, defaultLocale(locales), options);
+    };
+    for (const name of ['language', 'languages']) {
+        Object.defineProperty(navigator, name, {
+            configurable: true,
+            get: () => {
+                if (!locale || locale.languages.length === 0) return name === 'language' ? realLanguage : realLanguages;
+                return name === 'language' ? locale.languages[0] : Object.freeze([...locale.languages]);
+            },
+        });
+    }
+
+    // Timezone: local Date methods read and write through the offset of the emulated zone
+    const proto = RealDate.prototype;
+    const real = {};
+    const LOCAL = ['FullYear', 'Month', 'Date', 'Day', 'Hours', 'Minutes', 'Seconds', 'Milliseconds'];
+    for (const name of ['getTimezoneOffset', 'toString', 'toDateString', 'toTimeString', ...LOCAL.flatMap((part) => [`get${part}`, `set${part}`])]) {
+        real[name] = proto[name];
+    }
+    let zoneFormat = null;
+    // Minutes to add to the local time to get UTC, as getTimezoneOffset returns
+    const offsetAt = (time) => {
+        const parts = {};
+        for (const part of zoneFormat.formatToParts(new RealDate(time))) parts[part.type] = Number(part.value);
+        const local = RealDate.UTC(parts.year, parts.month - 1, parts.day, parts.hour, parts.minute, parts.second);
+        return (Math.floor(time / 1000) * 1000 - local) / 60000;
+    };
+    // UTC time of a local time given as if it were UTC
+    const fromLocal = (localTime) => {
+        const offset = offsetAt(localTime);
+        const time = localTime + offset * 60000;
+        const corrected = offsetAt(time);
+        if (corrected === offset) return time;
+        // Across a transition; a skipped local time moves forward, as with the real Date
+        const other = localTime + corrected * 60000;
+        return offsetAt(other) === corrected ? other : time;
+    };
+    const shifted = (date) => new RealDate(date.getTime() - offsetAt(date.getTime()) * 60000);
+    const zoned = () => locale && locale.timezone && zoneFormat;
+    proto.getTimezoneOffset = function () {
+        if (!zoned() || isNaN(this.getTime())) return real.getTimezoneOffset.call(this);
+        return offsetAt(this.getTime());
+    };
+    for (const part of LOCAL) {
+        proto[`get${part}`] = function () {
+            if (!zoned() || isNaN(this.getTime())) return real[`get${part}`].call(this);
+            return proto[`getUTC${part}`].call(shifted(this));
+        };
+        if (part === 'Day') continue;
+        proto[`set${part}`] = function (...args) {
+            if (!zoned() || isNaN(this.getTime())) return real[`set${part}`].apply(this, args);
+            const local = shifted(this);
+            proto[`setUTC${part}`].apply(local, args);
+            return this.setTime(fromLocal(local.getTime()));
+        };
+    }
+    const DAYS = ['Sun', 'Mon', 'Tue', 'Wed', 'Thu', 'Fri', 'Sat'];
+    const MONTHS = ['Jan', 'Feb', 'Mar', 'Apr', 'May', 'Jun', 'Jul', 'Aug', 'Sep', 'Oct', 'Nov', 'Dec'];
+    const pad = (value, length = 2) => String(value).padStart(length, '0');
+    const dateString = (date) => `${DAYS[date.getDay()]} ${MONTHS[date.getMonth()]} ${pad(date.getDate())} ${pad(date.getFullYear(), 4)}`;
+    const timeString = (date) => {
+        const offset = -date.getTimezoneOffset();
+        const zoneName = new RealDateTimeFormat('en-US', { timeZone: locale.timezone, timeZoneName: 'long' })
+            .formatToParts(date).find((part) => part.type === 'timeZoneName');
+        return `${pad(date.getHours())}:${pad(date.getMinutes())}:${pad(date.getSeconds())} GMT${offset < 0 ? '-' : '+'}${pad(Math.floor(Math.abs(offset) / 60))}${pad(Math.abs(offset) % 60)}`
+            + (zoneName ? ` (${zoneName.value})` : '');
+    };
+    proto.toString = function () {
+        if (!zoned() || isNaN(this.getTime())) return real.toString.call(this);
+        return `${dateString(this)} ${timeString(this)}`;
+    };
+    proto.toDateString = function () {
+        if (!zoned() || isNaN(this.getTime())) return real.toDateString.call(this);
+        return dateString(this);
+    };
+    proto.toTimeString = function () {
+        if (!zoned() || isNaN(this.getTime())) return real.toTimeString.call(this);
+        return timeString(this);
+    };
+    const setLocale = (settings) => {
+        const languagesBefore = navigator.languages;
+        locale = settings ? { locale: settings.locale || null, languages: settings.languages || [], timezone: settings.timezone || null } : null;
+        zoneFormat = null;
+        if (locale && locale.timezone) {
+            try {
+                zoneFormat = new RealDateTimeFormat('en-US', {
+                    timeZone: locale.timezone, hourCycle: 'h23',
+                    year: 'numeric', month: 'numeric', day: 'numeric', hour: 'numeric', minute: 'numeric', second: 'numeric',
+                });
+            } catch (e) {
+                console.warn('[TAURI_MCP] Invalid emulated timezone', locale.timezone, e);
+                locale.timezone = null;
+            }
+        }
+        installDate();
+        if (String(navigator.languages) !== String(languagesBefore)) window.dispatchEvent(new Event('languagechange'));
+    };
+
+    // Color scheme: prefers-color-scheme in matchMedia and in stylesheets follows the
+    // emulated scheme. Stylesheet media queries are rewritten to always or never match.
+    let colorScheme = null;
+    const SCHEME_QUERY = /\(\s*prefers-color-scheme\s*:\s*(light|dark)\s*\)/gi;
+    const hasScheme = (query) => /prefers-color-scheme/i.test(query);
+    const emulatedQuery = (query) => query.replace(SCHEME_QUERY, (_, scheme) =>
+        scheme.toLowerCase() === colorScheme ? '(min-width: 0px)' : '(not (min-width: 0px))');
+    const realMatchMedia = window.matchMedia && window.matchMedia.bind(window);
+    const schemeLists = new Set();
+    if (realMatchMedia) {
+        window.matchMedia = (query) => {
+            const real = realMatchMedia(query);
+            if (!hasScheme(String(query))) return real;
+            // A stand-in, so listeners also hear about emulated changes
+            const list = new EventTarget();
+            let last = null;
+            const evaluate = () => colorScheme ? realMatchMedia(emulatedQuery(real.media)).matches : real.matches;
+            const notify = () => {
+                const matches = evaluate();
+                if (matches === last) return;
+                last = matches;
+                const event = new Event('change');
+                Object.defineProperties(event, { matches: { value: matches }, media: { value: real.media } });
+                list.dispatchEvent(event);
+                if (typeof list.onchange === 'function') list.onchange(event);
+            };
+            Object.defineProperties(list, {
+                media: { value: real.media },
+                matches: { get: evaluate },
+                onchange: { value: null, writable: true },
+                addListener: { value: (listener) => list.addEventListener('change', listener) },
+                removeListener: { value: (listener) => list.removeEventListener('change', listener) },
+                notify: { value: notify },
+            });
+            last = evaluate();
+            real.addEventListener('change', notify);
+            schemeLists.add(new WeakRef(list));
+            return list;
+        };
+    }
+    const originalMedia = new WeakMap();
+    const rewriteMedia = (media) => {
+        if (!media) return;
+        if (!originalMedia.has(media)) {
+            if (!hasScheme(media.mediaText)) return;
+            originalMedia.set(media, media.mediaText);
+        }
+        const original = originalMedia.get(media);
+        const text = colorScheme ? emulatedQuery(original) : original;
+        if (media.mediaText !== text) media.mediaText = text;
+    };
+    const rewriteRules = (rules) => {
+        for (const rule of rules) {
+            if (rule.media) rewriteMedia(rule.media);
+            if (rule.styleSheet) rewriteSheet(rule.styleSheet);
+            if (rule.cssRules) rewriteRules(rule.cssRules);
+        }
+    };
+    const rewriteSheet = (sheet) => {
+        rewriteMedia(sheet.media);
+        try {
+            rewriteRules(sheet.cssRules);
+        } catch (_) {
+            // Cross-origin stylesheets cannot be read
+        }
+    };
+    const rewriteSheets = () => {
+        for (const sheet of document.styleSheets) rewriteSheet(sheet);
+    };
+    let rewriteScheduled = false;
+    const scheduleRewrite = () => {
+        if (rewriteScheduled) return;
+        rewriteScheduled = true;
+        queueMicrotask(() => {
+            rewriteScheduled = false;
+            if (colorScheme) rewriteSheets();
+        });
+    };
+    if (typeof MutationObserver !== 'undefined') {
+        new MutationObserver(scheduleRewrite).observe(document, { childList: true, subtree: true });
+        // Linked stylesheets have no rules until they load
+        document.addEventListener('load', scheduleRewrite, true);
+    }
+    const setColorScheme = (scheme) => {
+        if (scheme === colorScheme) return;
+        colorScheme = scheme;
+        rewriteSheets();
+        for (const ref of schemeLists) {
+            const list = ref.deref();
+            if (list) list.notify(); else schemeLists.delete(ref);
+        }
+    };
+
+    // Device: pixel ratio, screen size, user agent and touch support. Mouse input becomes
+    // touch events too, as on a touch screen.
+    let device = null;
+    const realProperty = (target, name) => {
+        for (let owner = target; owner; owner = Object.getPrototypeOf(owner)) {
+            const descriptor = Object.getOwnPropertyDescriptor(owner, name);
+            if (descriptor) return () => descriptor.get ? descriptor.get.call(target) : descriptor.value;
+        }
+        return () => undefined;
+    };
+    const emulateProperty = (target, name, value) => {
+        if (!target) return;
+        const real = realProperty(target, name);
+        try {
+            Object.defineProperty(target, name, {
+                configurable: true,
+                get: () => { const emulated = device && value(device); return emulated ?? real(); },
+            });
+        } catch (_) {}
+    };
+    emulateProperty(window, 'devicePixelRatio', (d) => d.deviceScaleFactor);
+    emulateProperty(navigator, 'userAgent', (d) => d.userAgent);
+    emulateProperty(navigator, 'maxTouchPoints', (d) => d.touch ? 5 : 0);
+    for (const [name, size] of [['width', 'width'], ['availWidth', 'width'], ['height', 'height'], ['availHeight', 'height']]) {
+        emulateProperty(window.screen, name, (d) => d[size]);
+    }
+    let touchActive = null;
+    const makeTouch = (target, e) => {
+        const init = {
+            identifier: 0, target, clientX: e.clientX, clientY: e.clientY, pageX: e.pageX, pageY: e.pageY,
+            screenX: e.screenX, screenY: e.screenY, radiusX: 1, radiusY: 1, force: 1,
+        };
+        try { return new Touch(init); } catch (_) { return init; }
+    };
+    const dispatchTouch = (type, target, touch, active) => {
+        const init = {
+            bubbles: true, cancelable: true, composed: true,
+            touches: active ? [touch] : [], targetTouches: active ? [touch] : [], changedTouches: [touch],
+        };
+        let event;
+        try {
+            event = new TouchEvent(type, init);
+        } catch (_) {
+            event = new UIEvent(type, { bubbles: true, cancelable: true, composed: true });
+            for (const key of ['touches', 'targetTouches', 'changedTouches']) Object.defineProperty(event, key, { value: init[key] });
+        }
+        return target.dispatchEvent(event);
+    };
+    const mouseToTouch = (e) => {
+        if (!device || !device.touch || e.button !== 0) return;
+        let delivered = true;
+        if (e.type === 'mousedown') {
+            touchActive = e.target;
+            delivered = dispatchTouch('touchstart', touchActive, makeTouch(touchActive, e), true);
+        } else if (touchActive && e.type === 'mousemove') {
+            delivered = dispatchTouch('touchmove', touchActive, makeTouch(touchActive, e), true);
+        } else if (touchActive && e.type === 'mouseup') {
+            const target = touchActive;
+            touchActive = null;
+            delivered = dispatchTouch('touchend', target, makeTouch(target, e), false);
+        }
+        // A handled touch cancels the mouse event, like on a touch screen
+        if (!delivered) {
+            e.preventDefault();
+            e.stopImmediatePropagation();
+        }
+    };
+    for (const type of ['mousedown', 'mousemove', 'mouseup']) window.addEventListener(type, mouseToTouch, true);
+    let addedOnTouchStart = false;
+    const setDevice = (settings) => {
+        device = settings || null;
+        touchActive = null;
+        // Feature checks look for ontouchstart
+        if (device && device.touch && !('ontouchstart' in window)) {
+            Object.defineProperty(window, 'ontouchstart', { configurable: true, writable: true, value: null });
+            addedOnTouchStart = true;
+        } else if (!(device && device.touch) && addedOnTouchStart) {
+            delete window.ontouchstart;
+            addedOnTouchStart = false;
+        }
+    };
+
+    // Network: fetch and XMLHttpRequest fail or slow down as configured. Requests to the
+    // Tauri IPC, asset and app protocols are left alone, so the app and the plugin still work.
+    let network = null;
+    const isInternal = (url) => {
+        try {
+            const parsed = new URL(url, location.href);
+            return ['ipc:', 'asset:', 'tauri:', 'data:', 'blob:'].includes(parsed.protocol)
+                || /^(ipc|asset|tauri)\.localhost$/.test(parsed.hostname);
+        } catch (_) {
+            return true;
+        }
+    };
+    const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+    // Milliseconds to send `bytes` at `kbps` kilobits per second
+    const transferTime = (bytes, kbps) => kbps ? bytes * 8 / kbps : 0;
+    const bodySize = (body) => {
+        if (body === null || body === undefined) return 0;
+        if (typeof body === 'string') return new TextEncoder().encode(body).byteLength;
+        if (body instanceof URLSearchParams) return body.toString().length;
+        if (typeof Blob !== 'undefined' && body instanceof Blob) return body.size;
+        if (body.byteLength !== undefined) return body.byteLength;
+        return 0;
+    };
+    const throttle = (response, kbps) => {
+        const reader = response.body.getReader();
+        const body = new ReadableStream({
+            async pull(controller) {
+                const { done, value } = await reader.read();
+                if (done) {
+                    controller.close();
+                    return;
+                }
+                await sleep(transferTime(value.byteLength, kbps));
+                controller.enqueue(value);
+            },
+            cancel: (reason) => reader.cancel(reason),
+        });
+        const throttled = new Response(body, { status: response.status, statusText: response.statusText, headers: response.headers });
+        Object.defineProperties(throttled, {
+            url: { value: response.url },
+            redirected: { value: response.redirected },
+            type: { value: response.type },
+        });
+        return throttled;
+    };
+    // Mock routes: matching requests get a canned response, or go to another URL
+    let routes = [];
+    const escapeRegExp = (text) => text.replace(/[.+?^${}()|[\]\\]/g, '\\$&');
+    const compileRoute = (route) => {
+        let pattern = null;
+        try {
+            pattern = route.regex
+                ? new RegExp(route.url)
+                : new RegExp(`^${route.url.split('*').map(escapeRegExp).join('.*')}$`);
+        } catch (e) {
+            console.warn('[TAURI_MCP] Invalid mock route pattern', route.url, e);
+        }
+        return { ...route, pattern };
+    };
+    // Patterns match the full URL, or its path and query
+    const findRoute = (url, method) => {
+        if (routes.length === 0 || !url) return null;
+        let parsed;
+        try {
+            parsed = new URL(url, location.href);
+        } catch (_) {
+            return null;
+        }
+        const candidates = [parsed.href, parsed.pathname + parsed.search];
+        return routes.find((route) => route.pattern
+            && candidates.some((candidate) => route.pattern.test(candidate))
+            && (!route.method || route.method.toUpperCase() === String(method || 'GET').toUpperCase())) || null;
+    };
+    const mockContent = (route) => {
+        const headers = {};
+        for (const [name, value] of Object.entries(route.headers || {})) headers[name.toLowerCase()] = String(value);
+        let text = '';
+        if (typeof route.body === 'string') {
+            text = route.body;
+        } else if (route.body !== undefined && route.body !== null) {
+            text = JSON.stringify(route.body);
+            if (!headers['content-type']) headers['content-type'] = 'application/json';
+        }
+        return { text, headers };
+    };
+    const NULL_BODY_STATUSES = [204, 205, 304];
+    const realFetch = window.fetch;
+    const mockFetch = async function (route, input, init) {
+        if (route.delayMs) await sleep(route.delayMs);
+        if (route.rewriteUrl) {
+            const request = typeof input === 'object' && input.url && !(input instanceof URL);
+            return realFetch.call(this, request ? new Request(route.rewriteUrl, input) : route.rewriteUrl, init);
+        }
+        const { text, headers } = mockContent(route);
+        const response = new Response(NULL_BODY_STATUSES.includes(route.status) ? null : text, { status: route.status, headers });
+        const url = typeof input === 'string' ? input : (input.href || input.url);
+        Object.defineProperty(response, 'url', { value: new URL(url, location.href).href });
+        return response;
+    };
+    if (realFetch) {
+        window.fetch = function (input, init) {
+            if (!network && routes.length === 0) return realFetch.apply(this, arguments);
+            const url = typeof input === 'string' ? input : (input && (input.href || input.url));
+            if (isInternal(url)) return realFetch.apply(this, arguments);
+            const route = findRoute(url, (init && init.method) || (input && input.method));
+            if (!network && !route) return realFetch.apply(this, arguments);
+            const conditions = network || { offline: false, latencyMs: 0, downloadKbps: 0, uploadKbps: 0 };
+            const signal = (init && init.signal) || (input && input.signal);
+            return (async () => {
+                if (conditions.offline) throw new TypeError('Failed to fetch');
+                await sleep(conditions.latencyMs + transferTime(bodySize(init && init.body), conditions.uploadKbps));
+                if (signal && signal.aborted) throw signal.reason || new DOMException('The operation was aborted.', 'AbortError');
+                const response = route
+                    ? await mockFetch.call(this, route, input, init)
+                    : await realFetch.call(this, input, init);
+                return conditions.downloadKbps && response.body && response.type !== 'opaque'
+                    ? throttle(response, conditions.downloadKbps)
+                    : response;
+            })();
+        };
+    }
+    const xhr = window.XMLHttpRequest && XMLHttpRequest.prototype;
+    if (xhr) {
+        const realOpen = xhr.open;
+        const realSend = xhr.send;
+        const requests = new WeakMap();
+        const OVERRIDDEN = ['readyState', 'status', 'statusText', 'response', 'responseText', 'responseURL', 'getResponseHeader', 'getAllResponseHeaders'];
+        xhr.open = function (method, url, async) {
+            for (const name of OVERRIDDEN) delete this[name];
+            const args = Array.from(arguments);
+            const internal = isInternal(String(url));
+            const route = internal ? null : findRoute(String(url), method);
+            if (route && route.rewriteUrl) args[1] = route.rewriteUrl;
+            requests.set(this, { url: String(url), async: async !== false, internal, route });
+            return realOpen.apply(this, args);
+        };
+        const override = (request, values) => {
+            for (const [name, value] of Object.entries(values)) Object.defineProperty(request, name, { configurable: true, value });
+        };
+        // Like a request that could not reach the network
+        const fail = (request) => {
+            override(request, { readyState: 4 });
+            request.dispatchEvent(new Event('readystatechange'));
+            for (const type of ['error', 'loadend']) request.dispatchEvent(new ProgressEvent(type));
+        };
+        const respond = (request, route, url) => {
+            const { text, headers } = mockContent(route);
+            let response = text;
+            if (request.responseType === 'json') {
+                try { response = JSON.parse(text); } catch (_) { response = null; }
+            } else if (request.responseType === 'arraybuffer') {
+                response = new TextEncoder().encode(text).buffer;
+            } else if (request.responseType === 'blob') {
+                response = new Blob([text], { type: headers['content-type'] || '' });
+            } else if (request.responseType === 'document') {
+                response = null;
+            }
+            override(request, {
+                readyState: 4,
+                status: route.status,
+                statusText: '',
+                response,
+                responseText: text,
+                responseURL: new URL(url, location.href).href,
+                getResponseHeader: (name) => headers[String(name).toLowerCase()] ?? null,
+                getAllResponseHeaders: () => Object.entries(headers).map(([name, value]) => `${name}: ${value}\r\n`).join(''),
+            });
+            request.dispatchEvent(new Event('readystatechange'));
+            for (const type of ['load', 'loadend']) {
+                request.dispatchEvent(new ProgressEvent(type, { lengthComputable: true, loaded: text.length, total: text.length }));
+            }
+        };
+        xhr.send = function (body) {
+            const request = requests.get(this);
+            if (!request || request.internal || (!network && !request.route)) return realSend.apply(this, arguments);
+            const route = request.route;
+            const canned = route && !route.rewriteUrl;
+            // Synchronous requests cannot wait
+            if (!request.async) return canned ? respond(this, route, request.url) : realSend.apply(this, arguments);
+            if (network && network.offline) {
+                setTimeout(() => fail(this), 0);
+                return;
+            }
+            const delay = (network ? network.latencyMs + transferTime(bodySize(body), network.uploadKbps) : 0)
+                + (route && route.delayMs ? route.delayMs : 0);
+            if (!delay && !canned) return realSend.apply(this, arguments);
+            setTimeout(() => {
+                if (this.readyState !== XMLHttpRequest.OPENED) return;
+                if (canned) respond(this, route, request.url); else realSend.call(this, body);
+            }, delay);
+        };
+    }
+    const setMockRoutes = (settings) => {
+        routes = (settings || []).map(compileRoute);
+    };
+    const realOnLine = realProperty(navigator, 'onLine');
+    try {
+        Object.defineProperty(navigator, 'onLine', { configurable: true, get: () => network && network.offline ? false : realOnLine() });
+    } catch (_) {}
+    const setNetwork = (settings) => {
+        const wasOnline = navigator.onLine;
+        network = settings ? { offline: !!settings.offline, latencyMs: settings.latencyMs || 0, downloadKbps: settings.downloadKbps || 0, uploadKbps: settings.uploadKbps || 0 } : null;
+        if (navigator.onLine !== wasOnline) window.dispatchEvent(new Event(navigator.onLine ? 'online' : 'offline'));
+    };
+
+    const state = {
+        apply(settings) {
+            settings = settings || {};
+            setClock(settings.clock || null);
+            setRandom(settings.randomSeed);
+            setGeolocation(settings.geolocation || null);
+            setLocale(settings.locale || null);
+            setColorScheme(settings.colorScheme || null);
+            setDevice(settings.device || null);
+            setNetwork(settings.network || null);
+            setMockRoutes(settings.mockRoutes);
+            try {
+                if (Object.keys(settings).length === 0) {
+                    sessionStorage.removeItem(STORAGE_KEY);
+                } else {
+                    sessionStorage.setItem(STORAGE_KEY, JSON.stringify({ settings, savedAt: realDateNow() }));
+                }
+            } catch (_) {}
+        },
+        advanceClock(ms) {
+            if (!clock) return;
+            clock.advanced += ms;
+            try {
+                const saved = JSON.parse(sessionStorage.getItem(STORAGE_KEY) || 'null');
+                if (saved && saved.settings && saved.settings.clock) {
+                    saved.settings.clock.timeMs += ms;
+                    sessionStorage.setItem(STORAGE_KEY, JSON.stringify(saved));
+                }
+            } catch (_) {}
+        },
+    };
+    window.__TAURI_MCP_EMULATION__ = state;
+
+    try {
+        const saved = JSON.parse(sessionStorage.getItem(STORAGE_KEY) || 'null');
+        if (saved && saved.settings) {
+            const settings = saved.settings;
+            // A running clock kept going while the page reloaded
+            if (settings.clock && !settings.clock.frozen) settings.clock.timeMs += realDateNow() - saved.savedAt;
+            state.apply(settings);
+        }
+    } catch (_) {}
+})();
+"#;
+
+/// Emulated clock of a window.
+#[derive(Debug, Clone)]
+struct ClockState {
+    /// Emulated time when the clock was set, in milliseconds since the Unix epoch.
+    time_ms: u64,
+    frozen: bool,
+    set_at: Instant,
+    /// Time added with `advance_clock`.
+    advanced_ms: u64,
+}
+
+impl ClockState {
+    fn now_ms(&self) -> u64 {
+        let running = if self.frozen {
+            0
+        } else {
+            self.set_at.elapsed().as_millis() as u64
+        };
+        self.time_ms + running + self.advanced_ms
+    }
+}
+
+/// Overrides applied to a window, as sent to the webview and reported to clients.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmulationSettings {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub geolocation: Option<GeolocationSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<LocaleSettings>,
+    /// Answer of `prefers-color-scheme` queries.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color_scheme: Option<ColorScheme>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub device: Option<DeviceSettings>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<NetworkConditions>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mock_routes: Vec<MockRoute>,
+}
+
+/// Answer given to the `fetch` and `XMLHttpRequest` requests matching `url`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MockRoute {
+    pub id: String,
+    /// Glob (`*` matches anything) or regular expression, matched against the full URL or
+    /// its path and query.
+    pub url: String,
+    #[serde(default)]
+    pub regex: bool,
+    /// Only match this HTTP method.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Response body; anything but a string is sent as JSON.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// Send the request to this URL instead of answering it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rewrite_url: Option<String>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+/// Conditions applied to `fetch` and `XMLHttpRequest` requests of the page.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkConditions {
+    /// Fail requests as if the network were down.
+    #[serde(default)]
+    pub offline: bool,
+    /// Delay added before each request.
+    #[serde(default)]
+    pub latency_ms: u64,
+    /// Response bodies are read at this rate, in kilobits per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_kbps: Option<u64>,
+    /// Request bodies are sent at this rate, in kilobits per second.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub upload_kbps: Option<u64>,
+}
+
+/// Emulated device: viewport size, pixel ratio, touch support and user agent.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceSettings {
+    /// Profile the settings come from, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Viewport size in CSS pixels.
+    pub width: u32,
+    pub height: u32,
+    pub device_scale_factor: f64,
+    pub touch: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorScheme {
+    Light,
+    Dark,
+}
+
+/// Locale, languages and timezone reported to the page.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LocaleSettings {
+    /// Default locale of `Intl` and the `toLocale*` methods.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// `navigator.languages`, the first one being `navigator.language`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<String>,
+    /// IANA timezone of local dates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+/// Position reported by `navigator.geolocation`, or a permission denial.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeolocationSettings {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Accuracy radius in meters.
+    pub accuracy: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub altitude: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heading: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f64>,
+    /// Fail requests with PERMISSION_DENIED instead.
+    #[serde(default)]
+    pub denied: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClockSettings {
+    /// Current emulated time, in milliseconds since the Unix epoch.
+    pub time_ms: u64,
+    /// Whether the clock only moves with `advance_clock`.
+    pub frozen: bool,
+}
+
+#[derive(Default)]
+struct WindowEmulation {
+    clock: Option<ClockState>,
+    random_seed: Option<u32>,
+    geolocation: Option<GeolocationSettings>,
+    locale: Option<LocaleSettings>,
+    color_scheme: Option<ColorScheme>,
+    device: Option<DeviceSettings>,
+    network: Option<NetworkConditions>,
+    mock_routes: Vec<MockRoute>,
+    /// Inner size of the window before the device emulation resized it.
+    original_size: Option<LogicalSize<f64>>,
+}
+
+impl WindowEmulation {
+    fn settings(&self) -> EmulationSettings {
+        EmulationSettings {
+            clock: self.clock.as_ref().map(|clock| ClockSettings {
+                time_ms: clock.now_ms(),
+                frozen: clock.frozen,
+            }),
+            random_seed: self.random_seed,
+            geolocation: self.geolocation.clone(),
+            locale: self.locale.clone(),
+            color_scheme: self.color_scheme,
+            device: self.device.clone(),
+            network: self.network.clone(),
+            mock_routes: self.mock_routes.clone(),
+        }
+    }
+}
+
+/// Overrides that `reset` removes by name.
+const FEATURES: &[&str] = &[
+    "clock",
+    "random",
+    "geolocation",
+    "locale",
+    "theme",
+    "device",
+    "network",
+    "mocks",
+];
+
+/// Overrides of browser APIs (clock, randomness, geolocation, locale, color scheme, device,
+/// network, mock routes) in the webviews, so runs are
+/// reproducible.
+pub struct Emulation {
+    windows: Mutex<HashMap<String, WindowEmulation>>,
+    next_route: AtomicU64,
+}
+
+impl Emulation {
+    pub(crate) fn new() -> Self {
+        Self {
+            windows: Mutex::new(HashMap::new()),
+            next_route: AtomicU64::new(1),
+        }
+    }
+
+    pub(crate) fn init_script() -> String {
+        EMULATION_HOOK_SCRIPT.to_string()
+    }
+
+    /// Overrides currently applied to a window.
+    pub fn settings(&self, window_label: &str) -> EmulationSettings {
+        self.windows
+            .lock()
+            .unwrap()
+            .get(window_label)
+            .map(WindowEmulation::settings)
+            .unwrap_or_default()
+    }
+
+    /// Set the clock of a window, starting at `time_ms` (now when None).
+    pub fn set_clock<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        time_ms: Option<u64>,
+        frozen: bool,
+    ) -> crate::Result<EmulationSettings> {
+        let time_ms = time_ms.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or_default()
+        });
+        self.update(app, window_label, |emulation| {
+            emulation.clock = Some(ClockState {
+                time_ms,
+                frozen,
+                set_at: Instant::now(),
+                advanced_ms: 0,
+            });
+        })
+    }
+
+    /// Move the emulated clock of a window forward.
+    pub fn advance_clock<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        ms: u64,
+    ) -> crate::Result<EmulationSettings> {
+        let window = webview_window(app, window_label)?;
+        let settings = {
+            let mut windows = self.windows.lock().unwrap();
+            let clock = windows
+                .get_mut(window_label)
+                .and_then(|emulation| emulation.clock.as_mut())
+                .ok_or_else(|| {
+                    crate::Error::Anyhow(format!(
+                        "The clock of window {} is not emulated",
+                        window_label
+                    ))
+                })?;
+            clock.advanced_ms += ms;
+            windows[window_label].settings()
+        };
+        // Setting the clock again would restart it, so only move it
+        eval(
+            window.as_ref(),
+            &format!(
+                "window.__TAURI_MCP_EMULATION__ && window.__TAURI_MCP_EMULATION__.advanceClock({});",
+                ms
+            ),
+        );
+        Ok(settings)
+    }
+
+    /// Seed `Math.random` and the crypto helpers of a window.
+    pub fn set_random_seed<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        seed: u32,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            emulation.random_seed = Some(seed);
+        })
+    }
+
+    /// Override the position reported to a window.
+    pub fn set_geolocation<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        geolocation: GeolocationSettings,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            emulation.geolocation = Some(geolocation);
+        })
+    }
+
+    /// Override the locale, languages and timezone of a window.
+    pub fn set_locale<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        locale: LocaleSettings,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            emulation.locale = Some(locale);
+        })
+    }
+
+    /// Override the `prefers-color-scheme` of a window.
+    pub fn set_color_scheme<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        color_scheme: ColorScheme,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            emulation.color_scheme = Some(color_scheme);
+        })
+    }
+
+    /// Emulate a device in a window, resizing its content area to the device viewport.
+    pub fn set_device<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        device: DeviceSettings,
+    ) -> crate::Result<EmulationSettings> {
+        let window = webview_window(app, window_label)?;
+        let current = window
+            .inner_size()
+            .and_then(|size| Ok(size.to_logical::<f64>(window.scale_factor()?)))
+            .map_err(|e| crate::Error::Anyhow(format!("Failed to read window size: {}", e)))?;
+        resize(&window, LogicalSize::new(device.width, device.height))?;
+        self.update(app, window_label, |emulation| {
+            emulation.original_size.get_or_insert(current);
+            emulation.device = Some(device);
+        })
+    }
+
+    /// Slow down or cut the network requests of a window.
+    pub fn set_network<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        network: NetworkConditions,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            emulation.network = Some(network);
+        })
+    }
+
+    /// Id for a mock route added without one.
+    pub(crate) fn next_route_id(&self) -> String {
+        format!("route-{}", self.next_route.fetch_add(1, Ordering::SeqCst))
+    }
+
+    /// Add a mock route to a window, replacing the route with the same id.
+    pub fn add_mock_route<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        route: MockRoute,
+    ) -> crate::Result<EmulationSettings> {
+        self.update(app, window_label, |emulation| {
+            match emulation.mock_routes.iter_mut().find(|r| r.id == route.id) {
+                Some(existing) => *existing = route,
+                None => emulation.mock_routes.push(route),
+            }
+        })
+    }
+
+    /// Remove a mock route of a window.
+    pub fn remove_mock_route<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        id: &str,
+    ) -> crate::Result<EmulationSettings> {
+        let known = self
+            .windows
+            .lock()
+            .unwrap()
+            .get(window_label)
+            .is_some_and(|emulation| emulation.mock_routes.iter().any(|r| r.id == id));
+        if !known {
+            return Err(crate::Error::Anyhow(format!(
+                "Mock route not found: {}",
+                id
+            )));
+        }
+        self.update(app, window_label, |emulation| {
+            emulation.mock_routes.retain(|r| r.id != id);
+        })
+    }
+
+    /// Remove the named overrides of a window ("clock", "random", "geolocation", "locale",
+    /// "theme", "device", "network", "mocks"), or all of them with "all".
+    pub fn reset<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        features: &[String],
+    ) -> crate::Result<EmulationSettings> {
+        for feature in features {
+            if feature != "all" && !FEATURES.contains(&feature.as_str()) {
+                return Err(crate::Error::Anyhow(format!(
+                    "Unknown emulation feature: {}",
+                    feature
+                )));
+            }
+        }
+        let reset = |name: &str| features.iter().any(|f| f == name || f == "all");
+        let mut original_size = None;
+        let settings = self.update(app, window_label, |emulation| {
+            if reset("clock") {
+                emulation.clock = None;
+            }
+            if reset("random") {
+                emulation.random_seed = None;
+            }
+            if reset("geolocation") {
+                emulation.geolocation = None;
+            }
+            if reset("locale") {
+                emulation.locale = None;
+            }
+            if reset("theme") {
+                emulation.color_scheme = None;
+            }
+            if reset("network") {
+                emulation.network = None;
+            }
+            if reset("mocks") {
+                emulation.mock_routes.clear();
+            }
+            if reset("device") {
+                emulation.device = None;
+                original_size = emulation.original_size.take();
+            }
+        })?;
+        if let Some(size) = original_size {
+            resize(&webview_window(app, window_label)?, size)?;
+        }
+        Ok(settings)
+    }
+
+    fn update<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        change: impl FnOnce(&mut WindowEmulation),
+    ) -> crate::Result<EmulationSettings> {
+        let window = webview_window(app, window_label)?;
+        let settings = {
+            let mut windows = self.windows.lock().unwrap();
+            let emulation = windows.entry(window_label.to_string()).or_default();
+            change(emulation);
+            emulation.settings()
+        };
+        self.apply(window.as_ref(), &settings);
+        Ok(settings)
+    }
+
+    /// Apply the overrides of a webview again, e.g. after a page load.
+    pub(crate) fn sync<R: Runtime>(&self, webview: &tauri::Webview<R>) {
+        let Some(settings) = self
+            .windows
+            .lock()
+            .unwrap()
+            .get(webview.label())
+            .map(WindowEmulation::settings)
+        else {
+            return;
+        };
+        self.apply(webview, &settings);
+    }
+
+    fn apply<R: Runtime>(&self, webview: &tauri::Webview<R>, settings: &EmulationSettings) {
+        let settings = serde_json::to_value(settings).unwrap_or(Value::Null);
+        eval(
+            webview,
+            &format!(
+                "window.__TAURI_MCP_EMULATION__ && window.__TAURI_MCP_EMULATION__.apply({});",
+                settings
+            ),
+        );
+    }
+}
+
+fn webview_window<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: &str,
+) -> crate::Result<tauri::WebviewWindow<R>> {
+    crate::windows::resolve_window(app, window_label)
+}
+
+#[cfg(desktop)]
+fn resize<R: Runtime>(
+    window: &tauri::WebviewWindow<R>,
+    size: impl Into<tauri::Size>,
+) -> crate::Result<()> {
+    window
+        .set_size(size)
+        .map_err(|e| crate::Error::Anyhow(format!("Failed to resize window: {}", e)))
+}
+
+#[cfg(mobile)]
+fn resize<R: Runtime>(
+    _window: &tauri::WebviewWindow<R>,
+    _size: impl Into<tauri::Size>,
+) -> crate::Result<()> {
+    Err(crate::mobile::unsupported("Resizing windows"))
+}
+
+fn eval<R: Runtime>(webview: &tauri::Webview<R>, script: &str) {
+    if let Err(e) = webview.eval(script) {
+        warn!(
+            "[TAURI_MCP] Failed to update emulation in {}: {}",
+            webview.label(),
+            e
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use log::info;
+
+use crate::error::Error;
+use crate::{PluginConfig, SocketType};
+
+/// Environment variable moving the socket server to another IPC socket path.
+pub const SOCKET_PATH_ENV: &str = "TAURI_MCP_SOCKET_PATH";
+
+/// Environment variable moving the socket server to TCP on this port.
+pub const PORT_ENV: &str = "TAURI_MCP_PORT";
+
+/// Environment variable keeping the socket server from starting (`1`, `true`, `yes`).
+pub const DISABLE_ENV: &str = "TAURI_MCP_DISABLE";
+
+/// Environment variable setting the token clients must send in their handshake.
+pub const TOKEN_ENV: &str = "TAURI_MCP_TOKEN";
+
+/// Override `config` with the environment of the app, so a run can redirect or disable the
+/// server without code changes. The environment wins over the code and `tauri.conf.json`.
+pub fn apply(mut config: PluginConfig) -> crate::Result<PluginConfig> {
+    let socket_path = var(SOCKET_PATH_ENV);
+    let port = var(PORT_ENV);
+    match (socket_path, port) {
+        (Some(_), Some(_)) => {
+            return Err(Error::PluginInit(format!(
+                "{} and {} are both set, choose one transport",
+                SOCKET_PATH_ENV, PORT_ENV
+            )));
+        }
+        (Some(path), None) => {
+            info!(
+                "[TAURI_MCP] {} moves the socket to {}",
+                SOCKET_PATH_ENV, path
+            );
+            config = config.socket_path(path.into());
+        }
+        (None, Some(port)) => {
+            let port: u16 = port.parse().map_err(|_| {
+                Error::PluginInit(format!("{} is not a valid port: {}", PORT_ENV, port))
+            })?;
+            // A TCP config keeps its host, e.g. 0.0.0.0 for devices
+            let host = match &config.socket_type {
+                SocketType::Tcp { host, .. } => host.clone(),
+                SocketType::Ipc { .. } => "127.0.0.1".to_string(),
+            };
+            info!(
+                "[TAURI_MCP] {} moves the socket to {}:{}",
+                PORT_ENV, host, port
+            );
+            config = config.tcp(host, port);
+        }
+        (None, None) => {}
+    }
+    if var(DISABLE_ENV).is_some_and(|value| crate::server_mode::is_truthy(&value)) {
+        info!(
+            "[TAURI_MCP] {} keeps the socket server from starting",
+            DISABLE_ENV
+        );
+        config = config.start_socket_server(false);
+    }
+    if let Some(token) = var(TOKEN_ENV) {
+        config = config.auth_token(token);
+    }
+    Ok(config)
+}
+
+/// The value of an environment variable, None when unset or blank.
+fn var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
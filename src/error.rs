@@ -23,6 +23,62 @@ pub enum Error {
 
     #[error("Tauri error: {0}")]
     TauriError(String),
+
+    #[error("Artifact store error: {0}")]
+    Artifact(String),
+
+    #[error("Request cancelled: {0}")]
+    Cancelled(String),
+
+    #[error("The app is shutting down: {0}")]
+    ShuttingDown(String),
+
+    #[error("{}", unknown_window_message(label, available, suggestion.as_deref()))]
+    UnknownWindow {
+        /// The requested label, empty when no window is open.
+        label: String,
+        /// Labels of the open windows.
+        available: Vec<String>,
+        /// The open window the label most likely meant.
+        suggestion: Option<String>,
+    },
+}
+
+impl Error {
+    /// Structured details of the error, sent as the `data` of the failed response.
+    pub fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            Self::UnknownWindow {
+                label,
+                available,
+                suggestion,
+            } => Some(serde_json::json!({
+                "windowLabel": label,
+                "availableLabels": available,
+                "suggestion": suggestion,
+            })),
+            _ => None,
+        }
+    }
+}
+
+fn unknown_window_message(label: &str, available: &[String], suggestion: Option<&str>) -> String {
+    if available.is_empty() {
+        return if label.is_empty() {
+            "No window is open".to_string()
+        } else {
+            format!("Window not found: {}. No window is open", label)
+        };
+    }
+    let mut message = format!(
+        "Window not found: {}. Available windows: {}",
+        label,
+        available.join(", ")
+    );
+    if let Some(suggestion) = suggestion {
+        message.push_str(&format!(". Did you mean {}?", suggestion));
+    }
+    message
 }
 
 impl From<std::io::Error> for Error {
@@ -37,6 +93,13 @@ impl From<anyhow::Error> for Error {
     }
 }
 
+#[cfg(mobile)]
+impl From<tauri::plugin::mobile::PluginInvokeError> for Error {
+    fn from(error: tauri::plugin::mobile::PluginInvokeError) -> Self {
+        Self::Anyhow(error.to_string())
+    }
+}
+
 impl From<tauri::Error> for Error {
     fn from(error: tauri::Error) -> Self {
         Self::TauriError(error.to_string())
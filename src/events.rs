@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use crate::audit::now_ms;
+
+/// How long the cursor of a session that stopped polling is kept.
+const SESSION_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Most sessions remembered, the ones polled least recently are forgotten first.
+const MAX_SESSIONS: usize = 256;
+
+/// A notification produced by the plugin (client connections, window and page events...).
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpEvent {
+    /// Monotonic sequence number, used as the polling cursor.
+    pub seq: u64,
+    pub timestamp_ms: u64,
+    pub kind: String,
+    pub data: Value,
+}
+
+/// Result of a poll on the event bus.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollResult {
+    pub events: Vec<McpEvent>,
+    /// Cursor to pass to the next poll.
+    pub cursor: u64,
+    /// True when events older than the buffer were dropped before the client could fetch them.
+    pub missed_events: bool,
+}
+
+struct Inner {
+    events: VecDeque<McpEvent>,
+    next_seq: u64,
+    /// Last cursor of each long-poll session and when it was stored, for clients that
+    /// cannot keep state.
+    sessions: HashMap<String, (u64, Instant)>,
+}
+
+impl Inner {
+    /// Store the cursor of a session, forgetting sessions idle for too long and the least
+    /// recently polled ones beyond the limit.
+    fn save_session(&mut self, id: &str, cursor: u64, now: Instant) {
+        self.sessions
+            .retain(|_, (_, polled)| now.duration_since(*polled) < SESSION_TTL);
+        self.sessions.insert(id.to_string(), (cursor, now));
+        while self.sessions.len() > MAX_SESSIONS {
+            let Some(oldest) = self
+                .sessions
+                .iter()
+                .min_by_key(|(_, (_, polled))| *polled)
+                .map(|(id, _)| id.clone())
+            else {
+                break;
+            };
+            self.sessions.remove(&oldest);
+        }
+    }
+}
+
+/// Buffers plugin events so clients without persistent connections can fetch them
+/// with cursor-based long polling.
+pub struct EventBus {
+    inner: Mutex<Inner>,
+    notify: Notify,
+    capacity: usize,
+}
+
+impl EventBus {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                events: VecDeque::with_capacity(capacity.min(1024)),
+                next_seq: 1,
+                sessions: HashMap::new(),
+            }),
+            notify: Notify::new(),
+            capacity,
+        }
+    }
+
+    /// Publish an event and wake up pending polls.
+    pub fn publish(&self, kind: &str, data: Value) {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            let event = McpEvent {
+                seq: inner.next_seq,
+                timestamp_ms: now_ms(),
+                kind: kind.to_string(),
+                data,
+            };
+            inner.next_seq += 1;
+            if self.capacity > 0 {
+                while inner.events.len() >= self.capacity {
+                    inner.events.pop_front();
+                }
+                inner.events.push_back(event);
+            }
+        }
+        self.notify.notify_waiters();
+    }
+
+    /// Sequence number of the most recent event (0 if none was published yet).
+    pub fn latest_seq(&self) -> u64 {
+        self.inner.lock().unwrap().next_seq - 1
+    }
+
+    /// Fetch events after `cursor`, waiting up to `timeout` for new ones if none are buffered.
+    ///
+    /// When `cursor` is None the session's stored cursor is used, so a client only needs
+    /// to remember its session ID. Sessions not polled for 10 minutes start over from the
+    /// oldest buffered event.
+    pub async fn poll(
+        &self,
+        session_id: Option<&str>,
+        cursor: Option<u64>,
+        kinds: Option<&[String]>,
+        max_events: usize,
+        timeout: Duration,
+    ) -> PollResult {
+        let deadline = Instant::now() + timeout;
+        let mut cursor = cursor.unwrap_or_else(|| {
+            let inner = self.inner.lock().unwrap();
+            session_id
+                .and_then(|id| inner.sessions.get(id))
+                .map_or(0, |(cursor, _)| *cursor)
+        });
+
+        loop {
+            // Register interest before checking so a publish in between is not missed
+            let notified = self.notify.notified();
+
+            let result = self.collect(cursor, kinds, max_events);
+            cursor = result.cursor;
+            let now = Instant::now();
+            if !result.events.is_empty() || result.missed_events || now >= deadline {
+                if let Some(id) = session_id {
+                    let mut inner = self.inner.lock().unwrap();
+                    inner.save_session(id, result.cursor, now);
+                }
+                return result;
+            }
+
+            let _ = tokio::time::timeout(deadline - now, notified).await;
+        }
+    }
+
+    fn collect(&self, cursor: u64, kinds: Option<&[String]>, max_events: usize) -> PollResult {
+        let inner = self.inner.lock().unwrap();
+        let oldest = inner.events.front().map_or(inner.next_seq, |e| e.seq);
+        let missed_events = cursor + 1 < oldest && cursor < inner.next_seq - 1;
+
+        let mut events = Vec::new();
+        // Filtered out events still advance the cursor
+        let mut new_cursor = cursor.max(inner.next_seq - 1);
+        for event in inner.events.iter().filter(|e| e.seq > cursor) {
+            if events.len() >= max_events {
+                new_cursor = event.seq - 1;
+                break;
+            }
+            if kinds.is_none_or(|k| k.iter().any(|kind| kind == &event.kind)) {
+                events.push(event.clone());
+            }
+        }
+
+        PollResult {
+            events,
+            cursor: new_cursor,
+            missed_events,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn bus_with(capacity: usize, kinds: &[&str]) -> EventBus {
+        let bus = EventBus::new(capacity);
+        for (index, kind) in kinds.iter().enumerate() {
+            bus.publish(kind, json!({ "index": index }));
+        }
+        bus
+    }
+
+    fn seqs(result: &PollResult) -> Vec<u64> {
+        result.events.iter().map(|event| event.seq).collect()
+    }
+
+    #[test]
+    fn collect_returns_events_after_the_cursor() {
+        let bus = bus_with(10, &["a", "b", "c"]);
+        let result = bus.collect(1, None, 100);
+        assert_eq!(seqs(&result), [2, 3]);
+        assert_eq!(result.cursor, 3);
+        assert!(!result.missed_events);
+
+        let result = bus.collect(3, None, 100);
+        assert!(result.events.is_empty());
+        assert_eq!(result.cursor, 3);
+    }
+
+    #[test]
+    fn filtered_events_still_advance_the_cursor() {
+        let bus = bus_with(10, &["a", "b", "a", "b"]);
+        let kinds = ["a".to_string()];
+        let result = bus.collect(0, Some(&kinds), 100);
+        assert_eq!(seqs(&result), [1, 3]);
+        assert_eq!(result.cursor, 4);
+    }
+
+    #[test]
+    fn max_events_stops_the_cursor_at_the_last_event_returned() {
+        let bus = bus_with(10, &["a", "b", "c", "d"]);
+        let result = bus.collect(0, None, 2);
+        assert_eq!(seqs(&result), [1, 2]);
+        assert_eq!(result.cursor, 2);
+        assert_eq!(seqs(&bus.collect(result.cursor, None, 2)), [3, 4]);
+    }
+
+    #[test]
+    fn dropped_events_are_reported_as_missed() {
+        let bus = bus_with(2, &["a", "b", "c", "d"]);
+        let result = bus.collect(0, None, 100);
+        assert_eq!(seqs(&result), [3, 4]);
+        assert!(result.missed_events);
+
+        // Right before the oldest buffered event nothing was missed
+        assert!(!bus.collect(2, None, 100).missed_events);
+        assert!(!bus.collect(4, None, 100).missed_events);
+    }
+
+    #[test]
+    fn empty_bus_misses_nothing() {
+        let bus = EventBus::new(10);
+        let result = bus.collect(0, None, 100);
+        assert!(result.events.is_empty());
+        assert_eq!(result.cursor, 0);
+        assert!(!result.missed_events);
+        assert_eq!(bus.latest_seq(), 0);
+    }
+
+    #[test]
+    fn zero_capacity_keeps_counting() {
+        let bus = bus_with(0, &["a", "b"]);
+        assert_eq!(bus.latest_seq(), 2);
+        let result = bus.collect(0, None, 100);
+        assert!(result.events.is_empty());
+        assert_eq!(result.cursor, 2);
+    }
+
+    #[tokio::test]
+    async fn sessions_remember_their_cursor() {
+        let bus = bus_with(10, &["a", "b"]);
+        let first = bus.poll(Some("s"), None, None, 100, Duration::ZERO).await;
+        assert_eq!(seqs(&first), [1, 2]);
+
+        bus.publish("c", Value::Null);
+        let second = bus.poll(Some("s"), None, None, 100, Duration::ZERO).await;
+        assert_eq!(seqs(&second), [3]);
+
+        // Another session starts from the beginning
+        let other = bus.poll(Some("t"), None, None, 100, Duration::ZERO).await;
+        assert_eq!(seqs(&other), [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn poll_waits_for_a_publish() {
+        let bus = Arc::new(EventBus::new(10));
+        let publisher = bus.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            publisher.publish("late", Value::Null);
+        });
+        let result = bus
+            .poll(None, Some(0), None, 100, Duration::from_secs(5))
+            .await;
+        assert_eq!(result.events[0].kind, "late");
+    }
+
+    #[tokio::test]
+    async fn poll_times_out_without_events() {
+        let bus = bus_with(10, &["a"]);
+        let result = bus
+            .poll(None, Some(1), None, 100, Duration::from_millis(10))
+            .await;
+        assert!(result.events.is_empty());
+        assert_eq!(result.cursor, 1);
+    }
+
+    #[test]
+    fn idle_sessions_expire() {
+        let bus = EventBus::new(10);
+        let mut inner = bus.inner.lock().unwrap();
+        let start = Instant::now();
+        inner.save_session("idle", 5, start);
+        inner.save_session("active", 7, start + SESSION_TTL / 2);
+        inner.save_session("new", 9, start + SESSION_TTL);
+        assert!(!inner.sessions.contains_key("idle"));
+        assert_eq!(inner.sessions["active"].0, 7);
+        assert_eq!(inner.sessions["new"].0, 9);
+    }
+
+    #[test]
+    fn least_recently_polled_sessions_go_first() {
+        let bus = EventBus::new(10);
+        let mut inner = bus.inner.lock().unwrap();
+        let start = Instant::now();
+        for index in 0..MAX_SESSIONS {
+            inner.save_session(
+                &index.to_string(),
+                index as u64,
+                start + Duration::from_millis(index as u64),
+            );
+        }
+        // Polling again moves a session to the back of the line
+        inner.save_session("0", 1, start + Duration::from_secs(1));
+        inner.save_session("extra", 1, start + Duration::from_secs(2));
+        assert_eq!(inner.sessions.len(), MAX_SESSIONS);
+        assert!(inner.sessions.contains_key("0"));
+        assert!(!inner.sessions.contains_key("1"));
+        assert!(inner.sessions.contains_key("extra"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Directories `read_app_file` and `write_app_file` may access, by name.
+pub struct FileRoots {
+    roots: BTreeMap<String, PathBuf>,
+}
+
+impl FileRoots {
+    /// Roots given as relative paths are resolved against `base`, the app data dir.
+    pub(crate) fn new(roots: &[(String, PathBuf)], base: &Path) -> Self {
+        Self {
+            roots: roots
+                .iter()
+                .map(|(name, dir)| (name.clone(), base.join(dir)))
+                .collect(),
+        }
+    }
+
+    /// Names and directories of the roots.
+    pub fn roots(&self) -> &BTreeMap<String, PathBuf> {
+        &self.roots
+    }
+
+    /// Resolve a path relative to a root, refusing anything outside of it, symbolic links
+    /// included. The file itself does not have to exist, but its directory does unless
+    /// `create_parents` is set, in which case the missing directories are created.
+    pub fn resolve(&self, root: &str, path: &str, create_parents: bool) -> crate::Result<PathBuf> {
+        let dir = self.roots.get(root).ok_or_else(|| {
+            crate::Error::Anyhow(format!(
+                "Unknown file root {}, configured roots: {}",
+                root,
+                self.roots.keys().cloned().collect::<Vec<_>>().join(", ")
+            ))
+        })?;
+        let relative = Path::new(path);
+        if relative
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(crate::Error::Anyhow(format!(
+                "Path {} has to be relative to the root, without ..",
+                path
+            )));
+        }
+
+        if create_parents {
+            std::fs::create_dir_all(dir)?;
+        }
+        let dir = dir.canonicalize().map_err(|e| {
+            crate::Error::Anyhow(format!("File root {} is not available: {}", root, e))
+        })?;
+        let target = dir.join(relative);
+        // Check the part that exists before creating anything, links may lead elsewhere
+        let exists = |path: &Path| path.symlink_metadata().is_ok();
+        let mut existing = target.as_path();
+        while !exists(existing) {
+            existing = existing.parent().unwrap_or(&dir);
+        }
+        let existing = existing.canonicalize()?;
+        if !existing.starts_with(&dir) {
+            return Err(crate::Error::Anyhow(format!(
+                "Path {} leads outside of the {} root",
+                path, root
+            )));
+        }
+        if exists(&target) {
+            return Ok(existing);
+        }
+
+        let (Some(parent), Some(file_name)) = (target.parent(), target.file_name()) else {
+            return Err(crate::Error::Anyhow(format!(
+                "Path {} does not name a file",
+                path
+            )));
+        };
+        if create_parents {
+            std::fs::create_dir_all(parent)?;
+        }
+        Ok(parent.canonicalize()?.join(file_name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scratch directory removed at the end of the test.
+    struct Scratch(PathBuf);
+
+    impl Scratch {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!(
+                "tauri-mcp-files-{}-{}",
+                std::process::id(),
+                name
+            ));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir.canonicalize().unwrap())
+        }
+
+        /// Roots "data" in `data/` and "other" in `other/`.
+        fn roots(&self) -> FileRoots {
+            std::fs::create_dir_all(self.0.join("data/sub")).unwrap();
+            std::fs::create_dir_all(self.0.join("other")).unwrap();
+            std::fs::write(self.0.join("data/sub/file.txt"), "data").unwrap();
+            std::fs::write(self.0.join("other/secret.txt"), "secret").unwrap();
+            FileRoots::new(
+                &[
+                    ("data".to_string(), PathBuf::from("data")),
+                    ("other".to_string(), self.0.join("other")),
+                ],
+                &self.0,
+            )
+        }
+    }
+
+    impl Drop for Scratch {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn resolves_existing_and_new_files() {
+        let scratch = Scratch::new("resolve");
+        let roots = scratch.roots();
+        assert_eq!(
+            roots.resolve("data", "sub/file.txt", false).unwrap(),
+            scratch.0.join("data/sub/file.txt")
+        );
+        assert_eq!(
+            roots.resolve("data", "./sub/new.txt", false).unwrap(),
+            scratch.0.join("data/sub/new.txt")
+        );
+        assert_eq!(
+            roots.resolve("other", "secret.txt", false).unwrap(),
+            scratch.0.join("other/secret.txt")
+        );
+    }
+
+    #[test]
+    fn unknown_roots_list_the_configured_ones() {
+        let scratch = Scratch::new("unknown");
+        let error = scratch
+            .roots()
+            .resolve("logs", "file.txt", false)
+            .unwrap_err();
+        assert!(error.to_string().contains("configured roots: data, other"));
+    }
+
+    #[test]
+    fn refuses_paths_leaving_the_root() {
+        let scratch = Scratch::new("escape");
+        let roots = scratch.roots();
+        for path in [
+            "../other/secret.txt",
+            "sub/../../other/secret.txt",
+            "/etc/passwd",
+        ] {
+            assert!(roots.resolve("data", path, false).is_err(), "{}", path);
+        }
+    }
+
+    #[test]
+    fn missing_directories_need_create_parents() {
+        let scratch = Scratch::new("parents");
+        let roots = scratch.roots();
+        assert!(roots.resolve("data", "a/b/new.txt", false).is_err());
+        assert!(!scratch.0.join("data/a").exists());
+
+        assert_eq!(
+            roots.resolve("data", "a/b/new.txt", true).unwrap(),
+            scratch.0.join("data/a/b/new.txt")
+        );
+        assert!(scratch.0.join("data/a/b").is_dir());
+    }
+
+    #[test]
+    fn missing_roots_are_created_with_create_parents() {
+        let scratch = Scratch::new("root");
+        let roots = FileRoots::new(&[("cache".to_string(), PathBuf::from("cache"))], &scratch.0);
+        assert!(roots.resolve("cache", "file.txt", false).is_err());
+        assert_eq!(
+            roots.resolve("cache", "file.txt", true).unwrap(),
+            scratch.0.join("cache/file.txt")
+        );
+    }
+
+    #[test]
+    fn empty_path_is_the_root() {
+        let scratch = Scratch::new("dir");
+        assert_eq!(
+            scratch.roots().resolve("data", "", false).unwrap(),
+            scratch.0.join("data")
+        );
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn refuses_links_leading_outside() {
+        let scratch = Scratch::new("links");
+        let roots = scratch.roots();
+        std::os::unix::fs::symlink(scratch.0.join("other"), scratch.0.join("data/out")).unwrap();
+        std::os::unix::fs::symlink(
+            scratch.0.join("other/secret.txt"),
+            scratch.0.join("data/secret.txt"),
+        )
+        .unwrap();
+
+        assert!(roots.resolve("data", "out/secret.txt", false).is_err());
+        assert!(roots.resolve("data", "out/new.txt", true).is_err());
+        assert!(roots.resolve("data", "secret.txt", false).is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn follows_links_staying_inside() {
+        let scratch = Scratch::new("inner-links");
+        let roots = scratch.roots();
+        std::os::unix::fs::symlink(scratch.0.join("data/sub"), scratch.0.join("data/alias"))
+            .unwrap();
+        assert_eq!(
+            roots.resolve("data", "alias/file.txt", false).unwrap(),
+            scratch.0.join("data/sub/file.txt")
+        );
+    }
+}
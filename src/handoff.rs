@@ -0,0 +1,315 @@
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::OwnedMutexGuard;
+
+use crate::TauriMcpExt;
+use crate::audit::now_ms;
+use crate::shared::commands;
+
+/// Event emitted by the webview hook when the user hands control back to the agent.
+pub(crate) const RESUME_REQUESTED_EVENT: &str = "tauri-mcp:resume-requested";
+
+/// Banner text when `pause_session` gives no message.
+pub const DEFAULT_PAUSE_MESSAGE: &str = "MCP agent paused \u{2014} user in control";
+
+/// Commands simulating user input, held while the session is paused.
+pub(crate) const QUEUED_COMMANDS: &[&str] = &[
+    commands::SIMULATE_TEXT_INPUT,
+    commands::SIMULATE_MOUSE_MOVEMENT,
+    commands::SIMULATE_KEY_PRESS,
+    commands::SEND_TEXT_TO_ELEMENT,
+    commands::CLICK_ELEMENT,
+    commands::SCROLL_INTO_VIEW,
+    commands::SIMULATE_TOUCH,
+    commands::SET_ELEMENT_VALUE,
+    commands::SET_FILE_INPUT,
+    commands::FILL_FORM,
+    commands::FOCUS_ELEMENT,
+    commands::BLUR_ELEMENT,
+    commands::CLICK_MENU_ITEM,
+    commands::TRIGGER_TRAY_MENU_ITEM,
+    commands::CLICK_TRAY,
+];
+
+/// Injected in every webview. Shows the pause banner on top of the page, in a closed
+/// shadow root so the app's styles and selectors do not reach it, with a button handing
+/// control back to the agent.
+const HANDOFF_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_HANDOFF__) return;
+    let banner = null;
+    let text = null;
+    const resume = () => {
+        const internals = window.__TAURI_INTERNALS__;
+        if (!internals) return;
+        internals.invoke('plugin:event|emit', {
+            event: '__EVENT__',
+            payload: { windowLabel: internals.metadata.currentWindow.label },
+        }).catch(() => {});
+    };
+    const create = () => {
+        banner = document.createElement('div');
+        banner.setAttribute('data-tauri-mcp-handoff', '');
+        const root = banner.attachShadow({ mode: 'closed' });
+        root.innerHTML = `
+            <style>
+                :host { all: initial; position: fixed; top: 0; left: 0; right: 0; z-index: 2147483647; }
+                .bar { display: flex; align-items: center; justify-content: center; gap: 12px; padding: 6px 12px;
+                    background: #b45309; color: #fff; font: 600 13px/1.4 system-ui, sans-serif;
+                    box-shadow: 0 1px 4px rgba(0, 0, 0, 0.3); }
+                button { font: inherit; font-weight: 500; color: #b45309; background: #fff; border: 0;
+                    border-radius: 4px; padding: 2px 10px; cursor: pointer; }
+            </style>
+            <div class="bar" role="status"><span></span><button type="button">Resume agent</button></div>`;
+        text = root.querySelector('span');
+        root.querySelector('button').addEventListener('click', resume);
+    };
+    const state = {
+        show(message) {
+            const mount = () => {
+                if (!banner) create();
+                text.textContent = message;
+                if (!banner.isConnected) document.documentElement.appendChild(banner);
+            };
+            if (document.documentElement) mount();
+            else document.addEventListener('DOMContentLoaded', mount, { once: true });
+        },
+        hide() {
+            if (banner) banner.remove();
+        },
+    };
+    window.__TAURI_MCP_HANDOFF__ = state;
+})();
+"#;
+
+/// A command waiting for the session to resume.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueuedCommand {
+    pub id: u64,
+    pub command: String,
+    pub queued_ms: u64,
+}
+
+/// Whether the session is paused, and what waits for it to resume.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseStatus {
+    pub paused: bool,
+    /// Text of the banner.
+    pub message: Option<String>,
+    pub paused_ms: Option<u64>,
+    /// Queued commands, oldest first.
+    pub queued: Vec<QueuedCommand>,
+}
+
+/// What a resume did with the queued commands.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeOutcome {
+    /// Whether the session was paused.
+    pub resumed: bool,
+    /// Commands let through, in the order they arrived.
+    pub released: usize,
+    /// Commands answered with an error instead.
+    pub discarded: usize,
+}
+
+struct Paused {
+    message: String,
+    paused_ms: u64,
+    /// Lock on the gate, released on resume.
+    _guard: OwnedMutexGuard<()>,
+}
+
+#[derive(Default)]
+struct PauseState {
+    paused: Option<Paused>,
+    queued: Vec<QueuedCommand>,
+    next_id: u64,
+    discarded: HashSet<u64>,
+}
+
+/// Hands control of the app to a user: while paused, a banner shows in every webview and
+/// commands simulating input wait until the agent or the user resumes the session.
+///
+/// Queued commands still count against the in-flight limit of the socket server and can
+/// be cancelled by request ID.
+pub struct SessionPause {
+    state: Mutex<PauseState>,
+    /// Locked while paused. Queued commands wait for it and pass one after the other, in
+    /// the order they arrived.
+    gate: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl SessionPause {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(PauseState::default()),
+            gate: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    /// Script showing the pause banner in a webview, hidden until a pause.
+    pub(crate) fn init_script() -> String {
+        HANDOFF_HOOK_SCRIPT.replace("__EVENT__", RESUME_REQUESTED_EVENT)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.state.lock().unwrap().paused.is_some()
+    }
+
+    pub fn status(&self) -> PauseStatus {
+        let state = self.state.lock().unwrap();
+        PauseStatus {
+            paused: state.paused.is_some(),
+            message: state.paused.as_ref().map(|paused| paused.message.clone()),
+            paused_ms: state.paused.as_ref().map(|paused| paused.paused_ms),
+            queued: state.queued.clone(),
+        }
+    }
+
+    /// Pause the session and show `message` in every webview. Pausing again only changes
+    /// the message.
+    pub async fn pause<R: Runtime>(&self, app: &AppHandle<R>, message: Option<String>) {
+        let message = message.unwrap_or_else(|| DEFAULT_PAUSE_MESSAGE.to_string());
+        loop {
+            {
+                let mut state = self.state.lock().unwrap();
+                if let Some(paused) = state.paused.as_mut() {
+                    paused.message = message.clone();
+                    break;
+                }
+                if let Ok(guard) = self.gate.clone().try_lock_owned() {
+                    state.paused = Some(Paused {
+                        message: message.clone(),
+                        paused_ms: now_ms(),
+                        _guard: guard,
+                    });
+                    break;
+                }
+            }
+            // Commands released by the last resume are still going through the gate
+            tokio::task::yield_now().await;
+        }
+        self.sync_all(app);
+        info!("[TAURI_MCP] Session paused: {}", message);
+        app.tauri_mcp()
+            .events()
+            .publish("sessionPaused", serde_json::json!({ "message": message }));
+    }
+
+    /// Resume the session and hide the banner. Queued commands run, or fail when
+    /// `discard_queued` is set.
+    pub fn resume<R: Runtime>(&self, app: &AppHandle<R>, discard_queued: bool) -> ResumeOutcome {
+        let outcome = {
+            let mut state = self.state.lock().unwrap();
+            let Some(paused) = state.paused.take() else {
+                return ResumeOutcome {
+                    resumed: false,
+                    released: 0,
+                    discarded: 0,
+                };
+            };
+            let queued = state.queued.len();
+            if discard_queued {
+                let ids: Vec<u64> = state.queued.iter().map(|queued| queued.id).collect();
+                state.discarded.extend(ids);
+            }
+            // Dropping the guard lets the queued commands through
+            drop(paused);
+            ResumeOutcome {
+                resumed: true,
+                released: if discard_queued { 0 } else { queued },
+                discarded: if discard_queued { queued } else { 0 },
+            }
+        };
+        self.sync_all(app);
+        info!(
+            "[TAURI_MCP] Session resumed, {} queued commands released, {} discarded",
+            outcome.released, outcome.discarded
+        );
+        app.tauri_mcp().events().publish(
+            "sessionResumed",
+            serde_json::to_value(&outcome).unwrap_or_default(),
+        );
+        outcome
+    }
+
+    /// Wait for the session to resume if `command` simulates input and the session is
+    /// paused. Fails when the resume discarded the command.
+    pub(crate) async fn wait(&self, command: &str) -> crate::Result<()> {
+        if !QUEUED_COMMANDS.contains(&command) {
+            return Ok(());
+        }
+        let id = {
+            let mut state = self.state.lock().unwrap();
+            if state.paused.is_none() {
+                return Ok(());
+            }
+            state.next_id += 1;
+            let id = state.next_id;
+            state.queued.push(QueuedCommand {
+                id,
+                command: command.to_string(),
+                queued_ms: now_ms(),
+            });
+            id
+        };
+        info!("[TAURI_MCP] Session paused, queuing {}", command);
+        // Forgets the command when it is cancelled while waiting
+        let _queued = Dequeue { pause: self, id };
+
+        drop(self.gate.lock().await);
+        if self.state.lock().unwrap().discarded.remove(&id) {
+            return Err(crate::Error::Anyhow(format!(
+                "{} was discarded when the session resumed",
+                command
+            )));
+        }
+        Ok(())
+    }
+
+    fn sync_all<R: Runtime>(&self, app: &AppHandle<R>) {
+        for window in app.webview_windows().values() {
+            self.sync(window.as_ref());
+        }
+    }
+
+    /// Show or hide the banner in a webview, e.g. after a page load.
+    pub(crate) fn sync<R: Runtime>(&self, webview: &tauri::Webview<R>) {
+        let script = match self.status().message {
+            Some(message) => format!(
+                "window.__TAURI_MCP_HANDOFF__ && window.__TAURI_MCP_HANDOFF__.show({});",
+                Value::from(message)
+            ),
+            None => {
+                "window.__TAURI_MCP_HANDOFF__ && window.__TAURI_MCP_HANDOFF__.hide();".to_string()
+            }
+        };
+        if let Err(e) = webview.eval(&script) {
+            warn!(
+                "[TAURI_MCP] Failed to update the pause banner in {}: {}",
+                webview.label(),
+                e
+            );
+        }
+    }
+}
+
+struct Dequeue<'a> {
+    pause: &'a SessionPause,
+    id: u64,
+}
+
+impl Drop for Dequeue<'_> {
+    fn drop(&mut self) {
+        let mut state = self.pause.state.lock().unwrap();
+        state.queued.retain(|queued| queued.id != self.id);
+        state.discarded.remove(&self.id);
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+use crate::PluginConfig;
+
+/// Environment variable that forces headless mode on (`1`, `true`, `yes`) or off (`0`,
+/// `false`, `no`), whatever the config and the display say.
+pub const HEADLESS_ENV: &str = "TAURI_MCP_HEADLESS";
+
+/// Whether the plugin runs headless, and why.
+///
+/// Headless, nobody watches the screen and the OS cannot be relied on to deliver input or
+/// move windows, so the input tools switch to DOM events and `manage_window` skips its
+/// operations with a warning. The same client scripts then run on a desktop and on CI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HeadlessMode {
+    /// A real display, tools use the OS.
+    Off,
+    /// Headless because the config or the environment asked for it.
+    Configured,
+    /// Headless because no display server was found.
+    NoDisplay,
+    /// Headless because the display is a virtual framebuffer such as Xvfb, with no window
+    /// manager to focus or place windows.
+    VirtualDisplay,
+}
+
+impl HeadlessMode {
+    /// Resolve the mode from the environment, then the plugin config, then the display.
+    pub fn resolve(config: &PluginConfig) -> Self {
+        let configured = env_override().or(config.headless);
+        match configured {
+            Some(true) => HeadlessMode::Configured,
+            Some(false) => HeadlessMode::Off,
+            None => detect(),
+        }
+    }
+
+    /// Whether the tools run in headless mode.
+    pub fn is_headless(self) -> bool {
+        self != HeadlessMode::Off
+    }
+
+    /// Why the plugin runs headless, None when it does not.
+    pub fn reason(self) -> Option<&'static str> {
+        match self {
+            HeadlessMode::Off => None,
+            HeadlessMode::Configured => Some("headless mode is configured"),
+            HeadlessMode::NoDisplay => Some("no display server was found"),
+            HeadlessMode::VirtualDisplay => Some("the display is a virtual framebuffer"),
+        }
+    }
+}
+
+/// The value of the headless environment variable, None when unset or not a boolean.
+pub fn env_override() -> Option<bool> {
+    let value = std::env::var(HEADLESS_ENV).ok()?;
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "0" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+/// Headless mode from the display the app runs on. Only Linux runs apps without a real
+/// display: CI runners of macOS and Windows have a logged-in desktop session.
+#[cfg(target_os = "linux")]
+fn detect() -> HeadlessMode {
+    let display = std::env::var("DISPLAY").ok();
+    let has_wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    match display {
+        None if !has_wayland => HeadlessMode::NoDisplay,
+        Some(display) if !has_wayland && is_virtual_display(&display) => {
+            HeadlessMode::VirtualDisplay
+        }
+        _ => HeadlessMode::Off,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn detect() -> HeadlessMode {
+    HeadlessMode::Off
+}
+
+/// Whether a local X display, e.g. `:99`, is served by an Xvfb process.
+#[cfg(target_os = "linux")]
+fn is_virtual_display(display: &str) -> bool {
+    // Remote displays such as `localhost:10.0` come from SSH forwarding, not a framebuffer
+    let Some(number) = display
+        .strip_prefix(':')
+        .and_then(|rest| rest.split('.').next())
+    else {
+        return false;
+    };
+    let argument = format!(":{}", number);
+
+    let Ok(processes) = std::fs::read_dir("/proc") else {
+        return false;
+    };
+    processes.flatten().any(|process| {
+        let path = process.path();
+        let is_xvfb = std::fs::read_to_string(path.join("comm"))
+            .is_ok_and(|name| matches!(name.trim(), "Xvfb" | "Xvnc"));
+        // The display number is one of the NUL-separated arguments of the server
+        is_xvfb
+            && std::fs::read(path.join("cmdline")).is_ok_and(|cmdline| {
+                cmdline
+                    .split(|byte| *byte == 0)
+                    .any(|arg| arg == argument.as_bytes())
+            })
+    })
+}
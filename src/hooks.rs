@@ -0,0 +1,88 @@
+use serde_json::Value;
+use std::sync::{Arc, RwLock};
+
+use crate::clients::ClientInfo;
+
+/// Callback told about a client of the socket server.
+pub type ClientHook = Arc<dyn Fn(&ClientInfo) + Send + Sync>;
+
+/// Callback deciding whether a tool call may run.
+pub type ToolHook = Arc<dyn Fn(&ToolInvocation) -> ToolDecision + Send + Sync>;
+
+/// A tool call about to run, as shown to the `on_tool_invoked` hooks.
+#[derive(Debug)]
+pub struct ToolInvocation<'a> {
+    pub command: &'a str,
+    pub payload: &'a Value,
+    /// ID the client gave the request, if any.
+    pub request_id: Option<&'a str>,
+}
+
+/// Answer of an `on_tool_invoked` hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ToolDecision {
+    /// Let the tool run.
+    Allow,
+    /// Refuse the call, telling the client why.
+    Deny(String),
+}
+
+/// Callbacks of the host app on the socket server lifecycle.
+///
+/// They run on the threads of the socket server, while the client waits, so they should
+/// return quickly and hand anything slow, such as UI updates, to the event loop.
+#[derive(Default)]
+pub struct LifecycleHooks {
+    client_connected: RwLock<Vec<ClientHook>>,
+    client_disconnected: RwLock<Vec<ClientHook>>,
+    tool_invoked: RwLock<Vec<ToolHook>>,
+}
+
+impl LifecycleHooks {
+    pub fn new(
+        client_connected: Vec<ClientHook>,
+        client_disconnected: Vec<ClientHook>,
+        tool_invoked: Vec<ToolHook>,
+    ) -> Self {
+        Self {
+            client_connected: RwLock::new(client_connected),
+            client_disconnected: RwLock::new(client_disconnected),
+            tool_invoked: RwLock::new(tool_invoked),
+        }
+    }
+
+    pub fn add_client_connected(&self, hook: ClientHook) {
+        self.client_connected.write().unwrap().push(hook);
+    }
+
+    pub fn add_client_disconnected(&self, hook: ClientHook) {
+        self.client_disconnected.write().unwrap().push(hook);
+    }
+
+    pub fn add_tool_invoked(&self, hook: ToolHook) {
+        self.tool_invoked.write().unwrap().push(hook);
+    }
+
+    pub fn client_connected(&self, client: &ClientInfo) {
+        for hook in self.client_connected.read().unwrap().iter() {
+            hook(client);
+        }
+    }
+
+    pub fn client_disconnected(&self, client: &ClientInfo) {
+        for hook in self.client_disconnected.read().unwrap().iter() {
+            hook(client);
+        }
+    }
+
+    /// Ask the hooks about a tool call. The first one denying it wins.
+    pub fn tool_invoked(&self, invocation: &ToolInvocation) -> ToolDecision {
+        self.tool_invoked
+            .read()
+            .unwrap()
+            .iter()
+            .map(|hook| hook(invocation))
+            .find(|decision| *decision != ToolDecision::Allow)
+            .unwrap_or(ToolDecision::Allow)
+    }
+}
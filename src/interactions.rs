@@ -0,0 +1,380 @@
+use log::{info, warn};
+use serde::Deserialize;
+use serde_json::Value;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::{AppHandle, Manager, Runtime, Url};
+
+use crate::models::ScriptStep;
+use crate::shared::commands;
+use crate::tools::run_script::WAIT_STEP;
+use crate::tools::selector::FIND_ELEMENT_JS;
+
+/// Event emitted by the webview hook for each recorded interaction.
+pub(crate) const INTERACTION_EVENT: &str = "tauri-mcp:interaction";
+
+/// Injected in every webview, inactive until a recording starts. It turns trusted user
+/// events into `run_script` steps: clicks into `click_element`, value changes into
+/// `set_element_value`, Enter, Escape and shortcuts into `execute_js` key events, and
+/// in-page navigations into `wait` steps on the URL.
+///
+/// Each element gets the first selector that finds it again: id, test attributes, name,
+/// text, then a CSS path.
+const INTERACTION_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_INTERACTIONS__) return;
+    const state = { active: false };
+    window.__TAURI_MCP_INTERACTIONS__ = state;
+    __FIND_ELEMENT__
+    const record = (command, payload) => {
+        const internals = window.__TAURI_INTERNALS__;
+        if (!state.active || !internals) return;
+        internals.invoke('plugin:event|emit', {
+            event: '__EVENT__',
+            payload: { windowLabel: internals.metadata.currentWindow.label, command, payload },
+        }).catch(() => {});
+    };
+
+    const cssEscape = (value) => window.CSS && CSS.escape ? CSS.escape(value) : value.replace(/["\\]/g, '\\$&');
+    const cssPath = (element) => {
+        const parts = [];
+        for (let node = element; node && node.nodeType === 1 && node !== document.documentElement; node = node.parentElement) {
+            if (node.id) {
+                parts.unshift(`#${cssEscape(node.id)}`);
+                break;
+            }
+            const tag = node.tagName.toLowerCase();
+            const siblings = node.parentElement
+                ? Array.from(node.parentElement.children).filter((child) => child.tagName === node.tagName)
+                : [];
+            parts.unshift(siblings.length > 1 ? `${tag}:nth-of-type(${siblings.indexOf(node) + 1})` : tag);
+        }
+        return parts.join(' > ');
+    };
+    const findsElement = (element, selectorType, selectorValue) => {
+        try {
+            return findElement(selectorType, selectorValue) === element;
+        } catch (_) {
+            return false;
+        }
+    };
+    const selectorFor = (element) => {
+        const tag = element.tagName.toLowerCase();
+        const candidates = [];
+        if (element.id) candidates.push(['id', element.id]);
+        for (const attribute of ['data-testid', 'data-test', 'data-cy']) {
+            const value = element.getAttribute(attribute);
+            if (value) candidates.push(['css', `[${attribute}="${cssEscape(value)}"]`]);
+        }
+        const name = element.getAttribute('name');
+        if (name) {
+            candidates.push(['css', `${tag}[name="${cssEscape(name)}"]`]);
+            if (element.type === 'radio') {
+                candidates.push(['css', `${tag}[name="${cssEscape(name)}"][value="${cssEscape(element.value)}"]`]);
+            }
+        }
+        const label = element.getAttribute('aria-label');
+        if (label) candidates.push(['text', label]);
+        const text = (element.textContent || '').trim();
+        if (text && text.length <= 40 && !/^(input|select|textarea)$/.test(tag)) candidates.push(['text', text]);
+        candidates.push(['css', cssPath(element)]);
+        const [selector_type, selector_value] = candidates.find(([type, value]) => findsElement(element, type, value))
+            || candidates[candidates.length - 1];
+        return { selector_type, selector_value };
+    };
+
+    const isField = (element) => element.tagName === 'TEXTAREA' || element.tagName === 'SELECT'
+        || (element.tagName === 'INPUT' && !/^(button|submit|reset|image)$/.test(element.type));
+    const isTextField = (element) => isField(element) && element.tagName !== 'SELECT'
+        && !/^(checkbox|radio|file)$/.test(element.type);
+    const CLICKABLE = 'a, button, summary, label, [role="button"], [role="link"], [role="menuitem"], [role="tab"], '
+        + '[role="checkbox"], [role="option"], input[type="button"], input[type="submit"], input[type="reset"], input[type="image"]';
+    const MODIFIERS = ['shift', 'ctrl', 'alt', 'meta'];
+
+    const recordClick = (event, clickCount) => {
+        if (!state.active || !event.isTrusted || !(event.target instanceof Element)) return;
+        // The pause banner belongs to the plugin, not the app
+        if (event.target.closest('[data-tauri-mcp-handoff]')) return;
+        const target = event.target.closest(CLICKABLE) || event.target;
+        // Fields are recorded by their value, labels of fields by the field's change
+        if (isField(target) || (target.tagName === 'LABEL' && target.control && isField(target.control))) return;
+        const payload = selectorFor(target);
+        const modifiers = MODIFIERS.filter((modifier) => event[`${modifier}Key`]);
+        if (modifiers.length) payload.modifiers = modifiers;
+        if (clickCount > 1) payload.click_count = clickCount;
+        record('click_element', payload);
+    };
+    document.addEventListener('click', (event) => {
+        if (event.detail <= 1) recordClick(event, 1);
+    }, true);
+    document.addEventListener('dblclick', (event) => recordClick(event, 2), true);
+
+    document.addEventListener('change', (event) => {
+        const target = event.target;
+        if (!state.active || !event.isTrusted || !(target instanceof Element) || !isField(target)) return;
+        if (target.type === 'file') return;
+        const payload = selectorFor(target);
+        if (target.tagName === 'SELECT') {
+            payload.value = target.multiple
+                ? Array.from(target.selectedOptions).map((option) => option.value)
+                : target.value;
+        } else if (target.type === 'checkbox' || target.type === 'radio') {
+            payload.value = target.checked;
+        } else {
+            payload.value = target.value;
+        }
+        record('set_element_value', payload);
+    }, true);
+
+    document.addEventListener('keydown', (event) => {
+        if (!state.active || !event.isTrusted || event.repeat) return;
+        const shortcut = (event.ctrlKey || event.metaKey) && event.key.length === 1;
+        if (event.key !== 'Enter' && event.key !== 'Escape' && !shortcut) return;
+        const target = event.target instanceof Element ? event.target : null;
+        // The typed value has to be in place before the key, change only fires after it
+        if (target && isTextField(target)) {
+            record('set_element_value', Object.assign(selectorFor(target), { value: target.value }));
+        }
+        const init = {
+            key: event.key, code: event.code,
+            ctrlKey: event.ctrlKey, shiftKey: event.shiftKey, altKey: event.altKey, metaKey: event.metaKey,
+        };
+        record('execute_js', {
+            code: `(() => {
+    const element = document.activeElement || document.body;
+    const init = Object.assign({ bubbles: true, cancelable: true }, ${JSON.stringify(init)});
+    const proceed = element.dispatchEvent(new KeyboardEvent('keydown', init));
+    if (proceed && init.key === 'Enter' && element.tagName === 'INPUT' && element.form) element.form.requestSubmit();
+    element.dispatchEvent(new KeyboardEvent('keyup', init));
+    return init.key;
+})()`,
+        });
+    }, true);
+
+    const recordNavigation = () => record('wait', { pattern: location.pathname + location.search + location.hash });
+    for (const method of ['pushState', 'replaceState']) {
+        const original = history[method];
+        history[method] = function () {
+            const result = original.apply(this, arguments);
+            recordNavigation();
+            return result;
+        };
+    }
+    window.addEventListener('popstate', recordNavigation);
+    window.addEventListener('hashchange', recordNavigation);
+})();
+"#;
+
+/// An interaction reported by the webview hook.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct RecordedInteraction {
+    pub window_label: String,
+    pub command: String,
+    pub payload: Value,
+}
+
+struct Session {
+    /// Only this window is recorded, all of them when None.
+    window_label: Option<String>,
+    started: Instant,
+    steps: Vec<ScriptStep>,
+}
+
+impl Session {
+    fn records(&self, window_label: &str) -> bool {
+        self.window_label
+            .as_deref()
+            .is_none_or(|label| label == window_label)
+    }
+
+    /// Append a step, merging it with the previous one when it only refines it.
+    fn push(&mut self, step: ScriptStep) {
+        // The value typed before a key is reported again by the change that follows it
+        if step.command == commands::SET_ELEMENT_VALUE
+            && self
+                .steps
+                .iter()
+                .rev()
+                .take(2)
+                .any(|recent| recent.command == step.command && recent.payload == step.payload)
+        {
+            return;
+        }
+        if let Some(last) = self.steps.last_mut()
+            && last.command == step.command
+            && same_target(&last.payload, &step.payload)
+        {
+            match step.command.as_str() {
+                // Several changes of the same field keep the final value
+                commands::SET_ELEMENT_VALUE => {
+                    *last = step;
+                    return;
+                }
+                // A double click follows the single click the browser reports first
+                commands::CLICK_ELEMENT if step.payload.get("click_count").is_some() => {
+                    *last = step;
+                    return;
+                }
+                WAIT_STEP if last.payload == step.payload => return,
+                _ => {}
+            }
+        }
+        self.steps.push(step);
+    }
+}
+
+fn same_target(a: &Value, b: &Value) -> bool {
+    ["window_label", "selector_type", "selector_value"]
+        .iter()
+        .all(|key| a.get(key) == b.get(key))
+}
+
+/// What a recording produced.
+pub struct RecordedScript {
+    pub steps: Vec<ScriptStep>,
+    pub duration_ms: u64,
+}
+
+/// Records what a user does in the webviews as a script `run_script` can replay.
+pub struct InteractionRecorder {
+    session: Mutex<Option<Session>>,
+}
+
+impl InteractionRecorder {
+    pub(crate) fn new() -> Self {
+        Self {
+            session: Mutex::new(None),
+        }
+    }
+
+    /// Script hooking user events in a webview, inactive until a recording starts.
+    pub(crate) fn init_script() -> String {
+        INTERACTION_HOOK_SCRIPT
+            .replace("__FIND_ELEMENT__", FIND_ELEMENT_JS)
+            .replace("__EVENT__", INTERACTION_EVENT)
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.session.lock().unwrap().is_some()
+    }
+
+    /// Start recording the given window, or every window. Fails if a recording is running.
+    pub fn start<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: Option<String>,
+    ) -> crate::Result<()> {
+        {
+            let mut session = self.session.lock().unwrap();
+            if session.is_some() {
+                return Err(crate::Error::Anyhow(
+                    "An interaction recording is already running".to_string(),
+                ));
+            }
+            *session = Some(Session {
+                window_label,
+                started: Instant::now(),
+                steps: Vec::new(),
+            });
+        }
+        self.sync_all(app);
+        info!("[TAURI_MCP] Interaction recording started");
+        Ok(())
+    }
+
+    /// Stop recording and return the recorded steps.
+    pub fn stop<R: Runtime>(&self, app: &AppHandle<R>) -> crate::Result<RecordedScript> {
+        let session = self.session.lock().unwrap().take().ok_or_else(|| {
+            crate::Error::Anyhow("No interaction recording is running".to_string())
+        })?;
+        self.sync_all(app);
+        info!(
+            "[TAURI_MCP] Interaction recording stopped with {} steps",
+            session.steps.len()
+        );
+        Ok(RecordedScript {
+            steps: session.steps,
+            duration_ms: session.started.elapsed().as_millis() as u64,
+        })
+    }
+
+    fn sync_all<R: Runtime>(&self, app: &AppHandle<R>) {
+        for window in app.webview_windows().values() {
+            self.sync(window.as_ref());
+        }
+    }
+
+    /// Turn the hook of a webview on or off for the current recording, e.g. after a page load.
+    pub(crate) fn sync<R: Runtime>(&self, webview: &tauri::Webview<R>) {
+        let active = self
+            .session
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|session| session.records(webview.label()));
+        let script = format!(
+            "if (window.__TAURI_MCP_INTERACTIONS__) window.__TAURI_MCP_INTERACTIONS__.active = {};",
+            active
+        );
+        if let Err(e) = webview.eval(&script) {
+            warn!(
+                "[TAURI_MCP] Failed to update interaction recording in {}: {}",
+                webview.label(),
+                e
+            );
+        }
+    }
+
+    /// Add an interaction reported by a webview hook.
+    pub(crate) fn recorded(&self, interaction: RecordedInteraction) {
+        let mut payload = interaction.payload;
+        if let Some(payload) = payload.as_object_mut() {
+            payload.insert(
+                "window_label".to_string(),
+                Value::from(interaction.window_label.as_str()),
+            );
+        }
+        self.push(
+            &interaction.window_label,
+            ScriptStep {
+                command: interaction.command,
+                payload,
+                name: None,
+                on_error: None,
+                retries: 0,
+            },
+        );
+    }
+
+    /// Record a page load of a window as a wait for its URL.
+    pub(crate) fn navigated(&self, window_label: &str, url: &Url) {
+        let mut pattern = url.path().to_string();
+        if let Some(query) = url.query() {
+            pattern.push('?');
+            pattern.push_str(query);
+        }
+        if let Some(fragment) = url.fragment() {
+            pattern.push('#');
+            pattern.push_str(fragment);
+        }
+        self.push(
+            window_label,
+            ScriptStep {
+                command: WAIT_STEP.to_string(),
+                payload: serde_json::json!({ "window_label": window_label, "pattern": pattern }),
+                name: None,
+                on_error: None,
+                retries: 0,
+            },
+        );
+    }
+
+    fn push(&self, window_label: &str, step: ScriptStep) {
+        if let Some(session) = self.session.lock().unwrap().as_mut()
+            && session.records(window_label)
+        {
+            session.push(step);
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use crate::audit::now_ms;
+
+/// Event emitted by the webview hook for each uncaught error.
+pub(crate) const JS_ERROR_EVENT: &str = "tauri-mcp:js-error";
+
+/// Number of errors kept; the oldest are dropped first.
+const CAPACITY: usize = 500;
+
+/// Injected in every webview. Reports uncaught errors and unhandled promise rejections,
+/// without preventing the default console output.
+const JS_ERROR_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_ERRORS__) return;
+    window.__TAURI_MCP_ERRORS__ = true;
+    const report = (error) => {
+        const internals = window.__TAURI_INTERNALS__;
+        if (!internals) return;
+        error.windowLabel = internals.metadata.currentWindow.label;
+        error.url = location.href;
+        internals.invoke('plugin:event|emit', { event: '__EVENT__', payload: error }).catch(() => {});
+    };
+    const describe = (value) => {
+        if (value instanceof Error) {
+            return { message: `${value.name}: ${value.message}`, stack: value.stack || null };
+        }
+        let message;
+        try {
+            message = typeof value === 'string' ? value : JSON.stringify(value);
+        } catch (_) {}
+        return { message: message === undefined ? String(value) : message, stack: null };
+    };
+
+    // Failed resource loads also dispatch error events, but not as ErrorEvent
+    window.addEventListener('error', (event) => {
+        if (!(event instanceof ErrorEvent)) return;
+        report({
+            kind: 'error',
+            message: event.message || describe(event.error).message,
+            source: event.filename || null,
+            line: event.lineno || null,
+            column: event.colno || null,
+            stack: event.error instanceof Error ? event.error.stack || null : null,
+        });
+    });
+    window.addEventListener('unhandledrejection', (event) => {
+        report(Object.assign({ kind: 'unhandledRejection', source: null, line: null, column: null }, describe(event.reason)));
+    });
+})();
+"#;
+
+/// How an error reached the top level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum JsErrorKind {
+    /// An exception nobody caught (`window.onerror`).
+    Error,
+    /// A rejected promise without a handler.
+    UnhandledRejection,
+}
+
+/// An uncaught error reported by a webview.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsError {
+    /// Sequence number, used as the `since` cursor.
+    #[serde(default)]
+    pub id: u64,
+    #[serde(default)]
+    pub timestamp_ms: u64,
+    pub window_label: String,
+    pub kind: JsErrorKind,
+    pub message: String,
+    /// Script the error was thrown from, when the browser reports it.
+    pub source: Option<String>,
+    pub line: Option<u32>,
+    pub column: Option<u32>,
+    pub stack: Option<String>,
+    /// Page the error happened on.
+    pub url: String,
+}
+
+struct Inner {
+    errors: VecDeque<JsError>,
+    next_id: u64,
+}
+
+/// Keeps the latest uncaught errors and unhandled rejections of the webviews.
+pub struct JsErrorLog {
+    inner: Mutex<Inner>,
+}
+
+impl JsErrorLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                errors: VecDeque::new(),
+                next_id: 1,
+            }),
+        }
+    }
+
+    /// Script reporting the errors of a webview.
+    pub(crate) fn init_script() -> String {
+        JS_ERROR_HOOK_SCRIPT.replace("__EVENT__", JS_ERROR_EVENT)
+    }
+
+    /// Store an error reported by a webview hook and return it with its ID.
+    pub(crate) fn record(&self, mut error: JsError) -> JsError {
+        let mut inner = self.inner.lock().unwrap();
+        error.id = inner.next_id;
+        error.timestamp_ms = now_ms();
+        inner.next_id += 1;
+        while inner.errors.len() >= CAPACITY {
+            inner.errors.pop_front();
+        }
+        inner.errors.push_back(error.clone());
+        error
+    }
+
+    /// Errors after the `since` ID, oldest first, optionally of one window only.
+    pub fn errors(&self, since: u64, window_label: Option<&str>) -> Vec<JsError> {
+        self.inner
+            .lock()
+            .unwrap()
+            .errors
+            .iter()
+            .filter(|error| error.id > since)
+            .filter(|error| window_label.is_none_or(|label| label == error.window_label))
+            .cloned()
+            .collect()
+    }
+
+    /// ID of the most recent error (0 if none was reported yet).
+    pub fn latest_id(&self) -> u64 {
+        self.inner.lock().unwrap().next_id - 1
+    }
+
+    /// True when errors after `since` were dropped to make room for newer ones.
+    pub fn missed_since(&self, since: u64) -> bool {
+        let inner = self.inner.lock().unwrap();
+        let oldest = inner.errors.front().map_or(inner.next_id, |e| e.id);
+        since + 1 < oldest && since < inner.next_id - 1
+    }
+
+    /// Forget the stored errors. IDs keep increasing, so cursors stay valid.
+    pub fn clear(&self) {
+        self.inner.lock().unwrap().errors.clear();
+    }
+}
@@ -0,0 +1,143 @@
+use log::info;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::audit::now_ms;
+
+/// Interval of the keepalive messages sent to clients that ask for them.
+pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often a connection's watchdog checks it.
+const TICK: Duration = Duration::from_millis(500);
+
+/// Keepalive messages and idle timeout of the socket connections.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeepaliveSettings {
+    /// How often clients that asked for it in their handshake get a `{"keepalive": ms}`
+    /// message, so both sides notice a dead connection. None disables them.
+    pub interval: Option<Duration>,
+    /// How long a connection may go without requests before it is closed. None keeps idle
+    /// connections open.
+    pub idle_timeout: Option<Duration>,
+}
+
+impl Default for KeepaliveSettings {
+    fn default() -> Self {
+        Self {
+            interval: Some(DEFAULT_KEEPALIVE_INTERVAL),
+            idle_timeout: None,
+        }
+    }
+}
+
+/// Activity of a connection, watched by its watchdog thread.
+pub(crate) struct ConnectionWatch {
+    settings: KeepaliveSettings,
+    last_activity: Mutex<Instant>,
+    in_flight: AtomicUsize,
+    keepalive: AtomicBool,
+    closed: AtomicBool,
+}
+
+impl ConnectionWatch {
+    pub fn new(settings: KeepaliveSettings) -> Arc<Self> {
+        Arc::new(Self {
+            settings,
+            last_activity: Mutex::new(Instant::now()),
+            in_flight: AtomicUsize::new(0),
+            keepalive: AtomicBool::new(false),
+            closed: AtomicBool::new(false),
+        })
+    }
+
+    pub fn settings(&self) -> KeepaliveSettings {
+        self.settings
+    }
+
+    /// Note a request, which keeps the connection from being idle until it is answered.
+    pub fn request_started(&self) {
+        self.in_flight.fetch_add(1, Ordering::SeqCst);
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    pub fn request_finished(&self) {
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        *self.last_activity.lock().unwrap() = Instant::now();
+    }
+
+    /// Send keepalive messages from now on, if they are enabled. Returns their interval.
+    pub fn enable_keepalive(&self) -> Option<Duration> {
+        let interval = self.settings.interval?;
+        self.keepalive.store(true, Ordering::SeqCst);
+        Some(interval)
+    }
+
+    /// Stop the watchdog when the returned guard is dropped, as the connection is gone.
+    pub fn close_on_drop(self: &Arc<Self>) -> WatchGuard {
+        WatchGuard(self.clone())
+    }
+
+    /// How long the connection has been idle, None while a request is in flight.
+    fn idle_for(&self) -> Option<Duration> {
+        (self.in_flight.load(Ordering::SeqCst) == 0)
+            .then(|| self.last_activity.lock().unwrap().elapsed())
+    }
+
+    /// Watch the connection until it closes: `send` writes a message to the client and
+    /// `close` closes the connection when it is idle for too long or a keepalive fails.
+    pub fn spawn_watchdog<S, C>(self: &Arc<Self>, send: S, close: C)
+    where
+        S: Fn(&Value) -> std::io::Result<()> + Send + 'static,
+        C: FnOnce() + Send + 'static,
+    {
+        if self.settings.interval.is_none() && self.settings.idle_timeout.is_none() {
+            return;
+        }
+        let watch = self.clone();
+        thread::spawn(move || {
+            let mut last_keepalive = Instant::now();
+            while !watch.closed.load(Ordering::SeqCst) {
+                thread::sleep(TICK);
+                if let (Some(timeout), Some(idle)) = (watch.settings.idle_timeout, watch.idle_for())
+                    && idle >= timeout
+                {
+                    info!(
+                        "[TAURI_MCP] Closing connection idle for {} ms",
+                        idle.as_millis()
+                    );
+                    if watch.keepalive.load(Ordering::SeqCst) {
+                        let _ = send(&json!({
+                            "event": "idleTimeout",
+                            "idleTimeoutMs": timeout.as_millis() as u64,
+                        }));
+                    }
+                    close();
+                    return;
+                }
+                if let Some(interval) = watch.settings.interval
+                    && watch.keepalive.load(Ordering::SeqCst)
+                    && last_keepalive.elapsed() >= interval
+                {
+                    last_keepalive = Instant::now();
+                    if let Err(e) = send(&json!({ "keepalive": now_ms() })) {
+                        info!("[TAURI_MCP] Keepalive failed, closing connection: {}", e);
+                        close();
+                        return;
+                    }
+                }
+            }
+        });
+    }
+}
+
+/// Stops the watchdog of a connection when dropped, see [`ConnectionWatch::close_on_drop`].
+pub(crate) struct WatchGuard(Arc<ConnectionWatch>);
+
+impl Drop for WatchGuard {
+    fn drop(&mut self) {
+        self.0.closed.store(true, Ordering::SeqCst);
+    }
+}
@@ -1,30 +1,132 @@
-use log::info;
+use log::{info, warn};
 use tauri::{
-    Manager, Runtime,
+    AppHandle, Manager, RunEvent, Runtime, WindowEvent,
     plugin::{Builder, TauriPlugin},
+    webview::PageLoadEvent,
 };
 
 pub use models::*;
 
-#[cfg(desktop)]
+// The plugin state, shared by mobile builds, which set it up for their transport
 mod desktop;
 #[cfg(mobile)]
 mod mobile;
 
+mod app_logs;
+mod artifacts;
+mod audit;
+mod cancellation;
+mod canvas;
+mod capabilities;
+mod client_config;
+mod clients;
 mod commands;
+mod config_file;
+mod consent;
+mod coordinates;
+mod database;
+mod devices;
+mod dialogs;
+mod discovery;
+mod downloads;
+mod emulation;
+mod env_overrides;
 mod error;
+mod events;
+mod files;
+mod handoff;
+mod headless;
+mod hooks;
+mod interactions;
+mod js_errors;
+mod keepalive;
+#[cfg(feature = "mdns")]
+mod mdns;
+mod menu;
 mod models;
+mod navigation;
+mod overlay;
+mod permissions;
+pub mod platform;
+mod process_stats;
+mod protocol;
+mod rate_limit;
+mod recording;
+mod server_mode;
+mod session_status;
 pub mod shared;
+mod shutdown;
 mod socket_server;
+mod state_inspector;
+mod status;
+mod timeouts;
+mod tool_docs;
 mod tools;
+mod trace;
+mod tray;
+mod windows;
 
+pub use app_logs::{AppLogQuery, AppLogRecord, AppLogs, LogBridge};
+#[cfg(feature = "s3")]
+pub use artifacts::S3ArtifactStore;
+pub use artifacts::{ArtifactId, ArtifactInfo, ArtifactStore, LocalDirStore};
+pub use audit::{AuditEntry, AuditLog};
+pub use capabilities::{Capability, CapabilityReport, CapabilityState, ToolDescription};
+pub use client_config::{ClientConfig, DEFAULT_SERVER_NAME, McpClient, McpServerLauncher};
+pub use clients::{ClientInfo, ClientRegistry};
+pub use config_file::{DatabaseConfig, PluginFileConfig, TcpConfig, TimeoutsConfig};
+pub use consent::{ConsentState, ConsentStatus, InputConsent};
+pub use coordinates::{CoordinateSpace, WindowGeometry};
+pub use database::{DatabaseConnection, Databases, QueryResult};
+pub use devices::{DEVICE_PROFILES, DeviceProfile, find_device};
+pub use dialogs::{AnsweredDialog, DialogInterceptor, DialogPolicy, DialogRule, PendingDialog};
+pub use discovery::{InstanceInfo, default_socket_path, discover_instances, discovery_dir};
+pub use downloads::{Download, DownloadLog, DownloadStatus};
+pub use emulation::{
+    ClockSettings, ColorScheme, DeviceSettings, Emulation, EmulationSettings, GeolocationSettings,
+    LocaleSettings, MockRoute, NetworkConditions,
+};
+pub use env_overrides::{DISABLE_ENV, PORT_ENV, SOCKET_PATH_ENV, TOKEN_ENV};
 pub use error::{Error, Result};
+pub use events::{EventBus, McpEvent, PollResult};
+pub use files::FileRoots;
+pub use handoff::{DEFAULT_PAUSE_MESSAGE, PauseStatus, QueuedCommand, ResumeOutcome, SessionPause};
+pub use headless::{HEADLESS_ENV, HeadlessMode};
+pub use hooks::{ToolDecision, ToolInvocation};
+pub use interactions::{InteractionRecorder, RecordedScript};
+pub use js_errors::{JsError, JsErrorKind, JsErrorLog};
+pub use keepalive::{DEFAULT_KEEPALIVE_INTERVAL, KeepaliveSettings};
+#[cfg(feature = "mdns")]
+pub use mdns::SERVICE_TYPE as MDNS_SERVICE_TYPE;
+pub use menu::{MenuEntry, MenuEntryKind};
+pub use navigation::{Navigation, NavigationKind, NavigationLog};
+pub use overlay::ActionOverlay;
+pub use permissions::{
+    PermissionDecision, PermissionGate, PermissionOutcome, PermissionPolicy, RequestedPermission,
+};
+pub use process_stats::{
+    MINIMUM_CPU_UPDATE_INTERVAL, ProcessMonitor, ProcessStats, ProcessStatsReport, ProcessTotals,
+};
+pub use protocol::{
+    Compression, CompressionSettings, Encoding, Framing, HandshakeRequest, HandshakeResponse,
+    MIN_PROTOCOL_VERSION, PROTOCOL_VERSION, ServerProtocol, WireFormat,
+};
+pub use rate_limit::{RateLimitRejection, RateLimits};
+pub use recording::{RecordingEntry, RecoveredSession, SessionRecorder};
+pub use server_mode::{ALLOW_IN_RELEASE_ENV, ServerMode};
+pub use session_status::{SESSION_STATUS_EVENT, SessionState, SessionStatus};
 pub use shared::{McpInterface, WindowManagerParams, WindowManagerResult};
+pub use shutdown::DEFAULT_SHUTDOWN_TIMEOUT;
+pub use state_inspector::StateInspector;
+pub use status::{ServerError, ServerStats, ServerStatus};
+pub use timeouts::Timeouts;
+pub use trace::{TraceManifest, TracePage, TraceStep, TraceStepSummary, Tracer};
+pub use tray::TrayInfo;
+#[cfg(all(desktop, feature = "tray"))]
+pub use tray::TrayRegistry;
+pub use windows::{MAIN_WINDOW_LABEL, default_window, resolve_window, window_labels};
 
-#[cfg(desktop)]
 use desktop::TauriMcp;
-#[cfg(mobile)]
-use mobile::TauriMcp;
 
 /// Extensions to [`tauri::App`], [`tauri::AppHandle`] and [`tauri::Window`] to access the tauri-mcp APIs.
 pub trait TauriMcpExt<R: Runtime> {
@@ -61,7 +163,6 @@ impl Default for SocketType {
 }
 
 /// Plugin configuration options.
-#[derive(Default)]
 pub struct PluginConfig {
     /// Application name (used for default socket naming)
     pub application_name: String,
@@ -69,6 +170,68 @@ pub struct PluginConfig {
     pub socket_type: SocketType,
     /// Whether to start the socket server automatically. Default is true.
     pub start_socket_server: bool,
+    /// Whether the socket server may start in release builds. Default is false.
+    pub allow_in_release: bool,
+    /// Token clients must send in their handshake before any command. Default is none.
+    pub auth_token: Option<String>,
+    /// Storage backend for session artifacts. If None, a local directory in the app data dir is used.
+    pub artifact_store: Option<std::sync::Arc<dyn ArtifactStore>>,
+    /// Number of audit log entries kept in memory. Default is 1000, 0 disables the ring buffer.
+    pub audit_capacity: usize,
+    /// Whether audit log entries are also persisted as a JSONL artifact. Default is false.
+    pub persist_audit_log: bool,
+    /// Number of events buffered for long-polling clients. Default is 1000.
+    pub event_buffer_capacity: usize,
+    /// Per-connection rate limit and in-flight request cap.
+    pub rate_limits: RateLimits,
+    /// Keepalive messages and idle timeout of the connections. Default is a keepalive every
+    /// 15 seconds for clients asking for it, and no idle timeout.
+    pub keepalive: KeepaliveSettings,
+    /// Whether executed commands are journaled to disk so they survive a crash. Default is true.
+    pub record_sessions: bool,
+    /// Directory of the session journals. If None, a directory in the app data dir is used.
+    pub session_dir: Option<std::path::PathBuf>,
+    /// Whether the TCP transport is advertised over mDNS (requires the `mdns` feature). Default is false.
+    pub advertise_mdns: bool,
+    /// How long tools wait for the webview. Requests can override it with `timeout_ms`.
+    pub timeouts: Timeouts,
+    /// How long in-flight commands may finish when the app exits before they are
+    /// cancelled. Default is 5 seconds.
+    pub shutdown_timeout: std::time::Duration,
+    /// Whether dialog plugin dialogs opened from the webview are answered by clients. Default is false.
+    pub intercept_dialogs: bool,
+    /// Whether WebGL canvases keep their drawing buffer for `capture_canvas`. Default is false.
+    pub preserve_drawing_buffers: bool,
+    /// Whether `simulate_*` commands need the user's consent first. Default is false.
+    pub require_input_consent: bool,
+    /// Whether a ghost cursor and outlines show the actions of tools in the webview. Default is false.
+    pub show_actions: bool,
+    /// Store files `manage_store` may access (requires the `store` feature). Default is none.
+    pub allowed_stores: Vec<std::path::PathBuf>,
+    /// SQLite databases `query_db` may access (requires the `sql` feature). Default is none.
+    pub databases: Vec<DatabaseConnection>,
+    /// Directories `read_app_file` and `write_app_file` may access, by name. Default is none.
+    pub file_roots: Vec<(String, std::path::PathBuf)>,
+    /// Directory downloads of the webviews are redirected to. If None, they go where the
+    /// webview puts them.
+    pub download_dir: Option<std::path::PathBuf>,
+    /// Whether tools run headless, with DOM input and no window management. If None, it is
+    /// detected from the display.
+    pub headless: Option<bool>,
+    /// Called when a client connects to the socket server. Default is none.
+    pub on_client_connected: Vec<hooks::ClientHook>,
+    /// Called when a client disconnects from the socket server. Default is none.
+    pub on_client_disconnected: Vec<hooks::ClientHook>,
+    /// Called before each tool call, which it may refuse. Default is none.
+    pub on_tool_invoked: Vec<hooks::ToolHook>,
+}
+
+/// The defaults of [`PluginConfig::new`], without an application name. The plugin setup names
+/// the app after its product name then.
+impl Default for PluginConfig {
+    fn default() -> Self {
+        Self::new(String::new())
+    }
 }
 
 impl PluginConfig {
@@ -78,6 +241,31 @@ impl PluginConfig {
             application_name,
             socket_type: SocketType::default(),
             start_socket_server: true,
+            allow_in_release: false,
+            auth_token: None,
+            artifact_store: None,
+            audit_capacity: 1000,
+            persist_audit_log: false,
+            event_buffer_capacity: 1000,
+            rate_limits: RateLimits::default(),
+            keepalive: KeepaliveSettings::default(),
+            record_sessions: true,
+            session_dir: None,
+            advertise_mdns: false,
+            timeouts: Timeouts::default(),
+            shutdown_timeout: shutdown::DEFAULT_SHUTDOWN_TIMEOUT,
+            intercept_dialogs: false,
+            preserve_drawing_buffers: false,
+            require_input_consent: false,
+            show_actions: false,
+            allowed_stores: Vec::new(),
+            databases: Vec::new(),
+            file_roots: Vec::new(),
+            download_dir: None,
+            headless: None,
+            on_client_connected: Vec::new(),
+            on_client_disconnected: Vec::new(),
+            on_tool_invoked: Vec::new(),
         }
     }
 
@@ -98,17 +286,414 @@ impl PluginConfig {
         self.start_socket_server = start;
         self
     }
+
+    /// Allow the socket server to start in release builds.
+    ///
+    /// Without this (or the `TAURI_MCP_ALLOW_IN_RELEASE` environment variable),
+    /// release builds never expose the socket server.
+    pub fn allow_in_release(mut self, allow: bool) -> Self {
+        self.allow_in_release = allow;
+        self
+    }
+
+    /// Require clients to send `token` in their handshake before any other command.
+    ///
+    /// Useful with TCP, which any local user, or the network with `0.0.0.0`, can reach.
+    pub fn auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Set the storage backend used for recordings, baselines and audit logs.
+    pub fn artifact_store(mut self, store: impl ArtifactStore + 'static) -> Self {
+        self.artifact_store = Some(std::sync::Arc::new(store));
+        self
+    }
+
+    /// Store session artifacts in the given local directory.
+    pub fn artifact_dir(self, path: std::path::PathBuf) -> Self {
+        self.artifact_store(LocalDirStore::new(path))
+    }
+
+    /// Set the number of audit log entries kept in memory (0 disables the ring buffer).
+    pub fn audit_log(mut self, capacity: usize) -> Self {
+        self.audit_capacity = capacity;
+        self
+    }
+
+    /// Set whether audit log entries are persisted as a JSONL artifact.
+    pub fn persist_audit_log(mut self, persist: bool) -> Self {
+        self.persist_audit_log = persist;
+        self
+    }
+
+    /// Set the number of events buffered for `poll_events` clients.
+    pub fn event_buffer(mut self, capacity: usize) -> Self {
+        self.event_buffer_capacity = capacity;
+        self
+    }
+
+    /// Limit each connection to the given number of commands per second.
+    ///
+    /// Excess requests are rejected with a structured `429` error instead of being queued.
+    pub fn rate_limit(mut self, commands_per_second: u32) -> Self {
+        self.rate_limits.commands_per_second = Some(commands_per_second);
+        self
+    }
+
+    /// Set the maximum number of requests processed at once per connection (0 for no limit).
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.rate_limits.max_in_flight = max;
+        self
+    }
+
+    /// Set whether executed commands are journaled to disk for crash recovery.
+    pub fn record_sessions(mut self, record: bool) -> Self {
+        self.record_sessions = record;
+        self
+    }
+
+    /// Store the session journals in the given directory.
+    pub fn session_dir(mut self, path: std::path::PathBuf) -> Self {
+        self.session_dir = Some(path);
+        self
+    }
+
+    /// Set the timeout of tools without a specific one (5 seconds by default).
+    pub fn default_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeouts.default = timeout;
+        self
+    }
+
+    /// Set the timeout of a tool, by command name (e.g. `"get_dom"`).
+    pub fn tool_timeout(mut self, command: &str, timeout: std::time::Duration) -> Self {
+        self.timeouts.per_tool.insert(command.to_string(), timeout);
+        self
+    }
+
+    /// Set how often clients that ask for it get a keepalive message (15 seconds by
+    /// default). `Duration::ZERO` disables keepalives.
+    pub fn keepalive_interval(mut self, interval: std::time::Duration) -> Self {
+        self.keepalive.interval = (!interval.is_zero()).then_some(interval);
+        self
+    }
+
+    /// Close connections without requests for `timeout`, e.g. ones left open by crashed
+    /// clients. Requests in flight keep a connection open. `Duration::ZERO` disables it,
+    /// which is the default.
+    pub fn idle_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.keepalive.idle_timeout = (!timeout.is_zero()).then_some(timeout);
+        self
+    }
+
+    /// Set how long in-flight commands may finish when the app exits, after which they are
+    /// cancelled with a shutdown error (5 seconds by default).
+    pub fn shutdown_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    /// Advertise the TCP transport as `_tauri-mcp._tcp` over mDNS, with the app name and
+    /// protocol version, so clients on the network can find it. Requires the `mdns` feature.
+    pub fn advertise_mdns(mut self, advertise: bool) -> Self {
+        self.advertise_mdns = advertise;
+        self
+    }
+
+    /// Intercept the dialogs of the Tauri dialog plugin opened from the webview: instead of
+    /// blocking on a modal, they are reported to clients, which answer them. Can also be
+    /// toggled at runtime with the `set_dialog_interception` command.
+    pub fn intercept_dialogs(mut self, intercept: bool) -> Self {
+        self.intercept_dialogs = intercept;
+        self
+    }
+
+    /// Create WebGL contexts with `preserveDrawingBuffer`, so `capture_canvas` can read
+    /// canvases the app only draws on demand. Costs some rendering performance.
+    pub fn preserve_drawing_buffers(mut self, preserve: bool) -> Self {
+        self.preserve_drawing_buffers = preserve;
+        self
+    }
+
+    /// Ask the user before the first `simulate_*` command drives the OS mouse and keyboard,
+    /// and refuse input simulation unless they allow it. Recommended when the plugin ships
+    /// in builds given to users.
+    pub fn require_input_consent(mut self, require: bool) -> Self {
+        self.require_input_consent = require;
+        self
+    }
+
+    /// Show a ghost cursor moving to the elements tools click or type into, outlined for a
+    /// moment, so people watching a session or its recording can follow it. Can also be
+    /// toggled with the `set_action_overlay` command or per request with `show_action`.
+    pub fn show_actions(mut self, show: bool) -> Self {
+        self.show_actions = show;
+        self
+    }
+
+    /// Let clients read and write a tauri-plugin-store file with `manage_store`, by the path
+    /// given to the store plugin (e.g. `"settings.json"`). Requires the `store` feature.
+    pub fn allow_store(mut self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.allowed_stores.push(path.into());
+        self
+    }
+
+    /// Let clients query a SQLite database with `query_db`, read-only. `url` is the
+    /// `sqlite:` URL given to tauri-plugin-sql (e.g. `"sqlite:app.db"`). Requires the `sql`
+    /// feature.
+    pub fn database(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.databases.push(DatabaseConnection {
+            name: name.into(),
+            url: url.into(),
+            read_only: true,
+        });
+        self
+    }
+
+    /// Let clients read and write the files of a directory with `read_app_file` and
+    /// `write_app_file`, under the given root name. A relative `dir` is resolved against the
+    /// app data dir, so `file_root("data", ".")` exposes the app data dir itself.
+    pub fn file_root(
+        mut self,
+        name: impl Into<String>,
+        dir: impl Into<std::path::PathBuf>,
+    ) -> Self {
+        self.file_roots.push((name.into(), dir.into()));
+        self
+    }
+
+    /// Save the files the webviews download in `dir` instead of the user's Downloads, so
+    /// tests can check exports with `get_downloads` without leaving files behind. A
+    /// relative `dir` is resolved against the app data dir. Downloads are redirected on
+    /// Linux for now.
+    pub fn download_dir(mut self, dir: impl Into<std::path::PathBuf>) -> Self {
+        self.download_dir = Some(dir.into());
+        self
+    }
+
+    /// Like [`PluginConfig::database`], but clients may also change the database.
+    pub fn writable_database(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.databases.push(DatabaseConnection {
+            name: name.into(),
+            url: url.into(),
+            read_only: false,
+        });
+        self
+    }
+
+    /// Run the tools headless, or with a display, instead of detecting it.
+    ///
+    /// The `TAURI_MCP_HEADLESS` environment variable overrides this, so CI jobs can switch
+    /// it without rebuilding the app.
+    pub fn headless(mut self, headless: bool) -> Self {
+        self.headless = Some(headless);
+        self
+    }
+
+    /// Call `hook` when a client connects, e.g. to show a status indicator.
+    pub fn on_client_connected<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ClientInfo) + Send + Sync + 'static,
+    {
+        self.on_client_connected.push(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` when a client disconnects, with the requests it made.
+    pub fn on_client_disconnected<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ClientInfo) + Send + Sync + 'static,
+    {
+        self.on_client_disconnected.push(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Call `hook` before each tool call, including the steps of scripts. A
+    /// [`ToolDecision::Deny`] refuses the call with its reason.
+    pub fn on_tool_invoked<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ToolInvocation) -> ToolDecision + Send + Sync + 'static,
+    {
+        self.on_tool_invoked.push(std::sync::Arc::new(hook));
+        self
+    }
 }
 
-/// Initializes the plugin.
-pub fn init<R: Runtime>() -> TauriPlugin<R> {
-    init_with_config(PluginConfig::default())
+/// Publish an event to long-polling clients, if the plugin is set up.
+fn publish_event<R: Runtime>(app: &AppHandle<R>, kind: &str, data: serde_json::Value) {
+    if let Some(tauri_mcp) = app.try_state::<TauriMcp<R>>() {
+        tauri_mcp.events().publish(kind, data);
+    }
 }
 
-/// Initializes the plugin with the given configuration.
-pub fn init_with_config<R: Runtime>(config: PluginConfig) -> TauriPlugin<R> {
+/// Report dialogs caught by the webview hook to clients.
+fn listen_for_dialogs<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(dialogs::DIALOG_OPENED_EVENT, move |event| {
+        let dialog: PendingDialog = match serde_json::from_str(event.payload()) {
+            Ok(dialog) => dialog,
+            Err(e) => {
+                warn!("[TAURI_MCP] Invalid dialog notification: {}", e);
+                return;
+            }
+        };
+        info!(
+            "[TAURI_MCP] Intercepted {} dialog {} in {}",
+            dialog.kind, dialog.dialog_id, dialog.window_label
+        );
+        if handle.tauri_mcp().dialogs().opened(&handle, dialog.clone()) {
+            publish_event(
+                &handle,
+                "dialogOpened",
+                serde_json::to_value(&dialog).unwrap_or_default(),
+            );
+        }
+    });
+
+    let handle = app.clone();
+    app.listen(dialogs::DIALOG_ANSWERED_EVENT, move |event| {
+        let dialog: dialogs::AnsweredDialog = match serde_json::from_str(event.payload()) {
+            Ok(dialog) => dialog,
+            Err(e) => {
+                warn!("[TAURI_MCP] Invalid dialog answer notification: {}", e);
+                return;
+            }
+        };
+        info!(
+            "[TAURI_MCP] Answered {} dialog in {} by policy",
+            dialog.kind, dialog.window_label
+        );
+        handle.tauri_mcp().dialogs().record_answer(dialog.clone());
+        publish_event(
+            &handle,
+            "dialogAnswered",
+            serde_json::to_value(&dialog).unwrap_or_default(),
+        );
+    });
+}
+
+/// Add the interactions reported by the webview hook to the running recording.
+fn listen_for_interactions<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(
+        interactions::INTERACTION_EVENT,
+        move |event| match serde_json::from_str(event.payload()) {
+            Ok(interaction) => handle.tauri_mcp().interactions().recorded(interaction),
+            Err(e) => warn!("[TAURI_MCP] Invalid interaction notification: {}", e),
+        },
+    );
+}
+
+/// Record the permissions the pages ask for, and serve the clipboard reads the permission
+/// policy grants.
+fn listen_for_permission_requests<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(permissions::PERMISSION_REQUESTED_EVENT, move |event| {
+        let request = match serde_json::from_str(event.payload()) {
+            Ok(request) => handle.tauri_mcp().permissions().record(request),
+            Err(e) => {
+                warn!("[TAURI_MCP] Invalid permission request notification: {}", e);
+                return;
+            }
+        };
+        publish_event(
+            &handle,
+            "permissionRequested",
+            serde_json::to_value(&request).unwrap_or_default(),
+        );
+    });
+
+    #[derive(serde::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct ClipboardRead {
+        id: u64,
+        window_label: String,
+    }
+
+    let handle = app.clone();
+    app.listen(
+        permissions::CLIPBOARD_READ_EVENT,
+        move |event| match serde_json::from_str::<ClipboardRead>(event.payload()) {
+            Ok(read) => handle.tauri_mcp().permissions().read_clipboard(
+                &handle,
+                &read.window_label,
+                read.id,
+            ),
+            Err(e) => warn!("[TAURI_MCP] Invalid clipboard read request: {}", e),
+        },
+    );
+}
+
+/// Store the errors reported by the webview hook and notify clients.
+fn listen_for_js_errors<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(js_errors::JS_ERROR_EVENT, move |event| {
+        let error = match serde_json::from_str(event.payload()) {
+            Ok(error) => handle.tauri_mcp().js_errors().record(error),
+            Err(e) => {
+                warn!("[TAURI_MCP] Invalid JS error notification: {}", e);
+                return;
+            }
+        };
+        publish_event(
+            &handle,
+            "jsError",
+            serde_json::to_value(&error).unwrap_or_default(),
+        );
+    });
+}
+
+/// Store the in-page navigations reported by the webview hook and notify clients.
+fn listen_for_navigations<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(navigation::NAVIGATION_EVENT, move |event| {
+        let navigation = match serde_json::from_str(event.payload()) {
+            Ok(navigation) => handle.tauri_mcp().navigations().record(navigation),
+            Err(e) => {
+                warn!("[TAURI_MCP] Invalid navigation notification: {}", e);
+                return;
+            }
+        };
+        publish_event(
+            &handle,
+            "navigation",
+            serde_json::to_value(&navigation).unwrap_or_default(),
+        );
+    });
+}
+
+/// Resume the session when the user hands control back from the pause banner.
+fn listen_for_resume_requests<R: Runtime>(app: &AppHandle<R>) {
+    use tauri::Listener;
+
+    let handle = app.clone();
+    app.listen(handoff::RESUME_REQUESTED_EVENT, move |_| {
+        info!("[TAURI_MCP] User handed control back to the agent");
+        handle.tauri_mcp().session_pause().resume(&handle, false);
+    });
+}
+
+/// Log the transport and safety mode the plugin starts with.
+fn log_config(config: &PluginConfig) {
     // Log socket configuration
     match &config.socket_type {
+        #[cfg(mobile)]
+        SocketType::Ipc { path: None } => {
+            info!(
+                "[TAURI_MCP] Socket server will use TCP on mobile: 127.0.0.1:{0}, on Android forward it with `adb forward tcp:{0} tcp:{0}`",
+                mobile::DEFAULT_MOBILE_PORT
+            );
+        }
         SocketType::Ipc { path } => {
             if let Some(path) = path {
                 info!(
@@ -116,7 +701,7 @@ pub fn init_with_config<R: Runtime>(config: PluginConfig) -> TauriPlugin<R> {
                     path.display()
                 );
             } else {
-                let default_path = std::env::temp_dir().join("tauri-mcp.sock");
+                let default_path = discovery::default_socket_path(&config.application_name);
                 info!(
                     "[TAURI_MCP] Socket server will use default IPC path: {}",
                     default_path.display()
@@ -128,25 +713,175 @@ pub fn init_with_config<R: Runtime>(config: PluginConfig) -> TauriPlugin<R> {
         }
     }
 
-    if config.start_socket_server {
+    let mode = ServerMode::resolve(config);
+    if !mode.allows_socket_server() {
+        warn!(
+            "[TAURI_MCP] Release build without opt-in, socket server is disabled. Use PluginConfig::allow_in_release(true) or set {}=1 to enable it",
+            ALLOW_IN_RELEASE_ENV
+        );
+    } else if config.start_socket_server {
         info!("[TAURI_MCP] Socket server will start automatically");
     } else {
         info!("[TAURI_MCP] Socket server auto-start is disabled");
     }
+}
+
+/// Initializes the plugin with the default configuration, named after the app's product name.
+///
+/// The `plugins.tauri-mcp` section of `tauri.conf.json` and the `TAURI_MCP_*` environment
+/// variables configure it, as with [`init_with_config`].
+pub fn init<R: Runtime>() -> TauriPlugin<R, Option<PluginFileConfig>> {
+    init_with_config(PluginConfig::default())
+}
 
-    Builder::new("tauri-mcp")
+/// Initializes the plugin with the given configuration.
+///
+/// The `plugins.tauri-mcp` section of `tauri.conf.json`, if any, overrides it, see
+/// [`PluginFileConfig`], and the `TAURI_MCP_*` environment variables override both.
+pub fn init_with_config<R: Runtime>(
+    config: PluginConfig,
+) -> TauriPlugin<R, Option<PluginFileConfig>> {
+    let hooks = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        JsErrorLog::init_script(),
+        NavigationLog::init_script(),
+        DialogInterceptor::init_script(config.intercept_dialogs),
+        InteractionRecorder::init_script(),
+        Emulation::init_script(),
+        PermissionGate::init_script(),
+        canvas::init_script(config.preserve_drawing_buffers),
+        SessionPause::init_script(),
+        ActionOverlay::init_script()
+    );
+
+    Builder::<R, Option<PluginFileConfig>>::new("tauri-mcp")
+        .js_init_script(hooks)
         .invoke_handler(tauri::generate_handler![
-        // Server Commands
+            // Server Commands
+            commands::get_server_mode,
+            commands::start_socket_server,
+            commands::stop_socket_server,
+            commands::restart_socket_server,
+            commands::get_session_status,
         ])
         .setup(move |app, api| {
             info!("[TAURI_MCP] Setting up plugin");
-            #[cfg(mobile)]
-            panic!("Mobile is not supported");
-            #[cfg(desktop)]
+            let config = match api.config().clone() {
+                Some(file_config) => file_config.apply(config)?,
+                None => config,
+            };
+            let mut config = env_overrides::apply(config)?;
+            if config.application_name.is_empty() {
+                config.application_name = app.package_info().name.clone();
+            }
+            log_config(&config);
             let tauri_mcp = desktop::init(app, api, &config)?;
             app.manage(tauri_mcp);
+            app.manage(tools::bridge::PendingRequests::default());
+            listen_for_dialogs(app.app_handle());
+            listen_for_permission_requests(app.app_handle());
+            listen_for_interactions(app.app_handle());
+            listen_for_js_errors(app.app_handle());
+            listen_for_navigations(app.app_handle());
+            listen_for_resume_requests(app.app_handle());
             info!("[TAURI_MCP] Plugin setup complete");
             Ok(())
         })
+        .on_window_ready(|window| {
+            publish_event(
+                window.app_handle(),
+                "windowCreated",
+                serde_json::json!({ "label": window.label() }),
+            );
+        })
+        .on_webview_ready(|webview| {
+            #[cfg(target_os = "linux")]
+            {
+                downloads::platform::attach(&webview);
+                permissions::platform::attach(&webview);
+            }
+            #[cfg(not(target_os = "linux"))]
+            let _ = webview;
+        })
+        .on_page_load(|webview, payload| {
+            let (status, kind) = match payload.event() {
+                PageLoadEvent::Started => ("started", NavigationKind::LoadStarted),
+                PageLoadEvent::Finished => ("finished", NavigationKind::LoadFinished),
+            };
+            if let Some(tauri_mcp) = webview.try_state::<TauriMcp<R>>() {
+                tauri_mcp.navigations().record(Navigation {
+                    seq: 0,
+                    timestamp_ms: 0,
+                    window_label: webview.label().to_string(),
+                    kind,
+                    url: payload.url().to_string(),
+                    title: None,
+                });
+            }
+            // The hook starts with the configured state, apply runtime changes
+            if payload.event() == PageLoadEvent::Finished
+                && let Some(tauri_mcp) = webview.try_state::<TauriMcp<R>>()
+            {
+                tauri_mcp.dialogs().sync(webview);
+                tauri_mcp.permissions().sync(webview);
+                if tauri_mcp.interactions().is_recording() {
+                    tauri_mcp
+                        .interactions()
+                        .navigated(webview.label(), payload.url());
+                }
+                tauri_mcp.interactions().sync(webview);
+                tauri_mcp.emulation().sync(webview);
+                tauri_mcp.session_pause().sync(webview);
+            }
+            publish_event(
+                webview.app_handle(),
+                "pageLoad",
+                serde_json::json!({
+                    "label": webview.label(),
+                    "url": payload.url().to_string(),
+                    "status": status,
+                }),
+            );
+        })
+        .on_event(|app, event| match event {
+            RunEvent::WindowEvent { label, event, .. } => {
+                let kind = match event {
+                    WindowEvent::Destroyed => "windowDestroyed",
+                    WindowEvent::Focused(true) => "windowFocused",
+                    WindowEvent::Focused(false) => "windowBlurred",
+                    _ => return,
+                };
+                publish_event(app, kind, serde_json::json!({ "label": label }));
+            }
+            RunEvent::ExitRequested { code, api, .. } => {
+                // Tools waiting on a webview need the event loop, so in-flight commands are
+                // drained in the background before exiting again
+                if let Some(tauri_mcp) = app.try_state::<TauriMcp<R>>()
+                    && !tauri_mcp.shutdown().is_shutting_down()
+                    && tauri_mcp.shutdown().in_flight() > 0
+                {
+                    api.prevent_exit();
+                    let app = app.clone();
+                    let code = *code;
+                    std::thread::spawn(move || {
+                        app.tauri_mcp().shut_down();
+                        if code == Some(tauri::RESTART_EXIT_CODE) {
+                            app.restart();
+                        } else {
+                            app.exit(code.unwrap_or(0));
+                        }
+                    });
+                }
+            }
+            RunEvent::Exit => {
+                // Managed state is not always dropped on exit, release the socket and close
+                // the journal explicitly
+                if let Some(tauri_mcp) = app.try_state::<TauriMcp<R>>() {
+                    tauri_mcp.shut_down();
+                    tauri_mcp.session_recorder().finish();
+                }
+            }
+            _ => {}
+        })
         .build()
 }
@@ -0,0 +1,81 @@
+use log::{info, warn};
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use std::net::IpAddr;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::protocol::PROTOCOL_VERSION;
+
+/// DNS-SD service type advertised for the TCP transport.
+pub const SERVICE_TYPE: &str = "_tauri-mcp._tcp.local.";
+
+/// Advertises the TCP socket server over mDNS while it is alive.
+pub(crate) struct MdnsAdvertiser {
+    daemon: ServiceDaemon,
+    fullname: String,
+}
+
+impl MdnsAdvertiser {
+    /// Register the `_tauri-mcp._tcp` service for the given bind address.
+    ///
+    /// Unspecified hosts (`0.0.0.0`, `::`) advertise every address of the machine.
+    pub fn start(application_name: &str, host: &str, port: u16) -> crate::Result<Self> {
+        let pid = std::process::id();
+        let instance_name = if application_name.is_empty() {
+            format!("tauri-mcp-{}", pid)
+        } else {
+            format!("{}-{}", application_name, pid)
+        };
+        let properties = [
+            ("app", application_name.to_string()),
+            ("pid", pid.to_string()),
+            ("protocolVersion", PROTOCOL_VERSION.to_string()),
+        ];
+
+        let ip: Option<IpAddr> = host.parse().ok();
+        let host_name = format!("tauri-mcp-{}.local.", pid);
+        let service = match ip {
+            Some(ip) if !ip.is_unspecified() => ServiceInfo::new(
+                SERVICE_TYPE,
+                &instance_name,
+                &host_name,
+                ip,
+                port,
+                &properties[..],
+            ),
+            _ => ServiceInfo::new(
+                SERVICE_TYPE,
+                &instance_name,
+                &host_name,
+                (),
+                port,
+                &properties[..],
+            )
+            .map(ServiceInfo::enable_addr_auto),
+        }
+        .map_err(|e| Error::Io(format!("Invalid mDNS service: {}", e)))?;
+
+        let fullname = service.get_fullname().to_string();
+        let daemon = ServiceDaemon::new()
+            .map_err(|e| Error::Io(format!("Failed to start mDNS daemon: {}", e)))?;
+        daemon
+            .register(service)
+            .map_err(|e| Error::Io(format!("Failed to register mDNS service: {}", e)))?;
+        info!("[TAURI_MCP] Advertising {} over mDNS", fullname);
+
+        Ok(Self { daemon, fullname })
+    }
+}
+
+impl Drop for MdnsAdvertiser {
+    fn drop(&mut self) {
+        // Wait for the goodbye packets before shutting the daemon down
+        match self.daemon.unregister(&self.fullname) {
+            Ok(status) => {
+                let _ = status.recv_timeout(Duration::from_secs(1));
+            }
+            Err(e) => warn!("[TAURI_MCP] Failed to unregister mDNS service: {}", e),
+        }
+        let _ = self.daemon.shutdown();
+    }
+}
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+
+/// Kind of a menu item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MenuEntryKind {
+    Normal,
+    Check,
+    Icon,
+    Submenu,
+    /// Separator or OS-provided item like quit or copy.
+    Predefined,
+}
+
+/// An item of a menu bar or tray menu.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuEntry {
+    /// ID to pass to `click_menu_item` or `trigger_tray_menu_item`.
+    pub id: String,
+    pub kind: MenuEntryKind,
+    pub text: String,
+    /// None for predefined items.
+    pub enabled: Option<bool>,
+    /// Only set for check items.
+    pub checked: Option<bool>,
+    /// Items of a submenu.
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub items: Vec<MenuEntry>,
+}
+
+#[cfg(desktop)]
+pub(crate) use native::*;
+
+#[cfg(desktop)]
+mod native {
+    use std::sync::{Arc, Mutex};
+    use tauri::menu::{MenuEvent, MenuItemKind};
+    use tauri::{AppHandle, Runtime};
+
+    use super::{MenuEntry, MenuEntryKind};
+    use crate::TauriMcpExt;
+
+    pub(crate) type MenuHandler<R> = Arc<dyn Fn(&AppHandle<R>, MenuEvent) + Send + Sync>;
+
+    /// Menu event handlers installed through the plugin, which `click_menu_item` and
+    /// `trigger_tray_menu_item` call since Tauri offers no way to inject menu events.
+    pub(crate) struct MenuHandlers<R: Runtime> {
+        handlers: Mutex<Vec<MenuHandler<R>>>,
+    }
+
+    impl<R: Runtime> MenuHandlers<R> {
+        pub(crate) fn new() -> Self {
+            Self {
+                handlers: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Keep a handler and return the function to install on the app or tray.
+        pub(crate) fn add(
+            &self,
+            handler: MenuHandler<R>,
+        ) -> impl Fn(&AppHandle<R>, MenuEvent) + Send + Sync + 'static {
+            self.handlers.lock().unwrap().push(handler.clone());
+            move |app: &AppHandle<R>, event: MenuEvent| handler(app, event)
+        }
+
+        fn list(&self) -> Vec<MenuHandler<R>> {
+            self.handlers.lock().unwrap().clone()
+        }
+    }
+
+    /// Select a menu item as if the user clicked it: disabled items are refused, check
+    /// items are toggled, then the handlers are called on the main thread like for a real
+    /// click. Returns the item as it is after the click.
+    pub(crate) fn activate<R: Runtime>(
+        app: &AppHandle<R>,
+        item: &MenuItemKind<R>,
+    ) -> crate::Result<MenuEntry> {
+        let handlers = app.tauri_mcp().menu_handlers().list();
+        if handlers.is_empty() {
+            return Err(crate::Error::Anyhow(
+                "No menu event handler to call, set it with TauriMcp::on_menu_event".to_string(),
+            ));
+        }
+        if describe_item(item)?.enabled == Some(false) {
+            return Err(crate::Error::Anyhow(format!(
+                "Menu item {} is disabled",
+                item.id().as_ref()
+            )));
+        }
+        if let Some(check) = item.as_check_menuitem() {
+            check.set_checked(!check.is_checked()?)?;
+        }
+
+        let id = item.id().clone();
+        let handle = app.clone();
+        app.run_on_main_thread(move || {
+            for handler in handlers {
+                handler(&handle, MenuEvent { id: id.clone() });
+            }
+        })?;
+        describe_item(item)
+    }
+
+    /// Find an item by ID among `items` and their submenus, with the texts leading to it.
+    pub(crate) fn find_by_id<R: Runtime>(
+        items: Vec<MenuItemKind<R>>,
+        id: &str,
+    ) -> crate::Result<Option<(MenuItemKind<R>, Vec<String>)>> {
+        for item in items {
+            let text = item_text(&item)?;
+            if item.id().as_ref() == id {
+                return Ok(Some((item, vec![text])));
+            }
+            if let Some(submenu) = item.as_submenu()
+                && let Some((found, mut path)) = find_by_id(submenu.items()?, id)?
+            {
+                path.insert(0, text);
+                return Ok(Some((found, path)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Find an item by the texts leading to it, e.g. `["File", "Export", "PDF"]`. Texts are
+    /// compared ignoring case and `&` mnemonic markers.
+    pub(crate) fn find_by_path<R: Runtime>(
+        items: Vec<MenuItemKind<R>>,
+        path: &[String],
+    ) -> crate::Result<Option<(MenuItemKind<R>, Vec<String>)>> {
+        let Some((first, rest)) = path.split_first() else {
+            return Ok(None);
+        };
+        for item in items {
+            let text = item_text(&item)?;
+            if label(&text) != label(first) {
+                continue;
+            }
+            if rest.is_empty() {
+                return Ok(Some((item, vec![text])));
+            }
+            if let Some(submenu) = item.as_submenu()
+                && let Some((found, mut texts)) = find_by_path(submenu.items()?, rest)?
+            {
+                texts.insert(0, text);
+                return Ok(Some((found, texts)));
+            }
+        }
+        Ok(None)
+    }
+
+    fn label(text: &str) -> String {
+        text.replace("&&", "\u{0}")
+            .replace('&', "")
+            .replace('\u{0}', "&")
+            .trim()
+            .to_lowercase()
+    }
+
+    fn item_text<R: Runtime>(item: &MenuItemKind<R>) -> crate::Result<String> {
+        let text = match item {
+            MenuItemKind::MenuItem(item) => item.text()?,
+            MenuItemKind::Check(item) => item.text()?,
+            MenuItemKind::Icon(item) => item.text()?,
+            MenuItemKind::Submenu(item) => item.text()?,
+            MenuItemKind::Predefined(item) => item.text()?,
+        };
+        Ok(text)
+    }
+
+    pub(crate) fn describe_items<R: Runtime>(
+        items: Vec<MenuItemKind<R>>,
+    ) -> crate::Result<Vec<MenuEntry>> {
+        items.iter().map(describe_item).collect()
+    }
+
+    pub(crate) fn describe_item<R: Runtime>(item: &MenuItemKind<R>) -> crate::Result<MenuEntry> {
+        let (kind, enabled, checked, items) = match item {
+            MenuItemKind::MenuItem(item) => (
+                MenuEntryKind::Normal,
+                Some(item.is_enabled()?),
+                None,
+                Vec::new(),
+            ),
+            MenuItemKind::Check(item) => (
+                MenuEntryKind::Check,
+                Some(item.is_enabled()?),
+                Some(item.is_checked()?),
+                Vec::new(),
+            ),
+            MenuItemKind::Icon(item) => (
+                MenuEntryKind::Icon,
+                Some(item.is_enabled()?),
+                None,
+                Vec::new(),
+            ),
+            MenuItemKind::Submenu(item) => (
+                MenuEntryKind::Submenu,
+                Some(item.is_enabled()?),
+                None,
+                describe_items(item.items()?)?,
+            ),
+            MenuItemKind::Predefined(_) => (MenuEntryKind::Predefined, None, None, Vec::new()),
+        };
+        Ok(MenuEntry {
+            id: item.id().as_ref().to_string(),
+            kind,
+            text: item_text(item)?,
+            enabled,
+            checked,
+            items,
+        })
+    }
+}
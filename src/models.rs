@@ -1,15 +1,35 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
 
-#[derive(Debug, Deserialize, Serialize)]
+use crate::downloads::Download;
+use crate::menu::MenuEntry;
+use crate::navigation::Navigation;
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PingRequest {
     pub value: Option<String>,
+    /// Any JSON, echoed back, e.g. a client timestamp to measure the round trip.
+    pub payload: Option<Value>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PingResponse {
     pub value: Option<String>,
+    pub payload: Option<Value>,
+    /// When the app answered, in milliseconds since the Unix epoch.
+    pub server_time_ms: u64,
+    pub protocol_version: u32,
+    pub app_name: String,
+    /// Bundle identifier of the app, from `tauri.conf.json`.
+    pub identifier: String,
+    pub app_version: String,
+    pub pid: u32,
+    pub tauri_version: String,
+    pub plugin_version: String,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -87,12 +107,21 @@ pub struct ElementRequest {
     pub selector: String,
 }
 
-#[derive(Debug, Deserialize, Serialize)]
-#[serde(rename_all = "camelCase")]
+// Form control value request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
 pub struct SetElementValueRequest {
+    #[serde(default = "default_window_label")]
     pub window_label: String,
-    pub selector: String,
-    pub value: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Option value(s) of a select, checked state of a checkbox or radio (toggled when
+    /// absent), or value of another input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// Option label(s) of a select, instead of `value`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<Value>,
 }
 
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
@@ -143,7 +172,7 @@ pub struct HtmlResponse {
 }
 
 // LocalStorage request model
-#[derive(Debug, Deserialize, Serialize, Clone)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct LocalStorageRequest {
     pub action: String,
@@ -152,8 +181,1464 @@ pub struct LocalStorageRequest {
     pub window_label: Option<String>,
 }
 
+// Execute JS request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ExecuteJsRequest {
+    pub window_label: Option<String>,
+    pub code: String,
+    pub timeout_ms: Option<u64>,
+}
+
+// Execute JS response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExecuteJsResponse {
+    pub result: String,
+    #[serde(rename = "type")]
+    pub result_type: String,
+}
+
+// Frame selector model
+/// A same-origin iframe of the page: the CSS selector of the frame, or the selectors of
+/// the nested frames leading to it, outermost first.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(untagged)]
+pub enum FrameSelector {
+    Selector(String),
+    Path(Vec<String>),
+}
+
+// Element position request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ElementPositionRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the element in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    pub selector_type: String,
+    pub selector_value: String,
+    #[serde(default)]
+    pub should_click: bool,
+    #[serde(default)]
+    pub raw_coordinates: bool,
+    /// Scroll the element to the center of the window first when it is not fully visible.
+    #[serde(default)]
+    pub scroll_into_view: bool,
+}
+
+// Send text to element request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SendTextToElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the element in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    pub selector_type: String,
+    pub selector_value: String,
+    pub text: String,
+    #[serde(default = "default_delay_ms")]
+    pub delay_ms: u32,
+}
+
+// Default delay between keystrokes
+fn default_delay_ms() -> u32 {
+    20
+}
+
+// DOM snapshot options of get_dom, all off by default
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct DomSnapshotOptions {
+    /// Snapshot this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    /// Drop `<script>` and `<noscript>` elements.
+    #[serde(default)]
+    pub strip_scripts: bool,
+    /// Drop `<style>` elements and stylesheet links.
+    #[serde(default)]
+    pub strip_styles: bool,
+    /// Collapse runs of whitespace in text and drop whitespace-only text nodes.
+    #[serde(default)]
+    pub collapse_whitespace: bool,
+    /// Drop elements of the body that are not rendered (`display: none`, `visibility: hidden`, `hidden`).
+    #[serde(default)]
+    pub exclude_hidden: bool,
+    /// Replace the children of elements deeper than this with a comment counting them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_depth: Option<u32>,
+    /// Cut the HTML after this many characters and append a truncation marker.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+// Page text request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct PageTextRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// CSS selector; the text of every match is returned, separated by blank lines.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selector: Option<String>,
+    /// Prefer the main content and skip navigation, headers, footers and sidebars.
+    #[serde(default)]
+    pub readable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_length: Option<usize>,
+}
+
+// Page text response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PageTextResponse {
+    pub text: String,
+    /// Length of the full text in characters, before truncation.
+    pub length: usize,
+    pub truncated: bool,
+    pub match_count: usize,
+}
+
+// UI snapshot request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct UiSnapshotRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Most outline lines returned, deeper and later elements are left out.
+    #[serde(default = "default_max_outline_nodes")]
+    pub max_nodes: usize,
+    /// Include a JPEG of the viewport.
+    #[serde(default = "default_true")]
+    pub screenshot: bool,
+    /// Widest screenshot in pixels, larger viewports are scaled down.
+    #[serde(default = "default_snapshot_max_width")]
+    pub screenshot_max_width: u32,
+    /// JPEG quality of the screenshot, from 1 to 100.
+    #[serde(default = "default_snapshot_quality")]
+    pub screenshot_quality: u8,
+}
+
+// UI snapshot response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UiSnapshotResponse {
+    pub url: String,
+    pub title: String,
+    /// None when nothing has the focus.
+    pub focused: Option<FocusedElement>,
+    /// One line per landmark, heading and control, as `- role "name" [states]`.
+    pub outline: String,
+    /// Number of elements in the full outline, before `max_nodes`.
+    pub node_count: usize,
+    pub truncated: bool,
+    #[serde(default)]
+    pub screenshot: Option<ViewportScreenshot>,
+    /// Why no screenshot was taken, when one was requested.
+    #[serde(default)]
+    pub screenshot_error: Option<String>,
+}
+
+// Viewport screenshot model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ViewportScreenshot {
+    pub mime_type: String,
+    /// Base64 encoded image.
+    pub data: String,
+    /// Size of the image in pixels, after scaling down.
+    pub width: u32,
+    pub height: u32,
+}
+
+// Focused element model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct FocusedElement {
+    pub tag: String,
+    pub role: Option<String>,
+    pub name: String,
+    /// CSS selector of the element.
+    pub selector: String,
+}
+
+// Element info request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ElementInfoRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Computed style properties to return, `["*"]` for all. A common set when empty.
+    #[serde(default)]
+    pub styles: Vec<String>,
+}
+
+// Canvas capture request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct CaptureCanvasRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    #[serde(default = "default_canvas_selector_type")]
+    pub selector_type: String,
+    #[serde(default = "default_canvas_selector_value")]
+    pub selector_value: String,
+    /// Capture every matching canvas rather than the first, with css, tag and class selectors.
+    #[serde(default)]
+    pub all: bool,
+    /// "png", "jpeg" or "webp".
+    #[serde(default = "default_canvas_format")]
+    pub format: String,
+    /// Quality of jpeg and webp images, from 0 to 1.
+    pub quality: Option<f64>,
+    /// Dispatch a resize event and capture again when a canvas comes out blank.
+    #[serde(default = "default_true")]
+    pub rerender: bool,
+}
+
+// Canvas capture response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CaptureCanvasResponse {
+    pub canvases: Vec<CanvasCapture>,
+}
+
+// Captured canvas model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CanvasCapture {
+    /// Position among the matching canvases.
+    pub index: usize,
+    pub id: Option<String>,
+    /// Size of the drawing buffer in pixels.
+    pub width: u32,
+    pub height: u32,
+    /// Position and size on the page, in CSS pixels.
+    pub rect: Value,
+    /// "2d", "webgl", "webgl2" or "bitmaprenderer", None when no context was created.
+    pub context: Option<String>,
+    /// For WebGL canvases, whether the drawing buffer is kept after being shown.
+    pub preserve_drawing_buffer: Option<bool>,
+    /// Type of the image, PNG when the requested format is not supported.
+    pub mime_type: String,
+    /// Base64 encoded image, None when the canvas could not be read.
+    pub data: Option<String>,
+    /// True when every pixel is transparent.
+    pub blank: bool,
+    /// Why the canvas could not be read, e.g. because cross-origin images tainted it.
+    pub error: Option<String>,
+}
+
+fn default_canvas_selector_type() -> String {
+    "css".to_string()
+}
+
+fn default_canvas_selector_value() -> String {
+    "canvas".to_string()
+}
+
+fn default_canvas_format() -> String {
+    "png".to_string()
+}
+
+// Element inspection request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct InspectElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the element in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Include a PNG of the element, scrolling it into view when it is not fully visible.
+    #[serde(default = "default_true")]
+    pub screenshot: bool,
+    /// CSS pixels of surroundings kept on each side of the element in the screenshot.
+    #[serde(default)]
+    pub padding: f64,
+    /// Longest text returned, in characters.
+    #[serde(default = "default_inspect_max_text_length")]
+    pub max_text_length: usize,
+}
+
+fn default_inspect_max_text_length() -> usize {
+    2000
+}
+
+// Element highlight request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct HighlightElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the elements in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Outline every matching element rather than the first, with css, class, tag and xpath
+    /// selectors.
+    #[serde(default)]
+    pub all: bool,
+    /// Text shown above each outline, numbered when several elements match.
+    pub label: Option<String>,
+    /// How long the outlines stay, in milliseconds.
+    #[serde(default = "default_highlight_duration_ms")]
+    pub duration_ms: u64,
+}
+
+fn default_highlight_duration_ms() -> u64 {
+    2000
+}
+
+// Element highlight response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HighlightElementResponse {
+    /// Number of matching elements, of which the first 100 are outlined.
+    pub count: usize,
+    pub elements: Vec<HighlightedElement>,
+}
+
+// Highlighted element model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct HighlightedElement {
+    pub tag: String,
+    pub id: String,
+    /// Start of the element's text.
+    pub text: String,
+    /// Bounding box in the window's viewport, in CSS pixels.
+    pub rect: ElementBox,
+}
+
+// Page info request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct PageInfoRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+}
+
+// Page info response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageInfoResponse {
+    /// Label of the window the page is in, useful when the request named none.
+    #[serde(default)]
+    pub window_label: String,
+    pub url: String,
+    pub title: String,
+    /// `document.readyState`: "loading", "interactive" or "complete".
+    pub ready_state: String,
+    /// Entries of the window's session history.
+    pub history_length: u32,
+    /// `document.visibilityState`: "visible" or "hidden".
+    pub visibility_state: String,
+    /// Whether the page has keyboard focus.
+    pub focused: bool,
+    /// Route reported by the provider the app registered with `registerRouteProvider`, as
+    /// `{"path": ...}` when it returns a string. None without a provider.
+    pub route: Option<Value>,
+    /// Why the route provider failed.
+    pub route_error: Option<String>,
+}
+
+// Wait for navigation request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct WaitForNavigationRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// URL to wait for, contained in the URL unless `exact` or `regex` is set. Any
+    /// navigation will do without one.
+    pub pattern: Option<String>,
+    /// Match the whole URL.
+    #[serde(default)]
+    pub exact: bool,
+    /// Treat `pattern` as a regular expression.
+    #[serde(default)]
+    pub regex: bool,
+    /// Only count pages that finished loading, not client-side route changes.
+    #[serde(default)]
+    pub wait_for_load: bool,
+    /// Only count navigations after this sequence number, e.g. the `cursor` of an earlier
+    /// response. When not set, a page already at a matching URL counts, else the next
+    /// navigation does.
+    pub after_seq: Option<u64>,
+}
+
+// Wait for navigation response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WaitForNavigationResponse {
+    pub url: String,
+    /// The navigation waited for, None when the page was already at a matching URL.
+    pub navigation: Option<Navigation>,
+    pub waited_ms: u64,
+    /// Sequence number of the latest navigation, to pass as `after_seq` next time.
+    pub cursor: u64,
+}
+
+// Get downloads request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct GetDownloadsRequest {
+    /// Only report the downloads of this window.
+    pub window_label: Option<String>,
+    /// Only report the downloads after this ID, e.g. the cursor of the previous call.
+    pub since: Option<u64>,
+    /// Wait for a download to end first: one in progress or the next one started, or the
+    /// first one after `since` when set.
+    #[serde(default)]
+    pub wait_for_completion: bool,
+}
+
+// Get downloads response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GetDownloadsResponse {
+    pub downloads: Vec<Download>,
+    /// The download waited for, with `wait_for_completion`.
+    pub completed: Option<Download>,
+    /// ID of the latest download, to pass as `since` on the next call.
+    pub cursor: u64,
+    /// Directory downloads are redirected to, if any.
+    pub sandbox_dir: Option<std::path::PathBuf>,
+}
+
+// Control media request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ControlMediaRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the element in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// "status" (the default), "play", "pause", "seek", "mute" or "unmute".
+    #[serde(default = "default_media_action")]
+    pub action: String,
+    /// Position to seek to, in seconds.
+    pub time: Option<f64>,
+    /// Volume to set, between 0 and 1.
+    pub volume: Option<f64>,
+    /// Playback speed to set, 1 being normal.
+    pub playback_rate: Option<f64>,
+}
+
+fn default_media_action() -> String {
+    "status".to_string()
+}
+
+// Media state response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaState {
+    /// "audio" or "video".
+    pub tag: String,
+    pub src: String,
+    /// Playback position in seconds.
+    pub current_time: f64,
+    /// Length in seconds, None while unknown or for live streams.
+    pub duration: Option<f64>,
+    pub live: bool,
+    pub paused: bool,
+    pub ended: bool,
+    pub seeking: bool,
+    pub muted: bool,
+    pub volume: f64,
+    pub playback_rate: f64,
+    /// `HTMLMediaElement.readyState`, from 0 (no data) to 4 (enough data to play through).
+    pub ready_state: u8,
+    /// `HTMLMediaElement.networkState`, from 0 (empty) to 3 (no source).
+    pub network_state: u8,
+    /// Buffered time ranges, start and end in seconds.
+    pub buffered: Vec<[f64; 2]>,
+    /// Error of the media element, e.g. an unsupported format.
+    pub error: Option<String>,
+    /// Why `play()` failed, e.g. an autoplay policy.
+    #[serde(default)]
+    pub play_error: Option<String>,
+}
+
+// Scroll into view request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ScrollIntoViewRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Look for the element in this iframe instead of the page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frame_selector: Option<FrameSelector>,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Vertical alignment: "start", "center", "end" or "nearest".
+    #[serde(default = "default_scroll_block")]
+    pub block: String,
+    /// Horizontal alignment: "start", "center", "end" or "nearest".
+    #[serde(default = "default_scroll_inline")]
+    pub inline: String,
+    /// "instant" or "smooth".
+    #[serde(default = "default_scroll_behavior")]
+    pub behavior: String,
+    /// Scroll even when the element is already fully visible.
+    #[serde(default)]
+    pub force: bool,
+}
+
+fn default_scroll_block() -> String {
+    "center".to_string()
+}
+
+fn default_scroll_inline() -> String {
+    "nearest".to_string()
+}
+
+fn default_scroll_behavior() -> String {
+    "instant".to_string()
+}
+
+// Scroll into view response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScrollIntoViewResponse {
+    /// False when the element was already fully visible.
+    pub scrolled: bool,
+    /// Bounding box in the window's viewport once scrolled, in CSS pixels.
+    pub rect: ElementBox,
+    /// Whether the whole element now shows in the window and its scroll containers.
+    pub fully_visible: bool,
+    /// Scroll offsets of the element's document once scrolled.
+    pub scroll_x: f64,
+    pub scroll_y: f64,
+}
+
+// Element inspection response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InspectElementResponse {
+    pub tag: String,
+    pub id: String,
+    pub classes: Vec<String>,
+    /// ARIA role, explicit or implied by the tag.
+    pub role: Option<String>,
+    /// Accessible name, as the `role` selector matches it.
+    pub name: String,
+    /// Rendered text, clipped to `max_text_length`.
+    pub text: String,
+    pub attributes: serde_json::Map<String, Value>,
+    /// Value of form fields, None for other elements and password fields.
+    pub value: Option<String>,
+    /// Bounding box in the window's viewport, in CSS pixels, after any scrolling.
+    pub rect: ElementBox,
+    pub flags: InteractabilityFlags,
+    /// Whether the element was scrolled into view for the screenshot.
+    pub scrolled: bool,
+    #[serde(default)]
+    pub screenshot: Option<ElementScreenshot>,
+    /// Why no screenshot was taken, when one was requested.
+    #[serde(default)]
+    pub screenshot_error: Option<String>,
+}
+
+// Element bounding box model
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct ElementBox {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// Element interactability model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InteractabilityFlags {
+    /// Rendered, not hidden by `display`, `visibility` or a zero opacity.
+    pub visible: bool,
+    /// At least partly inside the viewport.
+    pub in_viewport: bool,
+    /// Neither disabled nor inside an `aria-disabled` element.
+    pub enabled: bool,
+    /// Accepts typed text.
+    pub editable: bool,
+    pub focusable: bool,
+    pub focused: bool,
+    /// State of checkboxes and radio buttons, None for other elements.
+    pub checked: Option<bool>,
+    /// Another element covers the element's center.
+    pub obscured: bool,
+    /// `pointer-events` does not make clicks go through the element.
+    pub receives_pointer_events: bool,
+    /// A click at the element's center would reach it.
+    pub clickable: bool,
+}
+
+// Element screenshot model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ElementScreenshot {
+    pub mime_type: String,
+    /// Base64 encoded image.
+    pub data: String,
+    /// Size of the image in device pixels.
+    pub width: u32,
+    pub height: u32,
+    /// Part of the viewport shown, in CSS pixels: the element and its padding, clipped to
+    /// the viewport.
+    pub rect: ElementBox,
+}
+
+// Print to PDF request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct PrintToPdfRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Where to write the PDF, a file in the temp directory when not set.
+    pub path: Option<String>,
+    #[serde(default)]
+    pub landscape: bool,
+    /// Also return the PDF, base64 encoded.
+    #[serde(default)]
+    pub include_data: bool,
+}
+
+// Print to PDF response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrintToPdfResponse {
+    pub path: String,
+    /// Size of the file in bytes.
+    pub size: u64,
+    /// Base64 encoded PDF, when `include_data` was set.
+    pub data: Option<String>,
+}
+
+// Click element request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClickElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// "left", "middle" or "right".
+    #[serde(default = "default_button")]
+    pub button: String,
+    #[serde(default = "default_click_count")]
+    pub click_count: u32,
+    /// Held during the click: "shift", "ctrl", "alt" or "meta".
+    #[serde(default)]
+    pub modifiers: Vec<String>,
+    /// "dom" dispatches events in the page, "os" clicks with the OS mouse, "auto" uses the OS
+    /// mouse when OS-level input is fully available.
+    #[serde(default = "default_click_strategy")]
+    pub strategy: String,
+    /// Scroll the element to the center of the window first when it is not fully visible.
+    #[serde(default = "default_true")]
+    pub scroll_into_view: bool,
+}
+
+// Click element response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ClickElementResponse {
+    /// Strategy actually used, "dom" or "os".
+    pub strategy: String,
+    /// Clicked point in viewport CSS pixels.
+    pub x: f64,
+    pub y: f64,
+    pub element: Value,
+}
+
+fn default_button() -> String {
+    "left".to_string()
+}
+
+fn default_click_count() -> u32 {
+    1
+}
+
+fn default_click_strategy() -> String {
+    "dom".to_string()
+}
+
+// File input request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetFileInputRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Files to attach, read by the app from its own file system.
+    pub paths: Vec<String>,
+    /// Keep the files already selected in the input.
+    #[serde(default)]
+    pub append: bool,
+}
+
+// Focus element request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct FocusElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Do not scroll the element into view.
+    #[serde(default)]
+    pub prevent_scroll: bool,
+}
+
+// Blur element request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct BlurElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Element to blur, the focused one when absent.
+    pub selector_type: Option<String>,
+    pub selector_value: Option<String>,
+}
+
+// Form fill request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct FillFormRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Fields to fill, in order.
+    pub fields: Vec<FormField>,
+    /// Skip the remaining fields after the first failure.
+    #[serde(default)]
+    pub stop_on_error: bool,
+    /// Blur the last focused field, so validation on blur runs.
+    #[serde(default)]
+    pub blur: bool,
+}
+
+/// A field of a `fill_form` request, with the value semantics of `set_element_value`.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct FormField {
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label" (the default).
+    #[serde(default = "default_selector_type")]
+    pub selector_type: String,
+    pub selector_value: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<Value>,
+}
+
+// Element assertion request model, for assert_element_exists and assert_element_visible
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssertElementRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// One of "id", "class", "tag", "text", "css", "xpath", "aria-label", "role" or "label".
+    pub selector_type: String,
+    pub selector_value: String,
+    /// Assert the opposite: the element does not exist, or is not visible.
+    #[serde(default)]
+    pub negate: bool,
+}
+
+// Text assertion request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssertTextContainsRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Element whose rendered text is checked, the whole page when absent.
+    pub selector_type: Option<String>,
+    pub selector_value: Option<String>,
+    pub text: String,
+    #[serde(default)]
+    pub ignore_case: bool,
+    #[serde(default)]
+    pub negate: bool,
+}
+
+// URL assertion request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AssertUrlMatchesRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Substring of the URL, the whole URL with `exact`, or a JavaScript regular expression
+    /// with `regex`.
+    pub pattern: String,
+    #[serde(default)]
+    pub exact: bool,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub negate: bool,
+}
+
+/// Verdict of an assert command. A failed assertion is still a successful response.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AssertionResult {
+    /// "element_exists", "element_visible", "text_contains" or "url_matches".
+    pub assertion: String,
+    pub passed: bool,
+    pub expected: Value,
+    pub actual: Value,
+    /// Human-readable verdict, explaining the failure.
+    pub message: String,
+}
+
+// Script execution request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunScriptRequest {
+    pub steps: Vec<ScriptStep>,
+    /// What to do when a step fails, unless the step says otherwise.
+    #[serde(default)]
+    pub on_error: StepErrorPolicy,
+}
+
+/// A command run by `run_script`, or a `wait` for `ms` or until an element is visible.
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScriptStep {
+    pub command: String,
+    #[serde(default = "empty_object")]
+    pub payload: Value,
+    /// Label shown in the report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_error: Option<StepErrorPolicy>,
+    /// Extra attempts before the step counts as failed.
+    #[serde(default)]
+    pub retries: u32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StepErrorPolicy {
+    /// Skip the remaining steps.
+    #[default]
+    Stop,
+    /// Go on with the next step.
+    Continue,
+}
+
+// Script execution response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunScriptResponse {
+    /// True when every step ran and passed.
+    pub passed: bool,
+    pub completed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub duration_ms: u64,
+    pub steps: Vec<StepReport>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepReport {
+    pub index: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub command: String,
+    pub status: StepStatus,
+    pub attempts: u32,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+// Emulation request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetEmulationRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock: Option<ClockRequest>,
+    /// Seed of `Math.random`, `crypto.getRandomValues` and `crypto.randomUUID`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u32>,
+    /// Overrides to remove before applying the others: "clock", "random", "geolocation",
+    /// "locale", "theme", "device", "network", "mocks" or "all".
+    #[serde(default)]
+    pub reset: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct ClockRequest {
+    /// Start time in milliseconds since the Unix epoch, now when absent.
+    pub time_ms: Option<u64>,
+    /// Only move the clock with `advance_clock`.
+    #[serde(default)]
+    pub frozen: bool,
+}
+
+// Geolocation emulation request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetGeolocationRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Required unless `denied` or `reset` is set.
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+    /// Accuracy radius in meters.
+    #[serde(default = "default_geolocation_accuracy")]
+    pub accuracy: f64,
+    pub altitude: Option<f64>,
+    pub heading: Option<f64>,
+    pub speed: Option<f64>,
+    /// Answer location requests with PERMISSION_DENIED.
+    #[serde(default)]
+    pub denied: bool,
+    /// Remove the override and use the real location service again.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+fn default_geolocation_accuracy() -> f64 {
+    10.0
+}
+
+// Locale emulation request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetLocaleRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// BCP 47 tag used as the default locale, e.g. "fr-FR".
+    pub locale: Option<String>,
+    /// Languages of `navigator.languages`; defaults to `locale`.
+    #[serde(default)]
+    pub languages: Vec<String>,
+    /// IANA timezone, e.g. "America/New_York".
+    pub timezone: Option<String>,
+    /// Remove the override.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+// Theme request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetThemeRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Theme to switch to; only reports the current theme when absent.
+    pub theme: Option<ThemeMode>,
+    /// Set the native theme of the Tauri window.
+    #[serde(default = "default_true")]
+    pub native: bool,
+    /// Override `prefers-color-scheme` in the page.
+    #[serde(default = "default_true")]
+    pub media: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    Light,
+    Dark,
+    /// Follow the OS again.
+    System,
+}
+
+// Theme response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThemeResponse {
+    /// Native theme of the window.
+    pub window_theme: crate::ColorScheme,
+    /// `prefers-color-scheme` as the page sees it.
+    pub color_scheme: crate::ColorScheme,
+    /// Override of `prefers-color-scheme`, if any.
+    pub emulated_color_scheme: Option<crate::ColorScheme>,
+}
+
+// Device emulation request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct EmulateDeviceRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Name of a device profile, e.g. "iPhone 15"; the other fields override it.
+    pub device: Option<String>,
+    /// Viewport size in CSS pixels.
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub device_scale_factor: Option<f64>,
+    pub touch: Option<bool>,
+    pub user_agent: Option<String>,
+    /// Swap width and height.
+    #[serde(default)]
+    pub landscape: bool,
+    /// Remove the emulation and restore the window size.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+// Network conditions request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetNetworkConditionsRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// "offline", "slow-3g" or "fast-3g"; the other fields override it.
+    pub preset: Option<String>,
+    pub offline: Option<bool>,
+    pub latency_ms: Option<u64>,
+    pub download_kbps: Option<u64>,
+    pub upload_kbps: Option<u64>,
+    /// Remove the conditions.
+    #[serde(default)]
+    pub reset: bool,
+}
+
+// Mock route request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AddMockRouteRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Replaces the route with the same id; generated when absent.
+    pub id: Option<String>,
+    /// Glob (`*` matches anything) or regular expression, matched against the full URL or
+    /// its path and query.
+    pub url: String,
+    #[serde(default)]
+    pub regex: bool,
+    pub method: Option<String>,
+    #[serde(default = "default_mock_status")]
+    pub status: u16,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Response body; anything but a string is sent as JSON.
+    pub body: Option<Value>,
+    /// Send matching requests to this URL instead of answering them.
+    pub rewrite_url: Option<String>,
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+fn default_mock_status() -> u16 {
+    200
+}
+
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct RemoveMockRouteRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    pub id: Option<String>,
+    /// Remove every route of the window.
+    #[serde(default)]
+    pub all: bool,
+}
+
+// Process stats request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct GetProcessStatsRequest {
+    /// Also report the processes started by the app, such as webview processes.
+    #[serde(default = "default_true")]
+    pub include_children: bool,
+}
+
+// JS errors request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct GetJsErrorsRequest {
+    /// Only report the errors of this window.
+    pub window_label: Option<String>,
+    /// Only report the errors after this ID, e.g. the cursor of the previous call.
+    pub since: Option<u64>,
+    /// Forget the stored errors once reported.
+    #[serde(default)]
+    pub clear: bool,
+}
+
+// JS errors response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsErrorsResponse {
+    pub errors: Vec<crate::js_errors::JsError>,
+    /// ID of the latest error, to pass as `since` on the next call.
+    pub cursor: u64,
+    /// True when errors after `since` were dropped before they could be fetched.
+    pub missed_errors: bool,
+}
+
+// App logs request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct GetAppLogsRequest {
+    /// Only report the records after this ID, e.g. the cursor of the previous call.
+    pub since: Option<u64>,
+    /// Only report records at this level or more severe: error, warn, info, debug or trace.
+    #[schemars(with = "Option<String>")]
+    pub level: Option<log::Level>,
+    /// Only report records whose target starts with this prefix (e.g. a crate name).
+    pub target: Option<String>,
+    /// Maximum number of records returned, 500 by default.
+    pub limit: Option<usize>,
+}
+
+// App state request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct GetAppStateRequest {
+    /// States to snapshot, all the exposed ones if absent.
+    pub names: Option<Vec<String>>,
+}
+
+// App state response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppStateResponse {
+    pub states: std::collections::BTreeMap<String, Value>,
+    /// Requested states that are not exposed or failed to serialize.
+    pub errors: std::collections::BTreeMap<String, String>,
+    /// Names of all the exposed states.
+    pub exposed: Vec<String>,
+}
+
+// Store action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StoreAction {
+    /// Read the value of `key`, or every entry without a key.
+    Get,
+    Set,
+    Delete,
+    Clear,
+    /// Discard unsaved changes and read the file again.
+    Reload,
+}
+
+// Store request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ManageStoreRequest {
+    /// Store file, as given to tauri-plugin-store (relative to the app data dir).
+    pub path: String,
+    pub action: StoreAction,
+    pub key: Option<String>,
+    pub value: Option<Value>,
+    /// Write the file after a change, so it survives a restart.
+    #[serde(default = "default_true")]
+    pub save: bool,
+}
+
+// Store response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreResponse {
+    /// Resolved path of the store file.
+    pub path: String,
+    /// Value of the key, previous value for a delete (null when absent).
+    pub value: Option<Value>,
+    /// Every entry, for a get without key, a clear or a reload.
+    pub entries: Option<std::collections::BTreeMap<String, Value>>,
+}
+
+// Database query request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct QueryDbRequest {
+    /// Name of a database configured with `PluginConfig::database`.
+    pub database: String,
+    /// SQL to run; several statements can be separated by semicolons.
+    pub query: String,
+    /// Values bound to the `?` placeholders, in order.
+    #[serde(default)]
+    pub params: Vec<Value>,
+    /// Maximum number of rows returned, 1000 by default.
+    pub max_rows: Option<usize>,
+}
+
+// App file encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileEncoding {
+    #[default]
+    Utf8,
+    Base64,
+}
+
+// App file read request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ReadAppFileRequest {
+    /// Name of a root configured with `PluginConfig::file_root`.
+    pub root: String,
+    /// Path relative to the root; a directory is listed. The root itself if empty.
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub encoding: FileEncoding,
+    /// Maximum number of bytes returned, 10 MiB by default.
+    pub max_bytes: Option<u64>,
+}
+
+// App directory entry model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFileEntry {
+    pub name: String,
+    pub dir: bool,
+    pub size: u64,
+}
+
+// App file read response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppFileResponse {
+    /// Resolved path.
+    pub path: String,
+    pub size: u64,
+    pub encoding: FileEncoding,
+    /// Content of a file.
+    pub content: Option<String>,
+    /// True when the file is larger than `max_bytes`.
+    pub truncated: bool,
+    /// Entries of a directory, sorted by name.
+    pub entries: Option<Vec<AppFileEntry>>,
+}
+
+// App file write request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct WriteAppFileRequest {
+    /// Name of a root configured with `PluginConfig::file_root`.
+    pub root: String,
+    /// Path relative to the root; missing directories are created.
+    pub path: String,
+    pub content: String,
+    #[serde(default)]
+    pub encoding: FileEncoding,
+    /// Append to the file instead of replacing it.
+    #[serde(default)]
+    pub append: bool,
+}
+
+// App file write response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WriteAppFileResponse {
+    /// Resolved path.
+    pub path: String,
+    /// Size of the file after the write.
+    pub size: u64,
+}
+
+// Deep link request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TriggerDeepLinkRequest {
+    /// URL the app is opened with, e.g. `myapp://invite/abc`.
+    pub url: String,
+    /// Event to emit instead of the one of tauri-plugin-deep-link, for apps handling
+    /// links themselves.
+    pub event: Option<String>,
+}
+
+// Deep link response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerDeepLinkResponse {
+    /// Normalized URL.
+    pub url: String,
+    pub event: String,
+}
+
+// Tray menu item trigger request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TriggerTrayMenuItemRequest {
+    /// Tray whose menu holds the item, any registered tray when absent.
+    pub tray_id: Option<String>,
+    pub item_id: String,
+}
+
+// Tray click request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClickTrayRequest {
+    pub tray_id: String,
+    /// "left", "middle" or "right".
+    #[serde(default = "default_button")]
+    pub button: String,
+    /// Also send a double click event after the click.
+    #[serde(default)]
+    pub double: bool,
+}
+
+// Menu listing request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct ListMenuRequest {
+    /// Only list the menu of this window, the app menu and all windows when absent.
+    pub window_label: Option<String>,
+}
+
+// Menu listing response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MenuListResponse {
+    /// Items of the app menu, None without one.
+    pub app: Option<Vec<MenuEntry>>,
+    /// Items of the menu of each window that has its own.
+    pub windows: std::collections::BTreeMap<String, Vec<MenuEntry>>,
+}
+
+// Menu item click request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ClickMenuItemRequest {
+    /// Look in the menu of this window, the app menu then every window when absent.
+    pub window_label: Option<String>,
+    /// ID of the item.
+    pub id: Option<String>,
+    /// Texts leading to the item instead of its ID, e.g. "File > Export > PDF".
+    pub path: Option<String>,
+}
+
+// Menu item click response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClickMenuItemResponse {
+    /// "app" or the label of the window whose menu holds the item.
+    pub menu: String,
+    /// Texts leading to the item.
+    pub path: Vec<String>,
+    /// The item after the click.
+    pub item: MenuEntry,
+    /// Menu event delivered to the handlers.
+    pub event: MenuEventPayload,
+}
+
+// Menu event model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MenuEventPayload {
+    pub id: String,
+}
+
+// Action overlay request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct SetActionOverlayRequest {
+    pub enabled: bool,
+}
+
+// Session pause request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct PauseSessionRequest {
+    /// Text of the banner, "MCP agent paused — user in control" when absent.
+    pub message: Option<String>,
+}
+
+// Session resume request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct ResumeSessionRequest {
+    /// Fail the queued input commands instead of running them.
+    #[serde(default)]
+    pub discard_queued: bool,
+}
+
+// Clock advance request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct AdvanceClockRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    pub ms: u64,
+}
+
+// Interaction recording start request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StartInteractionRecordingRequest {
+    /// Only record this window, all windows when absent.
+    pub window_label: Option<String>,
+}
+
+// Interaction recording stop request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopInteractionRecordingRequest {
+    /// Also store the script as a "script" artifact.
+    #[serde(default)]
+    pub save: bool,
+    /// Artifact name, "interaction-script.json" by default.
+    pub name: Option<String>,
+}
+
+// Interaction recording stop response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopInteractionRecordingResponse {
+    /// Payload for `run_script`.
+    pub script: RunScriptRequest,
+    pub duration_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<crate::ArtifactInfo>,
+}
+
+// Trace start request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct StartTraceRequest {
+    /// Name of the bundle, "trace" by default.
+    pub name: Option<String>,
+    /// Window whose page is read around calls that name no window.
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Store the HTML of each distinct DOM in the bundle, only its hash otherwise.
+    #[serde(default = "default_true")]
+    pub include_dom: bool,
+    /// Store a JPEG of the viewport before and after each call.
+    #[serde(default = "default_true")]
+    pub include_screenshots: bool,
+}
+
+// Trace stop request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct StopTraceRequest {
+    /// Drop the trace instead of storing it.
+    #[serde(default)]
+    pub discard: bool,
+}
+
+// Trace stop response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StopTraceResponse {
+    pub manifest: crate::TraceManifest,
+    /// The zip bundle, absent when the trace was discarded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub artifact: Option<crate::ArtifactInfo>,
+}
+
+fn empty_object() -> Value {
+    Value::Object(Default::default())
+}
+
+fn default_selector_type() -> String {
+    "css".to_string()
+}
+
+fn default_max_outline_nodes() -> usize {
+    500
+}
+
+fn default_snapshot_max_width() -> u32 {
+    1024
+}
+
+fn default_snapshot_quality() -> u8 {
+    60
+}
+
+fn default_coordinate_space() -> crate::CoordinateSpace {
+    crate::CoordinateSpace::Viewport
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_window_label() -> String {
+    // Left empty, the focused window is used, see `crate::windows::resolve_window`
+    String::new()
+}
+
 // Window manager request model
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct WindowManagerRequest {
     pub window_label: Option<String>,
     pub operation: String,
@@ -168,15 +1653,57 @@ pub struct WindowManagerRequest {
 pub struct WindowManagerResponse {
     pub success: bool,
     pub error: Option<String>,
+    /// Why the operation was skipped, in headless mode.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+}
+
+/// How the input tools deliver their input.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum InputMode {
+    /// Real keyboard and mouse events from the OS, to whatever window has the focus.
+    #[default]
+    Os,
+    /// Synthetic events dispatched in the webview, which works in background windows and
+    /// without a display but is not trusted by the page.
+    Dom,
 }
 
 // TextInput request model
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TextInputRequest {
     pub text: String,
     pub delay_ms: Option<u64>,
     pub initial_delay_ms: Option<u64>,
+    #[serde(default, alias = "input_mode")]
+    pub input_mode: InputMode,
+    #[serde(default)]
+    pub strategy: TextInputStrategy,
+    /// Window typed into: its page in DOM mode, the window to focus in OS mode.
+    #[serde(default = "default_window_label", alias = "window_label")]
+    pub window_label: String,
+    /// Focus the window before typing with the OS keyboard, which otherwise types into
+    /// whatever app has the focus.
+    #[serde(default, alias = "focus_window")]
+    pub focus_window: bool,
+    /// Give the focus back to the window that had it once typed. Windows of other apps are
+    /// only known on Windows.
+    #[serde(default, alias = "restore_focus")]
+    pub restore_focus: bool,
+}
+
+/// How `simulate_text_input` enters its text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TextInputStrategy {
+    /// Type one grapheme at a time, paced by `delay_ms`.
+    #[default]
+    Type,
+    /// Paste the whole text at once, which keyboard layouts and input methods cannot
+    /// mangle. In OS mode it goes through the system clipboard, whose content is put back.
+    Paste,
 }
 
 // TextInput response model
@@ -185,10 +1712,23 @@ pub struct TextInputRequest {
 pub struct TextInputResponse {
     pub chars_typed: u32,
     pub duration_ms: u64,
+    /// Graphemes that could not be typed; the rest of the text was.
+    #[serde(default)]
+    pub failures: Vec<TextInputFailure>,
+}
+
+// Text input failure model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextInputFailure {
+    /// Position of the grapheme in the text, counted in graphemes.
+    pub index: u32,
+    pub text: String,
+    pub error: String,
 }
 
 // Mouse movement request model
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MouseMovementRequest {
     pub x: i32,
@@ -196,6 +1736,47 @@ pub struct MouseMovementRequest {
     pub relative: Option<bool>,
     pub click: Option<bool>,
     pub button: Option<String>, // "left", "right", or "middle"
+    /// In DOM mode, positions are still taken from the window's outer corner, relative
+    /// moves are in CSS pixels.
+    #[serde(default, alias = "input_mode")]
+    pub input_mode: InputMode,
+    /// Window the positions are taken from, the focused one by default.
+    #[serde(default = "default_window_label", alias = "window_label")]
+    pub window_label: String,
+}
+
+// Coordinate conversion request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct ConvertCoordinatesRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    pub x: f64,
+    pub y: f64,
+    /// Space the point is given in, "viewport" by default.
+    #[serde(default = "default_coordinate_space")]
+    pub from: crate::CoordinateSpace,
+}
+
+// Coordinate point model
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+pub struct CoordinatePoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+// Coordinate conversion response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConvertCoordinatesResponse {
+    pub geometry: crate::WindowGeometry,
+    pub viewport: CoordinatePoint,
+    /// None when the page did not report its scroll offset.
+    pub document: Option<CoordinatePoint>,
+    pub window: CoordinatePoint,
+    pub logical: CoordinatePoint,
+    pub physical: CoordinatePoint,
+    /// Where the OS mouse goes for the point: logical on macOS, physical elsewhere.
+    pub os_input: CoordinatePoint,
 }
 
 // Mouse movement response model
@@ -206,3 +1787,263 @@ pub struct MouseMovementResponse {
     pub duration_ms: u64,
     pub position: Option<(i32, i32)>,
 }
+
+// Key press request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct KeyPressRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    /// Keys by name, such as "ctrl", "enter", "f5" or "a", which the keyboard layout maps
+    /// to keys.
+    #[serde(default)]
+    pub keys: Vec<String>,
+    /// Physical keys by W3C `code` value, such as "ControlLeft" or "KeyS", the same keys
+    /// whatever the keyboard layout. Used instead of `keys`.
+    #[serde(default)]
+    pub codes: Vec<String>,
+    #[serde(default)]
+    pub input_mode: InputMode,
+    /// Focus the window before pressing with the OS keyboard.
+    #[serde(default)]
+    pub focus_window: bool,
+}
+
+// Key press response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPressResponse {
+    /// Keys pressed in order, then released in reverse.
+    pub keys: Vec<crate::platform::input::Key>,
+    pub duration_ms: u64,
+}
+
+// Touch request model
+#[derive(Debug, Clone, Deserialize, JsonSchema, Serialize)]
+pub struct TouchRequest {
+    #[serde(default = "default_window_label")]
+    pub window_label: String,
+    pub gesture: TouchGesture,
+    /// Element whose center the gesture starts on, one of "id", "class", "tag", "text",
+    /// "css", "xpath", "aria-label", "role" or "label". Used instead of `x` and `y`.
+    pub selector_type: Option<String>,
+    pub selector_value: Option<String>,
+    /// Point the gesture starts on, in viewport CSS pixels.
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    /// Distance a swipe travels, in CSS pixels.
+    #[serde(default)]
+    pub dx: f64,
+    #[serde(default)]
+    pub dy: f64,
+    /// Speed of a swipe in CSS pixels per millisecond, used instead of `duration_ms`.
+    pub velocity: Option<f64>,
+    /// Final distance between the two fingers of a pinch over the initial one: below 1
+    /// pinches in, above 1 spreads out.
+    pub scale: Option<f64>,
+    /// Initial distance between the two fingers of a pinch, in CSS pixels.
+    pub spread: Option<f64>,
+    /// How long the fingers stay down. Defaults to 50 ms for a tap, 600 ms for a long
+    /// press, 300 ms for a swipe and 400 ms for a pinch.
+    pub duration_ms: Option<u64>,
+}
+
+/// A touch gesture of `simulate_touch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TouchGesture {
+    Tap,
+    LongPress,
+    Swipe,
+    Pinch,
+}
+
+// Touch response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchResponse {
+    pub gesture: TouchGesture,
+    /// Tag and id of the element the fingers went down on.
+    pub target: Value,
+    /// Point the gesture started on, in viewport CSS pixels.
+    pub origin: CoordinatePoint,
+    /// Number of pointer and touch events dispatched.
+    pub events: u32,
+    /// Whether the webview has touch events; without them only pointer events are sent.
+    pub touch_events: bool,
+    pub duration_ms: u64,
+}
+
+// Server mode response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerModeResponse {
+    pub mode: crate::server_mode::ServerMode,
+    pub debug_build: bool,
+    pub allow_in_release: bool,
+    pub env_opt_in: bool,
+    pub socket_server_running: bool,
+}
+
+// Artifact request models
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListArtifactsRequest {
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactRequest {
+    pub id: String,
+}
+
+// Artifact content response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtifactContentResponse {
+    pub id: String,
+    pub size: u64,
+    pub content_base64: String,
+}
+
+// Audit log request model
+#[derive(Debug, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogRequest {
+    pub limit: Option<usize>,
+    pub command: Option<String>,
+}
+
+// Audit log response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogResponse {
+    pub entries: Vec<crate::audit::AuditEntry>,
+    pub artifact_id: Option<String>,
+}
+
+// Describe tools request model
+#[derive(Debug, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescribeToolsRequest {
+    pub include_unavailable: Option<bool>,
+}
+
+// Describe tools response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DescribeToolsResponse {
+    pub platform: String,
+    pub headless: crate::headless::HeadlessMode,
+    pub capabilities: Vec<crate::capabilities::CapabilityReport>,
+    pub tools: Vec<crate::capabilities::ToolDescription>,
+}
+
+// Poll events request model
+#[derive(Debug, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PollEventsRequest {
+    pub session_id: Option<String>,
+    pub cursor: Option<u64>,
+    pub timeout_ms: Option<u64>,
+    pub max_events: Option<usize>,
+    pub kinds: Option<Vec<String>>,
+}
+
+// Recover last session response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoverSessionResponse {
+    pub found: bool,
+    #[serde(flatten)]
+    pub session: Option<crate::recording::RecoveredSession>,
+}
+
+// Cancel request model
+#[derive(Debug, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelRequest {
+    pub request_id: String,
+}
+
+// Cancel response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelResponse {
+    /// False when no request with this ID was in flight.
+    pub cancelled: bool,
+}
+
+// Dialog interception request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetDialogInterceptionRequest {
+    pub enabled: Option<bool>,
+    /// Answers given to the next dialogs, in order, replacing the queued ones.
+    pub auto_responses: Option<Vec<Value>>,
+}
+
+// Dialog answer request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RespondToDialogRequest {
+    /// Dialog to answer, the oldest pending one if absent.
+    pub dialog_id: Option<String>,
+    /// Selected path(s), button or boolean; null cancels.
+    pub value: Option<Value>,
+}
+
+// Dialog interception response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DialogsResponse {
+    pub enabled: bool,
+    pub pending: Vec<crate::dialogs::PendingDialog>,
+    /// Policy answering alert, confirm and prompt dialogs, if set.
+    pub policy: Option<crate::dialogs::DialogPolicy>,
+    /// Dialogs answered by the policy, oldest first.
+    pub answered: Vec<crate::dialogs::AnsweredDialog>,
+}
+
+// Dialog policy request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct SetDialogPolicyRequest {
+    /// Policy to apply, null to show dialogs normally again.
+    pub policy: Option<crate::dialogs::DialogPolicy>,
+}
+
+// Permission policy request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct SetPermissionPolicyRequest {
+    /// Policy to apply, the current one is kept when absent.
+    pub policy: Option<crate::permissions::PermissionPolicy>,
+    /// Remove the policy, so the webview decides again.
+    #[serde(default)]
+    pub clear: bool,
+    /// Forget the recorded requests once reported.
+    #[serde(default)]
+    pub clear_requests: bool,
+}
+
+// Permission policy response model
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PermissionsResponse {
+    pub policy: Option<crate::permissions::PermissionPolicy>,
+    /// Permissions requested by the pages, oldest first.
+    pub requests: Vec<crate::permissions::RequestedPermission>,
+}
+
+// Client configuration request model
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct GenerateClientConfigRequest {
+    /// Client to configure, Claude Desktop by default.
+    #[serde(default)]
+    pub client: crate::client_config::McpClient,
+    /// Server the client starts, detected if absent.
+    pub server: Option<crate::client_config::McpServerLauncher>,
+    /// Path of `mcp-server-ts/build/index.js` for Node, or of the `tauri-mcp-server` binary.
+    pub server_path: Option<String>,
+    /// Name of the server entry, `tauri-mcp` by default.
+    pub server_name: Option<String>,
+}
@@ -0,0 +1,196 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
+
+use crate::audit::now_ms;
+
+/// Event emitted by the webview hook for each in-page navigation.
+pub(crate) const NAVIGATION_EVENT: &str = "tauri-mcp:navigation";
+
+/// Number of navigations kept; the oldest are dropped first.
+const CAPACITY: usize = 200;
+
+/// Injected in every webview. Reports the URL changes of client-side routers, made with
+/// `history.pushState` and `replaceState` or by going back, forward or to an anchor.
+/// Changes leaving the URL as it was are not reported.
+const NAVIGATION_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_NAVIGATION__) return;
+    window.__TAURI_MCP_NAVIGATION__ = true;
+    let last = location.href;
+    const report = (kind) => {
+        if (location.href === last) return;
+        last = location.href;
+        const internals = window.__TAURI_INTERNALS__;
+        if (!internals) return;
+        const navigation = {
+            windowLabel: internals.metadata.currentWindow.label,
+            kind,
+            url: location.href,
+            title: document.title,
+        };
+        internals.invoke('plugin:event|emit', { event: '__EVENT__', payload: navigation }).catch(() => {});
+    };
+    for (const method of ['pushState', 'replaceState']) {
+        const original = history[method];
+        history[method] = function () {
+            const result = original.apply(this, arguments);
+            report(method);
+            return result;
+        };
+    }
+    // Going to an anchor fires popstate then hashchange, the URL check keeps one of them
+    window.addEventListener('popstate', () => report('popState'));
+    window.addEventListener('hashchange', () => report('hashChange'));
+})();
+"#;
+
+/// How a page got to its URL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NavigationKind {
+    /// A page started loading.
+    LoadStarted,
+    /// A page finished loading.
+    LoadFinished,
+    /// `history.pushState` changed the URL.
+    PushState,
+    /// `history.replaceState` changed the URL.
+    ReplaceState,
+    /// Back or forward within the page's history.
+    PopState,
+    /// The fragment of the URL changed.
+    HashChange,
+}
+
+impl NavigationKind {
+    /// Whether the page is ready at the navigation's URL: page loads count once finished.
+    pub fn is_complete(self) -> bool {
+        self != NavigationKind::LoadStarted
+    }
+}
+
+/// A page load or client-side route change of a webview.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Navigation {
+    /// Sequence number, used as the `after_seq` cursor.
+    #[serde(default)]
+    pub seq: u64,
+    #[serde(default)]
+    pub timestamp_ms: u64,
+    pub window_label: String,
+    pub kind: NavigationKind,
+    pub url: String,
+    /// Document title at the time, for in-page navigations.
+    #[serde(default)]
+    pub title: Option<String>,
+}
+
+struct Inner {
+    navigations: VecDeque<Navigation>,
+    next_seq: u64,
+}
+
+/// Keeps the latest page loads and client-side route changes of the webviews, and wakes
+/// up the tools waiting for one.
+pub struct NavigationLog {
+    inner: Mutex<Inner>,
+    notify: Notify,
+}
+
+impl NavigationLog {
+    pub(crate) fn new() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                navigations: VecDeque::new(),
+                next_seq: 1,
+            }),
+            notify: Notify::new(),
+        }
+    }
+
+    /// Script reporting the in-page navigations of a webview.
+    pub(crate) fn init_script() -> String {
+        NAVIGATION_HOOK_SCRIPT.replace("__EVENT__", NAVIGATION_EVENT)
+    }
+
+    /// Store a navigation and return it with its sequence number.
+    pub(crate) fn record(&self, mut navigation: Navigation) -> Navigation {
+        {
+            let mut inner = self.inner.lock().unwrap();
+            navigation.seq = inner.next_seq;
+            navigation.timestamp_ms = now_ms();
+            inner.next_seq += 1;
+            while inner.navigations.len() >= CAPACITY {
+                inner.navigations.pop_front();
+            }
+            inner.navigations.push_back(navigation.clone());
+        }
+        self.notify.notify_waiters();
+        navigation
+    }
+
+    /// Navigations after the `since` sequence number, oldest first, optionally of one
+    /// window only.
+    pub fn navigations(&self, since: u64, window_label: Option<&str>) -> Vec<Navigation> {
+        self.inner
+            .lock()
+            .unwrap()
+            .navigations
+            .iter()
+            .filter(|navigation| navigation.seq > since)
+            .filter(|navigation| window_label.is_none_or(|label| label == navigation.window_label))
+            .cloned()
+            .collect()
+    }
+
+    /// Most recent navigation of a window.
+    pub fn latest(&self, window_label: &str) -> Option<Navigation> {
+        self.inner
+            .lock()
+            .unwrap()
+            .navigations
+            .iter()
+            .rev()
+            .find(|navigation| navigation.window_label == window_label)
+            .cloned()
+    }
+
+    /// Sequence number of the most recent navigation (0 if none was recorded yet).
+    pub fn latest_seq(&self) -> u64 {
+        self.inner.lock().unwrap().next_seq - 1
+    }
+
+    /// Wait up to `timeout` for the first navigation after `since` accepted by `filter`.
+    pub async fn wait_for(
+        &self,
+        since: u64,
+        filter: impl Fn(&Navigation) -> bool,
+        timeout: Duration,
+    ) -> Option<Navigation> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            // Register interest before checking so a record in between is not missed
+            let notified = self.notify.notified();
+            let found = self
+                .inner
+                .lock()
+                .unwrap()
+                .navigations
+                .iter()
+                .find(|navigation| navigation.seq > since && filter(navigation))
+                .cloned();
+            if found.is_some() {
+                return found;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            let _ = tokio::time::timeout(deadline - now, notified).await;
+        }
+    }
+}
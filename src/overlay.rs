@@ -0,0 +1,250 @@
+use log::{info, warn};
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::coordinates::{CoordinateSpace, WindowGeometry};
+use crate::shared::commands;
+use crate::tools::selector::FIND_ELEMENT_JS;
+
+/// Time given to the cursor to reach its target before the action runs.
+const ACTION_LEAD: Duration = Duration::from_millis(250);
+
+/// Injected in every webview. Draws a ghost cursor and a short-lived outline with the
+/// action's name over the elements tools act on, in a closed shadow root that lets
+/// clicks and `elementFromPoint` through.
+const OVERLAY_HOOK_SCRIPT: &str = r##"
+(() => {
+    if (window.__TAURI_MCP_OVERLAY__) return;
+    __FIND_ELEMENT__
+    let layer = null;
+    let root = null;
+    let cursor = null;
+    const position = { x: 24, y: 24 };
+    const create = () => {
+        layer = document.createElement('div');
+        layer.setAttribute('data-tauri-mcp-overlay', '');
+        root = layer.attachShadow({ mode: 'closed' });
+        root.innerHTML = `
+            <style>
+                :host { all: initial; position: fixed; inset: 0; z-index: 2147483646; pointer-events: none; }
+                .cursor { position: fixed; left: 0; top: 0; width: 22px; height: 22px;
+                    transition: transform 200ms ease-out; filter: drop-shadow(0 1px 2px rgba(0, 0, 0, 0.5)); }
+                .cursor.pressed { scale: 0.85; }
+                .box { position: fixed; box-sizing: border-box; border: 2px solid #e11d48; border-radius: 3px;
+                    background: rgba(225, 29, 72, 0.12); transition: opacity 400ms ease-in; }
+                .box span { position: absolute; left: -2px; bottom: 100%; margin-bottom: 2px; padding: 1px 6px;
+                    border-radius: 3px; background: #e11d48; color: #fff; font: 600 11px/1.5 system-ui, sans-serif;
+                    white-space: nowrap; }
+            </style>
+            <svg class="cursor" viewBox="0 0 22 22"><path d="M3 2 L3 18 L7.5 14 L10.5 20.5 L13 19.3 L10 13 L16 13 Z"
+                fill="#e11d48" stroke="#fff" stroke-width="1.5" stroke-linejoin="round"/></svg>`;
+        cursor = root.querySelector('.cursor');
+        cursor.style.transform = `translate(${position.x}px, ${position.y}px)`;
+    };
+    const mount = () => {
+        if (!layer) create();
+        if (!layer.isConnected) document.documentElement.appendChild(layer);
+    };
+    const moveTo = (x, y, press) => {
+        position.x = x;
+        position.y = y;
+        cursor.style.transform = `translate(${x}px, ${y}px)`;
+        if (press) {
+            setTimeout(() => cursor.classList.add('pressed'), 200);
+            setTimeout(() => cursor.classList.remove('pressed'), 400);
+        }
+    };
+    const outline = (element, label) => {
+        const rect = element.getBoundingClientRect();
+        const box = document.createElement('div');
+        box.className = 'box';
+        Object.assign(box.style, {
+            left: `${rect.left - 3}px`,
+            top: `${rect.top - 3}px`,
+            width: `${rect.width + 6}px`,
+            height: `${rect.height + 6}px`,
+        });
+        const text = document.createElement('span');
+        text.textContent = label;
+        box.appendChild(text);
+        root.appendChild(box);
+        setTimeout(() => { box.style.opacity = '0'; }, 900);
+        setTimeout(() => box.remove(), 1400);
+        return rect;
+    };
+    const find = (target) => {
+        try {
+            return findElement(target.selector_type || 'css', target.selector_value);
+        } catch (_) {
+            return null;
+        }
+    };
+    window.__TAURI_MCP_OVERLAY__ = {
+        show(action) {
+            if (!document.documentElement) return;
+            mount();
+            const elements = action.targets.map(find).filter(Boolean);
+            if (action.focused && document.activeElement && document.activeElement !== document.body) {
+                elements.push(document.activeElement);
+            }
+            elements.forEach((element, i) => {
+                const rect = outline(element, action.label);
+                if (i === 0) moveTo(rect.left + rect.width / 2, rect.top + rect.height / 2, action.press);
+            });
+            if (action.point) moveTo(action.point.x, action.point.y, action.press);
+            if (action.delta) moveTo(position.x + action.delta.x, position.y + action.delta.y, action.press);
+        },
+    };
+})();
+"##;
+
+/// Shows what tools do in the webview, for people watching an agent-driven session or its
+/// recording: a ghost cursor moves to the target of each action, which is outlined with
+/// the action's name for a moment before the action runs.
+pub struct ActionOverlay {
+    enabled: AtomicBool,
+}
+
+impl ActionOverlay {
+    pub(crate) fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+        }
+    }
+
+    /// Script drawing the overlay in a webview, idle until an action is shown.
+    pub(crate) fn init_script() -> String {
+        OVERLAY_HOOK_SCRIPT.replace("__FIND_ELEMENT__", FIND_ELEMENT_JS)
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::SeqCst)
+    }
+
+    /// Show the actions of every following command, or stop showing them.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::SeqCst);
+        info!(
+            "[TAURI_MCP] Action overlay {}",
+            if enabled { "enabled" } else { "disabled" }
+        );
+    }
+
+    /// Show the action of a command about to run, when the overlay is enabled or the
+    /// request sets `show_action`, and give the cursor time to reach its target.
+    pub(crate) async fn show<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        command: &str,
+        payload: &Value,
+    ) {
+        let show = payload
+            .get("show_action")
+            .and_then(Value::as_bool)
+            .unwrap_or_else(|| self.is_enabled());
+        if !show {
+            return;
+        }
+        let Some(action) = describe(app, command, payload) else {
+            return;
+        };
+        let label = payload
+            .get("window_label")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let Ok(window) = crate::windows::resolve_window(app, label) else {
+            return;
+        };
+        let script = format!(
+            "window.__TAURI_MCP_OVERLAY__ && window.__TAURI_MCP_OVERLAY__.show({});",
+            action
+        );
+        if let Err(e) = window.eval(&script) {
+            warn!(
+                "[TAURI_MCP] Failed to show action in {}: {}",
+                window.label(),
+                e
+            );
+            return;
+        }
+        tokio::time::sleep(ACTION_LEAD).await;
+    }
+}
+
+/// What the overlay shows for a command, None for commands without a visible target.
+fn describe<R: Runtime>(app: &AppHandle<R>, command: &str, payload: &Value) -> Option<Value> {
+    let target = || {
+        payload.get("selector_value").map(|value| {
+            json!({
+                "selector_type": payload.get("selector_type").cloned().unwrap_or(Value::Null),
+                "selector_value": value,
+            })
+        })
+    };
+    let single = |label: &str, press: bool| {
+        Some(
+            json!({ "label": label, "press": press, "targets": target().into_iter().collect::<Vec<_>>() }),
+        )
+    };
+    match command {
+        commands::CLICK_ELEMENT => single("click", true),
+        commands::SET_ELEMENT_VALUE => single("set value", true),
+        commands::SET_FILE_INPUT => single("upload", false),
+        commands::FOCUS_ELEMENT => single("focus", false),
+        commands::SEND_TEXT_TO_ELEMENT => single("type", false),
+        commands::BLUR_ELEMENT => Some(json!({
+            "label": "blur",
+            "targets": target().into_iter().collect::<Vec<_>>(),
+            "focused": payload.get("selector_value").is_none(),
+        })),
+        commands::FILL_FORM => Some(json!({
+            "label": "fill",
+            "targets": payload.get("fields").cloned().unwrap_or_else(|| json!([])),
+        })),
+        commands::SIMULATE_TEXT_INPUT => {
+            Some(json!({ "label": "type", "targets": [], "focused": true }))
+        }
+        commands::SIMULATE_KEY_PRESS => {
+            Some(json!({ "label": "keys", "targets": [], "focused": true }))
+        }
+        commands::SIMULATE_TOUCH => {
+            let label = payload
+                .get("gesture")
+                .and_then(Value::as_str)
+                .unwrap_or("tap")
+                .replace('_', " ");
+            if payload.get("selector_value").is_some() {
+                return single(&label, true);
+            }
+            let x = payload.get("x")?.as_f64()?;
+            let y = payload.get("y")?.as_f64()?;
+            Some(
+                json!({ "label": label, "targets": [], "point": { "x": x, "y": y }, "press": true }),
+            )
+        }
+        commands::SIMULATE_MOUSE_MOVEMENT => {
+            let x = payload.get("x")?.as_f64()?;
+            let y = payload.get("y")?.as_f64()?;
+            let press = payload.get("click").and_then(Value::as_bool) == Some(true);
+            if payload.get("relative").and_then(Value::as_bool) == Some(true) {
+                return Some(json!({ "targets": [], "delta": { "x": x, "y": y }, "press": press }));
+            }
+            // The tool takes positions from the window's outer corner, the page starts
+            // below the decorations
+            let label = payload
+                .get("window_label")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let window = crate::windows::resolve_window(app, label).ok()?;
+            let (x, y) = WindowGeometry::of(&window).ok()?.convert(
+                CoordinateSpace::Window,
+                CoordinateSpace::Viewport,
+                (x, y),
+            );
+            Some(json!({ "targets": [], "point": { "x": x, "y": y }, "press": press }))
+        }
+        _ => None,
+    }
+}
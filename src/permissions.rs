@@ -0,0 +1,452 @@
+use log::{info, warn};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::audit::now_ms;
+
+/// Event emitted by the webview hook when the page asks for a permission.
+pub(crate) const PERMISSION_REQUESTED_EVENT: &str = "tauri-mcp:permission-requested";
+
+/// Event emitted by the webview hook to read the clipboard through the plugin.
+pub(crate) const CLIPBOARD_READ_EVENT: &str = "tauri-mcp:clipboard-read";
+
+/// Permissions a policy can answer.
+pub(crate) const PERMISSIONS: &[&str] = &[
+    "camera",
+    "microphone",
+    "geolocation",
+    "notifications",
+    "clipboard-read",
+    "clipboard-write",
+];
+
+/// Number of permission requests kept; the oldest are dropped first.
+const CAPACITY: usize = 200;
+
+/// Injected in every webview, after the emulation hook so it wraps the emulated APIs.
+/// Reports the permissions the page asks for with `getUserMedia`, `Notification`,
+/// `geolocation` and `clipboard`, and answers them while a policy is set: denied ones fail
+/// at once with a `NotAllowedError`, granted clipboard reads get the system clipboard
+/// through the plugin, and the other granted ones go on to the webview, which the plugin
+/// allows on Linux. The policy is kept in the session storage to survive reloads.
+const PERMISSION_HOOK_SCRIPT: &str = r#"
+(() => {
+    if (window.__TAURI_MCP_PERMISSIONS__) return;
+    const POLICY_KEY = '__tauri_mcp_permission_policy__';
+    const state = { policy: null, pending: new Map(), next: 0 };
+    try {
+        state.policy = JSON.parse(sessionStorage.getItem(POLICY_KEY) || 'null');
+    } catch (_) {}
+    state.setPolicy = (policy) => {
+        state.policy = policy;
+        try {
+            if (policy) sessionStorage.setItem(POLICY_KEY, JSON.stringify(policy));
+            else sessionStorage.removeItem(POLICY_KEY);
+        } catch (_) {}
+    };
+    state.resolveClipboard = (id, text, error) => {
+        const pending = state.pending.get(id);
+        if (!pending) return;
+        state.pending.delete(id);
+        if (error == null) pending.resolve(text);
+        else pending.reject(new DOMException(error, 'NotAllowedError'));
+    };
+    window.__TAURI_MCP_PERMISSIONS__ = state;
+
+    const decision = (permission) => {
+        const policy = state.policy;
+        if (!policy) return null;
+        return (policy.permissions || {})[permission] || policy.default || null;
+    };
+    const denied = () => new DOMException('Permission denied', 'NotAllowedError');
+    // Reports the permissions of a request and answers it: 'grant' when all are granted,
+    // 'deny' when one is denied, null to let the webview decide
+    const request = (permissions) => {
+        const decisions = permissions.map(decision);
+        const internals = window.__TAURI_INTERNALS__;
+        if (internals) {
+            permissions.forEach((permission, i) => {
+                const payload = {
+                    windowLabel: internals.metadata.currentWindow.label,
+                    permission,
+                    url: location.href,
+                    outcome: decisions[i] === 'grant' ? 'granted' : decisions[i] === 'deny' ? 'denied' : 'prompted',
+                };
+                internals.invoke('plugin:event|emit', { event: '__EVENT__', payload }).catch(() => {});
+            });
+        }
+        if (decisions.includes('deny')) return 'deny';
+        return decisions.every((d) => d === 'grant') ? 'grant' : null;
+    };
+
+    const media = navigator.mediaDevices;
+    if (media && typeof media.getUserMedia === 'function') {
+        const getUserMedia = media.getUserMedia.bind(media);
+        media.getUserMedia = (constraints) => {
+            const wanted = [];
+            if (constraints && constraints.video) wanted.push('camera');
+            if (constraints && constraints.audio) wanted.push('microphone');
+            if (wanted.length && request(wanted) === 'deny') return Promise.reject(denied());
+            return getUserMedia(constraints);
+        };
+    }
+
+    if (window.Notification && typeof Notification.requestPermission === 'function') {
+        const requestPermission = Notification.requestPermission.bind(Notification);
+        Notification.requestPermission = (callback) => {
+            const outcome = request(['notifications']);
+            if (!outcome) return requestPermission(callback);
+            const result = outcome === 'grant' ? 'granted' : 'denied';
+            if (typeof callback === 'function') callback(result);
+            return Promise.resolve(result);
+        };
+        const descriptor = Object.getOwnPropertyDescriptor(Notification, 'permission');
+        if (descriptor && descriptor.get && descriptor.configurable) {
+            Object.defineProperty(Notification, 'permission', {
+                configurable: true,
+                get() {
+                    const d = decision('notifications');
+                    return d ? (d === 'grant' ? 'granted' : 'denied') : descriptor.get.call(this);
+                },
+            });
+        }
+    }
+
+    const geolocation = navigator.geolocation;
+    if (geolocation) {
+        for (const method of ['getCurrentPosition', 'watchPosition']) {
+            const original = geolocation[method];
+            if (typeof original !== 'function') continue;
+            geolocation[method] = function (success, error) {
+                if (request(['geolocation']) !== 'deny') return original.apply(this, arguments);
+                if (typeof error === 'function') {
+                    setTimeout(() => error({ code: 1, message: 'User denied Geolocation', PERMISSION_DENIED: 1, POSITION_UNAVAILABLE: 2, TIMEOUT: 3 }));
+                }
+                return method === 'watchPosition' ? 0 : undefined;
+            };
+        }
+    }
+
+    const clipboard = navigator.clipboard;
+    if (clipboard) {
+        const readSystemClipboard = () => new Promise((resolve, reject) => {
+            const internals = window.__TAURI_INTERNALS__;
+            const id = ++state.next;
+            state.pending.set(id, { resolve, reject });
+            const payload = { id, windowLabel: internals.metadata.currentWindow.label };
+            internals.invoke('plugin:event|emit', { event: '__CLIPBOARD_EVENT__', payload }).catch((e) => {
+                state.pending.delete(id);
+                reject(e);
+            });
+        });
+        const wrap = (method, permission, grant) => {
+            const original = clipboard[method];
+            if (typeof original !== 'function') return;
+            clipboard[method] = function () {
+                const outcome = request([permission]);
+                if (outcome === 'deny') return Promise.reject(denied());
+                if (outcome === 'grant' && grant && window.__TAURI_INTERNALS__) return grant();
+                return original.apply(clipboard, arguments);
+            };
+        };
+        wrap('readText', 'clipboard-read', readSystemClipboard);
+        wrap('read', 'clipboard-read', typeof ClipboardItem === 'function'
+            ? () => readSystemClipboard().then((text) => [new ClipboardItem({ 'text/plain': new Blob([text], { type: 'text/plain' }) })])
+            : null);
+        wrap('writeText', 'clipboard-write', null);
+        wrap('write', 'clipboard-write', null);
+    }
+
+    const permissions = navigator.permissions;
+    if (permissions && typeof permissions.query === 'function') {
+        const query = permissions.query;
+        permissions.query = function (descriptor) {
+            const name = descriptor && descriptor.name;
+            const d = name ? decision(name) : null;
+            if (!d) return query.apply(permissions, arguments);
+            return Promise.resolve({ name, state: d === 'grant' ? 'granted' : 'denied', onchange: null, addEventListener() {}, removeEventListener() {} });
+        };
+    }
+})();
+"#;
+
+/// Answer of a permission request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PermissionDecision {
+    Grant,
+    Deny,
+}
+
+/// How the permission requests of the webviews are answered, instead of prompting.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+pub struct PermissionPolicy {
+    /// Decisions by permission: "camera", "microphone", "geolocation", "notifications",
+    /// "clipboard-read" or "clipboard-write".
+    #[serde(default)]
+    pub permissions: BTreeMap<String, PermissionDecision>,
+    /// Decision for the permissions not listed, None lets the webview decide.
+    #[serde(default)]
+    pub default: Option<PermissionDecision>,
+}
+
+impl PermissionPolicy {
+    /// Decision for a permission, None when the webview decides.
+    pub fn decision(&self, permission: &str) -> Option<PermissionDecision> {
+        self.permissions.get(permission).copied().or(self.default)
+    }
+}
+
+/// What became of a permission request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PermissionOutcome {
+    /// Granted by the policy.
+    Granted,
+    /// Denied by the policy.
+    Denied,
+    /// Left to the webview, which may prompt the user.
+    Prompted,
+}
+
+/// A permission a page asked for.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestedPermission {
+    pub window_label: String,
+    pub permission: String,
+    pub url: String,
+    pub outcome: PermissionOutcome,
+    #[serde(default)]
+    pub requested_ms: u64,
+}
+
+#[derive(Default)]
+struct PermissionState {
+    policy: Option<PermissionPolicy>,
+    requests: VecDeque<RequestedPermission>,
+}
+
+/// Answers the permission requests of the webviews (camera, microphone, geolocation,
+/// notifications, clipboard) according to a [`PermissionPolicy`], so flows asking for them
+/// do not stall on a prompt, and records which permissions were requested.
+pub struct PermissionGate {
+    state: Mutex<PermissionState>,
+}
+
+impl PermissionGate {
+    pub(crate) fn new() -> Self {
+        Self {
+            state: Mutex::new(PermissionState::default()),
+        }
+    }
+
+    pub(crate) fn init_script() -> String {
+        PERMISSION_HOOK_SCRIPT
+            .replace("__CLIPBOARD_EVENT__", CLIPBOARD_READ_EVENT)
+            .replace("__EVENT__", PERMISSION_REQUESTED_EVENT)
+    }
+
+    /// The policy answering permission requests, None when the webview decides.
+    pub fn policy(&self) -> Option<PermissionPolicy> {
+        self.state.lock().unwrap().policy.clone()
+    }
+
+    /// Decision of the policy for a permission, None when the webview decides.
+    pub fn decision(&self, permission: &str) -> Option<PermissionDecision> {
+        self.state
+            .lock()
+            .unwrap()
+            .policy
+            .as_ref()
+            .and_then(|policy| policy.decision(permission))
+    }
+
+    /// Set or remove the permission policy in every webview.
+    pub fn set_policy<R: Runtime>(&self, app: &AppHandle<R>, policy: Option<PermissionPolicy>) {
+        let enabled = policy.is_some();
+        self.state.lock().unwrap().policy = policy;
+        for (label, window) in app.webview_windows() {
+            if let Err(e) = window.eval(self.sync_script()) {
+                warn!(
+                    "[TAURI_MCP] Failed to update the permission policy in {}: {}",
+                    label, e
+                );
+            }
+        }
+        info!(
+            "[TAURI_MCP] Permission policy {}",
+            if enabled { "set" } else { "removed" }
+        );
+    }
+
+    /// Apply the current policy to a webview, e.g. after a page load.
+    pub(crate) fn sync<R: Runtime>(&self, webview: &tauri::Webview<R>) {
+        if let Err(e) = webview.eval(self.sync_script()) {
+            warn!(
+                "[TAURI_MCP] Failed to update the permission policy in {}: {}",
+                webview.label(),
+                e
+            );
+        }
+    }
+
+    fn sync_script(&self) -> String {
+        let policy = serde_json::to_string(&self.policy()).unwrap_or_else(|_| "null".into());
+        format!(
+            "if (window.__TAURI_MCP_PERMISSIONS__) {{ window.__TAURI_MCP_PERMISSIONS__.setPolicy({}); }}",
+            policy
+        )
+    }
+
+    /// Permissions requested by the pages, oldest first.
+    pub fn requests(&self) -> Vec<RequestedPermission> {
+        self.state
+            .lock()
+            .unwrap()
+            .requests
+            .iter()
+            .cloned()
+            .collect()
+    }
+
+    /// Forget the recorded requests.
+    pub fn clear_requests(&self) {
+        self.state.lock().unwrap().requests.clear();
+    }
+
+    /// Record a permission request reported by a webview.
+    pub(crate) fn record(&self, mut request: RequestedPermission) -> RequestedPermission {
+        request.requested_ms = now_ms();
+        let mut state = self.state.lock().unwrap();
+        while state.requests.len() >= CAPACITY {
+            state.requests.pop_front();
+        }
+        state.requests.push_back(request.clone());
+        request
+    }
+
+    /// Answer a clipboard read of a page with the system clipboard, if the policy grants it.
+    pub(crate) fn read_clipboard<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        window_label: &str,
+        id: u64,
+    ) {
+        let Some(window) = app.get_webview_window(window_label) else {
+            return;
+        };
+        if self.decision("clipboard-read") != Some(PermissionDecision::Grant) {
+            answer_clipboard(
+                &window,
+                id,
+                Err("Clipboard reads are not granted".to_string()),
+            );
+            return;
+        }
+        std::thread::spawn(move || {
+            let text = crate::platform::clipboard::read_text().map_err(|e| e.to_string());
+            answer_clipboard(&window, id, text);
+        });
+    }
+}
+
+fn answer_clipboard<R: Runtime>(
+    window: &tauri::WebviewWindow<R>,
+    id: u64,
+    text: Result<String, String>,
+) {
+    let (text, error) = match text {
+        Ok(text) => (serde_json::Value::from(text), serde_json::Value::Null),
+        Err(e) => (serde_json::Value::Null, serde_json::Value::from(e)),
+    };
+    let script = format!(
+        "if (window.__TAURI_MCP_PERMISSIONS__) {{ window.__TAURI_MCP_PERMISSIONS__.resolveClipboard({}, {}, {}); }}",
+        id, text, error
+    );
+    if let Err(e) = window.eval(script) {
+        warn!(
+            "[TAURI_MCP] Failed to answer a clipboard read in {}: {}",
+            window.label(),
+            e
+        );
+    }
+}
+
+/// Answers the permission requests WebKitGTK makes for the pages, which are denied when
+/// nobody handles them.
+#[cfg(target_os = "linux")]
+pub(crate) mod platform {
+    use gtk::glib::object::{Cast, ObjectExt};
+    use tauri::{Manager, Runtime, Webview};
+    use webkit2gtk::{
+        DeviceInfoPermissionRequest, GeolocationPermissionRequest, NotificationPermissionRequest,
+        PermissionRequestExt, UserMediaPermissionRequest, UserMediaPermissionRequestExt,
+        WebViewExt,
+    };
+
+    use super::{PermissionDecision, PermissionGate};
+    use crate::desktop::TauriMcp;
+
+    /// Answer the permission requests of `webview` according to the policy.
+    pub(crate) fn attach<R: Runtime>(webview: &Webview<R>) {
+        let app = webview.app_handle().clone();
+        let result = webview.with_webview(move |platform_webview| {
+            platform_webview
+                .inner()
+                .connect_permission_request(move |_, request| {
+                    let Some(tauri_mcp) = app.try_state::<TauriMcp<R>>() else {
+                        return false;
+                    };
+                    let gate: &PermissionGate = tauri_mcp.permissions();
+                    let decisions: Vec<Option<PermissionDecision>> =
+                        if let Some(media) = request.downcast_ref::<UserMediaPermissionRequest>() {
+                            let mut wanted = Vec::new();
+                            if media.is_for_video_device() {
+                                wanted.push(gate.decision("camera"));
+                            }
+                            if media.is_for_audio_device() {
+                                wanted.push(gate.decision("microphone"));
+                            }
+                            wanted
+                        } else if request.is::<DeviceInfoPermissionRequest>() {
+                            // Device labels of enumerateDevices, given with camera or microphone
+                            let camera = gate.decision("camera");
+                            let microphone = gate.decision("microphone");
+                            if camera == Some(PermissionDecision::Grant)
+                                || microphone == Some(PermissionDecision::Grant)
+                            {
+                                vec![Some(PermissionDecision::Grant)]
+                            } else {
+                                vec![camera.and(microphone)]
+                            }
+                        } else if request.is::<GeolocationPermissionRequest>() {
+                            vec![gate.decision("geolocation")]
+                        } else if request.is::<NotificationPermissionRequest>() {
+                            vec![gate.decision("notifications")]
+                        } else {
+                            Vec::new()
+                        };
+                    if decisions.is_empty() || decisions.contains(&None) {
+                        // Left to the app's own handler or WebKit
+                        false
+                    } else if decisions.contains(&Some(PermissionDecision::Deny)) {
+                        request.deny();
+                        true
+                    } else {
+                        request.allow();
+                        true
+                    }
+                });
+        });
+        if let Err(e) = result {
+            log::warn!(
+                "[TAURI_MCP] Failed to answer the permission requests of a webview: {}",
+                e
+            );
+        }
+    }
+}
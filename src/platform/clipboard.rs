@@ -0,0 +1,170 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::error::Error;
+
+/// Text held by the system clipboard.
+pub fn read_text() -> crate::Result<String> {
+    let mut last_error = None;
+    for (program, args) in read_commands() {
+        match Command::new(program)
+            .args(&args)
+            .env("LANG", "en_US.UTF-8")
+            .stdin(Stdio::null())
+            .stderr(Stdio::null())
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                return decode(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+            Ok(output) => {
+                last_error = Some(format!("{} exited with {}", program, output.status));
+            }
+            Err(e) => last_error = Some(format!("failed to run {}: {}", program, e)),
+        }
+    }
+    Err(Error::Anyhow(format!(
+        "Failed to read the clipboard: {}",
+        last_error.unwrap_or_else(|| "no clipboard tool on this platform".to_string())
+    )))
+}
+
+/// Replace the content of the system clipboard with `text`.
+pub fn write_text(text: &str) -> crate::Result<()> {
+    let mut last_error = None;
+    for (program, args) in write_commands(text) {
+        // Some tools keep running to serve the clipboard, so their output is not waited on
+        let spawned = Command::new(program)
+            .args(&args)
+            // pbcopy and pbpaste pick the encoding from the locale
+            .env("LANG", "en_US.UTF-8")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let mut child = match spawned {
+            Ok(child) => child,
+            Err(e) => {
+                last_error = Some(format!("failed to run {}: {}", program, e));
+                continue;
+            }
+        };
+        if let Some(mut stdin) = child.stdin.take()
+            && !cfg!(windows)
+        {
+            stdin
+                .write_all(text.as_bytes())
+                .map_err(|e| Error::Anyhow(format!("Failed to write the clipboard: {}", e)))?;
+        }
+        let status = child
+            .wait()
+            .map_err(|e| Error::Anyhow(format!("Failed to write the clipboard: {}", e)))?;
+        if status.success() {
+            return Ok(());
+        }
+        last_error = Some(format!("{} exited with {}", program, status));
+    }
+    Err(Error::Anyhow(format!(
+        "Failed to write the clipboard: {}",
+        last_error.unwrap_or_else(|| "no clipboard tool on this platform".to_string())
+    )))
+}
+
+#[cfg(target_os = "macos")]
+fn read_commands() -> Vec<(&'static str, Vec<String>)> {
+    vec![("pbpaste", vec![])]
+}
+
+#[cfg(target_os = "macos")]
+fn write_commands(_text: &str) -> Vec<(&'static str, Vec<String>)> {
+    vec![("pbcopy", vec![])]
+}
+
+#[cfg(target_os = "macos")]
+fn decode(output: String) -> crate::Result<String> {
+    Ok(output)
+}
+
+// PowerShell reads and writes the console in the OEM code page, so the text travels in
+// base64 to survive characters outside it
+#[cfg(windows)]
+fn read_commands() -> Vec<(&'static str, Vec<String>)> {
+    vec![(
+        "powershell",
+        vec![
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            "[Convert]::ToBase64String([Text.Encoding]::UTF8.GetBytes([string](Get-Clipboard -Raw)))"
+                .to_string(),
+        ],
+    )]
+}
+
+#[cfg(windows)]
+fn write_commands(text: &str) -> Vec<(&'static str, Vec<String>)> {
+    use base64::Engine;
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    vec![(
+        "powershell",
+        vec![
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            format!(
+                "Set-Clipboard -Value ([Text.Encoding]::UTF8.GetString([Convert]::FromBase64String('{}')))",
+                encoded
+            ),
+        ],
+    )]
+}
+
+#[cfg(windows)]
+fn decode(output: String) -> crate::Result<String> {
+    use base64::Engine;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(output.trim())
+        .map_err(|e| Error::Anyhow(format!("Failed to read the clipboard: {}", e)))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn read_commands() -> Vec<(&'static str, Vec<String>)> {
+    let mut commands = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-paste", vec!["--no-newline".to_string()]));
+    }
+    commands.push((
+        "xclip",
+        vec![
+            "-selection".to_string(),
+            "clipboard".to_string(),
+            "-o".to_string(),
+        ],
+    ));
+    commands.push((
+        "xsel",
+        vec!["--clipboard".to_string(), "--output".to_string()],
+    ));
+    commands
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn write_commands(_text: &str) -> Vec<(&'static str, Vec<String>)> {
+    let mut commands = Vec::new();
+    if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        commands.push(("wl-copy", vec![]));
+    }
+    commands.push((
+        "xclip",
+        vec!["-selection".to_string(), "clipboard".to_string()],
+    ));
+    commands.push((
+        "xsel",
+        vec!["--clipboard".to_string(), "--input".to_string()],
+    ));
+    commands
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn decode(output: String) -> crate::Result<String> {
+    Ok(output)
+}
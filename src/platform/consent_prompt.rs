@@ -0,0 +1,142 @@
+use tauri::{AppHandle, Runtime};
+
+const TITLE: &str = "Allow automated input?";
+const MESSAGE: &str = "An MCP agent wants to control the mouse and keyboard while this app runs. \
+                       Allow it only if you started the agent.";
+
+/// Called with true when the user allowed input simulation, false when they denied it or
+/// closed the prompt.
+pub type Answer = Box<dyn FnOnce(bool) + Send + 'static>;
+
+/// Ask the user for consent to input simulation in a native dialog, outside the webviews
+/// so no page script can reach it, and pass the answer to `answer` once they gave it.
+pub fn show<R: Runtime>(app: &AppHandle<R>, answer: Answer) {
+    imp::show(app, answer)
+}
+
+/// A GTK message dialog, on the main thread like the app's windows.
+#[cfg(target_os = "linux")]
+mod imp {
+    use gtk::prelude::*;
+    use log::warn;
+    use std::cell::Cell;
+    use tauri::{AppHandle, Runtime};
+
+    use super::{Answer, MESSAGE, TITLE};
+
+    pub(super) fn show<R: Runtime>(app: &AppHandle<R>, answer: Answer) {
+        let shown = app.run_on_main_thread(move || {
+            let dialog = gtk::MessageDialog::new(
+                None::<&gtk::Window>,
+                gtk::DialogFlags::MODAL,
+                gtk::MessageType::Warning,
+                gtk::ButtonsType::None,
+                MESSAGE,
+            );
+            dialog.set_title(TITLE);
+            dialog.add_button("Deny", gtk::ResponseType::Reject);
+            dialog.add_button("Allow", gtk::ResponseType::Accept);
+            dialog.set_default_response(gtk::ResponseType::Reject);
+            dialog.set_position(gtk::WindowPosition::Center);
+            dialog.set_keep_above(true);
+            let answer = Cell::new(Some(answer));
+            dialog.connect_response(move |dialog, response| {
+                if let Some(answer) = answer.take() {
+                    answer(response == gtk::ResponseType::Accept);
+                }
+                // SAFETY: the dialog is not used once answered
+                unsafe { dialog.destroy() };
+            });
+            dialog.show_all();
+        });
+        if let Err(e) = shown {
+            warn!("[TAURI_MCP] Failed to show the consent prompt: {}", e);
+        }
+    }
+}
+
+/// A MessageBox, on its own thread as it blocks until answered.
+#[cfg(windows)]
+mod imp {
+    use tauri::{AppHandle, Runtime};
+
+    use super::{Answer, MESSAGE, TITLE};
+
+    const MB_YESNO: u32 = 0x4;
+    const MB_ICONWARNING: u32 = 0x30;
+    const MB_DEFBUTTON2: u32 = 0x100;
+    const MB_SETFOREGROUND: u32 = 0x10000;
+    const MB_TOPMOST: u32 = 0x40000;
+    const IDYES: i32 = 6;
+
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        fn MessageBoxW(hwnd: isize, text: *const u16, caption: *const u16, kind: u32) -> i32;
+    }
+
+    fn wide(text: &str) -> Vec<u16> {
+        text.encode_utf16().chain(std::iter::once(0)).collect()
+    }
+
+    pub(super) fn show<R: Runtime>(_app: &AppHandle<R>, answer: Answer) {
+        std::thread::spawn(move || {
+            let text = wide(MESSAGE);
+            let caption = wide(TITLE);
+            // SAFETY: both strings are NUL-terminated and outlive the call
+            let result = unsafe {
+                MessageBoxW(
+                    0,
+                    text.as_ptr(),
+                    caption.as_ptr(),
+                    MB_YESNO | MB_ICONWARNING | MB_DEFBUTTON2 | MB_SETFOREGROUND | MB_TOPMOST,
+                )
+            };
+            answer(result == IDYES);
+        });
+    }
+}
+
+/// An AppleScript dialog run by `osascript`, which answers through its exit status and output.
+#[cfg(target_os = "macos")]
+mod imp {
+    use log::warn;
+    use std::process::Command;
+    use tauri::{AppHandle, Runtime};
+
+    use super::{Answer, MESSAGE, TITLE};
+
+    pub(super) fn show<R: Runtime>(_app: &AppHandle<R>, answer: Answer) {
+        std::thread::spawn(move || {
+            let script = format!(
+                "display dialog \"{}\" with title \"{}\" buttons {{\"Deny\", \"Allow\"}} \
+                 default button \"Deny\" cancel button \"Deny\" with icon caution",
+                MESSAGE, TITLE
+            );
+            let granted = match Command::new("osascript").arg("-e").arg(script).output() {
+                Ok(output) => {
+                    output.status.success()
+                        && String::from_utf8_lossy(&output.stdout).contains("button returned:Allow")
+                }
+                Err(e) => {
+                    warn!("[TAURI_MCP] Failed to show the consent prompt: {}", e);
+                    false
+                }
+            };
+            answer(granted);
+        });
+    }
+}
+
+/// No native dialog here; OS input is not simulated on these platforms anyway.
+#[cfg(not(any(target_os = "linux", windows, target_os = "macos")))]
+mod imp {
+    use log::warn;
+    use tauri::{AppHandle, Runtime};
+
+    use super::Answer;
+
+    pub(super) fn show<R: Runtime>(_app: &AppHandle<R>, answer: Answer) {
+        warn!("[TAURI_MCP] No native consent prompt on this platform, denying input simulation");
+        answer(false);
+    }
+}
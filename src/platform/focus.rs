@@ -0,0 +1,84 @@
+use log::warn;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+
+/// The window that had the keyboard focus, to give it back after input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FocusedWindow {
+    /// A window of this app, by label.
+    App(String),
+    /// A window of another app, by native handle. Only known on Windows.
+    Native(isize),
+}
+
+/// The window that has the keyboard focus, None when another app has it and the platform
+/// does not tell which window.
+pub fn focused_window<R: Runtime>(app: &AppHandle<R>) -> Option<FocusedWindow> {
+    let own = app
+        .webview_windows()
+        .into_iter()
+        .find(|(_, window)| window.is_focused().unwrap_or(false))
+        .map(|(label, _)| FocusedWindow::App(label));
+    own.or_else(native_foreground)
+}
+
+/// Focus `window` again. Failures are logged, the input it followed already happened.
+pub fn restore<R: Runtime>(app: &AppHandle<R>, window: &FocusedWindow) {
+    match window {
+        FocusedWindow::App(label) => {
+            if let Some(window) = app.get_webview_window(label)
+                && let Err(e) = focus(&window)
+            {
+                warn!("[TAURI_MCP] Failed to focus {} again: {}", label, e);
+            }
+        }
+        FocusedWindow::Native(handle) => {
+            if !set_native_foreground(*handle) {
+                warn!("[TAURI_MCP] Failed to focus window {:#x} again", handle);
+            }
+        }
+    }
+}
+
+/// Give the keyboard focus to `window`.
+#[cfg(desktop)]
+pub fn focus<R: Runtime>(window: &WebviewWindow<R>) -> crate::Result<()> {
+    Ok(window.set_focus()?)
+}
+
+/// Give the keyboard focus to `window`.
+#[cfg(mobile)]
+pub fn focus<R: Runtime>(_window: &WebviewWindow<R>) -> crate::Result<()> {
+    Err(crate::mobile::unsupported("Focusing windows"))
+}
+
+#[cfg(windows)]
+mod user32 {
+    #[link(name = "user32")]
+    unsafe extern "system" {
+        pub fn GetForegroundWindow() -> isize;
+        pub fn SetForegroundWindow(hwnd: isize) -> i32;
+    }
+}
+
+#[cfg(windows)]
+fn native_foreground() -> Option<FocusedWindow> {
+    // SAFETY: GetForegroundWindow takes no arguments and returns a handle or null
+    let handle = unsafe { user32::GetForegroundWindow() };
+    (handle != 0).then_some(FocusedWindow::Native(handle))
+}
+
+#[cfg(windows)]
+fn set_native_foreground(handle: isize) -> bool {
+    // SAFETY: SetForegroundWindow fails on handles of windows that are gone
+    unsafe { user32::SetForegroundWindow(handle) != 0 }
+}
+
+#[cfg(not(windows))]
+fn native_foreground() -> Option<FocusedWindow> {
+    None
+}
+
+#[cfg(not(windows))]
+fn set_native_foreground(_handle: isize) -> bool {
+    false
+}
@@ -0,0 +1,700 @@
+#[cfg(desktop)]
+use enigo::{Axis, Coordinate, Enigo, Keyboard, Mouse, Settings};
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[cfg(desktop)]
+use crate::error::Error;
+use crate::platform::clipboard;
+use crate::platform::keycodes::KeyCode;
+
+/// Intermediate moves of a drag, so the app sees the pointer travel between the two ends.
+const DRAG_STEPS: i32 = 10;
+
+/// Time given to the app to read the clipboard after the paste shortcut, before the
+/// previous content is put back.
+const PASTE_SETTLE: Duration = Duration::from_millis(150);
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+}
+
+impl MouseButton {
+    /// Parse "left", "middle" or "right".
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "left" => Some(Self::Left),
+            "middle" => Some(Self::Middle),
+            "right" => Some(Self::Right),
+            _ => None,
+        }
+    }
+}
+
+/// A key of the keyboard, named by what it does rather than where it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Key {
+    Shift,
+    Control,
+    Alt,
+    /// Command on macOS, the Windows or Super key elsewhere.
+    Meta,
+    Enter,
+    Tab,
+    Escape,
+    Backspace,
+    Delete,
+    Space,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// F1 to F12.
+    Function(u8),
+    /// The key typing this character.
+    Char(char),
+    /// A physical key by its position, whatever the keyboard layout types with it.
+    Code(KeyCode),
+}
+
+impl Key {
+    /// Parse a key name such as "ctrl", "enter", "f5" or "a", ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        let mut chars = name.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Some(Self::Char(c));
+        }
+        let key = match name.to_ascii_lowercase().as_str() {
+            "shift" => Self::Shift,
+            "ctrl" | "control" => Self::Control,
+            "alt" | "option" => Self::Alt,
+            "meta" | "cmd" | "command" | "super" | "win" => Self::Meta,
+            "enter" | "return" => Self::Enter,
+            "tab" => Self::Tab,
+            "escape" | "esc" => Self::Escape,
+            "backspace" => Self::Backspace,
+            "delete" | "del" => Self::Delete,
+            "space" => Self::Space,
+            "up" | "arrowup" => Self::ArrowUp,
+            "down" | "arrowdown" => Self::ArrowDown,
+            "left" | "arrowleft" => Self::ArrowLeft,
+            "right" | "arrowright" => Self::ArrowRight,
+            "home" => Self::Home,
+            "end" => Self::End,
+            "pageup" => Self::PageUp,
+            "pagedown" => Self::PageDown,
+            other => {
+                let number = other.strip_prefix('f')?.parse().ok()?;
+                if !(1..=12).contains(&number) {
+                    return None;
+                }
+                Self::Function(number)
+            }
+        };
+        Some(key)
+    }
+}
+
+/// Whether a key or button goes down, up, or both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    Press,
+    Release,
+    Click,
+}
+
+/// OS-level keyboard and mouse input. Positions are in the units the OS mouse takes, see
+/// [`crate::WindowGeometry::os_input_point`].
+///
+/// Implementors provide the primitives, combos, clicks and drags are built on them.
+pub trait InputBackend {
+    /// Type `text` as the keyboard would, whatever the layout.
+    fn text(&mut self, text: &str) -> crate::Result<()>;
+    fn key(&mut self, key: Key, direction: Direction) -> crate::Result<()>;
+    fn move_to(&mut self, x: i32, y: i32) -> crate::Result<()>;
+    fn move_by(&mut self, dx: i32, dy: i32) -> crate::Result<()>;
+    fn button(&mut self, button: MouseButton, direction: Direction) -> crate::Result<()>;
+    /// Scroll by wheel notches, positive to the right and down.
+    fn scroll(&mut self, dx: i32, dy: i32) -> crate::Result<()>;
+    /// Position of the mouse.
+    fn location(&self) -> crate::Result<(i32, i32)>;
+
+    /// Press the keys in order and release them in reverse, e.g. ctrl+shift+t.
+    fn key_combo(&mut self, keys: &[Key]) -> crate::Result<()> {
+        let Some((last, held)) = keys.split_last() else {
+            return Ok(());
+        };
+        with_keys_held(self, held, |input| input.key(*last, Direction::Click))
+    }
+
+    /// Click `count` times with `modifiers` held.
+    fn click(&mut self, button: MouseButton, count: u32, modifiers: &[Key]) -> crate::Result<()> {
+        with_keys_held(self, modifiers, |input| {
+            (0..count).try_for_each(|_| input.button(button, Direction::Click))
+        })
+    }
+
+    /// Press `button` at `from`, move to `to` and release it there.
+    fn drag(&mut self, from: (i32, i32), to: (i32, i32), button: MouseButton) -> crate::Result<()> {
+        self.move_to(from.0, from.1)?;
+        self.button(button, Direction::Press)?;
+        let moved = (1..=DRAG_STEPS).try_for_each(|step| {
+            self.move_to(
+                from.0 + (to.0 - from.0) * step / DRAG_STEPS,
+                from.1 + (to.1 - from.1) * step / DRAG_STEPS,
+            )
+        });
+        // Always release the button, even when a move failed
+        let released = self.button(button, Direction::Release);
+        moved.and(released)
+    }
+
+    /// Paste `text` through the clipboard with the platform's paste shortcut, which no
+    /// keyboard layout or input method can mangle, then put back what the clipboard held.
+    fn paste(&mut self, text: &str) -> crate::Result<()> {
+        let previous = clipboard::read_text().ok();
+        clipboard::write_text(text)?;
+        let modifier = if cfg!(target_os = "macos") {
+            Key::Meta
+        } else {
+            Key::Control
+        };
+        let pasted = self.key_combo(&[modifier, Key::Char('v')]);
+        std::thread::sleep(PASTE_SETTLE);
+        if let Some(previous) = previous {
+            let _ = clipboard::write_text(&previous);
+        }
+        pasted
+    }
+}
+
+/// Run `action` with `keys` held, releasing them afterwards even when it failed.
+fn with_keys_held<B, F>(input: &mut B, keys: &[Key], action: F) -> crate::Result<()>
+where
+    B: InputBackend + ?Sized,
+    F: FnOnce(&mut B) -> crate::Result<()>,
+{
+    let mut pressed = 0;
+    let mut result = keys.iter().try_for_each(|key| {
+        input.key(*key, Direction::Press)?;
+        pressed += 1;
+        Ok(())
+    });
+    if result.is_ok() {
+        result = action(input);
+    }
+    for key in keys[..pressed].iter().rev() {
+        let _ = input.key(*key, Direction::Release);
+    }
+    result
+}
+
+/// Opens an input backend for a command, see [`crate::TauriMcp::set_input_backend`].
+pub(crate) type InputFactory = Arc<dyn Fn() -> crate::Result<Box<dyn InputBackend>> + Send + Sync>;
+
+/// The OS input of the desktop, through enigo.
+#[cfg(desktop)]
+pub struct EnigoInput {
+    enigo: Enigo,
+    /// Presses physical keys on Linux, opened on the first one.
+    #[cfg(target_os = "linux")]
+    xtest: Option<crate::platform::xtest::XTest>,
+}
+
+#[cfg(desktop)]
+impl EnigoInput {
+    pub fn new() -> crate::Result<Self> {
+        let enigo = Enigo::new(&Settings::default())
+            .map_err(|e| Error::Anyhow(format!("Failed to initialize Enigo: {}", e)))?;
+        Ok(Self {
+            enigo,
+            #[cfg(target_os = "linux")]
+            xtest: None,
+        })
+    }
+
+    /// Press a physical key by its X keycode, as enigo's xdo backend cannot.
+    #[cfg(target_os = "linux")]
+    fn raw_key(&mut self, code: KeyCode, direction: Direction) -> crate::Result<()> {
+        let xtest = match &mut self.xtest {
+            Some(xtest) => xtest,
+            None => self.xtest.insert(crate::platform::xtest::XTest::open()?),
+        };
+        let keycode = code.raw();
+        match direction {
+            Direction::Press => xtest.key(keycode, true),
+            Direction::Release => xtest.key(keycode, false),
+            Direction::Click => {
+                xtest.key(keycode, true)?;
+                xtest.key(keycode, false)
+            }
+        }
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn raw_key(&mut self, code: KeyCode, direction: Direction) -> crate::Result<()> {
+        self.enigo
+            .raw(code.raw(), enigo_direction(direction))
+            .map_err(|e| Error::Anyhow(format!("Failed to press {:?}: {}", code, e)))
+    }
+}
+
+#[cfg(desktop)]
+fn enigo_key(key: Key) -> enigo::Key {
+    match key {
+        Key::Shift => enigo::Key::Shift,
+        Key::Control => enigo::Key::Control,
+        Key::Alt => enigo::Key::Alt,
+        Key::Meta => enigo::Key::Meta,
+        Key::Enter => enigo::Key::Return,
+        Key::Tab => enigo::Key::Tab,
+        Key::Escape => enigo::Key::Escape,
+        Key::Backspace => enigo::Key::Backspace,
+        Key::Delete => enigo::Key::Delete,
+        Key::Space => enigo::Key::Space,
+        Key::ArrowUp => enigo::Key::UpArrow,
+        Key::ArrowDown => enigo::Key::DownArrow,
+        Key::ArrowLeft => enigo::Key::LeftArrow,
+        Key::ArrowRight => enigo::Key::RightArrow,
+        Key::Home => enigo::Key::Home,
+        Key::End => enigo::Key::End,
+        Key::PageUp => enigo::Key::PageUp,
+        Key::PageDown => enigo::Key::PageDown,
+        Key::Function(1) => enigo::Key::F1,
+        Key::Function(2) => enigo::Key::F2,
+        Key::Function(3) => enigo::Key::F3,
+        Key::Function(4) => enigo::Key::F4,
+        Key::Function(5) => enigo::Key::F5,
+        Key::Function(6) => enigo::Key::F6,
+        Key::Function(7) => enigo::Key::F7,
+        Key::Function(8) => enigo::Key::F8,
+        Key::Function(9) => enigo::Key::F9,
+        Key::Function(10) => enigo::Key::F10,
+        Key::Function(11) => enigo::Key::F11,
+        Key::Function(_) => enigo::Key::F12,
+        Key::Char(c) => enigo::Key::Unicode(c),
+        Key::Code(_) => unreachable!("physical keys are pressed by keycode"),
+    }
+}
+
+#[cfg(desktop)]
+fn enigo_direction(direction: Direction) -> enigo::Direction {
+    match direction {
+        Direction::Press => enigo::Direction::Press,
+        Direction::Release => enigo::Direction::Release,
+        Direction::Click => enigo::Direction::Click,
+    }
+}
+
+#[cfg(desktop)]
+impl InputBackend for EnigoInput {
+    fn text(&mut self, text: &str) -> crate::Result<()> {
+        self.enigo
+            .text(text)
+            .map_err(|e| Error::Anyhow(format!("Failed to simulate text input: {}", e)))
+    }
+
+    fn key(&mut self, key: Key, direction: Direction) -> crate::Result<()> {
+        if let Key::Code(code) = key {
+            return self.raw_key(code, direction);
+        }
+        self.enigo
+            .key(enigo_key(key), enigo_direction(direction))
+            .map_err(|e| Error::Anyhow(format!("Failed to press {:?}: {}", key, e)))
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) -> crate::Result<()> {
+        self.enigo
+            .move_mouse(x, y, Coordinate::Abs)
+            .map_err(|e| Error::Anyhow(format!("Failed to move mouse: {}", e)))
+    }
+
+    fn move_by(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+        self.enigo
+            .move_mouse(dx, dy, Coordinate::Rel)
+            .map_err(|e| Error::Anyhow(format!("Failed to move mouse: {}", e)))
+    }
+
+    fn button(&mut self, button: MouseButton, direction: Direction) -> crate::Result<()> {
+        let button = match button {
+            MouseButton::Left => enigo::Button::Left,
+            MouseButton::Middle => enigo::Button::Middle,
+            MouseButton::Right => enigo::Button::Right,
+        };
+        self.enigo
+            .button(button, enigo_direction(direction))
+            .map_err(|e| Error::Anyhow(format!("Failed to press mouse button: {}", e)))
+    }
+
+    fn scroll(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+        if dx != 0 {
+            self.enigo
+                .scroll(dx, Axis::Horizontal)
+                .map_err(|e| Error::Anyhow(format!("Failed to scroll: {}", e)))?;
+        }
+        if dy != 0 {
+            self.enigo
+                .scroll(dy, Axis::Vertical)
+                .map_err(|e| Error::Anyhow(format!("Failed to scroll: {}", e)))?;
+        }
+        Ok(())
+    }
+
+    fn location(&self) -> crate::Result<(i32, i32)> {
+        self.enigo
+            .location()
+            .map_err(|e| Error::Anyhow(format!("Failed to get mouse position: {}", e)))
+    }
+}
+
+/// An input primitive, as recorded by [`RecordingInput`].
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum InputEvent {
+    Text {
+        text: String,
+    },
+    Paste {
+        text: String,
+    },
+    Key {
+        key: Key,
+        direction: Direction,
+    },
+    MoveTo {
+        x: i32,
+        y: i32,
+    },
+    MoveBy {
+        dx: i32,
+        dy: i32,
+    },
+    Button {
+        button: MouseButton,
+        direction: Direction,
+    },
+    Scroll {
+        dx: i32,
+        dy: i32,
+    },
+}
+
+/// Stand-in for the OS input in test builds and CI without a display: records what the
+/// tools would do instead of doing it. Clones share their record.
+#[derive(Debug, Clone, Default)]
+pub struct RecordingInput {
+    events: Arc<Mutex<Vec<InputEvent>>>,
+    position: Arc<Mutex<(i32, i32)>>,
+}
+
+impl RecordingInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Events recorded so far, oldest first.
+    pub fn events(&self) -> Vec<InputEvent> {
+        self.events.lock().unwrap().clone()
+    }
+
+    /// Forget the recorded events.
+    pub fn clear(&self) {
+        self.events.lock().unwrap().clear();
+    }
+
+    fn record(&self, event: InputEvent) -> crate::Result<()> {
+        self.events.lock().unwrap().push(event);
+        Ok(())
+    }
+}
+
+impl InputBackend for RecordingInput {
+    fn text(&mut self, text: &str) -> crate::Result<()> {
+        self.record(InputEvent::Text {
+            text: text.to_string(),
+        })
+    }
+
+    fn key(&mut self, key: Key, direction: Direction) -> crate::Result<()> {
+        self.record(InputEvent::Key { key, direction })
+    }
+
+    fn paste(&mut self, text: &str) -> crate::Result<()> {
+        self.record(InputEvent::Paste {
+            text: text.to_string(),
+        })
+    }
+
+    fn move_to(&mut self, x: i32, y: i32) -> crate::Result<()> {
+        *self.position.lock().unwrap() = (x, y);
+        self.record(InputEvent::MoveTo { x, y })
+    }
+
+    fn move_by(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+        {
+            let mut position = self.position.lock().unwrap();
+            *position = (position.0 + dx, position.1 + dy);
+        }
+        self.record(InputEvent::MoveBy { dx, dy })
+    }
+
+    fn button(&mut self, button: MouseButton, direction: Direction) -> crate::Result<()> {
+        self.record(InputEvent::Button { button, direction })
+    }
+
+    fn scroll(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+        self.record(InputEvent::Scroll { dx, dy })
+    }
+
+    fn location(&self) -> crate::Result<(i32, i32)> {
+        Ok(*self.position.lock().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records like [`RecordingInput`], but fails on the first event `fails` matches.
+    struct FailingInput {
+        recording: RecordingInput,
+        fails: fn(&InputEvent) -> bool,
+    }
+
+    impl FailingInput {
+        fn new(fails: fn(&InputEvent) -> bool) -> Self {
+            Self {
+                recording: RecordingInput::new(),
+                fails,
+            }
+        }
+
+        fn check(&mut self, event: InputEvent) -> crate::Result<()> {
+            if (self.fails)(&event) {
+                self.fails = |_| false;
+                return Err(crate::Error::Anyhow(format!("{:?} failed", event)));
+            }
+            Ok(())
+        }
+    }
+
+    impl InputBackend for FailingInput {
+        fn text(&mut self, text: &str) -> crate::Result<()> {
+            self.recording.text(text)
+        }
+
+        fn key(&mut self, key: Key, direction: Direction) -> crate::Result<()> {
+            self.check(InputEvent::Key { key, direction })?;
+            self.recording.key(key, direction)
+        }
+
+        fn move_to(&mut self, x: i32, y: i32) -> crate::Result<()> {
+            self.check(InputEvent::MoveTo { x, y })?;
+            self.recording.move_to(x, y)
+        }
+
+        fn move_by(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+            self.recording.move_by(dx, dy)
+        }
+
+        fn button(&mut self, button: MouseButton, direction: Direction) -> crate::Result<()> {
+            self.check(InputEvent::Button { button, direction })?;
+            self.recording.button(button, direction)
+        }
+
+        fn scroll(&mut self, dx: i32, dy: i32) -> crate::Result<()> {
+            self.recording.scroll(dx, dy)
+        }
+
+        fn location(&self) -> crate::Result<(i32, i32)> {
+            self.recording.location()
+        }
+    }
+
+    fn key(key: Key, direction: Direction) -> InputEvent {
+        InputEvent::Key { key, direction }
+    }
+
+    fn button(button: MouseButton, direction: Direction) -> InputEvent {
+        InputEvent::Button { button, direction }
+    }
+
+    #[test]
+    fn key_combo_holds_keys_and_releases_them_in_reverse() {
+        let mut input = RecordingInput::new();
+        input
+            .key_combo(&[Key::Control, Key::Shift, Key::Char('t')])
+            .unwrap();
+        assert_eq!(
+            input.events(),
+            [
+                key(Key::Control, Direction::Press),
+                key(Key::Shift, Direction::Press),
+                key(Key::Char('t'), Direction::Click),
+                key(Key::Shift, Direction::Release),
+                key(Key::Control, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_combo_of_one_key_clicks_it() {
+        let mut input = RecordingInput::new();
+        input.key_combo(&[Key::Enter]).unwrap();
+        assert_eq!(input.events(), [key(Key::Enter, Direction::Click)]);
+
+        input.clear();
+        input.key_combo(&[]).unwrap();
+        assert!(input.events().is_empty());
+    }
+
+    #[test]
+    fn key_combo_releases_held_keys_when_the_last_one_fails() {
+        let mut input = FailingInput::new(|event| {
+            matches!(
+                event,
+                InputEvent::Key {
+                    key: Key::Char('v'),
+                    ..
+                }
+            )
+        });
+        assert!(input.key_combo(&[Key::Control, Key::Char('v')]).is_err());
+        assert_eq!(
+            input.recording.events(),
+            [
+                key(Key::Control, Direction::Press),
+                key(Key::Control, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn key_combo_only_releases_the_keys_it_pressed() {
+        let mut input = FailingInput::new(|event| {
+            matches!(
+                event,
+                InputEvent::Key {
+                    key: Key::Shift,
+                    direction: Direction::Press
+                }
+            )
+        });
+        assert!(
+            input
+                .key_combo(&[Key::Control, Key::Shift, Key::Char('t')])
+                .is_err()
+        );
+        assert_eq!(
+            input.recording.events(),
+            [
+                key(Key::Control, Direction::Press),
+                key(Key::Control, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn click_repeats_with_modifiers_held() {
+        let mut input = RecordingInput::new();
+        input.click(MouseButton::Left, 2, &[Key::Shift]).unwrap();
+        assert_eq!(
+            input.events(),
+            [
+                key(Key::Shift, Direction::Press),
+                button(MouseButton::Left, Direction::Click),
+                button(MouseButton::Left, Direction::Click),
+                key(Key::Shift, Direction::Release),
+            ]
+        );
+    }
+
+    #[test]
+    fn click_without_modifiers_only_clicks() {
+        let mut input = RecordingInput::new();
+        input.click(MouseButton::Right, 1, &[]).unwrap();
+        assert_eq!(
+            input.events(),
+            [button(MouseButton::Right, Direction::Click)]
+        );
+    }
+
+    #[test]
+    fn drag_presses_moves_in_steps_and_releases_at_the_end() {
+        let mut input = RecordingInput::new();
+        input.drag((0, 0), (100, 50), MouseButton::Left).unwrap();
+        let events = input.events();
+
+        assert_eq!(events[0], InputEvent::MoveTo { x: 0, y: 0 });
+        assert_eq!(events[1], button(MouseButton::Left, Direction::Press));
+        let moves: Vec<_> = events[2..events.len() - 1].to_vec();
+        assert_eq!(moves.len(), DRAG_STEPS as usize);
+        assert_eq!(moves[0], InputEvent::MoveTo { x: 10, y: 5 });
+        assert_eq!(moves[4], InputEvent::MoveTo { x: 50, y: 25 });
+        assert_eq!(moves[moves.len() - 1], InputEvent::MoveTo { x: 100, y: 50 });
+        assert_eq!(
+            events[events.len() - 1],
+            button(MouseButton::Left, Direction::Release)
+        );
+        assert_eq!(input.location().unwrap(), (100, 50));
+    }
+
+    #[test]
+    fn drag_releases_the_button_when_a_move_fails() {
+        let mut input = FailingInput::new(|event| *event == InputEvent::MoveTo { x: 30, y: 0 });
+        assert!(input.drag((0, 0), (100, 0), MouseButton::Middle).is_err());
+        let events = input.recording.events();
+        assert_eq!(
+            events[events.len() - 1],
+            button(MouseButton::Middle, Direction::Release)
+        );
+        assert!(!events.contains(&InputEvent::MoveTo { x: 100, y: 0 }));
+    }
+
+    #[test]
+    fn drag_stops_when_the_press_fails() {
+        let mut input =
+            FailingInput::new(|event| *event == button(MouseButton::Left, Direction::Press));
+        assert!(input.drag((5, 5), (10, 10), MouseButton::Left).is_err());
+        assert_eq!(
+            input.recording.events(),
+            [InputEvent::MoveTo { x: 5, y: 5 }]
+        );
+    }
+
+    #[test]
+    fn recording_tracks_relative_moves() {
+        let mut input = RecordingInput::new();
+        input.move_to(10, 10).unwrap();
+        input.move_by(-4, 6).unwrap();
+        assert_eq!(input.location().unwrap(), (6, 16));
+
+        // Clones share their record
+        let clone = input.clone();
+        input.scroll(0, 3).unwrap();
+        assert_eq!(clone.events().len(), 3);
+    }
+
+    #[test]
+    fn key_names_parse_ignoring_case() {
+        assert_eq!(Key::parse("Ctrl"), Some(Key::Control));
+        assert_eq!(Key::parse("CMD"), Some(Key::Meta));
+        assert_eq!(Key::parse("f12"), Some(Key::Function(12)));
+        assert_eq!(Key::parse("a"), Some(Key::Char('a')));
+        assert_eq!(Key::parse("f13"), None);
+        assert_eq!(Key::parse("hyper"), None);
+        assert_eq!(MouseButton::parse("Middle"), Some(MouseButton::Middle));
+    }
+}
@@ -0,0 +1,274 @@
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A physical key: its W3C `code` value, its Linux evdev code, its Windows scan code (set
+/// 1, with 0xE0 in the high byte for extended keys), its macOS virtual key code, and the
+/// key it types on a US layout.
+struct PhysicalKey {
+    code: &'static str,
+    evdev: u16,
+    windows: u16,
+    macos: u16,
+    us_key: &'static str,
+}
+
+const fn key(
+    code: &'static str,
+    evdev: u16,
+    windows: u16,
+    macos: u16,
+    us_key: &'static str,
+) -> PhysicalKey {
+    PhysicalKey {
+        code,
+        evdev,
+        windows,
+        macos,
+        us_key,
+    }
+}
+
+const PHYSICAL_KEYS: &[PhysicalKey] = &[
+    key("KeyA", 30, 0x1E, 0x00, "a"),
+    key("KeyB", 48, 0x30, 0x0B, "b"),
+    key("KeyC", 46, 0x2E, 0x08, "c"),
+    key("KeyD", 32, 0x20, 0x02, "d"),
+    key("KeyE", 18, 0x12, 0x0E, "e"),
+    key("KeyF", 33, 0x21, 0x03, "f"),
+    key("KeyG", 34, 0x22, 0x05, "g"),
+    key("KeyH", 35, 0x23, 0x04, "h"),
+    key("KeyI", 23, 0x17, 0x22, "i"),
+    key("KeyJ", 36, 0x24, 0x26, "j"),
+    key("KeyK", 37, 0x25, 0x28, "k"),
+    key("KeyL", 38, 0x26, 0x25, "l"),
+    key("KeyM", 50, 0x32, 0x2E, "m"),
+    key("KeyN", 49, 0x31, 0x2D, "n"),
+    key("KeyO", 24, 0x18, 0x1F, "o"),
+    key("KeyP", 25, 0x19, 0x23, "p"),
+    key("KeyQ", 16, 0x10, 0x0C, "q"),
+    key("KeyR", 19, 0x13, 0x0F, "r"),
+    key("KeyS", 31, 0x1F, 0x01, "s"),
+    key("KeyT", 20, 0x14, 0x11, "t"),
+    key("KeyU", 22, 0x16, 0x20, "u"),
+    key("KeyV", 47, 0x2F, 0x09, "v"),
+    key("KeyW", 17, 0x11, 0x0D, "w"),
+    key("KeyX", 45, 0x2D, 0x07, "x"),
+    key("KeyY", 21, 0x15, 0x10, "y"),
+    key("KeyZ", 44, 0x2C, 0x06, "z"),
+    key("Digit1", 2, 0x02, 0x12, "1"),
+    key("Digit2", 3, 0x03, 0x13, "2"),
+    key("Digit3", 4, 0x04, 0x14, "3"),
+    key("Digit4", 5, 0x05, 0x15, "4"),
+    key("Digit5", 6, 0x06, 0x17, "5"),
+    key("Digit6", 7, 0x07, 0x16, "6"),
+    key("Digit7", 8, 0x08, 0x1A, "7"),
+    key("Digit8", 9, 0x09, 0x1C, "8"),
+    key("Digit9", 10, 0x0A, 0x19, "9"),
+    key("Digit0", 11, 0x0B, 0x1D, "0"),
+    key("Enter", 28, 0x1C, 0x24, "Enter"),
+    key("Escape", 1, 0x01, 0x35, "Escape"),
+    key("Backspace", 14, 0x0E, 0x33, "Backspace"),
+    key("Tab", 15, 0x0F, 0x30, "Tab"),
+    key("Space", 57, 0x39, 0x31, " "),
+    key("Minus", 12, 0x0C, 0x1B, "-"),
+    key("Equal", 13, 0x0D, 0x18, "="),
+    key("BracketLeft", 26, 0x1A, 0x21, "["),
+    key("BracketRight", 27, 0x1B, 0x1E, "]"),
+    key("Backslash", 43, 0x2B, 0x2A, "\\"),
+    key("Semicolon", 39, 0x27, 0x29, ";"),
+    key("Quote", 40, 0x28, 0x27, "'"),
+    key("Backquote", 41, 0x29, 0x32, "`"),
+    key("Comma", 51, 0x33, 0x2B, ","),
+    key("Period", 52, 0x34, 0x2F, "."),
+    key("Slash", 53, 0x35, 0x2C, "/"),
+    key("IntlBackslash", 86, 0x56, 0x0A, "\\"),
+    key("CapsLock", 58, 0x3A, 0x39, "CapsLock"),
+    key("F1", 59, 0x3B, 0x7A, "F1"),
+    key("F2", 60, 0x3C, 0x78, "F2"),
+    key("F3", 61, 0x3D, 0x63, "F3"),
+    key("F4", 62, 0x3E, 0x76, "F4"),
+    key("F5", 63, 0x3F, 0x60, "F5"),
+    key("F6", 64, 0x40, 0x61, "F6"),
+    key("F7", 65, 0x41, 0x62, "F7"),
+    key("F8", 66, 0x42, 0x64, "F8"),
+    key("F9", 67, 0x43, 0x65, "F9"),
+    key("F10", 68, 0x44, 0x6D, "F10"),
+    key("F11", 87, 0x57, 0x67, "F11"),
+    key("F12", 88, 0x58, 0x6F, "F12"),
+    key("ArrowUp", 103, 0xE048, 0x7E, "ArrowUp"),
+    key("ArrowDown", 108, 0xE050, 0x7D, "ArrowDown"),
+    key("ArrowLeft", 105, 0xE04B, 0x7B, "ArrowLeft"),
+    key("ArrowRight", 106, 0xE04D, 0x7C, "ArrowRight"),
+    key("Home", 102, 0xE047, 0x73, "Home"),
+    key("End", 107, 0xE04F, 0x77, "End"),
+    key("PageUp", 104, 0xE049, 0x74, "PageUp"),
+    key("PageDown", 109, 0xE051, 0x79, "PageDown"),
+    key("Insert", 110, 0xE052, 0x72, "Insert"),
+    key("Delete", 111, 0xE053, 0x75, "Delete"),
+    key("ShiftLeft", 42, 0x2A, 0x38, "Shift"),
+    key("ShiftRight", 54, 0x36, 0x3C, "Shift"),
+    key("ControlLeft", 29, 0x1D, 0x3B, "Control"),
+    key("ControlRight", 97, 0xE01D, 0x3E, "Control"),
+    key("AltLeft", 56, 0x38, 0x3A, "Alt"),
+    key("AltRight", 100, 0xE038, 0x3D, "Alt"),
+    key("MetaLeft", 125, 0xE05B, 0x37, "Meta"),
+    key("MetaRight", 126, 0xE05C, 0x36, "Meta"),
+];
+
+/// A physical key by its position on the keyboard, named by its W3C `code` value such as
+/// "KeyS" or "ControlLeft". It is the same key on every layout: "KeyQ" is where Q is on a
+/// US keyboard, which types A on an AZERTY one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct KeyCode(&'static PhysicalKey);
+
+impl KeyCode {
+    /// Look up a W3C `code` value, ignoring case.
+    pub fn parse(code: &str) -> Option<Self> {
+        PHYSICAL_KEYS
+            .iter()
+            .find(|key| key.code.eq_ignore_ascii_case(code))
+            .map(KeyCode)
+    }
+
+    /// The key typing `key` on a US layout, by W3C `key` value and ignoring case.
+    pub fn from_us_key(key: &str) -> Option<Self> {
+        PHYSICAL_KEYS
+            .iter()
+            .find(|physical| physical.us_key.eq_ignore_ascii_case(key))
+            .map(KeyCode)
+    }
+
+    /// The W3C `code` value.
+    pub fn code(&self) -> &'static str {
+        self.0.code
+    }
+
+    /// The W3C `key` value of the key on a US layout, without modifiers.
+    pub fn us_key(&self) -> &'static str {
+        self.0.us_key
+    }
+
+    /// The keycode the OS takes for this key: the scan code on Windows, the virtual key
+    /// code on macOS and the X11 keycode (evdev code + 8) elsewhere.
+    pub fn raw(&self) -> u16 {
+        if cfg!(windows) {
+            self.0.windows
+        } else if cfg!(target_os = "macos") {
+            self.0.macos
+        } else {
+            self.0.evdev + 8
+        }
+    }
+}
+
+impl std::fmt::Debug for KeyCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0.code)
+    }
+}
+
+impl PartialEq for PhysicalKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.code == other.code
+    }
+}
+
+impl Eq for PhysicalKey {}
+
+impl Serialize for KeyCode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.0.code)
+    }
+}
+
+impl<'de> Deserialize<'de> for KeyCode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        KeyCode::parse(&code)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown key code: {}", code)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(name: &str) -> KeyCode {
+        KeyCode::parse(name).unwrap()
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn raw_is_the_scan_code() {
+        assert_eq!(code("KeyA").raw(), 0x1E);
+        assert_eq!(code("ArrowUp").raw(), 0xE048);
+    }
+
+    #[test]
+    #[cfg(target_os = "macos")]
+    fn raw_is_the_virtual_key_code() {
+        assert_eq!(code("KeyA").raw(), 0x00);
+        assert_eq!(code("ArrowUp").raw(), 0x7E);
+    }
+
+    #[test]
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn raw_is_the_x11_keycode() {
+        // X11 keycodes are evdev codes shifted by 8
+        assert_eq!(code("KeyA").raw(), 38);
+        assert_eq!(code("Escape").raw(), 9);
+        assert_eq!(code("ArrowUp").raw(), 111);
+    }
+
+    #[test]
+    #[cfg(not(any(windows, target_os = "macos")))]
+    fn x11_keycodes_fit_in_a_byte() {
+        assert!(
+            PHYSICAL_KEYS
+                .iter()
+                .all(|key| key.evdev + 8 <= u8::MAX as u16)
+        );
+    }
+
+    #[test]
+    fn codes_are_unique() {
+        for (index, key) in PHYSICAL_KEYS.iter().enumerate() {
+            assert!(
+                PHYSICAL_KEYS[index + 1..]
+                    .iter()
+                    .all(|other| other.code != key.code
+                        && other.evdev != key.evdev
+                        && other.windows != key.windows
+                        && other.macos != key.macos),
+                "{} shares a code",
+                key.code
+            );
+        }
+    }
+
+    #[test]
+    fn parse_ignores_case() {
+        assert_eq!(code("keyq"), code("KeyQ"));
+        assert_eq!(code("CONTROLLEFT").code(), "ControlLeft");
+        assert!(KeyCode::parse("KeyÅ").is_none());
+    }
+
+    #[test]
+    fn us_keys_map_back_to_their_position() {
+        assert_eq!(KeyCode::from_us_key("q"), Some(code("KeyQ")));
+        assert_eq!(KeyCode::from_us_key("Q"), Some(code("KeyQ")));
+        assert_eq!(KeyCode::from_us_key("enter"), Some(code("Enter")));
+        assert_eq!(code("BracketLeft").us_key(), "[");
+        assert!(KeyCode::from_us_key("é").is_none());
+    }
+
+    #[test]
+    fn serializes_as_the_code_value() {
+        let json = serde_json::to_string(&code("Digit1")).unwrap();
+        assert_eq!(json, "\"Digit1\"");
+        let parsed: KeyCode = serde_json::from_str("\"digit1\"").unwrap();
+        assert_eq!(parsed, code("Digit1"));
+
+        let error = serde_json::from_str::<KeyCode>("\"Teleport\"").unwrap_err();
+        assert!(error.to_string().contains("unknown key code: Teleport"));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod clipboard;
+pub mod consent_prompt;
+pub mod focus;
+pub mod input;
+pub mod keycodes;
+pub mod snapshot;
+#[cfg(target_os = "linux")]
+pub mod xtest;
@@ -0,0 +1,258 @@
+use std::time::Duration;
+use tauri::{Runtime, WebviewWindow};
+
+use crate::models::ElementBox;
+
+/// Encoding of a snapshot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    Png,
+    /// JPEG at this quality, from 1 to 100.
+    Jpeg(u8),
+}
+
+impl ImageFormat {
+    pub fn mime_type(self) -> &'static str {
+        match self {
+            Self::Png => "image/png",
+            Self::Jpeg(_) => "image/jpeg",
+        }
+    }
+}
+
+/// What to take of the visible part of the page, and how to encode it.
+#[derive(Debug, Clone, Copy)]
+pub struct CaptureOptions {
+    /// Part of the viewport in CSS pixels, the whole viewport when None.
+    pub clip: Option<ElementBox>,
+    /// Widest image in pixels, larger ones are scaled down keeping their aspect ratio.
+    pub max_width: Option<u32>,
+    pub format: ImageFormat,
+}
+
+/// An encoded image of the page with its size in pixels.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub data: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Snapshot the visible part of the page of `window`, in device pixels unless scaled down.
+pub async fn capture<R: Runtime>(
+    window: &WebviewWindow<R>,
+    options: CaptureOptions,
+    timeout: Duration,
+) -> crate::Result<Snapshot> {
+    imp::capture(window, options, timeout).await
+}
+
+/// Size of an image `width` by `height` fitted in `max_width`.
+#[cfg(target_os = "linux")]
+fn fitted(width: u32, height: u32, max_width: Option<u32>) -> (u32, u32) {
+    match max_width {
+        Some(max_width) if max_width > 0 && width > max_width => {
+            let scaled = (height as u64 * max_width as u64 / width as u64).max(1);
+            (max_width, scaled as u32)
+        }
+        _ => (width, height),
+    }
+}
+
+/// Snapshots go through WebKitGTK, cropped and scaled with cairo and gdk-pixbuf.
+#[cfg(target_os = "linux")]
+mod imp {
+    use gtk::cairo;
+    use gtk::gdk_pixbuf::InterpType;
+    use std::time::Duration;
+    use tauri::{Runtime, WebviewWindow};
+    use tokio::sync::oneshot;
+    use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+
+    use super::{CaptureOptions, ImageFormat, Snapshot};
+    use crate::error::Error;
+    use crate::models::ElementBox;
+
+    pub(super) async fn capture<R: Runtime>(
+        window: &WebviewWindow<R>,
+        options: CaptureOptions,
+        timeout: Duration,
+    ) -> crate::Result<Snapshot> {
+        let (tx, rx) = oneshot::channel::<Result<Snapshot, String>>();
+
+        window.with_webview(move |webview| {
+            webview.inner().snapshot(
+                SnapshotRegion::Visible,
+                SnapshotOptions::NONE,
+                None::<&gtk::gio::Cancellable>,
+                move |result| {
+                    let _ = tx.send(
+                        result
+                            .map_err(|e| e.to_string())
+                            .and_then(|surface| encode(&surface, options)),
+                    );
+                },
+            );
+        })?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(result)) => result.map_err(Error::Anyhow),
+            Ok(Err(_)) => Err(Error::Anyhow(
+                "The snapshot ended without a result".to_string(),
+            )),
+            Err(_) => Err(Error::Anyhow(format!(
+                "The snapshot did not finish within {} ms",
+                timeout.as_millis()
+            ))),
+        }
+    }
+
+    /// Copy the clip out of the snapshot, which is scaled by the screen's scale factor, then
+    /// scale and encode it.
+    fn encode(surface: &cairo::Surface, options: CaptureOptions) -> Result<Snapshot, String> {
+        let (scale_x, scale_y) = surface.device_scale();
+        let clip = match options.clip {
+            Some(clip) => clip,
+            None => {
+                let image = cairo::ImageSurface::try_from(surface.clone())
+                    .map_err(|_| "The snapshot is not an image".to_string())?;
+                ElementBox {
+                    x: 0.0,
+                    y: 0.0,
+                    width: image.width() as f64 / scale_x,
+                    height: image.height() as f64 / scale_y,
+                }
+            }
+        };
+        let width = (clip.width * scale_x).round().max(1.0) as i32;
+        let height = (clip.height * scale_y).round().max(1.0) as i32;
+        let cropped = cairo::ImageSurface::create(cairo::Format::ARgb32, width, height)
+            .map_err(|e| e.to_string())?;
+        {
+            let context = cairo::Context::new(&cropped).map_err(|e| e.to_string())?;
+            context.scale(scale_x, scale_y);
+            context
+                .set_source_surface(surface, -clip.x, -clip.y)
+                .map_err(|e| e.to_string())?;
+            context.paint().map_err(|e| e.to_string())?;
+        }
+        cropped.flush();
+        let mut pixbuf = gtk::gdk::pixbuf_get_from_surface(&cropped, 0, 0, width, height)
+            .ok_or_else(|| "Could not read the snapshot".to_string())?;
+
+        let (fitted_width, fitted_height) =
+            super::fitted(width as u32, height as u32, options.max_width);
+        if fitted_width != width as u32 {
+            pixbuf = pixbuf
+                .scale_simple(
+                    fitted_width as i32,
+                    fitted_height as i32,
+                    InterpType::Bilinear,
+                )
+                .ok_or_else(|| "Could not scale the snapshot".to_string())?;
+        }
+        let data = match options.format {
+            ImageFormat::Png => pixbuf.save_to_bufferv("png", &[]),
+            ImageFormat::Jpeg(quality) => {
+                let quality = quality.clamp(1, 100).to_string();
+                pixbuf.save_to_bufferv("jpeg", &[("quality", quality.as_str())])
+            }
+        }
+        .map_err(|e| e.to_string())?;
+        Ok(Snapshot {
+            data,
+            width: fitted_width,
+            height: fitted_height,
+        })
+    }
+}
+
+/// Snapshots go through the Kotlin and Swift side of the plugin: `View.draw` of the WebView on
+/// Android, `takeSnapshot` of the WKWebView on iOS. Both crop, scale and encode the image.
+#[cfg(mobile)]
+mod imp {
+    use base64::Engine;
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use tauri::{Manager, Runtime, WebviewWindow};
+
+    use super::{CaptureOptions, ImageFormat, Snapshot};
+    use crate::TauriMcpExt;
+    use crate::error::Error;
+    use crate::models::ElementBox;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct SnapshotArgs {
+        clip: Option<ElementBox>,
+        max_width: Option<u32>,
+        /// `png` or `jpeg`.
+        format: &'static str,
+        quality: u8,
+    }
+
+    #[derive(Deserialize)]
+    struct SnapshotResponse {
+        /// The encoded image, base64.
+        data: String,
+        width: u32,
+        height: u32,
+    }
+
+    pub(super) async fn capture<R: Runtime>(
+        window: &WebviewWindow<R>,
+        options: CaptureOptions,
+        timeout: Duration,
+    ) -> crate::Result<Snapshot> {
+        let (format, quality) = match options.format {
+            ImageFormat::Png => ("png", 100),
+            ImageFormat::Jpeg(quality) => ("jpeg", quality.clamp(1, 100)),
+        };
+        let args = SnapshotArgs {
+            clip: options.clip,
+            max_width: options.max_width.filter(|width| *width > 0),
+            format,
+            quality,
+        };
+        // Mobile apps have a single webview, the plugin snapshots the one it was loaded in
+        let plugin = window.app_handle().tauri_mcp().mobile_plugin().clone();
+        let response = tokio::time::timeout(
+            timeout,
+            plugin.run_mobile_plugin_async::<SnapshotResponse>("snapshotWebview", args),
+        )
+        .await
+        .map_err(|_| {
+            Error::Anyhow(format!(
+                "The snapshot did not finish within {} ms",
+                timeout.as_millis()
+            ))
+        })??;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(response.data)
+            .map_err(|e| Error::Anyhow(format!("Invalid snapshot data: {}", e)))?;
+        Ok(Snapshot {
+            data,
+            width: response.width,
+            height: response.height,
+        })
+    }
+}
+
+#[cfg(all(desktop, not(target_os = "linux")))]
+mod imp {
+    use std::time::Duration;
+    use tauri::{Runtime, WebviewWindow};
+
+    use super::{CaptureOptions, Snapshot};
+    use crate::error::Error;
+
+    pub(super) async fn capture<R: Runtime>(
+        _window: &WebviewWindow<R>,
+        _options: CaptureOptions,
+        _timeout: Duration,
+    ) -> crate::Result<Snapshot> {
+        Err(Error::Anyhow(
+            "Webview snapshots are only supported on Linux, Android and iOS for now".to_string(),
+        ))
+    }
+}
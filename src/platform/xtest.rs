@@ -0,0 +1,78 @@
+use std::ffi::{c_char, c_int, c_uint, c_ulong, c_void};
+use std::ptr::NonNull;
+
+use crate::error::Error;
+
+#[link(name = "X11")]
+unsafe extern "C" {
+    fn XOpenDisplay(name: *const c_char) -> *mut c_void;
+    fn XCloseDisplay(display: *mut c_void) -> c_int;
+    fn XSync(display: *mut c_void, discard: c_int) -> c_int;
+}
+
+#[link(name = "Xtst")]
+unsafe extern "C" {
+    fn XTestFakeKeyEvent(
+        display: *mut c_void,
+        keycode: c_uint,
+        is_press: c_int,
+        delay: c_ulong,
+    ) -> c_int;
+}
+
+/// A connection to the X server pressing keys by X keycode through the XTest extension, so
+/// the app's keyboard layout decides what they type. enigo's xdo backend only presses keysyms.
+pub struct XTest {
+    display: NonNull<c_void>,
+}
+
+// SAFETY: the connection is only used through `&mut self`, one thread at a time
+unsafe impl Send for XTest {}
+
+impl XTest {
+    /// Connect to the display in `DISPLAY`.
+    pub fn open() -> crate::Result<Self> {
+        // SAFETY: a null name makes Xlib read DISPLAY, and a failure returns null
+        let display = unsafe { XOpenDisplay(std::ptr::null()) };
+        NonNull::new(display)
+            .map(|display| Self { display })
+            .ok_or_else(|| {
+                Error::Anyhow(
+                    "Could not open the X display to press keys by keycode, is DISPLAY set?"
+                        .to_string(),
+                )
+            })
+    }
+
+    /// Press or release the key with X keycode `keycode`, and wait until the server handled it.
+    pub fn key(&mut self, keycode: u16, press: bool) -> crate::Result<()> {
+        if !(8..=255).contains(&keycode) {
+            return Err(Error::Anyhow(format!(
+                "X keycode {} is out of range",
+                keycode
+            )));
+        }
+        // SAFETY: the display is open until drop, and XTest ignores keycodes the server lacks
+        let sent = unsafe {
+            let sent =
+                XTestFakeKeyEvent(self.display.as_ptr(), keycode as c_uint, press as c_int, 0);
+            XSync(self.display.as_ptr(), 0);
+            sent
+        };
+        if sent == 0 {
+            return Err(Error::Anyhow(
+                "The X server does not support the XTest extension".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl Drop for XTest {
+    fn drop(&mut self) {
+        // SAFETY: the display was opened by XOpenDisplay and is not used after this
+        unsafe {
+            XCloseDisplay(self.display.as_ptr());
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Mutex;
+use std::time::Instant;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+
+pub use sysinfo::MINIMUM_CPU_UPDATE_INTERVAL;
+
+/// Resource usage of one process.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStats {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    /// True for the app process, false for the processes it started (webviews, helpers).
+    pub main: bool,
+    /// CPU usage since the previous sample, 100 being one full core.
+    pub cpu_percent: f32,
+    /// Resident memory.
+    pub memory_bytes: u64,
+    pub virtual_memory_bytes: u64,
+    /// Open file descriptors, or handles on Windows, when the platform reports them.
+    pub open_files: Option<usize>,
+    /// Reported on Linux only.
+    pub threads: Option<usize>,
+    pub run_time_secs: u64,
+}
+
+/// Sums over the sampled processes; counts only include the processes reporting them.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessTotals {
+    pub cpu_percent: f32,
+    pub memory_bytes: u64,
+    pub open_files: usize,
+    pub threads: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessStatsReport {
+    /// Time covered by the CPU figures.
+    pub interval_ms: u64,
+    pub processes: Vec<ProcessStats>,
+    pub total: ProcessTotals,
+}
+
+/// Samples the resource usage of the app process and its child processes.
+///
+/// CPU usage is measured between two samples, so the monitor keeps the previous one.
+/// Webview processes started by the OS rather than by the app (WKWebView on macOS) are not
+/// children of the app and are not reported.
+pub struct ProcessMonitor {
+    sample: Mutex<Option<(System, Instant)>>,
+}
+
+impl ProcessMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            sample: Mutex::new(None),
+        }
+    }
+
+    /// Refresh the figures and report them, or None on the first call, which only takes
+    /// the baseline for CPU usage. Sample again after [`MINIMUM_CPU_UPDATE_INTERVAL`].
+    pub fn sample(&self, include_children: bool) -> Option<ProcessStatsReport> {
+        let mut sample = self.sample.lock().unwrap();
+        let first = sample.is_none();
+        let (system, sampled_at) = sample.get_or_insert_with(|| (System::new(), Instant::now()));
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cpu()
+                .with_memory()
+                .with_tasks(),
+        );
+        let interval_ms = sampled_at.elapsed().as_millis() as u64;
+        *sampled_at = Instant::now();
+        if first {
+            return None;
+        }
+
+        let main = Pid::from_u32(std::process::id());
+        let mut pids = vec![main];
+        if include_children {
+            // Walk down the process tree, skipping the threads listed as processes on Linux
+            let mut known: HashSet<Pid> = HashSet::from([main]);
+            let mut index = 0;
+            while index < pids.len() {
+                let parent = pids[index];
+                for (pid, process) in system.processes() {
+                    if process.parent() == Some(parent)
+                        && process.thread_kind().is_none()
+                        && known.insert(*pid)
+                    {
+                        pids.push(*pid);
+                    }
+                }
+                index += 1;
+            }
+        }
+
+        let processes: Vec<ProcessStats> = pids
+            .iter()
+            .filter_map(|pid| system.process(*pid))
+            .map(|process| ProcessStats {
+                pid: process.pid().as_u32(),
+                parent_pid: process.parent().map(Pid::as_u32),
+                name: process.name().to_string_lossy().into_owned(),
+                main: process.pid() == main,
+                cpu_percent: process.cpu_usage(),
+                memory_bytes: process.memory(),
+                virtual_memory_bytes: process.virtual_memory(),
+                open_files: process.open_files(),
+                threads: process.tasks().map(HashSet::len),
+                run_time_secs: process.run_time(),
+            })
+            .collect();
+        let total = processes
+            .iter()
+            .fold(ProcessTotals::default(), |total, process| ProcessTotals {
+                cpu_percent: total.cpu_percent + process.cpu_percent,
+                memory_bytes: total.memory_bytes + process.memory_bytes,
+                open_files: total.open_files + process.open_files.unwrap_or(0),
+                threads: total.threads + process.threads.unwrap_or(0),
+            });
+        Some(ProcessStatsReport {
+            interval_ms,
+            processes,
+            total,
+        })
+    }
+}
@@ -0,0 +1,582 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use flate2::write::GzEncoder;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+pub use crate::shared::{MIN_PROTOCOL_VERSION, PROTOCOL_VERSION};
+
+/// Largest frame accepted in length-prefixed mode.
+const MAX_FRAME_SIZE: usize = 256 * 1024 * 1024;
+
+/// Default size of the chunks of a streamed response.
+pub const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+/// How messages are delimited on the socket.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Framing {
+    /// One JSON document per line (default, backward compatible).
+    #[default]
+    JsonLines,
+    /// Each message is preceded by its length as a 4-byte big-endian integer.
+    LengthPrefixed,
+}
+
+/// How message bodies are serialized.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Encoding {
+    #[default]
+    Json,
+    /// MessagePack, only available with length-prefixed framing.
+    MessagePack,
+}
+
+/// Wire format negotiated for a connection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WireFormat {
+    pub framing: Framing,
+    pub encoding: Encoding,
+}
+
+impl WireFormat {
+    /// Check that the combination can be used.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.framing == Framing::JsonLines && self.encoding == Encoding::MessagePack {
+            return Err("MessagePack encoding requires length-prefixed framing".to_string());
+        }
+        Ok(())
+    }
+
+    /// Read the next message. Returns None when the peer closed the connection.
+    pub fn read_message<Rd: BufRead>(&self, reader: &mut Rd) -> io::Result<Option<Vec<u8>>> {
+        match self.framing {
+            Framing::JsonLines => {
+                let mut line = Vec::new();
+                if reader.read_until(b'\n', &mut line)? == 0 {
+                    return Ok(None);
+                }
+                Ok(Some(line))
+            }
+            Framing::LengthPrefixed => {
+                let mut header = [0u8; 4];
+                match reader.read_exact(&mut header) {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+                    Err(e) => return Err(e),
+                }
+                let len = u32::from_be_bytes(header) as usize;
+                if len > MAX_FRAME_SIZE {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Frame of {} bytes exceeds the {} bytes limit",
+                            len, MAX_FRAME_SIZE
+                        ),
+                    ));
+                }
+                let mut body = vec![0u8; len];
+                reader.read_exact(&mut body)?;
+                Ok(Some(body))
+            }
+        }
+    }
+
+    /// Decode a message body read with [`WireFormat::read_message`].
+    pub fn decode<T: DeserializeOwned>(&self, body: &[u8]) -> Result<T, String> {
+        match self.encoding {
+            Encoding::Json => serde_json::from_slice(body).map_err(|e| e.to_string()),
+            Encoding::MessagePack => rmp_serde::from_slice(body).map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Serialize and frame a message, ready to be written to the socket.
+    pub fn encode(&self, message: &Value) -> io::Result<Vec<u8>> {
+        self.frame(self.serialize(message)?)
+    }
+
+    /// Serialize a message body without framing it.
+    pub fn serialize<T: Serialize>(&self, message: &T) -> io::Result<Vec<u8>> {
+        match self.encoding {
+            Encoding::Json => serde_json::to_vec(message).map_err(io::Error::other),
+            Encoding::MessagePack => rmp_serde::to_vec_named(message).map_err(io::Error::other),
+        }
+    }
+
+    /// Frame a serialized message body.
+    pub fn frame(&self, body: Vec<u8>) -> io::Result<Vec<u8>> {
+        Ok(match self.framing {
+            Framing::JsonLines => {
+                let mut line = body;
+                line.push(b'\n');
+                line
+            }
+            Framing::LengthPrefixed => {
+                let len = u32::try_from(body.len()).map_err(|_| {
+                    io::Error::new(io::ErrorKind::InvalidData, "Message too large to frame")
+                })?;
+                let mut frame = Vec::with_capacity(body.len() + 4);
+                frame.extend_from_slice(&len.to_be_bytes());
+                frame.extend_from_slice(&body);
+                frame
+            }
+        })
+    }
+}
+
+/// Compression algorithm applied to large outgoing messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Compression {
+    #[default]
+    None,
+    Gzip,
+    Zstd,
+}
+
+/// Default size above which messages are compressed.
+pub const DEFAULT_COMPRESSION_THRESHOLD: usize = 64 * 1024;
+
+/// Compression negotiated for a connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressionSettings {
+    pub algorithm: Compression,
+    /// Messages whose serialized body is at least this many bytes are compressed.
+    pub threshold: usize,
+}
+
+impl Default for CompressionSettings {
+    fn default() -> Self {
+        Self {
+            algorithm: Compression::None,
+            threshold: DEFAULT_COMPRESSION_THRESHOLD,
+        }
+    }
+}
+
+impl CompressionSettings {
+    /// Compress a serialized message body if it exceeds the threshold.
+    ///
+    /// Compressed bodies are wrapped in an envelope `{"id", "compressed": {"algorithm", "data"}}`
+    /// serialized with the connection encoding, where `data` is the compressed original body
+    /// (base64 in JSON, binary in MessagePack).
+    pub fn apply(
+        &self,
+        format: &WireFormat,
+        id: Option<&str>,
+        body: Vec<u8>,
+    ) -> io::Result<Vec<u8>> {
+        if self.algorithm == Compression::None || body.len() < self.threshold {
+            return Ok(body);
+        }
+
+        let compressed = match self.algorithm {
+            Compression::None => unreachable!(),
+            Compression::Gzip => {
+                let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::fast());
+                encoder.write_all(&body)?;
+                encoder.finish()?
+            }
+            Compression::Zstd => zstd::encode_all(body.as_slice(), 0)?,
+        };
+
+        match format.encoding {
+            Encoding::Json => format.serialize(&serde_json::json!({
+                "id": id,
+                "compressed": {
+                    "algorithm": self.algorithm,
+                    "data": STANDARD.encode(&compressed),
+                },
+            })),
+            Encoding::MessagePack => format.serialize(&CompressedEnvelope {
+                id,
+                compressed: CompressedBody {
+                    algorithm: self.algorithm,
+                    data: serde_bytes::Bytes::new(&compressed),
+                },
+            }),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct CompressedEnvelope<'a> {
+    id: Option<&'a str>,
+    compressed: CompressedBody<'a>,
+}
+
+#[derive(Serialize)]
+struct CompressedBody<'a> {
+    algorithm: Compression,
+    data: &'a serde_bytes::Bytes,
+}
+
+/// Payload of the `handshake` command, which checks that client and server are compatible
+/// and switches the wire format of the connection.
+///
+/// The response is sent in the previous format; every later message uses the new one.
+#[derive(Debug, Clone, Default, Deserialize, JsonSchema, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandshakeRequest {
+    /// Protocol version spoken by the client. Clients that omit it are assumed compatible.
+    pub protocol_version: Option<u32>,
+    /// Tools the client needs; the handshake fails if any of them is missing.
+    pub required_tools: Option<Vec<String>>,
+    pub framing: Option<Framing>,
+    pub encoding: Option<Encoding>,
+    pub compression: Option<Compression>,
+    /// Size in bytes above which messages are compressed.
+    pub compression_threshold: Option<usize>,
+    /// Token of apps requiring one, see `PluginConfig::auth_token`.
+    pub token: Option<String>,
+    /// Whether the client wants periodic `{"keepalive": ms}` messages, which carry no ID.
+    pub keepalive: Option<bool>,
+}
+
+impl HandshakeRequest {
+    /// Check that the client can talk to this server.
+    pub fn check_compatibility(&self) -> Result<(), String> {
+        if let Some(version) = self.protocol_version
+            && !(MIN_PROTOCOL_VERSION..=PROTOCOL_VERSION).contains(&version)
+        {
+            return Err(format!(
+                "Incompatible protocol version {}: this app supports versions {} to {}",
+                version, MIN_PROTOCOL_VERSION, PROTOCOL_VERSION
+            ));
+        }
+
+        let missing: Vec<&str> = self
+            .required_tools
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|tool| !crate::shared::commands::ALL.contains(tool))
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Required tools not supported by this app (protocol version {}): {}",
+                PROTOCOL_VERSION,
+                missing.join(", ")
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Server side of the version exchange, sent with every handshake answer.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerProtocol {
+    pub protocol_version: u32,
+    pub min_protocol_version: u32,
+    /// Commands understood by this app.
+    pub tools: Vec<String>,
+}
+
+impl ServerProtocol {
+    pub fn current() -> Self {
+        Self {
+            protocol_version: PROTOCOL_VERSION,
+            min_protocol_version: MIN_PROTOCOL_VERSION,
+            tools: crate::shared::commands::ALL
+                .iter()
+                .map(|tool| tool.to_string())
+                .collect(),
+        }
+    }
+}
+
+/// Response of the `handshake` command.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HandshakeResponse {
+    #[serde(flatten)]
+    pub server: ServerProtocol,
+    pub framing: Framing,
+    pub encoding: Encoding,
+    pub compression: CompressionSettings,
+    /// Interval of the keepalive messages, None if the client did not ask for them or the
+    /// app disabled them.
+    #[serde(default)]
+    pub keepalive_interval_ms: Option<u64>,
+    /// How long the connection may stay without requests before the app closes it.
+    #[serde(default)]
+    pub idle_timeout_ms: Option<u64>,
+}
+
+/// Split a serialized payload into chunks of at most `max` bytes, on character boundaries.
+pub fn split_chunks(text: &str, max: usize) -> Vec<&str> {
+    let max = max.max(4);
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = rest.len().min(max);
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::{BufReader, Read};
+
+    const LENGTH_PREFIXED_JSON: WireFormat = WireFormat {
+        framing: Framing::LengthPrefixed,
+        encoding: Encoding::Json,
+    };
+
+    const MESSAGE_PACK: WireFormat = WireFormat {
+        framing: Framing::LengthPrefixed,
+        encoding: Encoding::MessagePack,
+    };
+
+    /// Frame `messages` one after the other and read them back.
+    fn round_trip(format: WireFormat, messages: &[Value]) -> Vec<Value> {
+        let mut stream = Vec::new();
+        for message in messages {
+            stream.extend(format.encode(message).unwrap());
+        }
+        let mut reader = BufReader::new(stream.as_slice());
+        let mut read = Vec::new();
+        while let Some(body) = format.read_message(&mut reader).unwrap() {
+            read.push(format.decode(&body).unwrap());
+        }
+        read
+    }
+
+    fn messages() -> Vec<Value> {
+        vec![
+            json!({"command": "ping", "id": "1", "payload": {}}),
+            json!({"text": "multi\nline ünïcode", "list": [1, 2.5, null, true]}),
+            json!({}),
+        ]
+    }
+
+    #[test]
+    fn json_lines_round_trip() {
+        assert_eq!(round_trip(WireFormat::default(), &messages()), messages());
+    }
+
+    #[test]
+    fn length_prefixed_round_trip() {
+        assert_eq!(round_trip(LENGTH_PREFIXED_JSON, &messages()), messages());
+    }
+
+    #[test]
+    fn message_pack_round_trip() {
+        assert_eq!(round_trip(MESSAGE_PACK, &messages()), messages());
+    }
+
+    #[test]
+    fn length_prefix_is_big_endian_body_length() {
+        let frame = LENGTH_PREFIXED_JSON.frame(b"{}".to_vec()).unwrap();
+        assert_eq!(frame, [0, 0, 0, 2, b'{', b'}']);
+    }
+
+    #[test]
+    fn json_lines_end_with_a_newline() {
+        let frame = WireFormat::default().encode(&json!({"a": 1})).unwrap();
+        assert_eq!(frame, b"{\"a\":1}\n");
+    }
+
+    #[test]
+    fn closed_connection_reads_none() {
+        for format in [WireFormat::default(), LENGTH_PREFIXED_JSON] {
+            let mut reader = BufReader::new(&b""[..]);
+            assert!(format.read_message(&mut reader).unwrap().is_none());
+        }
+    }
+
+    #[test]
+    fn truncated_frame_is_an_error() {
+        let mut frame = LENGTH_PREFIXED_JSON.encode(&json!({"a": 1})).unwrap();
+        frame.pop();
+        let mut reader = BufReader::new(frame.as_slice());
+        assert!(LENGTH_PREFIXED_JSON.read_message(&mut reader).is_err());
+    }
+
+    #[test]
+    fn oversized_frame_is_refused() {
+        let header = ((MAX_FRAME_SIZE + 1) as u32).to_be_bytes();
+        let mut reader = BufReader::new(&header[..]);
+        let error = LENGTH_PREFIXED_JSON.read_message(&mut reader).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn message_pack_needs_length_prefix() {
+        let format = WireFormat {
+            framing: Framing::JsonLines,
+            encoding: Encoding::MessagePack,
+        };
+        assert!(format.validate().is_err());
+        assert!(MESSAGE_PACK.validate().is_ok());
+        assert!(WireFormat::default().validate().is_ok());
+    }
+
+    fn large_body(format: &WireFormat) -> (Value, Vec<u8>) {
+        let message = json!({"id": "7", "data": "x".repeat(4096)});
+        let body = format.serialize(&message).unwrap();
+        (message, body)
+    }
+
+    fn settings(algorithm: Compression) -> CompressionSettings {
+        CompressionSettings {
+            algorithm,
+            threshold: 1024,
+        }
+    }
+
+    fn decompress(algorithm: Compression, data: &[u8]) -> Vec<u8> {
+        match algorithm {
+            Compression::Gzip => {
+                let mut body = Vec::new();
+                flate2::read::GzDecoder::new(data)
+                    .read_to_end(&mut body)
+                    .unwrap();
+                body
+            }
+            Compression::Zstd => zstd::decode_all(data).unwrap(),
+            Compression::None => data.to_vec(),
+        }
+    }
+
+    #[test]
+    fn json_bodies_compress_into_a_base64_envelope() {
+        let format = WireFormat::default();
+        for algorithm in [Compression::Gzip, Compression::Zstd] {
+            let (message, body) = large_body(&format);
+            let wrapped = settings(algorithm)
+                .apply(&format, Some("7"), body.clone())
+                .unwrap();
+            assert!(wrapped.len() < body.len());
+
+            let envelope: Value = format.decode(&wrapped).unwrap();
+            assert_eq!(envelope["id"], "7");
+            assert_eq!(envelope["compressed"]["algorithm"], json!(algorithm));
+            let data = STANDARD
+                .decode(envelope["compressed"]["data"].as_str().unwrap())
+                .unwrap();
+            let original: Value = serde_json::from_slice(&decompress(algorithm, &data)).unwrap();
+            assert_eq!(original, message);
+        }
+    }
+
+    #[test]
+    fn message_pack_bodies_compress_into_a_binary_envelope() {
+        #[derive(Deserialize)]
+        struct Envelope {
+            id: Option<String>,
+            compressed: Body,
+        }
+        #[derive(Deserialize)]
+        struct Body {
+            algorithm: Compression,
+            #[serde(with = "serde_bytes")]
+            data: Vec<u8>,
+        }
+
+        for algorithm in [Compression::Gzip, Compression::Zstd] {
+            let (message, body) = large_body(&MESSAGE_PACK);
+            let wrapped = settings(algorithm)
+                .apply(&MESSAGE_PACK, Some("7"), body)
+                .unwrap();
+            let envelope: Envelope = MESSAGE_PACK.decode(&wrapped).unwrap();
+            assert_eq!(envelope.id.as_deref(), Some("7"));
+            assert_eq!(envelope.compressed.algorithm, algorithm);
+            let original: Value =
+                rmp_serde::from_slice(&decompress(algorithm, &envelope.compressed.data)).unwrap();
+            assert_eq!(original, message);
+        }
+    }
+
+    #[test]
+    fn small_or_uncompressed_bodies_are_left_alone() {
+        let format = WireFormat::default();
+        let body = format.serialize(&json!({"id": "1"})).unwrap();
+        let gzip = settings(Compression::Gzip);
+        assert_eq!(gzip.apply(&format, None, body.clone()).unwrap(), body);
+
+        let (_, large) = large_body(&format);
+        let none = settings(Compression::None);
+        assert_eq!(none.apply(&format, None, large.clone()).unwrap(), large);
+    }
+
+    #[test]
+    fn threshold_is_inclusive() {
+        let format = WireFormat::default();
+        let body = vec![b' '; 1024];
+        let wrapped = settings(Compression::Gzip)
+            .apply(&format, None, body.clone())
+            .unwrap();
+        assert_ne!(wrapped, body);
+    }
+
+    #[test]
+    fn chunks_split_at_the_limit() {
+        assert_eq!(split_chunks("abcdefghij", 4), ["abcd", "efgh", "ij"]);
+        assert_eq!(split_chunks("abcdefgh", 4), ["abcd", "efgh"]);
+        assert!(split_chunks("", 4).is_empty());
+    }
+
+    #[test]
+    fn chunks_keep_characters_whole() {
+        // "é" takes two bytes, so the first chunk stops before it
+        let chunks = split_chunks("abcé€z", 4);
+        assert_eq!(chunks, ["abc", "é", "€z"]);
+        assert_eq!(chunks.concat(), "abcé€z");
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 4));
+    }
+
+    #[test]
+    fn chunks_are_at_least_four_bytes() {
+        // A smaller limit could not hold every character
+        assert_eq!(split_chunks("€€", 1), ["€", "€"]);
+    }
+
+    #[test]
+    fn compatible_versions_pass() {
+        for version in [None, Some(MIN_PROTOCOL_VERSION), Some(PROTOCOL_VERSION)] {
+            let request = HandshakeRequest {
+                protocol_version: version,
+                ..Default::default()
+            };
+            assert!(request.check_compatibility().is_ok());
+        }
+    }
+
+    #[test]
+    fn version_mismatch_is_refused() {
+        for version in [MIN_PROTOCOL_VERSION - 1, PROTOCOL_VERSION + 1] {
+            let request = HandshakeRequest {
+                protocol_version: Some(version),
+                ..Default::default()
+            };
+            let error = request.check_compatibility().unwrap_err();
+            assert!(error.contains("Incompatible protocol version"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn missing_required_tools_are_listed() {
+        let request = HandshakeRequest {
+            required_tools: Some(vec!["ping".to_string(), "teleport".to_string()]),
+            ..Default::default()
+        };
+        let error = request.check_compatibility().unwrap_err();
+        assert!(error.ends_with(": teleport"), "{}", error);
+    }
+}
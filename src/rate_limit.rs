@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// Status code used in rejection payloads, mirroring HTTP 429 Too Many Requests.
+pub const TOO_MANY_REQUESTS: u16 = 429;
+
+/// Per-connection request limits enforced by the socket server.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimits {
+    /// Maximum sustained commands per second. None disables rate limiting.
+    pub commands_per_second: Option<u32>,
+    /// Maximum number of requests being processed at once. 0 disables the cap.
+    pub max_in_flight: usize,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            commands_per_second: None,
+            max_in_flight: 16,
+        }
+    }
+}
+
+/// Structured error returned in the `data` field of a rejected request.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitRejection {
+    pub code: u16,
+    /// "rateLimited" or "tooManyInFlight".
+    pub reason: String,
+    /// Suggested delay before retrying.
+    pub retry_after_ms: u64,
+    pub limit: u64,
+}
+
+impl RateLimitRejection {
+    pub fn message(&self) -> String {
+        match self.reason.as_str() {
+            "rateLimited" => format!(
+                "Rate limit exceeded ({} commands/second), retry in {} ms",
+                self.limit, self.retry_after_ms
+            ),
+            _ => format!(
+                "Too many requests in flight on this connection (max {})",
+                self.limit
+            ),
+        }
+    }
+}
+
+/// Limits applied to a single client connection: a token bucket for the command
+/// rate and a counter of in-flight requests.
+pub struct ConnectionLimiter {
+    limits: RateLimits,
+    tokens: f64,
+    last_refill: Instant,
+    in_flight: Arc<AtomicUsize>,
+}
+
+/// Releases an in-flight slot when dropped.
+#[derive(Debug)]
+pub struct InFlightGuard {
+    in_flight: Arc<AtomicUsize>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.in_flight.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+impl ConnectionLimiter {
+    pub fn new(limits: RateLimits) -> Self {
+        Self {
+            limits,
+            // Allow a burst of one second worth of commands
+            tokens: limits.commands_per_second.unwrap_or(0) as f64,
+            last_refill: Instant::now(),
+            in_flight: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Admit a new request, or explain why it is rejected.
+    ///
+    /// The returned guard must be kept alive until the request has been answered.
+    pub fn try_acquire(&mut self) -> Result<InFlightGuard, RateLimitRejection> {
+        let max_in_flight = self.limits.max_in_flight;
+        if max_in_flight > 0 && self.in_flight.load(Ordering::Acquire) >= max_in_flight {
+            return Err(RateLimitRejection {
+                code: TOO_MANY_REQUESTS,
+                reason: "tooManyInFlight".to_string(),
+                retry_after_ms: 100,
+                limit: max_in_flight as u64,
+            });
+        }
+
+        if let Some(rate) = self.limits.commands_per_second.filter(|r| *r > 0) {
+            let rate = rate as f64;
+            let now = Instant::now();
+            let elapsed = now.duration_since(self.last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * rate).min(rate.max(1.0));
+            self.last_refill = now;
+
+            if self.tokens < 1.0 {
+                let wait = Duration::from_secs_f64((1.0 - self.tokens) / rate);
+                return Err(RateLimitRejection {
+                    code: TOO_MANY_REQUESTS,
+                    reason: "rateLimited".to_string(),
+                    retry_after_ms: wait.as_millis().max(1) as u64,
+                    limit: rate as u64,
+                });
+            }
+            self.tokens -= 1.0;
+        }
+
+        self.in_flight.fetch_add(1, Ordering::AcqRel);
+        Ok(InFlightGuard {
+            in_flight: self.in_flight.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(commands_per_second: Option<u32>, max_in_flight: usize) -> RateLimits {
+        RateLimits {
+            commands_per_second,
+            max_in_flight,
+        }
+    }
+
+    #[test]
+    fn in_flight_cap_rejects_then_frees_slots() {
+        let mut limiter = ConnectionLimiter::new(limits(None, 2));
+        let first = limiter.try_acquire().unwrap();
+        let _second = limiter.try_acquire().unwrap();
+
+        let rejection = limiter.try_acquire().unwrap_err();
+        assert_eq!(rejection.code, TOO_MANY_REQUESTS);
+        assert_eq!(rejection.reason, "tooManyInFlight");
+        assert_eq!(rejection.limit, 2);
+
+        drop(first);
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn zero_disables_the_in_flight_cap() {
+        let mut limiter = ConnectionLimiter::new(limits(None, 0));
+        let guards: Vec<_> = (0..100).map(|_| limiter.try_acquire().unwrap()).collect();
+        assert_eq!(guards.len(), 100);
+    }
+
+    #[test]
+    fn rate_limit_allows_a_one_second_burst() {
+        let mut limiter = ConnectionLimiter::new(limits(Some(5), 0));
+        for _ in 0..5 {
+            limiter.try_acquire().unwrap();
+        }
+
+        let rejection = limiter.try_acquire().unwrap_err();
+        assert_eq!(rejection.reason, "rateLimited");
+        assert_eq!(rejection.limit, 5);
+        assert!((1..=200).contains(&rejection.retry_after_ms));
+    }
+
+    #[test]
+    fn rate_limit_refills_over_time() {
+        let mut limiter = ConnectionLimiter::new(limits(Some(100), 0));
+        for _ in 0..100 {
+            limiter.try_acquire().unwrap();
+        }
+        assert!(limiter.try_acquire().is_err());
+
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(limiter.try_acquire().is_ok());
+    }
+
+    #[test]
+    fn rejected_requests_take_no_slot() {
+        let mut limiter = ConnectionLimiter::new(limits(Some(1), 1));
+        let guard = limiter.try_acquire().unwrap();
+        assert!(limiter.try_acquire().is_err());
+        drop(guard);
+        // The bucket is still empty, but the slot is free again
+        assert_eq!(limiter.try_acquire().unwrap_err().reason, "rateLimited");
+        assert_eq!(limiter.in_flight.load(Ordering::Acquire), 0);
+    }
+
+    #[test]
+    fn rejection_messages_name_the_limit() {
+        let rejection = RateLimitRejection {
+            code: TOO_MANY_REQUESTS,
+            reason: "rateLimited".to_string(),
+            retry_after_ms: 40,
+            limit: 25,
+        };
+        assert_eq!(
+            rejection.message(),
+            "Rate limit exceeded (25 commands/second), retry in 40 ms"
+        );
+        let rejection = RateLimitRejection {
+            reason: "tooManyInFlight".to_string(),
+            limit: 16,
+            ..rejection
+        };
+        assert_eq!(
+            rejection.message(),
+            "Too many requests in flight on this connection (max 16)"
+        );
+    }
+}
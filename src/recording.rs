@@ -0,0 +1,206 @@
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Journal of the running session.
+const CURRENT_FILE: &str = "current-session.jsonl";
+/// Marker written when the running session ends cleanly.
+const CLEAN_MARKER: &str = "current-session.clean";
+/// Journal of the previous session, rotated on startup.
+const LAST_FILE: &str = "last-session.jsonl";
+/// Marker telling whether the previous session ended cleanly.
+const LAST_CLEAN_MARKER: &str = "last-session.clean";
+
+/// Maximum time between two fsyncs of the journal.
+const FSYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A single command in a session recording.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingEntry {
+    pub timestamp_ms: u64,
+    pub command: String,
+    pub payload: Value,
+    pub success: bool,
+    pub error: Option<String>,
+    pub duration_ms: u64,
+}
+
+/// Recording of a previous session, recovered after a restart.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveredSession {
+    pub entries: Vec<RecordingEntry>,
+    /// True when the session did not end cleanly (crash, kill...).
+    pub crashed: bool,
+    /// Lines that could not be parsed, typically a write interrupted by the crash.
+    pub corrupt_lines: usize,
+}
+
+struct Journal {
+    file: File,
+    last_sync: Instant,
+    dirty: bool,
+}
+
+/// Streams the commands of the running session to an append-only journal on disk,
+/// so the recording survives a crash of the app.
+///
+/// Entries are written as soon as they are recorded and fsynced at most every second.
+pub struct SessionRecorder {
+    dir: PathBuf,
+    journal: Mutex<Option<Journal>>,
+}
+
+impl SessionRecorder {
+    /// Start recording in the given directory, rotating the journal of the previous session.
+    pub fn start(dir: impl Into<PathBuf>) -> crate::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        rotate_previous(&dir)?;
+
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(dir.join(CURRENT_FILE))?;
+        info!("[TAURI_MCP] Recording session to {}", dir.display());
+
+        Ok(Self {
+            dir,
+            journal: Mutex::new(Some(Journal {
+                file,
+                last_sync: Instant::now(),
+                dirty: false,
+            })),
+        })
+    }
+
+    /// A recorder that does not write anything.
+    pub fn disabled() -> Self {
+        Self {
+            dir: PathBuf::new(),
+            journal: Mutex::new(None),
+        }
+    }
+
+    /// Whether entries are being written to disk.
+    pub fn is_enabled(&self) -> bool {
+        self.journal.lock().unwrap().is_some()
+    }
+
+    /// Directory holding the session journals.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Append an entry to the journal.
+    pub fn record(&self, entry: &RecordingEntry) {
+        let mut journal = self.journal.lock().unwrap();
+        let Some(journal) = journal.as_mut() else {
+            return;
+        };
+
+        let mut line = match serde_json::to_vec(entry) {
+            Ok(line) => line,
+            Err(e) => {
+                error!("[TAURI_MCP] Failed to serialize recording entry: {}", e);
+                return;
+            }
+        };
+        line.push(b'\n');
+
+        // A single write per entry, so a crash can at worst truncate the last line
+        if let Err(e) = journal.file.write_all(&line) {
+            error!("[TAURI_MCP] Failed to write session recording: {}", e);
+            return;
+        }
+        journal.dirty = true;
+
+        if journal.last_sync.elapsed() >= FSYNC_INTERVAL {
+            sync(journal);
+        }
+    }
+
+    /// Flush the journal and mark the session as cleanly ended. Later records are ignored.
+    pub fn finish(&self) {
+        let Some(mut journal) = self.journal.lock().unwrap().take() else {
+            return;
+        };
+        sync(&mut journal);
+        if let Err(e) = File::create(self.dir.join(CLEAN_MARKER)) {
+            warn!("[TAURI_MCP] Failed to mark session as cleanly ended: {}", e);
+        }
+    }
+
+    /// Load the recording of the previous session, if there was one.
+    pub fn last_session(&self) -> crate::Result<Option<RecoveredSession>> {
+        if self.dir.as_os_str().is_empty() {
+            return Ok(None);
+        }
+        let content = match fs::read_to_string(self.dir.join(LAST_FILE)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut entries = Vec::new();
+        let mut corrupt_lines = 0;
+        for line in content.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(_) => corrupt_lines += 1,
+            }
+        }
+
+        Ok(Some(RecoveredSession {
+            entries,
+            crashed: !self.dir.join(LAST_CLEAN_MARKER).exists(),
+            corrupt_lines,
+        }))
+    }
+}
+
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+fn sync(journal: &mut Journal) {
+    if journal.dirty {
+        if let Err(e) = journal.file.sync_data() {
+            warn!("[TAURI_MCP] Failed to fsync session recording: {}", e);
+        }
+        journal.dirty = false;
+    }
+    journal.last_sync = Instant::now();
+}
+
+/// Move the journal of the previous run to the "last session" slot.
+fn rotate_previous(dir: &Path) -> crate::Result<()> {
+    let current = dir.join(CURRENT_FILE);
+    if !current.exists() {
+        return Ok(());
+    }
+
+    let clean = dir.join(CLEAN_MARKER);
+    let was_clean = clean.exists();
+    if !was_clean {
+        warn!("[TAURI_MCP] Previous session did not end cleanly, its recording can be recovered");
+    }
+
+    fs::rename(&current, dir.join(LAST_FILE))?;
+    let last_clean = dir.join(LAST_CLEAN_MARKER);
+    if was_clean {
+        fs::rename(&clean, &last_clean)?;
+    } else if last_clean.exists() {
+        fs::remove_file(&last_clean)?;
+    }
+    Ok(())
+}
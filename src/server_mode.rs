@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+use crate::PluginConfig;
+
+/// Environment variable that permits the socket server in release builds.
+pub const ALLOW_IN_RELEASE_ENV: &str = "TAURI_MCP_ALLOW_IN_RELEASE";
+
+/// Safety mode the plugin is running in.
+///
+/// Release builds refuse to start the socket server unless the application
+/// explicitly opted in, so a remote-control endpoint is never shipped by accident.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServerMode {
+    /// Debug build, the socket server is always allowed.
+    Debug,
+    /// Release build with an explicit opt-in from the config or the environment.
+    ReleaseOptIn,
+    /// Release build without opt-in, the socket server is refused.
+    ReleaseBlocked,
+}
+
+impl ServerMode {
+    /// Resolve the mode from the build profile, the plugin config and the environment.
+    pub fn resolve(config: &PluginConfig) -> Self {
+        if cfg!(debug_assertions) {
+            ServerMode::Debug
+        } else if config.allow_in_release || env_opt_in() {
+            ServerMode::ReleaseOptIn
+        } else {
+            ServerMode::ReleaseBlocked
+        }
+    }
+
+    /// Whether the socket server is allowed to start in this mode.
+    pub fn allows_socket_server(self) -> bool {
+        !matches!(self, ServerMode::ReleaseBlocked)
+    }
+}
+
+/// Returns true when the release opt-in environment variable is set to a truthy value.
+pub fn env_opt_in() -> bool {
+    std::env::var(ALLOW_IN_RELEASE_ENV).is_ok_and(|v| is_truthy(&v))
+}
+
+/// Whether an environment variable value means yes: `1`, `true` or `yes`, in any case.
+pub(crate) fn is_truthy(value: &str) -> bool {
+    matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "1" | "true" | "yes"
+    )
+}
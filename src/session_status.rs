@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Event emitted to the webviews with the new [`SessionStatus`] whenever it changes.
+pub const SESSION_STATUS_EVENT: &str = "tauri-mcp:session-status";
+
+/// Callback told about the new [`SessionStatus`].
+pub type StatusListener = Arc<dyn Fn(&SessionStatus) + Send + Sync>;
+
+/// What the MCP session is doing with the app.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SessionState {
+    /// No client is connected.
+    Disconnected,
+    /// A client is connected and no tool is running.
+    Idle,
+    /// A tool is running.
+    Executing,
+}
+
+/// State of the MCP session, for apps showing that an agent is controlling them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionStatus {
+    pub state: SessionState,
+    pub connected_clients: usize,
+    /// Tool running, the latest started when several are.
+    pub command: Option<String>,
+}
+
+#[derive(Default)]
+struct Activity {
+    clients: usize,
+    running: Vec<String>,
+}
+
+impl Activity {
+    fn status(&self) -> SessionStatus {
+        let command = self.running.last().cloned();
+        let state = if command.is_some() {
+            SessionState::Executing
+        } else if self.clients > 0 {
+            SessionState::Idle
+        } else {
+            SessionState::Disconnected
+        };
+        SessionStatus {
+            state,
+            connected_clients: self.clients,
+            command,
+        }
+    }
+}
+
+/// Follows the clients and running tools, and tells the webviews and the app's listener
+/// when the session status changes.
+pub struct SessionActivity {
+    activity: Mutex<Activity>,
+    emit: StatusListener,
+    listener: RwLock<Option<StatusListener>>,
+}
+
+impl SessionActivity {
+    /// Activity reporting each new status to `emit`, e.g. as a webview event.
+    pub(crate) fn new(emit: StatusListener) -> Self {
+        Self {
+            activity: Mutex::new(Activity::default()),
+            emit,
+            listener: RwLock::new(None),
+        }
+    }
+
+    /// The current status of the session.
+    pub fn status(&self) -> SessionStatus {
+        self.activity.lock().unwrap().status()
+    }
+
+    /// Call `listener` with each new status, replacing the previous listener.
+    pub fn set_listener(&self, listener: StatusListener) {
+        *self.listener.write().unwrap() = Some(listener);
+    }
+
+    pub(crate) fn client_connected(&self) {
+        self.update(|activity| activity.clients += 1);
+    }
+
+    pub(crate) fn client_disconnected(&self) {
+        self.update(|activity| activity.clients = activity.clients.saturating_sub(1));
+    }
+
+    pub(crate) fn tool_started(&self, command: &str) {
+        self.update(|activity| activity.running.push(command.to_string()));
+    }
+
+    pub(crate) fn tool_finished(&self, command: &str) {
+        self.update(|activity| {
+            if let Some(index) = activity.running.iter().rposition(|c| c == command) {
+                activity.running.remove(index);
+            }
+        });
+    }
+
+    /// Apply `change` and report the new status if it differs.
+    fn update(&self, change: impl FnOnce(&mut Activity)) {
+        let (before, after) = {
+            let mut activity = self.activity.lock().unwrap();
+            let before = activity.status();
+            change(&mut activity);
+            (before, activity.status())
+        };
+        // Listeners run outside the lock, so they may read the status
+        if before != after {
+            (self.emit)(&after);
+            if let Some(listener) = self.listener.read().unwrap().clone() {
+                listener(&after);
+            }
+        }
+    }
+}
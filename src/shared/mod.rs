@@ -1,5 +1,14 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::{InputMode, TextInputFailure, TextInputStrategy};
+
+/// Version of the socket protocol spoken by this build, exchanged in the `handshake` command.
+/// Bump it on breaking changes to the commands or to the shared structs below.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Oldest client protocol version still accepted by this build.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
 /// Shared interface traits and types for the MCP server and Tauri plugin
 /// This ensures both sides maintain compatible function signatures
 
@@ -19,6 +28,8 @@ pub struct WindowManagerParams {
 pub struct WindowManagerResult {
     pub success: bool,
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
 }
 
 // Text input parameters
@@ -28,6 +39,21 @@ pub struct TextInputParams {
     pub text: String,
     pub delay_ms: Option<u64>,
     pub initial_delay_ms: Option<u64>,
+    #[serde(default, alias = "input_mode")]
+    pub input_mode: InputMode,
+    #[serde(default)]
+    pub strategy: TextInputStrategy,
+    #[serde(default = "default_window_label", alias = "window_label")]
+    pub window_label: String,
+    #[serde(default, alias = "focus_window")]
+    pub focus_window: bool,
+    #[serde(default, alias = "restore_focus")]
+    pub restore_focus: bool,
+}
+
+fn default_window_label() -> String {
+    // Left empty, the focused window is used, see `crate::windows::resolve_window`
+    String::new()
 }
 
 // Text input result
@@ -38,6 +64,8 @@ pub struct TextInputResult {
     pub chars_typed: u32,
     pub duration_ms: u64,
     pub error: Option<String>,
+    #[serde(default)]
+    pub failures: Vec<TextInputFailure>,
 }
 
 // Mouse movement parameters
@@ -49,6 +77,10 @@ pub struct MouseMovementParams {
     pub relative: Option<bool>,
     pub click: Option<bool>,
     pub button: Option<String>, // "left", "right", or "middle"
+    #[serde(default, alias = "input_mode")]
+    pub input_mode: InputMode,
+    #[serde(default = "default_window_label", alias = "window_label")]
+    pub window_label: String,
 }
 
 // Mouse movement result
@@ -95,4 +127,168 @@ pub mod commands {
     pub const SIMULATE_MOUSE_MOVEMENT: &str = "simulate_mouse_movement";
     pub const GET_ELEMENT_POSITION: &str = "get_element_position";
     pub const SEND_TEXT_TO_ELEMENT: &str = "send_text_to_element";
+    pub const GET_SERVER_MODE: &str = "get_server_mode";
+    pub const LIST_ARTIFACTS: &str = "list_artifacts";
+    pub const GET_ARTIFACT: &str = "get_artifact";
+    pub const DELETE_ARTIFACT: &str = "delete_artifact";
+    pub const GET_AUDIT_LOG: &str = "get_audit_log";
+    pub const DESCRIBE_TOOLS: &str = "describe_tools";
+    pub const POLL_EVENTS: &str = "poll_events";
+    pub const RECOVER_LAST_SESSION: &str = "recover_last_session";
+    pub const CANCEL: &str = "cancel";
+    pub const LIST_CLIENTS: &str = "list_clients";
+    pub const STATUS: &str = "status";
+    pub const GET_PAGE_TEXT: &str = "get_page_text";
+    pub const GET_ELEMENT_INFO: &str = "get_element_info";
+    pub const CLICK_ELEMENT: &str = "click_element";
+    pub const SET_ELEMENT_VALUE: &str = "set_element_value";
+    pub const SET_FILE_INPUT: &str = "set_file_input";
+    pub const LIST_DIALOGS: &str = "list_dialogs";
+    pub const RESPOND_TO_DIALOG: &str = "respond_to_dialog";
+    pub const SET_DIALOG_INTERCEPTION: &str = "set_dialog_interception";
+    pub const SET_DIALOG_POLICY: &str = "set_dialog_policy";
+    pub const CAPTURE_CANVAS: &str = "capture_canvas";
+    pub const PRINT_TO_PDF: &str = "print_to_pdf";
+    pub const GET_UI_SNAPSHOT: &str = "get_ui_snapshot";
+    pub const FOCUS_ELEMENT: &str = "focus_element";
+    pub const BLUR_ELEMENT: &str = "blur_element";
+    pub const FILL_FORM: &str = "fill_form";
+    pub const ASSERT_ELEMENT_EXISTS: &str = "assert_element_exists";
+    pub const ASSERT_ELEMENT_VISIBLE: &str = "assert_element_visible";
+    pub const ASSERT_TEXT_CONTAINS: &str = "assert_text_contains";
+    pub const ASSERT_URL_MATCHES: &str = "assert_url_matches";
+    pub const RUN_SCRIPT: &str = "run_script";
+    pub const START_INTERACTION_RECORDING: &str = "start_interaction_recording";
+    pub const STOP_INTERACTION_RECORDING: &str = "stop_interaction_recording";
+    pub const SET_EMULATION: &str = "set_emulation";
+    pub const ADVANCE_CLOCK: &str = "advance_clock";
+    pub const SET_GEOLOCATION: &str = "set_geolocation";
+    pub const SET_LOCALE: &str = "set_locale";
+    pub const SET_THEME: &str = "set_theme";
+    pub const EMULATE_DEVICE: &str = "emulate_device";
+    pub const SET_NETWORK_CONDITIONS: &str = "set_network_conditions";
+    pub const ADD_MOCK_ROUTE: &str = "add_mock_route";
+    pub const REMOVE_MOCK_ROUTE: &str = "remove_mock_route";
+    pub const GET_PROCESS_STATS: &str = "get_process_stats";
+    pub const GET_JS_ERRORS: &str = "get_js_errors";
+    pub const GET_APP_LOGS: &str = "get_app_logs";
+    pub const GET_APP_STATE: &str = "get_app_state";
+    pub const MANAGE_STORE: &str = "manage_store";
+    pub const QUERY_DB: &str = "query_db";
+    pub const READ_APP_FILE: &str = "read_app_file";
+    pub const WRITE_APP_FILE: &str = "write_app_file";
+    pub const TRIGGER_DEEP_LINK: &str = "trigger_deep_link";
+    pub const LIST_TRAYS: &str = "list_trays";
+    pub const TRIGGER_TRAY_MENU_ITEM: &str = "trigger_tray_menu_item";
+    pub const CLICK_TRAY: &str = "click_tray";
+    pub const LIST_MENU: &str = "list_menu";
+    pub const CLICK_MENU_ITEM: &str = "click_menu_item";
+    pub const PAUSE_SESSION: &str = "pause_session";
+    pub const RESUME_SESSION: &str = "resume_session";
+    pub const GET_INPUT_CONSENT: &str = "get_input_consent";
+    pub const SET_ACTION_OVERLAY: &str = "set_action_overlay";
+    pub const START_TRACE: &str = "start_trace";
+    pub const STOP_TRACE: &str = "stop_trace";
+    pub const CONVERT_COORDINATES: &str = "convert_coordinates";
+    pub const SIMULATE_KEY_PRESS: &str = "simulate_key_press";
+    pub const SIMULATE_TOUCH: &str = "simulate_touch";
+    pub const GENERATE_CLIENT_CONFIG: &str = "generate_client_config";
+    pub const INSPECT_ELEMENT: &str = "inspect_element";
+    pub const SCROLL_INTO_VIEW: &str = "scroll_into_view";
+    pub const HIGHLIGHT_ELEMENT: &str = "highlight_element";
+    pub const GET_PAGE_INFO: &str = "get_page_info";
+    pub const WAIT_FOR_NAVIGATION: &str = "wait_for_navigation";
+    pub const GET_DOWNLOADS: &str = "get_downloads";
+    pub const SET_PERMISSION_POLICY: &str = "set_permission_policy";
+    pub const CONTROL_MEDIA: &str = "control_media";
+    /// Handled by the socket server itself, changes the wire format of the connection.
+    pub const HANDSHAKE: &str = "handshake";
+
+    /// Every command understood by the socket server.
+    pub const ALL: &[&str] = &[
+        PING,
+        GET_DOM,
+        MANAGE_LOCAL_STORAGE,
+        EXECUTE_JS,
+        MANAGE_WINDOW,
+        SIMULATE_TEXT_INPUT,
+        SIMULATE_MOUSE_MOVEMENT,
+        GET_ELEMENT_POSITION,
+        SEND_TEXT_TO_ELEMENT,
+        GET_SERVER_MODE,
+        LIST_ARTIFACTS,
+        GET_ARTIFACT,
+        DELETE_ARTIFACT,
+        GET_AUDIT_LOG,
+        DESCRIBE_TOOLS,
+        POLL_EVENTS,
+        RECOVER_LAST_SESSION,
+        CANCEL,
+        LIST_CLIENTS,
+        STATUS,
+        GET_PAGE_TEXT,
+        GET_ELEMENT_INFO,
+        CLICK_ELEMENT,
+        SET_ELEMENT_VALUE,
+        SET_FILE_INPUT,
+        LIST_DIALOGS,
+        RESPOND_TO_DIALOG,
+        SET_DIALOG_INTERCEPTION,
+        SET_DIALOG_POLICY,
+        CAPTURE_CANVAS,
+        PRINT_TO_PDF,
+        GET_UI_SNAPSHOT,
+        FOCUS_ELEMENT,
+        BLUR_ELEMENT,
+        FILL_FORM,
+        ASSERT_ELEMENT_EXISTS,
+        ASSERT_ELEMENT_VISIBLE,
+        ASSERT_TEXT_CONTAINS,
+        ASSERT_URL_MATCHES,
+        RUN_SCRIPT,
+        START_INTERACTION_RECORDING,
+        STOP_INTERACTION_RECORDING,
+        SET_EMULATION,
+        ADVANCE_CLOCK,
+        SET_GEOLOCATION,
+        SET_LOCALE,
+        SET_THEME,
+        EMULATE_DEVICE,
+        SET_NETWORK_CONDITIONS,
+        ADD_MOCK_ROUTE,
+        REMOVE_MOCK_ROUTE,
+        GET_PROCESS_STATS,
+        GET_JS_ERRORS,
+        GET_APP_LOGS,
+        GET_APP_STATE,
+        MANAGE_STORE,
+        QUERY_DB,
+        READ_APP_FILE,
+        WRITE_APP_FILE,
+        TRIGGER_DEEP_LINK,
+        LIST_TRAYS,
+        TRIGGER_TRAY_MENU_ITEM,
+        CLICK_TRAY,
+        LIST_MENU,
+        CLICK_MENU_ITEM,
+        PAUSE_SESSION,
+        RESUME_SESSION,
+        GET_INPUT_CONSENT,
+        SET_ACTION_OVERLAY,
+        START_TRACE,
+        STOP_TRACE,
+        CONVERT_COORDINATES,
+        SIMULATE_KEY_PRESS,
+        SIMULATE_TOUCH,
+        GENERATE_CLIENT_CONFIG,
+        INSPECT_ELEMENT,
+        SCROLL_INTO_VIEW,
+        HIGHLIGHT_ELEMENT,
+        GET_PAGE_INFO,
+        WAIT_FOR_NAVIGATION,
+        GET_DOWNLOADS,
+        SET_PERMISSION_POLICY,
+        CONTROL_MEDIA,
+        HANDSHAKE,
+    ];
 }
@@ -0,0 +1,127 @@
+use log::{info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Grace period in-flight commands get to finish when the app exits.
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long cancelled commands get to send their error before the app goes on exiting.
+const CANCEL_GRACE: Duration = Duration::from_secs(1);
+
+/// Graceful shutdown of the socket commands: once begun, new commands are refused and
+/// long polls end, in-flight commands get a grace period, then the rest are cancelled.
+pub(crate) struct Shutdown {
+    begun: AtomicBool,
+    in_flight: Mutex<usize>,
+    idle: Condvar,
+    /// Cancelled when shutdown begins, ends the commands that only wait, like long polls.
+    stopping: CancellationToken,
+    /// Cancelled when the grace period is over, ends every command.
+    deadline: CancellationToken,
+    timeout: Duration,
+}
+
+impl Shutdown {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            begun: AtomicBool::new(false),
+            in_flight: Mutex::new(0),
+            idle: Condvar::new(),
+            stopping: CancellationToken::new(),
+            deadline: CancellationToken::new(),
+            timeout,
+        }
+    }
+
+    /// Whether the app is shutting down and refuses new commands.
+    pub fn is_shutting_down(&self) -> bool {
+        self.begun.load(Ordering::SeqCst)
+    }
+
+    pub fn in_flight(&self) -> usize {
+        *self.in_flight.lock().unwrap()
+    }
+
+    /// Count a command as in flight until the guard is dropped, after its response is sent.
+    pub fn track(&self) -> InFlightGuard<'_> {
+        *self.in_flight.lock().unwrap() += 1;
+        InFlightGuard { shutdown: self }
+    }
+
+    /// Cancelled when shutdown begins.
+    pub fn stopping(&self) -> &CancellationToken {
+        &self.stopping
+    }
+
+    /// Cancelled when the grace period of the in-flight commands is over.
+    pub fn deadline(&self) -> &CancellationToken {
+        &self.deadline
+    }
+
+    /// Refuse new commands from now on. Returns false if shutdown had already begun.
+    pub fn begin(&self) -> bool {
+        if self.begun.swap(true, Ordering::SeqCst) {
+            return false;
+        }
+        self.stopping.cancel();
+        true
+    }
+
+    /// Wait for the in-flight commands, cancelling the ones still running after the grace
+    /// period. Blocks the calling thread.
+    pub fn drain(&self) {
+        let pending = self.in_flight();
+        if pending == 0 {
+            return;
+        }
+        info!(
+            "[TAURI_MCP] Waiting up to {:?} for {} in-flight commands",
+            self.timeout, pending
+        );
+        let remaining = self.wait_idle(self.timeout);
+        if remaining > 0 {
+            warn!(
+                "[TAURI_MCP] Cancelling {} commands still running at shutdown",
+                remaining
+            );
+            self.deadline.cancel();
+            let remaining = self.wait_idle(CANCEL_GRACE);
+            if remaining > 0 {
+                warn!(
+                    "[TAURI_MCP] {} commands did not answer before shutdown",
+                    remaining
+                );
+            }
+        }
+    }
+
+    /// Wait until no command is in flight or `timeout` passed, returning how many are left.
+    fn wait_idle(&self, timeout: Duration) -> usize {
+        let deadline = Instant::now() + timeout;
+        let mut in_flight = self.in_flight.lock().unwrap();
+        while *in_flight > 0 {
+            let Some(left) = deadline.checked_duration_since(Instant::now()) else {
+                break;
+            };
+            in_flight = self.idle.wait_timeout(in_flight, left).unwrap().0;
+        }
+        *in_flight
+    }
+}
+
+/// Marks a command in flight, see [`Shutdown::track`].
+pub(crate) struct InFlightGuard<'a> {
+    shutdown: &'a Shutdown,
+}
+
+impl Drop for InFlightGuard<'_> {
+    fn drop(&mut self) {
+        let mut in_flight = self.shutdown.in_flight.lock().unwrap();
+        *in_flight -= 1;
+        if *in_flight == 0 {
+            self.shutdown.idle.notify_all();
+        }
+    }
+}
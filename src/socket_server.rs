@@ -3,21 +3,50 @@ use interprocess::local_socket::{
     GenericFilePath, GenericNamespaced, Listener as IpcListener, ListenerOptions, Name,
     Stream as IpcStream, ToFsName, ToNsName, prelude::*,
 };
-use log::{error, info};
+#[cfg(unix)]
+use interprocess::os::unix::local_socket::ListenerOptionsExt;
+use log::{Level, error, info, log_enabled, trace, warn};
+use regex::Regex;
 use serde_json::Value;
-use std::io::{BufRead, BufReader, Read, Write};
+use std::io::{BufReader, Read, Write};
 use std::net::{TcpListener, TcpStream};
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, LazyLock, Mutex};
 use std::thread;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Runtime};
 
 use serde::{Deserialize, Serialize};
 
 use crate::SocketType;
+use crate::TauriMcpExt;
+use crate::clients::{ClientHandle, ClientRegistry};
+use crate::discovery;
 use crate::error::Error;
+use crate::events::EventBus;
+use crate::keepalive::{ConnectionWatch, KeepaliveSettings};
+#[cfg(feature = "mdns")]
+use crate::mdns::MdnsAdvertiser;
+use crate::protocol::{
+    CompressionSettings, DEFAULT_CHUNK_SIZE, HandshakeRequest, HandshakeResponse, ServerProtocol,
+    WireFormat, split_chunks,
+};
+use crate::rate_limit::{ConnectionLimiter, RateLimits};
+use crate::shared::commands;
+use crate::status::ServerStats;
 use crate::tools;
 
-/// A wrapper stream that logs all reads and writes for debugging
+/// `"token": "..."` fields in raw JSON, as sent in handshakes.
+static TOKEN_FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#""token"\s*:\s*"(?:[^"\\]|\\.)*""#).unwrap());
+
+/// The bytes as text for the logs, with the values of `token` fields hidden.
+fn redacted_text(buf: &[u8]) -> String {
+    TOKEN_FIELD
+        .replace_all(&String::from_utf8_lossy(buf), r#""token":"[redacted]""#)
+        .into_owned()
+}
+
+/// A wrapper stream that logs all reads and writes at trace level for debugging
 struct LoggingStream<S: Write + Read> {
     inner: S,
 }
@@ -30,7 +59,9 @@ impl<S: Write + Read> LoggingStream<S> {
 
 impl<S: Write + Read> Write for LoggingStream<S> {
     fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
-        info!("[TAURI_MCP] Writing: {}", String::from_utf8_lossy(buf));
+        if log_enabled!(Level::Trace) {
+            trace!("[TAURI_MCP] Writing: {}", redacted_text(buf));
+        }
         self.inner.write(buf)
     }
 
@@ -42,7 +73,9 @@ impl<S: Write + Read> Write for LoggingStream<S> {
 impl<S: Write + Read> Read for LoggingStream<S> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let n = self.inner.read(buf)?;
-        info!("[TAURI_MCP] Read: {}", String::from_utf8_lossy(&buf[..n]));
+        if log_enabled!(Level::Trace) {
+            trace!("[TAURI_MCP] Read: {}", redacted_text(&buf[..n]));
+        }
         Ok(n)
     }
 }
@@ -50,8 +83,17 @@ impl<S: Write + Read> Read for LoggingStream<S> {
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct SocketRequest {
+    /// Optional client-chosen ID, echoed in the response and usable with the `cancel` command.
+    #[serde(default)]
+    id: Option<String>,
     command: String,
     payload: Value,
+    /// Send large results as a sequence of chunks. Requires an `id`.
+    #[serde(default)]
+    stream: bool,
+    /// Maximum size of each chunk in bytes when streaming.
+    #[serde(default)]
+    chunk_size: Option<usize>,
 }
 
 #[derive(Debug, Serialize)]
@@ -62,6 +104,16 @@ pub struct SocketResponse {
     pub error: Option<String>,
 }
 
+/// How long binding is retried while the address is in use, e.g. by the exiting
+/// instance of a restarted app.
+const REBIND_WINDOW: Duration = Duration::from_secs(2);
+
+const REBIND_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+/// Control commands that bypass the rate limits, so a client whose slots are taken by long
+/// calls can still cancel them and check on the server.
+const UNLIMITED_COMMANDS: &[&str] = &[commands::CANCEL, commands::PING, commands::STATUS];
+
 /// Unified stream type that can handle both IPC and TCP
 enum UnifiedStream {
     Ipc(IpcStream),
@@ -100,6 +152,24 @@ impl UnifiedStream {
             UnifiedStream::Tcp(stream) => Ok(UnifiedStream::Tcp(stream.try_clone()?)),
         }
     }
+
+    /// Close the connection for every handle of it, waking up a blocked read.
+    fn shutdown(&self) -> std::io::Result<()> {
+        match self {
+            UnifiedStream::Tcp(stream) => stream.shutdown(std::net::Shutdown::Both),
+            #[cfg(unix)]
+            UnifiedStream::Ipc(IpcStream::UdSocket(stream)) => {
+                use std::os::fd::AsFd;
+                // Shutting down a duplicate of the socket closes it for all of them
+                let fd = stream.as_fd().try_clone_to_owned()?;
+                std::os::unix::net::UnixStream::from(fd).shutdown(std::net::Shutdown::Both)
+            }
+            // Named pipes cannot be closed while another thread reads them, the read ends
+            // when the client goes away
+            #[cfg(windows)]
+            UnifiedStream::Ipc(_) => Ok(()),
+        }
+    }
 }
 
 /// Unified listener type that can handle both IPC and TCP
@@ -113,24 +183,34 @@ pub struct SocketServer<R: Runtime> {
     socket_type: SocketType,
     app: AppHandle<R>,
     running: Arc<Mutex<bool>>,
+    listener_thread: Option<thread::JoinHandle<()>>,
+    events: Arc<EventBus>,
+    limits: RateLimits,
+    clients: Arc<ClientRegistry>,
+    stats: Arc<ServerStats>,
+    application_name: String,
+    advertise_mdns: bool,
+    auth_token: Option<Arc<str>>,
+    keepalive: KeepaliveSettings,
+    #[cfg(feature = "mdns")]
+    mdns: Option<MdnsAdvertiser>,
 }
 
 impl<R: Runtime> SocketServer<R> {
-    pub fn new(app: AppHandle<R>, socket_type: SocketType) -> Self {
+    pub fn new(
+        app: AppHandle<R>,
+        socket_type: SocketType,
+        events: Arc<EventBus>,
+        limits: RateLimits,
+        clients: Arc<ClientRegistry>,
+        stats: Arc<ServerStats>,
+        application_name: String,
+    ) -> Self {
         match &socket_type {
             SocketType::Ipc { path } => {
-                let socket_path = if let Some(path) = path {
-                    path.to_string_lossy().to_string()
-                } else {
-                    let temp_dir = std::env::temp_dir();
-                    temp_dir
-                        .join("tauri-mcp.sock")
-                        .to_string_lossy()
-                        .to_string()
-                };
                 info!(
                     "[TAURI_MCP] Initializing IPC socket server at: {}",
-                    socket_path
+                    ipc_path(path, &application_name).display()
                 );
             }
             SocketType::Tcp { host, port } => {
@@ -146,54 +226,89 @@ impl<R: Runtime> SocketServer<R> {
             socket_type,
             app,
             running: Arc::new(Mutex::new(false)),
+            listener_thread: None,
+            events,
+            limits,
+            clients,
+            stats,
+            application_name,
+            advertise_mdns: false,
+            auth_token: None,
+            keepalive: KeepaliveSettings::default(),
+            #[cfg(feature = "mdns")]
+            mdns: None,
         }
     }
 
+    /// Advertise the TCP transport over mDNS while the server runs.
+    pub fn set_advertise_mdns(&mut self, enabled: bool) {
+        self.advertise_mdns = enabled;
+    }
+
+    /// Require clients to send `token` in their handshake, from the next start.
+    pub fn set_auth_token(&mut self, token: Option<String>) {
+        self.auth_token = token.map(Arc::from);
+    }
+
+    /// Keepalive messages and idle timeout of the connections, from the next start.
+    pub fn set_keepalive(&mut self, settings: KeepaliveSettings) {
+        self.keepalive = settings;
+    }
+
+    /// Whether the server is accepting connections.
+    pub fn is_running(&self) -> bool {
+        *self.running.lock().unwrap()
+    }
+
     pub fn start(&mut self) -> crate::Result<()> {
+        if self.is_running() {
+            info!("[TAURI_MCP] Socket server already running");
+            return Ok(());
+        }
         info!("[TAURI_MCP] Starting socket server...");
 
-        let listener = match &self.socket_type {
-            SocketType::Ipc { path } => {
-                // Create a name for our socket based on the platform
-                let socket_name = self.get_socket_name(path)?;
-
-                // Configure and create the IPC listener
-                let opts = ListenerOptions::new().name(socket_name);
-                let ipc_listener = opts.create_sync()
-                    .map_err(|e| {
-                        info!("[TAURI_MCP] Error creating IPC socket listener: {}", e);
-                        if e.kind() == std::io::ErrorKind::AddrInUse {
-                            Error::Io(format!("Socket address already in use. If the socket file exists, it may be a stale socket. Try removing it manually."))
-                        } else {
-                            Error::Io(format!("Failed to create local socket: {}", e))
-                        }
-                    })?;
-                UnifiedListener::Ipc(ipc_listener)
-            }
-            SocketType::Tcp { host, port } => {
-                // Create TCP listener
-                let addr = format!("{}:{}", host, port);
-                let tcp_listener = TcpListener::bind(&addr).map_err(|e| {
-                    info!("[TAURI_MCP] Error creating TCP socket listener: {}", e);
-                    Error::Io(format!("Failed to bind to {}: {}", addr, e))
-                })?;
-                UnifiedListener::Tcp(tcp_listener)
+        // A restarted app may come up while its previous instance still holds the address
+        let bind_started = Instant::now();
+        let listener = loop {
+            match self.bind() {
+                Ok(listener) => break listener,
+                Err(e)
+                    if e.kind() == std::io::ErrorKind::AddrInUse
+                        && bind_started.elapsed() < REBIND_WINDOW =>
+                {
+                    thread::sleep(REBIND_RETRY_DELAY);
+                }
+                Err(e) => {
+                    info!("[TAURI_MCP] Error creating socket listener: {}", e);
+                    return Err(Error::Io(format!(
+                        "Failed to listen on {}: {}",
+                        endpoint_address(&self.socket_type, &self.application_name),
+                        e
+                    )));
+                }
             }
         };
 
         let listener = Arc::new(Mutex::new(listener));
         self.listener = Some(listener.clone());
 
-        *self.running.lock().unwrap() = true;
+        // A fresh flag per run, so connections of a previous run stay stopped after a restart
+        self.running = Arc::new(Mutex::new(true));
         info!("[TAURI_MCP] Set running flag to true");
 
         let app = self.app.clone();
         let running = self.running.clone();
         let socket_type = self.socket_type.clone();
+        let events = self.events.clone();
+        let limits = self.limits;
+        let clients = self.clients.clone();
+        let stats = self.stats.clone();
+        let auth_token = self.auth_token.clone();
+        let keepalive = self.keepalive;
 
         // Spawn a thread to handle socket connections
         info!("[TAURI_MCP] Spawning listener thread");
-        thread::spawn(move || {
+        self.listener_thread = Some(thread::spawn(move || {
             match &socket_type {
                 SocketType::Ipc { .. } => {
                     info!("[TAURI_MCP] Listener thread started for IPC socket");
@@ -246,6 +361,11 @@ impl<R: Runtime> SocketServer<R> {
                                 Ok(stream) => {
                                     info!("[TAURI_MCP] Accepted new IPC connection");
                                     let app_clone = app.clone();
+                                    let events = events.clone();
+                                    let running = running.clone();
+                                    let stats = stats.clone();
+                                    let auth_token = auth_token.clone();
+                                    let client = clients.connect("ipc", None);
                                     let unified_stream = UnifiedStream::Ipc(stream);
 
                                     // Spawn a new thread with its own panic handler for client handling
@@ -279,20 +399,46 @@ impl<R: Runtime> SocketServer<R> {
                                             }
                                         }));
 
+                                        let peer = serde_json::json!({
+                                            "clientId": client.id(),
+                                            "transport": "ipc",
+                                        });
+                                        events.publish("clientConnected", peer.clone());
+
                                         // Handle the client with error trapping
-                                        if let Err(e) = handle_client(unified_stream, app_clone) {
+                                        if let Err(e) = handle_client(
+                                            unified_stream,
+                                            app_clone.clone(),
+                                            limits,
+                                            auth_token.as_deref(),
+                                            keepalive,
+                                            &client,
+                                            &running,
+                                        ) {
                                             if e.to_string().contains(
                                                 "No process is on the other end of the pipe",
                                             ) {
                                                 info!("[TAURI_MCP] Client disconnected normally");
                                             } else {
                                                 error!("[TAURI_MCP] Error handling client: {}", e);
+                                                stats.record_error(&e.to_string());
                                             }
                                         }
+
+                                        drop(client);
+                                        events.publish("clientDisconnected", peer);
+                                        app_clone
+                                            .tauri_mcp()
+                                            .input_consent()
+                                            .client_disconnected(&app_clone);
                                     });
                                 }
                                 Err(e) => {
                                     error!("[TAURI_MCP] Error accepting IPC connection: {}", e);
+                                    stats.record_error(&format!(
+                                        "Error accepting IPC connection: {}",
+                                        e
+                                    ));
                                     // Short sleep to avoid busy waiting in case of persistent errors
                                     std::thread::sleep(std::time::Duration::from_millis(100));
                                 }
@@ -328,14 +474,42 @@ impl<R: Runtime> SocketServer<R> {
                                     }
 
                                     let app_clone = app.clone();
+                                    let events = events.clone();
+                                    let running = running.clone();
+                                    let stats = stats.clone();
+                                    let auth_token = auth_token.clone();
+                                    let client = clients.connect("tcp", Some(addr.to_string()));
                                     let unified_stream = UnifiedStream::Tcp(stream);
 
                                     // Spawn a new thread for client handling
                                     thread::spawn(move || {
+                                        let peer = serde_json::json!({
+                                            "clientId": client.id(),
+                                            "transport": "tcp",
+                                            "address": addr.to_string(),
+                                        });
+                                        events.publish("clientConnected", peer.clone());
+
                                         // Handle the client with error trapping
-                                        if let Err(e) = handle_client(unified_stream, app_clone) {
+                                        if let Err(e) = handle_client(
+                                            unified_stream,
+                                            app_clone.clone(),
+                                            limits,
+                                            auth_token.as_deref(),
+                                            keepalive,
+                                            &client,
+                                            &running,
+                                        ) {
                                             error!("[TAURI_MCP] Error handling TCP client: {}", e);
+                                            stats.record_error(&e.to_string());
                                         }
+
+                                        drop(client);
+                                        events.publish("clientDisconnected", peer);
+                                        app_clone
+                                            .tauri_mcp()
+                                            .input_consent()
+                                            .client_disconnected(&app_clone);
                                     });
                                 }
                                 Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => {
@@ -344,6 +518,10 @@ impl<R: Runtime> SocketServer<R> {
                                 }
                                 Err(e) => {
                                     error!("[TAURI_MCP] Error accepting TCP connection: {}", e);
+                                    stats.record_error(&format!(
+                                        "Error accepting TCP connection: {}",
+                                        e
+                                    ));
                                     std::thread::sleep(std::time::Duration::from_millis(100));
                                 }
                             }
@@ -352,21 +530,18 @@ impl<R: Runtime> SocketServer<R> {
                 }
             }
             info!("[TAURI_MCP] Listener thread ending");
-        });
+        }));
+        self.stats.mark_started();
+        discovery::advertise(&self.application_name, &self.socket_type);
+        if self.advertise_mdns {
+            self.start_mdns();
+        }
 
         match &self.socket_type {
             SocketType::Ipc { path } => {
-                let display_path = if let Some(p) = path {
-                    p.to_string_lossy().to_string()
-                } else {
-                    std::env::temp_dir()
-                        .join("tauri-mcp.sock")
-                        .to_string_lossy()
-                        .to_string()
-                };
                 info!(
                     "[TAURI_MCP] Socket server started successfully at {}",
-                    display_path
+                    ipc_path(path, &self.application_name).display()
                 );
             }
             SocketType::Tcp { host, port } => {
@@ -379,27 +554,103 @@ impl<R: Runtime> SocketServer<R> {
         Ok(())
     }
 
-    pub fn stop(&self) -> crate::Result<()> {
+    /// Stop accepting connections and release the socket.
+    ///
+    /// Connected clients are answered with an error and disconnected when they send their
+    /// next request.
+    pub fn stop(&mut self) -> crate::Result<()> {
+        if !self.is_running() {
+            return Ok(());
+        }
         info!("[TAURI_MCP] Stopping socket server");
         // Set running flag to false to stop the server thread
         *self.running.lock().unwrap() = false;
 
-        // The interprocess crate automatically cleans up the socket file on drop for Unix platforms
+        // The IPC listener blocks on accept, wake it up with a throwaway connection
+        if let SocketType::Ipc { path } = &self.socket_type {
+            let name = self.get_socket_name(path)?;
+            if let Err(e) = IpcStream::connect(name) {
+                warn!("[TAURI_MCP] Failed to wake up the listener thread: {}", e);
+            }
+        }
+        if let Some(thread) = self.listener_thread.take() {
+            let _ = thread.join();
+        }
+
+        // The interprocess crate cleans up the socket file on drop for Unix platforms, remove
+        // it in case it did not, so no client connects to a dead socket
+        self.listener = None;
+        #[cfg(unix)]
+        if let SocketType::Ipc { .. } = &self.socket_type {
+            let path = endpoint_address(&self.socket_type, &self.application_name);
+            if let Err(e) = std::fs::remove_file(&path)
+                && e.kind() != std::io::ErrorKind::NotFound
+            {
+                warn!("[TAURI_MCP] Failed to remove socket {}: {}", path, e);
+            }
+        }
+        self.stats.mark_stopped();
+        discovery::withdraw();
+        #[cfg(feature = "mdns")]
+        {
+            self.mdns = None;
+        }
         info!("[TAURI_MCP] Socket server stopped");
         Ok(())
     }
 
-    #[cfg(desktop)]
-    fn get_socket_name(&self, path: &Option<std::path::PathBuf>) -> Result<Name, Error> {
-        let socket_path = if let Some(p) = path {
-            p.to_string_lossy().to_string()
-        } else {
-            let temp_dir = std::env::temp_dir();
-            temp_dir
-                .join("tauri-mcp.sock")
-                .to_string_lossy()
-                .to_string()
+    /// Create the listener, failing with `AddrInUse` while another process holds the address.
+    fn bind(&self) -> std::io::Result<UnifiedListener> {
+        match &self.socket_type {
+            SocketType::Ipc { path } => {
+                // A crashed run may have left its socket file behind
+                #[cfg(unix)]
+                remove_stale_socket(std::path::Path::new(&endpoint_address(
+                    &self.socket_type,
+                    &self.application_name,
+                )))?;
+
+                // Create a name for our socket based on the platform
+                let socket_name = self
+                    .get_socket_name(path)
+                    .map_err(|e| std::io::Error::other(e.to_string()))?;
+
+                // Configure and create the IPC listener, only accessible to the current user
+                let opts = ListenerOptions::new().name(socket_name);
+                #[cfg(unix)]
+                let opts = opts.mode(0o600);
+                Ok(UnifiedListener::Ipc(opts.create_sync()?))
+            }
+            SocketType::Tcp { host, port } => Ok(UnifiedListener::Tcp(TcpListener::bind(
+                format!("{}:{}", host, port),
+            )?)),
+        }
+    }
+
+    fn start_mdns(&mut self) {
+        let SocketType::Tcp { host, port } = &self.socket_type else {
+            warn!("[TAURI_MCP] mDNS advertisement is only available with the TCP transport");
+            return;
         };
+        #[cfg(feature = "mdns")]
+        match MdnsAdvertiser::start(&self.application_name, host, *port) {
+            Ok(advertiser) => self.mdns = Some(advertiser),
+            Err(e) => {
+                warn!("[TAURI_MCP] {}", e);
+                self.stats.record_error(&e.to_string());
+            }
+        }
+        #[cfg(not(feature = "mdns"))]
+        {
+            let _ = (host, port);
+            warn!("[TAURI_MCP] mDNS advertisement requires the `mdns` feature");
+        }
+    }
+
+    fn get_socket_name(&self, path: &Option<std::path::PathBuf>) -> Result<Name, Error> {
+        let socket_path = ipc_path(path, &self.application_name)
+            .to_string_lossy()
+            .to_string();
 
         if cfg!(target_os = "windows") {
             // Use named pipe on Windows
@@ -416,163 +667,546 @@ impl<R: Runtime> SocketServer<R> {
     }
 }
 
-fn handle_client<R: Runtime>(stream: UnifiedStream, app: AppHandle<R>) -> crate::Result<()> {
-    info!("[TAURI_MCP] Handling new client connection");
-    // Use tokio runtime to handle async functions
+/// Remove a socket file left behind by a previous run, after checking nothing listens on it.
+#[cfg(unix)]
+fn remove_stale_socket(path: &std::path::Path) -> std::io::Result<()> {
+    use std::io::{Error as IoError, ErrorKind};
+    use std::os::unix::fs::FileTypeExt;
+
+    let Ok(metadata) = std::fs::symlink_metadata(path) else {
+        return Ok(());
+    };
+    if !metadata.file_type().is_socket() {
+        return Err(IoError::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "{} exists and is not a socket, refusing to replace it",
+                path.display()
+            ),
+        ));
+    }
+
+    match std::os::unix::net::UnixStream::connect(path) {
+        Ok(_) => Err(IoError::new(
+            ErrorKind::AddrInUse,
+            "another instance is already listening",
+        )),
+        Err(e) if e.kind() == ErrorKind::ConnectionRefused => {
+            warn!("[TAURI_MCP] Removing stale socket {}", path.display());
+            std::fs::remove_file(path)
+        }
+        Err(e) => Err(IoError::new(
+            e.kind(),
+            format!("failed to check the existing socket: {}", e),
+        )),
+    }
+}
+
+/// Path of an IPC socket, the app's default one when the config sets none.
+pub(crate) fn ipc_path(
+    path: &Option<std::path::PathBuf>,
+    application_name: &str,
+) -> std::path::PathBuf {
+    path.clone()
+        .unwrap_or_else(|| discovery::default_socket_path(application_name))
+}
+
+/// Human-readable address of the socket: its path for IPC, host:port for TCP.
+pub(crate) fn endpoint_address(socket_type: &SocketType, application_name: &str) -> String {
+    match socket_type {
+        SocketType::Ipc { path } => ipc_path(path, application_name)
+            .to_string_lossy()
+            .to_string(),
+        SocketType::Tcp { host, port } => format!("{}:{}", host, port),
+    }
+}
+
+/// Write half of a connection, shared by the tasks answering requests.
+struct ResponseWriter {
+    stream: LoggingStream<UnifiedStream>,
+    format: WireFormat,
+    compression: CompressionSettings,
+}
+
+type SharedWriter = Arc<Mutex<ResponseWriter>>;
+
+/// Whether an IO error means the client went away.
+fn is_disconnect(e: &std::io::Error) -> bool {
+    e.to_string()
+        .contains("No process is on the other end of the pipe")
+        || e.kind() == std::io::ErrorKind::BrokenPipe
+}
+
+/// Serialize a response and tag it with the ID of the request it answers.
+fn response_message(response: &SocketResponse, id: Option<String>) -> std::io::Result<Value> {
+    let mut message = serde_json::to_value(response).map_err(std::io::Error::other)?;
+    if let Some(id) = id {
+        message["id"] = Value::String(id);
+    }
+    Ok(message)
+}
+
+impl ResponseWriter {
+    fn send(&mut self, message: &Value) -> std::io::Result<()> {
+        let body = self.format.serialize(message)?;
+        let id = message.get("id").and_then(Value::as_str);
+        let body = self.compression.apply(&self.format, id, body)?;
+        let bytes = self.format.frame(body)?;
+        info!(
+            "[TAURI_MCP] Sending response: length = {} bytes",
+            bytes.len()
+        );
+        self.stream.write_all(&bytes)?;
+        self.stream.flush()
+    }
+}
+
+/// Write a response as a single message, tagged with the ID of the request it answers.
+fn write_response(
+    writer: &SharedWriter,
+    response: &SocketResponse,
+    id: Option<String>,
+) -> std::io::Result<()> {
+    let message = response_message(response, id)?;
+    // Hold the lock for the whole message so concurrent responses are not interleaved
+    writer.lock().unwrap().send(&message)
+}
+
+/// Write a response as a sequence of chunks followed by a terminator.
+///
+/// The serialized `data` is split into `{"id", "chunk": {"seq", "data"}}` messages; the final
+/// message is the response itself with `data` omitted and `streamed` set to the chunk count.
+/// The lock is released between chunks so other responses are not held back.
+fn write_streamed(
+    writer: &SharedWriter,
+    response: SocketResponse,
+    id: String,
+    chunk_size: usize,
+) -> std::io::Result<()> {
+    let serialized = match &response.data {
+        Some(data) => serde_json::to_string(data).map_err(std::io::Error::other)?,
+        None => return write_response(writer, &response, Some(id)),
+    };
+    if serialized.len() <= chunk_size {
+        return write_response(writer, &response, Some(id));
+    }
+
+    let chunks = split_chunks(&serialized, chunk_size);
+    for (seq, chunk) in chunks.iter().enumerate() {
+        let message = serde_json::json!({
+            "id": id,
+            "chunk": { "seq": seq, "data": chunk },
+        });
+        writer.lock().unwrap().send(&message)?;
+    }
+
+    let mut terminator = response_message(
+        &SocketResponse {
+            success: response.success,
+            data: None,
+            error: response.error,
+        },
+        Some(id),
+    )?;
+    terminator["streamed"] = serde_json::json!({
+        "chunks": chunks.len(),
+        "totalBytes": serialized.len(),
+    });
+    writer.lock().unwrap().send(&terminator)
+}
+
+/// Answer a `handshake` request and switch the connection to the requested wire format
+/// and compression.
+///
+/// The answer uses the previous format; later messages in both directions use the new one.
+/// Compression only applies to messages sent by the plugin.
+/// Returns false when the client is incompatible and the connection must be closed.
+fn negotiate(
+    writer: &SharedWriter,
+    format: &mut WireFormat,
+    watch: &ConnectionWatch,
+    payload: Value,
+    id: Option<String>,
+) -> std::io::Result<bool> {
+    let current = writer.lock().unwrap().compression;
+    let params = serde_json::from_value::<HandshakeRequest>(payload)
+        .map_err(|e| format!("Invalid payload for handshake: {}", e));
+
+    if let Ok(params) = &params
+        && let Err(e) = params.check_compatibility()
+    {
+        warn!("[TAURI_MCP] Rejecting incompatible client: {}", e);
+        let response = SocketResponse {
+            success: false,
+            data: serde_json::to_value(ServerProtocol::current()).ok(),
+            error: Some(e),
+        };
+        writer
+            .lock()
+            .unwrap()
+            .send(&response_message(&response, id)?)?;
+        return Ok(false);
+    }
+
+    let requested = params.and_then(|params| {
+        let requested = WireFormat {
+            framing: params.framing.unwrap_or(format.framing),
+            encoding: params.encoding.unwrap_or(format.encoding),
+        };
+        let compression = CompressionSettings {
+            algorithm: params.compression.unwrap_or(current.algorithm),
+            threshold: params.compression_threshold.unwrap_or(current.threshold),
+        };
+        let wants_keepalive = params.keepalive.unwrap_or(false);
+        requested
+            .validate()
+            .map(|_| (requested, compression, wants_keepalive))
+    });
+
+    let settings = watch.settings();
+    let response = match &requested {
+        Ok((requested, compression, wants_keepalive)) => SocketResponse {
+            success: true,
+            data: serde_json::to_value(HandshakeResponse {
+                server: ServerProtocol::current(),
+                framing: requested.framing,
+                encoding: requested.encoding,
+                compression: *compression,
+                keepalive_interval_ms: settings
+                    .interval
+                    .filter(|_| *wants_keepalive)
+                    .map(|interval| interval.as_millis() as u64),
+                idle_timeout_ms: settings
+                    .idle_timeout
+                    .map(|timeout| timeout.as_millis() as u64),
+            })
+            .ok(),
+            error: None,
+        },
+        Err(e) => SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.clone()),
+        },
+    };
+
+    // Keep the lock while switching so no response is sent in between
+    let mut writer = writer.lock().unwrap();
+    writer.send(&response_message(&response, id)?)?;
+    if let Ok((requested, compression, wants_keepalive)) = requested {
+        info!(
+            "[TAURI_MCP] Switching connection to {:?}, compression {:?}",
+            requested, compression
+        );
+        writer.format = requested;
+        writer.compression = compression;
+        *format = requested;
+        if wants_keepalive {
+            watch.enable_keepalive();
+        }
+    }
+    Ok(true)
+}
+
+fn handle_client<R: Runtime>(
+    stream: UnifiedStream,
+    app: AppHandle<R>,
+    limits: RateLimits,
+    auth_token: Option<&str>,
+    keepalive: KeepaliveSettings,
+    client: &ClientHandle,
+    running: &Mutex<bool>,
+) -> crate::Result<()> {
+    info!(
+        "[TAURI_MCP] Handling new client connection (client {})",
+        client.id()
+    );
+    // Requests run as independent tasks on a runtime dedicated to this client
     let rt = tokio::runtime::Runtime::new()
         .map_err(|e| Error::Anyhow(format!("Failed to create runtime: {}", e)))?;
 
-    rt.block_on(async {
-        // Create a buffered reader and separate writer for the socket
-        let stream_clone = match stream.try_clone() {
-            Ok(clone) => clone,
-            Err(e) => {
-                // This might be a disconnection error on Windows
-                if e.to_string()
-                    .contains("No process is on the other end of the pipe")
+    // Create a buffered reader and separate writer for the socket
+    let stream_clone = match stream.try_clone() {
+        Ok(clone) => clone,
+        Err(e) => {
+            // This might be a disconnection error on Windows
+            if e.to_string()
+                .contains("No process is on the other end of the pipe")
+            {
+                info!("[TAURI_MCP] Client already disconnected (pipe error)");
+                return Ok(());
+            }
+            return Err(Error::Io(format!("Failed to clone stream: {}", e)));
+        }
+    };
+
+    let closer = stream.try_clone();
+
+    // Wrap the streams with our logging wrapper
+    let logging_reader = LoggingStream::new(stream_clone);
+    let mut reader = BufReader::new(logging_reader);
+    let mut format = WireFormat::default();
+    let writer: SharedWriter = Arc::new(Mutex::new(ResponseWriter {
+        stream: LoggingStream::new(stream),
+        format,
+        compression: CompressionSettings::default(),
+    }));
+
+    // Keepalives and the idle timeout are handled beside the reads, closing the connection
+    // under them when it is dead or idle
+    let watch = ConnectionWatch::new(keepalive);
+    let _watch_guard = watch.close_on_drop();
+    match closer {
+        Ok(closer) => {
+            let keepalive_writer = writer.clone();
+            watch.spawn_watchdog(
+                move |message| keepalive_writer.lock().unwrap().send(message),
+                move || {
+                    if let Err(e) = closer.shutdown() {
+                        warn!("[TAURI_MCP] Failed to close connection: {}", e);
+                    }
+                },
+            );
+        }
+        Err(e) => warn!(
+            "[TAURI_MCP] Keepalive and idle timeout disabled for client {}: {}",
+            client.id(),
+            e
+        ),
+    }
+    let mut limiter = ConnectionLimiter::new(limits);
+    let mut authenticated = auth_token.is_none();
+    let mut in_flight: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+
+    // Keep handling requests until the client disconnects
+    loop {
+        let message = match format.read_message(&mut reader) {
+            Ok(None) => {
+                // End of stream, answer the pending requests before closing
+                info!("[TAURI_MCP] Client disconnected cleanly");
+                rt.block_on(async {
+                    for task in in_flight {
+                        let _ = task.await;
+                    }
+                });
+                return Ok(());
+            }
+            Ok(Some(message)) if !*running.lock().unwrap() => {
+                info!("[TAURI_MCP] Socket server stopped, closing client connection");
+                // Answer the request so the client does not wait for it
+                let id = format
+                    .decode::<SocketRequest>(&message)
+                    .ok()
+                    .and_then(|request| request.id);
+                let response = SocketResponse {
+                    success: false,
+                    data: None,
+                    error: Some("The socket server stopped".to_string()),
+                };
+                if let Err(e) = write_response(&writer, &response, id)
+                    && !is_disconnect(&e)
                 {
-                    info!("[TAURI_MCP] Client already disconnected (pipe error)");
+                    warn!("[TAURI_MCP] Error writing response: {}", e);
+                }
+                rt.block_on(async {
+                    for task in in_flight {
+                        let _ = task.await;
+                    }
+                });
+                return Ok(());
+            }
+            Ok(Some(message)) => {
+                info!(
+                    "[TAURI_MCP] Received message: length = {} bytes",
+                    message.len()
+                );
+                message
+            }
+            Err(e) => {
+                // Check if this is a pipe disconnection error
+                if is_disconnect(&e) {
+                    info!("[TAURI_MCP] Client disconnected during read (pipe error)");
                     return Ok(());
                 }
-                return Err(Error::Io(format!("Failed to clone stream: {}", e)));
+                return Err(Error::Io(format!("Error reading from socket: {}", e)));
             }
         };
 
-        // Wrap the streams with our logging wrapper
-        let logging_reader = LoggingStream::new(stream_clone);
-        let mut reader = BufReader::new(logging_reader);
-        let mut writer = LoggingStream::new(stream);
-
-        // Keep handling requests until the client disconnects
-        loop {
-            let mut line = String::new();
-            match reader.read_line(&mut line) {
-                Ok(0) => {
-                    // End of stream, client disconnected
-                    info!("[TAURI_MCP] Client disconnected cleanly");
-                    return Ok(());
-                }
-                Ok(_) => {
-                    info!("[TAURI_MCP] Received command: {}", line.trim());
+        // Parse and process the request
+        let request: SocketRequest = match format.decode(&message) {
+            Ok(req) => req,
+            Err(e) => {
+                let error_msg = format!("Invalid request format: {}", e);
+                info!("[TAURI_MCP] {}", error_msg);
+
+                // Create and send an error response
+                let error_response = SocketResponse {
+                    success: false,
+                    data: None,
+                    error: Some(error_msg),
+                };
+
+                if let Err(e) = write_response(&writer, &error_response, None) {
+                    if is_disconnect(&e) {
+                        info!("[TAURI_MCP] Client disconnected during write (pipe error)");
+                        return Ok(());
+                    }
+                    return Err(Error::Io(format!("Error writing error response: {}", e)));
                 }
-                Err(e) => {
-                    // Check if this is a pipe disconnection error
-                    if e.to_string()
-                        .contains("No process is on the other end of the pipe")
-                        || e.kind() == std::io::ErrorKind::BrokenPipe
-                    {
-                        info!("[TAURI_MCP] Client disconnected during read (pipe error)");
+                continue;
+            }
+        };
+
+        info!("[TAURI_MCP] Processing command: {}", request.command);
+        client.record_request(&request.command);
+
+        // Apps with a token only serve clients that sent it in their handshake. A wrong
+        // token closes the connection, so each guess costs a reconnection
+        if !authenticated {
+            let is_handshake = request.command == commands::HANDSHAKE;
+            let token = request.payload.get("token").and_then(Value::as_str);
+            if is_handshake
+                && let (Some(expected), Some(token)) = (auth_token, token)
+                && tokens_match(expected, token)
+            {
+                authenticated = true;
+            } else {
+                warn!(
+                    "[TAURI_MCP] Refusing {} from unauthenticated client {}",
+                    request.command,
+                    client.id()
+                );
+                let response = SocketResponse {
+                    success: false,
+                    data: None,
+                    error: Some(
+                        "Authentication required: send the app's token in the handshake"
+                            .to_string(),
+                    ),
+                };
+                if let Err(e) = write_response(&writer, &response, request.id) {
+                    if is_disconnect(&e) {
+                        info!("[TAURI_MCP] Client disconnected during write (pipe error)");
                         return Ok(());
                     }
-                    return Err(Error::Io(format!("Error reading from socket: {}", e)));
+                    return Err(Error::Io(format!("Error writing response: {}", e)));
                 }
-            };
+                if is_handshake {
+                    return Ok(());
+                }
+                continue;
+            }
+        }
 
-            // Parse and process the request
-            let request: SocketRequest = match serde_json::from_str(&line) {
-                Ok(req) => req,
+        // The handshake changes the connection itself, so it is handled before the tools
+        if request.command == commands::HANDSHAKE {
+            match negotiate(&writer, &mut format, &watch, request.payload, request.id) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(e) if is_disconnect(&e) => {
+                    info!("[TAURI_MCP] Client disconnected during write (pipe error)");
+                    return Ok(());
+                }
                 Err(e) => {
-                    let error_msg = format!("Invalid request format: {}", e);
-                    info!("[TAURI_MCP] {}", error_msg);
+                    return Err(Error::Io(format!(
+                        "Error writing handshake response: {}",
+                        e
+                    )));
+                }
+            }
+            continue;
+        }
 
-                    // Create and send an error response
-                    let error_response = SocketResponse {
+        let admission = if UNLIMITED_COMMANDS.contains(&request.command.as_str()) {
+            None
+        } else {
+            match limiter.try_acquire() {
+                Ok(admission) => Some(admission),
+                Err(rejection) => {
+                    warn!(
+                        "[TAURI_MCP] Rejecting command {}: {}",
+                        request.command,
+                        rejection.message()
+                    );
+                    let response = SocketResponse {
                         success: false,
-                        data: None,
-                        error: Some(error_msg),
-                    };
-
-                    let error_json = match serde_json::to_string(&error_response) {
-                        Ok(json) => json + "\n",
-                        Err(_) => {
-                            return Err(Error::Anyhow(
-                                "Failed to serialize error response".to_string(),
-                            ));
-                        }
+                        data: serde_json::to_value(&rejection).ok(),
+                        error: Some(rejection.message()),
                     };
-
-                    match writer.write_all(error_json.as_bytes()) {
-                        Ok(_) => {
-                            if let Err(e) = writer.flush() {
-                                return Err(Error::Io(format!(
-                                    "Error flushing error response: {}",
-                                    e
-                                )));
-                            }
-                        }
-                        Err(e) => {
-                            return Err(Error::Io(format!("Error writing error response: {}", e)));
+                    if let Err(e) = write_response(&writer, &response, request.id) {
+                        if is_disconnect(&e) {
+                            info!("[TAURI_MCP] Client disconnected during write (pipe error)");
+                            return Ok(());
                         }
+                        return Err(Error::Io(format!("Error writing response: {}", e)));
                     }
-
-                    // Clear the line and continue to the next iteration
-                    line.clear();
                     continue;
                 }
-            };
+            }
+        };
 
-            info!("[TAURI_MCP] Processing command: {}", request.command);
+        // Run the command in its own task so a slow tool does not block quick ones
+        let app = app.clone();
+        let client_id = client.id();
+        let writer = writer.clone();
+        let watch = watch.clone();
+        watch.request_started();
+        in_flight.retain(|task| !task.is_finished());
+        in_flight.push(rt.spawn(async move {
+            // The in-flight slot is held until the response has been written
+            let _admission = admission;
+            let tauri_mcp = app.tauri_mcp();
+            let _in_flight = tauri_mcp.shutdown().track();
 
             // Use the centralized command handler from tools module
-            let response =
-                match tools::handle_command(&app, &request.command, request.payload).await {
-                    Ok(resp) => resp,
-                    Err(e) => {
-                        // Convert the error into a response structure
-                        info!("[TAURI_MCP] Command error: {}", e);
-                        SocketResponse {
-                            success: false,
-                            data: None,
-                            error: Some(e.to_string()),
-                        }
-                    }
-                };
-
-            // When writing the response, handle pipe errors gracefully
-            let response_json = serde_json::to_string(&response)
-                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?
-                + "\n";
-            info!(
-                "[TAURI_MCP] Sending response: length = {} bytes",
-                response_json.len()
-            );
-
-            // Write the response directly without chunking
-            match writer.write_all(response_json.as_bytes()) {
-                Ok(_) => {
-                    match writer.flush() {
-                        Ok(_) => {
-                            info!("[TAURI_MCP] Response sent successfully");
-                            // Continue to the next iteration of the loop
-                        }
-                        Err(e) => {
-                            if e.to_string()
-                                .contains("No process is on the other end of the pipe")
-                                || e.kind() == std::io::ErrorKind::BrokenPipe
-                            {
-                                info!("[TAURI_MCP] Client disconnected during flush (pipe error)");
-                                return Ok(()); // Return success for expected client disconnect
-                            } else {
-                                return Err(Error::Io(format!("Error flushing response: {}", e)));
-                            }
-                        }
-                    }
-                }
+            let response = match tools::handle_command(
+                &app,
+                &request.command,
+                request.payload,
+                request.id.as_deref(),
+                Some(client_id),
+            )
+            .await
+            {
+                Ok(resp) => resp,
                 Err(e) => {
-                    if e.to_string()
-                        .contains("No process is on the other end of the pipe")
-                        || e.kind() == std::io::ErrorKind::BrokenPipe
-                    {
-                        info!("[TAURI_MCP] Client disconnected during write (pipe error)");
-                        return Ok(()); // Return success for expected client disconnect
-                    } else {
-                        return Err(Error::Io(format!("Error writing response: {}", e)));
+                    // Convert the error into a response structure
+                    info!("[TAURI_MCP] Command error: {}", e);
+                    SocketResponse {
+                        success: false,
+                        data: e.data(),
+                        error: Some(e.to_string()),
                     }
                 }
+            };
+
+            let result = match request.id {
+                Some(id) if request.stream => write_streamed(
+                    &writer,
+                    response,
+                    id,
+                    request.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE),
+                ),
+                id => write_response(&writer, &response, id),
+            };
+            match result {
+                Ok(()) => info!("[TAURI_MCP] Response sent successfully"),
+                Err(e) if is_disconnect(&e) => {
+                    info!("[TAURI_MCP] Client disconnected before the response was sent")
+                }
+                Err(e) => error!("[TAURI_MCP] Error writing response: {}", e),
             }
+            watch.request_finished();
+        }));
+    }
+}
 
-            // Clear the line for the next command
-            line.clear();
-        } // End of loop
-    })
+/// Compare tokens in a time independent of where they differ.
+fn tokens_match(expected: &str, actual: &str) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .bytes()
+            .zip(actual.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
 }
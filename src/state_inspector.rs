@@ -0,0 +1,54 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+type Snapshot = Arc<dyn Fn() -> serde_json::Result<Value> + Send + Sync>;
+
+/// States the app chose to expose to clients, as serializable snapshots.
+///
+/// Nothing is exposed by default: the app registers a closure per state, which picks what
+/// to reveal, typically by reading a managed state through a captured `AppHandle`.
+pub struct StateInspector {
+    snapshots: Mutex<BTreeMap<String, Snapshot>>,
+}
+
+impl StateInspector {
+    pub(crate) fn new() -> Self {
+        Self {
+            snapshots: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Expose a state under `name`, replacing any state exposed under that name. The
+    /// closure is called on each request, from a socket thread.
+    pub fn expose<T, F>(&self, name: impl Into<String>, snapshot: F)
+    where
+        T: Serialize,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.snapshots.lock().unwrap().insert(
+            name.into(),
+            Arc::new(move || serde_json::to_value(snapshot())),
+        );
+    }
+
+    /// Stop exposing a state. Returns false if nothing was exposed under that name.
+    pub fn unexpose(&self, name: &str) -> bool {
+        self.snapshots.lock().unwrap().remove(name).is_some()
+    }
+
+    /// Names of the exposed states, sorted.
+    pub fn names(&self) -> Vec<String> {
+        self.snapshots.lock().unwrap().keys().cloned().collect()
+    }
+
+    /// Take a snapshot of a state. None if nothing is exposed under that name.
+    pub fn snapshot(&self, name: &str) -> Option<crate::Result<Value>> {
+        // Called outside the lock, so a snapshot may expose or read other states
+        let snapshot = self.snapshots.lock().unwrap().get(name).cloned()?;
+        Some(snapshot().map_err(|e| {
+            crate::Error::Anyhow(format!("Failed to serialize state {}: {}", name, e))
+        }))
+    }
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::audit::now_ms;
+
+/// Most recent error seen by the socket server.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerError {
+    pub timestamp_ms: u64,
+    /// Command that failed, None for connection-level errors.
+    pub command: Option<String>,
+    pub message: String,
+}
+
+/// Health of the socket server, as reported by the `status` command.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerStatus {
+    pub running: bool,
+    /// "ipc" or "tcp".
+    pub transport: String,
+    /// Socket path or host:port.
+    pub address: String,
+    /// Time since the server was last started, None while stopped.
+    pub uptime_ms: Option<u64>,
+    pub connected_clients: usize,
+    /// Socket commands handled since the app started.
+    pub commands_served: u64,
+    pub last_error: Option<ServerError>,
+    /// Whether the session is paused for a user, see `pause_session`.
+    #[serde(default)]
+    pub paused: bool,
+}
+
+/// Counters backing [`ServerStatus`], shared by the socket server and the command handlers.
+#[derive(Default)]
+pub struct ServerStats {
+    started: Mutex<Option<Instant>>,
+    commands_served: AtomicU64,
+    last_error: Mutex<Option<ServerError>>,
+}
+
+impl ServerStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub(crate) fn mark_started(&self) {
+        *self.started.lock().unwrap() = Some(Instant::now());
+    }
+
+    pub(crate) fn mark_stopped(&self) {
+        *self.started.lock().unwrap() = None;
+    }
+
+    /// Time since the server was last started, None while stopped.
+    pub fn uptime_ms(&self) -> Option<u64> {
+        self.started
+            .lock()
+            .unwrap()
+            .map(|started| started.elapsed().as_millis() as u64)
+    }
+
+    pub fn commands_served(&self) -> u64 {
+        self.commands_served.load(Ordering::Relaxed)
+    }
+
+    pub fn last_error(&self) -> Option<ServerError> {
+        self.last_error.lock().unwrap().clone()
+    }
+
+    /// Count a handled command, remembering its error if it failed.
+    pub(crate) fn record_command(&self, command: &str, error: Option<&str>) {
+        self.commands_served.fetch_add(1, Ordering::Relaxed);
+        if let Some(message) = error {
+            self.set_error(Some(command), message);
+        }
+    }
+
+    /// Remember a connection-level error.
+    pub(crate) fn record_error(&self, message: &str) {
+        self.set_error(None, message);
+    }
+
+    fn set_error(&self, command: Option<&str>, message: &str) {
+        *self.last_error.lock().unwrap() = Some(ServerError {
+            timestamp_ms: now_ms(),
+            command: command.map(str::to_string),
+            message: message.to_string(),
+        });
+    }
+}
@@ -0,0 +1,60 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::shared::commands;
+
+/// How long tools wait for the webview to answer.
+#[derive(Debug, Clone)]
+pub struct Timeouts {
+    /// Timeout of tools without a specific one. Default is 5 seconds.
+    pub default: Duration,
+    /// Timeouts by command name.
+    pub per_tool: HashMap<String, Duration>,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            default: Duration::from_secs(5),
+            per_tool: HashMap::from([
+                // Typing is paced character by character, so it gets more time
+                (
+                    commands::SEND_TEXT_TO_ELEMENT.to_string(),
+                    Duration::from_secs(30),
+                ),
+                // Printing lays out every page of the document
+                (commands::PRINT_TO_PDF.to_string(), Duration::from_secs(30)),
+                // Page loads wait for the app and the network; kept under the 30 seconds
+                // clients commonly give a request
+                (
+                    commands::WAIT_FOR_NAVIGATION.to_string(),
+                    Duration::from_secs(25),
+                ),
+                // Waiting for downloads to complete, same as above
+                (commands::GET_DOWNLOADS.to_string(), Duration::from_secs(25)),
+            ]),
+        }
+    }
+}
+
+impl Timeouts {
+    /// Timeout configured for a tool.
+    pub fn for_tool(&self, command: &str) -> Duration {
+        self.per_tool.get(command).copied().unwrap_or(self.default)
+    }
+
+    /// Timeout of a request: its `timeout_ms` (or `timeoutMs`) field if set, else the tool's.
+    pub fn for_request(&self, command: &str, payload: &Value) -> Duration {
+        request_timeout_ms(payload)
+            .map(Duration::from_millis)
+            .unwrap_or_else(|| self.for_tool(command))
+    }
+}
+
+fn request_timeout_ms(payload: &Value) -> Option<u64> {
+    payload
+        .get("timeout_ms")
+        .or_else(|| payload.get("timeoutMs"))
+        .and_then(Value::as_u64)
+}
@@ -0,0 +1,488 @@
+use schemars::JsonSchema;
+use schemars::r#gen::SchemaSettings;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::models::*;
+use crate::protocol::HandshakeRequest;
+use crate::shared::commands;
+
+/// Payload of `get_dom` in its object format.
+#[derive(Deserialize, JsonSchema)]
+#[allow(dead_code)]
+struct GetDomPayload {
+    /// Label of the window, the focused window without it.
+    window_label: Option<String>,
+    #[serde(flatten)]
+    options: DomSnapshotOptions,
+}
+
+/// Commands taking the time to wait for the webview from their payload, see `Timeouts`.
+const TIMED_COMMANDS: &[&str] = &[
+    commands::GET_DOM,
+    commands::MANAGE_LOCAL_STORAGE,
+    commands::EXECUTE_JS,
+    commands::GET_ELEMENT_POSITION,
+    commands::SEND_TEXT_TO_ELEMENT,
+    commands::GET_PAGE_TEXT,
+    commands::GET_ELEMENT_INFO,
+    commands::INSPECT_ELEMENT,
+    commands::SCROLL_INTO_VIEW,
+    commands::HIGHLIGHT_ELEMENT,
+    commands::GET_PAGE_INFO,
+    commands::WAIT_FOR_NAVIGATION,
+    commands::GET_DOWNLOADS,
+    commands::CONTROL_MEDIA,
+    commands::CLICK_ELEMENT,
+    commands::SET_ELEMENT_VALUE,
+    commands::SET_FILE_INPUT,
+    commands::CAPTURE_CANVAS,
+    commands::PRINT_TO_PDF,
+    commands::GET_UI_SNAPSHOT,
+    commands::FOCUS_ELEMENT,
+    commands::BLUR_ELEMENT,
+    commands::FILL_FORM,
+    commands::ASSERT_ELEMENT_EXISTS,
+    commands::ASSERT_ELEMENT_VISIBLE,
+    commands::ASSERT_TEXT_CONTAINS,
+    commands::ASSERT_URL_MATCHES,
+    commands::CONVERT_COORDINATES,
+    commands::SIMULATE_KEY_PRESS,
+    commands::SIMULATE_TOUCH,
+    commands::SET_LOCALE,
+    commands::SET_THEME,
+    commands::ADD_MOCK_ROUTE,
+    commands::QUERY_DB,
+];
+
+/// Commands the action overlay shows, which take `show_action` from their payload.
+const OVERLAY_COMMANDS: &[&str] = &[
+    commands::CLICK_ELEMENT,
+    commands::SET_ELEMENT_VALUE,
+    commands::SET_FILE_INPUT,
+    commands::FOCUS_ELEMENT,
+    commands::BLUR_ELEMENT,
+    commands::SEND_TEXT_TO_ELEMENT,
+    commands::FILL_FORM,
+    commands::SIMULATE_TEXT_INPUT,
+    commands::SIMULATE_KEY_PRESS,
+    commands::SIMULATE_TOUCH,
+    commands::SIMULATE_MOUSE_MOVEMENT,
+];
+
+/// What a socket command does, for clients listing the commands as tools.
+pub fn description(command: &str) -> &'static str {
+    match command {
+        commands::PING => {
+            "Checks the connection to the Tauri app and which app answered: returns the app name, bundle identifier, app version, process id, protocol, Tauri and plugin versions, the app's clock, the payload sent, and the measured round-trip time in milliseconds."
+        }
+        commands::GET_DOM => {
+            "Retrieves the HTML Document Object Model (DOM) content from the specified application window as a string. This tool is read-only and provides a snapshot of the window's current HTML structure. Useful for parsing, analysis, or data extraction. Full pages can be very large: use the filtering options and max_length to keep the snapshot small."
+        }
+        commands::MANAGE_LOCAL_STORAGE => {
+            "Allows reading from or modifying the browser's localStorage data associated with a specified application window's webview (e.g., a Tauri webview). Supports getting, setting, removing items, clearing all items, or listing keys. Some actions are destructive."
+        }
+        commands::EXECUTE_JS => {
+            "Executes arbitrary JavaScript code within the context of a specified application window's webview (e.g., a Tauri webview). Returns the result of the last executed statement or a promise resolution. Caution: This tool is destructive and can modify the window's content, state, or trigger unintended actions. Use with careful consideration of the code being executed."
+        }
+        commands::MANAGE_WINDOW => {
+            "Manages the state and geometry of Tauri application windows. Allows operations such as focusing, minimizing, maximizing, closing, showing/hiding, positioning, resizing, centering, and toggling fullscreen. Some operations like 'close' are destructive."
+        }
+        commands::SIMULATE_TEXT_INPUT => {
+            "Simulates keyboard input, typing the specified text content character by character into the currently focused input field or application element. Allows configuration of typing speed via inter-keystroke delay and initial delay. This action modifies the content of the target input field."
+        }
+        commands::SIMULATE_MOUSE_MOVEMENT => {
+            "Simulates the movement of the mouse cursor to specified screen coordinates, either absolute or relative to its current position. This action can trigger hover events or other UI interactions in the targeted application or operating system. Considered destructive as it can alter UI state or initiate actions."
+        }
+        commands::GET_ELEMENT_POSITION => {
+            "Finds an HTML element on the page by ID, class, tag name, text content, CSS selector, XPath, ARIA label, ARIA role or form label, and returns its raw position coordinates for use with mouse_movement. Can optionally click the element. Works on any page, without the app's frontend listening for MCP events."
+        }
+        commands::SEND_TEXT_TO_ELEMENT => {
+            "Finds an HTML element by selector and sends text input to it, suitable for inputs, textareas, and contentEditable elements. Note: While this tool updates DOM content, it may not trigger React state updates in applications using React - visual changes appear but application state may not reflect the changes."
+        }
+        commands::GET_SERVER_MODE => {
+            "Reports the safety mode of the Tauri MCP plugin: whether the app is a debug build, whether the socket server was explicitly allowed in a release build (via config or the TAURI_MCP_ALLOW_IN_RELEASE environment variable), and whether the socket server is running."
+        }
+        commands::LIST_ARTIFACTS => {
+            "Lists session artifacts (recordings, baselines, audit logs, ...) stored by the Tauri MCP plugin. Each entry contains the artifact ID to use with get_artifact and delete_artifact, its kind, name, size and creation time."
+        }
+        commands::GET_ARTIFACT => {
+            "Retrieves the content of a stored session artifact by ID. Text artifacts are returned as text, binary artifacts as base64."
+        }
+        commands::DELETE_ARTIFACT => "Deletes a stored session artifact by ID.",
+        commands::GET_AUDIT_LOG => {
+            "Returns the audit log of socket commands executed against the Tauri application: timestamp, command, truncated payload, outcome and duration of each call, oldest first. Useful to review exactly what an agent did to the app."
+        }
+        commands::DESCRIBE_TOOLS => {
+            "Reports the live platform capabilities of the Tauri application (OS input, window management, webview access) and which tools are available, degraded (with the reason) or unavailable on this platform."
+        }
+        commands::POLL_EVENTS => {
+            "Long-polls events emitted by the Tauri application (client connections, window creation/destruction/focus, page loads). Returns buffered events after the given cursor, waiting up to timeoutMs for new ones. Pass the returned cursor to the next call, or reuse the same sessionId and let the server remember it. missedEvents is true when older events were dropped from the buffer."
+        }
+        commands::RECOVER_LAST_SESSION => {
+            "Returns the recording of the previous run of the Tauri application: every command executed with its payload, outcome and duration. The recording is journaled to disk as commands run, so it is available even if the app crashed; 'crashed' tells whether the previous session ended abruptly. Use it after a restart to see what led to a crash."
+        }
+        commands::CANCEL => {
+            "Cancels a request still running on this connection, e.g. a long text input or script, by the ID it was sent with. Returns whether a request was cancelled; only the connection that sent a request can cancel it."
+        }
+        commands::LIST_CLIENTS => {
+            "Lists the clients currently connected to the Tauri application's socket server (including this one): transport, peer address for TCP, connection time, number of requests and last command. Useful when several MCP clients or debugging tools share the same app."
+        }
+        commands::STATUS => {
+            "Reports the health of the Tauri application's socket server: whether it is running, transport and address, uptime, number of connected clients, commands served and the last error. Call it before a run to verify the bridge works."
+        }
+        commands::GET_PAGE_TEXT => {
+            "Retrieves the rendered, visible text of an application window (its innerText), or of every element matching a CSS selector. Usually 10-50x smaller than the DOM; prefer it over get_dom when reading what the page says. Set readable to focus on the main content and skip navigation, headers, footers and sidebars."
+        }
+        commands::GET_ELEMENT_INFO => {
+            "Inspects an element: attributes, dataset, computed styles, bounding rect, scroll offsets and state (visible, disabled, focused, checked, value). Use it to verify visual state, e.g. whether a button is disabled or greyed out, without a screenshot."
+        }
+        commands::CLICK_ELEMENT => {
+            "Clicks an element found by selector. An element that is not fully visible is scrolled into view first. The 'dom' strategy (default) dispatches pointer and mouse events in the page; 'os' moves the real mouse and clicks at the element's center, which the page sees as a trusted click; 'auto' uses the OS mouse when OS-level input is fully available. The response reports the strategy used."
+        }
+        commands::SET_ELEMENT_VALUE => {
+            "Sets the value of a form control the way a user would, firing input/change events so frameworks like React pick it up: chooses <option>s of a <select> by value or label, checks or unchecks checkboxes and radios, or sets range sliders, dates, colors and other inputs. Use send_text_to_element for typing into text fields."
+        }
+        commands::SET_FILE_INPUT => {
+            "Attaches files to an <input type=\"file\"> without a native file dialog, firing input/change events like a real selection. The application reads the files from disk, so paths must exist on the machine running the app (up to 25 MiB in total). Use it to test upload flows."
+        }
+        commands::LIST_DIALOGS => {
+            "Lists the intercepted dialogs waiting for an answer, oldest first, with their ID, window, kind (open, save, message, ask, confirm) and the options the app passed (title, filters, default path...). Also returns the dialog policy and the dialogs it answered."
+        }
+        commands::RESPOND_TO_DIALOG => {
+            "Answers an intercepted dialog as if the user had, resuming the app code awaiting it. Use a path or array of paths for open dialogs, a path for save dialogs, true/false for ask and confirm, and null to cancel. Paths are used as given on the machine running the app."
+        }
+        commands::SET_DIALOG_INTERCEPTION => {
+            "Turns interception of native dialogs (file open/save, message, ask, confirm) opened through the Tauri dialog plugin on or off. While enabled, no native dialog is shown: the dialog waits until it is answered with respond_to_dialog, and a 'dialogOpened' event is published for poll_events. Queued auto-responses answer the next dialogs without a round trip. Returns the interception state and pending dialogs."
+        }
+        commands::SET_DIALOG_POLICY => {
+            "Sets how the webview's alert, confirm and prompt dialogs are answered, so they never block the session. While a policy is set they return immediately: the first matching rule gives the answer, otherwise confirms are accepted and prompts get their default value (unless accept_confirms/accept_prompts say otherwise). Each answered dialog is recorded (see list_dialogs) and published as a 'dialogAnswered' event. With plugin_dialogs, message/ask/confirm dialogs of the Tauri dialog plugin are answered the same way."
+        }
+        commands::CAPTURE_CANVAS => {
+            "Captures the content of <canvas> elements (charts, WebGL scenes, drawings) as images, since their content is invisible to get_dom and get_page_text. Returns each canvas as an image plus its size, context type and whether it came out blank. WebGL canvases are read right after the app draws a frame; blank ones are retried after a resize event."
+        }
+        commands::PRINT_TO_PDF => {
+            "Prints the page shown in a window to a PDF file, as the app's print stylesheet lays it out, to check report or invoice layouts. Returns the path and size of the file, and the PDF itself when include_data is set. Only supported on Linux for now."
+        }
+        commands::GET_UI_SNAPSHOT => {
+            "Observes an application window in one round trip: its URL and title, the focused element, a compressed screenshot of the viewport, and an outline of the visible landmarks, headings and controls with their role, accessible name and state (focused, disabled, checked, value...), indented by nesting. Prefer it over separate get_dom, get_page_text and execute_js calls to see where the app is and what can be done next."
+        }
+        commands::FOCUS_ELEMENT => {
+            "Gives keyboard focus to an element, so the next simulated keystrokes go to it. Reports whether focus moved (with the reason when the element is disabled or not focusable), the previously focused element and the one focused now."
+        }
+        commands::BLUR_ELEMENT => {
+            "Removes keyboard focus from an element, firing blur/focusout so validation on blur runs. Without a selector, blurs whatever element currently has focus. Reports the previously focused element and the one focused now."
+        }
+        commands::FILL_FORM => {
+            "Fills several form fields in one call, in order, with the same semantics as set_element_value: text for inputs and textareas, option values or labels for selects, true/false for checkboxes and radios. input/change events fire for each field so framework state updates. Returns a result per field (final state or error) instead of failing on the first bad selector."
+        }
+        commands::ASSERT_ELEMENT_EXISTS => {
+            "Checks that an element exists in the page (or does not, with negate). Returns a verdict with passed, expected, actual and a message; a failed assertion is not an error."
+        }
+        commands::ASSERT_ELEMENT_VISIBLE => {
+            "Checks that an element is visible: it exists, is rendered (not display:none, visibility:hidden or opacity 0) and has a size. With negate, checks that it is not visible. The message explains why a failed assertion failed."
+        }
+        commands::ASSERT_TEXT_CONTAINS => {
+            "Checks that the rendered text of an element, or of the whole page without a selector, contains a string. The actual text (up to 2000 characters) is returned for comparison."
+        }
+        commands::ASSERT_URL_MATCHES => {
+            "Checks the current URL of a window. By default the pattern is a substring of the URL; use exact to compare the whole URL or regex for a JavaScript regular expression."
+        }
+        commands::RUN_SCRIPT => {
+            "Runs an ordered list of commands inside the app in a single call, e.g. a login flow: fill_form, click_element, wait for an element, assert_text_contains. Failed assertions count as failed steps. Returns a step-by-step report with status, attempts, duration, data and error of each step, and whether the whole script passed. Much faster than one tool call per step."
+        }
+        commands::START_INTERACTION_RECORDING => {
+            "Starts recording what a person does in the app (clicks, field changes, Enter/Escape and shortcuts, navigations) so the flow can be replayed later with run_script. Ask the user to perform the flow, then call stop_interaction_recording."
+        }
+        commands::STOP_INTERACTION_RECORDING => {
+            "Stops the interaction recording and returns the recorded flow as a run_script payload ({steps, onError}), ready to be replayed or edited, e.g. to add assertions."
+        }
+        commands::SET_EMULATION => {
+            "Makes a window deterministic: sets Date and performance.now to an emulated clock (frozen, or running from a given time) and seeds Math.random, crypto.getRandomValues and crypto.randomUUID. Overrides survive reloads. Call without options to read the current overrides."
+        }
+        commands::ADVANCE_CLOCK => {
+            "Moves the emulated clock of a window forward, e.g. to step through a countdown or expire a session while the clock is frozen. Timers (setTimeout, setInterval) are not fast-forwarded."
+        }
+        commands::SET_GEOLOCATION => {
+            "Makes navigator.geolocation of a window report a given position, with the permission granted, or deny location access. Running watchPosition watches receive the new position. Use reset to restore the real location service."
+        }
+        commands::SET_LOCALE => {
+            "Makes a window see another locale, language list or timezone, without changing the OS settings: sets the default locale of Intl and toLocaleString, navigator.language(s), and the timezone of local Date methods. Use reset to remove the override."
+        }
+        commands::SET_THEME => {
+            "Switches a window between light and dark: sets the native Tauri window theme and overrides prefers-color-scheme in the page (matchMedia and stylesheet rules). 'system' removes both overrides. Without theme, only reports the window theme and the color scheme the page sees."
+        }
+        commands::EMULATE_DEVICE => {
+            "Emulates a device in a window to exercise responsive layouts: resizes the content area to the device viewport and overrides devicePixelRatio, screen size, user agent and touch support (mouse input also fires touch events). Pick a named profile and/or give explicit values; reset restores the window."
+        }
+        commands::SET_NETWORK_CONDITIONS => {
+            "Simulates poor or missing connectivity for the fetch and XMLHttpRequest requests of a window: offline mode, added latency, and download/upload bandwidth limits. Tauri IPC is not affected. Use it to check loading states and error handling; reset restores the network."
+        }
+        commands::ADD_MOCK_ROUTE => {
+            "Intercepts fetch and XMLHttpRequest requests of a window matching a URL pattern and answers them with a canned status, headers and body, or sends them to another URL, to test the frontend against simulated backend responses. Returns the active routes with their ids."
+        }
+        commands::REMOVE_MOCK_ROUTE => {
+            "Removes a mock route added with add_mock_route, or all routes of a window."
+        }
+        commands::GET_PROCESS_STATS => {
+            "Reports the resource usage of the Tauri application process and its child processes (e.g. webview processes): CPU %, resident memory, open file descriptors or handles, and thread count, with totals. CPU is measured since the previous call. Call it periodically during long sessions to detect leaks."
+        }
+        commands::GET_JS_ERRORS => {
+            "Gets the uncaught JavaScript errors and unhandled promise rejections of the webviews, with message, source location and stack trace. Pass the returned cursor as `since` to only get the errors that happened afterwards, e.g. after an interaction."
+        }
+        commands::GET_APP_LOGS => {
+            "Gets the log records of the Tauri application's Rust side (requires the app to install the plugin's LogBridge). Pass the returned cursor as `since` to only get the records logged afterwards, e.g. to correlate an interaction with backend logging."
+        }
+        commands::GET_APP_STATE => {
+            "Gets snapshots of the backend state the Tauri application chose to expose (e.g. settings, session, counters), to verify the effect of UI actions. The response lists the names of all exposed states."
+        }
+        commands::MANAGE_STORE => {
+            "Reads or writes the entries of a tauri-plugin-store file of the application, to seed or verify persisted settings. Only the store files allowed by the app can be accessed."
+        }
+        commands::QUERY_DB => {
+            "Runs SQL on a SQLite database of the Tauri application, e.g. to verify that a UI action persisted rows. Only databases configured by the app can be queried, and they are read-only unless the app made them writable. Rows are returned as objects keyed by column name."
+        }
+        commands::READ_APP_FILE => {
+            "Reads a file, or lists a directory, in one of the directories the Tauri application exposes (e.g. its data dir or a test fixtures dir), to verify exported output. Paths are relative to the named root."
+        }
+        commands::WRITE_APP_FILE => {
+            "Writes a file in one of the directories the Tauri application exposes, e.g. to place a fixture before an import. Missing directories are created."
+        }
+        commands::TRIGGER_DEEP_LINK => {
+            "Delivers a URL to the Tauri application as if it was opened through its custom URL scheme (e.g. 'myapp://invite/abc'), to test deep-link flows on any platform. Works with tauri-plugin-deep-link's onOpenUrl handlers."
+        }
+        commands::LIST_TRAYS => {
+            "Lists the tray icons the Tauri application registered for automation, with their menu items (id, text, kind, enabled and checked state, submenus). Use the item ids with trigger_tray_menu_item."
+        }
+        commands::TRIGGER_TRAY_MENU_ITEM => {
+            "Selects a tray menu item as if the user clicked it: check items are toggled and the app's menu handler runs. Disabled items are refused."
+        }
+        commands::CLICK_TRAY => {
+            "Clicks a tray icon, delivering the press and release (and optionally a double click) to the app's tray icon handler."
+        }
+        commands::LIST_MENU => {
+            "Lists the items of the Tauri application's menu bar: the app menu and the menus of the windows, with each item's id, text, kind, enabled and checked state, and submenus. Use the ids or texts with click_menu_item."
+        }
+        commands::CLICK_MENU_ITEM => {
+            "Selects a menu bar item as if the user clicked it, by id or by path (e.g. 'File > Export > PDF'), and returns the menu event delivered to the app. Check items are toggled; disabled items are refused."
+        }
+        commands::PAUSE_SESSION => {
+            "Hands the app to a human: shows a banner on top of every window ('MCP agent paused — user in control' by default) and holds input commands (clicks, typing, form filling, mouse, menu and tray clicks) until resume_session, or until the user presses 'Resume agent' in the banner. Read-only tools keep working. Returns the pause status with the queued commands."
+        }
+        commands::RESUME_SESSION => {
+            "Takes control back after pause_session: hides the banner and runs the input commands queued meanwhile in the order they arrived, or fails them with discard_queued. Returns how many were released or discarded."
+        }
+        commands::GET_INPUT_CONSENT => {
+            "Reports whether the app requires the user's consent before simulate_text_input, simulate_mouse_movement and OS-level click_element drive the real mouse and keyboard, and where that consent stands: notAsked, pending (the prompt is shown and input waits for the user), granted or denied. When denied, use DOM-level tools such as click_element with the dom strategy or send_text_to_element instead."
+        }
+        commands::SET_ACTION_OVERLAY => {
+            "Turns the action overlay on or off for the following commands: before clicking, typing into, setting or focusing an element, a ghost cursor moves to it and the element is outlined with the action's name, so people watching the session or a recording of it can follow what the agent does. Each command waits a quarter of a second for the cursor to arrive. Input tools also take a show_action parameter to show a single action."
+        }
+        commands::START_TRACE => {
+            "Starts tracing the following tool calls for offline review of a run: each call is recorded with its payload, response, timing, the page URL, title, DOM hash and a screenshot before and after it, and the JavaScript errors, console messages, fetch/XHR requests and app logs reported while it ran. stop_trace stores it all as a zip bundle."
+        }
+        commands::STOP_TRACE => {
+            "Stops the trace started by start_trace and stores it as a 'trace' artifact: a zip with trace.json listing the steps, one steps/NNNN-<command>.json file per call, and dom/<hash>.html snapshots. Returns the step list and the artifact, which get_artifact retrieves."
+        }
+        commands::CONVERT_COORDINATES => {
+            "Converts a point of a window between coordinate spaces, to debug clicks that land in the wrong place on HiDPI screens: viewport (CSS pixels as getBoundingClientRect reports them), document (viewport plus scroll), window (logical pixels from the window's outer corner, as simulate_mouse_movement takes them), logical and physical screen pixels. Returns the point in every space, where the OS mouse goes for it, and the window's scale factor and position."
+        }
+        commands::SIMULATE_KEY_PRESS => {
+            "Presses keys together and releases them in reverse, for shortcuts and navigation keys such as ctrl+s, escape or arrow keys. Keys are given either by name, which the keyboard layout maps to keys, or by W3C physical key code, which presses the same keys whatever the layout: ['ControlLeft', 'KeyS'] is Ctrl+S on a US keyboard and the key where S is on a US keyboard on any other. Use simulate_text_input to type text."
+        }
+        commands::SIMULATE_TOUCH => {
+            "Performs a touch gesture in a page with synthetic touch pointer events, plus touch events where the webview has them, to test touch-optimized UIs on desktop: 'tap' (followed by the emulated mouse events and click), 'long_press' (with a contextmenu event), 'swipe' by dx/dy at a given duration or velocity, and a two-finger 'pinch' scaling the distance between the fingers. The gesture starts at the center of an element found by selector, or at a point in viewport CSS pixels."
+        }
+        commands::GENERATE_CLIENT_CONFIG => {
+            "Generates ready-to-paste MCP client configuration for this app, derived from its live plugin configuration: the server command and arguments, and the environment selecting its transport (application name, custom socket path or TCP host and port) and auth token. Returns the JSON to merge into the client's configuration file, where that file is, and for Claude Code the `claude mcp add` command line."
+        }
+        commands::INSPECT_ELEMENT => {
+            "Inspects one element in a single call: a screenshot cropped to it, its bounding box, text, accessible role and name, attributes, value, and interactability flags (visible, inViewport, enabled, editable, focusable, focused, checked, obscured by another element, receivesPointerEvents, clickable). Use it to check why a click or typing did not work. Elements cut off by the viewport are scrolled into view for the screenshot. Screenshots are only taken on Linux for now; elsewhere screenshotError says why."
+        }
+        commands::SCROLL_INTO_VIEW => {
+            "Scrolls an element into view unless it is already fully visible, and waits for the scrolling to settle. Use it before get_element_position and mouse_movement, which otherwise get coordinates outside the window for off-screen elements. Returns whether it scrolled, the element's rect in the window's viewport and whether it is now fully visible."
+        }
+        commands::HIGHLIGHT_ELEMENT => {
+            "Outlines the element a selector finds, or every matching element, for a few seconds with an optional label, so a person watching the app can check that the selector resolves to what the agent thinks. The outlines follow their elements as the page scrolls and do not catch clicks. Returns how many elements matched and the tag, id, text and rect of the outlined ones."
+        }
+        commands::GET_PAGE_INFO => {
+            "Returns basic facts about a window's page: URL, title, document.readyState, history length, visibility, whether it has focus, and the route of its client-side router when the app registered a route provider. Cheaper than get_ui_snapshot when only the location or loading state matters."
+        }
+        commands::WAIT_FOR_NAVIGATION => {
+            "Waits until a window navigates to a URL matching a pattern, or at all without one. Counts finished page loads and client-side route changes (pushState, replaceState, back/forward, anchors). A window already at a matching URL resolves at once, since navigations often finish before this is called; pass the cursor of an earlier response as after_seq to only count later navigations. Use it after clicks or form submissions that change pages."
+        }
+        commands::GET_DOWNLOADS => {
+            "Gets the files the webviews downloaded: URL, suggested file name, destination path, MIME type, expected and received bytes, size on disk, and status (inProgress, finished, failed, cancelled). Use it to check export features: click the export button, then call this with wait_for_completion. When the app sets a download directory, files go there instead of the user's Downloads (sandboxDir). Downloads are recorded on Linux, and elsewhere only when the app reports them."
+        }
+        commands::SET_PERMISSION_POLICY => {
+            "Pre-answers the permission requests of the webviews (camera and microphone for getUserMedia, geolocation, notifications, clipboard-read, clipboard-write), so flows asking for them never stall on a prompt, and returns the permissions the pages requested so far with their outcome (granted, denied, or prompted when left to the webview). Denied requests fail at once with a NotAllowedError; granted clipboard reads get the system clipboard. Call it without arguments to only get the requests."
+        }
+        commands::CONTROL_MEDIA => {
+            "Plays, pauses, seeks, mutes or unmutes an <audio> or <video> element, or just reads its state, and returns currentTime, duration (null while unknown or for live streams), paused, ended, seeking, muted, volume, playbackRate, readyState (0 no data to 4 enough to play through), networkState, buffered ranges and any media error. Use it to verify media playback without watching pixels. playError tells why play() failed, e.g. an autoplay policy."
+        }
+        commands::HANDSHAKE => {
+            "Checks that the client and the app speak compatible protocol versions and have the tools the client needs, authenticates clients of apps requiring a token, and switches the connection to another framing, encoding or compression. The response is sent in the previous format."
+        }
+        _ => "",
+    }
+}
+
+/// JSON schema of the payload of a socket command, with every subschema inlined.
+pub fn input_schema(command: &str) -> Value {
+    let mut schema = match command {
+        commands::PING => schema_for::<PingRequest>(),
+        commands::GET_DOM => schema_for::<GetDomPayload>(),
+        commands::MANAGE_LOCAL_STORAGE => schema_for::<LocalStorageRequest>(),
+        commands::EXECUTE_JS => schema_for::<ExecuteJsRequest>(),
+        commands::MANAGE_WINDOW => schema_for::<WindowManagerRequest>(),
+        commands::SIMULATE_TEXT_INPUT => schema_for::<TextInputRequest>(),
+        commands::SIMULATE_MOUSE_MOVEMENT => schema_for::<MouseMovementRequest>(),
+        commands::GET_ELEMENT_POSITION => schema_for::<ElementPositionRequest>(),
+        commands::SEND_TEXT_TO_ELEMENT => schema_for::<SendTextToElementRequest>(),
+        commands::LIST_ARTIFACTS => schema_for::<ListArtifactsRequest>(),
+        commands::GET_ARTIFACT | commands::DELETE_ARTIFACT => schema_for::<ArtifactRequest>(),
+        commands::GET_AUDIT_LOG => schema_for::<AuditLogRequest>(),
+        commands::DESCRIBE_TOOLS => schema_for::<DescribeToolsRequest>(),
+        commands::POLL_EVENTS => schema_for::<PollEventsRequest>(),
+        commands::CANCEL => schema_for::<CancelRequest>(),
+        commands::GET_PAGE_TEXT => schema_for::<PageTextRequest>(),
+        commands::GET_ELEMENT_INFO => schema_for::<ElementInfoRequest>(),
+        commands::INSPECT_ELEMENT => schema_for::<InspectElementRequest>(),
+        commands::SCROLL_INTO_VIEW => schema_for::<ScrollIntoViewRequest>(),
+        commands::HIGHLIGHT_ELEMENT => schema_for::<HighlightElementRequest>(),
+        commands::GET_PAGE_INFO => schema_for::<PageInfoRequest>(),
+        commands::WAIT_FOR_NAVIGATION => schema_for::<WaitForNavigationRequest>(),
+        commands::GET_DOWNLOADS => schema_for::<GetDownloadsRequest>(),
+        commands::CONTROL_MEDIA => schema_for::<ControlMediaRequest>(),
+        commands::CLICK_ELEMENT => schema_for::<ClickElementRequest>(),
+        commands::SET_ELEMENT_VALUE => schema_for::<SetElementValueRequest>(),
+        commands::SET_FILE_INPUT => schema_for::<SetFileInputRequest>(),
+        commands::RESPOND_TO_DIALOG => schema_for::<RespondToDialogRequest>(),
+        commands::SET_DIALOG_INTERCEPTION => schema_for::<SetDialogInterceptionRequest>(),
+        commands::SET_DIALOG_POLICY => schema_for::<SetDialogPolicyRequest>(),
+        commands::SET_PERMISSION_POLICY => schema_for::<SetPermissionPolicyRequest>(),
+        commands::CAPTURE_CANVAS => schema_for::<CaptureCanvasRequest>(),
+        commands::PRINT_TO_PDF => schema_for::<PrintToPdfRequest>(),
+        commands::GET_UI_SNAPSHOT => schema_for::<UiSnapshotRequest>(),
+        commands::FOCUS_ELEMENT => schema_for::<FocusElementRequest>(),
+        commands::BLUR_ELEMENT => schema_for::<BlurElementRequest>(),
+        commands::FILL_FORM => schema_for::<FillFormRequest>(),
+        commands::ASSERT_ELEMENT_EXISTS | commands::ASSERT_ELEMENT_VISIBLE => {
+            schema_for::<AssertElementRequest>()
+        }
+        commands::ASSERT_TEXT_CONTAINS => schema_for::<AssertTextContainsRequest>(),
+        commands::ASSERT_URL_MATCHES => schema_for::<AssertUrlMatchesRequest>(),
+        commands::RUN_SCRIPT => schema_for::<RunScriptRequest>(),
+        commands::START_INTERACTION_RECORDING => schema_for::<StartInteractionRecordingRequest>(),
+        commands::STOP_INTERACTION_RECORDING => schema_for::<StopInteractionRecordingRequest>(),
+        commands::SET_EMULATION => schema_for::<SetEmulationRequest>(),
+        commands::ADVANCE_CLOCK => schema_for::<AdvanceClockRequest>(),
+        commands::SET_GEOLOCATION => schema_for::<SetGeolocationRequest>(),
+        commands::SET_LOCALE => schema_for::<SetLocaleRequest>(),
+        commands::SET_THEME => schema_for::<SetThemeRequest>(),
+        commands::EMULATE_DEVICE => schema_for::<EmulateDeviceRequest>(),
+        commands::SET_NETWORK_CONDITIONS => schema_for::<SetNetworkConditionsRequest>(),
+        commands::ADD_MOCK_ROUTE => schema_for::<AddMockRouteRequest>(),
+        commands::REMOVE_MOCK_ROUTE => schema_for::<RemoveMockRouteRequest>(),
+        commands::GET_PROCESS_STATS => schema_for::<GetProcessStatsRequest>(),
+        commands::GET_JS_ERRORS => schema_for::<GetJsErrorsRequest>(),
+        commands::GET_APP_LOGS => schema_for::<GetAppLogsRequest>(),
+        commands::GET_APP_STATE => schema_for::<GetAppStateRequest>(),
+        commands::MANAGE_STORE => schema_for::<ManageStoreRequest>(),
+        commands::QUERY_DB => schema_for::<QueryDbRequest>(),
+        commands::READ_APP_FILE => schema_for::<ReadAppFileRequest>(),
+        commands::WRITE_APP_FILE => schema_for::<WriteAppFileRequest>(),
+        commands::TRIGGER_DEEP_LINK => schema_for::<TriggerDeepLinkRequest>(),
+        commands::TRIGGER_TRAY_MENU_ITEM => schema_for::<TriggerTrayMenuItemRequest>(),
+        commands::CLICK_TRAY => schema_for::<ClickTrayRequest>(),
+        commands::LIST_MENU => schema_for::<ListMenuRequest>(),
+        commands::CLICK_MENU_ITEM => schema_for::<ClickMenuItemRequest>(),
+        commands::PAUSE_SESSION => schema_for::<PauseSessionRequest>(),
+        commands::RESUME_SESSION => schema_for::<ResumeSessionRequest>(),
+        commands::SET_ACTION_OVERLAY => schema_for::<SetActionOverlayRequest>(),
+        commands::START_TRACE => schema_for::<StartTraceRequest>(),
+        commands::STOP_TRACE => schema_for::<StopTraceRequest>(),
+        commands::CONVERT_COORDINATES => schema_for::<ConvertCoordinatesRequest>(),
+        commands::SIMULATE_KEY_PRESS => schema_for::<KeyPressRequest>(),
+        commands::SIMULATE_TOUCH => schema_for::<TouchRequest>(),
+        commands::GENERATE_CLIENT_CONFIG => schema_for::<GenerateClientConfigRequest>(),
+        commands::HANDSHAKE => schema_for::<HandshakeRequest>(),
+        // The rest ignore their payload
+        _ => json!({ "type": "object", "properties": {} }),
+    };
+
+    // Fields read from the raw payload rather than the request models
+    if let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) {
+        if TIMED_COMMANDS.contains(&command) && !properties.contains_key("timeout_ms") {
+            properties.insert(
+                "timeout_ms".to_string(),
+                json!({
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "How long to wait for the webview to answer, in milliseconds, instead of the timeout configured in the app.",
+                }),
+            );
+        }
+        if OVERLAY_COMMANDS.contains(&command) {
+            properties.insert(
+                "show_action".to_string(),
+                json!({
+                    "type": "boolean",
+                    "description": "Show a ghost cursor and outline the target before acting, for people watching. Defaults to the setting of set_action_overlay.",
+                }),
+            );
+        }
+    }
+    schema
+}
+
+fn schema_for<T: JsonSchema>() -> Value {
+    let generator = SchemaSettings::draft07()
+        .with(|settings| {
+            settings.inline_subschemas = true;
+            settings.meta_schema = None;
+        })
+        .into_generator();
+    serde_json::to_value(generator.into_root_schema_for::<T>())
+        .unwrap_or_else(|_| json!({ "type": "object" }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_command_is_described() {
+        for command in commands::ALL {
+            assert!(
+                !description(command).is_empty(),
+                "{} has no description",
+                command
+            );
+            let schema = input_schema(command);
+            assert_eq!(schema["type"], "object", "{}: {}", command, schema);
+            assert!(
+                schema.get("definitions").is_none(),
+                "{}: {}",
+                command,
+                schema
+            );
+        }
+    }
+
+    #[test]
+    fn adds_fields_read_from_the_raw_payload() {
+        let schema = input_schema(commands::CLICK_ELEMENT);
+        assert_eq!(schema["properties"]["timeout_ms"]["type"], "integer");
+        assert_eq!(schema["properties"]["show_action"]["type"], "boolean");
+        assert_eq!(
+            schema["required"],
+            json!(["selector_type", "selector_value"])
+        );
+
+        let schema = input_schema(commands::GET_DOM);
+        assert!(schema["properties"].get("window_label").is_some());
+        assert!(schema["properties"].get("strip_scripts").is_some());
+        assert!(schema["properties"].get("show_action").is_none());
+    }
+}
@@ -0,0 +1,142 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value;
+use std::io::{Read, Write};
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{
+    AppFileEntry, AppFileResponse, FileEncoding, ReadAppFileRequest, WriteAppFileRequest,
+    WriteAppFileResponse,
+};
+use crate::socket_server::SocketResponse;
+
+/// Default maximum number of bytes returned by a read.
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+pub fn handle_read_app_file<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ReadAppFileRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for read_app_file: {}", e)))?;
+    respond(read(app, request))
+}
+
+pub fn handle_write_app_file<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: WriteAppFileRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for write_app_file: {}", e)))?;
+    respond(write(app, request))
+}
+
+fn read<R: Runtime>(
+    app: &AppHandle<R>,
+    request: ReadAppFileRequest,
+) -> crate::Result<AppFileResponse> {
+    let path = app
+        .tauri_mcp()
+        .file_roots()
+        .resolve(&request.root, &request.path, false)?;
+    let metadata = std::fs::metadata(&path)?;
+    let mut response = AppFileResponse {
+        path: path.display().to_string(),
+        size: metadata.len(),
+        encoding: request.encoding,
+        content: None,
+        truncated: false,
+        entries: None,
+    };
+
+    if metadata.is_dir() {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            let metadata = entry.metadata()?;
+            entries.push(AppFileEntry {
+                name: entry.file_name().to_string_lossy().into_owned(),
+                dir: metadata.is_dir(),
+                size: metadata.len(),
+            });
+        }
+        entries.sort_by(|a, b| a.name.cmp(&b.name));
+        response.entries = Some(entries);
+        return Ok(response);
+    }
+
+    let max_bytes = request.max_bytes.unwrap_or(DEFAULT_MAX_BYTES);
+    let mut bytes = Vec::new();
+    std::fs::File::open(&path)?
+        .take(max_bytes)
+        .read_to_end(&mut bytes)?;
+    response.truncated = (bytes.len() as u64) < metadata.len();
+    response.content = Some(match request.encoding {
+        FileEncoding::Base64 => STANDARD.encode(&bytes),
+        FileEncoding::Utf8 => match String::from_utf8(bytes) {
+            Ok(text) => text,
+            // The cut may fall inside a character
+            Err(e) if response.truncated && e.utf8_error().error_len().is_none() => {
+                let valid = e.utf8_error().valid_up_to();
+                let mut bytes = e.into_bytes();
+                bytes.truncate(valid);
+                String::from_utf8(bytes).unwrap_or_default()
+            }
+            Err(_) => {
+                return Err(Error::Anyhow(format!(
+                    "{} is not UTF-8 text, read it as base64",
+                    request.path
+                )));
+            }
+        },
+    });
+    Ok(response)
+}
+
+fn write<R: Runtime>(
+    app: &AppHandle<R>,
+    request: WriteAppFileRequest,
+) -> crate::Result<WriteAppFileResponse> {
+    let bytes = match request.encoding {
+        FileEncoding::Utf8 => request.content.into_bytes(),
+        FileEncoding::Base64 => STANDARD
+            .decode(&request.content)
+            .map_err(|e| Error::Anyhow(format!("Invalid base64 content: {}", e)))?,
+    };
+    let path = app
+        .tauri_mcp()
+        .file_roots()
+        .resolve(&request.root, &request.path, true)?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(request.append)
+        .truncate(!request.append)
+        .open(&path)?;
+    file.write_all(&bytes)?;
+    Ok(WriteAppFileResponse {
+        path: path.display().to_string(),
+        size: file.metadata()?.len(),
+    })
+}
+
+fn respond<T: serde::Serialize>(result: crate::Result<T>) -> Result<SocketResponse, Error> {
+    match result {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -0,0 +1,42 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::app_logs::AppLogs;
+use crate::error::Error;
+use crate::models::GetAppLogsRequest;
+use crate::socket_server::SocketResponse;
+
+const DEFAULT_LIMIT: usize = 500;
+
+pub fn handle_get_app_logs<R: Runtime>(
+    _app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: GetAppLogsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_app_logs: {}", e)))?;
+
+    let Some(logs) = AppLogs::get() else {
+        return Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(
+                "Log capture is not enabled, the app has to install tauri_plugin_mcp::LogBridge"
+                    .to_string(),
+            ),
+        });
+    };
+    let query = logs.query(
+        request.since.unwrap_or(0),
+        request.level,
+        request.target.as_deref(),
+        request.limit.unwrap_or(DEFAULT_LIMIT).max(1),
+    );
+
+    let data = serde_json::to_value(query)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,47 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{AppStateResponse, GetAppStateRequest};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_get_app_state<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: GetAppStateRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_app_state: {}", e)))?;
+
+    let inspector = app.tauri_mcp().state_inspector();
+    let exposed = inspector.names();
+    let mut states = BTreeMap::new();
+    let mut errors = BTreeMap::new();
+    for name in request.names.unwrap_or_else(|| exposed.clone()) {
+        match inspector.snapshot(&name) {
+            Some(Ok(state)) => {
+                states.insert(name, state);
+            }
+            Some(Err(e)) => {
+                errors.insert(name, e.to_string());
+            }
+            None => {
+                let error = format!("No state exposed as {}", name);
+                errors.insert(name, error);
+            }
+        }
+    }
+
+    let data = serde_json::to_value(AppStateResponse {
+        states,
+        errors,
+        exposed,
+    })
+    .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,89 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{ArtifactContentResponse, ArtifactRequest, ListArtifactsRequest};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_list_artifacts<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: ListArtifactsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for list_artifacts: {}", e)))?;
+
+    match app
+        .tauri_mcp()
+        .artifact_store()
+        .list(params.kind.as_deref())
+    {
+        Ok(artifacts) => {
+            let data = serde_json::to_value(artifacts)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_get_artifact<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: ArtifactRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_artifact: {}", e)))?;
+
+    match app.tauri_mcp().artifact_store().get(&params.id) {
+        Ok(content) => {
+            let response = ArtifactContentResponse {
+                id: params.id,
+                size: content.len() as u64,
+                content_base64: STANDARD.encode(&content),
+            };
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_delete_artifact<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: ArtifactRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for delete_artifact: {}", e)))?;
+
+    match app.tauri_mcp().artifact_store().delete(&params.id) {
+        Ok(()) => Ok(SocketResponse {
+            success: true,
+            data: Some(serde_json::json!({ "id": params.id })),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -0,0 +1,204 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{
+    AssertElementRequest, AssertTextContainsRequest, AssertUrlMatchesRequest, AssertionResult,
+};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Helpers of the assertion scripts. `queryElement` returns null instead of throwing when
+/// nothing matches, since a missing element is a failed assertion, not an error.
+const ASSERT_JS: &str = r#"
+const queryElement = (selectorType, selectorValue) => {
+    try {
+        return findElement(selectorType, selectorValue);
+    } catch (e) {
+        if (String(e && e.message).includes('not found')) return null;
+        throw e;
+    }
+};
+const describeSelector = (selectorType, selectorValue) => `${selectorType}="${selectorValue}"`;
+const verdict = (assertion, passed, expected, actual, message) =>
+    ({ assertion, passed, expected, actual, message });
+"#;
+
+const ELEMENT_EXISTS_SCRIPT: &str = r#"
+const target = describeSelector(options.selector_type, options.selector_value);
+const exists = queryElement(options.selector_type, options.selector_value) !== null;
+const passed = exists !== options.negate;
+return verdict('element_exists', passed, !options.negate, exists, passed
+    ? `Element ${target} ${exists ? 'exists' : 'does not exist'}`
+    : `Expected element ${target} ${options.negate ? 'not ' : ''}to exist, but it ${exists ? 'does' : 'does not'}`);
+"#;
+
+const ELEMENT_VISIBLE_SCRIPT: &str = r#"
+const target = describeSelector(options.selector_type, options.selector_value);
+const element = queryElement(options.selector_type, options.selector_value);
+let visible = false;
+let reason = 'it does not exist';
+if (element) {
+    const computed = window.getComputedStyle(element);
+    const rect = element.getBoundingClientRect();
+    if (typeof element.checkVisibility === 'function'
+        && !element.checkVisibility({ checkOpacity: true, checkVisibilityCSS: true })) {
+        reason = `it is not rendered (display: ${computed.display}, visibility: ${computed.visibility}, opacity: ${computed.opacity})`;
+    } else if (computed.display === 'none' || computed.visibility === 'hidden' || computed.opacity === '0') {
+        reason = `it is hidden (display: ${computed.display}, visibility: ${computed.visibility}, opacity: ${computed.opacity})`;
+    } else if (rect.width === 0 || rect.height === 0) {
+        reason = `it has no size (${rect.width}x${rect.height})`;
+    } else {
+        visible = true;
+        reason = 'it is visible';
+    }
+}
+const passed = visible !== options.negate;
+return verdict('element_visible', passed, !options.negate, visible, passed
+    ? `Element ${target} is ${visible ? '' : 'not '}visible`
+    : `Expected element ${target} ${options.negate ? 'not ' : ''}to be visible, but ${reason}`);
+"#;
+
+const TEXT_CONTAINS_SCRIPT: &str = r#"
+const scoped = options.selector_type && options.selector_value;
+const target = scoped ? describeSelector(options.selector_type, options.selector_value) : 'the page';
+const element = scoped ? queryElement(options.selector_type, options.selector_value) : document.body;
+if (!element) {
+    return verdict('text_contains', false, options.text, null,
+        `Expected ${target} to contain "${options.text}", but the element does not exist`);
+}
+const text = element.innerText !== undefined ? element.innerText : element.textContent;
+const normalize = (value) => options.ignore_case ? value.toLowerCase() : value;
+const contains = normalize(text).includes(normalize(options.text));
+const passed = contains !== options.negate;
+const MAX_ACTUAL = 2000;
+const actual = text.length > MAX_ACTUAL ? text.slice(0, MAX_ACTUAL) + '...' : text;
+return verdict('text_contains', passed, options.text, actual, passed
+    ? `${target} ${contains ? 'contains' : 'does not contain'} "${options.text}"`
+    : `Expected ${target} ${options.negate ? 'not ' : ''}to contain "${options.text}", but it ${contains ? 'does' : 'does not'}`);
+"#;
+
+const URL_MATCHES_SCRIPT: &str = r#"
+const url = window.location.href;
+let matches;
+if (options.regex) {
+    matches = new RegExp(options.pattern).test(url);
+} else {
+    matches = options.exact ? url === options.pattern : url.includes(options.pattern);
+}
+const how = options.regex ? 'match' : (options.exact ? 'equal' : 'contain');
+const verbs = {
+    match: ['matches', 'does not match'],
+    equal: ['equals', 'does not equal'],
+    contain: ['contains', 'does not contain'],
+};
+const passed = matches !== options.negate;
+return verdict('url_matches', passed, options.pattern, url, passed
+    ? `URL ${url} ${verbs[how][matches ? 0 : 1]} ${options.pattern}`
+    : `Expected URL ${options.negate ? 'not ' : ''}to ${how} ${options.pattern}, but it is ${url}`);
+"#;
+
+pub async fn handle_assert_element_exists<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: AssertElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for assert_element_exists: {}", e)))?;
+    run_assertion(
+        app,
+        &request.window_label,
+        &request,
+        ELEMENT_EXISTS_SCRIPT,
+        timeout,
+    )
+    .await
+}
+
+pub async fn handle_assert_element_visible<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: AssertElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for assert_element_visible: {}", e)))?;
+    run_assertion(
+        app,
+        &request.window_label,
+        &request,
+        ELEMENT_VISIBLE_SCRIPT,
+        timeout,
+    )
+    .await
+}
+
+pub async fn handle_assert_text_contains<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: AssertTextContainsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for assert_text_contains: {}", e)))?;
+    run_assertion(
+        app,
+        &request.window_label,
+        &request,
+        TEXT_CONTAINS_SCRIPT,
+        timeout,
+    )
+    .await
+}
+
+pub async fn handle_assert_url_matches<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: AssertUrlMatchesRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for assert_url_matches: {}", e)))?;
+    run_assertion(
+        app,
+        &request.window_label,
+        &request,
+        URL_MATCHES_SCRIPT,
+        timeout,
+    )
+    .await
+}
+
+/// Evaluate an assertion script. A failed assertion is a successful response with
+/// `passed: false`; only a script or bridge failure is an error.
+async fn run_assertion<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: &str,
+    options: &impl Serialize,
+    body: &str,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let window = crate::windows::resolve_window(app, window_label)?;
+
+    let script = element_script(options, &format!("{}{}", ASSERT_JS, body))
+        .map_err(|e| Error::Anyhow(format!("Invalid assertion options: {}", e)))?;
+    let result = match bridge::eval(&window, &script, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to evaluate assertion: {}", e)),
+            });
+        }
+    };
+    let result: AssertionResult = serde_json::from_value(result)
+        .map_err(|e| Error::Anyhow(format!("Invalid assertion result: {}", e)))?;
+    let data = serde_json::to_value(result)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,29 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{AuditLogRequest, AuditLogResponse};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_get_audit_log<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: AuditLogRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_audit_log: {}", e)))?;
+
+    let audit = app.tauri_mcp().audit_log();
+    let response = AuditLogResponse {
+        entries: audit.entries(params.limit, params.command.as_deref()),
+        artifact_id: audit.artifact_id(),
+    };
+
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
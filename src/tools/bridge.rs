@@ -0,0 +1,250 @@
+use log::debug;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Listener, Manager, Runtime, WebviewWindow};
+use tokio::sync::oneshot;
+
+/// Why a webview did not answer a bridge request.
+#[derive(Debug)]
+pub enum BridgeError {
+    /// The request event could not be emitted.
+    Emit(String),
+    /// No response arrived in time.
+    Timeout(Duration),
+    /// The listener was removed before a response arrived.
+    Closed,
+    /// An injected script could not be evaluated or threw.
+    Script(String),
+}
+
+impl fmt::Display for BridgeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BridgeError::Emit(e) => write!(f, "failed to emit event: {}", e),
+            BridgeError::Timeout(timeout) => {
+                write!(f, "no response within {} ms", timeout.as_millis())
+            }
+            BridgeError::Closed => write!(f, "response listener closed"),
+            BridgeError::Script(e) => write!(f, "script failed: {}", e),
+        }
+    }
+}
+
+/// Event the scripts injected by [`eval`] answer on.
+const EVAL_RESPONSE_EVENT: &str = "tauri-mcp:eval-response";
+
+/// Payload emitted to the webview: the request ID is echoed back in the response.
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Envelope<P> {
+    request_id: u64,
+    payload: P,
+}
+
+struct Waiter {
+    response_event: String,
+    tx: oneshot::Sender<String>,
+}
+
+#[derive(Default)]
+struct Registry {
+    waiters: HashMap<u64, Waiter>,
+    /// Response events that already have a routing listener.
+    listening: HashSet<String>,
+}
+
+impl Registry {
+    /// Hand a response to its waiter. Responses without a request ID come from frontends
+    /// predating correlation IDs and go to the oldest request waiting on that event.
+    fn route(&mut self, response_event: &str, raw: &str) {
+        let parsed: Option<Value> = serde_json::from_str(raw).ok();
+        let request_id = parsed
+            .as_ref()
+            .and_then(|value| value.get("requestId"))
+            .and_then(Value::as_u64);
+
+        let (id, response) = match request_id {
+            Some(id) => {
+                let payload = parsed
+                    .as_ref()
+                    .and_then(|value| value.get("payload"))
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                (Some(id), payload.to_string())
+            }
+            None => {
+                let oldest = self
+                    .waiters
+                    .iter()
+                    .filter(|(_, waiter)| waiter.response_event == response_event)
+                    .map(|(id, _)| *id)
+                    .min();
+                (oldest, raw.to_string())
+            }
+        };
+
+        match id.and_then(|id| self.waiters.remove(&id)) {
+            Some(waiter) => {
+                let _ = waiter.tx.send(response);
+            }
+            None => debug!(
+                "[TAURI_MCP] Dropping {} with no pending request (id {:?})",
+                response_event, request_id
+            ),
+        }
+    }
+}
+
+/// Webview requests waiting for a response, matched by request ID.
+#[derive(Default)]
+pub struct PendingRequests {
+    next_id: AtomicU64,
+    registry: Arc<Mutex<Registry>>,
+}
+
+impl PendingRequests {
+    /// Register a waiter, installing the routing listener for `response_event` on first use.
+    fn register<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        response_event: &str,
+    ) -> (u64, oneshot::Receiver<String>) {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        let (tx, rx) = oneshot::channel();
+
+        let mut registry = self.registry.lock().unwrap();
+        if registry.listening.insert(response_event.to_string()) {
+            let routes = self.registry.clone();
+            let event_name = response_event.to_string();
+            app.listen(response_event, move |event| {
+                routes.lock().unwrap().route(&event_name, event.payload());
+            });
+        }
+        registry.waiters.insert(
+            id,
+            Waiter {
+                response_event: response_event.to_string(),
+                tx,
+            },
+        );
+        (id, rx)
+    }
+}
+
+/// Removes the pending request when it ends, including when its future is dropped because
+/// the request was cancelled.
+struct WaiterGuard<'a> {
+    pending: &'a PendingRequests,
+    id: u64,
+}
+
+impl Drop for WaiterGuard<'_> {
+    fn drop(&mut self) {
+        // Harmless if the response already arrived
+        self.pending
+            .registry
+            .lock()
+            .unwrap()
+            .waiters
+            .remove(&self.id);
+    }
+}
+
+/// Emit `event` to the `target` window and wait for the matching `response_event`.
+///
+/// The payload is sent as `{ requestId, payload }` and the webview answers with the same
+/// shape, so overlapping requests each get their own response. The request is registered
+/// before emitting, so a fast answer cannot be missed, and is removed on timeout.
+pub async fn request<R: Runtime, P: Serialize + Clone>(
+    app: &AppHandle<R>,
+    target: &str,
+    event: &str,
+    payload: P,
+    response_event: &str,
+    timeout: Duration,
+) -> Result<String, BridgeError> {
+    let pending = app.state::<PendingRequests>();
+    let (request_id, rx) = pending.register(app, response_event);
+    let _guard = WaiterGuard {
+        pending: &pending,
+        id: request_id,
+    };
+
+    app.emit_to(
+        target,
+        event,
+        Envelope {
+            request_id,
+            payload,
+        },
+    )
+    .map_err(|e| BridgeError::Emit(e.to_string()))?;
+
+    wait(rx, timeout).await
+}
+
+/// Run `body` as an async function in `window` and return the value it resolves to.
+///
+/// Unlike [`request`], this needs no listener in the app's frontend: the script reports
+/// back through the event plugin of the Tauri internals, which every webview has.
+pub async fn eval<R: Runtime>(
+    window: &WebviewWindow<R>,
+    body: &str,
+    timeout: Duration,
+) -> Result<Value, BridgeError> {
+    let app = window.app_handle();
+    let pending = app.state::<PendingRequests>();
+    let (request_id, rx) = pending.register(app, EVAL_RESPONSE_EVENT);
+    let _guard = WaiterGuard {
+        pending: &pending,
+        id: request_id,
+    };
+
+    let script = format!(
+        r#"(async () => {{
+    let payload;
+    try {{
+        payload = {{ success: true, data: await (async () => {{ {body} }})() }};
+    }} catch (e) {{
+        payload = {{ success: false, error: e instanceof Error ? e.toString() : String(e) }};
+    }}
+    await window.__TAURI_INTERNALS__.invoke('plugin:event|emit', {{
+        event: '{event}',
+        payload: {{ requestId: {request_id}, payload }}
+    }});
+}})();"#,
+        body = body,
+        event = EVAL_RESPONSE_EVENT,
+        request_id = request_id,
+    );
+    window
+        .eval(&script)
+        .map_err(|e| BridgeError::Script(e.to_string()))?;
+
+    let response: Value = serde_json::from_str(&wait(rx, timeout).await?)
+        .map_err(|e| BridgeError::Script(format!("invalid response: {}", e)))?;
+    if response.get("success").and_then(Value::as_bool) == Some(true) {
+        Ok(response.get("data").cloned().unwrap_or(Value::Null))
+    } else {
+        Err(BridgeError::Script(
+            response
+                .get("error")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown error")
+                .to_string(),
+        ))
+    }
+}
+
+async fn wait(rx: oneshot::Receiver<String>, timeout: Duration) -> Result<String, BridgeError> {
+    match tokio::time::timeout(timeout, rx).await {
+        Ok(Ok(response)) => Ok(response),
+        Ok(Err(_)) => Err(BridgeError::Closed),
+        Err(_) => Err(BridgeError::Timeout(timeout)),
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{CancelRequest, CancelResponse};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_cancel<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    client: Option<u64>,
+) -> Result<SocketResponse, Error> {
+    let params: CancelRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for cancel: {}", e)))?;
+
+    let response = CancelResponse {
+        // Only the client that sent a request can cancel it
+        cancelled: client.is_some_and(|client| {
+            app.tauri_mcp()
+                .cancellations()
+                .cancel(client, &params.request_id)
+        }),
+    };
+
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,118 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::CaptureCanvasRequest;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Captures the canvases matching the selector. WebGL drawing buffers are cleared once
+/// shown unless `preserveDrawingBuffer` is set, so canvases are read in an animation frame
+/// callback, right after the app's own callback drew; blank results are retried after a
+/// resize event, on which chart libraries redraw.
+const CAPTURE_CANVAS_SCRIPT: &str = r#"
+const matches = (() => {
+    if (!options.all) return [findElement(options.selector_type, options.selector_value)];
+    if (options.selector_type === 'css') return Array.from(document.querySelectorAll(options.selector_value));
+    if (options.selector_type === 'tag') return Array.from(document.getElementsByTagName(options.selector_value));
+    if (options.selector_type === 'class') return Array.from(document.getElementsByClassName(options.selector_value));
+    return [findElement(options.selector_type, options.selector_value)];
+})();
+const canvases = matches.filter((element) => element instanceof HTMLCanvasElement);
+if (!canvases.length) {
+    throw new Error(`No canvas element with ${options.selector_type}="${options.selector_value}"`);
+}
+const hook = window.__TAURI_MCP_CANVAS__;
+const requested = `image/${options.format}`;
+
+const isBlank = (canvas) => {
+    const probe = document.createElement('canvas');
+    probe.width = Math.max(1, Math.min(canvas.width, 64));
+    probe.height = Math.max(1, Math.min(canvas.height, 64));
+    const context = probe.getContext('2d');
+    context.drawImage(canvas, 0, 0, probe.width, probe.height);
+    const pixels = context.getImageData(0, 0, probe.width, probe.height).data;
+    for (let i = 3; i < pixels.length; i += 4) {
+        if (pixels[i] !== 0) return false;
+    }
+    return true;
+};
+const capture = (canvas, index) => {
+    const info = (hook && hook.contexts.get(canvas)) || null;
+    const rect = canvas.getBoundingClientRect();
+    const result = {
+        index,
+        id: canvas.id || null,
+        width: canvas.width,
+        height: canvas.height,
+        rect: { x: rect.x, y: rect.y, width: rect.width, height: rect.height },
+        context: info ? info.type : null,
+        preserveDrawingBuffer: info ? info.preserveDrawingBuffer : null,
+        mimeType: requested,
+        data: null,
+        blank: false,
+        error: null,
+    };
+    try {
+        const url = canvas.toDataURL(requested, options.quality == null ? undefined : options.quality);
+        // Unsupported formats fall back to PNG
+        result.mimeType = url.slice(5, url.indexOf(';'));
+        result.data = url.slice(url.indexOf(',') + 1);
+        result.blank = canvas.width === 0 || canvas.height === 0 || isBlank(canvas);
+    } catch (e) {
+        // Canvases with cross-origin images are tainted and cannot be read
+        result.error = e instanceof Error ? e.toString() : String(e);
+    }
+    return result;
+};
+const captureAll = () => new Promise((resolve) => {
+    requestAnimationFrame(() => resolve(canvases.map(capture)));
+});
+
+let results = await captureAll();
+if (options.rerender && results.some((result) => result.blank)) {
+    window.dispatchEvent(new Event('resize'));
+    await new Promise((resolve) => requestAnimationFrame(resolve));
+    const retried = await captureAll();
+    results = results.map((result, i) => (result.blank && !retried[i].blank ? retried[i] : result));
+}
+return { canvases: results };
+"#;
+
+pub async fn handle_capture_canvas<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: CaptureCanvasRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for capture_canvas: {}", e)))?;
+    if !matches!(request.format.as_str(), "png" | "jpeg" | "webp") {
+        return Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!(
+                "Unsupported format {}, expected png, jpeg or webp",
+                request.format
+            )),
+        });
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, CAPTURE_CANVAS_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid capture_canvas options: {}", e)))?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(capture) => Ok(SocketResponse {
+            success: true,
+            data: Some(capture),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to capture canvas: {}", e)),
+        }),
+    }
+}
@@ -0,0 +1,176 @@
+use log::info;
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+
+use crate::TauriMcpExt;
+use crate::capabilities::{self, Capability, CapabilityState};
+use crate::coordinates::{CoordinateSpace, WindowGeometry};
+use crate::error::Error;
+use crate::models::{ClickElementRequest, ClickElementResponse, InputMode};
+use crate::platform::input::{Key, MouseButton};
+use crate::shared::commands;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Scrolls the element into view unless `options.scroll_into_view` is off, and returns its
+/// center in viewport CSS pixels. Clicks it with synthetic DOM events unless
+/// `options.resolve_only` is set.
+const CLICK_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+if (options.scroll_into_view) await scrollIntoViewIfNeeded(element, { block: 'center', inline: 'center' });
+const rect = element.getBoundingClientRect();
+const x = rect.left + rect.width / 2;
+const y = rect.top + rect.height / 2;
+const info = { x, y, tag: element.tagName.toLowerCase(), id: element.id, text: (element.innerText || '').trim().slice(0, 100) };
+if (options.resolve_only) return info;
+
+const button = { left: 0, middle: 1, right: 2 }[options.button];
+const modifiers = {
+    shiftKey: options.modifiers.includes('shift'),
+    ctrlKey: options.modifiers.includes('ctrl'),
+    altKey: options.modifiers.includes('alt'),
+    metaKey: options.modifiers.includes('meta'),
+};
+const init = (detail) => ({
+    bubbles: true, cancelable: true, composed: true, view: window,
+    clientX: x, clientY: y, screenX: window.screenX + x, screenY: window.screenY + y,
+    button, buttons: 1 << [0, 2, 1][button], detail, ...modifiers,
+});
+for (let detail = 1; detail <= options.click_count; detail++) {
+    element.dispatchEvent(new PointerEvent('pointerdown', { ...init(detail), pointerType: 'mouse', isPrimary: true }));
+    element.dispatchEvent(new MouseEvent('mousedown', init(detail)));
+    if (detail === 1 && button === 0 && typeof element.focus === 'function') element.focus();
+    element.dispatchEvent(new PointerEvent('pointerup', { ...init(detail), buttons: 0, pointerType: 'mouse', isPrimary: true }));
+    element.dispatchEvent(new MouseEvent('mouseup', { ...init(detail), buttons: 0 }));
+    if (button === 0) {
+        element.dispatchEvent(new MouseEvent('click', { ...init(detail), buttons: 0 }));
+    } else if (button === 1) {
+        element.dispatchEvent(new MouseEvent('auxclick', { ...init(detail), buttons: 0 }));
+    }
+}
+if (button === 0 && options.click_count === 2) element.dispatchEvent(new MouseEvent('dblclick', { ...init(2), buttons: 0 }));
+if (button === 2) element.dispatchEvent(new MouseEvent('contextmenu', { ...init(1), buttons: 0 }));
+return info;
+"#;
+
+pub async fn handle_click_element<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let mut request: ClickElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for click_element: {}", e)))?;
+    validate(&mut request)?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let use_os = match request.strategy.as_str() {
+        "os" => {
+            let mode = app
+                .tauri_mcp()
+                .input_mode(commands::CLICK_ELEMENT, InputMode::Os);
+            mode == InputMode::Os
+        }
+        "auto" => os_input_available(app),
+        _ => false,
+    };
+
+    // With the OS mouse, the script only locates the element
+    let mut options = serde_json::to_value(&request)
+        .map_err(|e| Error::Anyhow(format!("Invalid click_element options: {}", e)))?;
+    options["resolve_only"] = Value::Bool(use_os);
+    let script = element_script(&options, CLICK_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid click_element options: {}", e)))?;
+    let target = match bridge::eval(&window, &script, timeout).await {
+        Ok(target) => target,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to click element: {}", e)),
+            });
+        }
+    };
+    let x = target.get("x").and_then(Value::as_f64).unwrap_or_default();
+    let y = target.get("y").and_then(Value::as_f64).unwrap_or_default();
+
+    if use_os {
+        os_click(&window, &request, x, y)?;
+    }
+
+    let response = ClickElementResponse {
+        strategy: if use_os { "os" } else { "dom" }.to_string(),
+        x,
+        y,
+        element: target,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+fn validate(request: &mut ClickElementRequest) -> Result<(), Error> {
+    if !matches!(request.button.as_str(), "left" | "middle" | "right") {
+        return Err(Error::Anyhow(format!(
+            "Unsupported button: {} (expected left, middle or right)",
+            request.button
+        )));
+    }
+    if !matches!(request.strategy.as_str(), "auto" | "dom" | "os") {
+        return Err(Error::Anyhow(format!(
+            "Unsupported strategy: {} (expected auto, dom or os)",
+            request.strategy
+        )));
+    }
+    request.click_count = request.click_count.clamp(1, 3);
+    for modifier in request.modifiers.iter_mut() {
+        *modifier = match modifier.to_ascii_lowercase().as_str() {
+            "shift" => "shift",
+            "ctrl" | "control" => "ctrl",
+            "alt" | "option" => "alt",
+            "meta" | "cmd" | "command" | "super" => "meta",
+            other => return Err(Error::Anyhow(format!("Unsupported modifier: {}", other))),
+        }
+        .to_string();
+    }
+    Ok(())
+}
+
+fn os_input_available<R: Runtime>(app: &AppHandle<R>) -> bool {
+    capabilities::probe(app.tauri_mcp().headless())
+        .iter()
+        .any(|r| r.capability == Capability::OsInput && r.state == CapabilityState::Available)
+}
+
+/// Click at a point of the webview, given in viewport CSS pixels, with OS-level input.
+fn os_click<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &ClickElementRequest,
+    x: f64,
+    y: f64,
+) -> Result<(), Error> {
+    let (screen_x, screen_y) =
+        WindowGeometry::of(window)?.os_input_point(CoordinateSpace::Viewport, (x, y));
+    info!(
+        "[TAURI_MCP] OS click at ({}, {}) for viewport point ({}, {})",
+        screen_x, screen_y, x, y
+    );
+
+    // Modifiers and button were checked by validate
+    let modifiers: Vec<Key> = request
+        .modifiers
+        .iter()
+        .filter_map(|modifier| Key::parse(modifier))
+        .collect();
+    let button = MouseButton::parse(&request.button).unwrap_or(MouseButton::Left);
+
+    let mut input = window.app_handle().tauri_mcp().input()?;
+    input.move_to(screen_x, screen_y)?;
+    input.click(button, request.click_count, &modifiers)
+}
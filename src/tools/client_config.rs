@@ -0,0 +1,23 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::GenerateClientConfigRequest;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_generate_client_config<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: GenerateClientConfigRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for generate_client_config: {}", e)))?;
+    let response = app.tauri_mcp().generate_client_config(request);
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,21 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_list_clients<R: Runtime>(
+    app: &AppHandle<R>,
+    _payload: Value,
+) -> Result<SocketResponse, Error> {
+    let clients = app.tauri_mcp().clients().list();
+
+    let data = serde_json::to_value(clients)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,88 @@
+use log::debug;
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::coordinates::{CoordinateSpace, WindowGeometry};
+use crate::error::Error;
+use crate::models::{ConvertCoordinatesRequest, ConvertCoordinatesResponse, CoordinatePoint};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+
+pub async fn handle_convert_coordinates<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: ConvertCoordinatesRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for convert_coordinates: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let geometry = match WindowGeometry::of(&window) {
+        Ok(geometry) => geometry,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            });
+        }
+    };
+    // Document coordinates need the scroll offset of the page
+    let scroll = match bridge::eval(
+        &window,
+        "return { x: window.scrollX, y: window.scrollY };",
+        timeout,
+    )
+    .await
+    {
+        Ok(scroll) => scroll
+            .get("x")
+            .and_then(Value::as_f64)
+            .zip(scroll.get("y").and_then(Value::as_f64)),
+        Err(e) => {
+            debug!(
+                "[TAURI_MCP] No scroll offset from {}: {}",
+                window.label(),
+                e
+            );
+            None
+        }
+    };
+    if request.from == CoordinateSpace::Document && scroll.is_none() {
+        return Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some("The page did not report its scroll offset".to_string()),
+        });
+    }
+    let (scroll_x, scroll_y) = scroll.unwrap_or_default();
+    let geometry = geometry.with_scroll(scroll_x, scroll_y);
+
+    let point = (request.x, request.y);
+    let to = |space: CoordinateSpace| {
+        let (x, y) = geometry.convert(request.from, space, point);
+        CoordinatePoint { x, y }
+    };
+    let (os_x, os_y) = geometry.os_input_point(request.from, point);
+    let response = ConvertCoordinatesResponse {
+        geometry,
+        viewport: to(CoordinateSpace::Viewport),
+        document: scroll.map(|_| to(CoordinateSpace::Document)),
+        window: to(CoordinateSpace::Window),
+        logical: to(CoordinateSpace::Logical),
+        physical: to(CoordinateSpace::Physical),
+        os_input: CoordinatePoint {
+            x: f64::from(os_x),
+            y: f64::from(os_y),
+        },
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,41 @@
+use serde_json::Value;
+use tauri::{AppHandle, Emitter, Runtime, Url};
+
+use crate::error::Error;
+use crate::models::{TriggerDeepLinkRequest, TriggerDeepLinkResponse};
+use crate::socket_server::SocketResponse;
+
+/// Event of tauri-plugin-deep-link, heard by its `on_open_url` and the JS `onOpenUrl`.
+const DEEP_LINK_EVENT: &str = "deep-link://new-url";
+
+pub fn handle_trigger_deep_link<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: TriggerDeepLinkRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for trigger_deep_link: {}", e)))?;
+
+    let url = match Url::parse(&request.url) {
+        Ok(url) => url,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Invalid URL {}: {}", request.url, e)),
+            });
+        }
+    };
+    let event = request.event.as_deref().unwrap_or(DEEP_LINK_EVENT);
+    app.emit(event, vec![url.to_string()])?;
+
+    let data = serde_json::to_value(TriggerDeepLinkResponse {
+        url: url.to_string(),
+        event: event.to_string(),
+    })
+    .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,38 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::capabilities::{self, CapabilityState};
+use crate::error::Error;
+use crate::models::{DescribeToolsRequest, DescribeToolsResponse};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_describe_tools<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: DescribeToolsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for describe_tools: {}", e)))?;
+    let include_unavailable = params.include_unavailable.unwrap_or(false);
+
+    let headless = app.tauri_mcp().headless();
+    let reports = capabilities::probe(headless);
+    let tools = capabilities::describe_tools(&reports)
+        .into_iter()
+        .filter(|tool| include_unavailable || tool.state != CapabilityState::Unavailable)
+        .collect();
+
+    let response = DescribeToolsResponse {
+        platform: std::env::consts::OS.to_string(),
+        headless,
+        capabilities: reports,
+        tools,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,94 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{
+    DialogsResponse, RespondToDialogRequest, SetDialogInterceptionRequest, SetDialogPolicyRequest,
+};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_list_dialogs<R: Runtime>(
+    app: &AppHandle<R>,
+    _payload: Value,
+) -> Result<SocketResponse, Error> {
+    dialogs_response(app)
+}
+
+pub fn handle_respond_to_dialog<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: RespondToDialogRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for respond_to_dialog: {}", e)))?;
+
+    match app.tauri_mcp().dialogs().respond(
+        app,
+        request.dialog_id.as_deref(),
+        request.value.unwrap_or(Value::Null),
+    ) {
+        Ok(dialog) => {
+            let data = serde_json::to_value(dialog)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_set_dialog_interception<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetDialogInterceptionRequest = serde_json::from_value(payload).map_err(|e| {
+        Error::Anyhow(format!(
+            "Invalid payload for set_dialog_interception: {}",
+            e
+        ))
+    })?;
+
+    let dialogs = app.tauri_mcp().dialogs();
+    if let Some(auto_responses) = request.auto_responses {
+        dialogs.set_auto_responses(auto_responses);
+    }
+    if let Some(enabled) = request.enabled {
+        dialogs.set_enabled(app, enabled);
+    }
+    dialogs_response(app)
+}
+
+pub fn handle_set_dialog_policy<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetDialogPolicyRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_dialog_policy: {}", e)))?;
+
+    app.tauri_mcp().dialogs().set_policy(app, request.policy);
+    dialogs_response(app)
+}
+
+fn dialogs_response<R: Runtime>(app: &AppHandle<R>) -> Result<SocketResponse, Error> {
+    let dialogs = app.tauri_mcp().dialogs();
+    let response = DialogsResponse {
+        enabled: dialogs.is_enabled(),
+        pending: dialogs.pending(),
+        policy: dialogs.policy(),
+        answered: dialogs.answered(),
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,342 @@
+use log::info;
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
+use tauri::{Runtime, WebviewWindow};
+use tokio_util::sync::CancellationToken;
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::coordinates::{CoordinateSpace, WindowGeometry};
+use crate::error::Error;
+use crate::models::{MouseMovementRequest, MouseMovementResponse, TextInputFailure};
+use crate::platform::input::Key;
+use crate::platform::keycodes::KeyCode;
+use crate::tools::bridge;
+
+/// Types `options.text` into the focused element, looking through shadow roots, with the
+/// keydown, keypress, beforeinput, input and keyup events a keyboard would cause, one
+/// grapheme at a time. CJK characters go through composition events as an input method
+/// would send them. With `options.paste`, dispatches a paste event and inserts the whole
+/// text. Values go through the native setters, so framework-controlled inputs see the
+/// change.
+const DOM_TYPE_SCRIPT: &str = r#"
+let target = document.activeElement || document.body;
+while (target && target.shadowRoot && target.shadowRoot.activeElement) target = target.shadowRoot.activeElement;
+if (!target) throw new Error('No element to type into');
+const codeOf = (ch) => {
+    if (/^[a-z]$/i.test(ch)) return `Key${ch.toUpperCase()}`;
+    if (/^[0-9]$/.test(ch)) return `Digit${ch}`;
+    if (ch === ' ') return 'Space';
+    return '';
+};
+const isField = target instanceof HTMLInputElement || target instanceof HTMLTextAreaElement;
+const setValue = (value) => {
+    const setter = Object.getOwnPropertyDescriptor(Object.getPrototypeOf(target), 'value');
+    if (setter && setter.set) setter.set.call(target, value);
+    else target.value = value;
+};
+const insert = (data) => {
+    if (isField) {
+        const start = target.selectionStart ?? target.value.length;
+        const end = target.selectionEnd ?? start;
+        setValue(target.value.slice(0, start) + data + target.value.slice(end));
+        try {
+            target.setSelectionRange(start + data.length, start + data.length);
+        } catch (_) {
+            // Inputs like number and email have no selection
+        }
+        return false;
+    }
+    // The editing command fires the input events itself
+    return target.isContentEditable && document.execCommand('insertText', false, data);
+};
+const events = { bubbles: true, cancelable: true, composed: true };
+const count = (text) => Array.from(text).length;
+if (options.paste) {
+    const clipboardData = new DataTransfer();
+    clipboardData.setData('text/plain', options.text);
+    const paste = new Event('paste', events);
+    Object.defineProperty(paste, 'clipboardData', { value: clipboardData });
+    // A page handling the paste itself cancels it
+    if (target.dispatchEvent(paste)) {
+        const inputInit = { ...events, inputType: 'insertFromPaste', data: options.text };
+        if (target.dispatchEvent(new InputEvent('beforeinput', inputInit)) && !insert(options.text)) {
+            target.dispatchEvent(new InputEvent('input', { ...inputInit, cancelable: false }));
+        }
+    }
+    return { typed: count(options.text), tag: target.tagName.toLowerCase(), id: target.id };
+}
+
+// Characters typed through an input method rather than a key of their own
+const IME = /[\u1100-\u11ff\u2e80-\u9fff\uac00-\ud7af\uf900-\ufaff\uff00-\uffef]/;
+const compose = (data) => {
+    target.dispatchEvent(new KeyboardEvent('keydown', { ...events, key: 'Process', isComposing: true }));
+    target.dispatchEvent(new CompositionEvent('compositionstart', { ...events, data: '' }));
+    target.dispatchEvent(new CompositionEvent('compositionupdate', { ...events, data }));
+    const inputInit = { ...events, cancelable: false, inputType: 'insertCompositionText', data, isComposing: true };
+    target.dispatchEvent(new InputEvent('beforeinput', inputInit));
+    if (!insert(data)) target.dispatchEvent(new InputEvent('input', inputInit));
+    target.dispatchEvent(new CompositionEvent('compositionend', { ...events, data }));
+    target.dispatchEvent(new KeyboardEvent('keyup', { ...events, key: 'Process' }));
+};
+const graphemes = typeof Intl.Segmenter === 'function'
+    ? Array.from(new Intl.Segmenter(undefined, { granularity: 'grapheme' }).segment(options.text), (s) => s.segment)
+    : Array.from(options.text);
+let typed = 0;
+for (const ch of graphemes) {
+    if (IME.test(ch)) {
+        compose(ch);
+        typed += count(ch);
+        continue;
+    }
+    const enter = ch === '\n';
+    const key = { key: enter ? 'Enter' : ch, code: enter ? 'Enter' : codeOf(ch) };
+    const init = { ...key, bubbles: true, cancelable: true, composed: true };
+    if (target.dispatchEvent(new KeyboardEvent('keydown', init))) {
+        target.dispatchEvent(new KeyboardEvent('keypress', init));
+        if (enter && target instanceof HTMLInputElement) {
+            if (target.form) target.form.requestSubmit();
+        } else {
+            const data = enter && !isField ? null : ch;
+            const inputType = enter ? (isField ? 'insertLineBreak' : 'insertParagraph') : 'insertText';
+            const inputInit = { inputType, data, bubbles: true, cancelable: true, composed: true };
+            if (target.dispatchEvent(new InputEvent('beforeinput', inputInit)) && !insert(ch)) {
+                target.dispatchEvent(new InputEvent('input', { ...inputInit, cancelable: false }));
+            }
+        }
+    }
+    target.dispatchEvent(new KeyboardEvent('keyup', init));
+    typed += count(ch);
+}
+return { typed, tag: target.tagName.toLowerCase(), id: target.id };
+"#;
+
+/// Presses `options.keys` on the focused element, looking through shadow roots: keydown
+/// in order, then keyup in reverse, with the modifier flags of the keys held.
+const DOM_KEYS_SCRIPT: &str = r#"
+let target = document.activeElement || document.body;
+while (target && target.shadowRoot && target.shadowRoot.activeElement) target = target.shadowRoot.activeElement;
+if (!target) throw new Error('No element to press keys on');
+const modifiers = { Shift: 'shiftKey', Control: 'ctrlKey', Alt: 'altKey', Meta: 'metaKey' };
+const held = { shiftKey: false, ctrlKey: false, altKey: false, metaKey: false };
+const send = (type, { key, code }) => target.dispatchEvent(new KeyboardEvent(type, {
+    key: held.shiftKey && /^[a-z]$/.test(key) ? key.toUpperCase() : key,
+    code, ...held, bubbles: true, cancelable: true, composed: true,
+}));
+for (const key of options.keys) {
+    if (modifiers[key.key]) held[modifiers[key.key]] = true;
+    send('keydown', key);
+}
+for (const key of options.keys.slice().reverse()) {
+    send('keyup', key);
+    if (modifiers[key.key]) held[modifiers[key.key]] = false;
+}
+return { tag: target.tagName.toLowerCase(), id: target.id };
+"#;
+
+/// Moves a synthetic pointer to `options.x`, `options.y` in viewport CSS pixels, or by
+/// that much with `options.relative`, and clicks with `options.click`. The pointer is kept
+/// in the page, so relative moves and over/out events follow from the last one.
+const DOM_MOUSE_SCRIPT: &str = r#"
+const state = window.__TAURI_MCP_POINTER__ || (window.__TAURI_MCP_POINTER__ = { x: 0, y: 0, over: null });
+const x = options.relative ? state.x + options.x : options.x;
+const y = options.relative ? state.y + options.y : options.y;
+const target = document.elementFromPoint(x, y) || document.documentElement;
+const init = (extra) => ({
+    bubbles: true, cancelable: true, composed: true, view: window,
+    clientX: x, clientY: y, screenX: window.screenX + x, screenY: window.screenY + y,
+    button: 0, buttons: 0, ...extra,
+});
+const pointer = (element, type, extra) => element.dispatchEvent(
+    new PointerEvent(type, { ...init(extra), pointerId: 1, pointerType: 'mouse', isPrimary: true }));
+const mouse = (element, type, extra) => element.dispatchEvent(new MouseEvent(type, init(extra)));
+
+if (state.over !== target) {
+    if (state.over && state.over.isConnected) {
+        pointer(state.over, 'pointerout', { relatedTarget: target });
+        mouse(state.over, 'mouseout', { relatedTarget: target });
+    }
+    const previous = state.over && state.over.isConnected ? state.over : null;
+    pointer(target, 'pointerover', { relatedTarget: previous });
+    mouse(target, 'mouseover', { relatedTarget: previous });
+}
+pointer(target, 'pointermove');
+mouse(target, 'mousemove');
+state.x = x;
+state.y = y;
+state.over = target;
+
+if (options.click) {
+    const button = { left: 0, middle: 1, right: 2 }[options.button] ?? 0;
+    const down = { button, buttons: 1 << [0, 2, 1][button], detail: 1 };
+    const up = { button, buttons: 0, detail: 1 };
+    pointer(target, 'pointerdown', down);
+    if (mouse(target, 'mousedown', down) && button === 0) {
+        const focusable = target.closest('input, select, textarea, button, a[href], [tabindex], [contenteditable]');
+        if (focusable && typeof focusable.focus === 'function') focusable.focus();
+    }
+    pointer(target, 'pointerup', up);
+    mouse(target, 'mouseup', up);
+    if (button === 0) mouse(target, 'click', up);
+    else if (button === 1) mouse(target, 'auxclick', up);
+    else mouse(target, 'contextmenu', up);
+}
+return { x, y, tag: target.tagName.toLowerCase(), id: target.id };
+"#;
+
+/// Type `text` into the focused element of `window` with synthetic events, one grapheme
+/// every `delay`, stopping between two graphemes once `cancel` is triggered. Returns the
+/// number of characters typed and the graphemes that failed.
+pub(crate) async fn type_text<R: Runtime>(
+    window: &WebviewWindow<R>,
+    text: &str,
+    paste: bool,
+    delay: Duration,
+    cancel: &CancellationToken,
+    timeout: Duration,
+) -> crate::Result<(u32, Vec<TextInputFailure>)> {
+    // Without a delay the whole text goes in one script
+    let chunks: Vec<&str> = if paste || delay.is_zero() {
+        vec![text]
+    } else {
+        text.graphemes(true).collect()
+    };
+    let mut typed = 0;
+    let mut failures = Vec::new();
+    for (index, chunk) in chunks.into_iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled(format!(
+                "text input stopped after {} characters",
+                typed
+            )));
+        }
+        let script = format!(
+            "const options = {};\n{}",
+            json!({ "text": chunk, "paste": paste }),
+            DOM_TYPE_SCRIPT
+        );
+        match bridge::eval(window, &script, timeout).await {
+            Ok(result) => {
+                typed += result.get("typed").and_then(Value::as_u64).unwrap_or(0) as u32;
+            }
+            Err(e) => failures.push(TextInputFailure {
+                index: index as u32,
+                text: chunk.to_string(),
+                error: format!("Failed to type in the page: {}", e),
+            }),
+        }
+        if !delay.is_zero() {
+            tokio::time::sleep(delay).await;
+        }
+    }
+    // Nothing typed at all is a broken page, not a few odd characters
+    if typed == 0
+        && let Some(failure) = failures.first()
+    {
+        return Err(Error::Anyhow(failure.error.clone()));
+    }
+    Ok((typed, failures))
+}
+
+/// The W3C `key` and `code` values of `key`, the code guessed from a US layout for keys
+/// named by what they do.
+fn dom_key(key: Key) -> (String, &'static str) {
+    let name = match key {
+        Key::Code(code) => return (code.us_key().to_string(), code.code()),
+        Key::Function(number) => format!("F{}", number),
+        Key::Char(c) => c.to_string(),
+        Key::Space => " ".to_string(),
+        Key::Shift => "Shift".to_string(),
+        Key::Control => "Control".to_string(),
+        Key::Alt => "Alt".to_string(),
+        Key::Meta => "Meta".to_string(),
+        Key::Enter => "Enter".to_string(),
+        Key::Tab => "Tab".to_string(),
+        Key::Escape => "Escape".to_string(),
+        Key::Backspace => "Backspace".to_string(),
+        Key::Delete => "Delete".to_string(),
+        Key::ArrowUp => "ArrowUp".to_string(),
+        Key::ArrowDown => "ArrowDown".to_string(),
+        Key::ArrowLeft => "ArrowLeft".to_string(),
+        Key::ArrowRight => "ArrowRight".to_string(),
+        Key::Home => "Home".to_string(),
+        Key::End => "End".to_string(),
+        Key::PageUp => "PageUp".to_string(),
+        Key::PageDown => "PageDown".to_string(),
+    };
+    let code = KeyCode::from_us_key(&name).map_or("", |code| code.code());
+    (name, code)
+}
+
+/// Press `keys` together on the focused element of `window` with synthetic events.
+pub(crate) async fn press_keys<R: Runtime>(
+    window: &WebviewWindow<R>,
+    keys: &[Key],
+    timeout: Duration,
+) -> crate::Result<()> {
+    let keys: Vec<Value> = keys
+        .iter()
+        .map(|key| {
+            let (key, code) = dom_key(*key);
+            json!({ "key": key, "code": code })
+        })
+        .collect();
+    let script = format!(
+        "const options = {};\n{}",
+        json!({ "keys": keys }),
+        DOM_KEYS_SCRIPT
+    );
+    bridge::eval(window, &script, timeout)
+        .await
+        .map_err(|e| Error::Anyhow(format!("Failed to press keys in the page: {}", e)))?;
+    Ok(())
+}
+
+/// Move a synthetic pointer in `window` and click with it, as requested for the OS mouse.
+pub(crate) async fn move_mouse<R: Runtime>(
+    window: &WebviewWindow<R>,
+    params: &MouseMovementRequest,
+    timeout: Duration,
+) -> crate::Result<MouseMovementResponse> {
+    let geometry = WindowGeometry::of(window)?;
+    let relative = params.relative.unwrap_or(false);
+    let (x, y) = if relative {
+        (f64::from(params.x), f64::from(params.y))
+    } else {
+        geometry.convert(
+            CoordinateSpace::Window,
+            CoordinateSpace::Viewport,
+            (f64::from(params.x), f64::from(params.y)),
+        )
+    };
+
+    let start_time = Instant::now();
+    let options = json!({
+        "x": x,
+        "y": y,
+        "relative": relative,
+        "click": params.click.unwrap_or(false),
+        "button": params.button.as_deref().unwrap_or("left"),
+    });
+    let script = format!("const options = {};\n{}", options, DOM_MOUSE_SCRIPT);
+    let result = bridge::eval(window, &script, timeout)
+        .await
+        .map_err(|e| Error::Anyhow(format!("Failed to move the pointer in the page: {}", e)))?;
+    let x = result.get("x").and_then(Value::as_f64).unwrap_or(x);
+    let y = result.get("y").and_then(Value::as_f64).unwrap_or(y);
+    info!(
+        "[MOUSE_MOVEMENT] Synthetic pointer at viewport point ({}, {}) over {}",
+        x,
+        y,
+        result
+            .get("tag")
+            .and_then(Value::as_str)
+            .unwrap_or("nothing")
+    );
+
+    Ok(MouseMovementResponse {
+        success: true,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+        // Where the OS mouse would be, as in OS mode
+        position: Some(geometry.os_input_point(CoordinateSpace::Viewport, (x, y))),
+    })
+}
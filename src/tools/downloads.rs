@@ -0,0 +1,62 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::downloads::{Download, DownloadStatus};
+use crate::error::Error;
+use crate::models::{GetDownloadsRequest, GetDownloadsResponse};
+use crate::socket_server::SocketResponse;
+
+pub async fn handle_get_downloads<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: GetDownloadsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_downloads: {}", e)))?;
+    let log = app.tauri_mcp().downloads();
+    let label = request.window_label.as_deref();
+
+    let completed = if request.wait_for_completion {
+        // The download may have started before the request, e.g. right after a click
+        let since = request.since.unwrap_or_else(|| {
+            log.downloads(0, label)
+                .iter()
+                .find(|download| download.status == DownloadStatus::InProgress)
+                .map(|download| download.id - 1)
+                .unwrap_or_else(|| log.latest_id())
+        });
+        let accepts =
+            |download: &Download| label.is_none_or(|label| label == download.window_label);
+        match log.wait_for_completion(since, accepts, timeout).await {
+            Some(download) => Some(download),
+            None => {
+                return Ok(SocketResponse {
+                    success: false,
+                    data: None,
+                    error: Some(format!(
+                        "No download completed within {} ms",
+                        timeout.as_millis()
+                    )),
+                });
+            }
+        }
+    } else {
+        None
+    };
+
+    let response = GetDownloadsResponse {
+        downloads: log.downloads(request.since.unwrap_or(0), label),
+        completed,
+        cursor: log.latest_id(),
+        sandbox_dir: log.sandbox_dir(),
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,89 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::ElementInfoRequest;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Computed style properties returned when the request does not list any.
+const DEFAULT_STYLES: &[&str] = &[
+    "display",
+    "visibility",
+    "opacity",
+    "color",
+    "background-color",
+    "cursor",
+    "pointer-events",
+    "font-size",
+    "font-weight",
+];
+
+/// Describes the element found by `findElement`.
+const ELEMENT_INFO_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+const computed = window.getComputedStyle(element);
+const names = options.styles.includes('*') ? Array.from(computed) : options.styles;
+const styles = {};
+for (const name of names) styles[name] = computed.getPropertyValue(name);
+const attributes = {};
+for (const attribute of element.attributes) attributes[attribute.name] = attribute.value;
+const rect = element.getBoundingClientRect();
+const visible = typeof element.checkVisibility === 'function'
+    ? element.checkVisibility({ checkOpacity: true, checkVisibilityCSS: true })
+    : computed.display !== 'none' && computed.visibility !== 'hidden' && computed.opacity !== '0';
+return {
+    tag: element.tagName.toLowerCase(),
+    id: element.id,
+    classes: Array.from(element.classList),
+    attributes,
+    dataset: Object.assign({}, element.dataset),
+    styles,
+    rect: {
+        x: rect.x, y: rect.y, width: rect.width, height: rect.height,
+        top: rect.top, right: rect.right, bottom: rect.bottom, left: rect.left,
+    },
+    scroll: {
+        top: element.scrollTop, left: element.scrollLeft,
+        width: element.scrollWidth, height: element.scrollHeight,
+    },
+    state: {
+        visible,
+        disabled: element.matches(':disabled'),
+        focused: document.activeElement === element,
+        checked: 'checked' in element ? element.checked : null,
+        value: 'value' in element ? String(element.value) : null,
+    },
+};
+"#;
+
+pub async fn handle_get_element_info<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let mut request: ElementInfoRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_element_info: {}", e)))?;
+    if request.styles.is_empty() {
+        request.styles = DEFAULT_STYLES.iter().map(|s| s.to_string()).collect();
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, ELEMENT_INFO_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid get_element_info options: {}", e)))?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(info) => Ok(SocketResponse {
+            success: true,
+            data: Some(info),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to get element info: {}", e)),
+        }),
+    }
+}
@@ -0,0 +1,114 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::SetElementValueRequest;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Defines `setElementValue(element, value, label)`, which sets the value of a form control
+/// the way a user would, so framework change handlers run.
+///
+/// Values go through the native prototype setters, since React tracks the last value set on
+/// the instance and would otherwise swallow the `input` event. Checkboxes and radios are
+/// clicked, which is the event React listens to for them.
+pub(crate) const SET_VALUE_JS: &str = r#"
+const setElementValue = (element, value, label) => {
+    if (element.disabled) throw new Error('Element is disabled');
+    const tag = element.tagName.toLowerCase();
+    const type = tag === 'input' ? element.type : null;
+    const setNative = (prop, value) => {
+        const descriptor = Object.getOwnPropertyDescriptor(Object.getPrototypeOf(element), prop);
+        if (descriptor && descriptor.set) descriptor.set.call(element, value); else element[prop] = value;
+    };
+    const fire = () => {
+        element.dispatchEvent(new Event('input', { bubbles: true }));
+        element.dispatchEvent(new Event('change', { bubbles: true }));
+    };
+    const wanted = (value) => Array.isArray(value) ? value.map(String) : [String(value)];
+
+    if (tag === 'select') {
+        const byLabel = label !== undefined && label !== null;
+        const targets = byLabel ? wanted(label) : wanted(value);
+        const matches = (option) => byLabel
+            ? targets.includes(option.label.trim()) || targets.includes(option.text.trim())
+            : targets.includes(option.value);
+        const selected = Array.from(element.options).filter(matches);
+        if (selected.length === 0) {
+            const available = Array.from(element.options).map((o) => `${o.value} (${o.text.trim()})`).join(', ');
+            throw new Error(`No option matches ${targets.join(', ')}; available: ${available}`);
+        }
+        if (element.multiple) {
+            for (const option of element.options) option.selected = selected.includes(option);
+        } else {
+            setNative('value', selected[0].value);
+        }
+        fire();
+    } else if (type === 'checkbox' || type === 'radio') {
+        const checked = value === undefined || value === null
+            ? (type === 'radio' ? true : !element.checked)
+            : (value === true || value === 'true' || value === 'on' || value === 1);
+        if (element.checked !== checked) {
+            if (type === 'radio' && !checked) {
+                setNative('checked', false);
+                fire();
+            } else {
+                element.click();
+            }
+        }
+    } else if (tag === 'input' || tag === 'textarea') {
+        if (value === undefined || value === null) throw new Error('A value is required');
+        element.focus();
+        setNative('value', String(value));
+        fire();
+    } else {
+        throw new Error(`Element <${tag}> is not a form control`);
+    }
+
+    return {
+        tag,
+        type,
+        value: 'value' in element ? element.value : null,
+        checked: type === 'checkbox' || type === 'radio' ? element.checked : null,
+        selectedOptions: tag === 'select'
+            ? Array.from(element.selectedOptions).map((o) => ({ value: o.value, label: o.text.trim() }))
+            : null,
+    };
+};
+"#;
+
+const SET_VALUE_SCRIPT: &str = r#"
+return setElementValue(
+    findElement(options.selector_type, options.selector_value),
+    options.value,
+    options.label,
+);
+"#;
+
+pub async fn handle_set_element_value<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: SetElementValueRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_element_value: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, &format!("{}{}", SET_VALUE_JS, SET_VALUE_SCRIPT))
+        .map_err(|e| Error::Anyhow(format!("Invalid set_element_value options: {}", e)))?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(state) => Ok(SocketResponse {
+            success: true,
+            data: Some(state),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to set element value: {}", e)),
+        }),
+    }
+}
@@ -0,0 +1,475 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::devices::{DEVICE_PROFILES, find_device};
+use crate::emulation::{
+    ColorScheme, DeviceSettings, EmulationSettings, GeolocationSettings, LocaleSettings, MockRoute,
+    NetworkConditions,
+};
+use crate::error::Error;
+use crate::models::{
+    AddMockRouteRequest, AdvanceClockRequest, EmulateDeviceRequest, RemoveMockRouteRequest,
+    SetEmulationRequest, SetGeolocationRequest, SetLocaleRequest, SetNetworkConditionsRequest,
+    SetThemeRequest, ThemeMode, ThemeResponse,
+};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+
+/// Checks the locale settings with the `Intl` of the webview, which throws on an unknown
+/// locale or timezone, and returns them in canonical form.
+const VALIDATE_LOCALE_SCRIPT: &str = r#"
+const canonical = (tag) => Intl.getCanonicalLocales(tag)[0];
+return {
+    locale: options.locale ? canonical(options.locale) : null,
+    languages: options.languages.map(canonical),
+    timezone: options.timezone
+        ? new Intl.DateTimeFormat('en-US', { timeZone: options.timezone }).resolvedOptions().timeZone
+        : null,
+};
+"#;
+
+pub fn handle_set_emulation<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetEmulationRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_emulation: {}", e)))?;
+
+    Ok(settings_response(apply(app, &request)))
+}
+
+/// Reset first, so a request can replace an override in one go.
+fn apply<R: Runtime>(
+    app: &AppHandle<R>,
+    request: &SetEmulationRequest,
+) -> crate::Result<EmulationSettings> {
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    if !request.reset.is_empty() {
+        emulation.reset(app, label, &request.reset)?;
+    }
+    if let Some(clock) = &request.clock {
+        emulation.set_clock(app, label, clock.time_ms, clock.frozen)?;
+    }
+    if let Some(seed) = request.random_seed {
+        emulation.set_random_seed(app, label, seed)?;
+    }
+    Ok(emulation.settings(label))
+}
+
+pub fn handle_advance_clock<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: AdvanceClockRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for advance_clock: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    Ok(settings_response(
+        app.tauri_mcp()
+            .emulation()
+            .advance_clock(app, window.label(), request.ms),
+    ))
+}
+
+pub fn handle_set_geolocation<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetGeolocationRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_geolocation: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    if request.reset {
+        return Ok(settings_response(emulation.reset(
+            app,
+            label,
+            &["geolocation".to_string()],
+        )));
+    }
+
+    let (latitude, longitude) = match (request.latitude, request.longitude) {
+        (Some(latitude), Some(longitude)) => (latitude, longitude),
+        (None, None) if request.denied => (0.0, 0.0),
+        _ => {
+            return Err(Error::Anyhow(
+                "set_geolocation needs latitude and longitude".to_string(),
+            ));
+        }
+    };
+    if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+        return Err(Error::Anyhow(format!(
+            "Invalid coordinates: {}, {}",
+            latitude, longitude
+        )));
+    }
+
+    Ok(settings_response(emulation.set_geolocation(
+        app,
+        label,
+        GeolocationSettings {
+            latitude,
+            longitude,
+            accuracy: request.accuracy,
+            altitude: request.altitude,
+            heading: request.heading,
+            speed: request.speed,
+            denied: request.denied,
+        },
+    )))
+}
+
+pub async fn handle_set_locale<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let mut request: SetLocaleRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_locale: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    if request.reset {
+        return Ok(settings_response(emulation.reset(
+            app,
+            label,
+            &["locale".to_string()],
+        )));
+    }
+    if request.locale.is_none() && request.languages.is_empty() && request.timezone.is_none() {
+        return Err(Error::Anyhow(
+            "set_locale needs a locale, languages or a timezone".to_string(),
+        ));
+    }
+    if request.languages.is_empty()
+        && let Some(locale) = &request.locale
+    {
+        request.languages.push(locale.clone());
+    }
+
+    let script = format!(
+        "const options = {};\n{}",
+        serde_json::to_string(&request)
+            .map_err(|e| Error::Anyhow(format!("Invalid locale options: {}", e)))?,
+        VALIDATE_LOCALE_SCRIPT
+    );
+    let locale = match bridge::eval(&window, &script, timeout).await {
+        Ok(locale) => serde_json::from_value::<LocaleSettings>(locale)
+            .map_err(|e| Error::Anyhow(format!("Invalid locale settings: {}", e)))?,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to validate locale: {}", e)),
+            });
+        }
+    };
+
+    Ok(settings_response(emulation.set_locale(app, label, locale)))
+}
+
+pub async fn handle_set_theme<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: SetThemeRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_theme: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+
+    if let Some(theme) = request.theme {
+        if request.native {
+            let native = match theme {
+                ThemeMode::Light => Some(tauri::Theme::Light),
+                ThemeMode::Dark => Some(tauri::Theme::Dark),
+                ThemeMode::System => None,
+            };
+            #[cfg(desktop)]
+            window
+                .set_theme(native)
+                .map_err(|e| Error::Anyhow(format!("Failed to set window theme: {}", e)))?;
+            #[cfg(mobile)]
+            {
+                let _ = native;
+                return Err(crate::mobile::unsupported("Setting the native theme"));
+            }
+        }
+        if request.media {
+            match theme {
+                ThemeMode::Light => emulation.set_color_scheme(app, label, ColorScheme::Light)?,
+                ThemeMode::Dark => emulation.set_color_scheme(app, label, ColorScheme::Dark)?,
+                ThemeMode::System => emulation.reset(app, label, &["theme".to_string()])?,
+            };
+        }
+    }
+
+    let window_theme = match window.theme() {
+        Ok(tauri::Theme::Dark) => ColorScheme::Dark,
+        Ok(_) => ColorScheme::Light,
+        Err(e) => {
+            return Err(Error::Anyhow(format!("Failed to read window theme: {}", e)));
+        }
+    };
+    let color_scheme = match bridge::eval(
+        &window,
+        "return window.matchMedia('(prefers-color-scheme: dark)').matches;",
+        timeout,
+    )
+    .await
+    {
+        Ok(Value::Bool(true)) => ColorScheme::Dark,
+        Ok(_) => ColorScheme::Light,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to read color scheme: {}", e)),
+            });
+        }
+    };
+
+    let response = ThemeResponse {
+        window_theme,
+        color_scheme,
+        emulated_color_scheme: emulation.settings(label).color_scheme,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+pub fn handle_emulate_device<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: EmulateDeviceRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for emulate_device: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    if request.reset {
+        return Ok(settings_response(emulation.reset(
+            app,
+            label,
+            &["device".to_string()],
+        )));
+    }
+
+    let profile = match &request.device {
+        Some(name) => Some(find_device(name).ok_or_else(|| {
+            let known: Vec<&str> = DEVICE_PROFILES.iter().map(|p| p.name).collect();
+            Error::Anyhow(format!(
+                "Unknown device: {} (known devices: {})",
+                name,
+                known.join(", ")
+            ))
+        })?),
+        None => None,
+    };
+    let (Some(width), Some(height)) = (
+        request.width.or(profile.map(|p| p.width)),
+        request.height.or(profile.map(|p| p.height)),
+    ) else {
+        return Err(Error::Anyhow(
+            "emulate_device needs a device, or a width and height".to_string(),
+        ));
+    };
+    if width == 0 || height == 0 {
+        return Err(Error::Anyhow(format!(
+            "Invalid viewport size: {}x{}",
+            width, height
+        )));
+    }
+    let (width, height) = if request.landscape {
+        (height, width)
+    } else {
+        (width, height)
+    };
+
+    let device = DeviceSettings {
+        name: profile.map(|p| p.name.to_string()),
+        width,
+        height,
+        device_scale_factor: request
+            .device_scale_factor
+            .or(profile.map(|p| p.device_scale_factor))
+            .unwrap_or(1.0),
+        touch: request.touch.or(profile.map(|p| p.touch)).unwrap_or(false),
+        user_agent: request
+            .user_agent
+            .clone()
+            .or(profile.and_then(|p| p.user_agent.map(str::to_string))),
+    };
+    Ok(settings_response(emulation.set_device(app, label, device)))
+}
+
+pub fn handle_set_network_conditions<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetNetworkConditionsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_network_conditions: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    if request.reset {
+        return Ok(settings_response(emulation.reset(
+            app,
+            label,
+            &["network".to_string()],
+        )));
+    }
+
+    let preset = match request.preset.as_deref() {
+        None => NetworkConditions::default(),
+        Some(name) => network_preset(name)
+            .ok_or_else(|| Error::Anyhow(format!("Unknown network preset: {}", name)))?,
+    };
+    let conditions = NetworkConditions {
+        offline: request.offline.unwrap_or(preset.offline),
+        latency_ms: request.latency_ms.unwrap_or(preset.latency_ms),
+        download_kbps: request.download_kbps.or(preset.download_kbps),
+        upload_kbps: request.upload_kbps.or(preset.upload_kbps),
+    };
+    if conditions.download_kbps == Some(0) || conditions.upload_kbps == Some(0) {
+        return Err(Error::Anyhow(
+            "Bandwidth limits must be above 0; use offline to cut the network".to_string(),
+        ));
+    }
+    Ok(settings_response(
+        emulation.set_network(app, label, conditions),
+    ))
+}
+
+/// Conditions of the browser devtools presets.
+fn network_preset(name: &str) -> Option<NetworkConditions> {
+    let conditions = |latency_ms, download_kbps, upload_kbps| NetworkConditions {
+        offline: false,
+        latency_ms,
+        download_kbps: Some(download_kbps),
+        upload_kbps: Some(upload_kbps),
+    };
+    match name {
+        "offline" => Some(NetworkConditions {
+            offline: true,
+            ..Default::default()
+        }),
+        "slow-3g" => Some(conditions(2000, 400, 400)),
+        "fast-3g" => Some(conditions(563, 1475, 675)),
+        _ => None,
+    }
+}
+
+pub async fn handle_add_mock_route<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: AddMockRouteRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for add_mock_route: {}", e)))?;
+    if request.url.is_empty() {
+        return Err(Error::Anyhow(
+            "A mock route needs a url pattern".to_string(),
+        ));
+    }
+    if request.rewrite_url.is_some() && request.body.is_some() {
+        return Err(Error::Anyhow(
+            "A mock route has either a body or a rewrite_url, not both".to_string(),
+        ));
+    }
+    if !(200..=599).contains(&request.status) {
+        return Err(Error::Anyhow(format!(
+            "Invalid mock status: {} (expected 200 to 599)",
+            request.status
+        )));
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    // JavaScript and Rust regular expressions differ, so the webview checks the pattern
+    if request.regex {
+        let script = format!(
+            "new RegExp({}); return true;",
+            serde_json::to_string(&request.url)
+                .map_err(|e| Error::Anyhow(format!("Invalid url pattern: {}", e)))?
+        );
+        if let Err(e) = bridge::eval(&window, &script, timeout).await {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Invalid url pattern: {}", e)),
+            });
+        }
+    }
+
+    let emulation = app.tauri_mcp().emulation();
+    let route = MockRoute {
+        id: request.id.unwrap_or_else(|| emulation.next_route_id()),
+        url: request.url,
+        regex: request.regex,
+        method: request.method.map(|method| method.to_uppercase()),
+        status: request.status,
+        headers: request.headers,
+        body: request.body,
+        rewrite_url: request.rewrite_url,
+        delay_ms: request.delay_ms,
+    };
+    Ok(settings_response(
+        emulation.add_mock_route(app, label, route),
+    ))
+}
+
+pub fn handle_remove_mock_route<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: RemoveMockRouteRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for remove_mock_route: {}", e)))?;
+
+    let emulation = app.tauri_mcp().emulation();
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label();
+    let result = match (&request.id, request.all) {
+        (_, true) => emulation.reset(app, label, &["mocks".to_string()]),
+        (Some(id), false) => emulation.remove_mock_route(app, label, id),
+        (None, false) => {
+            return Err(Error::Anyhow(
+                "remove_mock_route needs an id, or all".to_string(),
+            ));
+        }
+    };
+    Ok(settings_response(result))
+}
+
+fn settings_response(result: crate::Result<EmulationSettings>) -> SocketResponse {
+    match result.and_then(|settings| {
+        serde_json::to_value(settings)
+            .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))
+    }) {
+        Ok(data) => SocketResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        },
+        Err(e) => SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        },
+    }
+}
@@ -1,12 +1,13 @@
 use serde::{Serialize, Serializer};
 use serde_json::Value;
 use std::fmt;
-use std::sync::mpsc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+use tauri::{AppHandle, Runtime};
 
 use crate::error::Error;
+use crate::models::{ExecuteJsRequest, ExecuteJsResponse};
 use crate::socket_server::SocketResponse;
+use crate::tools::bridge::{self, BridgeError};
 
 // Define a custom error type for JavaScript execution operations
 #[derive(Debug)]
@@ -38,50 +39,36 @@ impl Serialize for ExecuteJsError {
     }
 }
 
-// Support conversion from timeout error
-impl From<mpsc::RecvTimeoutError> for ExecuteJsError {
-    fn from(err: mpsc::RecvTimeoutError) -> Self {
-        ExecuteJsError::Timeout(format!(
-            "Timeout waiting for JavaScript execution response: {}",
-            err
-        ))
+// Support conversion from bridge errors
+impl From<BridgeError> for ExecuteJsError {
+    fn from(err: BridgeError) -> Self {
+        match err {
+            BridgeError::Emit(e) => {
+                ExecuteJsError::WebviewOperation(format!("Failed to emit execute-js event: {}", e))
+            }
+            err => ExecuteJsError::Timeout(format!(
+                "Timeout waiting for JavaScript execution response: {}",
+                err
+            )),
+        }
     }
 }
 
-#[derive(Debug, Clone, serde::Deserialize)]
-pub struct ExecuteJsRequest {
-    window_label: Option<String>,
-    code: String,
-    timeout_ms: Option<u64>,
-}
-
-#[derive(Debug, serde::Serialize, serde::Deserialize)]
-pub struct ExecuteJsResponse {
-    result: String,
-    #[serde(rename = "type")]
-    result_type: String,
-}
-
 pub async fn handle_execute_js<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    timeout: Duration,
 ) -> Result<SocketResponse, Error> {
-    let request: ExecuteJsRequest = serde_json::from_value(payload)
+    let mut request: ExecuteJsRequest = serde_json::from_value(payload)
         .map_err(|e| Error::Anyhow(format!("Invalid payload for executeJs: {}", e)))?;
 
-    // Get the window label or use "main" as default
-    let window_label = request
-        .window_label
-        .clone()
-        .unwrap_or_else(|| "main".to_string());
-
-    // Verify the window exists
-    let _window = app
-        .get_webview_window(&window_label)
-        .ok_or_else(|| Error::Anyhow(format!("Window not found: {}", window_label)))?;
+    // Resolve the window, the focused one when no label is given
+    let window =
+        crate::windows::resolve_window(app, request.window_label.as_deref().unwrap_or_default())?;
+    request.window_label = Some(window.label().to_string());
 
     // Execute JavaScript and get the result
-    let result = execute_js_in_window(app.clone(), request).await;
+    let result = execute_js_in_window(app.clone(), request, timeout).await;
 
     // Handle the result
     match result {
@@ -108,33 +95,22 @@ pub async fn handle_execute_js<R: Runtime>(
 async fn execute_js_in_window<R: Runtime>(
     app: AppHandle<R>,
     params: ExecuteJsRequest,
+    timeout: Duration,
 ) -> Result<ExecuteJsResponse, ExecuteJsError> {
     // Get window label
-    let window_label = params
-        .window_label
-        .clone()
-        .unwrap_or_else(|| "main".to_string());
-
-    // Get timeout or use default (5 seconds)
-    let timeout = Duration::from_millis(params.timeout_ms.unwrap_or(5000));
-
-    // Emit event to execute the JavaScript in the specified window
-    app.emit_to(&window_label, "execute-js", &params.code)
-        .map_err(|e| {
-            ExecuteJsError::WebviewOperation(format!("Failed to emit execute-js event: {}", e))
-        })?;
-
-    // Set up a channel to receive the response
-    let (tx, rx) = mpsc::channel();
-
-    // Listen for response
-    app.once("execute-js-response", move |event| {
-        let payload = event.payload().to_string();
-        let _ = tx.send(payload);
-    });
-
-    // Wait for the response with timeout
-    match rx.recv_timeout(timeout) {
+    let window_label = params.window_label.clone().unwrap_or_default();
+
+    // Execute the JavaScript in the specified window and wait for the response
+    match bridge::request(
+        &app,
+        &window_label,
+        "execute-js",
+        &params.code,
+        "execute-js-response",
+        timeout,
+    )
+    .await
+    {
         Ok(result_string) => {
             // Parse the response JSON
             let response: Value = serde_json::from_str(&result_string).map_err(|e| {
@@ -0,0 +1,157 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Serialize;
+use serde_json::Value;
+use std::path::Path;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::SetFileInputRequest;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Largest total size of the files attached in one request. They are inlined in the script
+/// as base64, so this keeps the evaluated script reasonable.
+const MAX_TOTAL_BYTES: u64 = 25 * 1024 * 1024;
+
+/// Attaches the files to an `<input type="file">` through a DataTransfer, as a drop would.
+const SET_FILES_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+if (element.tagName.toLowerCase() !== 'input' || element.type !== 'file') {
+    throw new Error(`Element is not an <input type="file"> but <${element.tagName.toLowerCase()}>`);
+}
+if (element.disabled) throw new Error('Element is disabled');
+if (!element.multiple && options.files.length + (options.append ? element.files.length : 0) > 1) {
+    throw new Error('Input does not accept multiple files');
+}
+const transfer = new DataTransfer();
+if (options.append) for (const file of element.files) transfer.items.add(file);
+for (const file of options.files) {
+    const binary = atob(file.content);
+    const bytes = new Uint8Array(binary.length);
+    for (let i = 0; i < binary.length; i++) bytes[i] = binary.charCodeAt(i);
+    transfer.items.add(new File([bytes], file.name, { type: file.mimeType, lastModified: Date.now() }));
+}
+element.files = transfer.files;
+element.dispatchEvent(new Event('input', { bubbles: true }));
+element.dispatchEvent(new Event('change', { bubbles: true }));
+return {
+    files: Array.from(element.files).map((f) => ({ name: f.name, size: f.size, type: f.type })),
+};
+"#;
+
+/// A file as passed to the script.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct InlineFile {
+    name: String,
+    mime_type: String,
+    content: String,
+}
+
+pub async fn handle_set_file_input<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: SetFileInputRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_file_input: {}", e)))?;
+    if request.paths.is_empty() {
+        return Err(Error::Anyhow(
+            "set_file_input needs at least one path".to_string(),
+        ));
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let files = read_files(&request.paths)?;
+    let options = serde_json::json!({
+        "selector_type": request.selector_type,
+        "selector_value": request.selector_value,
+        "append": request.append,
+        "files": files,
+    });
+    let script = element_script(&options, SET_FILES_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid set_file_input options: {}", e)))?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(state) => Ok(SocketResponse {
+            success: true,
+            data: Some(state),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to set file input: {}", e)),
+        }),
+    }
+}
+
+fn read_files(paths: &[String]) -> Result<Vec<InlineFile>, Error> {
+    let mut total = 0;
+    paths
+        .iter()
+        .map(|path| {
+            let path = Path::new(path);
+            let metadata = std::fs::metadata(path)
+                .map_err(|e| Error::Io(format!("Cannot read {}: {}", path.display(), e)))?;
+            if !metadata.is_file() {
+                return Err(Error::Anyhow(format!("Not a file: {}", path.display())));
+            }
+            total += metadata.len();
+            if total > MAX_TOTAL_BYTES {
+                return Err(Error::Anyhow(format!(
+                    "Files exceed the {} MiB limit of set_file_input",
+                    MAX_TOTAL_BYTES / (1024 * 1024)
+                )));
+            }
+            let content = std::fs::read(path)
+                .map_err(|e| Error::Io(format!("Cannot read {}: {}", path.display(), e)))?;
+            Ok(InlineFile {
+                name: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+                mime_type: mime_type(path).to_string(),
+                content: STANDARD.encode(content),
+            })
+        })
+        .collect()
+}
+
+/// MIME type of common upload formats, by extension.
+fn mime_type(path: &Path) -> &'static str {
+    let extension = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "bmp" => "image/bmp",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "zip" => "application/zip",
+        "gz" => "application/gzip",
+        "xml" => "application/xml",
+        "txt" | "log" => "text/plain",
+        "csv" => "text/csv",
+        "html" | "htm" => "text/html",
+        "md" => "text/markdown",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        "doc" => "application/msword",
+        "docx" => "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        "xls" => "application/vnd.ms-excel",
+        "xlsx" => "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        _ => "application/octet-stream",
+    }
+}
@@ -0,0 +1,73 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::FillFormRequest;
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::element_value::SET_VALUE_JS;
+use crate::tools::selector::element_script;
+
+/// Fills the fields in order with `setElementValue`, recording a result per field.
+const FILL_FORM_SCRIPT: &str = r#"
+const results = [];
+let stopped = false;
+for (const field of options.fields) {
+    const result = { selector_type: field.selector_type, selector_value: field.selector_value };
+    if (stopped) {
+        result.success = false;
+        result.skipped = true;
+    } else {
+        try {
+            result.state = setElementValue(
+                findElement(field.selector_type, field.selector_value),
+                field.value,
+                field.label,
+            );
+            result.success = true;
+        } catch (e) {
+            result.success = false;
+            result.error = e instanceof Error ? e.message : String(e);
+            stopped = options.stop_on_error;
+        }
+    }
+    results.push(result);
+}
+if (options.blur && document.activeElement && document.activeElement !== document.body) {
+    document.activeElement.blur();
+}
+const filled = results.filter((result) => result.success).length;
+return { filled, failed: results.length - filled, results };
+"#;
+
+pub async fn handle_fill_form<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: FillFormRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for fill_form: {}", e)))?;
+    if request.fields.is_empty() {
+        return Err(Error::Anyhow(
+            "fill_form needs at least one field".to_string(),
+        ));
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, &format!("{}{}", SET_VALUE_JS, FILL_FORM_SCRIPT))
+        .map_err(|e| Error::Anyhow(format!("Invalid fill_form options: {}", e)))?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(report) => Ok(SocketResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to fill form: {}", e)),
+        }),
+    }
+}
@@ -0,0 +1,105 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{BlurElementRequest, FocusElementRequest};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Defines `describeFocus(element)`, a short description of the focused element, or null
+/// when nothing but the document body has focus.
+const DESCRIBE_FOCUS_JS: &str = r#"
+const describeFocus = (element) => {
+    if (!element || element === document.body || element === document.documentElement) return null;
+    const text = (element.innerText || element.value || '').trim();
+    return {
+        tag: element.tagName.toLowerCase(),
+        id: element.id,
+        classes: Array.from(element.classList),
+        name: element.getAttribute('name'),
+        text: text.length > 80 ? text.slice(0, 80) + '...' : text,
+    };
+};
+"#;
+
+/// Focuses the element found by `findElement`.
+const FOCUS_SCRIPT: &str = r#"
+const previous = describeFocus(document.activeElement);
+const element = findElement(options.selector_type, options.selector_value);
+element.focus({ preventScroll: options.prevent_scroll });
+const focused = document.activeElement === element;
+return {
+    focused,
+    reason: focused ? null : (element.matches(':disabled')
+        ? 'element is disabled'
+        : 'element is not focusable, give it a tabindex'),
+    previous,
+    active: describeFocus(document.activeElement),
+};
+"#;
+
+/// Blurs the element found by `findElement`, or the focused element without a selector.
+const BLUR_SCRIPT: &str = r#"
+const previous = describeFocus(document.activeElement);
+const element = options.selector_type && options.selector_value
+    ? findElement(options.selector_type, options.selector_value)
+    : document.activeElement;
+const hadFocus = !!element && element === document.activeElement && element !== document.body;
+if (element && typeof element.blur === 'function') element.blur();
+return {
+    blurred: hadFocus && document.activeElement !== element,
+    previous,
+    active: describeFocus(document.activeElement),
+};
+"#;
+
+pub async fn handle_focus_element<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: FocusElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for focus_element: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, &format!("{}{}", DESCRIBE_FOCUS_JS, FOCUS_SCRIPT))
+        .map_err(|e| Error::Anyhow(format!("Invalid focus_element options: {}", e)))?;
+    Ok(respond(
+        bridge::eval(&window, &script, timeout).await,
+        "focus element",
+    ))
+}
+
+pub async fn handle_blur_element<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: BlurElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for blur_element: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, &format!("{}{}", DESCRIBE_FOCUS_JS, BLUR_SCRIPT))
+        .map_err(|e| Error::Anyhow(format!("Invalid blur_element options: {}", e)))?;
+    Ok(respond(
+        bridge::eval(&window, &script, timeout).await,
+        "blur element",
+    ))
+}
+
+fn respond(result: Result<Value, bridge::BridgeError>, action: &str) -> SocketResponse {
+    match result {
+        Ok(data) => SocketResponse {
+            success: true,
+            data: Some(data),
+            error: None,
+        },
+        Err(e) => SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to {}: {}", action, e)),
+        },
+    }
+}
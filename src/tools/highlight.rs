@@ -0,0 +1,142 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{HighlightElementRequest, HighlightElementResponse};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Most elements outlined by one request.
+const MAX_HIGHLIGHTS: usize = 100;
+
+/// Outlines the elements matching the selector, every one of them with `options.all`, for
+/// `options.duration_ms`. The outlines live in a closed shadow root of the elements'
+/// document that lets clicks and `elementFromPoint` through, and follow their element as
+/// the page scrolls or changes.
+const HIGHLIGHT_SCRIPT: &str = r#"
+const matches = (() => {
+    const value = options.selector_value;
+    if (!options.all) return [findElement(options.selector_type, value)];
+    let found;
+    switch (options.selector_type) {
+        case 'css': found = value.includes('>>>') ? [findByCss(value)] : deepQueryAll(document, value); break;
+        case 'class': found = deepQueryAll(document, `.${CSS.escape(value)}`); break;
+        case 'tag': found = deepQueryAll(document, CSS.escape(value)); break;
+        case 'xpath': {
+            const snapshot = document.evaluate(value, document, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+            found = [];
+            for (let i = 0; i < snapshot.snapshotLength; i++) found.push(snapshot.snapshotItem(i));
+            break;
+        }
+        default: found = [findElement(options.selector_type, value)];
+    }
+    found = found.filter((node) => node && node.nodeType === Node.ELEMENT_NODE);
+    if (!found.length) throw new Error(`Element with ${options.selector_type}="${value}" not found`);
+    return found;
+})();
+const elements = matches.slice(0, options.max_highlights);
+
+const layer = document.createElement('div');
+layer.setAttribute('data-tauri-mcp-highlight', '');
+const root = layer.attachShadow({ mode: 'closed' });
+root.innerHTML = `
+    <style>
+        :host { all: initial; position: fixed; inset: 0; z-index: 2147483646; pointer-events: none; }
+        .box { position: fixed; box-sizing: border-box; border: 2px solid #2563eb; border-radius: 3px;
+            background: rgba(37, 99, 235, 0.15); transition: opacity 300ms ease-in; }
+        .box span { position: absolute; left: -2px; bottom: 100%; margin-bottom: 2px; padding: 1px 6px;
+            border-radius: 3px; background: #2563eb; color: #fff; font: 600 11px/1.5 system-ui, sans-serif;
+            white-space: nowrap; }
+        .box.below span { bottom: auto; top: 100%; margin: 2px 0 0; }
+    </style>`;
+const boxes = elements.map((element, i) => {
+    const box = document.createElement('div');
+    box.className = 'box';
+    if (options.label) {
+        const text = document.createElement('span');
+        text.textContent = elements.length > 1 ? `${options.label} ${i + 1}` : options.label;
+        box.appendChild(text);
+    }
+    root.appendChild(box);
+    return box;
+});
+const place = () => elements.forEach((element, i) => {
+    const rect = element.getBoundingClientRect();
+    Object.assign(boxes[i].style, {
+        left: `${rect.left - 3}px`,
+        top: `${rect.top - 3}px`,
+        width: `${rect.width + 6}px`,
+        height: `${rect.height + 6}px`,
+        display: element.isConnected ? '' : 'none',
+    });
+    // Labels of elements at the top of the viewport go below them
+    boxes[i].classList.toggle('below', rect.top < 20);
+});
+place();
+document.documentElement.appendChild(layer);
+
+const view = document.defaultView;
+const end = Date.now() + options.duration_ms;
+const follow = () => {
+    if (Date.now() >= end) {
+        boxes.forEach((box) => { box.style.opacity = '0'; });
+        view.setTimeout(() => layer.remove(), 300);
+        return;
+    }
+    place();
+    view.requestAnimationFrame(follow);
+};
+view.requestAnimationFrame(follow);
+// Animation frames stop in windows that are not painted
+view.setTimeout(() => layer.remove(), options.duration_ms + 1000);
+
+return {
+    count: matches.length,
+    elements: elements.map((element) => {
+        const rect = element.getBoundingClientRect();
+        return {
+            tag: element.tagName.toLowerCase(),
+            id: element.id,
+            text: squeeze(element.innerText ?? element.textContent).slice(0, 80),
+            rect: { x: frame.offsetX + rect.x, y: frame.offsetY + rect.y, width: rect.width, height: rect.height },
+        };
+    }),
+};
+"#;
+
+pub async fn handle_highlight_element<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: HighlightElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for highlight_element: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let mut options = serde_json::to_value(&request)
+        .map_err(|e| Error::Anyhow(format!("Invalid highlight_element options: {}", e)))?;
+    options["max_highlights"] = MAX_HIGHLIGHTS.into();
+    let script = element_script(&options, HIGHLIGHT_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid highlight_element options: {}", e)))?;
+    let result = match bridge::eval(&window, &script, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to highlight element: {}", e)),
+            });
+        }
+    };
+    let response: HighlightElementResponse = serde_json::from_value(result)
+        .map_err(|e| Error::Anyhow(format!("Unexpected highlight result: {}", e)))?;
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,184 @@
+use base64::Engine;
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime, WebviewWindow};
+
+use crate::error::Error;
+use crate::models::{ElementBox, ElementScreenshot, InspectElementRequest, InspectElementResponse};
+use crate::platform::snapshot::{self, CaptureOptions, ImageFormat};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Describes the element found by `findElement` and whether a user could interact with
+/// it. Rects are moved to the window's viewport, which the screenshot is cropped from, and
+/// the element is scrolled into view first when a screenshot is wanted and it is cut off.
+const INSPECT_ELEMENT_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+const view = element.ownerDocument.defaultView;
+const viewportRect = () => {
+    const rect = element.getBoundingClientRect();
+    return { x: frame.offsetX + rect.x, y: frame.offsetY + rect.y, width: rect.width, height: rect.height };
+};
+const overlapsViewport = (rect) => rect.x < window.innerWidth && rect.y < window.innerHeight
+    && rect.x + rect.width > 0 && rect.y + rect.height > 0;
+const insideViewport = (rect) => rect.x >= 0 && rect.y >= 0
+    && rect.x + rect.width <= window.innerWidth && rect.y + rect.height <= window.innerHeight;
+
+let rect = viewportRect();
+let scrolled = false;
+if (options.screenshot && rect.width > 0 && rect.height > 0 && !insideViewport(rect)) {
+    element.scrollIntoView({ block: 'center', inline: 'center' });
+    // Let the page lay out and paint at its new scroll position
+    await new Promise((resolve) => requestAnimationFrame(() => requestAnimationFrame(resolve)));
+    rect = viewportRect();
+    scrolled = true;
+}
+
+const style = view.getComputedStyle(element);
+const visible = typeof element.checkVisibility === 'function'
+    ? element.checkVisibility({ checkOpacity: true, checkVisibilityCSS: true })
+    : style.display !== 'none' && style.visibility !== 'hidden' && style.opacity !== '0';
+const disabled = element.matches(':disabled') || element.closest('[aria-disabled="true"]') !== null;
+const textField = element.tagName === 'TEXTAREA'
+    || (element.tagName === 'INPUT' && !/^(button|checkbox|color|file|hidden|image|radio|range|reset|submit)$/i.test(element.type));
+const editable = !disabled && (element.isContentEditable || (textField && !element.readOnly));
+const receivesPointerEvents = style.pointerEvents !== 'none';
+
+// The element a click at the center would reach, looked up in the element's own tree so
+// shadow DOM and frames answer with their elements rather than their hosts
+const local = element.getBoundingClientRect();
+const root = element.getRootNode();
+const hit = visible && overlapsViewport(rect) && local.width > 0 && local.height > 0 && root.elementFromPoint
+    ? root.elementFromPoint(local.x + local.width / 2, local.y + local.height / 2)
+    : null;
+const obscured = hit !== null && hit !== element && !element.contains(hit);
+
+const attributes = {};
+for (const attribute of element.attributes) attributes[attribute.name] = attribute.value;
+const text = squeeze(element.innerText ?? element.textContent);
+const hasValue = 'value' in element && element.tagName !== 'BUTTON' && element.tagName !== 'LI';
+return {
+    tag: element.tagName.toLowerCase(),
+    id: element.id,
+    classes: Array.from(element.classList),
+    role: roleOf(element),
+    name: accessibleName(element),
+    text: text.length > options.max_text_length ? `${text.slice(0, options.max_text_length)}…` : text,
+    attributes,
+    value: hasValue && element.type !== 'password' ? String(element.value) : null,
+    rect,
+    flags: {
+        visible,
+        inViewport: overlapsViewport(rect),
+        enabled: !disabled,
+        editable,
+        focusable: !disabled && (element.tabIndex >= 0 || element.isContentEditable),
+        focused: element.getRootNode().activeElement === element,
+        checked: element.type === 'checkbox' || element.type === 'radio' ? element.checked : null,
+        obscured,
+        receivesPointerEvents,
+        clickable: visible && !disabled && receivesPointerEvents && hit !== null && !obscured,
+    },
+    scrolled,
+    viewport: { x: 0, y: 0, width: window.innerWidth, height: window.innerHeight },
+};
+"#;
+
+pub async fn handle_inspect_element<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: InspectElementRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for inspect_element: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    match inspect(&window, &request, timeout).await {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to inspect element: {}", e)),
+        }),
+    }
+}
+
+async fn inspect<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &InspectElementRequest,
+    timeout: Duration,
+) -> crate::Result<InspectElementResponse> {
+    let script = element_script(request, INSPECT_ELEMENT_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid inspect_element options: {}", e)))?;
+    let mut info = bridge::eval(window, &script, timeout)
+        .await
+        .map_err(|e| Error::Anyhow(e.to_string()))?;
+    let viewport = info
+        .as_object_mut()
+        .and_then(|info| info.remove("viewport"))
+        .and_then(|viewport| serde_json::from_value::<ElementBox>(viewport).ok());
+    let mut response: InspectElementResponse = serde_json::from_value(info)
+        .map_err(|e| Error::Anyhow(format!("Unexpected inspection result: {}", e)))?;
+
+    if request.screenshot {
+        match viewport.and_then(|viewport| clip(response.rect, request.padding, viewport)) {
+            Some(clip) => match snapshot::capture(window, capture_options(clip), timeout).await {
+                Ok(image) => {
+                    response.screenshot = Some(ElementScreenshot {
+                        mime_type: ImageFormat::Png.mime_type().to_string(),
+                        data: base64::engine::general_purpose::STANDARD.encode(image.data),
+                        width: image.width,
+                        height: image.height,
+                        rect: clip,
+                    });
+                }
+                Err(e) => response.screenshot_error = Some(e.to_string()),
+            },
+            None => {
+                response.screenshot_error = Some(
+                    "The element has no size or is outside the visible part of the page"
+                        .to_string(),
+                );
+            }
+        }
+    }
+    Ok(response)
+}
+
+/// Full-resolution PNG of `clip`.
+fn capture_options(clip: ElementBox) -> CaptureOptions {
+    CaptureOptions {
+        clip: Some(clip),
+        max_width: None,
+        format: ImageFormat::Png,
+    }
+}
+
+/// The part of the viewport showing `rect` and `padding` around it, None when nothing of
+/// the element is visible.
+fn clip(rect: ElementBox, padding: f64, viewport: ElementBox) -> Option<ElementBox> {
+    if rect.width <= 0.0 || rect.height <= 0.0 {
+        return None;
+    }
+    let padding = padding.max(0.0);
+    let left = (rect.x - padding).max(0.0).floor();
+    let top = (rect.y - padding).max(0.0).floor();
+    let right = (rect.x + rect.width + padding).min(viewport.width).ceil();
+    let bottom = (rect.y + rect.height + padding).min(viewport.height).ceil();
+    (right > left && bottom > top).then_some(ElementBox {
+        x: left,
+        y: top,
+        width: right - left,
+        height: bottom - top,
+    })
+}
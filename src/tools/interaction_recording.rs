@@ -0,0 +1,97 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{
+    RunScriptRequest, StartInteractionRecordingRequest, StepErrorPolicy,
+    StopInteractionRecordingRequest, StopInteractionRecordingResponse,
+};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_start_interaction_recording<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: StartInteractionRecordingRequest =
+        serde_json::from_value(payload).map_err(|e| {
+            Error::Anyhow(format!(
+                "Invalid payload for start_interaction_recording: {}",
+                e
+            ))
+        })?;
+
+    match app
+        .tauri_mcp()
+        .interactions()
+        .start(app, request.window_label.clone())
+    {
+        Ok(()) => Ok(SocketResponse {
+            success: true,
+            data: Some(serde_json::json!({
+                "recording": true,
+                "windowLabel": request.window_label,
+            })),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_stop_interaction_recording<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: StopInteractionRecordingRequest =
+        serde_json::from_value(payload).map_err(|e| {
+            Error::Anyhow(format!(
+                "Invalid payload for stop_interaction_recording: {}",
+                e
+            ))
+        })?;
+
+    let recorded = match app.tauri_mcp().interactions().stop(app) {
+        Ok(recorded) => recorded,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            });
+        }
+    };
+    let script = RunScriptRequest {
+        steps: recorded.steps,
+        on_error: StepErrorPolicy::Stop,
+    };
+
+    let artifact = if request.save {
+        let content = serde_json::to_vec_pretty(&script)
+            .map_err(|e| Error::Anyhow(format!("Failed to serialize script: {}", e)))?;
+        let name = request.name.as_deref().unwrap_or("interaction-script.json");
+        Some(
+            app.tauri_mcp()
+                .artifact_store()
+                .put("script", name, &content)?,
+        )
+    } else {
+        None
+    };
+
+    let response = StopInteractionRecordingResponse {
+        script,
+        duration_ms: recorded.duration_ms,
+        artifact,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,34 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{GetJsErrorsRequest, JsErrorsResponse};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_get_js_errors<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: GetJsErrorsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_js_errors: {}", e)))?;
+
+    let log = app.tauri_mcp().js_errors();
+    let since = request.since.unwrap_or(0);
+    let response = JsErrorsResponse {
+        errors: log.errors(since, request.window_label.as_deref()),
+        cursor: log.latest_id(),
+        missed_errors: log.missed_since(since),
+    };
+    if request.clear {
+        log.clear();
+    }
+
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,95 @@
+use log::info;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime, WebviewWindow};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{InputMode, KeyPressRequest, KeyPressResponse};
+use crate::platform::focus;
+use crate::platform::input::Key;
+use crate::platform::keycodes::KeyCode;
+use crate::shared::commands;
+use crate::socket_server::SocketResponse;
+use crate::tools::dom_input;
+
+/// Time given to a window to come up after it is focused, before the keys go to it.
+const FOCUS_SETTLE: Duration = Duration::from_millis(100);
+
+pub async fn handle_simulate_key_press<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: KeyPressRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for simulate_key_press: {}", e)))?;
+    let keys = parse_keys(&request)?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let start_time = Instant::now();
+    let input_mode = app
+        .tauri_mcp()
+        .input_mode(commands::SIMULATE_KEY_PRESS, request.input_mode);
+    let result = match input_mode {
+        InputMode::Dom => dom_input::press_keys(&window, &keys, timeout).await,
+        InputMode::Os => press_with_os_input(app, &window, &keys, request.focus_window).await,
+    };
+    if let Err(e) = result {
+        return Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        });
+    }
+    info!("[KEY_PRESS] Pressed {:?}", keys);
+
+    let response = KeyPressResponse {
+        keys,
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+/// The keys of the request, from its key names or its physical key codes.
+fn parse_keys(request: &KeyPressRequest) -> Result<Vec<Key>, Error> {
+    let keys = match (request.keys.is_empty(), request.codes.is_empty()) {
+        (false, true) => request
+            .keys
+            .iter()
+            .map(|name| Key::parse(name).ok_or_else(|| format!("Unknown key: {}", name)))
+            .collect(),
+        (true, false) => request
+            .codes
+            .iter()
+            .map(|code| {
+                KeyCode::parse(code)
+                    .map(Key::Code)
+                    .ok_or_else(|| format!("Unknown key code: {}", code))
+            })
+            .collect(),
+        (true, true) => Err("Give the keys to press in keys or codes".to_string()),
+        (false, false) => Err("Give the keys to press in keys or codes, not both".to_string()),
+    };
+    keys.map_err(|e| Error::Anyhow(format!("Invalid payload for simulate_key_press: {}", e)))
+}
+
+async fn press_with_os_input<R: Runtime>(
+    app: &AppHandle<R>,
+    window: &WebviewWindow<R>,
+    keys: &[Key],
+    focus_window: bool,
+) -> crate::Result<()> {
+    // OS input goes to whatever has the focus
+    if focus_window {
+        focus::focus(window)?;
+        tokio::time::sleep(FOCUS_SETTLE).await;
+    }
+    app.tauri_mcp().input()?.key_combo(keys)
+}
@@ -1,13 +1,13 @@
 use serde::{Serialize, Serializer};
 use serde_json::Value;
 use std::fmt;
-use std::sync::mpsc;
 use std::time::Duration;
-use tauri::{AppHandle, Emitter, Listener, Manager, Runtime};
+use tauri::{AppHandle, Runtime};
 
 use crate::error::Error;
 use crate::models::LocalStorageRequest;
 use crate::socket_server::SocketResponse;
+use crate::tools::bridge::{self, BridgeError};
 
 // Define a custom error type for localStorage operations
 #[derive(Debug)]
@@ -40,22 +40,28 @@ impl Serialize for LocalStorageError {
     }
 }
 
-// Support conversion from timeout error
-impl From<mpsc::RecvTimeoutError> for LocalStorageError {
-    fn from(err: mpsc::RecvTimeoutError) -> Self {
-        LocalStorageError::Timeout(format!(
-            "Timeout waiting for localStorage response: {}",
-            err
-        ))
+// Support conversion from bridge errors
+impl From<BridgeError> for LocalStorageError {
+    fn from(err: BridgeError) -> Self {
+        match err {
+            BridgeError::Emit(e) => {
+                LocalStorageError::WebviewOperation(format!("Failed to emit event: {}", e))
+            }
+            err => LocalStorageError::Timeout(format!(
+                "Timeout waiting for localStorage response: {}",
+                err
+            )),
+        }
     }
 }
 // Handler function for the socket server
 pub async fn handle_get_local_storage<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    timeout: Duration,
 ) -> Result<SocketResponse, Error> {
     // Parse params from payload
-    let params: LocalStorageRequest = serde_json::from_value(payload)
+    let mut params: LocalStorageRequest = serde_json::from_value(payload)
         .map_err(|e| Error::Anyhow(format!("Invalid payload for localStorage: {}", e)))?;
 
     // Validate input parameters
@@ -96,17 +102,13 @@ pub async fn handle_get_local_storage<R: Runtime>(
         }
     };
 
-    // Get the window
-    let window_label = params
-        .window_label
-        .clone()
-        .unwrap_or_else(|| "main".to_string());
-    let _window = app
-        .get_webview_window(&window_label)
-        .ok_or_else(|| Error::Anyhow(format!("Window not found: {}", window_label)))?;
+    // Get the window, the focused one when no label is given
+    let window =
+        crate::windows::resolve_window(app, params.window_label.as_deref().unwrap_or_default())?;
+    params.window_label = Some(window.label().to_string());
 
     // Call the implementation function with cloned app handle and params
-    let result = perform_local_storage_operation(app.clone(), params.clone()).await;
+    let result = perform_local_storage_operation(app.clone(), params.clone(), timeout).await;
 
     // Handle the result
     match result {
@@ -130,28 +132,22 @@ pub async fn handle_get_local_storage<R: Runtime>(
 async fn perform_local_storage_operation<R: Runtime>(
     app: AppHandle<R>,
     params: LocalStorageRequest,
+    timeout: Duration,
 ) -> Result<Value, LocalStorageError> {
     // Get window label
-    let window_label = params
-        .window_label
-        .clone()
-        .unwrap_or_else(|| "main".to_string());
-
-    // Emit event to the window
-    app.emit_to(&window_label, "get-local-storage", &params)
-        .map_err(|e| LocalStorageError::WebviewOperation(format!("Failed to emit event: {}", e)))?;
-
-    // Set up channel for response
-    let (tx, rx) = mpsc::channel();
-
-    // Listen for response
-    app.once("get-local-storage-response", move |event| {
-        let payload = event.payload().to_string();
-        let _ = tx.send(payload);
-    });
-
-    // Wait for response with timeout
-    match rx.recv_timeout(Duration::from_secs(5)) {
+    let window_label = params.window_label.clone().unwrap_or_default();
+
+    // Send the operation to the window and wait for the response
+    match bridge::request(
+        &app,
+        &window_label,
+        "get-local-storage",
+        &params,
+        "get-local-storage-response",
+        timeout,
+    )
+    .await
+    {
         Ok(result_string) => {
             // Parse the response
             let response: Value = serde_json::from_str(&result_string).map_err(|e| {
@@ -0,0 +1,145 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{ControlMediaRequest, MediaState};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Longest wait for playback to start or a seek to finish, in milliseconds.
+const SETTLE_MS: u64 = 2000;
+
+/// Applies `options.action` to the `<audio>` or `<video>` element found by `findElement`
+/// and describes its state afterwards. Durations that are not finite (unknown yet, or a
+/// live stream) are reported as null.
+const CONTROL_MEDIA_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+if (element.tagName !== 'AUDIO' && element.tagName !== 'VIDEO') {
+    throw new Error(`Element is a ${element.tagName.toLowerCase()}, not an audio or video element`);
+}
+const view = element.ownerDocument.defaultView;
+const delay = () => new Promise((resolve) => view.setTimeout(() => resolve(null), options.settle_ms));
+
+if (options.volume != null) element.volume = options.volume;
+if (options.playback_rate != null) element.playbackRate = options.playback_rate;
+let playError = null;
+switch (options.action) {
+    case 'play': {
+        // play() settles once playback starts, which may wait for data, or fails e.g.
+        // under an autoplay policy
+        const playing = element.play().then(() => null, (e) => `${e.name}: ${e.message}`);
+        playError = await Promise.race([playing, delay()]);
+        break;
+    }
+    case 'pause':
+        element.pause();
+        break;
+    case 'seek': {
+        const seeked = new Promise((resolve) => {
+            const done = () => resolve(null);
+            element.addEventListener('seeked', done, { once: true });
+            element.addEventListener('error', done, { once: true });
+        });
+        element.currentTime = options.time;
+        await Promise.race([seeked, delay()]);
+        break;
+    }
+    case 'mute':
+        element.muted = true;
+        break;
+    case 'unmute':
+        element.muted = false;
+        break;
+}
+
+const buffered = [];
+for (let i = 0; i < element.buffered.length; i++) {
+    buffered.push([element.buffered.start(i), element.buffered.end(i)]);
+}
+const mediaError = element.error;
+return {
+    tag: element.tagName.toLowerCase(),
+    src: element.currentSrc || element.getAttribute('src') || '',
+    currentTime: element.currentTime,
+    duration: Number.isFinite(element.duration) ? element.duration : null,
+    live: element.duration === Infinity,
+    paused: element.paused,
+    ended: element.ended,
+    seeking: element.seeking,
+    muted: element.muted,
+    volume: element.volume,
+    playbackRate: element.playbackRate,
+    readyState: element.readyState,
+    networkState: element.networkState,
+    buffered,
+    error: mediaError ? `${mediaError.code}: ${mediaError.message || 'media error'}` : null,
+    playError,
+};
+"#;
+
+pub async fn handle_control_media<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: ControlMediaRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for control_media: {}", e)))?;
+    validate(&request)?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let mut options = serde_json::to_value(&request)
+        .map_err(|e| Error::Anyhow(format!("Invalid control_media options: {}", e)))?;
+    options["settle_ms"] = SETTLE_MS.into();
+    let script = element_script(&options, CONTROL_MEDIA_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid control_media options: {}", e)))?;
+    let result = match bridge::eval(&window, &script, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to control media: {}", e)),
+            });
+        }
+    };
+    let response: MediaState = serde_json::from_value(result)
+        .map_err(|e| Error::Anyhow(format!("Unexpected media state: {}", e)))?;
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+fn validate(request: &ControlMediaRequest) -> Result<(), Error> {
+    if !matches!(
+        request.action.as_str(),
+        "status" | "play" | "pause" | "seek" | "mute" | "unmute"
+    ) {
+        return Err(Error::Anyhow(format!(
+            "Unsupported media action: {} (expected status, play, pause, seek, mute or unmute)",
+            request.action
+        )));
+    }
+    if request.action == "seek" && !request.time.is_some_and(|time| time >= 0.0) {
+        return Err(Error::Anyhow(
+            "Seeking needs a time of 0 seconds or more".to_string(),
+        ));
+    }
+    if request
+        .volume
+        .is_some_and(|volume| !(0.0..=1.0).contains(&volume))
+    {
+        return Err(Error::Anyhow("Volume must be between 0 and 1".to_string()));
+    }
+    if request.playback_rate.is_some_and(|rate| rate <= 0.0) {
+        return Err(Error::Anyhow(
+            "Playback rate must be more than 0".to_string(),
+        ));
+    }
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use tauri::menu::Menu;
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::error::Error;
+use crate::menu;
+use crate::models::{
+    ClickMenuItemRequest, ClickMenuItemResponse, ListMenuRequest, MenuEventPayload,
+    MenuListResponse,
+};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_list_menu<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ListMenuRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for list_menu: {}", e)))?;
+
+    let mut response = MenuListResponse {
+        app: None,
+        windows: BTreeMap::new(),
+    };
+    let result = (|| {
+        for (label, menu) in menus(app, request.window_label.as_deref())? {
+            let items = menu::describe_items(menu.items()?)?;
+            match label {
+                Some(label) => {
+                    response.windows.insert(label, items);
+                }
+                None => response.app = Some(items),
+            }
+        }
+        Ok::<_, Error>(())
+    })();
+
+    match result {
+        Ok(()) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_click_menu_item<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ClickMenuItemRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for click_menu_item: {}", e)))?;
+
+    match click(app, request) {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+fn click<R: Runtime>(
+    app: &AppHandle<R>,
+    request: ClickMenuItemRequest,
+) -> crate::Result<ClickMenuItemResponse> {
+    let path = request.path.as_deref().map(|path| {
+        path.split('>')
+            .map(|text| text.trim().to_string())
+            .collect::<Vec<_>>()
+    });
+    let (wanted, target) = match (&request.id, &path) {
+        (Some(id), None) => (format!("id {}", id), request.id.as_deref()),
+        (None, Some(_)) => (
+            format!("path {}", request.path.as_deref().unwrap_or("")),
+            None,
+        ),
+        _ => {
+            return Err(Error::Anyhow(
+                "click_menu_item needs either an id or a path".to_string(),
+            ));
+        }
+    };
+
+    for (label, menu) in menus(app, request.window_label.as_deref())? {
+        let found = match (target, &path) {
+            (Some(id), _) => menu::find_by_id(menu.items()?, id)?,
+            (None, Some(path)) => menu::find_by_path(menu.items()?, path)?,
+            (None, None) => None,
+        };
+        if let Some((item, path)) = found {
+            let item = menu::activate(app, &item)?;
+            return Ok(ClickMenuItemResponse {
+                menu: label.unwrap_or_else(|| "app".to_string()),
+                path,
+                event: MenuEventPayload {
+                    id: item.id.clone(),
+                },
+                item,
+            });
+        }
+    }
+    Err(Error::Anyhow(format!("No menu item with {}", wanted)))
+}
+
+/// The app menu, labelled None, then the menus of the windows, or only the menu of
+/// `window_label`.
+fn menus<R: Runtime>(
+    app: &AppHandle<R>,
+    window_label: Option<&str>,
+) -> crate::Result<Vec<(Option<String>, Menu<R>)>> {
+    if let Some(label) = window_label {
+        let window = crate::windows::resolve_window(app, label)?;
+        let label = window.label();
+        let menu = window
+            .menu()
+            .ok_or_else(|| Error::Anyhow(format!("Window {} has no menu", label)))?;
+        return Ok(vec![(Some(label.to_string()), menu)]);
+    }
+
+    let mut menus: Vec<_> = app.menu().map(|menu| (None, menu)).into_iter().collect();
+    let mut windows: Vec<_> = app.webview_windows().into_iter().collect();
+    windows.sort_by(|a, b| a.0.cmp(&b.0));
+    for (label, window) in windows {
+        // Windows showing the app menu report it as theirs
+        if let Some(menu) = window.menu()
+            && !menus.iter().any(|(_, known)| known.id() == menu.id())
+        {
+            menus.push((Some(label), menu));
+        }
+    }
+    Ok(menus)
+}
@@ -1,57 +1,244 @@
 use log::info;
 use serde_json::Value;
+use std::time::Instant;
 use tauri::{AppHandle, Runtime};
+use tokio_util::sync::CancellationToken;
+
+use crate::TauriMcpExt;
+use crate::audit::{now_ms, redacted, summarize_payload};
+use crate::capabilities::{self, CapabilityState};
+use crate::error::Error;
+use crate::hooks::{ToolDecision, ToolInvocation};
+use crate::recording::RecordingEntry;
 
 use crate::shared::commands;
 use crate::socket_server::SocketResponse;
 
 // Export command modules
+pub mod app_files;
+pub mod app_logs;
+pub mod app_state;
+pub mod artifacts;
+pub mod assert;
+pub mod audit;
+pub mod bridge;
+pub mod cancel;
+pub mod canvas;
+pub mod click;
+pub mod client_config;
+pub mod clients;
+pub mod coordinates;
+pub mod deep_link;
+pub mod describe_tools;
+pub mod dialogs;
+pub mod dom_input;
+pub mod downloads;
+pub mod element_info;
+pub mod element_value;
+pub mod emulation;
 pub mod execute_js;
+pub mod file_input;
+pub mod fill_form;
+pub mod focus;
+pub mod highlight;
+pub mod input_consent;
+pub mod inspect_element;
+pub mod interaction_recording;
+pub mod js_errors;
+pub mod key_press;
 pub mod local_storage;
+pub mod media;
+#[cfg(desktop)]
+pub mod menu;
 pub mod mouse_movement;
+pub mod navigation;
+pub mod overlay;
+pub mod page_info;
+pub mod page_text;
+pub mod pause_session;
+pub mod permissions;
 pub mod ping;
+pub mod poll_events;
+pub mod print_pdf;
+pub mod process_stats;
+pub mod query_db;
+pub mod recover_session;
+pub mod run_script;
+pub mod scroll;
+pub mod selector;
+pub mod server_mode;
+pub mod status;
+pub mod store;
 pub mod text_input;
+pub mod touch;
+pub mod trace;
+pub mod tray;
+pub mod ui_snapshot;
 pub mod webview;
 pub mod window_manager;
 
 // Re-export command handler functions
+pub use app_files::{handle_read_app_file, handle_write_app_file};
+pub use app_logs::handle_get_app_logs;
+pub use app_state::handle_get_app_state;
+pub use artifacts::{handle_delete_artifact, handle_get_artifact, handle_list_artifacts};
+pub use assert::{
+    handle_assert_element_exists, handle_assert_element_visible, handle_assert_text_contains,
+    handle_assert_url_matches,
+};
+pub use audit::handle_get_audit_log;
+pub use cancel::handle_cancel;
+pub use canvas::handle_capture_canvas;
+pub use click::handle_click_element;
+pub use client_config::handle_generate_client_config;
+pub use clients::handle_list_clients;
+pub use coordinates::handle_convert_coordinates;
+pub use deep_link::handle_trigger_deep_link;
+pub use describe_tools::handle_describe_tools;
+pub use dialogs::{
+    handle_list_dialogs, handle_respond_to_dialog, handle_set_dialog_interception,
+    handle_set_dialog_policy,
+};
+pub use downloads::handle_get_downloads;
+pub use element_info::handle_get_element_info;
+pub use element_value::handle_set_element_value;
+pub use emulation::{
+    handle_add_mock_route, handle_advance_clock, handle_emulate_device, handle_remove_mock_route,
+    handle_set_emulation, handle_set_geolocation, handle_set_locale, handle_set_network_conditions,
+    handle_set_theme,
+};
 pub use execute_js::handle_execute_js;
+pub use file_input::handle_set_file_input;
+pub use fill_form::handle_fill_form;
+pub use focus::{handle_blur_element, handle_focus_element};
+pub use highlight::handle_highlight_element;
+pub use input_consent::handle_get_input_consent;
+pub use inspect_element::handle_inspect_element;
+pub use interaction_recording::{
+    handle_start_interaction_recording, handle_stop_interaction_recording,
+};
+pub use js_errors::handle_get_js_errors;
+pub use key_press::handle_simulate_key_press;
 pub use local_storage::handle_get_local_storage;
+pub use media::handle_control_media;
+#[cfg(desktop)]
+pub use menu::{handle_click_menu_item, handle_list_menu};
 pub use mouse_movement::handle_simulate_mouse_movement;
+pub use navigation::handle_wait_for_navigation;
+pub use overlay::handle_set_action_overlay;
+pub use page_info::handle_get_page_info;
+pub use page_text::handle_get_page_text;
+pub use pause_session::{handle_pause_session, handle_resume_session};
+pub use permissions::handle_set_permission_policy;
 pub use ping::handle_ping;
+pub use poll_events::handle_poll_events;
+pub use print_pdf::handle_print_to_pdf;
+pub use process_stats::handle_get_process_stats;
+pub use query_db::handle_query_db;
+pub use recover_session::handle_recover_last_session;
+pub use run_script::handle_run_script;
+pub use scroll::handle_scroll_into_view;
+pub use server_mode::handle_get_server_mode;
+pub use status::handle_status;
+pub use store::handle_manage_store;
 pub use text_input::handle_simulate_text_input;
+pub use touch::handle_simulate_touch;
+pub use trace::{handle_start_trace, handle_stop_trace};
+pub use tray::{handle_click_tray, handle_list_trays, handle_trigger_tray_menu_item};
+pub use ui_snapshot::handle_get_ui_snapshot;
 pub use webview::{handle_get_dom, handle_get_element_position, handle_send_text_to_element};
 pub use window_manager::handle_manage_window;
 
 /// Handle command routing for socket requests
+///
+/// When the request carries an ID, the client that sent it can stop it with the `cancel`
+/// command while it is in flight.
 pub async fn handle_command<R: Runtime>(
     app: &AppHandle<R>,
     command: &str,
     payload: Value,
+    request_id: Option<&str>,
+    client: Option<u64>,
 ) -> crate::Result<SocketResponse> {
     // Log the full request payload
     info!(
         "[TAURI_MCP] Received command: {} with payload: {}",
         command,
-        serde_json::to_string_pretty(&payload)
+        serde_json::to_string_pretty(&redacted(&payload))
             .unwrap_or_else(|_| "[failed to serialize]".to_string())
     );
 
-    let result = match command {
-        commands::PING => handle_ping(app, payload),
-        commands::GET_DOM => handle_get_dom(app, payload).await,
-        commands::MANAGE_LOCAL_STORAGE => handle_get_local_storage(app, payload).await,
-        commands::EXECUTE_JS => handle_execute_js(app, payload).await,
-        commands::MANAGE_WINDOW => handle_manage_window(app, payload).await,
-        commands::SIMULATE_TEXT_INPUT => handle_simulate_text_input(app, payload).await,
-        commands::SIMULATE_MOUSE_MOVEMENT => handle_simulate_mouse_movement(app, payload).await,
-        commands::GET_ELEMENT_POSITION => handle_get_element_position(app, payload).await,
-        commands::SEND_TEXT_TO_ELEMENT => handle_send_text_to_element(app, payload).await,
-        _ => Ok(SocketResponse {
+    let mut trace_step = None;
+    let payload_summary = summarize_payload(&payload);
+    let recorder = app.tauri_mcp().session_recorder();
+    let recorded_payload = recorder.is_enabled().then(|| payload.clone());
+    let start_time = Instant::now();
+
+    // Refuse tools that cannot work on this platform with a clear reason
+    let reports = capabilities::probe(app.tauri_mcp().headless());
+    let unavailable = capabilities::worst_requirement(command, &reports)
+        .filter(|report| report.state == CapabilityState::Unavailable);
+    let result = if app.tauri_mcp().shutdown().is_shutting_down() {
+        Err(Error::ShuttingDown(command.to_string()))
+    } else if let Some(report) = unavailable {
+        Ok(SocketResponse {
             success: false,
             data: None,
-            error: Some(format!("Unknown command: {}", command)),
-        }),
+            error: Some(format!(
+                "Tool {} is unavailable on this platform: {}",
+                command,
+                report.reason.clone().unwrap_or_default()
+            )),
+        })
+    } else if let ToolDecision::Deny(reason) =
+        app.tauri_mcp().hooks().tool_invoked(&ToolInvocation {
+            command,
+            payload: &payload,
+            request_id,
+        })
+    {
+        info!("[TAURI_MCP] The app refused {}: {}", command, reason);
+        Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!(
+                "Tool {} was refused by the app: {}",
+                command, reason
+            )),
+        })
+    } else {
+        // Only calls that run are traced, refused ones leave the page alone
+        trace_step = app.tauri_mcp().tracer().begin(app, command, &payload).await;
+        let cancellations = app.tauri_mcp().cancellations();
+        let cancellable = client.zip(request_id);
+        let cancel = cancellable
+            .map(|(client, id)| cancellations.register(client, id))
+            .unwrap_or_default();
+        // Long polls wait for events without acting on the app, so they leave the session idle
+        let activity = app.tauri_mcp().session_activity();
+        let tracked = command != commands::POLL_EVENTS;
+        if tracked {
+            activity.tool_started(command);
+        }
+        // At shutdown, long polls end at once and other tools after the grace period
+        let shutdown = app.tauri_mcp().shutdown();
+        let interrupted = if tracked {
+            shutdown.deadline()
+        } else {
+            shutdown.stopping()
+        };
+        let result = tokio::select! {
+            result = dispatch(app, command, payload, &cancel, client) => result,
+            _ = cancel.cancelled() => Err(Error::Cancelled(command.to_string())),
+            _ = interrupted.cancelled() => Err(Error::ShuttingDown(command.to_string())),
+        };
+        if tracked {
+            activity.tool_finished(command);
+        }
+        if let Some((client, id)) = cancellable {
+            cancellations.complete(client, id);
+        }
+        result
     };
 
     // Log the response before returning it
@@ -91,5 +278,163 @@ pub async fn handle_command<R: Runtime>(
         info!("[TAURI_MCP] Command {} failed with error: {}", command, e);
     }
 
+    let (success, error) = match &result {
+        Ok(response) => (response.success, response.error.clone()),
+        Err(e) => (false, Some(e.to_string())),
+    };
+    let duration = start_time.elapsed();
+    if let Some(payload) = recorded_payload {
+        recorder.record(&RecordingEntry {
+            timestamp_ms: now_ms(),
+            command: command.to_string(),
+            payload,
+            success,
+            error: error.clone(),
+            duration_ms: duration.as_millis() as u64,
+        });
+    }
+    if let Some(step) = trace_step {
+        let data = result
+            .as_ref()
+            .ok()
+            .and_then(|response| response.data.as_ref());
+        app.tauri_mcp()
+            .tracer()
+            .finish(app, step, success, error.clone(), data)
+            .await;
+    }
+    app.tauri_mcp()
+        .server_stats()
+        .record_command(command, error.as_deref());
+    app.tauri_mcp()
+        .audit_log()
+        .record(command, payload_summary, success, error, duration);
+
     result
 }
+
+/// Route a command to its handler, `client` being the connection that sent it, if any
+pub(crate) async fn dispatch<R: Runtime>(
+    app: &AppHandle<R>,
+    command: &str,
+    payload: Value,
+    cancel: &CancellationToken,
+    client: Option<u64>,
+) -> crate::Result<SocketResponse> {
+    // Input waits while a user has control of the app
+    app.tauri_mcp().session_pause().wait(command).await?;
+    app.tauri_mcp()
+        .input_consent()
+        .check(app, command, &payload)
+        .await?;
+    app.tauri_mcp()
+        .action_overlay()
+        .show(app, command, &payload)
+        .await;
+    let timeout = app.tauri_mcp().timeouts().for_request(command, &payload);
+    match command {
+        commands::PING => handle_ping(app, payload),
+        commands::GET_DOM => handle_get_dom(app, payload, timeout).await,
+        commands::MANAGE_LOCAL_STORAGE => handle_get_local_storage(app, payload, timeout).await,
+        commands::EXECUTE_JS => handle_execute_js(app, payload, timeout).await,
+        commands::MANAGE_WINDOW => handle_manage_window(app, payload).await,
+        commands::SIMULATE_TEXT_INPUT => handle_simulate_text_input(app, payload, cancel).await,
+        commands::SIMULATE_MOUSE_MOVEMENT => handle_simulate_mouse_movement(app, payload).await,
+        commands::GET_ELEMENT_POSITION => handle_get_element_position(app, payload, timeout).await,
+        commands::SEND_TEXT_TO_ELEMENT => handle_send_text_to_element(app, payload, timeout).await,
+        commands::GET_SERVER_MODE => handle_get_server_mode(app, payload),
+        commands::LIST_ARTIFACTS => handle_list_artifacts(app, payload),
+        commands::GET_ARTIFACT => handle_get_artifact(app, payload),
+        commands::DELETE_ARTIFACT => handle_delete_artifact(app, payload),
+        commands::GET_AUDIT_LOG => handle_get_audit_log(app, payload),
+        commands::DESCRIBE_TOOLS => handle_describe_tools(app, payload),
+        commands::POLL_EVENTS => handle_poll_events(app, payload).await,
+        commands::RECOVER_LAST_SESSION => handle_recover_last_session(app, payload),
+        commands::CANCEL => handle_cancel(app, payload, client),
+        commands::LIST_CLIENTS => handle_list_clients(app, payload),
+        commands::STATUS => handle_status(app, payload),
+        commands::GET_PAGE_TEXT => handle_get_page_text(app, payload, timeout).await,
+        commands::GET_ELEMENT_INFO => handle_get_element_info(app, payload, timeout).await,
+        commands::INSPECT_ELEMENT => handle_inspect_element(app, payload, timeout).await,
+        commands::SCROLL_INTO_VIEW => handle_scroll_into_view(app, payload, timeout).await,
+        commands::HIGHLIGHT_ELEMENT => handle_highlight_element(app, payload, timeout).await,
+        commands::GET_PAGE_INFO => handle_get_page_info(app, payload, timeout).await,
+        commands::WAIT_FOR_NAVIGATION => handle_wait_for_navigation(app, payload, timeout).await,
+        commands::GET_DOWNLOADS => handle_get_downloads(app, payload, timeout).await,
+        commands::CONTROL_MEDIA => handle_control_media(app, payload, timeout).await,
+        commands::CLICK_ELEMENT => handle_click_element(app, payload, timeout).await,
+        commands::SET_ELEMENT_VALUE => handle_set_element_value(app, payload, timeout).await,
+        commands::SET_FILE_INPUT => handle_set_file_input(app, payload, timeout).await,
+        commands::LIST_DIALOGS => handle_list_dialogs(app, payload),
+        commands::RESPOND_TO_DIALOG => handle_respond_to_dialog(app, payload),
+        commands::SET_DIALOG_INTERCEPTION => handle_set_dialog_interception(app, payload),
+        commands::SET_DIALOG_POLICY => handle_set_dialog_policy(app, payload),
+        commands::SET_PERMISSION_POLICY => handle_set_permission_policy(app, payload),
+        commands::CAPTURE_CANVAS => handle_capture_canvas(app, payload, timeout).await,
+        commands::PRINT_TO_PDF => handle_print_to_pdf(app, payload, timeout).await,
+        commands::GET_UI_SNAPSHOT => handle_get_ui_snapshot(app, payload, timeout).await,
+        commands::FOCUS_ELEMENT => handle_focus_element(app, payload, timeout).await,
+        commands::BLUR_ELEMENT => handle_blur_element(app, payload, timeout).await,
+        commands::FILL_FORM => handle_fill_form(app, payload, timeout).await,
+        commands::ASSERT_ELEMENT_EXISTS => {
+            handle_assert_element_exists(app, payload, timeout).await
+        }
+        commands::ASSERT_ELEMENT_VISIBLE => {
+            handle_assert_element_visible(app, payload, timeout).await
+        }
+        commands::ASSERT_TEXT_CONTAINS => handle_assert_text_contains(app, payload, timeout).await,
+        commands::ASSERT_URL_MATCHES => handle_assert_url_matches(app, payload, timeout).await,
+        commands::RUN_SCRIPT => handle_run_script(app, payload, cancel).await,
+        commands::START_INTERACTION_RECORDING => handle_start_interaction_recording(app, payload),
+        commands::STOP_INTERACTION_RECORDING => handle_stop_interaction_recording(app, payload),
+        commands::START_TRACE => handle_start_trace(app, payload),
+        commands::CONVERT_COORDINATES => handle_convert_coordinates(app, payload, timeout).await,
+        commands::SIMULATE_KEY_PRESS => handle_simulate_key_press(app, payload, timeout).await,
+        commands::SIMULATE_TOUCH => handle_simulate_touch(app, payload, timeout).await,
+        commands::GENERATE_CLIENT_CONFIG => handle_generate_client_config(app, payload),
+        commands::STOP_TRACE => handle_stop_trace(app, payload),
+        commands::SET_EMULATION => handle_set_emulation(app, payload),
+        commands::ADVANCE_CLOCK => handle_advance_clock(app, payload),
+        commands::SET_GEOLOCATION => handle_set_geolocation(app, payload),
+        commands::SET_LOCALE => handle_set_locale(app, payload, timeout).await,
+        commands::SET_THEME => handle_set_theme(app, payload, timeout).await,
+        commands::EMULATE_DEVICE => handle_emulate_device(app, payload),
+        commands::SET_NETWORK_CONDITIONS => handle_set_network_conditions(app, payload),
+        commands::ADD_MOCK_ROUTE => handle_add_mock_route(app, payload, timeout).await,
+        commands::REMOVE_MOCK_ROUTE => handle_remove_mock_route(app, payload),
+        commands::GET_PROCESS_STATS => handle_get_process_stats(app, payload).await,
+        commands::GET_JS_ERRORS => handle_get_js_errors(app, payload),
+        commands::GET_APP_LOGS => handle_get_app_logs(app, payload),
+        commands::GET_APP_STATE => handle_get_app_state(app, payload),
+        commands::MANAGE_STORE => handle_manage_store(app, payload),
+        commands::QUERY_DB => handle_query_db(app, payload, timeout).await,
+        commands::READ_APP_FILE => handle_read_app_file(app, payload),
+        commands::WRITE_APP_FILE => handle_write_app_file(app, payload),
+        commands::TRIGGER_DEEP_LINK => handle_trigger_deep_link(app, payload),
+        commands::LIST_TRAYS => handle_list_trays(app, payload),
+        commands::TRIGGER_TRAY_MENU_ITEM => handle_trigger_tray_menu_item(app, payload),
+        commands::CLICK_TRAY => handle_click_tray(app, payload),
+        #[cfg(desktop)]
+        commands::LIST_MENU => handle_list_menu(app, payload),
+        #[cfg(desktop)]
+        commands::CLICK_MENU_ITEM => handle_click_menu_item(app, payload),
+        #[cfg(mobile)]
+        commands::LIST_MENU | commands::CLICK_MENU_ITEM => {
+            Err(crate::mobile::unsupported("The menu bar"))
+        }
+        commands::PAUSE_SESSION => handle_pause_session(app, payload).await,
+        commands::RESUME_SESSION => handle_resume_session(app, payload),
+        commands::GET_INPUT_CONSENT => handle_get_input_consent(app, payload),
+        commands::SET_ACTION_OVERLAY => handle_set_action_overlay(app, payload),
+        commands::HANDSHAKE => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some("handshake is only available on socket connections".to_string()),
+        }),
+        _ => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Unknown command: {}", command)),
+        }),
+    }
+}
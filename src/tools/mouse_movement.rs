@@ -1,11 +1,15 @@
 use serde_json::Value;
-use tauri::{AppHandle, Manager, Runtime};
+use tauri::{AppHandle, Runtime};
 
+use crate::TauriMcpExt;
+use crate::coordinates::{CoordinateSpace, WindowGeometry};
 use crate::error::Error;
-use crate::models::MouseMovementRequest;
+use crate::models::{InputMode, MouseMovementRequest};
+use crate::platform::input::{Direction, MouseButton};
+use crate::shared::commands;
 use crate::shared::{MouseMovementParams, MouseMovementResult};
 use crate::socket_server::SocketResponse;
-use enigo::{Button, Coordinate, Direction, Enigo, Mouse, Settings};
+use crate::tools::dom_input;
 use log::info;
 use std::time::Instant;
 
@@ -19,27 +23,22 @@ pub async fn simulate_mouse_movement_async<R: Runtime>(
     );
 
     // Get the window reference
-    let window = app
-        .get_webview_window("main")
-        .ok_or_else(|| Error::Anyhow("Main window not found".to_string()))?;
-
-    // Get window position (outer includes window borders/decorations)
-    let window_position = window
-        .outer_position()
-        .map_err(|e| Error::Anyhow(format!("Failed to get window position: {}", e)))?;
-    info!(
-        "[MOUSE_MOVEMENT] Window outer position: {:?}",
-        window_position
-    );
+    let window = crate::windows::resolve_window(app, &params.window_label)?;
+
+    let input_mode = app
+        .tauri_mcp()
+        .input_mode(commands::SIMULATE_MOUSE_MOVEMENT, params.input_mode);
+    if input_mode == InputMode::Dom {
+        let timeout = app
+            .tauri_mcp()
+            .timeouts()
+            .for_tool(commands::SIMULATE_MOUSE_MOVEMENT);
+        return dom_input::move_mouse(&window, &params, timeout).await;
+    }
 
-    // Also get inner position for comparison
-    let window_inner_position = window
-        .inner_position()
-        .map_err(|e| Error::Anyhow(format!("Failed to get window inner position: {}", e)))?;
-    info!(
-        "[MOUSE_MOVEMENT] Window inner position: {:?}",
-        window_inner_position
-    );
+    // Window position (outer includes window borders/decorations) and scale factor
+    let geometry = WindowGeometry::of(&window)?;
+    info!("[MOUSE_MOVEMENT] Window geometry: {:?}", geometry);
 
     // Get window size for reference
     let window_size = window
@@ -47,12 +46,6 @@ pub async fn simulate_mouse_movement_async<R: Runtime>(
         .map_err(|e| Error::Anyhow(format!("Failed to get window size: {}", e)))?;
     info!("[MOUSE_MOVEMENT] Window inner size: {:?}", window_size);
 
-    // Get window scale factor for high DPI screens
-    let scale_factor = window
-        .scale_factor()
-        .map_err(|e| Error::Anyhow(format!("Failed to get scale factor: {}", e)))?;
-    info!("[MOUSE_MOVEMENT] Window scale factor: {}", scale_factor);
-
     let x = params.x;
     let y = params.y;
     let relative = params.relative.unwrap_or(false);
@@ -64,13 +57,10 @@ pub async fn simulate_mouse_movement_async<R: Runtime>(
         x, y, relative
     );
 
-    // Create Enigo instance
-    let mut enigo = Enigo::new(&Settings::default())
-        .map_err(|e| Error::Anyhow(format!("Failed to initialize Enigo: {}", e)))?;
+    let mut input = app.tauri_mcp().input()?;
 
     // Get current mouse position for reference
-    let current_position = Mouse::location(&enigo)
-        .map_err(|e| Error::Anyhow(format!("Failed to get current mouse position: {}", e)))?;
+    let current_position = input.location()?;
     info!(
         "[MOUSE_MOVEMENT] Current mouse position before move: ({}, {})",
         current_position.0, current_position.1
@@ -84,29 +74,11 @@ pub async fn simulate_mouse_movement_async<R: Runtime>(
         (x, y) // Keep as is for relative movements
     } else {
         // Adjust for window position and scale factor
-        let scaled_x = (x as f64 * scale_factor) as i32;
-        let scaled_y = (y as f64 * scale_factor) as i32;
-
-        info!("[MOUSE_MOVEMENT] Coordinate transformation:");
-        info!("[MOUSE_MOVEMENT] 1. Original coordinates: ({}, {})", x, y);
+        let (final_x, final_y) =
+            geometry.os_input_point(CoordinateSpace::Window, (f64::from(x), f64::from(y)));
         info!(
-            "[MOUSE_MOVEMENT] 2. After scale factor ({}): ({}, {})",
-            scale_factor, scaled_x, scaled_y
-        );
-
-        let final_x = scaled_x + window_position.x;
-        let final_y = scaled_y + window_position.y;
-        info!(
-            "[MOUSE_MOVEMENT] 3. After adding window position ({}, {}): ({}, {})",
-            window_position.x, window_position.y, final_x, final_y
-        );
-
-        // Calculate what it would be with inner position for comparison
-        let inner_x = scaled_x + window_inner_position.x;
-        let inner_y = scaled_y + window_inner_position.y;
-        info!(
-            "[MOUSE_MOVEMENT] (Alternative with inner position: ({}, {}))",
-            inner_x, inner_y
+            "[MOUSE_MOVEMENT] Window point ({}, {}) is ({}, {}) for the OS mouse",
+            x, y, final_x, final_y
         );
 
         (final_x, final_y)
@@ -118,39 +90,28 @@ pub async fn simulate_mouse_movement_async<R: Runtime>(
     );
 
     // Use calculated screen coordinates
-    let coordinate_type = if relative {
-        Coordinate::Rel
+    if relative {
+        input.move_by(screen_x, screen_y)?;
     } else {
-        Coordinate::Abs
-    };
-
-    Mouse::move_mouse(&mut enigo, screen_x, screen_y, coordinate_type)
-        .map_err(|e| Error::Anyhow(format!("Failed to move mouse: {}", e)))?;
+        input.move_to(screen_x, screen_y)?;
+    }
 
     // Perform click if requested
     if click {
-        // Convert string button type to Button enum
-        let button = match button_type {
-            "right" => Button::Right,
-            "middle" => Button::Middle,
-            _ => Button::Left, // Default to left button
-        };
+        // Default to left button
+        let button = MouseButton::parse(button_type).unwrap_or(MouseButton::Left);
 
         info!("[MOUSE_MOVEMENT] Clicking with {} button", button_type);
 
         // Perform click (press and release)
-        Mouse::button(&mut enigo, button, Direction::Press)
-            .map_err(|e| Error::Anyhow(format!("Failed to press mouse button: {}", e)))?;
-
-        Mouse::button(&mut enigo, button, Direction::Release)
-            .map_err(|e| Error::Anyhow(format!("Failed to release mouse button: {}", e)))?;
+        input.button(button, Direction::Press)?;
+        input.button(button, Direction::Release)?;
     }
 
     let duration_ms = start_time.elapsed().as_millis() as u64;
 
     // Get current position after movement
-    let position = Mouse::location(&enigo)
-        .map_err(|e| Error::Anyhow(format!("Failed to get mouse position: {}", e)))?;
+    let position = input.location()?;
     info!(
         "[MOUSE_MOVEMENT] Final mouse position after move: ({}, {})",
         position.0, position.1
@@ -178,6 +139,8 @@ pub fn simulate_mouse_movement_shared<R: Runtime>(
         relative: params.relative,
         click: params.click,
         button: params.button,
+        input_mode: params.input_mode,
+        window_label: params.window_label,
     };
 
     // Run async method
@@ -0,0 +1,125 @@
+use regex::Regex;
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{WaitForNavigationRequest, WaitForNavigationResponse};
+use crate::navigation::{Navigation, NavigationKind};
+use crate::socket_server::SocketResponse;
+
+/// How `pattern` is compared with URLs, as in `assert_url_matches`.
+enum UrlMatcher {
+    Any,
+    Contains(String),
+    Exact(String),
+    Regex(Regex),
+}
+
+impl UrlMatcher {
+    fn new(request: &WaitForNavigationRequest) -> Result<Self, Error> {
+        let Some(pattern) = &request.pattern else {
+            return Ok(UrlMatcher::Any);
+        };
+        Ok(if request.regex {
+            UrlMatcher::Regex(
+                Regex::new(pattern)
+                    .map_err(|e| Error::Anyhow(format!("Invalid URL pattern: {}", e)))?,
+            )
+        } else if request.exact {
+            UrlMatcher::Exact(pattern.clone())
+        } else {
+            UrlMatcher::Contains(pattern.clone())
+        })
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        match self {
+            UrlMatcher::Any => true,
+            UrlMatcher::Contains(pattern) => url.contains(pattern.as_str()),
+            UrlMatcher::Exact(pattern) => url == pattern,
+            UrlMatcher::Regex(regex) => regex.is_match(url),
+        }
+    }
+}
+
+pub async fn handle_wait_for_navigation<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: WaitForNavigationRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for wait_for_navigation: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+    let label = window.label().to_string();
+    let matcher = UrlMatcher::new(&request)?;
+    let navigations = app.tauri_mcp().navigations();
+    let started = Instant::now();
+
+    // The navigation may have happened before the request, e.g. right after a click
+    if request.after_seq.is_none()
+        && request.pattern.is_some()
+        && let Ok(url) = window.url()
+        && matcher.matches(url.as_str())
+        && navigations
+            .latest(&label)
+            .is_none_or(|navigation| navigation.kind.is_complete())
+    {
+        return respond(WaitForNavigationResponse {
+            url: url.to_string(),
+            navigation: None,
+            waited_ms: 0,
+            cursor: navigations.latest_seq(),
+        });
+    }
+
+    let since = request
+        .after_seq
+        .unwrap_or_else(|| navigations.latest_seq());
+    let accepts = |navigation: &Navigation| {
+        navigation.window_label == label
+            && if request.wait_for_load {
+                navigation.kind == NavigationKind::LoadFinished
+            } else {
+                navigation.kind.is_complete()
+            }
+            && matcher.matches(&navigation.url)
+    };
+    match navigations.wait_for(since, accepts, timeout).await {
+        Some(navigation) => respond(WaitForNavigationResponse {
+            url: navigation.url.clone(),
+            navigation: Some(navigation),
+            waited_ms: started.elapsed().as_millis() as u64,
+            cursor: navigations.latest_seq(),
+        }),
+        None => {
+            let current = window.url().map(|url| url.to_string()).unwrap_or_default();
+            let expected = match &request.pattern {
+                Some(pattern) => format!("matching {}", pattern),
+                None if request.wait_for_load => "finishing to load".to_string(),
+                None => "happening".to_string(),
+            };
+            Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!(
+                    "No navigation {} within {} ms, the page is at {}",
+                    expected,
+                    timeout.as_millis(),
+                    current
+                )),
+            })
+        }
+    }
+}
+
+fn respond(response: WaitForNavigationResponse) -> Result<SocketResponse, Error> {
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,24 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::SetActionOverlayRequest;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_set_action_overlay<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetActionOverlayRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_action_overlay: {}", e)))?;
+
+    app.tauri_mcp()
+        .action_overlay()
+        .set_enabled(request.enabled);
+    Ok(SocketResponse {
+        success: true,
+        data: Some(serde_json::json!({ "enabled": request.enabled })),
+        error: None,
+    })
+}
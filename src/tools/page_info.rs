@@ -0,0 +1,66 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{PageInfoRequest, PageInfoResponse};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+
+/// Describes the page, asking the route provider registered by the app, if any, for the
+/// route of its client-side router.
+const PAGE_INFO_SCRIPT: &str = r#"
+let route = null;
+let routeError = null;
+const provider = window.__TAURI_MCP_ROUTE__;
+if (typeof provider === 'function') {
+    try {
+        const value = await provider();
+        if (typeof value === 'string') route = { path: value };
+        else if (value != null) route = JSON.parse(JSON.stringify(value));
+    } catch (e) {
+        routeError = e instanceof Error ? e.message : String(e);
+    }
+}
+return {
+    url: location.href,
+    title: document.title,
+    readyState: document.readyState,
+    historyLength: history.length,
+    visibilityState: document.visibilityState,
+    focused: document.hasFocus(),
+    route,
+    routeError,
+};
+"#;
+
+pub async fn handle_get_page_info<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: PageInfoRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_page_info: {}", e)))?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let result = match bridge::eval(&window, PAGE_INFO_SCRIPT, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to get page info: {}", e)),
+            });
+        }
+    };
+    let mut response: PageInfoResponse = serde_json::from_value(result)
+        .map_err(|e| Error::Anyhow(format!("Unexpected page info: {}", e)))?;
+    response.window_label = window.label().to_string();
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,97 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{PageTextRequest, PageTextResponse};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+
+/// Collects the rendered text of the page, or of every element matching a selector.
+/// Expects an `options` constant holding the [`PageTextRequest`].
+const PAGE_TEXT_SCRIPT: &str = r#"
+const BOILERPLATE = 'nav, header, footer, aside, [role="navigation"], [role="banner"], [role="contentinfo"], [aria-hidden="true"]';
+const readableText = (el) => {
+    if (el.matches(BOILERPLATE)) return '';
+    if (!el.querySelector(BOILERPLATE)) return el.innerText;
+    return Array.from(el.childNodes)
+        .map((child) => child.nodeType === Node.ELEMENT_NODE
+            ? readableText(child)
+            : (child.nodeType === Node.TEXT_NODE ? child.textContent.trim() : ''))
+        .filter((text) => text !== '')
+        .join('\n');
+};
+let roots;
+if (options.selector) {
+    roots = Array.from(document.querySelectorAll(options.selector));
+    if (roots.length === 0) throw new Error(`No element matches selector ${options.selector}`);
+} else if (options.readable) {
+    roots = [document.querySelector('article, main, [role="main"]') || document.body];
+} else {
+    roots = [document.body];
+}
+const text = roots
+    .map((root) => options.readable ? readableText(root) : root.innerText)
+    .join('\n\n')
+    .replace(/[ \t]+\n/g, '\n')
+    .replace(/\n{3,}/g, '\n\n')
+    .trim();
+return { text, matchCount: roots.length };
+"#;
+
+pub async fn handle_get_page_text<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: PageTextRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_page_text: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let options = serde_json::to_string(&request)
+        .map_err(|e| Error::Anyhow(format!("Invalid get_page_text options: {}", e)))?;
+    let script = format!("const options = {};\n{}", options, PAGE_TEXT_SCRIPT);
+    let result = match bridge::eval(&window, &script, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to get page text: {}", e)),
+            });
+        }
+    };
+
+    let text = result.get("text").and_then(Value::as_str).unwrap_or("");
+    let length = text.chars().count();
+    let (text, truncated) = match request.max_length {
+        Some(max_length) if length > max_length => (
+            format!(
+                "{}\n[truncated: showing {} of {} characters]",
+                text.chars().take(max_length).collect::<String>(),
+                max_length,
+                length
+            ),
+            true,
+        ),
+        _ => (text.to_string(), false),
+    };
+    let response = PageTextResponse {
+        text,
+        length,
+        truncated,
+        match_count: result
+            .get("matchCount")
+            .and_then(Value::as_u64)
+            .unwrap_or(0) as usize,
+    };
+
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
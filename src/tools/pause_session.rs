@@ -0,0 +1,54 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{PauseSessionRequest, ResumeSessionRequest};
+use crate::socket_server::SocketResponse;
+
+pub async fn handle_pause_session<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: PauseSessionRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for pause_session: {}", e)))?;
+
+    let pause = app.tauri_mcp().session_pause();
+    pause.pause(app, request.message).await;
+
+    let data = serde_json::to_value(pause.status())
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+pub fn handle_resume_session<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ResumeSessionRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for resume_session: {}", e)))?;
+
+    let outcome = app
+        .tauri_mcp()
+        .session_pause()
+        .resume(app, request.discard_queued);
+    if !outcome.resumed {
+        return Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some("The session is not paused".to_string()),
+        });
+    }
+
+    let data = serde_json::to_value(outcome)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
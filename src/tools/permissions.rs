@@ -0,0 +1,50 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{PermissionsResponse, SetPermissionPolicyRequest};
+use crate::permissions::PERMISSIONS;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_set_permission_policy<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: SetPermissionPolicyRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for set_permission_policy: {}", e)))?;
+
+    let gate = app.tauri_mcp().permissions();
+    if let Some(policy) = &request.policy
+        && let Some(unknown) = policy
+            .permissions
+            .keys()
+            .find(|permission| !PERMISSIONS.contains(&permission.as_str()))
+    {
+        return Err(Error::Anyhow(format!(
+            "Unsupported permission: {} (expected one of {})",
+            unknown,
+            PERMISSIONS.join(", ")
+        )));
+    }
+    if request.clear {
+        gate.set_policy(app, None);
+    } else if let Some(policy) = request.policy {
+        gate.set_policy(app, Some(policy));
+    }
+
+    let response = PermissionsResponse {
+        policy: gate.policy(),
+        requests: gate.requests(),
+    };
+    if request.clear_requests {
+        gate.clear_requests();
+    }
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,50 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::PollEventsRequest;
+use crate::socket_server::SocketResponse;
+
+/// Default time a poll waits for new events.
+const DEFAULT_TIMEOUT_MS: u64 = 25_000;
+/// Upper bound on the wait, so a poll never holds the connection indefinitely.
+const MAX_TIMEOUT_MS: u64 = 120_000;
+const DEFAULT_MAX_EVENTS: usize = 100;
+
+pub async fn handle_poll_events<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let params: PollEventsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for poll_events: {}", e)))?;
+
+    let timeout = Duration::from_millis(
+        params
+            .timeout_ms
+            .unwrap_or(DEFAULT_TIMEOUT_MS)
+            .min(MAX_TIMEOUT_MS),
+    );
+    let max_events = params.max_events.unwrap_or(DEFAULT_MAX_EVENTS).max(1);
+
+    let result = app
+        .tauri_mcp()
+        .events()
+        .poll(
+            params.session_id.as_deref(),
+            params.cursor,
+            params.kinds.as_deref(),
+            max_events,
+            timeout,
+        )
+        .await;
+
+    let data = serde_json::to_value(result)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,168 @@
+use base64::Engine;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Runtime, WebviewWindow};
+
+use crate::error::Error;
+use crate::models::{PrintToPdfRequest, PrintToPdfResponse};
+use crate::socket_server::SocketResponse;
+
+pub async fn handle_print_to_pdf<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: PrintToPdfRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for print_to_pdf: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    match print_to_file(&window, &request, timeout).await {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(format!("Failed to print to PDF: {}", e)),
+        }),
+    }
+}
+
+async fn print_to_file<R: Runtime>(
+    window: &WebviewWindow<R>,
+    request: &PrintToPdfRequest,
+    timeout: Duration,
+) -> crate::Result<PrintToPdfResponse> {
+    let path = match &request.path {
+        Some(path) => std::path::absolute(path)?,
+        None => default_path(window.label()),
+    };
+    // A file left by an earlier run would pass for the output of a failed print
+    if path.exists() {
+        std::fs::remove_file(&path)?;
+    }
+
+    platform::print(window, &path, request.landscape, timeout).await?;
+
+    let size = std::fs::metadata(&path)
+        .map_err(|e| Error::Anyhow(format!("No PDF written to {}: {}", path.display(), e)))?
+        .len();
+    let data = if request.include_data {
+        Some(base64::engine::general_purpose::STANDARD.encode(std::fs::read(&path)?))
+    } else {
+        None
+    };
+    Ok(PrintToPdfResponse {
+        path: path.to_string_lossy().to_string(),
+        size,
+        data,
+    })
+}
+
+fn default_path(window_label: &str) -> PathBuf {
+    let millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis())
+        .unwrap_or_default();
+    std::env::temp_dir().join(format!("tauri-mcp-{}-{}.pdf", window_label, millis))
+}
+
+/// Prints through WebKitGTK's print operation to GTK's file printer, without showing the
+/// print dialog.
+#[cfg(target_os = "linux")]
+mod platform {
+    use gtk::gio::prelude::FileExt;
+    use std::cell::RefCell;
+    use std::path::Path;
+    use std::rc::Rc;
+    use std::time::Duration;
+    use tauri::{Runtime, WebviewWindow};
+    use tokio::sync::oneshot;
+    use webkit2gtk::PrintOperationExt;
+
+    use crate::error::Error;
+
+    pub(super) async fn print<R: Runtime>(
+        window: &WebviewWindow<R>,
+        path: &Path,
+        landscape: bool,
+        timeout: Duration,
+    ) -> crate::Result<()> {
+        let (tx, rx) = oneshot::channel::<Result<(), String>>();
+        let uri = gtk::gio::File::for_path(path).uri().to_string();
+
+        window.with_webview(move |webview| {
+            let settings = gtk::PrintSettings::new();
+            // The file printer is named in the user's language
+            settings.set_printer(&gtk::glib::dgettext(Some("gtk30"), "Print to File"));
+            settings.set(gtk::PRINT_SETTINGS_OUTPUT_FILE_FORMAT, Some("pdf"));
+            settings.set(gtk::PRINT_SETTINGS_OUTPUT_URI, Some(&uri));
+            let page_setup = gtk::PageSetup::new();
+            if landscape {
+                page_setup.set_orientation(gtk::PageOrientation::Landscape);
+            }
+
+            let operation = webkit2gtk::PrintOperation::new(&webview.inner());
+            operation.set_print_settings(&settings);
+            operation.set_page_setup(&page_setup);
+
+            // Failed is emitted before finished, so the first result wins. The operation
+            // is kept alive until it finishes.
+            let tx = Rc::new(RefCell::new(Some(tx)));
+            let kept = Rc::new(RefCell::new(Some(operation.clone())));
+            let failed_tx = tx.clone();
+            operation.connect_failed(move |_, error| {
+                if let Some(tx) = failed_tx.borrow_mut().take() {
+                    let _ = tx.send(Err(error.to_string()));
+                }
+            });
+            operation.connect_finished(move |_| {
+                if let Some(tx) = tx.borrow_mut().take() {
+                    let _ = tx.send(Ok(()));
+                }
+                kept.borrow_mut().take();
+            });
+            operation.print();
+        })?;
+
+        match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(Ok(()))) => Ok(()),
+            Ok(Ok(Err(e))) => Err(Error::Anyhow(e)),
+            Ok(Err(_)) => Err(Error::Anyhow(
+                "The print operation ended without a result".to_string(),
+            )),
+            Err(_) => Err(Error::Anyhow(format!(
+                "Printing did not finish within {} ms",
+                timeout.as_millis()
+            ))),
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod platform {
+    use std::path::Path;
+    use std::time::Duration;
+    use tauri::{Runtime, WebviewWindow};
+
+    use crate::error::Error;
+
+    pub(super) async fn print<R: Runtime>(
+        _window: &WebviewWindow<R>,
+        _path: &Path,
+        _landscape: bool,
+        _timeout: Duration,
+    ) -> crate::Result<()> {
+        Err(Error::Anyhow(
+            "print_to_pdf is only supported on Linux for now".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,36 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::GetProcessStatsRequest;
+use crate::process_stats::MINIMUM_CPU_UPDATE_INTERVAL;
+use crate::socket_server::SocketResponse;
+
+pub async fn handle_get_process_stats<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: GetProcessStatsRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_process_stats: {}", e)))?;
+
+    let monitor = app.tauri_mcp().process_monitor();
+    let report = match monitor.sample(request.include_children) {
+        Some(report) => report,
+        // The first sample only sets the baseline for CPU usage
+        None => {
+            tokio::time::sleep(MINIMUM_CPU_UPDATE_INTERVAL).await;
+            monitor
+                .sample(request.include_children)
+                .ok_or_else(|| Error::Anyhow("Failed to sample processes".to_string()))?
+        }
+    };
+
+    let data = serde_json::to_value(report)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,62 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::QueryDbRequest;
+use crate::socket_server::SocketResponse;
+
+const DEFAULT_MAX_ROWS: usize = 1000;
+
+pub async fn handle_query_db<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: QueryDbRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for query_db: {}", e)))?;
+
+    #[cfg(feature = "sql")]
+    let result = {
+        use crate::TauriMcpExt;
+
+        let query = app.tauri_mcp().databases().query(
+            app,
+            &request.database,
+            &request.query,
+            &request.params,
+            request.max_rows.unwrap_or(DEFAULT_MAX_ROWS),
+        );
+        match tokio::time::timeout(timeout, query).await {
+            Ok(result) => result,
+            Err(_) => Err(Error::Anyhow(format!(
+                "Query timed out after {} ms",
+                timeout.as_millis()
+            ))),
+        }
+    };
+    #[cfg(not(feature = "sql"))]
+    let result: crate::Result<crate::database::QueryResult> = {
+        let _ = (app, request, timeout, DEFAULT_MAX_ROWS);
+        Err(Error::Anyhow(
+            "query_db requires the sql feature of tauri-plugin-mcp".to_string(),
+        ))
+    };
+
+    match result {
+        Ok(result) => {
+            let data = serde_json::to_value(result)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
@@ -0,0 +1,33 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::RecoverSessionResponse;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_recover_last_session<R: Runtime>(
+    app: &AppHandle<R>,
+    _payload: Value,
+) -> Result<SocketResponse, Error> {
+    match app.tauri_mcp().session_recorder().last_session() {
+        Ok(session) => {
+            let response = RecoverSessionResponse {
+                found: session.is_some(),
+                session,
+            };
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
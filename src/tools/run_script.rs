@@ -0,0 +1,212 @@
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+use tokio_util::sync::CancellationToken;
+
+use crate::error::Error;
+use crate::models::{
+    RunScriptRequest, RunScriptResponse, ScriptStep, StepErrorPolicy, StepReport, StepStatus,
+};
+use crate::shared::commands;
+use crate::socket_server::SocketResponse;
+use crate::tools::{dispatch, handle_command};
+
+/// Pseudo-command only valid in scripts: sleeps for `ms`, or waits until an element is
+/// visible or the URL matches.
+pub(crate) const WAIT_STEP: &str = "wait";
+
+/// How often a `wait` step checks for its element.
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Default time a `wait` step waits for its element.
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Commands that cannot run as a step.
+const FORBIDDEN_STEPS: &[&str] = &[commands::RUN_SCRIPT, commands::HANDSHAKE];
+
+pub async fn handle_run_script<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    cancel: &CancellationToken,
+) -> Result<SocketResponse, Error> {
+    let request: RunScriptRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for run_script: {}", e)))?;
+    if let Some(step) = request
+        .steps
+        .iter()
+        .find(|step| FORBIDDEN_STEPS.contains(&step.command.as_str()))
+    {
+        return Err(Error::Anyhow(format!(
+            "{} cannot be used as a script step",
+            step.command
+        )));
+    }
+
+    let start = Instant::now();
+    let mut reports = Vec::with_capacity(request.steps.len());
+    let mut stopped = false;
+    for (index, step) in request.steps.iter().enumerate() {
+        if stopped {
+            reports.push(StepReport {
+                index,
+                name: step.name.clone(),
+                command: step.command.clone(),
+                status: StepStatus::Skipped,
+                attempts: 0,
+                duration_ms: 0,
+                data: None,
+                error: None,
+            });
+            continue;
+        }
+
+        let report = run_step(app, index, step, cancel).await;
+        if report.status == StepStatus::Failed
+            && step.on_error.unwrap_or(request.on_error) == StepErrorPolicy::Stop
+        {
+            stopped = true;
+        }
+        reports.push(report);
+    }
+
+    let count = |status| reports.iter().filter(|r| r.status == status).count();
+    let response = RunScriptResponse {
+        passed: count(StepStatus::Failed) == 0 && count(StepStatus::Skipped) == 0,
+        completed: count(StepStatus::Passed),
+        failed: count(StepStatus::Failed),
+        skipped: count(StepStatus::Skipped),
+        duration_ms: start.elapsed().as_millis() as u64,
+        steps: reports,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+/// Run a step, retrying it as configured.
+async fn run_step<R: Runtime>(
+    app: &AppHandle<R>,
+    index: usize,
+    step: &ScriptStep,
+    cancel: &CancellationToken,
+) -> StepReport {
+    let start = Instant::now();
+    let mut attempts = 0;
+    let (data, error) = loop {
+        attempts += 1;
+        let (data, error) = match execute(app, step, cancel).await {
+            Ok(response) => {
+                let data = response.data;
+                // Assertions answer successfully with a failed verdict
+                let error = match (&response.error, &data) {
+                    (Some(e), _) => Some(e.clone()),
+                    (None, _) if !response.success => Some("step failed".to_string()),
+                    (None, Some(data)) if data.get("passed") == Some(&Value::Bool(false)) => Some(
+                        data.get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("assertion failed")
+                            .to_string(),
+                    ),
+                    _ => None,
+                };
+                (data, error)
+            }
+            Err(e) => (None, Some(e.to_string())),
+        };
+        if error.is_none() || attempts > step.retries {
+            break (data, error);
+        }
+    };
+
+    StepReport {
+        index,
+        name: step.name.clone(),
+        command: step.command.clone(),
+        status: if error.is_none() {
+            StepStatus::Passed
+        } else {
+            StepStatus::Failed
+        },
+        attempts,
+        duration_ms: start.elapsed().as_millis() as u64,
+        data,
+        error,
+    }
+}
+
+async fn execute<R: Runtime>(
+    app: &AppHandle<R>,
+    step: &ScriptStep,
+    cancel: &CancellationToken,
+) -> crate::Result<SocketResponse> {
+    if step.command == WAIT_STEP {
+        return wait(app, &step.payload, cancel).await;
+    }
+    // Steps go through the full command path, so they are audited and checked against
+    // the platform capabilities like any other request
+    Box::pin(handle_command(
+        app,
+        &step.command,
+        step.payload.clone(),
+        None,
+        None,
+    ))
+    .await
+}
+
+/// Sleep for `ms`, or poll until the element given by `selector_type` and `selector_value`
+/// is visible or the URL matches `pattern` (see `assert_url_matches`), for `timeout_ms` at
+/// most.
+async fn wait<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: &Value,
+    cancel: &CancellationToken,
+) -> crate::Result<SocketResponse> {
+    if let Some(ms) = payload.get("ms").and_then(Value::as_u64) {
+        tokio::time::sleep(Duration::from_millis(ms)).await;
+        return Ok(SocketResponse {
+            success: true,
+            data: None,
+            error: None,
+        });
+    }
+    let condition = if payload.get("pattern").is_some() {
+        commands::ASSERT_URL_MATCHES
+    } else if payload.get("selector_value").is_some() {
+        commands::ASSERT_ELEMENT_VISIBLE
+    } else {
+        return Err(Error::Anyhow(
+            "A wait step needs ms, pattern, or selector_type and selector_value".to_string(),
+        ));
+    };
+
+    let timeout = payload
+        .get("timeout_ms")
+        .and_then(Value::as_u64)
+        .map(Duration::from_millis)
+        .unwrap_or(DEFAULT_WAIT_TIMEOUT);
+    let deadline = Instant::now() + timeout;
+    // The whole wait budget is for polling; each check gets the default webview timeout
+    let mut check = payload.clone();
+    if let Some(check) = check.as_object_mut() {
+        check.remove("timeout_ms");
+    }
+    loop {
+        let response = Box::pin(dispatch(app, condition, check.clone(), cancel, None)).await?;
+        let passed = response.success
+            && response
+                .data
+                .as_ref()
+                .and_then(|data| data.get("passed"))
+                .and_then(Value::as_bool)
+                == Some(true);
+        if passed || Instant::now() >= deadline {
+            return Ok(response);
+        }
+        tokio::time::sleep(WAIT_POLL_INTERVAL).await;
+    }
+}
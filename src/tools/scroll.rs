@@ -0,0 +1,79 @@
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{ScrollIntoViewRequest, ScrollIntoViewResponse};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Scrolls the element found by `findElement` into view and describes where it ended up, in
+/// the window's viewport.
+const SCROLL_INTO_VIEW_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+const scrolled = await scrollIntoViewIfNeeded(element, {
+    block: options.block, inline: options.inline, behavior: options.behavior, force: options.force,
+});
+if (scrolled) Object.assign(frame, resolveFrame(options.frame_selector));
+const rect = element.getBoundingClientRect();
+const view = element.ownerDocument.defaultView;
+return {
+    scrolled,
+    rect: { x: frame.offsetX + rect.x, y: frame.offsetY + rect.y, width: rect.width, height: rect.height },
+    fullyVisible: !isClipped(element),
+    scrollX: view.scrollX,
+    scrollY: view.scrollY,
+};
+"#;
+
+pub async fn handle_scroll_into_view<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: ScrollIntoViewRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for scroll_into_view: {}", e)))?;
+    validate(&request)?;
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let script = element_script(&request, SCROLL_INTO_VIEW_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid scroll_into_view options: {}", e)))?;
+    let result = match bridge::eval(&window, &script, timeout).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to scroll element into view: {}", e)),
+            });
+        }
+    };
+    let response: ScrollIntoViewResponse = serde_json::from_value(result)
+        .map_err(|e| Error::Anyhow(format!("Unexpected scroll result: {}", e)))?;
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+fn validate(request: &ScrollIntoViewRequest) -> Result<(), Error> {
+    for (name, alignment) in [("block", &request.block), ("inline", &request.inline)] {
+        if !matches!(alignment.as_str(), "start" | "center" | "end" | "nearest") {
+            return Err(Error::Anyhow(format!(
+                "Unsupported {} alignment: {} (expected start, center, end or nearest)",
+                name, alignment
+            )));
+        }
+    }
+    if !matches!(request.behavior.as_str(), "instant" | "smooth") {
+        return Err(Error::Anyhow(format!(
+            "Unsupported scroll behavior: {} (expected instant or smooth)",
+            request.behavior
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,218 @@
+/// Defines `findElement(selectorType, selectorValue)` for scripts run with `bridge::eval`.
+///
+/// Selector types are those of the guest bindings (`id`, `class`, `tag`, `text`) plus `css`,
+/// `xpath`, `aria-label`, `role` (an ARIA role, optionally with an accessible name, as in
+/// `button[name="Save"]`) and `label` (form controls by the text of their label). Every type
+/// but `xpath` also finds elements inside open shadow roots, and `css` selectors take a `>>>`
+/// combinator to step into the shadow root of the element matched so far.
+/// Also defines `roleOf(el)` and `accessibleName(el)`.
+/// Throws when nothing matches, so the error reaches the caller.
+pub const FIND_ELEMENT_JS: &str = r#"
+const LANDMARKS = { HEADER: 'banner', NAV: 'navigation', MAIN: 'main', FOOTER: 'contentinfo', ASIDE: 'complementary', FORM: 'form', DIALOG: 'dialog' };
+const INPUT_ROLES = { checkbox: 'checkbox', radio: 'radio', range: 'slider', button: 'button', submit: 'button', reset: 'button', image: 'button', search: 'searchbox' };
+const roleOf = (el) => {
+    const explicit = el.getAttribute('role');
+    if (explicit) return explicit.split(' ')[0];
+    const tag = el.tagName;
+    if (/^H[1-6]$/.test(tag)) return 'heading';
+    if (tag === 'A') return el.hasAttribute('href') ? 'link' : null;
+    if (tag === 'BUTTON' || tag === 'SUMMARY') return 'button';
+    if (tag === 'INPUT') return el.type === 'hidden' ? null : (INPUT_ROLES[el.type] || 'textbox');
+    if (tag === 'TEXTAREA') return 'textbox';
+    if (tag === 'SELECT') return el.multiple ? 'listbox' : 'combobox';
+    if (tag === 'OPTION') return 'option';
+    if (tag === 'IMG') return el.alt ? 'img' : null;
+    if (el.isContentEditable && !(el.parentElement && el.parentElement.isContentEditable)) return 'textbox';
+    return LANDMARKS[tag] || null;
+};
+const squeeze = (text) => (text || '').replace(/\s+/g, ' ').trim();
+// Labelling elements sit in the same document or shadow root as the labelled one
+const labelledByText = (el) => (el.getAttribute('aria-labelledby') || '').split(' ')
+    .map((id) => id && el.getRootNode().getElementById(id)).filter(Boolean)
+    .map((labelling) => labelling.textContent).join(' ');
+const accessibleName = (el) => {
+    const labelledBy = squeeze(labelledByText(el));
+    if (labelledBy) return labelledBy;
+    const label = el.getAttribute('aria-label')
+        || (el.labels && el.labels.length ? Array.from(el.labels).map((l) => l.textContent).join(' ') : '')
+        || el.getAttribute('alt')
+        || el.getAttribute('placeholder')
+        || el.getAttribute('title');
+    if (label) return squeeze(label);
+    if (el.tagName === 'INPUT' && /^(button|submit|reset)$/.test(el.type)) return squeeze(el.value);
+    if (LANDMARKS[el.tagName] || /^(INPUT|SELECT|TEXTAREA)$/.test(el.tagName)) return '';
+    return squeeze(el.innerText);
+};
+// Exact match first, then case-insensitive substring
+const matchText = (elements, text, textsOf) => elements.find((el) => textsOf(el).includes(text))
+    || elements.find((el) => textsOf(el).some((candidate) => candidate.toLowerCase().includes(text.toLowerCase())))
+    || null;
+// Open shadow roots under root, nested ones included
+const shadowRoots = (root) => {
+    const roots = [];
+    const walk = (node) => {
+        for (const el of node.querySelectorAll('*')) {
+            if (el.shadowRoot) {
+                roots.push(el.shadowRoot);
+                walk(el.shadowRoot);
+            }
+        }
+    };
+    walk(root);
+    return roots;
+};
+const deepQuery = (root, selector) => root.querySelector(selector)
+    || shadowRoots(root).map((shadow) => shadow.querySelector(selector)).find(Boolean)
+    || null;
+const deepQueryAll = (root, selector) => [root, ...shadowRoots(root)]
+    .flatMap((scope) => Array.from(scope.querySelectorAll(selector)));
+const findByCss = (selector) => {
+    let scope = document;
+    let element = null;
+    for (const part of selector.split('>>>').map((part) => part.trim())) {
+        element = deepQuery(scope, part);
+        if (!element) return null;
+        scope = element.shadowRoot || element;
+    }
+    return element;
+};
+const findByText = (text) => {
+    const all = deepQueryAll(document, '*');
+    const labels = (el) => [
+        el.placeholder,
+        el.getAttribute('title'),
+        el.getAttribute('aria-label'),
+    ].filter((label) => typeof label === 'string' && label !== '');
+    return all.find((el) => (el.textContent && el.textContent.trim() === text) || labels(el).includes(text))
+        // Partial matches take the innermost element, not its ancestors
+        || all.find((el) => labels(el).some((label) => label.includes(text))
+            || (el.textContent && el.textContent.includes(text)
+                && !Array.from(el.children).some((child) => child.textContent.includes(text))))
+        || null;
+};
+const findByXPath = (expression) => {
+    const node = document.evaluate(expression, document, null, XPathResult.FIRST_ORDERED_NODE_TYPE, null).singleNodeValue;
+    // Text and attribute nodes stand for the element holding them
+    if (node && node.nodeType !== Node.ELEMENT_NODE) return node.parentElement || node.ownerElement || null;
+    return node;
+};
+const findByAriaLabel = (label) => {
+    const all = deepQueryAll(document, '[aria-label], [aria-labelledby]');
+    return matchText(all, label, (el) => [el.getAttribute('aria-label') || squeeze(labelledByText(el))]);
+};
+// `role` or `role[name="accessible name"]`, among the rendered elements
+const findByRole = (selector) => {
+    const match = /^\s*([\w-]+)\s*(?:\[\s*name\s*=\s*(?:"((?:[^"\\]|\\.)*)"|'((?:[^'\\]|\\.)*)'|([^\]]*?))\s*\])?\s*$/.exec(selector);
+    if (!match) throw new Error(`Invalid role selector: ${selector}, expected role or role[name="..."]`);
+    const [, role, doubleQuoted, singleQuoted, bare] = match;
+    const name = [doubleQuoted, singleQuoted, bare].find((part) => part !== undefined);
+    const candidates = deepQueryAll(document, '*').filter((el) => roleOf(el) === role
+        && el.getAttribute('aria-hidden') !== 'true'
+        && el.getClientRects().length > 0
+        && el.ownerDocument.defaultView.getComputedStyle(el).visibility !== 'hidden');
+    if (name === undefined) return candidates[0] || null;
+    return matchText(candidates, name.replace(/\\(.)/g, '$1'), (el) => [accessibleName(el)]);
+};
+// Form controls by the text of a label element, aria-label or aria-labelledby
+const findByLabel = (text) => {
+    const controls = deepQueryAll(document, 'input:not([type=hidden]), textarea, select, button, meter, output, progress, [contenteditable], [aria-label], [aria-labelledby]');
+    return matchText(controls, text, (el) => [
+        ...(el.labels ? Array.from(el.labels).map((label) => squeeze(label.textContent)) : []),
+        squeeze(el.getAttribute('aria-label')),
+        squeeze(labelledByText(el)),
+    ].filter(Boolean));
+};
+const findElement = (selectorType, selectorValue) => {
+    let element;
+    switch (selectorType) {
+        case 'id': element = document.getElementById(selectorValue) || deepQuery(document, `#${CSS.escape(selectorValue)}`); break;
+        case 'class': element = document.getElementsByClassName(selectorValue)[0] || deepQuery(document, `.${CSS.escape(selectorValue)}`); break;
+        case 'tag': element = document.getElementsByTagName(selectorValue)[0] || deepQuery(document, CSS.escape(selectorValue)); break;
+        case 'text': element = findByText(selectorValue); break;
+        case 'css': element = findByCss(selectorValue); break;
+        case 'xpath': element = findByXPath(selectorValue); break;
+        case 'aria-label': element = findByAriaLabel(selectorValue); break;
+        case 'role': element = findByRole(selectorValue); break;
+        case 'label': element = findByLabel(selectorValue); break;
+        default: throw new Error(`Unsupported selector type: ${selectorType}`);
+    }
+    if (!element) throw new Error(`Element with ${selectorType}="${selectorValue}" not found`);
+    return element;
+};
+"#;
+
+/// Defines `frame`, the same-origin iframe named by `options.frame_selector` or the page
+/// itself, and shadows `document` with the frame's document for the rest of the script.
+///
+/// `frame.offsetX` and `frame.offsetY` locate the frame's viewport in the page's, so rects of
+/// frame elements can be moved to page coordinates. Throws when a frame is missing or
+/// cross-origin.
+pub const FRAME_JS: &str = r#"
+const resolveFrame = (frameSelector) => {
+    let frameDocument = window.document;
+    let offsetX = 0;
+    let offsetY = 0;
+    for (const selector of frameSelector == null ? [] : [].concat(frameSelector)) {
+        const element = frameDocument.querySelector(selector);
+        if (!element || (element.tagName !== 'IFRAME' && element.tagName !== 'FRAME')) {
+            throw new Error(`No frame matches ${selector}`);
+        }
+        if (!element.contentDocument) throw new Error(`Frame ${selector} is cross-origin or not loaded`);
+        // The frame's viewport starts inside its border and padding
+        const rect = element.getBoundingClientRect();
+        const style = frameDocument.defaultView.getComputedStyle(element);
+        offsetX += rect.left + element.clientLeft + parseFloat(style.paddingLeft);
+        offsetY += rect.top + element.clientTop + parseFloat(style.paddingTop);
+        frameDocument = element.contentDocument;
+    }
+    return { document: frameDocument, window: frameDocument.defaultView, offsetX, offsetY };
+};
+const frame = resolveFrame(options.frame_selector);
+const document = frame.document;
+"#;
+
+/// Defines `scrollIntoViewIfNeeded(element, { block, inline, behavior, force })`, which
+/// scrolls `element` into view unless it is already fully visible in the window and in its
+/// scroll containers, then waits for smooth scrolling to settle. Resolves to whether it
+/// scrolled. Expects `frame` from [`FRAME_JS`].
+pub const SCROLL_INTO_VIEW_JS: &str = r#"
+const isClipped = (element) => {
+    const rect = element.getBoundingClientRect();
+    const view = element.ownerDocument.defaultView;
+    const outside = (box) => rect.top < box.top || rect.left < box.left || rect.bottom > box.bottom || rect.right > box.right;
+    for (let node = element.parentElement || element.getRootNode().host; node; node = node.parentElement || node.getRootNode().host) {
+        const style = view.getComputedStyle(node);
+        if (/auto|scroll|hidden|clip/.test(style.overflowX + style.overflowY) && outside(node.getBoundingClientRect())) return true;
+    }
+    if (outside({ top: 0, left: 0, bottom: view.innerHeight, right: view.innerWidth })) return true;
+    return outside({
+        top: -frame.offsetY, left: -frame.offsetX,
+        bottom: window.innerHeight - frame.offsetY, right: window.innerWidth - frame.offsetX,
+    });
+};
+const scrollIntoViewIfNeeded = async (element, { block = 'center', inline = 'nearest', behavior = 'instant', force = false } = {}) => {
+    if (!force && !isClipped(element)) return false;
+    element.scrollIntoView({ block, inline, behavior });
+    // Smooth scrolling and scroll snapping move the element over several frames; timers
+    // stand in for animation frames in windows that are not painted
+    const nextFrame = () => new Promise((resolve) => { requestAnimationFrame(resolve); setTimeout(resolve, 50); });
+    let last = null;
+    for (let stable = 0, frames = 0; stable < 2 && frames < 120; frames++) {
+        await nextFrame();
+        const { x, y } = element.getBoundingClientRect();
+        stable = last && last.x === x && last.y === y ? stable + 1 : 0;
+        last = { x, y };
+    }
+    return true;
+};
+"#;
+
+/// Prefix `body` with the `options` constant, the frame it targets and the element lookup
+/// and scrolling helpers.
+pub fn element_script(options: &impl serde::Serialize, body: &str) -> Result<String, String> {
+    let options = serde_json::to_string(options).map_err(|e| e.to_string())?;
+    Ok(format!(
+        "const options = {};\n{}\n{}\n{}\n{}",
+        options, FRAME_JS, FIND_ELEMENT_JS, SCROLL_INTO_VIEW_JS, body
+    ))
+}
@@ -0,0 +1,127 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::ManageStoreRequest;
+use crate::socket_server::SocketResponse;
+
+pub fn handle_manage_store<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ManageStoreRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for manage_store: {}", e)))?;
+
+    #[cfg(feature = "store")]
+    let result = plugin_store::manage(app, request);
+    #[cfg(not(feature = "store"))]
+    let result: crate::Result<crate::models::StoreResponse> = {
+        let _ = (app, request);
+        Err(Error::Anyhow(
+            "manage_store requires the store feature of tauri-plugin-mcp".to_string(),
+        ))
+    };
+
+    match result {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+#[cfg(feature = "store")]
+mod plugin_store {
+    use serde_json::Value;
+    use std::panic::{AssertUnwindSafe, catch_unwind};
+    use tauri::{AppHandle, Runtime};
+    use tauri_plugin_store::{StoreExt, resolve_store_path};
+
+    use crate::TauriMcpExt;
+    use crate::error::Error;
+    use crate::models::{ManageStoreRequest, StoreAction, StoreResponse};
+
+    pub(super) fn manage<R: Runtime>(
+        app: &AppHandle<R>,
+        request: ManageStoreRequest,
+    ) -> crate::Result<StoreResponse> {
+        let resolve = |path: &std::path::Path| {
+            resolve_store_path(app, path)
+                .map_err(|e| Error::Anyhow(format!("Invalid store path: {}", e)))
+        };
+        let path = resolve(request.path.as_ref())?;
+        let mut allowed = false;
+        for allowed_path in app.tauri_mcp().allowed_stores() {
+            allowed |= resolve(allowed_path)? == path;
+        }
+        if !allowed {
+            return Err(Error::Anyhow(format!(
+                "Store {} is not allowed, add it with PluginConfig::allow_store",
+                request.path
+            )));
+        }
+
+        // The store plugin panics when it is not registered
+        let store = catch_unwind(AssertUnwindSafe(|| app.store(&path)))
+            .map_err(|_| Error::Anyhow("tauri-plugin-store is not registered".to_string()))?
+            .map_err(|e| Error::Anyhow(format!("Failed to open store: {}", e)))?;
+        let key = || {
+            request.key.as_deref().ok_or_else(|| {
+                Error::Anyhow(format!("{:?} needs a key", request.action).to_lowercase())
+            })
+        };
+
+        let mut response = StoreResponse {
+            path: path.display().to_string(),
+            value: None,
+            entries: None,
+        };
+        match request.action {
+            StoreAction::Get => match &request.key {
+                Some(key) => response.value = Some(store.get(key).unwrap_or(Value::Null)),
+                None => response.entries = Some(store.entries().into_iter().collect()),
+            },
+            StoreAction::Set => {
+                let value = request
+                    .value
+                    .clone()
+                    .ok_or_else(|| Error::Anyhow("set needs a value".to_string()))?;
+                store.set(key()?, value.clone());
+                response.value = Some(value);
+            }
+            StoreAction::Delete => {
+                let key = key()?;
+                response.value = Some(store.get(key).unwrap_or(Value::Null));
+                store.delete(key);
+            }
+            StoreAction::Clear => {
+                store.clear();
+                response.entries = Some(Default::default());
+            }
+            StoreAction::Reload => {
+                store
+                    .reload()
+                    .map_err(|e| Error::Anyhow(format!("Failed to reload store: {}", e)))?;
+                response.entries = Some(store.entries().into_iter().collect());
+            }
+        }
+
+        let writes = !matches!(request.action, StoreAction::Get | StoreAction::Reload);
+        if writes && request.save {
+            store
+                .save()
+                .map_err(|e| Error::Anyhow(format!("Failed to save store: {}", e)))?;
+        }
+        Ok(response)
+    }
+}
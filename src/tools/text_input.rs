@@ -1,5 +1,6 @@
 use serde_json::Value;
 use tauri::{AppHandle, Runtime};
+use tokio_util::sync::CancellationToken;
 
 use crate::TauriMcpExt;
 use crate::error::Error;
@@ -9,13 +10,17 @@ use crate::socket_server::SocketResponse;
 pub async fn handle_simulate_text_input<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    cancel: &CancellationToken,
 ) -> Result<SocketResponse, Error> {
     // Parse the payload
     let params: TextInputRequest = serde_json::from_value(payload)
         .map_err(|e| Error::Anyhow(format!("Invalid payload for simulateTextInput: {}", e)))?;
 
     // Call the async method
-    let result = app.tauri_mcp().simulate_text_input_async(params).await;
+    let result = app
+        .tauri_mcp()
+        .simulate_text_input_cancellable(params, cancel)
+        .await;
 
     match result {
         Ok(response) => {
@@ -0,0 +1,232 @@
+use serde_json::{Value, json};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{CoordinatePoint, TouchGesture, TouchRequest, TouchResponse};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::element_script;
+
+/// Performs `options.gesture` from the center of the element found by `findElement`, or
+/// from `options.x`, `options.y`, with touch pointer events and, when the webview has
+/// them, touch events. Fingers stay on the element they went down on, as touch input is
+/// captured by its first target. A tap the page does not cancel is followed by the mouse
+/// events and click browsers emulate.
+const TOUCH_SCRIPT: &str = r#"
+let origin;
+if (options.selector_type && options.selector_value) {
+    const element = findElement(options.selector_type, options.selector_value);
+    await scrollIntoViewIfNeeded(element, { block: 'center', inline: 'center' });
+    const rect = element.getBoundingClientRect();
+    origin = { x: rect.left + rect.width / 2, y: rect.top + rect.height / 2 };
+} else {
+    origin = { x: options.x, y: options.y };
+}
+const target = document.elementFromPoint(origin.x, origin.y);
+if (!target) throw new Error(`No element at (${origin.x}, ${origin.y})`);
+
+const touchEvents = typeof TouchEvent === 'function' && typeof Touch === 'function';
+const base = { bubbles: true, cancelable: true, composed: true, view: window };
+const sleep = (ms) => new Promise((resolve) => setTimeout(resolve, ms));
+const fingers = new Map();
+let events = 0;
+const touchAt = (id, point) => new Touch({
+    identifier: id, target,
+    clientX: point.x, clientY: point.y,
+    pageX: point.x + window.scrollX, pageY: point.y + window.scrollY,
+    screenX: window.screenX + point.x, screenY: window.screenY + point.y,
+    radiusX: 10, radiusY: 10, force: 0.5,
+});
+// Moves finger `id` through `phase` ("start", "move" or "end"), false when the page
+// cancelled the touch event
+const send = (phase, id, point) => {
+    if (phase === 'end') fingers.delete(id);
+    else fingers.set(id, point);
+    const type = { start: 'pointerdown', move: 'pointermove', end: 'pointerup' }[phase];
+    target.dispatchEvent(new PointerEvent(type, {
+        ...base,
+        pointerId: 2 + id, pointerType: 'touch', isPrimary: id === 0,
+        clientX: point.x, clientY: point.y,
+        screenX: window.screenX + point.x, screenY: window.screenY + point.y,
+        width: 20, height: 20, pressure: phase === 'end' ? 0 : 0.5,
+        button: phase === 'move' ? -1 : 0, buttons: phase === 'end' ? 0 : 1,
+    }));
+    events += 1;
+    if (!touchEvents) return true;
+    const touches = Array.from(fingers, ([finger, at]) => touchAt(finger, at));
+    events += 1;
+    return target.dispatchEvent(new TouchEvent(`touch${phase}`, {
+        ...base, touches, targetTouches: touches, changedTouches: [touchAt(id, point)],
+    }));
+};
+// Calls `frame` with the progress of the gesture, about once per frame
+const animate = async (duration, frame) => {
+    const steps = Math.max(1, Math.round(duration / 16));
+    for (let step = 1; step <= steps; step++) {
+        await sleep(duration / steps);
+        frame(step / steps);
+    }
+};
+const mouse = (type) => target.dispatchEvent(new MouseEvent(type, {
+    ...base, clientX: origin.x, clientY: origin.y, button: 0, detail: 1,
+}));
+
+switch (options.gesture) {
+    case 'tap': {
+        send('start', 0, origin);
+        await sleep(options.duration);
+        if (send('end', 0, origin)) {
+            mouse('mousemove');
+            if (mouse('mousedown')) {
+                const focusable = target.closest('input, select, textarea, button, a[href], [tabindex], [contenteditable]');
+                if (focusable && typeof focusable.focus === 'function') focusable.focus();
+            }
+            mouse('mouseup');
+            mouse('click');
+        }
+        break;
+    }
+    case 'long_press': {
+        send('start', 0, origin);
+        await sleep(options.duration);
+        mouse('contextmenu');
+        send('end', 0, origin);
+        break;
+    }
+    case 'swipe': {
+        let point = origin;
+        send('start', 0, origin);
+        await animate(options.duration, (progress) => {
+            point = { x: origin.x + options.dx * progress, y: origin.y + options.dy * progress };
+            send('move', 0, point);
+        });
+        send('end', 0, point);
+        break;
+    }
+    case 'pinch': {
+        const at = (spread) => [
+            { x: origin.x - spread / 2, y: origin.y },
+            { x: origin.x + spread / 2, y: origin.y },
+        ];
+        let points = at(options.spread);
+        points.forEach((point, id) => send('start', id, point));
+        await animate(options.duration, (progress) => {
+            points = at(options.spread * (1 + (options.scale - 1) * progress));
+            points.forEach((point, id) => send('move', id, point));
+        });
+        points.forEach((point, id) => send('end', id, point));
+        break;
+    }
+    default:
+        throw new Error(`Unsupported gesture: ${options.gesture}`);
+}
+return { target: { tag: target.tagName.toLowerCase(), id: target.id }, origin, events, touchEvents };
+"#;
+
+/// Default initial distance between the two fingers of a pinch, in CSS pixels.
+const DEFAULT_SPREAD: f64 = 100.0;
+
+pub async fn handle_simulate_touch<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: TouchRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for simulate_touch: {}", e)))?;
+    let duration = gesture_duration(&request)?;
+    let has_selector = request.selector_type.is_some() && request.selector_value.is_some();
+    if !has_selector && (request.x.is_none() || request.y.is_none()) {
+        return Err(Error::Anyhow(
+            "Invalid payload for simulate_touch: give selector_type and selector_value, or x and y"
+                .to_string(),
+        ));
+    }
+    let scale = request.scale.unwrap_or(1.0);
+    if request.gesture == TouchGesture::Pinch && (request.scale.is_none() || scale <= 0.0) {
+        return Err(Error::Anyhow(
+            "Invalid payload for simulate_touch: a pinch needs a scale above 0".to_string(),
+        ));
+    }
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let options = json!({
+        "gesture": request.gesture,
+        "selector_type": request.selector_type,
+        "selector_value": request.selector_value,
+        "x": request.x,
+        "y": request.y,
+        "dx": request.dx,
+        "dy": request.dy,
+        "scale": scale,
+        "spread": request.spread.unwrap_or(DEFAULT_SPREAD),
+        "duration": duration.as_millis() as u64,
+    });
+    let script = element_script(&options, TOUCH_SCRIPT)
+        .map_err(|e| Error::Anyhow(format!("Invalid simulate_touch options: {}", e)))?;
+
+    let start_time = Instant::now();
+    // The gesture itself runs in the page, on top of the usual script time
+    let result = match bridge::eval(&window, &script, timeout + duration).await {
+        Ok(result) => result,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to simulate touch: {}", e)),
+            });
+        }
+    };
+
+    let origin = result
+        .get("origin")
+        .cloned()
+        .and_then(|origin| serde_json::from_value::<CoordinatePoint>(origin).ok())
+        .unwrap_or(CoordinatePoint {
+            x: request.x.unwrap_or_default(),
+            y: request.y.unwrap_or_default(),
+        });
+    let response = TouchResponse {
+        gesture: request.gesture,
+        target: result.get("target").cloned().unwrap_or(Value::Null),
+        origin,
+        events: result.get("events").and_then(Value::as_u64).unwrap_or(0) as u32,
+        touch_events: result
+            .get("touchEvents")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+        duration_ms: start_time.elapsed().as_millis() as u64,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
+
+/// How long the fingers of the gesture stay down.
+fn gesture_duration(request: &TouchRequest) -> Result<Duration, Error> {
+    if let Some(duration_ms) = request.duration_ms {
+        return Ok(Duration::from_millis(duration_ms));
+    }
+    let duration_ms = match request.gesture {
+        TouchGesture::Tap => 50,
+        TouchGesture::LongPress => 600,
+        TouchGesture::Pinch => 400,
+        TouchGesture::Swipe => match request.velocity {
+            Some(velocity) if velocity > 0.0 => {
+                (request.dx.hypot(request.dy) / velocity).round() as u64
+            }
+            Some(_) => {
+                return Err(Error::Anyhow(
+                    "Invalid payload for simulate_touch: velocity must be above 0".to_string(),
+                ));
+            }
+            None => 300,
+        },
+    };
+    Ok(Duration::from_millis(duration_ms))
+}
@@ -0,0 +1,66 @@
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::TauriMcpExt;
+use crate::error::Error;
+use crate::models::{StartTraceRequest, StopTraceRequest, StopTraceResponse};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_start_trace<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: StartTraceRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for start_trace: {}", e)))?;
+
+    match app.tauri_mcp().tracer().start(
+        request.name.clone(),
+        request.window_label.clone(),
+        request.include_dom,
+        request.include_screenshots,
+    ) {
+        Ok(()) => Ok(SocketResponse {
+            success: true,
+            data: Some(serde_json::json!({
+                "tracing": true,
+                "windowLabel": request.window_label,
+            })),
+            error: None,
+        }),
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+pub fn handle_stop_trace<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: StopTraceRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for stop_trace: {}", e)))?;
+
+    let stopped = match app.tauri_mcp().tracer().stop(app, request.discard) {
+        Ok(stopped) => stopped,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(e.to_string()),
+            });
+        }
+    };
+    let response = StopTraceResponse {
+        manifest: stopped.manifest,
+        artifact: stopped.artifact,
+    };
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
@@ -0,0 +1,138 @@
+use serde::Serialize;
+use serde_json::Value;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{ClickTrayRequest, TriggerTrayMenuItemRequest};
+use crate::socket_server::SocketResponse;
+
+pub fn handle_list_trays<R: Runtime>(
+    app: &AppHandle<R>,
+    _payload: Value,
+) -> Result<SocketResponse, Error> {
+    respond(trays::list(app))
+}
+
+pub fn handle_trigger_tray_menu_item<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: TriggerTrayMenuItemRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for trigger_tray_menu_item: {}", e)))?;
+    respond(trays::trigger_menu_item(app, request))
+}
+
+pub fn handle_click_tray<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+) -> Result<SocketResponse, Error> {
+    let request: ClickTrayRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for click_tray: {}", e)))?;
+    respond(trays::click(app, request))
+}
+
+fn respond<T: Serialize>(result: crate::Result<T>) -> Result<SocketResponse, Error> {
+    match result {
+        Ok(response) => {
+            let data = serde_json::to_value(response)
+                .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+            Ok(SocketResponse {
+                success: true,
+                data: Some(data),
+                error: None,
+            })
+        }
+        Err(e) => Ok(SocketResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
+#[cfg(all(desktop, feature = "tray"))]
+mod trays {
+    use serde_json::Value;
+    use tauri::tray::MouseButton;
+    use tauri::{AppHandle, Runtime};
+
+    use crate::TauriMcpExt;
+    use crate::error::Error;
+    use crate::menu::MenuEntry;
+    use crate::models::{ClickTrayRequest, TriggerTrayMenuItemRequest};
+    use crate::tray::TrayInfo;
+
+    pub(super) fn list<R: Runtime>(app: &AppHandle<R>) -> crate::Result<Vec<TrayInfo>> {
+        app.tauri_mcp().trays().list()
+    }
+
+    pub(super) fn trigger_menu_item<R: Runtime>(
+        app: &AppHandle<R>,
+        request: TriggerTrayMenuItemRequest,
+    ) -> crate::Result<MenuEntry> {
+        app.tauri_mcp()
+            .trays()
+            .trigger_menu_item(request.tray_id.as_deref(), &request.item_id)
+    }
+
+    pub(super) fn click<R: Runtime>(
+        app: &AppHandle<R>,
+        request: ClickTrayRequest,
+    ) -> crate::Result<Value> {
+        let button = match request.button.as_str() {
+            "left" => MouseButton::Left,
+            "middle" => MouseButton::Middle,
+            "right" => MouseButton::Right,
+            other => {
+                return Err(Error::Anyhow(format!(
+                    "Unknown button {}, expected left, middle or right",
+                    other
+                )));
+            }
+        };
+        app.tauri_mcp()
+            .trays()
+            .click(&request.tray_id, button, request.double)?;
+        Ok(serde_json::json!({
+            "trayId": request.tray_id,
+            "button": request.button,
+            "double": request.double,
+        }))
+    }
+}
+
+#[cfg(not(all(desktop, feature = "tray")))]
+mod trays {
+    use serde_json::Value;
+    use tauri::{AppHandle, Runtime};
+
+    use crate::error::Error;
+    use crate::menu::MenuEntry;
+    use crate::models::{ClickTrayRequest, TriggerTrayMenuItemRequest};
+    use crate::tray::TrayInfo;
+
+    fn unavailable(command: &str) -> Error {
+        Error::Anyhow(format!(
+            "{} requires the tray feature of tauri-plugin-mcp, on desktop",
+            command
+        ))
+    }
+
+    pub(super) fn list<R: Runtime>(_app: &AppHandle<R>) -> crate::Result<Vec<TrayInfo>> {
+        Err(unavailable("list_trays"))
+    }
+
+    pub(super) fn trigger_menu_item<R: Runtime>(
+        _app: &AppHandle<R>,
+        _request: TriggerTrayMenuItemRequest,
+    ) -> crate::Result<MenuEntry> {
+        Err(unavailable("trigger_tray_menu_item"))
+    }
+
+    pub(super) fn click<R: Runtime>(
+        _app: &AppHandle<R>,
+        _request: ClickTrayRequest,
+    ) -> crate::Result<Value> {
+        Err(unavailable("click_tray"))
+    }
+}
@@ -0,0 +1,164 @@
+use base64::Engine;
+use serde_json::Value;
+use std::time::Duration;
+use tauri::{AppHandle, Runtime};
+
+use crate::error::Error;
+use crate::models::{UiSnapshotRequest, UiSnapshotResponse, ViewportScreenshot};
+use crate::platform::snapshot::{self, CaptureOptions, ImageFormat};
+use crate::socket_server::SocketResponse;
+use crate::tools::bridge;
+use crate::tools::selector::FIND_ELEMENT_JS;
+
+/// Describes the page in one pass: its URL and title, the focused element, and an outline
+/// of the landmarks, headings and controls a user can see, with their role, accessible
+/// name and state, indented by nesting. Expects an `options` constant holding the
+/// [`UiSnapshotRequest`], and [`FIND_ELEMENT_JS`] for roles and names.
+const UI_SNAPSHOT_SCRIPT: &str = r#"
+const clip = (text) => {
+    const squeezed = squeeze(text);
+    return squeezed.length > 80 ? `${squeezed.slice(0, 79)}…` : squeezed;
+};
+const nameOf = (el) => clip(accessibleName(el));
+const stateOf = (el, role) => {
+    const states = [];
+    if (el === document.activeElement) states.push('focused');
+    if (el.disabled || el.getAttribute('aria-disabled') === 'true') states.push('disabled');
+    if (role === 'heading') states.push(`level=${/^H[1-6]$/.test(el.tagName) ? el.tagName[1] : el.getAttribute('aria-level') || 2}`);
+    if (role === 'checkbox' || role === 'radio' || role === 'switch') {
+        const checked = 'checked' in el ? el.checked : el.getAttribute('aria-checked') === 'true';
+        states.push(checked ? 'checked' : 'unchecked');
+    }
+    const expanded = el.getAttribute('aria-expanded');
+    if (expanded) states.push(expanded === 'true' ? 'expanded' : 'collapsed');
+    if (el.required) states.push('required');
+    if (el.tagName === 'SELECT' && el.selectedOptions.length) {
+        states.push(`value="${clip(Array.from(el.selectedOptions).map((o) => o.text).join(', '))}"`);
+    } else if (role === 'textbox' || role === 'searchbox' || role === 'slider') {
+        const value = el.isContentEditable ? el.innerText : el.value;
+        if (value) states.push(el.type === 'password' ? 'value=<hidden>' : `value="${clip(value)}"`);
+    }
+    return states.length ? ` [${states.join(', ')}]` : '';
+};
+const isShown = (el) => {
+    if (el.matches('[data-tauri-mcp-overlay], [data-tauri-mcp-handoff]')) return false;
+    if (el.getAttribute('aria-hidden') === 'true') return false;
+    const style = getComputedStyle(el);
+    if (style.display === 'contents') return true;
+    return el.getClientRects().length > 0 && style.visibility !== 'hidden';
+};
+const cssEscape = (value) => window.CSS && CSS.escape ? CSS.escape(value) : value.replace(/["\\]/g, '\\$&');
+const cssPath = (element) => {
+    const parts = [];
+    for (let node = element; node && node.nodeType === 1 && node !== document.documentElement; node = node.parentElement) {
+        if (node.id) {
+            parts.unshift(`#${cssEscape(node.id)}`);
+            break;
+        }
+        const tag = node.tagName.toLowerCase();
+        const siblings = node.parentElement
+            ? Array.from(node.parentElement.children).filter((child) => child.tagName === node.tagName)
+            : [];
+        parts.unshift(siblings.length > 1 ? `${tag}:nth-of-type(${siblings.indexOf(node) + 1})` : tag);
+    }
+    return parts.join(' > ');
+};
+
+const lines = [];
+let nodeCount = 0;
+const walk = (parent, depth) => {
+    for (const el of parent.children) {
+        if (el.tagName === 'SCRIPT' || el.tagName === 'STYLE' || !isShown(el)) continue;
+        const role = roleOf(el);
+        if (!role) {
+            walk(el, depth);
+            continue;
+        }
+        nodeCount += 1;
+        if (lines.length < options.max_nodes) {
+            const name = nameOf(el);
+            lines.push(`${'  '.repeat(depth)}- ${role}${name ? ` "${name.replace(/"/g, '\\"')}"` : ''}${stateOf(el, role)}`);
+        }
+        // Controls are leaves, their content is their name
+        if (!/^(button|link|textbox|searchbox|combobox|listbox|checkbox|radio|slider|img|heading)$/.test(role)) {
+            walk(el, depth + 1);
+        }
+    }
+};
+if (document.body) walk(document.body, 0);
+
+const active = document.activeElement;
+const focused = active && active !== document.body && active !== document.documentElement
+    ? {
+        tag: active.tagName.toLowerCase(),
+        role: roleOf(active),
+        name: nameOf(active),
+        selector: cssPath(active),
+    }
+    : null;
+return {
+    url: location.href,
+    title: document.title,
+    focused,
+    outline: lines.join('\n'),
+    nodeCount,
+    truncated: nodeCount > lines.length,
+};
+"#;
+
+pub async fn handle_get_ui_snapshot<R: Runtime>(
+    app: &AppHandle<R>,
+    payload: Value,
+    timeout: Duration,
+) -> Result<SocketResponse, Error> {
+    let request: UiSnapshotRequest = serde_json::from_value(payload)
+        .map_err(|e| Error::Anyhow(format!("Invalid payload for get_ui_snapshot: {}", e)))?;
+
+    let window = crate::windows::resolve_window(app, &request.window_label)?;
+
+    let options = serde_json::to_string(&request)
+        .map_err(|e| Error::Anyhow(format!("Invalid get_ui_snapshot options: {}", e)))?;
+    let script = format!(
+        "const options = {};\n{}\n{}",
+        options, FIND_ELEMENT_JS, UI_SNAPSHOT_SCRIPT
+    );
+    let snapshot = match bridge::eval(&window, &script, timeout).await {
+        Ok(snapshot) => snapshot,
+        Err(e) => {
+            return Ok(SocketResponse {
+                success: false,
+                data: None,
+                error: Some(format!("Failed to take UI snapshot: {}", e)),
+            });
+        }
+    };
+    let mut response: UiSnapshotResponse = serde_json::from_value(snapshot)
+        .map_err(|e| Error::Anyhow(format!("Invalid UI snapshot: {}", e)))?;
+
+    if request.screenshot {
+        let options = CaptureOptions {
+            clip: None,
+            max_width: Some(request.screenshot_max_width),
+            format: ImageFormat::Jpeg(request.screenshot_quality),
+        };
+        match snapshot::capture(&window, options, timeout).await {
+            Ok(image) => {
+                response.screenshot = Some(ViewportScreenshot {
+                    mime_type: options.format.mime_type().to_string(),
+                    data: base64::engine::general_purpose::STANDARD.encode(image.data),
+                    width: image.width,
+                    height: image.height,
+                });
+            }
+            Err(e) => response.screenshot_error = Some(e.to_string()),
+        }
+    }
+
+    let data = serde_json::to_value(response)
+        .map_err(|e| Error::Anyhow(format!("Failed to serialize response: {}", e)))?;
+    Ok(SocketResponse {
+        success: true,
+        data: Some(data),
+        error: None,
+    })
+}
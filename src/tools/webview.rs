@@ -1,8 +1,61 @@
-use serde::{Deserialize, Serialize, Serializer}; // Add Deserialize for parsing payload
+use serde::{Serialize, Serializer};
 use serde_json::Value;
 use std::fmt;
-use std::sync::mpsc;
-use tauri::{AppHandle, Error as TauriError, Listener, Manager, Runtime, WebviewWindow};
+use std::time::Duration;
+use tauri::{AppHandle, Error as TauriError, Runtime, WebviewWindow};
+
+use crate::models::{DomSnapshotOptions, ElementPositionRequest, SendTextToElementRequest};
+use crate::tools::bridge::{self, BridgeError};
+use crate::tools::selector::{FRAME_JS, element_script};
+
+/// Returns the center of the element in document CSS pixels, as `simulate_mouse_movement`
+/// takes them, and clicks it with DOM events when `options.should_click` is set. Scrolls it
+/// into view first when `options.scroll_into_view` is set. Elements of an iframe are located
+/// in the page, their events use the frame's coordinates.
+const ELEMENT_POSITION_SCRIPT: &str = r#"
+const element = findElement(options.selector_type, options.selector_value);
+const scrolled = options.scroll_into_view ? await scrollIntoViewIfNeeded(element, { block: 'center', inline: 'center' }) : false;
+if (scrolled) Object.assign(frame, resolveFrame(options.frame_selector));
+const rect = element.getBoundingClientRect();
+const frameX = rect.left + rect.width / 2;
+const frameY = rect.top + rect.height / 2;
+const viewportX = frame.offsetX + frameX;
+const viewportY = frame.offsetY + frameY;
+let clickResult = null;
+if (options.should_click) {
+    const init = { bubbles: true, cancelable: true, view: frame.window, clientX: frameX, clientY: frameY };
+    try {
+        for (const type of ['mousedown', 'mouseup', 'click']) element.dispatchEvent(new frame.window.MouseEvent(type, init));
+        clickResult = { success: true, elementTag: element.tagName, position: { x: viewportX, y: viewportY } };
+    } catch (e) {
+        clickResult = { success: false, error: e instanceof Error ? e.toString() : String(e) };
+    }
+}
+return {
+    x: viewportX + window.scrollX,
+    y: viewportY + window.scrollY,
+    element: {
+        tag: element.tagName,
+        classes: typeof element.className === 'string' ? element.className : element.getAttribute('class') || '',
+        id: element.id,
+        text: (element.textContent || '').trim(),
+        placeholder: element instanceof frame.window.HTMLInputElement ? element.placeholder : undefined,
+    },
+    clicked: options.should_click,
+    scrolled,
+    clickResult,
+    debug: {
+        elementRect: rect.toJSON(),
+        viewportCenter: { x: viewportX, y: viewportY },
+        frameOffset: { x: frame.offsetX, y: frame.offsetY },
+        documentCenter: { x: viewportX + window.scrollX, y: viewportY + window.scrollY },
+        window: {
+            innerSize: { width: window.innerWidth, height: window.innerHeight },
+            scrollPosition: { x: window.scrollX, y: window.scrollY },
+        },
+    },
+};
+"#;
 
 // Custom error enum for the get_dom_text command
 #[derive(Debug)] // Add Serialize for the enum itself if it needs to be directly serialized
@@ -48,6 +101,7 @@ impl From<TauriError> for GetDomError {
 pub async fn handle_get_dom<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    timeout: Duration,
 ) -> Result<crate::socket_server::SocketResponse, crate::error::Error> {
     // Parse the window label from the payload - handle both string and object formats
     let window_label = if payload.is_string() {
@@ -59,16 +113,13 @@ pub async fn handle_get_dom<R: Runtime>(
             })?
             .to_string()
     } else if payload.is_object() {
-        // Object with window_label property
-        payload
-            .get("window_label")
-            .and_then(|v| v.as_str())
-            .map(|s| s.to_string())
-            .ok_or_else(|| {
-                crate::error::Error::Anyhow(
-                    "Missing or invalid window_label in payload object".to_string(),
-                )
-            })?
+        // Object with an optional window_label property, the focused window without it
+        match payload.get("window_label") {
+            None | Some(Value::Null) => String::new(),
+            Some(label) => label.as_str().map(|s| s.to_string()).ok_or_else(|| {
+                crate::error::Error::Anyhow("Invalid window_label in payload object".to_string())
+            })?,
+        }
     } else {
         return Err(crate::error::Error::Anyhow(format!(
             "Invalid payload format for getDom: expected string or object with window_label, got {}",
@@ -76,11 +127,17 @@ pub async fn handle_get_dom<R: Runtime>(
         )));
     };
 
+    // Snapshot options sit next to window_label in the object format
+    let options: DomSnapshotOptions = if payload.is_object() {
+        serde_json::from_value(payload.clone())
+            .map_err(|e| crate::error::Error::Anyhow(format!("Invalid get_dom options: {}", e)))?
+    } else {
+        DomSnapshotOptions::default()
+    };
+
     // Get the window by label using the Manager trait
-    let window = app.get_webview_window(&window_label).ok_or_else(|| {
-        crate::error::Error::Anyhow(format!("Window not found: {}", window_label))
-    })?;
-    let result = get_dom_text(app.clone(), window).await;
+    let window = crate::windows::resolve_window(app, &window_label)?;
+    let result = get_dom_snapshot(window, &options, timeout).await;
     match result {
         Ok(dom_text) => {
             let data = serde_json::to_value(dom_text).map_err(|e| {
@@ -99,192 +156,157 @@ pub async fn handle_get_dom<R: Runtime>(
         }),
     }
 }
-use tauri::Emitter;
-#[tauri::command]
-pub async fn get_dom_text<R: Runtime>(
-    app: AppHandle<R>,
-    _window: WebviewWindow<R>,
-) -> Result<String, GetDomError> {
-    app.emit_to("main", "got-dom-content", "test").unwrap();
-
-    let (tx, rx) = mpsc::channel();
-
-    app.once("got-dom-content-response", move |event| {
-        let payload = event.payload().to_string();
-        let _ = tx.send(payload);
-    });
-
-    // Wait for the content
-    match rx.recv_timeout(std::time::Duration::from_secs(5)) {
-        Ok(dom_string) => {
-            if dom_string.is_empty() {
-                Err(GetDomError::DomIsEmpty)
-            } else {
-                Ok(dom_string)
-            }
-        }
-        Err(e) => {
-            // This error (e: tauri::Error) could be from the eval call itself
-            // or an error from the JavaScript execution (Promise rejection).
-            Err(GetDomError::from(e))
-        }
+/// Walks the live document, so hidden elements can be detected, and serializes a filtered copy.
+/// Expects an `options` constant holding [`DomSnapshotOptions`], and [`FRAME_JS`] before it.
+const DOM_SNAPSHOT_SCRIPT: &str = r#"
+const isHidden = (el) => {
+    if (el.hidden) return true;
+    if (typeof el.checkVisibility === 'function') {
+        return !el.checkVisibility({ checkVisibilityCSS: true });
+    }
+    const style = frame.window.getComputedStyle(el);
+    return style.display === 'none' || style.visibility === 'hidden';
+};
+const skip = (el) => {
+    const tag = el.tagName.toLowerCase();
+    if (options.strip_scripts && (tag === 'script' || tag === 'noscript')) return true;
+    if (options.strip_styles && (tag === 'style' || (tag === 'link' && /stylesheet/i.test(el.rel)))) return true;
+    return options.exclude_hidden && document.body !== null && el !== document.body
+        && document.body.contains(el) && isHidden(el);
+};
+const copy = (node, depth) => {
+    if (node.nodeType === Node.TEXT_NODE && options.collapse_whitespace) {
+        const text = node.textContent.replace(/\s+/g, ' ');
+        return text.trim() === '' ? null : document.createTextNode(text);
     }
+    if (node.nodeType !== Node.ELEMENT_NODE) return node.cloneNode(false);
+    if (skip(node)) return null;
+    const clone = node.cloneNode(false);
+    if (options.max_depth != null && depth >= options.max_depth) {
+        const omitted = node.children.length;
+        if (omitted > 0) clone.appendChild(document.createComment(` ${omitted} child elements omitted `));
+        return clone;
+    }
+    for (const child of node.childNodes) {
+        const childCopy = copy(child, depth + 1);
+        if (childCopy !== null) clone.appendChild(childCopy);
+    }
+    return clone;
+};
+const filtered = options.strip_scripts || options.strip_styles || options.collapse_whitespace
+    || options.exclude_hidden || options.max_depth != null;
+return filtered ? copy(document.documentElement, 0).outerHTML : document.documentElement.outerHTML;
+"#;
+
+/// Take a possibly filtered snapshot of the HTML of a window.
+pub async fn get_dom_snapshot<R: Runtime>(
+    window: WebviewWindow<R>,
+    options: &DomSnapshotOptions,
+    timeout: Duration,
+) -> Result<String, GetDomError> {
+    let options_json = serde_json::to_string(options)
+        .map_err(|e| GetDomError::WebviewOperation(format!("Invalid options: {}", e)))?;
+    let script = format!(
+        "const options = {};\n{}\n{}",
+        options_json, FRAME_JS, DOM_SNAPSHOT_SCRIPT
+    );
+    let dom = bridge::eval(&window, &script, timeout).await?;
+    let dom_string = match dom.as_str() {
+        Some(dom_string) if !dom_string.is_empty() => dom_string,
+        _ => return Err(GetDomError::DomIsEmpty),
+    };
+    Ok(match options.max_length {
+        Some(max_length) => truncate(dom_string, max_length),
+        None => dom_string.to_string(),
+    })
 }
 
-// Second fix: add From implementation for RecvTimeoutError
-impl From<mpsc::RecvTimeoutError> for GetDomError {
-    fn from(err: mpsc::RecvTimeoutError) -> Self {
-        GetDomError::WebviewOperation(format!("Timeout waiting for DOM: {}", err))
+/// Cut `text` to `max_length` characters, marking how much was left out.
+fn truncate(text: &str, max_length: usize) -> String {
+    let total = text.chars().count();
+    if total <= max_length {
+        return text.to_string();
     }
+    let kept: String = text.chars().take(max_length).collect();
+    format!(
+        "{}\n<!-- truncated: showing {} of {} characters -->",
+        kept, max_length, total
+    )
 }
 
-// Define the structure for get_element_position payload
-#[derive(Debug, Deserialize)]
-struct GetElementPositionPayload {
-    window_label: String,
-    selector_type: String,
-    selector_value: String,
-    #[serde(default)]
-    should_click: bool,
-    #[serde(default)]
-    raw_coordinates: bool,
+impl From<BridgeError> for GetDomError {
+    fn from(err: BridgeError) -> Self {
+        match err {
+            BridgeError::Script(e) => GetDomError::JavaScriptError(e),
+            err => GetDomError::WebviewOperation(format!("Timeout waiting for DOM: {}", err)),
+        }
+    }
 }
 
 // Handle getting element position
 pub async fn handle_get_element_position<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    timeout: Duration,
 ) -> Result<crate::socket_server::SocketResponse, crate::error::Error> {
     // Parse the payload
-    let payload = serde_json::from_value::<GetElementPositionPayload>(payload).map_err(|e| {
+    let payload = serde_json::from_value::<ElementPositionRequest>(payload).map_err(|e| {
         crate::error::Error::Anyhow(format!("Invalid payload for get_element_position: {}", e))
     })?;
 
-    // Create a channel to receive the result
-    let (tx, rx) = mpsc::channel();
-
-    // Event name for the response
-    let event_name = "get-element-position-response";
-
-    // Set up the listener for the response
-    app.once(event_name, move |event| {
-        let payload = event.payload().to_string();
-        let _ = tx.send(payload);
-    });
+    let window = crate::windows::resolve_window(app, &payload.window_label)?;
 
-    // Prepare the request payload with selector information
-    let js_payload = serde_json::json!({
-        "windowLabel": payload.window_label,
-        "selectorType": payload.selector_type,
-        "selectorValue": payload.selector_value,
-        "shouldClick": payload.should_click,
-        "rawCoordinates": payload.raw_coordinates
-    });
-
-    // Emit the event to the webview
-    app.emit_to(&payload.window_label, "get-element-position", js_payload)
-        .map_err(|e| {
-            crate::error::Error::Anyhow(format!("Failed to emit get-element-position event: {}", e))
-        })?;
-
-    // Wait for the response with a timeout
-    match rx.recv_timeout(std::time::Duration::from_secs(5)) {
-        Ok(result) => {
-            // Parse the result
-            let result_value: Value = serde_json::from_str(&result).map_err(|e| {
-                crate::error::Error::Anyhow(format!("Failed to parse result: {}", e))
-            })?;
-
-            let success = result_value
-                .get("success")
-                .and_then(|v| v.as_bool())
-                .unwrap_or(false);
-
-            if success {
-                Ok(crate::socket_server::SocketResponse {
-                    success: true,
-                    data: Some(result_value.get("data").cloned().unwrap_or(Value::Null)),
-                    error: None,
-                })
-            } else {
-                let error = result_value
-                    .get("error")
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("Unknown error occurred");
-
-                Ok(crate::socket_server::SocketResponse {
-                    success: false,
-                    data: None,
-                    error: Some(error.to_string()),
-                })
-            }
-        }
+    // Resolve the element with the plugin's own script, so the frontend needs no listener
+    let script = element_script(&payload, ELEMENT_POSITION_SCRIPT).map_err(|e| {
+        crate::error::Error::Anyhow(format!("Invalid get_element_position options: {}", e))
+    })?;
+    match bridge::eval(&window, &script, timeout).await {
+        Ok(position) => Ok(crate::socket_server::SocketResponse {
+            success: true,
+            data: Some(position),
+            error: None,
+        }),
         Err(e) => Ok(crate::socket_server::SocketResponse {
             success: false,
             data: None,
-            error: Some(format!(
-                "Timeout waiting for element position result: {}",
-                e
-            )),
+            error: Some(format!("Failed to get element position: {}", e)),
         }),
     }
 }
 
-// Define the structure for send_text_to_element payload
-#[derive(Debug, Deserialize)]
-struct SendTextToElementPayload {
-    window_label: String,
-    selector_type: String,
-    selector_value: String,
-    text: String,
-    #[serde(default = "default_delay_ms")]
-    delay_ms: u32,
-}
-
-// Default delay_ms value
-fn default_delay_ms() -> u32 {
-    20
-}
-
 // Handle sending text to an element
 pub async fn handle_send_text_to_element<R: Runtime>(
     app: &AppHandle<R>,
     payload: Value,
+    timeout: Duration,
 ) -> Result<crate::socket_server::SocketResponse, crate::error::Error> {
     // Parse the payload
-    let payload = serde_json::from_value::<SendTextToElementPayload>(payload).map_err(|e| {
+    let payload = serde_json::from_value::<SendTextToElementRequest>(payload).map_err(|e| {
         crate::error::Error::Anyhow(format!("Invalid payload for send_text_to_element: {}", e))
     })?;
-
-    // Create a channel to receive the result
-    let (tx, rx) = mpsc::channel();
-
-    // Event name for the response
-    let event_name = "send-text-to-element-response";
-
-    // Set up the listener for the response
-    app.once(event_name, move |event| {
-        let payload = event.payload().to_string();
-        let _ = tx.send(payload);
-    });
+    let window = crate::windows::resolve_window(app, &payload.window_label)?;
 
     // Prepare the request payload
     let js_payload = serde_json::json!({
+        "frameSelector": payload.frame_selector,
         "selectorType": payload.selector_type,
         "selectorValue": payload.selector_value,
         "text": payload.text,
         "delayMs": payload.delay_ms
     });
 
-    // Emit the event to the webview
-    app.emit_to(&payload.window_label, "send-text-to-element", js_payload)
-        .map_err(|e| {
-            crate::error::Error::Anyhow(format!("Failed to emit send-text-to-element event: {}", e))
-        })?;
-
-    // Wait for the response with a timeout
-    match rx.recv_timeout(std::time::Duration::from_secs(30)) {
-        // Longer timeout for typing text
+    // Emit the event to the webview and wait for the typing to complete; a cancelled
+    // request drops this future, which removes the listener
+    let response = bridge::request(
+        app,
+        window.label(),
+        "send-text-to-element",
+        js_payload,
+        "send-text-to-element-response",
+        timeout,
+    )
+    .await;
+    match response {
         Ok(result) => {
             // Parse the result
             let result_value: Value = serde_json::from_str(&result).map_err(|e| {
@@ -315,6 +337,10 @@ pub async fn handle_send_text_to_element<R: Runtime>(
                 })
             }
         }
+        Err(BridgeError::Emit(e)) => Err(crate::error::Error::Anyhow(format!(
+            "Failed to emit send-text-to-element event: {}",
+            e
+        ))),
         Err(e) => Ok(crate::socket_server::SocketResponse {
             success: false,
             data: None,
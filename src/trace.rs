@@ -0,0 +1,622 @@
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::io::{Cursor, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Runtime};
+use zip::ZipWriter;
+use zip::write::SimpleFileOptions;
+
+use crate::TauriMcpExt;
+use crate::app_logs::{AppLogRecord, AppLogs};
+use crate::artifacts::ArtifactInfo;
+use crate::audit::now_ms;
+use crate::js_errors::JsError;
+use crate::platform::snapshot::{self, CaptureOptions, ImageFormat};
+use crate::shared::commands;
+use crate::tools::bridge;
+
+/// Time given to a webview to report its DOM before and after a step.
+const DOM_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Longest payload or response kept in a step, in characters of JSON.
+const MAX_VALUE_CHARS: usize = 64 * 1024;
+
+/// Log target of the plugin's own records, left out of the steps.
+const PLUGIN_LOG_TARGET: &str = "tauri_plugin_mcp";
+
+/// Widest screenshot stored in a step, in pixels.
+const SCREENSHOT_MAX_WIDTH: u32 = 800;
+
+const SCREENSHOT_QUALITY: u8 = 60;
+
+/// Reads the page, installing a recorder of console messages and `fetch` and
+/// `XMLHttpRequest` requests on its first run in a page, and takes what it recorded since
+/// the previous read. Requests to the app's own protocols are left out.
+const DOM_STATE_SCRIPT: &str = r#"
+const LIMIT = 500;
+const recorder = window.__TAURI_MCP_TRACE__ || (() => {
+    const state = { console: [], network: [] };
+    const push = (list, entry) => {
+        list.push(entry);
+        if (list.length > LIMIT) list.shift();
+    };
+    const describe = (value) => {
+        if (typeof value === 'string') return value;
+        if (value instanceof Error) return value.stack || `${value.name}: ${value.message}`;
+        try {
+            return JSON.stringify(value) ?? String(value);
+        } catch (_) {
+            return String(value);
+        }
+    };
+    const isInternal = (url) => {
+        try {
+            const parsed = new URL(url, location.href);
+            return ['ipc:', 'asset:', 'tauri:', 'data:', 'blob:'].includes(parsed.protocol)
+                || /^(ipc|asset|tauri)\.localhost$/.test(parsed.hostname);
+        } catch (_) {
+            return true;
+        }
+    };
+    for (const level of ['log', 'info', 'warn', 'error', 'debug']) {
+        const original = console[level];
+        console[level] = function (...args) {
+            push(state.console, { level, message: args.map(describe).join(' ').slice(0, 2000), timestampMs: Date.now() });
+            return original.apply(this, args);
+        };
+    }
+    const realFetch = window.fetch;
+    if (realFetch) {
+        window.fetch = function (input, init) {
+            const url = String(typeof input === 'string' ? input : (input && (input.href || input.url)));
+            if (isInternal(url)) return realFetch.apply(this, arguments);
+            const entry = {
+                kind: 'fetch',
+                method: String((init && init.method) || (input && input.method) || 'GET').toUpperCase(),
+                url,
+                startedMs: Date.now(),
+            };
+            push(state.network, entry);
+            return realFetch.apply(this, arguments).then((response) => {
+                entry.status = response.status;
+                entry.durationMs = Date.now() - entry.startedMs;
+                return response;
+            }, (error) => {
+                entry.error = String((error && error.message) || error);
+                entry.durationMs = Date.now() - entry.startedMs;
+                throw error;
+            });
+        };
+    }
+    const xhr = window.XMLHttpRequest && XMLHttpRequest.prototype;
+    if (xhr) {
+        const entries = new WeakMap();
+        const realOpen = xhr.open;
+        const realSend = xhr.send;
+        xhr.open = function (method, url) {
+            entries.set(this, { kind: 'xhr', method: String(method).toUpperCase(), url: String(url) });
+            return realOpen.apply(this, arguments);
+        };
+        xhr.send = function () {
+            const entry = entries.get(this);
+            if (entry && !isInternal(entry.url)) {
+                entry.startedMs = Date.now();
+                push(state.network, entry);
+                this.addEventListener('loadend', () => {
+                    entry.durationMs = Date.now() - entry.startedMs;
+                    if (this.status) entry.status = this.status;
+                    else entry.error = 'The request failed';
+                });
+            }
+            return realSend.apply(this, arguments);
+        };
+    }
+    Object.defineProperty(window, '__TAURI_MCP_TRACE__', { value: state });
+    return state;
+})();
+return {
+    url: location.href,
+    title: document.title,
+    html: document.documentElement ? document.documentElement.outerHTML : '',
+    console: recorder.console.splice(0),
+    network: recorder.network.splice(0),
+};
+"#;
+
+/// State of the traced window when a step started or ended.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TracePage {
+    pub url: String,
+    pub title: String,
+    /// Hash of the DOM, the HTML is stored as `dom/<hash>.html` in the bundle.
+    pub dom_hash: String,
+    /// Path of the JPEG of the viewport in the bundle, None when screenshots are off or
+    /// the platform cannot take them.
+    pub screenshot: Option<String>,
+}
+
+/// A console message of the traced page.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceConsoleMessage {
+    /// "log", "info", "warn", "error" or "debug".
+    pub level: String,
+    pub message: String,
+    pub timestamp_ms: u64,
+}
+
+/// A `fetch` or `XMLHttpRequest` request of the traced page.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceRequest {
+    /// "fetch" or "xhr".
+    pub kind: String,
+    pub method: String,
+    pub url: String,
+    pub started_ms: u64,
+    /// None while the request is pending or when it failed.
+    pub status: Option<u16>,
+    pub duration_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// What the page recorded between two reads.
+#[derive(Debug, Default, Deserialize)]
+struct PageActivity {
+    #[serde(default)]
+    console: Vec<TraceConsoleMessage>,
+    #[serde(default)]
+    network: Vec<TraceRequest>,
+}
+
+/// A tool call, as stored in `steps/` in the bundle.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceStep {
+    /// Position of the call in the trace, from 1.
+    pub index: u64,
+    pub command: String,
+    pub payload: Value,
+    pub started_ms: u64,
+    pub duration_ms: u64,
+    pub success: bool,
+    pub error: Option<String>,
+    pub response: Option<Value>,
+    /// None when the window did not report its DOM.
+    pub before: Option<TracePage>,
+    pub after: Option<TracePage>,
+    /// Uncaught errors of the webviews reported while the step ran.
+    pub js_errors: Vec<JsError>,
+    /// Log records of the app written while the step ran, when a `LogBridge` is installed.
+    pub app_logs: Vec<AppLogRecord>,
+    /// Console messages of the page while the step ran.
+    pub console: Vec<TraceConsoleMessage>,
+    /// Requests the page started while the step ran.
+    pub network: Vec<TraceRequest>,
+}
+
+/// Line of a step in `trace.json`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceStepSummary {
+    pub index: u64,
+    pub command: String,
+    pub success: bool,
+    pub duration_ms: u64,
+    /// Whether the DOM differs after the step, None when either state is missing.
+    pub dom_changed: Option<bool>,
+    /// Path of the step in the bundle.
+    pub file: String,
+}
+
+/// Content of `trace.json` in the bundle.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceManifest {
+    pub name: String,
+    pub window_label: String,
+    pub started_ms: u64,
+    pub duration_ms: u64,
+    /// Steps in the order they finished.
+    pub steps: Vec<TraceStepSummary>,
+}
+
+/// What a stopped trace produced.
+pub struct StoppedTrace {
+    pub manifest: TraceManifest,
+    /// The zip bundle, None when the trace was discarded.
+    pub artifact: Option<ArtifactInfo>,
+}
+
+/// A step waiting for its command to finish.
+pub(crate) struct PendingStep {
+    trace: u64,
+    index: u64,
+    command: String,
+    payload: Value,
+    window_label: String,
+    started: Instant,
+    started_ms: u64,
+    before: Option<TracePage>,
+    js_errors_since: u64,
+    app_logs_since: u64,
+}
+
+struct Trace {
+    id: u64,
+    name: String,
+    window_label: String,
+    include_dom: bool,
+    include_screenshots: bool,
+    started: Instant,
+    started_ms: u64,
+    next_index: u64,
+    bundle: ZipWriter<Cursor<Vec<u8>>>,
+    /// Hashes of the DOM snapshots already in the bundle.
+    snapshots: HashSet<String>,
+    steps: Vec<TraceStepSummary>,
+}
+
+impl Trace {
+    fn add_file(&mut self, path: &str, content: &[u8]) -> crate::Result<()> {
+        self.bundle
+            .start_file(path, SimpleFileOptions::default())
+            .map_err(|e| crate::Error::Anyhow(format!("Failed to write {}: {}", path, e)))?;
+        self.bundle.write_all(content)?;
+        Ok(())
+    }
+
+    fn add_snapshot(&mut self, hash: &str, html: &str) -> crate::Result<()> {
+        if !self.include_dom || !self.snapshots.insert(hash.to_string()) {
+            return Ok(());
+        }
+        self.add_file(&format!("dom/{}.html", hash), html.as_bytes())
+    }
+}
+
+/// Records each tool call with the state of the page before and after it, and what the
+/// app logged meanwhile, into a zip bundle to review failed runs offline.
+pub struct Tracer {
+    trace: Mutex<Option<Trace>>,
+    next_id: AtomicU64,
+}
+
+impl Tracer {
+    pub(crate) fn new() -> Self {
+        Self {
+            trace: Mutex::new(None),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    pub fn is_tracing(&self) -> bool {
+        self.trace.lock().unwrap().is_some()
+    }
+
+    /// Start tracing the following tool calls. Pages are read from `window_label` unless a
+    /// call names its own window; `include_dom` stores the HTML of each distinct DOM next
+    /// to its hash, and `include_screenshots` a JPEG of the viewport before and after each
+    /// call. Fails if a trace is running.
+    pub fn start(
+        &self,
+        name: Option<String>,
+        window_label: String,
+        include_dom: bool,
+        include_screenshots: bool,
+    ) -> crate::Result<()> {
+        let mut trace = self.trace.lock().unwrap();
+        if trace.is_some() {
+            return Err(crate::Error::Anyhow(
+                "A trace is already running".to_string(),
+            ));
+        }
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let name = name.unwrap_or_else(|| "trace".to_string());
+        info!("[TAURI_MCP] Trace {} started", name);
+        *trace = Some(Trace {
+            id,
+            name,
+            window_label,
+            include_dom,
+            include_screenshots,
+            started: Instant::now(),
+            started_ms: now_ms(),
+            next_index: 1,
+            bundle: ZipWriter::new(Cursor::new(Vec::new())),
+            snapshots: HashSet::new(),
+            steps: Vec::new(),
+        });
+        Ok(())
+    }
+
+    /// Stop tracing and store the bundle as a "trace" artifact, unless `discard` is set.
+    /// Calls still running are left out.
+    pub fn stop<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        discard: bool,
+    ) -> crate::Result<StoppedTrace> {
+        let mut trace = self
+            .trace
+            .lock()
+            .unwrap()
+            .take()
+            .ok_or_else(|| crate::Error::Anyhow("No trace is running".to_string()))?;
+        let manifest = TraceManifest {
+            name: trace.name.clone(),
+            window_label: trace.window_label.clone(),
+            started_ms: trace.started_ms,
+            duration_ms: trace.started.elapsed().as_millis() as u64,
+            steps: std::mem::take(&mut trace.steps),
+        };
+        info!(
+            "[TAURI_MCP] Trace {} stopped with {} steps",
+            manifest.name,
+            manifest.steps.len()
+        );
+        if discard {
+            return Ok(StoppedTrace {
+                manifest,
+                artifact: None,
+            });
+        }
+
+        let content = serde_json::to_vec_pretty(&manifest)
+            .map_err(|e| crate::Error::Anyhow(format!("Failed to serialize the trace: {}", e)))?;
+        trace.add_file("trace.json", &content)?;
+        let bundle = trace
+            .bundle
+            .finish()
+            .map_err(|e| crate::Error::Anyhow(format!("Failed to finish the bundle: {}", e)))?
+            .into_inner();
+        let artifact = app.tauri_mcp().artifact_store().put(
+            "trace",
+            &format!("{}.zip", manifest.name),
+            &bundle,
+        )?;
+        Ok(StoppedTrace {
+            manifest,
+            artifact: Some(artifact),
+        })
+    }
+
+    /// Note the state of the page before `command` runs, when a trace is running.
+    pub(crate) async fn begin<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        command: &str,
+        payload: &Value,
+    ) -> Option<PendingStep> {
+        if matches!(command, commands::START_TRACE | commands::STOP_TRACE) {
+            return None;
+        }
+        let (trace, index, window_label) = {
+            let mut guard = self.trace.lock().unwrap();
+            let trace = guard.as_mut()?;
+            let index = trace.next_index;
+            trace.next_index += 1;
+            let window_label = payload
+                .get("window_label")
+                .and_then(Value::as_str)
+                .unwrap_or(&trace.window_label)
+                .to_string();
+            (trace.id, index, window_label)
+        };
+        // Record the window an omitted label stands for, so both captures read the same one
+        let window_label = crate::windows::resolve_window(app, &window_label)
+            .map(|window| window.label().to_string())
+            .unwrap_or(window_label);
+
+        // What the page recorded before the step is not part of it
+        let before = self
+            .capture(app, trace, &window_label, &format!("{:04}-before", index))
+            .await
+            .map(|(page, _)| page);
+        Some(PendingStep {
+            trace,
+            index,
+            command: command.to_string(),
+            payload: capped(payload),
+            window_label,
+            started: Instant::now(),
+            started_ms: now_ms(),
+            before,
+            js_errors_since: app.tauri_mcp().js_errors().latest_id(),
+            app_logs_since: AppLogs::get().map_or(0, AppLogs::latest_id),
+        })
+    }
+
+    /// Note the outcome of a step and the state of the page after it, and add the step to
+    /// the bundle.
+    pub(crate) async fn finish<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        step: PendingStep,
+        success: bool,
+        error: Option<String>,
+        response: Option<&Value>,
+    ) {
+        let duration_ms = step.started.elapsed().as_millis() as u64;
+        let (after, activity) = match self
+            .capture(
+                app,
+                step.trace,
+                &step.window_label,
+                &format!("{:04}-after", step.index),
+            )
+            .await
+        {
+            Some((page, activity)) => (Some(page), activity),
+            None => (None, PageActivity::default()),
+        };
+        let app_logs = AppLogs::get()
+            .map(|logs| {
+                logs.query(step.app_logs_since, None, None, usize::MAX)
+                    .records
+                    .into_iter()
+                    .filter(|record| !record.target.starts_with(PLUGIN_LOG_TARGET))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let dom_changed = match (&step.before, &after) {
+            (Some(before), Some(after)) => Some(before.dom_hash != after.dom_hash),
+            _ => None,
+        };
+        // The command is the client's, keep it from naming paths outside `steps/`
+        let file = format!(
+            "steps/{:04}-{}.json",
+            step.index,
+            crate::artifacts::sanitize(&step.command)
+        );
+        let trace_step = TraceStep {
+            index: step.index,
+            command: step.command,
+            payload: step.payload,
+            started_ms: step.started_ms,
+            duration_ms,
+            success,
+            error,
+            response: response.map(capped),
+            before: step.before,
+            after,
+            js_errors: app
+                .tauri_mcp()
+                .js_errors()
+                .errors(step.js_errors_since, None),
+            app_logs,
+            console: activity.console,
+            network: activity.network,
+        };
+        let Ok(content) = serde_json::to_vec_pretty(&trace_step) else {
+            return;
+        };
+
+        let mut guard = self.trace.lock().unwrap();
+        // The trace this step belongs to may have stopped meanwhile
+        let Some(trace) = guard.as_mut().filter(|trace| trace.id == step.trace) else {
+            return;
+        };
+        if let Err(e) = trace.add_file(&file, &content) {
+            debug!(
+                "[TAURI_MCP] Failed to add step {} to the trace: {}",
+                step.index, e
+            );
+            return;
+        }
+        trace.steps.push(TraceStepSummary {
+            index: trace_step.index,
+            command: trace_step.command,
+            success,
+            duration_ms,
+            dom_changed,
+            file,
+        });
+    }
+
+    /// Read the page of a window and what it recorded since the last read, and store its
+    /// DOM and a screenshot named `name` in the bundle.
+    async fn capture<R: Runtime>(
+        &self,
+        app: &AppHandle<R>,
+        trace: u64,
+        window_label: &str,
+        name: &str,
+    ) -> Option<(TracePage, PageActivity)> {
+        let window = crate::windows::resolve_window(app, window_label).ok()?;
+        let state = match bridge::eval(&window, DOM_STATE_SCRIPT, DOM_TIMEOUT).await {
+            Ok(state) => state,
+            Err(e) => {
+                debug!("[TAURI_MCP] Trace could not read {}: {}", window_label, e);
+                return None;
+            }
+        };
+        let text = |key: &str| {
+            state
+                .get(key)
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        };
+        let html = text("html");
+        let activity = serde_json::from_value::<PageActivity>(state.clone()).unwrap_or_default();
+        let include_screenshots = self
+            .trace
+            .lock()
+            .unwrap()
+            .as_ref()
+            .is_some_and(|current| current.id == trace && current.include_screenshots);
+        let screenshot = if include_screenshots {
+            let options = CaptureOptions {
+                clip: None,
+                max_width: Some(SCREENSHOT_MAX_WIDTH),
+                format: ImageFormat::Jpeg(SCREENSHOT_QUALITY),
+            };
+            match snapshot::capture(&window, options, DOM_TIMEOUT).await {
+                Ok(image) => Some(image),
+                Err(e) => {
+                    debug!(
+                        "[TAURI_MCP] Trace could not screenshot {}: {}",
+                        window_label, e
+                    );
+                    None
+                }
+            }
+        } else {
+            None
+        };
+        let mut page = TracePage {
+            url: text("url"),
+            title: text("title"),
+            dom_hash: dom_hash(&html),
+            screenshot: None,
+        };
+
+        let mut guard = self.trace.lock().unwrap();
+        if let Some(trace) = guard.as_mut().filter(|current| current.id == trace) {
+            if let Err(e) = trace.add_snapshot(&page.dom_hash, &html) {
+                debug!(
+                    "[TAURI_MCP] Failed to add a DOM snapshot to the trace: {}",
+                    e
+                );
+            }
+            if let Some(image) = screenshot {
+                let path = format!("screenshots/{}.jpg", name);
+                match trace.add_file(&path, &image.data) {
+                    Ok(()) => page.screenshot = Some(path),
+                    Err(e) => debug!("[TAURI_MCP] Failed to add a screenshot to the trace: {}", e),
+                }
+            }
+        }
+        Some((page, activity))
+    }
+}
+
+/// FNV-1a hash of the HTML, stable across runs so traces can be compared.
+fn dom_hash(html: &str) -> String {
+    let hash = html.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    });
+    format!("{:016x}", hash)
+}
+
+/// The value, or a preview of it when its JSON is too long.
+fn capped(value: &Value) -> Value {
+    let text = value.to_string();
+    if text.len() <= MAX_VALUE_CHARS {
+        return value.clone();
+    }
+    let mut end = MAX_VALUE_CHARS;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    serde_json::json!({
+        "truncated": true,
+        "length": text.len(),
+        "preview": &text[..end],
+    })
+}
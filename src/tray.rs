@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+
+use crate::menu::MenuEntry;
+
+/// A tray icon registered with the [`TrayRegistry`].
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrayInfo {
+    pub id: String,
+    /// Items of the menu registered with the tray, empty without menu.
+    pub menu: Vec<MenuEntry>,
+    /// Whether `trigger_tray_menu_item` has a handler to call.
+    pub handles_menu_events: bool,
+    /// Whether `click_tray` has a handler to call.
+    pub handles_clicks: bool,
+}
+
+#[cfg(all(desktop, feature = "tray"))]
+pub use registry::TrayRegistry;
+
+#[cfg(all(desktop, feature = "tray"))]
+mod registry {
+    use std::sync::{Arc, Mutex};
+    use tauri::menu::{Menu, MenuEvent};
+    use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconEvent};
+    use tauri::{AppHandle, PhysicalPosition, PhysicalSize, Rect, Runtime};
+
+    use super::TrayInfo;
+    use crate::TauriMcpExt;
+    use crate::menu::{self, MenuEntry};
+
+    type TrayHandler<R> = Arc<dyn Fn(&TrayIcon<R>, TrayIconEvent) + Send + Sync>;
+
+    struct RegisteredTray<R: Runtime> {
+        tray: TrayIcon<R>,
+        menu: Option<Menu<R>>,
+        handles_menu_events: bool,
+        on_tray_icon_event: Option<TrayHandler<R>>,
+    }
+
+    impl<R: Runtime> Clone for RegisteredTray<R> {
+        fn clone(&self) -> Self {
+            Self {
+                tray: self.tray.clone(),
+                menu: self.menu.clone(),
+                handles_menu_events: self.handles_menu_events,
+                on_tray_icon_event: self.on_tray_icon_event.clone(),
+            }
+        }
+    }
+
+    /// Tray icons clients may inspect and drive with `list_trays`, `trigger_tray_menu_item`
+    /// and `click_tray`.
+    ///
+    /// Tauri neither lists tray icons and their menus nor lets events be injected, so the
+    /// app registers each tray with its menu, and sets the handlers through the registry,
+    /// which installs them on the tray and keeps them to call them on request:
+    ///
+    /// ```rust,ignore
+    /// let tray = TrayIconBuilder::with_id("main").menu(&menu).build(app)?;
+    /// let trays = app.tauri_mcp().trays();
+    /// trays.register(&tray, Some(menu));
+    /// trays.on_menu_event("main", handle_menu_event)?;
+    /// trays.on_tray_icon_event("main", handle_tray_event)?;
+    /// ```
+    pub struct TrayRegistry<R: Runtime> {
+        trays: Mutex<Vec<RegisteredTray<R>>>,
+    }
+
+    impl<R: Runtime> TrayRegistry<R> {
+        pub(crate) fn new() -> Self {
+            Self {
+                trays: Mutex::new(Vec::new()),
+            }
+        }
+
+        /// Register a tray icon and the menu it shows, replacing any tray registered with
+        /// the same ID.
+        pub fn register(&self, tray: &TrayIcon<R>, menu: Option<Menu<R>>) {
+            let mut trays = self.trays.lock().unwrap();
+            trays.retain(|registered| registered.tray.id() != tray.id());
+            trays.push(RegisteredTray {
+                tray: tray.clone(),
+                menu,
+                handles_menu_events: false,
+                on_tray_icon_event: None,
+            });
+        }
+
+        /// Stop tracking a tray. Returns false if no tray is registered with that ID.
+        pub fn unregister(&self, id: &str) -> bool {
+            let mut trays = self.trays.lock().unwrap();
+            let count = trays.len();
+            trays.retain(|registered| registered.tray.id().as_ref() != id);
+            trays.len() != count
+        }
+
+        /// Set the menu event handler of a registered tray, like `TrayIcon::on_menu_event`.
+        /// As there, the handler receives the events of every menu of the app, including
+        /// those of `click_menu_item`.
+        pub fn on_menu_event<F>(&self, id: &str, handler: F) -> crate::Result<()>
+        where
+            F: Fn(&AppHandle<R>, MenuEvent) + Send + Sync + 'static,
+        {
+            let mut trays = self.trays.lock().unwrap();
+            let registered = find(&mut trays, id)?;
+            let installed = registered
+                .tray
+                .app_handle()
+                .tauri_mcp()
+                .menu_handlers()
+                .add(Arc::new(handler));
+            registered.tray.on_menu_event(installed);
+            registered.handles_menu_events = true;
+            Ok(())
+        }
+
+        /// Set the click handler of a registered tray, like `TrayIcon::on_tray_icon_event`.
+        pub fn on_tray_icon_event<F>(&self, id: &str, handler: F) -> crate::Result<()>
+        where
+            F: Fn(&TrayIcon<R>, TrayIconEvent) + Send + Sync + 'static,
+        {
+            let handler: TrayHandler<R> = Arc::new(handler);
+            let mut trays = self.trays.lock().unwrap();
+            let registered = find(&mut trays, id)?;
+            let installed = handler.clone();
+            registered
+                .tray
+                .on_tray_icon_event(move |tray, event| installed(tray, event));
+            registered.on_tray_icon_event = Some(handler);
+            Ok(())
+        }
+
+        /// Menu getters wait on the main thread, so they are called on a copy of the
+        /// registrations rather than under the lock.
+        fn registered(&self) -> Vec<RegisteredTray<R>> {
+            self.trays.lock().unwrap().clone()
+        }
+
+        /// The registered trays with their menus.
+        pub fn list(&self) -> crate::Result<Vec<TrayInfo>> {
+            self.registered()
+                .iter()
+                .map(|registered| {
+                    let menu = match &registered.menu {
+                        Some(menu) => menu::describe_items(menu.items()?)?,
+                        None => Vec::new(),
+                    };
+                    Ok(TrayInfo {
+                        id: registered.tray.id().as_ref().to_string(),
+                        menu,
+                        handles_menu_events: registered.handles_menu_events,
+                        handles_clicks: registered.on_tray_icon_event.is_some(),
+                    })
+                })
+                .collect()
+        }
+
+        /// Select a menu item as if the user clicked it, calling the menu handlers of the
+        /// app and of every tray. Without `tray_id`, the item is looked up in every registered
+        /// menu. Returns the item as it is after the click.
+        pub fn trigger_menu_item(
+            &self,
+            tray_id: Option<&str>,
+            item_id: &str,
+        ) -> crate::Result<MenuEntry> {
+            for registered in self.registered() {
+                if tray_id.is_some_and(|id| registered.tray.id().as_ref() != id) {
+                    continue;
+                }
+                let Some(menu) = &registered.menu else {
+                    continue;
+                };
+                if let Some((item, _)) = menu::find_by_id(menu.items()?, item_id)? {
+                    return menu::activate(registered.tray.app_handle(), &item);
+                }
+            }
+            Err(crate::Error::Anyhow(match tray_id {
+                Some(id) => format!("No item {} in the menu of tray {}", item_id, id),
+                None => format!("No item {} in the registered tray menus", item_id),
+            }))
+        }
+
+        /// Click a tray icon: a press then a release of `button`, followed by a double
+        /// click event when `double` is set, delivered to the handler on the main thread.
+        pub fn click(&self, tray_id: &str, button: MouseButton, double: bool) -> crate::Result<()> {
+            let (tray, handler) = {
+                let mut trays = self.registered();
+                let registered = find(&mut trays, tray_id)?;
+                let handler = registered.on_tray_icon_event.clone().ok_or_else(|| {
+                    crate::Error::Anyhow(format!(
+                        "Tray {} has no click handler, set it with TrayRegistry::on_tray_icon_event",
+                        tray_id
+                    ))
+                })?;
+                (registered.tray.clone(), handler)
+            };
+
+            // Click in the middle of the icon, where the OS reports it
+            let rect = tray.rect()?.unwrap_or(Rect {
+                position: PhysicalPosition::new(0, 0).into(),
+                size: PhysicalSize::new(0, 0).into(),
+            });
+            let origin = rect.position.to_physical::<f64>(1.0);
+            let size = rect.size.to_physical::<f64>(1.0);
+            let position =
+                PhysicalPosition::new(origin.x + size.width / 2.0, origin.y + size.height / 2.0);
+            let id = tray.id().clone();
+            let mut events = vec![
+                TrayIconEvent::Click {
+                    id: id.clone(),
+                    position,
+                    rect,
+                    button,
+                    button_state: MouseButtonState::Down,
+                },
+                TrayIconEvent::Click {
+                    id: id.clone(),
+                    position,
+                    rect,
+                    button,
+                    button_state: MouseButtonState::Up,
+                },
+            ];
+            if double {
+                events.push(TrayIconEvent::DoubleClick {
+                    id,
+                    position,
+                    rect,
+                    button,
+                });
+            }
+            let app = tray.app_handle().clone();
+            app.run_on_main_thread(move || {
+                for event in events {
+                    handler(&tray, event);
+                }
+            })?;
+            Ok(())
+        }
+    }
+
+    fn find<'a, R: Runtime>(
+        trays: &'a mut [RegisteredTray<R>],
+        id: &str,
+    ) -> crate::Result<&'a mut RegisteredTray<R>> {
+        trays
+            .iter_mut()
+            .find(|registered| registered.tray.id().as_ref() == id)
+            .ok_or_else(|| {
+                crate::Error::Anyhow(format!(
+                    "Tray {} is not registered, add it with TrayRegistry::register",
+                    id
+                ))
+            })
+    }
+}
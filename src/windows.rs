@@ -0,0 +1,104 @@
+use tauri::{AppHandle, Manager, Runtime, WebviewWindow};
+
+use crate::error::Error;
+
+/// Label of the window tools fall back on when none is focused.
+pub const MAIN_WINDOW_LABEL: &str = "main";
+
+/// Resolve the window a tool acts on.
+///
+/// An empty label, as left by requests that omit `window_label`, picks the focused window,
+/// else `main`, else the first window by label. A label differing only in case from one
+/// window's label picks that window. Any other unknown label fails with
+/// [`Error::UnknownWindow`], listing the labels the client can use instead.
+pub fn resolve_window<R: Runtime>(
+    app: &AppHandle<R>,
+    label: &str,
+) -> crate::Result<WebviewWindow<R>> {
+    if label.is_empty() {
+        return default_window(app).ok_or_else(|| Error::UnknownWindow {
+            label: String::new(),
+            available: Vec::new(),
+            suggestion: None,
+        });
+    }
+    if let Some(window) = app.get_webview_window(label) {
+        return Ok(window);
+    }
+
+    let available = window_labels(app);
+    let mut same_case_insensitive = available
+        .iter()
+        .filter(|candidate| candidate.eq_ignore_ascii_case(label));
+    if let (Some(candidate), None) = (same_case_insensitive.next(), same_case_insensitive.next())
+        && let Some(window) = app.get_webview_window(candidate)
+    {
+        return Ok(window);
+    }
+
+    let suggestion = closest_label(label, &available);
+    Err(Error::UnknownWindow {
+        label: label.to_string(),
+        available,
+        suggestion,
+    })
+}
+
+/// The window tools use when the request does not name one, None without windows.
+pub fn default_window<R: Runtime>(app: &AppHandle<R>) -> Option<WebviewWindow<R>> {
+    let mut windows: Vec<(String, WebviewWindow<R>)> = app.webview_windows().into_iter().collect();
+    windows.sort_by(|(a, _), (b, _)| a.cmp(b));
+    if let Some(index) = windows
+        .iter()
+        .position(|(_, window)| window.is_focused().unwrap_or(false))
+        .or_else(|| {
+            windows
+                .iter()
+                .position(|(label, _)| label == MAIN_WINDOW_LABEL)
+        })
+    {
+        return Some(windows.swap_remove(index).1);
+    }
+    windows.into_iter().next().map(|(_, window)| window)
+}
+
+/// Labels of the open webview windows, sorted.
+pub fn window_labels<R: Runtime>(app: &AppHandle<R>) -> Vec<String> {
+    let mut labels: Vec<String> = app.webview_windows().into_keys().collect();
+    labels.sort();
+    labels
+}
+
+/// The label closest to `label`, if one is close enough to be a likely typo.
+fn closest_label(label: &str, available: &[String]) -> Option<String> {
+    let label = label.to_lowercase();
+    available
+        .iter()
+        .map(|candidate| {
+            let lowered = candidate.to_lowercase();
+            let distance = if lowered.contains(&label) || label.contains(&lowered) {
+                0
+            } else {
+                edit_distance(&label, &lowered)
+            };
+            (distance, candidate)
+        })
+        .filter(|(distance, candidate)| *distance <= (candidate.chars().count() / 3).max(1))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.clone())
+}
+
+/// Levenshtein distance between two strings, by character.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}